@@ -0,0 +1,6 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // The gRPC job service is generated from its contract; protoc must be installed
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/winscrape/v1/jobs.proto")?;
+    Ok(())
+}
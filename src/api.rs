@@ -1,11 +1,36 @@
-use actix_web::{web, HttpResponse, Result as ActixResult};
+use actix_http::ws;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header;
+use actix_web::middleware::{Condition, DefaultHeaders, Next};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Result as ActixResult};
+use anyhow::Result;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::pin::Pin;
 use std::sync::Arc;
-use tracing::{info, error};
+use std::time::Duration;
+use tracing::{info, warn, error};
 
+mod limits;
+mod openapi;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+use limits::ApiLimits;
+
+use crate::config::ApiConfig;
+use crate::core::events::{JobEvent, WorkflowEvent};
+use crate::core::health::ComponentState;
+use crate::core::progress::{JobProgress, ProgressEvent, ProgressSubscription};
 use crate::core::WinScrapeStudio;
-use crate::dsl::ScrapePlan;
-use crate::export::ExportFormat;
+use crate::dsl::{LintWarning, ScrapePlan, ValidationIssue};
+use crate::error::ErrorCode;
+use crate::export::{ExportFormat, ExportOptions, ExportProfile};
+use crate::i18n::Language;
+use crate::storage::{JobFilter, User};
+use crate::utils::time_utils;
 
 /// API request for DSL generation
 #[derive(Debug, Deserialize)]
@@ -21,10 +46,24 @@ pub struct GenerateDSLResponse {
     pub message: String,
 }
 
+/// API response for plan generation
+#[derive(Debug, Serialize)]
+pub struct GeneratePlanResponse {
+    pub plan: ScrapePlan,
+    pub warnings: Vec<LintWarning>,
+    /// Politeness limits the plan goes beyond; the UI asks for approval of these
+    pub politeness_violations: Vec<String>,
+    /// How sure the generator was of the kind of page, from 0 to 1
+    pub confidence: Option<f64>,
+}
+
 /// API request for scraping execution
 #[derive(Debug, Deserialize)]
 pub struct ExecuteScrapingRequest {
     pub dsl: ScrapePlan,
+    /// Values for `${name}` placeholders in the plan
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
 }
 
 /// API response for scraping execution
@@ -35,11 +74,449 @@ pub struct ExecuteScrapingResponse {
     pub message: String,
 }
 
+/// API response for DSL validation
+#[derive(Debug, Serialize)]
+pub struct ValidateDSLResponse {
+    pub valid: bool,
+    pub errors: Vec<ValidationIssue>,
+    pub warnings: Vec<LintWarning>,
+}
+
+/// API request for a plan explanation
+#[derive(Debug, Deserialize)]
+pub struct ExplainPlanRequest {
+    pub dsl: ScrapePlan,
+    /// Language code such as `en` or `ko`; English when omitted
+    pub language: Option<String>,
+}
+
+/// API request for refining a plan with a follow-up instruction
+#[derive(Debug, Deserialize)]
+pub struct RefineDSLRequest {
+    pub dsl: ScrapePlan,
+    pub instruction: String,
+}
+
 /// API request for job export
 #[derive(Debug, Deserialize)]
 pub struct ExportJobRequest {
     pub job_id: String,
     pub format: String,
+    /// Column selection, order and names
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+/// API request for exporting a job into the export database
+#[derive(Debug, Deserialize)]
+pub struct ExportDatabaseRequest {
+    /// Target table; the plan's domain when unset
+    #[serde(default)]
+    pub table: Option<String>,
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+/// API request naming a database backup file on the server
+#[derive(Debug, Deserialize)]
+pub struct DatabaseFileRequest {
+    pub path: String,
+}
+
+/// Query parameters for listing jobs; all are optional
+#[derive(Debug, Deserialize)]
+pub struct JobListQuery {
+    pub limit: Option<usize>,
+    pub status: Option<String>,
+    pub tag: Option<String>,
+    pub domain: Option<String>,
+    /// RFC 3339 timestamp or `YYYY-MM-DD`
+    pub since: Option<String>,
+    /// RFC 3339 timestamp or `YYYY-MM-DD`, exclusive
+    pub before: Option<String>,
+    /// Text in the title or prompt
+    pub search: Option<String>,
+}
+
+impl JobListQuery {
+    fn to_filter(&self) -> Result<JobFilter, String> {
+        let date = |text: &Option<String>| match text {
+            Some(text) => time_utils::parse_local_date(text)
+                .map(Some)
+                .ok_or_else(|| format!("Invalid date: {}", text)),
+            None => Ok(None),
+        };
+        Ok(JobFilter {
+            status: self.status.as_deref().map(str::parse).transpose().map_err(|e: anyhow::Error| e.to_string())?,
+            tag: self.tag.clone(),
+            domain: self.domain.clone(),
+            created_after: date(&self.since)?,
+            created_before: date(&self.before)?,
+            text: self.search.clone(),
+            owner: None,
+        })
+    }
+}
+
+/// API request for tagging a job
+#[derive(Debug, Deserialize)]
+pub struct TagRequest {
+    pub tag: String,
+}
+
+/// API request for archiving old results
+#[derive(Debug, Deserialize)]
+pub struct ArchiveRequest {
+    /// Age in days; the configured age when omitted
+    pub older_than_days: Option<u32>,
+}
+
+/// Query parameters for paging through a job's results
+#[derive(Debug, Deserialize)]
+pub struct ResultPageQuery {
+    /// Row index to continue after; the first page when omitted
+    pub after: Option<i32>,
+    pub limit: Option<usize>,
+}
+
+/// Idle time after which event streams send a keep-alive
+const EVENT_KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Largest WebSocket frame accepted from clients
+const WS_MAX_FRAME: usize = 64 * 1024;
+
+/// Token checks in force; `None` when auth is disabled
+struct ApiToken(Option<AuthTokens>);
+
+struct AuthTokens {
+    /// The configured `auth_token`, which acts as an admin
+    admin: Option<String>,
+}
+
+/// Who made a request
+///
+/// Handlers of authenticated routes take it as an extractor.
+#[derive(Debug, Clone)]
+pub enum Caller {
+    /// The configured token, or anyone while auth is disabled
+    Admin,
+    User(User),
+}
+
+impl Caller {
+    /// Admins see every job and manage users, export profiles and the database
+    fn is_admin(&self) -> bool {
+        match self {
+            Caller::Admin => true,
+            Caller::User(user) => user.is_admin,
+        }
+    }
+    
+    /// ID recorded as the owner of jobs the caller creates
+    fn user_id(&self) -> Option<&str> {
+        match self {
+            Caller::Admin => None,
+            Caller::User(user) => Some(&user.id),
+        }
+    }
+    
+    /// Owner the caller's job listings are limited to; `None` for admins
+    fn visible_owner(&self) -> Option<String> {
+        (!self.is_admin()).then(|| self.user_id().map(str::to_string)).flatten()
+    }
+}
+
+impl actix_web::FromRequest for Caller {
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+    
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        use actix_web::HttpMessage;
+        std::future::ready(
+            req.extensions().get::<Caller>().cloned()
+                .ok_or_else(|| actix_web::error::ErrorUnauthorized("Missing or invalid bearer token"))
+        )
+    }
+}
+
+/// Key a client's rate limit and quotas are tracked under
+///
+/// Users are keyed by ID, everyone else by address.
+#[derive(Debug, Clone)]
+struct ClientKey(String);
+
+impl ClientKey {
+    fn of(caller: &Caller, peer: Option<std::net::SocketAddr>) -> Self {
+        match caller {
+            Caller::User(user) => Self(format!("user:{}", user.id)),
+            Caller::Admin => Self(format!("addr:{}", peer.map(|addr| addr.ip().to_string()).unwrap_or_default())),
+        }
+    }
+}
+
+impl actix_web::FromRequest for ClientKey {
+    type Error = actix_web::Error;
+    type Future = std::future::Ready<Result<Self, Self::Error>>;
+    
+    fn from_request(req: &HttpRequest, _: &mut actix_web::dev::Payload) -> Self::Future {
+        use actix_web::HttpMessage;
+        std::future::ready(
+            req.extensions().get::<ClientKey>().cloned()
+                .ok_or_else(|| actix_web::error::ErrorUnauthorized("Missing or invalid bearer token"))
+        )
+    }
+}
+
+/// API request for adding a user
+#[derive(Debug, Deserialize)]
+pub struct CreateUserRequest {
+    pub name: String,
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+/// Query parameters for column statistics
+#[derive(Debug, Deserialize)]
+pub struct ColumnStatsQuery {
+    /// Number of most frequent values to return per column
+    pub top: Option<usize>,
+}
+
+/// Serve the API until the server is stopped
+///
+/// When auth is enabled, requests need `Authorization: Bearer <token>` with
+/// the configured `auth_token` or a user's token, except for the health checks
+/// and the API documentation. Users only see their own jobs. With the `grpc`
+/// feature and `grpc_port` set, the job service is served there under the same rules.
+pub async fn serve(app: Arc<WinScrapeStudio>, config: &ApiConfig) -> Result<()> {
+    let token = if config.enable_auth {
+        let admin = config.auth_token.clone().filter(|token| !token.is_empty());
+        if admin.is_none() && app.list_users().await?.is_empty() {
+            return Err(anyhow::anyhow!("API auth is enabled but there is no auth_token and no users"));
+        }
+        Some(AuthTokens { admin })
+    } else {
+        None
+    };
+    let token = web::Data::new(ApiToken(token));
+    let limits = web::Data::new(ApiLimits::new(config));
+    #[cfg(feature = "grpc")]
+    if let Some(port) = config.grpc_port {
+        let addr = tokio::net::lookup_host((config.host.as_str(), port)).await?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Cannot resolve {}", config.host))?;
+        let service = grpc::JobApi::new(app.clone(), token.clone().into_inner(), limits.clone().into_inner());
+        tokio::spawn(async move {
+            if let Err(e) = grpc::serve(service, addr).await {
+                error!("gRPC server failed: {}", e);
+            }
+        });
+    }
+    let core = app.clone();
+    let app = web::Data::new(app);
+    let body_limit = config.max_request_size_mb * 1024 * 1024;
+    let enable_cors = config.enable_cors;
+    
+    info!("Starting API server on {}:{}", config.host, config.port);
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(app.clone())
+            .app_data(token.clone())
+            .app_data(limits.clone())
+            .app_data(web::JsonConfig::default().limit(body_limit))
+            .wrap(actix_web::middleware::from_fn(limit_requests))
+            .wrap(actix_web::middleware::from_fn(authenticate))
+            .wrap(Condition::new(enable_cors, DefaultHeaders::new()
+                .add(("Access-Control-Allow-Origin", "*"))
+                .add(("Access-Control-Allow-Headers", "Authorization, Content-Type"))
+                .add(("Access-Control-Allow-Methods", "GET, POST, DELETE"))))
+            .configure(configure_routes)
+    })
+    .bind((config.host.as_str(), config.port))?
+    .disable_signals()
+    .run();
+    
+    // On SIGINT/SIGTERM, pause running jobs before the server stops answering
+    let handle = server.handle();
+    let stopping = async {
+        crate::core::shutdown::termination_signal().await;
+        core.shutdown().await;
+        handle.stop(true).await;
+    };
+    let resuming = async {
+        match core.resume_paused_jobs().await {
+            Ok(0) => {}
+            Ok(count) => info!("Resumed {} paused jobs", count),
+            Err(e) => warn!("Could not resume paused jobs: {}", e),
+        }
+    };
+    let (served, _, _) = tokio::join!(server, stopping, resuming);
+    served?;
+    
+    info!("API server stopped");
+    Ok(())
+}
+
+/// Identify the caller from the bearer token and apply access rules
+async fn authenticate<B: MessageBody + 'static>(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    token: web::Data<ApiToken>,
+    req: ServiceRequest,
+    next: Next<B>,
+) -> ActixResult<ServiceResponse<impl MessageBody>> {
+    use actix_web::HttpMessage;
+    
+    let public = matches!(
+        req.path(),
+        "/api/v1/health" | "/api/v1/health/live" | "/api/v1/health/ready" | openapi::OPENAPI_PATH | "/docs"
+    ) || req.path().starts_with("/docs/");
+    if public {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    }
+    
+    let presented = req.headers().get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let Some(caller) = identify(&app, &token, presented).await else {
+        let response = HttpResponse::Unauthorized().json(serde_json::json!({
+            "success": false,
+            "message": "Missing or invalid bearer token"
+        }));
+        return Ok(req.into_response(response).map_into_right_body());
+    };
+    
+    if let Err(response) = authorize(&app, &caller, req.method(), req.path()).await {
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    req.extensions_mut().insert(caller);
+    next.call(req).await.map(ServiceResponse::map_into_left_body)
+}
+
+/// Caller presenting the bearer token `presented`; `None` when it isn't accepted
+async fn identify(app: &WinScrapeStudio, token: &ApiToken, presented: Option<&str>) -> Option<Caller> {
+    let Some(tokens) = &token.0 else {
+        return Some(Caller::Admin);
+    };
+    match presented {
+        Some(presented) if tokens.admin.as_deref() == Some(presented) => Some(Caller::Admin),
+        Some(presented) => match app.authenticate_user(presented).await {
+            Ok(user) => user.map(Caller::User),
+            Err(e) => {
+                error!("API: Failed to look up token: {}", e);
+                None
+            }
+        },
+        None => None,
+    }
+}
+
+/// Apply the caller's rate limit and report their quota in the response headers
+///
+/// Runs after `authenticate`; public routes have no caller and aren't limited.
+async fn limit_requests<B: MessageBody + 'static>(
+    limits: web::Data<ApiLimits>,
+    req: ServiceRequest,
+    next: Next<B>,
+) -> ActixResult<ServiceResponse<impl MessageBody>> {
+    use actix_web::HttpMessage;
+    
+    let key = req.extensions().get::<Caller>().map(|caller| ClientKey::of(caller, req.peer_addr()).0);
+    let Some(key) = key else {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    };
+    
+    let rate = limits.check_rate(&key);
+    if let Some(rate) = rate.as_ref().filter(|rate| !rate.allowed) {
+        let mut response = HttpResponse::TooManyRequests().json(serde_json::json!({
+            "success": false,
+            "message": format!("Rate limit exceeded; retry in {} seconds", rate.retry_after)
+        }));
+        rate.apply(response.headers_mut());
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    
+    req.extensions_mut().insert(ClientKey(key.clone()));
+    let mut response = next.call(req).await?;
+    if let Some(rate) = rate {
+        rate.apply(response.headers_mut());
+    }
+    if limits.has_quotas() {
+        limits.quota(&key).apply(response.headers_mut());
+    }
+    Ok(response.map_into_left_body())
+}
+
+/// Refuse a job when the client's daily quota is used up
+fn reserve_job(limits: &ApiLimits, key: &ClientKey) -> Option<HttpResponse> {
+    let exceeded = limits.reserve_job(&key.0).err()?;
+    Some(HttpResponse::TooManyRequests()
+        .insert_header((header::RETRY_AFTER, limits.quota(&key.0).reset_after))
+        .json(ExecuteScrapingResponse {
+            job_id: String::new(),
+            success: false,
+            message: exceeded.to_string(),
+        }))
+}
+
+/// Count a finished job's rows against the client's daily quota
+async fn record_rows(app: &WinScrapeStudio, limits: &ApiLimits, key: &ClientKey, job_id: &str) {
+    if limits.has_quotas() {
+        match app.get_job_result_count(job_id).await {
+            Ok(rows) => limits.record_rows(&key.0, rows),
+            Err(e) => error!("API: Failed to count rows of job {}: {}", job_id, e),
+        }
+    }
+}
+
+/// Body of a failed request with the error's troubleshooting code and hint
+fn error_body(message: String, error: &anyhow::Error) -> serde_json::Value {
+    let code = ErrorCode::of(error);
+    serde_json::json!({
+        "success": false,
+        "message": message,
+        "code": code.as_str(),
+        "hint": code.hint()
+    })
+}
+
+/// Keep users to their own jobs and away from admin routes
+///
+/// Other users' jobs answer 404, so their IDs can't be probed.
+async fn authorize(
+    app: &WinScrapeStudio,
+    caller: &Caller,
+    method: &actix_web::http::Method,
+    path: &str,
+) -> Result<(), HttpResponse> {
+    if caller.is_admin() {
+        return Ok(());
+    }
+    
+    let admin_only = path.starts_with("/api/v1/database/")
+        || path == "/api/v1/users"
+        || path.starts_with("/api/v1/users/")
+        || (path.starts_with("/api/v1/export-profiles") && method != actix_web::http::Method::GET);
+    if admin_only {
+        return Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "message": "Only admins can do this"
+        })));
+    }
+    
+    if let Some(rest) = path.strip_prefix("/api/v1/jobs/") {
+        let job_id = rest.split('/').next().unwrap_or_default();
+        let owned = match app.get_job_owner(job_id).await {
+            Ok(owner) => owner.is_some() && owner.as_deref() == caller.user_id(),
+            Err(_) => false,
+        };
+        if !owned {
+            return Err(HttpResponse::NotFound().json(serde_json::json!({
+                "success": false,
+                "message": format!("Job not found: {}", job_id)
+            })));
+        }
+    }
+    Ok(())
 }
 
 /// Configure API routes
@@ -48,11 +525,73 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
         web::scope("/api/v1")
             .route("/generate-dsl", web::post().to(generate_dsl))
             .route("/execute-scraping", web::post().to(execute_scraping))
+            .route("/dsl/schema", web::get().to(dsl_schema))
+            .route("/dsl/validate", web::post().to(validate_dsl))
+            .route("/dsl/explain", web::post().to(explain_dsl))
+            .route("/dsl/simulate", web::post().to(simulate_dsl))
+            .route("/dsl/refine", web::post().to(refine_dsl))
+            .route("/plans/validate", web::post().to(validate_dsl))
+            .route("/plans/generate", web::post().to(generate_plan))
             .route("/jobs", web::get().to(list_jobs))
+            .route("/jobs", web::post().to(submit_job))
             .route("/jobs/{job_id}", web::get().to(get_job))
+            .route("/jobs/{job_id}", web::delete().to(delete_job))
+            .route("/jobs/{job_id}/results", web::get().to(get_job_results))
+            .route("/jobs/{job_id}/events", web::get().to(job_events))
+            .route("/jobs/{job_id}/ws", web::get().to(job_events_ws))
+            .route("/jobs/{job_id}/lineage", web::get().to(get_job_lineage))
+            .route("/jobs/{job_id}/attempts", web::get().to(get_job_attempts))
+            .route("/jobs/{job_id}/log", web::get().to(get_job_log))
+            .route("/jobs/{job_id}/failures", web::get().to(get_failure_report))
+            .route("/jobs/{job_id}/issues", web::get().to(get_job_issues))
+            .route("/jobs/{job_id}/rescrape-failed", web::post().to(rescrape_failed))
+            .route("/jobs/{job_id}/stats", web::get().to(get_job_stats))
+            .route("/jobs/{job_id}/tags", web::get().to(get_job_tags))
+            .route("/jobs/{job_id}/tags", web::post().to(add_job_tag))
+            .route("/jobs/{job_id}/tags/{tag}", web::delete().to(remove_job_tag))
+            .route("/tags", web::get().to(list_tags))
+            .route("/events", web::get().to(workflow_events))
             .route("/jobs/{job_id}/export", web::post().to(export_job))
+            .route("/jobs/{job_id}/export/database", web::post().to(export_job_to_database))
+            .route("/jobs/{job_id}/export/profile/{name}", web::post().to(export_job_with_profile))
+            .route("/export-profiles", web::get().to(list_export_profiles))
+            .route("/export-profiles", web::post().to(save_export_profile))
+            .route("/export-profiles/{name}", web::delete().to(delete_export_profile))
+            .route("/database/backup", web::post().to(backup_database))
+            .route("/database/restore", web::post().to(restore_database))
+            .route("/database/archive", web::post().to(archive_results))
+            .route("/users", web::get().to(list_users))
+            .route("/users", web::post().to(create_user))
+            .route("/users/{user_id}", web::delete().to(delete_user))
+            .route("/i18n/report", web::get().to(translation_report))
+            .route("/i18n/{lang}/missing", web::get().to(missing_translations))
             .route("/health", web::get().to(health_check))
+            .route("/health/live", web::get().to(liveness))
+            .route("/health/ready", web::get().to(readiness))
     );
+    cfg.route(openapi::OPENAPI_PATH, web::get().to(openapi_json))
+        .route("/docs", web::get().to(swagger_ui))
+        .route("/docs/{asset}", web::get().to(swagger_ui_asset));
+}
+
+/// OpenAPI document of the API
+async fn openapi_json() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok().json(openapi::openapi_document()))
+}
+
+/// Swagger UI for browsing the API
+async fn swagger_ui() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(openapi::swagger_ui_page()))
+}
+
+/// Script or stylesheet of the Swagger UI page
+async fn swagger_ui_asset(asset: web::Path<String>) -> ActixResult<HttpResponse> {
+    match openapi::swagger_ui_asset(&asset) {
+        Some((content_type, content)) => Ok(HttpResponse::Ok().content_type(content_type).body(content)),
+        None => Ok(HttpResponse::NotFound().finish()),
+    }
 }
 
 /// Generate DSL from natural language description
@@ -83,15 +622,69 @@ async fn generate_dsl(
     }
 }
 
+/// Generate a candidate plan from a description, checked like plans from the chat
+///
+/// The description goes through the input checks and the plan through
+/// validation and the security policy; a plan the policy rejects is returned
+/// with 422.
+async fn generate_plan(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<GenerateDSLRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Generating plan for description: {}", req.description);
+    
+    let plan = match app.generate_dsl(&req.description).await {
+        Ok(plan) => plan,
+        Err(e) => {
+            error!("API: Failed to generate plan: {}", e);
+            return Ok(HttpResponse::BadRequest().json(error_body(format!("Failed to generate plan: {}", e), &e)));
+        }
+    };
+    
+    let rejection = app.validate_dsl(&plan).await.err();
+    let response = GeneratePlanResponse {
+        warnings: app.lint_dsl(&plan),
+        politeness_violations: app.get_security_manager().check_politeness(&plan),
+        confidence: plan.get_metadata("confidence").and_then(|v| v.as_f64()),
+        plan,
+    };
+    
+    match rejection {
+        None => Ok(HttpResponse::Ok().json(response)),
+        Some(e) => {
+            error!("API: Generated plan was rejected: {}", e);
+            Ok(HttpResponse::UnprocessableEntity().json(serde_json::json!({
+                "success": false,
+                "message": format!("Generated plan was rejected: {}", e),
+                "candidate": response
+            })))
+        }
+    }
+}
+
 /// Execute scraping job
 async fn execute_scraping(
     app: web::Data<Arc<WinScrapeStudio>>,
+    limits: web::Data<ApiLimits>,
+    caller: Caller,
+    key: ClientKey,
     req: web::Json<ExecuteScrapingRequest>,
 ) -> ActixResult<HttpResponse> {
     info!("API: Executing scraping job");
     
-    match app.execute_scraping(&req.dsl).await {
+    if let Some(refused) = reserve_job(&limits, &key) {
+        return Ok(refused);
+    }
+    
+    let result = async {
+        let (job_id, run_plan) = app.create_scraping_job(&req.dsl, &req.variables, caller.user_id()).await?;
+        app.run_scraping_job(&job_id, run_plan).await?;
+        anyhow::Ok(job_id)
+    }.await;
+    
+    match result {
         Ok(job_id) => {
+            record_rows(&app, &limits, &key, &job_id).await;
             let response = ExecuteScrapingResponse {
                 job_id,
                 success: true,
@@ -111,20 +704,199 @@ async fn execute_scraping(
     }
 }
 
-/// List recent jobs
+/// Queue a scraping job and run it in the background
+///
+/// Responds with 202 and the job ID once the job is recorded; follow it with
+/// `GET /jobs/{job_id}` and `GET /jobs/{job_id}/results`.
+async fn submit_job(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    limits: web::Data<ApiLimits>,
+    caller: Caller,
+    key: ClientKey,
+    req: web::Json<ExecuteScrapingRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Submitting scraping job for domain: {}", req.dsl.target.domain);
+    
+    let errors = app.validate_dsl_detailed(&req.dsl);
+    if !errors.is_empty() {
+        return Ok(HttpResponse::UnprocessableEntity().json(ValidateDSLResponse {
+            valid: false,
+            errors,
+            warnings: app.lint_dsl(&req.dsl),
+        }));
+    }
+    if let Some(refused) = reserve_job(&limits, &key) {
+        return Ok(refused);
+    }
+    
+    match app.create_scraping_job(&req.dsl, &req.variables, caller.user_id()).await {
+        Ok((job_id, run_plan)) => {
+            let runner = app.get_ref().clone();
+            let run_id = job_id.clone();
+            actix_web::rt::spawn(async move {
+                if let Err(e) = runner.run_scraping_job(&run_id, run_plan).await {
+                    error!("API: Scraping job {} failed: {}", run_id, e);
+                }
+                record_rows(&runner, &limits, &key, &run_id).await;
+            });
+            Ok(HttpResponse::Accepted().json(ExecuteScrapingResponse {
+                job_id,
+                success: true,
+                message: "Scraping job queued".to_string(),
+            }))
+        }
+        Err(e) => {
+            error!("API: Failed to submit scraping job: {}", e);
+            Ok(HttpResponse::BadRequest().json(ExecuteScrapingResponse {
+                job_id: String::new(),
+                success: false,
+                message: format!("Failed to submit scraping job: {}", e),
+            }))
+        }
+    }
+}
+
+/// Get the JSON Schema for scrape plans
+async fn dsl_schema() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .content_type("application/schema+json")
+        .json(crate::dsl::schema::scrape_plan_schema()))
+}
+
+/// Validate a scrape plan and report every problem with its path
+async fn validate_dsl(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<ScrapePlan>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Validating DSL for domain: {}", req.target.domain);
+    
+    let errors = app.validate_dsl_detailed(&req);
+    let response = ValidateDSLResponse {
+        valid: errors.is_empty(),
+        errors,
+        warnings: app.lint_dsl(&req),
+    };
+    
+    if response.valid {
+        Ok(HttpResponse::Ok().json(response))
+    } else {
+        Ok(HttpResponse::UnprocessableEntity().json(response))
+    }
+}
+
+/// Describe a scrape plan in plain language
+async fn explain_dsl(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<ExplainPlanRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Explaining DSL for domain: {}", req.dsl.target.domain);
+    
+    let language = match req.language.as_deref() {
+        Some(code) => match Language::from_code(code) {
+            Some(language) => Some(language),
+            None => {
+                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                    "success": false,
+                    "message": format!("Unsupported language: {}", code)
+                })));
+            }
+        },
+        None => None,
+    };
+    
+    Ok(HttpResponse::Ok().json(app.explain_plan(&req.dsl, language)))
+}
+
+/// Completeness of each language's translations compared with English
+async fn translation_report(app: web::Data<Arc<WinScrapeStudio>>) -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok().json(app.translation_report()))
+}
+
+/// Untranslated keys of a language with their English text, as a stub for translators
+async fn missing_translations(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let code = path.into_inner();
+    match Language::from_code(&code) {
+        Some(language) => Ok(HttpResponse::Ok().json(app.missing_translations(language))),
+        None => Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "message": format!("Unsupported language: {}", code)
+        }))),
+    }
+}
+
+/// Work out what running a plan would do without fetching any page
+async fn simulate_dsl(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<ScrapePlan>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Simulating DSL for domain: {}", req.target.domain);
+    
+    match app.simulate(&req).await {
+        Ok(simulation) if simulation.is_runnable() => Ok(HttpResponse::Ok().json(simulation)),
+        Ok(simulation) => Ok(HttpResponse::UnprocessableEntity().json(simulation)),
+        Err(e) => {
+            error!("API: Simulation failed: {}", e);
+            Ok(HttpResponse::BadRequest().json(error_body(format!("Simulation failed: {}", e), &e)))
+        }
+    }
+}
+
+/// Apply a follow-up instruction to an existing plan
+async fn refine_dsl(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<RefineDSLRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Refining DSL: {}", req.instruction);
+    
+    match app.refine_dsl(&req.dsl, &req.instruction).await {
+        Ok(dsl) => {
+            let response = GenerateDSLResponse {
+                dsl,
+                success: true,
+                message: "DSL refined successfully".to_string(),
+            };
+            Ok(HttpResponse::Ok().json(response))
+        }
+        Err(e) => {
+            error!("API: Failed to refine DSL: {}", e);
+            let response = GenerateDSLResponse {
+                dsl: req.into_inner().dsl,
+                success: false,
+                message: format!("Failed to refine DSL: {}", e),
+            };
+            Ok(HttpResponse::BadRequest().json(response))
+        }
+    }
+}
+
+/// List recent jobs, optionally filtered by status, tag, domain, date or text
 async fn list_jobs(
     app: web::Data<Arc<WinScrapeStudio>>,
+    caller: Caller,
+    query: web::Query<JobListQuery>,
 ) -> ActixResult<HttpResponse> {
     info!("API: Listing jobs");
     
-    match app.list_jobs(50).await {
+    let mut filter = match query.to_filter() {
+        Ok(filter) => filter,
+        Err(message) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "message": message
+            })));
+        }
+    };
+    let limit = query.limit.unwrap_or(50).min(1000);
+    filter.owner = caller.visible_owner();
+    
+    match app.list_jobs_filtered(&filter, limit).await {
         Ok(jobs) => Ok(HttpResponse::Ok().json(jobs)),
         Err(e) => {
             error!("API: Failed to list jobs: {}", e);
-            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "success": false,
-                "message": format!("Failed to list jobs: {}", e)
-            })))
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to list jobs: {}", e), &e)))
         }
     }
 }
@@ -141,56 +913,771 @@ async fn get_job(
         Ok(job) => Ok(HttpResponse::Ok().json(job)),
         Err(e) => {
             error!("API: Failed to get job {}: {}", job_id, e);
-            Ok(HttpResponse::NotFound().json(serde_json::json!({
-                "success": false,
-                "message": format!("Job not found: {}", e)
-            })))
+            Ok(HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e)))
         }
     }
 }
 
-/// Export job results
-async fn export_job(
+/// Delete a finished job and its results
+async fn delete_job(
     app: web::Data<Arc<WinScrapeStudio>>,
     path: web::Path<String>,
-    req: web::Json<ExportJobRequest>,
 ) -> ActixResult<HttpResponse> {
     let job_id = path.into_inner();
-    info!("API: Exporting job {} in format {}", job_id, req.format);
-    
-    let format = match req.format.as_str() {
-        "csv" => ExportFormat::Csv,
-        "json" => ExportFormat::Json,
-        "xlsx" => ExportFormat::Xlsx,
-        "parquet" => ExportFormat::Parquet,
-        _ => {
-            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
-                "success": false,
-                "message": "Unsupported export format"
-            })));
-        }
-    };
+    info!("API: Deleting job: {}", job_id);
     
-    let output_path = format!("exports/job_{}_{}.{}", job_id, 
-        chrono::Utc::now().format("%Y%m%d_%H%M%S"), 
-        req.format);
+    if let Err(e) = app.get_job(&job_id).await {
+        return Ok(HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e)));
+    }
     
-    match app.export_job(&job_id, &output_path, format).await {
-        Ok(_) => Ok(HttpResponse::Ok().json(serde_json::json!({
+    match app.delete_job(&job_id).await {
+        Ok(()) => Ok(HttpResponse::Ok().json(serde_json::json!({
             "success": true,
-            "message": "Export completed successfully",
-            "file_path": output_path
+            "message": "Job deleted"
         }))),
         Err(e) => {
-            error!("API: Failed to export job {}: {}", job_id, e);
-            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "success": false,
-                "message": format!("Failed to export job: {}", e)
-            })))
+            error!("API: Failed to delete job {}: {}", job_id, e);
+            Ok(HttpResponse::Conflict().json(error_body(format!("Failed to delete job: {}", e), &e)))
         }
     }
 }
 
+/// Get a page of a job's results
+///
+/// Pass the returned `next_after` as `after` to fetch the next page; `finished`
+/// is set once the job has ended and every row has been returned.
+async fn get_job_results(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+    query: web::Query<ResultPageQuery>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    info!("API: Getting results for job: {}", job_id);
+    
+    match app.tail_job_results(&job_id, query.after, limit).await {
+        Ok(page) => Ok(HttpResponse::Ok().json(page)),
+        Err(e) => {
+            error!("API: Failed to get results for job {}: {}", job_id, e);
+            Ok(HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e)))
+        }
+    }
+}
+
+/// Stream a job's progress as server-sent events until it finishes
+///
+/// Each event is named after its `type`; a job that has already ended gets
+/// a single `finished` event.
+async fn job_events(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Streaming events of job: {}", job_id);
+    
+    let events = match follow_job(&app, &job_id).await {
+        Ok(events) => events,
+        Err(response) => return Ok(response),
+    };
+    let frames = events.map(|progress| {
+        let frame = match progress {
+            Some(progress) => {
+                let data = serde_json::to_value(&progress).unwrap_or_default();
+                format!("event: {}\ndata: {}\n\n", data["type"].as_str().unwrap_or("progress"), data)
+            }
+            None => ": keep-alive\n\n".to_string(),
+        };
+        Ok::<_, actix_web::Error>(web::Bytes::from(frame))
+    });
+    
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(frames))
+}
+
+/// Stream lifecycle events as server-sent events until the client leaves
+///
+/// Admins get every job's events, other users those of their own jobs.
+/// Each event is named after its `type`, e.g. `job_created` or `job_completed`.
+async fn workflow_events(app: web::Data<Arc<WinScrapeStudio>>, caller: Caller) -> ActixResult<HttpResponse> {
+    info!("API: Streaming workflow events");
+    
+    let owned = caller.visible_owner().map(OwnedJobs::new);
+    let subscription = app.subscribe_events();
+    let frames = futures::stream::unfold((app, subscription, owned), |(app, mut subscription, mut owned)| async move {
+        // Skipped events of other users' jobs don't hold off the keep-alive
+        let deadline = tokio::time::Instant::now() + EVENT_KEEPALIVE;
+        let frame = loop {
+            match tokio::time::timeout_at(deadline, subscription.next()).await {
+                Err(_) => break ": keep-alive\n\n".to_string(),
+                Ok(None) => return None,
+                Ok(Some(event)) => {
+                    if let Some(owned) = owned.as_mut() {
+                        if !owned.shows(&app, &event).await {
+                            continue;
+                        }
+                    }
+                    let data = serde_json::to_value(&event).unwrap_or_default();
+                    break format!("event: {}\ndata: {}\n\n", data["type"].as_str().unwrap_or("event"), data);
+                }
+            }
+        };
+        Some((Ok::<_, actix_web::Error>(web::Bytes::from(frame)), (app, subscription, owned)))
+    });
+    
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(frames))
+}
+
+/// Which jobs in a user's event stream are theirs
+///
+/// Owners are looked up once per job and forgotten when it ends. A job's
+/// creation event comes before its owner is recorded, so that lookup isn't kept.
+struct OwnedJobs {
+    owner: String,
+    known: HashMap<String, bool>,
+}
+
+impl OwnedJobs {
+    fn new(owner: String) -> Self {
+        Self { owner, known: HashMap::new() }
+    }
+    
+    async fn shows(&mut self, app: &WinScrapeStudio, event: &WorkflowEvent) -> bool {
+        let owned = match self.known.get(&event.job_id) {
+            Some(&owned) => owned,
+            None => {
+                let owned = app.get_job_owner(&event.job_id).await.ok().flatten().as_deref() == Some(self.owner.as_str());
+                if !matches!(event.event, JobEvent::JobCreated { .. }) {
+                    self.known.insert(event.job_id.clone(), owned);
+                }
+                owned
+            }
+        };
+        if event.is_final() {
+            self.known.remove(&event.job_id);
+        }
+        owned
+    }
+}
+
+/// Stream a job's progress over a WebSocket until it finishes
+///
+/// Events are sent as JSON text messages; the server closes the socket after
+/// the `finished` event.
+async fn job_events_ws(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+    req: HttpRequest,
+    payload: web::Payload,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Opening event socket for job: {}", job_id);
+    
+    if let Err(e) = ws::verify_handshake(req.head()) {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "message": format!("WebSocket handshake failed: {}", e)
+        })));
+    }
+    let events = match follow_job(&app, &job_id).await {
+        Ok(events) => events,
+        Err(response) => return Ok(response),
+    };
+    let key = req.headers().get(header::SEC_WEBSOCKET_KEY).map(|key| key.as_bytes()).unwrap_or_default();
+    let accept = ws::hash_key(key);
+    
+    Ok(HttpResponse::SwitchingProtocols()
+        .upgrade("websocket")
+        .insert_header((header::SEC_WEBSOCKET_ACCEPT, header::HeaderValue::from_bytes(&accept)?))
+        .streaming(ws_frames(events, payload)))
+}
+
+/// Progress events of a job, `None` standing for a keep-alive
+type ProgressStream = Pin<Box<dyn Stream<Item = Option<JobProgress>>>>;
+
+/// Follow a job's events, ending after its last one
+///
+/// Subscribes before reading the job so no event falls in between.
+async fn follow_job(app: &WinScrapeStudio, job_id: &str) -> Result<ProgressStream, HttpResponse> {
+    let subscription = app.subscribe_job_progress(job_id);
+    let job = app.get_job(job_id).await.map_err(|e| {
+        HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e))
+    })?;
+    
+    if !matches!(job.status, crate::storage::JobStatus::Queued | crate::storage::JobStatus::Running) {
+        let finished = JobProgress {
+            job_id: job.id,
+            at: chrono::Utc::now(),
+            event: ProgressEvent::Finished { status: job.status },
+        };
+        return Ok(Box::pin(futures::stream::once(async move { Some(finished) })));
+    }
+    
+    Ok(Box::pin(futures::stream::unfold(Some(subscription), |subscription: Option<ProgressSubscription>| async move {
+        let mut subscription = subscription?;
+        match tokio::time::timeout(EVENT_KEEPALIVE, subscription.next()).await {
+            Err(_) => Some((None, Some(subscription))),
+            Ok(None) => None,
+            Ok(Some(progress)) => {
+                let next = (!progress.is_final()).then_some(subscription);
+                Some((Some(progress), next))
+            }
+        }
+    })))
+}
+
+/// WebSocket frames carrying `events`, answering the client's pings and close
+fn ws_frames(events: ProgressStream, payload: web::Payload) -> impl Stream<Item = Result<web::Bytes, actix_web::Error>> {
+    struct Socket {
+        events: ProgressStream,
+        payload: web::Payload,
+        received: web::BytesMut,
+    }
+    
+    let socket = Socket { events, payload, received: web::BytesMut::new() };
+    futures::stream::unfold(Some(socket), |socket| async move {
+        let mut socket = socket?;
+        let mut out = web::BytesMut::new();
+        loop {
+            tokio::select! {
+                event = socket.events.next() => {
+                    match event {
+                        Some(Some(progress)) => {
+                            let text = serde_json::to_string(&progress).unwrap_or_default();
+                            ws::Parser::write_message(&mut out, text, ws::OpCode::Text, true, false);
+                        }
+                        Some(None) => ws::Parser::write_message(&mut out, "", ws::OpCode::Ping, true, false),
+                        None => {
+                            ws::Parser::write_close(&mut out, Some(ws::CloseCode::Normal.into()), false);
+                            return Some((Ok(out.freeze()), None));
+                        }
+                    }
+                    return Some((Ok(out.freeze()), Some(socket)));
+                }
+                chunk = socket.payload.next() => {
+                    let Some(Ok(chunk)) = chunk else {
+                        return None;
+                    };
+                    socket.received.extend_from_slice(&chunk);
+                    loop {
+                        match ws::Parser::parse(&mut socket.received, true, WS_MAX_FRAME) {
+                            Ok(Some((_, ws::OpCode::Ping, data))) => {
+                                ws::Parser::write_message(&mut out, data.unwrap_or_default(), ws::OpCode::Pong, true, false);
+                            }
+                            Ok(Some((_, ws::OpCode::Close, _))) | Err(_) => {
+                                ws::Parser::write_close(&mut out, Some(ws::CloseCode::Normal.into()), false);
+                                return Some((Ok(out.freeze()), None));
+                            }
+                            Ok(Some(_)) => {}
+                            Ok(None) => break,
+                        }
+                    }
+                    if !out.is_empty() {
+                        return Some((Ok(out.freeze()), Some(socket)));
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Get the jobs a job was cloned from
+async fn get_job_lineage(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    caller: Caller,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Getting lineage for job: {}", job_id);
+    
+    match app.get_job_lineage(&job_id).await {
+        Ok(mut lineage) => {
+            // Jobs cloned from another user's job don't reveal it
+            if let Some(owner) = caller.visible_owner() {
+                let mut visible = Vec::with_capacity(lineage.len());
+                for job in lineage {
+                    if app.get_job_owner(&job.id).await.ok().flatten().as_deref() == Some(owner.as_str()) {
+                        visible.push(job);
+                    }
+                }
+                lineage = visible;
+            }
+            Ok(HttpResponse::Ok().json(lineage))
+        }
+        Err(e) => {
+            error!("API: Failed to get lineage for job {}: {}", job_id, e);
+            Ok(HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e)))
+        }
+    }
+}
+
+/// Get per-column statistics of a job's results
+async fn get_job_stats(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+    query: web::Query<ColumnStatsQuery>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    let top = query.top.unwrap_or(crate::storage::column_stats::DEFAULT_TOP_VALUES).min(100);
+    info!("API: Getting column statistics for job: {}", job_id);
+    
+    match app.get_column_stats(&job_id, top).await {
+        Ok(stats) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "job_id": job_id,
+            "columns": stats
+        }))),
+        Err(e) => {
+            error!("API: Failed to get column statistics for job {}: {}", job_id, e);
+            Ok(HttpResponse::NotFound().json(error_body(format!("Failed to get statistics: {}", e), &e)))
+        }
+    }
+}
+
+/// Get the runs of a job and how each ended
+async fn get_job_attempts(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    
+    match app.get_job_attempts(&job_id).await {
+        Ok(attempts) => Ok(HttpResponse::Ok().json(attempts)),
+        Err(e) => {
+            error!("API: Failed to get attempts of job {}: {}", job_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to get attempts: {}", e), &e)))
+        }
+    }
+}
+
+/// Download a job's log as a text file
+async fn get_job_log(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    
+    if let Err(e) = app.get_job(&job_id).await {
+        return Ok(HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e)));
+    }
+    
+    match app.read_job_log_file(&job_id).await {
+        Ok(log) => Ok(HttpResponse::Ok()
+            .content_type("text/plain; charset=utf-8")
+            .insert_header(("Content-Disposition", format!("attachment; filename=\"job-{}.log\"", job_id)))
+            .body(log)),
+        Err(e) => {
+            error!("API: Failed to read log of job {}: {}", job_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to read job log: {}", e), &e)))
+        }
+    }
+}
+
+/// Get the URLs of a job that failed, grouped by reason
+async fn get_failure_report(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    
+    match app.get_failure_report(&job_id).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(e) => {
+            error!("API: Failed to get failure report of job {}: {}", job_id, e);
+            Ok(HttpResponse::NotFound().json(error_body(format!("Failed to get failure report: {}", e), &e)))
+        }
+    }
+}
+
+/// Get the problems a job's last run met without failing
+async fn get_job_issues(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    
+    match app.get_job_issues(&job_id).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(report)),
+        Err(e) => {
+            error!("API: Failed to get issues of job {}: {}", job_id, e);
+            Ok(HttpResponse::NotFound().json(error_body(format!("Failed to get job issues: {}", e), &e)))
+        }
+    }
+}
+
+/// Fetch the failed URLs of a job again in the background
+///
+/// Responds with 202 once the run is started; new rows are added to the
+/// job's results.
+async fn rescrape_failed(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Re-scraping failed URLs of job: {}", job_id);
+    
+    let failed = match app.get_failure_report(&job_id).await {
+        Ok(report) => report.failed.len(),
+        Err(e) => {
+            return Ok(HttpResponse::NotFound().json(error_body(format!("Job not found: {}", e), &e)));
+        }
+    };
+    if failed == 0 {
+        return Ok(HttpResponse::Conflict().json(serde_json::json!({
+            "success": false,
+            "message": format!("Job {} has no failed URLs", job_id)
+        })));
+    }
+    
+    let runner = app.get_ref().clone();
+    let run_id = job_id.clone();
+    actix_web::rt::spawn(async move {
+        match runner.rescrape_failed(&run_id).await {
+            Ok(report) => info!("API: Re-scraped {} URLs of job {}, {} rows added", report.urls, run_id, report.rows_added),
+            Err(e) => error!("API: Re-scraping job {} failed: {}", run_id, e),
+        }
+    });
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "success": true,
+        "job_id": job_id,
+        "message": format!("Fetching {} failed URLs again", failed)
+    })))
+}
+
+/// Get the tags of a job
+async fn get_job_tags(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    
+    match app.get_job_tags(&job_id).await {
+        Ok(tags) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "job_id": job_id,
+            "tags": tags
+        }))),
+        Err(e) => {
+            error!("API: Failed to get tags for job {}: {}", job_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to get tags: {}", e), &e)))
+        }
+    }
+}
+
+/// Tag a job
+async fn add_job_tag(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+    req: web::Json<TagRequest>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Tagging job {} with '{}'", job_id, req.tag);
+    
+    match app.add_job_tag(&job_id, &req.tag).await {
+        Ok(()) => get_job_tags(app, web::Path::from(job_id)).await,
+        Err(e) => {
+            error!("API: Failed to tag job {}: {}", job_id, e);
+            Ok(HttpResponse::BadRequest().json(error_body(format!("Failed to tag job: {}", e), &e)))
+        }
+    }
+}
+
+/// Remove a tag from a job
+async fn remove_job_tag(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<(String, String)>,
+) -> ActixResult<HttpResponse> {
+    let (job_id, tag) = path.into_inner();
+    info!("API: Removing tag '{}' from job {}", tag, job_id);
+    
+    match app.remove_job_tag(&job_id, &tag).await {
+        Ok(true) => get_job_tags(app, web::Path::from(job_id)).await,
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "message": format!("Job {} has no tag '{}'", job_id, tag)
+        }))),
+        Err(e) => {
+            error!("API: Failed to remove tag from job {}: {}", job_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to remove tag: {}", e), &e)))
+        }
+    }
+}
+
+/// List tags in use with their job counts
+async fn list_tags(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    caller: Caller,
+) -> ActixResult<HttpResponse> {
+    let tags = match caller.visible_owner() {
+        Some(owner) => app.list_owner_tags(&owner).await,
+        None => app.list_tags().await,
+    };
+    match tags {
+        Ok(tags) => Ok(HttpResponse::Ok().json(tags)),
+        Err(e) => {
+            error!("API: Failed to list tags: {}", e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to list tags: {}", e), &e)))
+        }
+    }
+}
+
+/// Export job results
+async fn export_job(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+    req: web::Json<ExportJobRequest>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Exporting job {} in format {}", job_id, req.format);
+    
+    let format = match req.format.as_str() {
+        "csv" => ExportFormat::Csv,
+        "json" => ExportFormat::Json,
+        "xlsx" => ExportFormat::Xlsx,
+        "parquet" => ExportFormat::Parquet,
+        "ndjson" | "jsonl" => ExportFormat::Ndjson,
+        _ => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "message": "Unsupported export format"
+            })));
+        }
+    };
+    
+    match app.export_job_to_default_path(&job_id, format, &req.options).await {
+        Ok(output_path) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Export completed successfully",
+            "file_path": output_path
+        }))),
+        Err(e) => {
+            error!("API: Failed to export job {}: {}", job_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to export job: {}", e), &e)))
+        }
+    }
+}
+
+/// Upsert job results into a table of the export database
+async fn export_job_to_database(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+    req: web::Json<ExportDatabaseRequest>,
+) -> ActixResult<HttpResponse> {
+    let job_id = path.into_inner();
+    info!("API: Exporting job {} to the export database", job_id);
+    
+    match app.export_job_to_database(&job_id, req.table.as_deref(), &req.options).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "report": report
+        }))),
+        Err(e) => {
+            error!("API: Failed to export job {} to the database: {}", job_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to export job: {}", e), &e)))
+        }
+    }
+}
+
+/// Export job results with a saved profile
+async fn export_job_with_profile(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<(String, String)>,
+) -> ActixResult<HttpResponse> {
+    let (job_id, name) = path.into_inner();
+    info!("API: Exporting job {} with profile {}", job_id, name);
+    
+    match app.export_job_with_profile(&job_id, &name).await {
+        Ok(target) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "target": target
+        }))),
+        Err(e) => {
+            error!("API: Failed to export job {} with profile {}: {}", job_id, name, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to export job: {}", e), &e)))
+        }
+    }
+}
+
+/// List saved export profiles
+async fn list_export_profiles(app: web::Data<Arc<WinScrapeStudio>>) -> ActixResult<HttpResponse> {
+    match app.list_export_profiles().await {
+        Ok(profiles) => Ok(HttpResponse::Ok().json(profiles)),
+        Err(e) => {
+            error!("API: Failed to list export profiles: {}", e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to list export profiles: {}", e), &e)))
+        }
+    }
+}
+
+/// Save an export profile, replacing one of the same name
+async fn save_export_profile(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<ExportProfile>,
+) -> ActixResult<HttpResponse> {
+    match app.save_export_profile(&req).await {
+        Ok(()) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "name": req.name
+        }))),
+        Err(e) => Ok(HttpResponse::BadRequest().json(error_body(format!("Invalid export profile: {}", e), &e))),
+    }
+}
+
+/// Delete an export profile
+async fn delete_export_profile(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let name = path.into_inner();
+    match app.delete_export_profile(&name).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(serde_json::json!({ "success": true }))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "message": format!("Export profile not found: {}", name)
+        }))),
+        Err(e) => {
+            error!("API: Failed to delete export profile {}: {}", name, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to delete export profile: {}", e), &e)))
+        }
+    }
+}
+
+/// Copy the job database to a file
+async fn backup_database(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<DatabaseFileRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Backing up database to {}", req.path);
+    
+    match app.backup_database(&req.path).await {
+        Ok(backup) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Backup completed successfully",
+            "backup": backup
+        }))),
+        Err(e) => {
+            error!("API: Failed to back up database: {}", e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to back up database: {}", e), &e)))
+        }
+    }
+}
+
+/// Move results of old finished jobs into Parquet files
+async fn archive_results(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<ArchiveRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Archiving old results");
+    
+    match app.archive_old_results(req.older_than_days).await {
+        Ok(report) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": format!("Archived {} jobs", report.jobs_archived),
+            "archive": report
+        }))),
+        Err(e) => {
+            error!("API: Failed to archive results: {}", e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to archive results: {}", e), &e)))
+        }
+    }
+}
+
+/// Replace the job database with a backup
+async fn restore_database(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<DatabaseFileRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Restoring database from {}", req.path);
+    
+    match app.restore_database(&req.path).await {
+        Ok((backup, previous)) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Restore completed successfully",
+            "backup": backup,
+            "previous_database": previous
+        }))),
+        Err(e) => {
+            error!("API: Failed to restore database: {}", e);
+            Ok(HttpResponse::BadRequest().json(error_body(format!("Failed to restore database: {}", e), &e)))
+        }
+    }
+}
+
+/// List API users
+async fn list_users(app: web::Data<Arc<WinScrapeStudio>>) -> ActixResult<HttpResponse> {
+    match app.list_users().await {
+        Ok(users) => Ok(HttpResponse::Ok().json(users)),
+        Err(e) => {
+            error!("API: Failed to list users: {}", e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to list users: {}", e), &e)))
+        }
+    }
+}
+
+/// Add an API user; the response holds their token, which is not shown again
+async fn create_user(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    req: web::Json<CreateUserRequest>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Creating user {}", req.name);
+    
+    match app.create_user(&req.name, req.is_admin).await {
+        Ok((user, token)) => Ok(HttpResponse::Created().json(serde_json::json!({
+            "success": true,
+            "user": user,
+            "token": token
+        }))),
+        Err(e) => Ok(HttpResponse::BadRequest().json(error_body(format!("Failed to create user: {}", e), &e))),
+    }
+}
+
+/// Remove an API user; their jobs are kept
+async fn delete_user(
+    app: web::Data<Arc<WinScrapeStudio>>,
+    path: web::Path<String>,
+) -> ActixResult<HttpResponse> {
+    let user_id = path.into_inner();
+    info!("API: Deleting user {}", user_id);
+    
+    match app.delete_user(&user_id).await {
+        Ok(true) => Ok(HttpResponse::Ok().json(serde_json::json!({ "success": true }))),
+        Ok(false) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "message": format!("No user with ID {}", user_id)
+        }))),
+        Err(e) => {
+            error!("API: Failed to delete user {}: {}", user_id, e);
+            Ok(HttpResponse::InternalServerError().json(error_body(format!("Failed to delete user: {}", e), &e)))
+        }
+    }
+}
+
+/// Liveness probe: the server is up and answering
+async fn liveness() -> ActixResult<HttpResponse> {
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "status": "alive",
+        "version": env!("CARGO_PKG_VERSION"),
+        "timestamp": chrono::Utc::now().to_rfc3339()
+    })))
+}
+
+/// Readiness probe: 503 while a dependency jobs need is down
+async fn readiness(app: web::Data<Arc<WinScrapeStudio>>) -> ActixResult<HttpResponse> {
+    let report = app.check_readiness().await;
+    if report.is_ready() {
+        Ok(HttpResponse::Ok().json(report))
+    } else {
+        let down: Vec<&str> = report.components.iter()
+            .filter(|c| c.status == ComponentState::Down)
+            .map(|c| c.name.as_str())
+            .collect();
+        error!("API: Not ready, down: {}", down.join(", "));
+        Ok(HttpResponse::ServiceUnavailable().json(report))
+    }
+}
+
 /// Health check endpoint
 async fn health_check() -> ActixResult<HttpResponse> {
     Ok(HttpResponse::Ok().json(serde_json::json!({
@@ -0,0 +1,411 @@
+use actix_web::http::{Method, StatusCode};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tonic::{Code, Request, Response, Status};
+use tracing::{error, info};
+
+use super::{authorize, identify, record_rows, ApiLimits, ApiToken, Caller, ClientKey};
+use crate::core::progress::{JobProgress, JobStage, ProgressEvent};
+use crate::core::WinScrapeStudio;
+use crate::dsl::{DSLParser, ScrapePlan};
+use crate::error::ErrorCode;
+use crate::export::{ExportFormat, ExportOptions};
+use crate::storage::{Job, JobFilter, JobStatus};
+
+/// Types generated from `proto/winscrape/v1/jobs.proto`, including the client
+pub mod proto {
+    tonic::include_proto!("winscrape.v1");
+}
+
+use proto::job_service_server::{JobService, JobServiceServer};
+
+/// The gRPC job service, under the access rules, rate limits and quotas of the REST API
+///
+/// Callers authenticate with `authorization: Bearer <token>` metadata, and each
+/// call is authorized as the REST route it mirrors.
+pub struct JobApi {
+    app: Arc<WinScrapeStudio>,
+    token: Arc<ApiToken>,
+    limits: Arc<ApiLimits>,
+}
+
+/// Serve the job service on `addr` until the process is asked to stop
+pub async fn serve(service: JobApi, addr: SocketAddr) -> Result<()> {
+    info!("Starting gRPC server on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(service.into_server())
+        .serve_with_shutdown(addr, crate::core::shutdown::termination_signal())
+        .await?;
+    info!("gRPC server stopped");
+    Ok(())
+}
+
+impl JobApi {
+    pub(super) fn new(app: Arc<WinScrapeStudio>, token: Arc<ApiToken>, limits: Arc<ApiLimits>) -> Self {
+        Self { app, token, limits }
+    }
+
+    pub fn into_server(self) -> JobServiceServer<Self> {
+        JobServiceServer::new(self)
+    }
+
+    /// Identify the caller, apply their rate limit and the rules of the REST route `path`
+    async fn admit<T>(&self, request: &Request<T>, method: Method, path: &str) -> Result<(Caller, ClientKey), Status> {
+        let presented = request.metadata().get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let caller = identify(&self.app, &self.token, presented).await
+            .ok_or_else(|| Status::unauthenticated("Missing or invalid bearer token"))?;
+
+        let key = ClientKey::of(&caller, request.remote_addr());
+        if let Some(rate) = self.limits.check_rate(&key.0).filter(|rate| !rate.allowed) {
+            return Err(Status::resource_exhausted(format!("Rate limit exceeded; retry in {} seconds", rate.retry_after)));
+        }
+
+        authorize(&self.app, &caller, &method, path).await.map_err(|response| match response.status() {
+            StatusCode::FORBIDDEN => Status::permission_denied("Only admins can do this"),
+            _ => Status::not_found("Job not found"),
+        })?;
+        Ok((caller, key))
+    }
+}
+
+/// Status for a failed call, with the error's troubleshooting code and hint
+fn failed(code: Code, message: &str, error: &anyhow::Error) -> Status {
+    let wss = ErrorCode::of(error);
+    Status::new(code, format!("{}: {} ({}: {})", message, error, wss.as_str(), wss.hint()))
+}
+
+/// Plan sent as YAML or JSON
+fn parse_plan(text: &str) -> Result<ScrapePlan, Status> {
+    DSLParser::parse(text, None).map_err(|e| Status::invalid_argument(format!("Invalid plan: {}", e)))
+}
+
+fn timestamp(at: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn date_time(at: &prost_types::Timestamp) -> Result<DateTime<Utc>, Status> {
+    DateTime::from_timestamp(at.seconds, at.nanos.max(0) as u32)
+        .ok_or_else(|| Status::invalid_argument("Timestamp out of range"))
+}
+
+impl From<JobStatus> for proto::JobStatus {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Queued => Self::Queued,
+            JobStatus::Running => Self::Running,
+            JobStatus::Completed => Self::Completed,
+            JobStatus::Failed => Self::Failed,
+            JobStatus::Cancelled => Self::Cancelled,
+            JobStatus::Paused => Self::Paused,
+        }
+    }
+}
+
+/// Storage status of a status sent by a client; `None` when unspecified
+fn job_status(status: i32) -> Result<Option<JobStatus>, Status> {
+    let status = proto::JobStatus::try_from(status)
+        .map_err(|_| Status::invalid_argument(format!("Unknown job status {}", status)))?;
+    Ok(match status {
+        proto::JobStatus::Unspecified => None,
+        proto::JobStatus::Queued => Some(JobStatus::Queued),
+        proto::JobStatus::Running => Some(JobStatus::Running),
+        proto::JobStatus::Completed => Some(JobStatus::Completed),
+        proto::JobStatus::Failed => Some(JobStatus::Failed),
+        proto::JobStatus::Cancelled => Some(JobStatus::Cancelled),
+        proto::JobStatus::Paused => Some(JobStatus::Paused),
+    })
+}
+
+impl From<Job> for proto::Job {
+    fn from(job: Job) -> Self {
+        Self {
+            id: job.id,
+            title: job.title,
+            status: proto::JobStatus::from(job.status) as i32,
+            created_at: Some(timestamp(job.created_at)),
+            plan_yaml: job.plan_yaml,
+            user_prompt: job.user_prompt,
+            cloned_from: job.cloned_from,
+            summary_json: job.summary_json,
+        }
+    }
+}
+
+impl From<JobProgress> for proto::JobProgress {
+    fn from(progress: JobProgress) -> Self {
+        use proto::job_progress::Event;
+
+        let event = match progress.event {
+            ProgressEvent::Stage { stage, total_urls } => Event::Stage(proto::StageChanged {
+                stage: match stage {
+                    JobStage::Queued => proto::JobStage::Queued,
+                    JobStage::Fetching => proto::JobStage::Fetching,
+                    JobStage::Storing => proto::JobStage::Storing,
+                    JobStage::Retrying => proto::JobStage::Retrying,
+                } as i32,
+                total_urls: total_urls.map(|total| total as u32),
+            }),
+            ProgressEvent::UrlProcessed { url, status_code, items, error } => Event::UrlProcessed(proto::UrlProcessed {
+                url,
+                status_code: status_code.map(u32::from),
+                items: items as u32,
+                error,
+            }),
+            ProgressEvent::Items { total } => Event::Items(proto::ItemCount { total: total as u64 }),
+            ProgressEvent::Error { message } => Event::Error(message),
+            ProgressEvent::Finished { status } => Event::Finished(proto::JobStatus::from(status) as i32),
+        };
+        Self {
+            job_id: progress.job_id,
+            at: Some(timestamp(progress.at)),
+            event: Some(event),
+        }
+    }
+}
+
+fn issue(path: String, message: String) -> proto::Issue {
+    proto::Issue { path, message }
+}
+
+type ProgressStream = Pin<Box<dyn Stream<Item = Result<proto::JobProgress, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl JobService for JobApi {
+    async fn validate_plan(
+        &self,
+        request: Request<proto::ValidatePlanRequest>,
+    ) -> Result<Response<proto::ValidatePlanResponse>, Status> {
+        self.admit(&request, Method::POST, "/api/v1/plans/validate").await?;
+        let plan = parse_plan(&request.get_ref().plan)?;
+
+        let errors = self.app.validate_dsl_detailed(&plan);
+        Ok(Response::new(proto::ValidatePlanResponse {
+            valid: errors.is_empty(),
+            errors: errors.into_iter().map(|e| issue(e.path, e.message)).collect(),
+            warnings: self.app.lint_dsl(&plan).into_iter().map(|w| issue(w.path, w.message)).collect(),
+        }))
+    }
+
+    async fn submit_job(
+        &self,
+        request: Request<proto::SubmitJobRequest>,
+    ) -> Result<Response<proto::SubmitJobResponse>, Status> {
+        let (caller, key) = self.admit(&request, Method::POST, "/api/v1/jobs").await?;
+        let plan = parse_plan(&request.get_ref().plan)?;
+        info!("gRPC: Submitting scraping job for domain: {}", plan.target.domain);
+
+        let errors = self.app.validate_dsl_detailed(&plan);
+        if !errors.is_empty() {
+            let messages: Vec<String> = errors.iter().map(|e| format!("{}: {}", e.path, e.message)).collect();
+            return Err(Status::invalid_argument(format!("Invalid plan: {}", messages.join("; "))));
+        }
+        if let Err(exceeded) = self.limits.reserve_job(&key.0) {
+            return Err(Status::resource_exhausted(exceeded.to_string()));
+        }
+
+        let variables = request.get_ref().variables.clone();
+        let (job_id, run_plan) = self.app.create_scraping_job(&plan, &variables, caller.user_id()).await
+            .map_err(|e| failed(Code::InvalidArgument, "Failed to create job", &e))?;
+
+        let app = self.app.clone();
+        let limits = self.limits.clone();
+        let run_id = job_id.clone();
+        tokio::spawn(async move {
+            if let Err(e) = app.run_scraping_job(&run_id, run_plan).await {
+                error!("gRPC: Scraping job {} failed: {}", run_id, e);
+            }
+            record_rows(&app, &limits, &key, &run_id).await;
+        });
+        Ok(Response::new(proto::SubmitJobResponse { job_id }))
+    }
+
+    async fn list_jobs(
+        &self,
+        request: Request<proto::ListJobsRequest>,
+    ) -> Result<Response<proto::ListJobsResponse>, Status> {
+        let (caller, _) = self.admit(&request, Method::GET, "/api/v1/jobs").await?;
+        let query = request.get_ref();
+
+        let filter = JobFilter {
+            status: query.status.map(job_status).transpose()?.flatten(),
+            tag: query.tag.clone(),
+            domain: query.domain.clone(),
+            created_after: query.since.as_ref().map(date_time).transpose()?,
+            created_before: query.before.as_ref().map(date_time).transpose()?,
+            text: query.search.clone(),
+            owner: caller.visible_owner(),
+        };
+        let limit = match query.limit {
+            0 => 50,
+            limit => (limit as usize).min(1000),
+        };
+
+        let jobs = self.app.list_jobs_filtered(&filter, limit).await
+            .map_err(|e| failed(Code::Internal, "Failed to list jobs", &e))?;
+        Ok(Response::new(proto::ListJobsResponse {
+            jobs: jobs.into_iter().map(proto::Job::from).collect(),
+        }))
+    }
+
+    async fn get_job(&self, request: Request<proto::GetJobRequest>) -> Result<Response<proto::Job>, Status> {
+        let job_id = &request.get_ref().job_id;
+        self.admit(&request, Method::GET, &format!("/api/v1/jobs/{}", job_id)).await?;
+
+        let job = self.app.get_job(job_id).await
+            .map_err(|e| failed(Code::NotFound, "Job not found", &e))?;
+        Ok(Response::new(job.into()))
+    }
+
+    async fn delete_job(
+        &self,
+        request: Request<proto::DeleteJobRequest>,
+    ) -> Result<Response<proto::DeleteJobResponse>, Status> {
+        let job_id = &request.get_ref().job_id;
+        self.admit(&request, Method::DELETE, &format!("/api/v1/jobs/{}", job_id)).await?;
+        info!("gRPC: Deleting job: {}", job_id);
+
+        self.app.get_job(job_id).await
+            .map_err(|e| failed(Code::NotFound, "Job not found", &e))?;
+        self.app.delete_job(job_id).await
+            .map_err(|e| failed(Code::FailedPrecondition, "Failed to delete job", &e))?;
+        Ok(Response::new(proto::DeleteJobResponse {}))
+    }
+
+    type StreamProgressStream = ProgressStream;
+
+    async fn stream_progress(
+        &self,
+        request: Request<proto::StreamProgressRequest>,
+    ) -> Result<Response<Self::StreamProgressStream>, Status> {
+        let job_id = &request.get_ref().job_id;
+        self.admit(&request, Method::GET, &format!("/api/v1/jobs/{}/events", job_id)).await?;
+
+        // Subscribe before reading the job so no event falls in between
+        let subscription = self.app.subscribe_job_progress(job_id);
+        let job = self.app.get_job(job_id).await
+            .map_err(|e| failed(Code::NotFound, "Job not found", &e))?;
+
+        if !matches!(job.status, JobStatus::Queued | JobStatus::Running) {
+            let finished = JobProgress {
+                job_id: job.id,
+                at: Utc::now(),
+                event: ProgressEvent::Finished { status: job.status },
+            };
+            let stream: ProgressStream = Box::pin(futures::stream::once(async move { Ok(finished.into()) }));
+            return Ok(Response::new(stream));
+        }
+
+        let stream = futures::stream::unfold(Some(subscription), |subscription| async move {
+            let mut subscription = subscription?;
+            let progress = subscription.next().await?;
+            let next = (!progress.is_final()).then_some(subscription);
+            Some((Ok(progress.into()), next))
+        });
+        let stream: ProgressStream = Box::pin(stream);
+        Ok(Response::new(stream))
+    }
+
+    async fn get_results(
+        &self,
+        request: Request<proto::GetResultsRequest>,
+    ) -> Result<Response<proto::ResultPage>, Status> {
+        let query = request.get_ref();
+        self.admit(&request, Method::GET, &format!("/api/v1/jobs/{}/results", query.job_id)).await?;
+        let limit = match query.limit {
+            0 => 100,
+            limit => (limit as usize).min(1000),
+        };
+
+        let page = self.app.tail_job_results(&query.job_id, query.after, limit).await
+            .map_err(|e| failed(Code::NotFound, "Job not found", &e))?;
+        Ok(Response::new(proto::ResultPage {
+            rows: page.rows.into_iter()
+                .map(|row| proto::ResultRow { row_idx: row.row_idx, data_json: row.data.to_string() })
+                .collect(),
+            next_after: page.next_after,
+            status: proto::JobStatus::from(page.status) as i32,
+            finished: page.finished,
+        }))
+    }
+
+    async fn export_job(
+        &self,
+        request: Request<proto::ExportJobRequest>,
+    ) -> Result<Response<proto::ExportJobResponse>, Status> {
+        let query = request.get_ref();
+        self.admit(&request, Method::POST, &format!("/api/v1/jobs/{}/export", query.job_id)).await?;
+        info!("gRPC: Exporting job {} in format {}", query.job_id, query.format);
+
+        let format: ExportFormat = query.format.parse()
+            .map_err(|_| Status::invalid_argument("Unsupported export format"))?;
+        let options = ExportOptions {
+            columns: query.columns.clone(),
+            rename_map: query.rename_map.clone().into_iter().collect(),
+            exclude_metadata: query.exclude_metadata,
+            ..ExportOptions::default()
+        };
+
+        let path = self.app.export_job_to_default_path(&query.job_id, format, &options).await
+            .map_err(|e| failed(Code::Internal, "Failed to export job", &e))?;
+        Ok(Response::new(proto::ExportJobResponse {
+            file_path: path.to_string_lossy().into_owned(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+    use proto::job_service_client::JobServiceClient;
+
+    #[tokio::test]
+    async fn test_plans_and_jobs_round_trip_over_grpc() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = crate::config::AppConfig::default();
+        config.database.path = dir.path().join("grpc.db");
+        let app = Arc::new(WinScrapeStudio::new(config.clone()).await.unwrap());
+        let service = JobApi::new(app, Arc::new(ApiToken(None)), Arc::new(ApiLimits::new(&config.api)));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(service.into_server())
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+        let mut client = JobServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+
+        let mut plan = DSLExamples::ecommerce_products();
+        let valid = client.validate_plan(proto::ValidatePlanRequest { plan: plan.to_yaml().unwrap() }).await.unwrap();
+        assert!(valid.get_ref().valid);
+
+        plan.rules.fields[1].selector.clear();
+        let invalid = client.validate_plan(proto::ValidatePlanRequest { plan: plan.to_json().unwrap() }).await.unwrap();
+        assert!(!invalid.get_ref().valid);
+        assert!(invalid.get_ref().errors.iter().any(|e| e.path == "rules.fields[1].selector"));
+
+        // Plans that would make the server fetch internal addresses are refused
+        let mut internal = DSLExamples::ecommerce_products();
+        internal.target.start_urls = vec!["http://169.254.169.254/latest/meta-data/".to_string()];
+        let refused = client.submit_job(proto::SubmitJobRequest {
+            plan: internal.to_yaml().unwrap(),
+            ..Default::default()
+        }).await.unwrap_err();
+        assert_eq!(refused.code(), Code::InvalidArgument);
+
+        let jobs = client.list_jobs(proto::ListJobsRequest::default()).await.unwrap();
+        assert!(jobs.get_ref().jobs.is_empty());
+        let missing = client.get_job(proto::GetJobRequest { job_id: "missing".to_string() }).await.unwrap_err();
+        assert_eq!(missing.code(), Code::NotFound);
+    }
+}
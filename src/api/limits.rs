@@ -0,0 +1,211 @@
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+use chrono::{NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::config::ApiConfig;
+
+/// Clients tracked before idle ones from earlier days are dropped
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Request rate limits and daily quotas of every API client
+///
+/// Users are told apart by their ID and everyone else by address. Usage lives
+/// in memory, so it starts over when the server restarts.
+pub struct ApiLimits {
+    /// Tokens added per second and bucket size
+    rate: Option<(f64, f64)>,
+    daily_jobs: Option<u32>,
+    daily_rows: Option<u64>,
+    clients: Mutex<HashMap<String, ClientUsage>>,
+}
+
+struct ClientUsage {
+    tokens: f64,
+    refilled: Instant,
+    /// UTC day the job and row counts belong to
+    day: NaiveDate,
+    jobs: u32,
+    rows: u64,
+}
+
+/// Outcome of the rate limit check of one request
+pub struct RateDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until a request would be allowed
+    pub retry_after: u64,
+}
+
+/// A daily quota that has been used up
+#[derive(Debug, thiserror::Error)]
+pub enum QuotaExceeded {
+    #[error("Daily job quota of {0} reached")]
+    Jobs(u32),
+    #[error("Daily row quota of {0} reached")]
+    Rows(u64),
+}
+
+/// Quota left to a client today
+pub struct QuotaUsage {
+    pub jobs_remaining: Option<u32>,
+    pub rows_remaining: Option<u64>,
+    /// Seconds until the quotas reset at UTC midnight
+    pub reset_after: u64,
+}
+
+impl ApiLimits {
+    pub fn new(config: &ApiConfig) -> Self {
+        let rate = config.rate_limit_per_sec
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| (rate, config.rate_limit_burst.max(1) as f64));
+        Self {
+            rate,
+            daily_jobs: config.daily_job_quota,
+            daily_rows: config.daily_row_quota,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether quota headers are sent and job submissions counted
+    pub fn has_quotas(&self) -> bool {
+        self.daily_jobs.is_some() || self.daily_rows.is_some()
+    }
+
+    /// Take a token from the client's bucket; `None` without a rate limit
+    pub fn check_rate(&self, key: &str) -> Option<RateDecision> {
+        let (per_sec, burst) = self.rate?;
+        let mut clients = self.clients.lock().unwrap();
+        let usage = self.usage_mut(&mut clients, key);
+
+        let now = Instant::now();
+        usage.tokens = (usage.tokens + now.duration_since(usage.refilled).as_secs_f64() * per_sec).min(burst);
+        usage.refilled = now;
+
+        let allowed = usage.tokens >= 1.0;
+        if allowed {
+            usage.tokens -= 1.0;
+        }
+        Some(RateDecision {
+            allowed,
+            limit: burst as u32,
+            remaining: usage.tokens.floor() as u32,
+            retry_after: if allowed { 0 } else { ((1.0 - usage.tokens) / per_sec).ceil() as u64 },
+        })
+    }
+
+    /// Count a job against the client's daily quota, unless a quota is used up
+    pub fn reserve_job(&self, key: &str) -> Result<(), QuotaExceeded> {
+        let mut clients = self.clients.lock().unwrap();
+        let (daily_jobs, daily_rows) = (self.daily_jobs, self.daily_rows);
+        let usage = self.usage_mut(&mut clients, key);
+
+        if let Some(limit) = daily_jobs.filter(|limit| usage.jobs >= *limit) {
+            return Err(QuotaExceeded::Jobs(limit));
+        }
+        if let Some(limit) = daily_rows.filter(|limit| usage.rows >= *limit) {
+            return Err(QuotaExceeded::Rows(limit));
+        }
+        usage.jobs += 1;
+        Ok(())
+    }
+
+    /// Count rows stored by one of the client's jobs
+    ///
+    /// A job started under the row quota runs to the end, so the count may
+    /// overshoot it; the next job is refused.
+    pub fn record_rows(&self, key: &str, rows: usize) {
+        let mut clients = self.clients.lock().unwrap();
+        self.usage_mut(&mut clients, key).rows += rows as u64;
+    }
+
+    /// What is left of the client's quotas today
+    pub fn quota(&self, key: &str) -> QuotaUsage {
+        let mut clients = self.clients.lock().unwrap();
+        let usage = self.usage_mut(&mut clients, key);
+        let now = Utc::now();
+        let midnight = (now.date_naive() + chrono::Days::new(1)).and_hms_opt(0, 0, 0).unwrap_or_default();
+        QuotaUsage {
+            jobs_remaining: self.daily_jobs.map(|limit| limit.saturating_sub(usage.jobs)),
+            rows_remaining: self.daily_rows.map(|limit| limit.saturating_sub(usage.rows)),
+            reset_after: (midnight - now.naive_utc()).num_seconds().max(0) as u64,
+        }
+    }
+
+    /// The client's usage, started over on a new day
+    fn usage_mut<'a>(&self, clients: &'a mut HashMap<String, ClientUsage>, key: &str) -> &'a mut ClientUsage {
+        let today = Utc::now().date_naive();
+        if clients.len() >= PRUNE_THRESHOLD && !clients.contains_key(key) {
+            clients.retain(|_, usage| usage.day == today);
+        }
+
+        let burst = self.rate.map_or(0.0, |(_, burst)| burst);
+        let usage = clients.entry(key.to_string()).or_insert_with(|| ClientUsage {
+            tokens: burst,
+            refilled: Instant::now(),
+            day: today,
+            jobs: 0,
+            rows: 0,
+        });
+        if usage.day != today {
+            usage.day = today;
+            usage.jobs = 0;
+            usage.rows = 0;
+        }
+        usage
+    }
+}
+
+impl RateDecision {
+    /// Add `X-RateLimit-*` headers, and `Retry-After` when refused
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        headers.insert(HeaderName::from_static("x-ratelimit-limit"), HeaderValue::from(self.limit));
+        headers.insert(HeaderName::from_static("x-ratelimit-remaining"), HeaderValue::from(self.remaining));
+        if !self.allowed {
+            headers.insert(actix_web::http::header::RETRY_AFTER, HeaderValue::from(self.retry_after));
+        }
+    }
+}
+
+impl QuotaUsage {
+    /// Add `X-Quota-*` headers for the configured quotas
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        if let Some(jobs) = self.jobs_remaining {
+            headers.insert(HeaderName::from_static("x-quota-jobs-remaining"), HeaderValue::from(jobs));
+        }
+        if let Some(rows) = self.rows_remaining {
+            headers.insert(HeaderName::from_static("x-quota-rows-remaining"), HeaderValue::from(rows));
+        }
+        headers.insert(HeaderName::from_static("x-quota-reset"), HeaderValue::from(self.reset_after));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_and_quotas_are_per_client() {
+        let limits = ApiLimits::new(&ApiConfig {
+            rate_limit_per_sec: Some(0.001),
+            rate_limit_burst: 2,
+            daily_job_quota: Some(1),
+            daily_row_quota: Some(10),
+            ..crate::config::AppConfig::default().api
+        });
+
+        assert!(limits.check_rate("a").unwrap().allowed);
+        assert!(limits.check_rate("a").unwrap().allowed);
+        let refused = limits.check_rate("a").unwrap();
+        assert!(!refused.allowed && refused.retry_after > 0);
+        assert!(limits.check_rate("b").unwrap().allowed);
+
+        limits.reserve_job("a").unwrap();
+        assert!(matches!(limits.reserve_job("a"), Err(QuotaExceeded::Jobs(1))));
+        limits.record_rows("b", 10);
+        assert!(matches!(limits.reserve_job("b"), Err(QuotaExceeded::Rows(10))));
+        assert_eq!(limits.quota("c").jobs_remaining, Some(1));
+    }
+}
@@ -0,0 +1,754 @@
+use serde_json::{json, Map, Value};
+use std::io::Read;
+use std::sync::OnceLock;
+
+/// Path of the served OpenAPI document
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// An operation of the API, as documented
+struct Operation {
+    method: &'static str,
+    /// Relative to `/api/v1`; `{name}` segments become path parameters
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    /// Component schema of the JSON request body
+    request: Option<&'static str>,
+    /// Query parameters as name, JSON type and description
+    query: &'static [(&'static str, &'static str, &'static str)],
+    /// Status and component schema of the successful response
+    response: (u16, &'static str),
+}
+
+const fn op(
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    request: Option<&'static str>,
+    response: (u16, &'static str),
+) -> Operation {
+    Operation { method, path, tag, summary, request, query: &[], response }
+}
+
+const JOB_LIST_QUERY: &[(&str, &str, &str)] = &[
+    ("limit", "integer", "Most jobs to return, up to 1000"),
+    ("status", "string", "queued, running, completed, failed, cancelled or paused"),
+    ("tag", "string", "Only jobs with this tag"),
+    ("domain", "string", "Only jobs for this domain"),
+    ("since", "string", "RFC 3339 timestamp or YYYY-MM-DD"),
+    ("before", "string", "RFC 3339 timestamp or YYYY-MM-DD, exclusive"),
+    ("search", "string", "Text in the title or prompt"),
+];
+
+const RESULT_PAGE_QUERY: &[(&str, &str, &str)] = &[
+    ("after", "integer", "Row index to continue after; the first page when omitted"),
+    ("limit", "integer", "Rows per page, 1 to 1000"),
+];
+
+const STATS_QUERY: &[(&str, &str, &str)] = &[
+    ("top", "integer", "Most frequent values to return per column"),
+];
+
+/// Every route of `configure_routes`, in the same order
+const OPERATIONS: &[Operation] = &[
+    op("post", "/generate-dsl", "plans", "Generate a plan from a description", Some("GenerateDSLRequest"), (200, "GenerateDSLResponse")),
+    op("post", "/execute-scraping", "jobs", "Run a plan and wait for the job to finish", Some("ExecuteScrapingRequest"), (200, "ExecuteScrapingResponse")),
+    op("get", "/dsl/schema", "plans", "JSON Schema of scrape plans", None, (200, "Object")),
+    op("post", "/dsl/validate", "plans", "Validate and lint a plan", Some("ScrapePlan"), (200, "ValidateDSLResponse")),
+    op("post", "/dsl/explain", "plans", "Describe a plan in plain language", Some("ExplainPlanRequest"), (200, "Object")),
+    op("post", "/dsl/simulate", "plans", "Check a plan and estimate its requests and duration, fetching only robots.txt", Some("ScrapePlan"), (200, "Simulation")),
+    op("post", "/dsl/refine", "plans", "Apply a follow-up instruction to a plan", Some("RefineDSLRequest"), (200, "GenerateDSLResponse")),
+    op("post", "/plans/validate", "plans", "Validate and lint a plan", Some("ScrapePlan"), (200, "ValidateDSLResponse")),
+    op("post", "/plans/generate", "plans", "Generate a checked candidate plan from a description", Some("GenerateDSLRequest"), (200, "GeneratePlanResponse")),
+    Operation { query: JOB_LIST_QUERY, ..op("get", "/jobs", "jobs", "List recent jobs", None, (200, "JobList")) },
+    op("post", "/jobs", "jobs", "Queue a job and run it in the background", Some("ExecuteScrapingRequest"), (202, "ExecuteScrapingResponse")),
+    op("get", "/jobs/{job_id}", "jobs", "Get a job", None, (200, "Job")),
+    op("delete", "/jobs/{job_id}", "jobs", "Delete a finished job and its results", None, (200, "Message")),
+    Operation { query: RESULT_PAGE_QUERY, ..op("get", "/jobs/{job_id}/results", "jobs", "Get a page of a job's results", None, (200, "ResultPage")) },
+    op("get", "/jobs/{job_id}/events", "jobs", "Stream a job's progress as server-sent events", None, (200, "JobProgress")),
+    op("get", "/jobs/{job_id}/ws", "jobs", "Stream a job's progress over a WebSocket", None, (101, "JobProgress")),
+    op("get", "/jobs/{job_id}/lineage", "jobs", "Jobs a job was cloned from", None, (200, "JobList")),
+    op("get", "/jobs/{job_id}/attempts", "jobs", "Runs of a job and how each ended, retries included", None, (200, "JobAttemptList")),
+    op("get", "/jobs/{job_id}/log", "jobs", "Download a job's log as a text file", None, (200, "Text")),
+    op("get", "/jobs/{job_id}/failures", "jobs", "URLs of a job that failed, grouped by reason", None, (200, "FailureReport")),
+    op("get", "/jobs/{job_id}/issues", "jobs", "Problems the job's last run met without failing, by kind, stage and field", None, (200, "IssueReport")),
+    op("post", "/jobs/{job_id}/rescrape-failed", "jobs", "Fetch a job's failed URLs again in the background, adding the new rows", None, (202, "Object")),
+    Operation { query: STATS_QUERY, ..op("get", "/jobs/{job_id}/stats", "jobs", "Per-column statistics of a job's results", None, (200, "Object")) },
+    op("get", "/jobs/{job_id}/tags", "tags", "Tags of a job", None, (200, "Object")),
+    op("post", "/jobs/{job_id}/tags", "tags", "Tag a job", Some("TagRequest"), (200, "Message")),
+    op("delete", "/jobs/{job_id}/tags/{tag}", "tags", "Remove a tag from a job", None, (200, "Message")),
+    op("get", "/tags", "tags", "Tags in use with their job counts", None, (200, "Object")),
+    op("get", "/events", "jobs", "Stream lifecycle events as server-sent events; users get those of their own jobs", None, (200, "WorkflowEvent")),
+    op("post", "/jobs/{job_id}/export", "export", "Export a job's results to a file", Some("ExportJobRequest"), (200, "Message")),
+    op("post", "/jobs/{job_id}/export/database", "export", "Export a job's results into the export database", Some("ExportDatabaseRequest"), (200, "Message")),
+    op("post", "/jobs/{job_id}/export/profile/{name}", "export", "Export a job with a saved profile", None, (200, "Message")),
+    op("get", "/export-profiles", "export", "List saved export profiles", None, (200, "ExportProfileList")),
+    op("post", "/export-profiles", "export", "Save an export profile", Some("ExportProfile"), (200, "Message")),
+    op("delete", "/export-profiles/{name}", "export", "Delete an export profile", None, (200, "Message")),
+    op("post", "/database/backup", "database", "Back up the job database", Some("DatabaseFileRequest"), (200, "Message")),
+    op("post", "/database/restore", "database", "Replace the job database with a backup", Some("DatabaseFileRequest"), (200, "Message")),
+    op("post", "/database/archive", "database", "Move results of old finished jobs into Parquet files", Some("ArchiveRequest"), (200, "Message")),
+    op("get", "/users", "users", "List API users (admins only)", None, (200, "UserList")),
+    op("post", "/users", "users", "Add an API user and issue their token (admins only)", Some("CreateUserRequest"), (201, "CreatedUser")),
+    op("delete", "/users/{user_id}", "users", "Remove an API user (admins only)", None, (200, "Message")),
+    op("get", "/i18n/report", "i18n", "Completeness of each language's translations compared with English", None, (200, "Object")),
+    op("get", "/i18n/{lang}/missing", "i18n", "Untranslated keys of a language with their English text", None, (200, "Object")),
+    op("get", "/health", "health", "Health check", None, (200, "Object")),
+    op("get", "/health/live", "health", "Liveness probe", None, (200, "Object")),
+    op("get", "/health/ready", "health", "Readiness probe with component statuses; 503 when a component is down", None, (200, "ReadinessReport")),
+];
+
+/// OpenAPI 3.1 document describing the API
+pub fn openapi_document() -> Value {
+    let mut paths = Map::new();
+    for operation in OPERATIONS {
+        let path = format!("/api/v1{}", operation.path);
+        let entry = paths.entry(path).or_insert_with(|| json!({}));
+        entry[operation.method] = operation_object(operation);
+    }
+
+    // Plan definitions move into the components so their references resolve
+    let mut plan = crate::dsl::schema::scrape_plan_schema();
+    let mut schemas = match plan.as_object_mut().and_then(|plan| plan.remove("$defs")) {
+        Some(Value::Object(defs)) => defs,
+        _ => Map::new(),
+    };
+    if let Some(plan) = plan.as_object_mut() {
+        plan.remove("$schema");
+        plan.remove("$id");
+    }
+    schemas.insert("ScrapePlan".to_string(), plan);
+    schemas.extend(api_schemas());
+    let mut schemas = Value::Object(schemas);
+    rewrite_refs(&mut schemas);
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "WinScrape Studio API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Generate scrape plans, run jobs and export their results."
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" }
+            }
+        },
+        "security": [{ "bearer": [] }]
+    })
+}
+
+fn operation_object(operation: &Operation) -> Value {
+    let mut parameters: Vec<Value> = operation.path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+        .collect();
+    parameters.extend(operation.query.iter().map(|(name, kind, description)| json!({
+        "name": name,
+        "in": "query",
+        "required": false,
+        "description": description,
+        "schema": { "type": kind }
+    })));
+
+    let (status, schema) = operation.response;
+    let content_type = if operation.path.ends_with("/events") {
+        "text/event-stream"
+    } else if operation.path.ends_with("/log") {
+        "text/plain"
+    } else {
+        "application/json"
+    };
+    let mut object = json!({
+        "tags": [operation.tag],
+        "summary": operation.summary,
+        "operationId": operation_id(operation),
+        "parameters": parameters,
+        "responses": {
+            status.to_string(): {
+                "description": "Success",
+                "content": { content_type: { "schema": schema_ref(schema) } }
+            },
+            "default": {
+                "description": "Error",
+                "content": { "application/json": { "schema": schema_ref("Message") } }
+            }
+        }
+    });
+    if let Some(request) = operation.request {
+        object["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema_ref(request) } }
+        });
+    }
+    if operation.tag == "health" {
+        object["security"] = json!([]);
+    } else {
+        object["responses"]["429"] = json!({
+            "description": "Rate limit or daily quota exceeded",
+            "headers": {
+                "Retry-After": { "description": "Seconds to wait before retrying", "schema": { "type": "integer" } }
+            },
+            "content": { "application/json": { "schema": schema_ref("Message") } }
+        });
+    }
+    object
+}
+
+/// `post_jobs_job_id_export` for `POST /jobs/{job_id}/export`
+fn operation_id(operation: &Operation) -> String {
+    let path: String = operation.path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let mut id = format!("{}_{}", operation.method, path.trim_matches('_'));
+    while id.contains("__") {
+        id = id.replace("__", "_");
+    }
+    id
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Point `#/$defs/...` references of the plan schema at the components
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match child {
+                    Value::String(target) if key == "$ref" => {
+                        if let Some(name) = target.strip_prefix("#/$defs/") {
+                            *target = format!("#/components/schemas/{}", name);
+                        }
+                    }
+                    _ => rewrite_refs(child),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
+
+/// Schemas of the API's own request and response bodies
+fn api_schemas() -> Map<String, Value> {
+    let export_options = json!({
+        "columns": { "type": "array", "items": { "type": "string" } },
+        "rename_map": { "type": "object", "additionalProperties": { "type": "string" } },
+        "exclude_metadata": { "type": "boolean" },
+        "date_format": { "type": ["string", "null"] },
+        "schema": { "type": "object", "additionalProperties": { "enum": ["string", "int", "float", "bool", "timestamp"] } },
+        "redact": { "type": "object", "additionalProperties": { "enum": ["hash", "mask", "drop", "patterns"] } },
+        "redact_salt": { "type": ["string", "null"] },
+        "max_rows_per_file": { "type": ["integer", "null"], "minimum": 1 },
+        "max_mb_per_file": { "type": ["integer", "null"], "minimum": 1 }
+    });
+    let with_options = |mut properties: Value| {
+        if let (Some(properties), Some(options)) = (properties.as_object_mut(), export_options.as_object()) {
+            properties.extend(options.clone());
+        }
+        properties
+    };
+
+    // Split in several literals to stay within `json!`'s recursion limit
+    let groups = [
+        json!({
+            "Object": { "type": "object" },
+            "Text": { "type": "string" },
+            "Message": {
+                "type": "object",
+                "properties": {
+                    "success": { "type": "boolean" },
+                    "message": { "type": "string" },
+                    "code": { "type": "string", "description": "Troubleshooting code of a failure, e.g. WSS-1003" },
+                    "hint": { "type": "string", "description": "What usually fixes the failure" }
+                }
+            },
+            "GenerateDSLRequest": {
+                "type": "object",
+                "required": ["description"],
+                "properties": { "description": { "type": "string" } }
+            },
+            "GenerateDSLResponse": {
+                "type": "object",
+                "properties": {
+                    "dsl": schema_ref("ScrapePlan"),
+                    "success": { "type": "boolean" },
+                    "message": { "type": "string" }
+                }
+            },
+            "GeneratePlanResponse": {
+                "type": "object",
+                "properties": {
+                    "plan": schema_ref("ScrapePlan"),
+                    "warnings": { "type": "array", "items": { "type": "object" } },
+                    "politeness_violations": { "type": "array", "items": { "type": "string" } },
+                    "confidence": { "type": ["number", "null"] }
+                }
+            },
+            "ExecuteScrapingRequest": {
+                "type": "object",
+                "required": ["dsl"],
+                "properties": {
+                    "dsl": schema_ref("ScrapePlan"),
+                    "variables": { "type": "object", "additionalProperties": { "type": "string" } }
+                }
+            },
+            "ExecuteScrapingResponse": {
+                "type": "object",
+                "properties": {
+                    "job_id": { "type": "string" },
+                    "success": { "type": "boolean" },
+                    "message": { "type": "string" }
+                }
+            },
+            "ValidateDSLResponse": {
+                "type": "object",
+                "properties": {
+                    "valid": { "type": "boolean" },
+                    "errors": { "type": "array", "items": { "type": "object" } },
+                    "warnings": { "type": "array", "items": { "type": "object" } }
+                }
+            },
+            "Simulation": {
+                "type": "object",
+                "properties": {
+                    "issues": { "type": "array", "items": { "type": "object" } },
+                    "lint": { "type": "array", "items": { "type": "object" } },
+                    "security": { "type": "object" },
+                    "execution": {
+                        "type": ["object", "null"],
+                        "properties": {
+                            "urls": { "type": "array", "items": { "type": "string" } },
+                            "skipped_urls": { "type": "array", "items": { "type": "object" } },
+                            "domains": { "type": "array", "items": { "type": "object" } },
+                            "concurrency": { "type": "integer" },
+                            "mean_delay_ms": { "type": "integer" },
+                            "requests": { "type": "integer" },
+                            "max_requests": { "type": "integer" },
+                            "estimated_duration_secs": { "type": "integer" }
+                        }
+                    }
+                }
+            },
+            "ExplainPlanRequest": {
+                "type": "object",
+                "required": ["dsl"],
+                "properties": {
+                    "dsl": schema_ref("ScrapePlan"),
+                    "language": { "type": ["string", "null"] }
+                }
+            },
+            "RefineDSLRequest": {
+                "type": "object",
+                "required": ["dsl", "instruction"],
+                "properties": {
+                    "dsl": schema_ref("ScrapePlan"),
+                    "instruction": { "type": "string" }
+                }
+            }
+        }),
+        json!({
+            "Job": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "title": { "type": "string" },
+                    "status": { "enum": ["Queued", "Running", "Completed", "Failed", "Cancelled", "Paused"] },
+                    "created_at": { "type": "string", "format": "date-time" },
+                    "plan_yaml": { "type": "string" },
+                    "user_prompt": { "type": "string" },
+                    "settings_json": { "type": ["string", "null"] },
+                    "cloned_from": { "type": ["string", "null"] },
+                    "summary_json": { "type": ["string", "null"] }
+                }
+            },
+            "JobList": { "type": "array", "items": schema_ref("Job") },
+            "ResultPage": {
+                "type": "object",
+                "properties": {
+                    "rows": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "row_idx": { "type": "integer" },
+                                "data": { "type": "object" }
+                            }
+                        }
+                    },
+                    "next_after": { "type": ["integer", "null"] },
+                    "status": { "enum": ["Queued", "Running", "Completed", "Failed", "Cancelled", "Paused"] },
+                    "finished": { "type": "boolean" }
+                }
+            },
+            "JobProgress": {
+                "type": "object",
+                "required": ["job_id", "at", "type"],
+                "properties": {
+                    "job_id": { "type": "string" },
+                    "at": { "type": "string", "format": "date-time" },
+                    "type": { "enum": ["stage", "url_processed", "items", "error", "finished"] },
+                    "stage": { "enum": ["queued", "fetching", "storing", "retrying"] },
+                    "total_urls": { "type": "integer" },
+                    "url": { "type": "string" },
+                    "status_code": { "type": ["integer", "null"] },
+                    "items": { "type": "integer" },
+                    "total": { "type": "integer" },
+                    "error": { "type": ["string", "null"] },
+                    "message": { "type": "string" },
+                    "status": { "enum": ["Queued", "Running", "Completed", "Failed", "Cancelled", "Paused"] }
+                }
+            },
+            "JobAttemptList": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "job_id": { "type": "string" },
+                        "attempt": { "type": "integer" },
+                        "started_at": { "type": "string", "format": "date-time" },
+                        "finished_at": { "type": "string", "format": "date-time" },
+                        "outcome": { "enum": ["completed", "partial", "failed", "cancelled"] },
+                        "urls": { "type": "integer" },
+                        "failed_urls": { "type": "integer" },
+                        "rows": { "type": "integer" },
+                        "error": { "type": ["string", "null"] }
+                    }
+                }
+            },
+            "FailureReport": {
+                "type": "object",
+                "properties": {
+                    "job_id": { "type": "string" },
+                    "total_urls": { "type": "integer" },
+                    "succeeded": { "type": "integer" },
+                    "failed": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "url": { "type": "string" },
+                                "success": { "type": "boolean" },
+                                "status_code": { "type": ["integer", "null"] },
+                                "error": { "type": ["string", "null"] },
+                                "items": { "type": "integer" },
+                                "attempts": { "type": "integer" },
+                                "fetched_at": { "type": "string", "format": "date-time" }
+                            }
+                        }
+                    },
+                    "reasons": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "reason": { "type": "string" },
+                                "count": { "type": "integer" }
+                            }
+                        }
+                    }
+                }
+            },
+            "IssueReport": {
+                "type": "object",
+                "properties": {
+                    "groups": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "kind": { "enum": ["selector_miss", "timeout", "blocked", "parse_error", "network", "script", "circuit_open", "rejected"] },
+                                "stage": { "type": "string" },
+                                "field": { "type": ["string", "null"] },
+                                "count": { "type": "integer" },
+                                "message": { "type": "string" },
+                                "urls": { "type": "array", "items": { "type": "string" } }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+        json!({
+            "WorkflowEvent": {
+                "type": "object",
+                "required": ["job_id", "at", "type"],
+                "properties": {
+                    "job_id": { "type": "string" },
+                    "at": { "type": "string", "format": "date-time" },
+                    "type": { "enum": ["job_created", "stage_changed", "item_scraped", "job_completed", "job_failed", "retry_scheduled", "job_stopped"] },
+                    "title": { "type": "string" },
+                    "stage": { "enum": ["queued", "fetching", "storing", "retrying"] },
+                    "row_idx": { "type": "integer" },
+                    "item": { "type": "object" },
+                    "rows": { "type": "integer" },
+                    "warnings": { "type": "integer" },
+                    "error": { "type": "string" },
+                    "code": { "type": "string" },
+                    "attempt": { "type": "integer" },
+                    "delay_ms": { "type": "integer" },
+                    "reason": { "type": "string" },
+                    "status": { "enum": ["Cancelled", "Paused"] }
+                }
+            },
+            "ExportJobRequest": {
+                "type": "object",
+                "required": ["job_id", "format"],
+                "properties": with_options(json!({
+                    "job_id": { "type": "string" },
+                    "format": { "enum": ["csv", "json", "xlsx", "parquet", "ndjson", "jsonl"] }
+                }))
+            },
+            "ExportDatabaseRequest": {
+                "type": "object",
+                "properties": with_options(json!({
+                    "table": { "type": ["string", "null"] }
+                }))
+            },
+            "ExportProfile": {
+                "type": "object",
+                "required": ["name"],
+                "properties": with_options(json!({
+                    "name": { "type": "string", "pattern": "^[A-Za-z0-9_-]{1,64}$" },
+                    "format": { "type": "string", "default": "csv" },
+                    "destination": {
+                        "type": "object",
+                        "required": ["kind"],
+                        "properties": {
+                            "kind": { "enum": ["output_directory", "directory", "database"] },
+                            "path": { "type": "string" },
+                            "table": { "type": ["string", "null"] }
+                        }
+                    },
+                    "compression": { "enum": ["gzip", "zstd", "brotli"] },
+                    "compression_level": { "type": "integer" }
+                }))
+            },
+            "ExportProfileList": { "type": "array", "items": schema_ref("ExportProfile") },
+            "TagRequest": {
+                "type": "object",
+                "required": ["tag"],
+                "properties": { "tag": { "type": "string" } }
+            },
+            "DatabaseFileRequest": {
+                "type": "object",
+                "required": ["path"],
+                "properties": { "path": { "type": "string" } }
+            },
+            "ReadinessReport": {
+                "type": "object",
+                "properties": {
+                    "status": { "enum": ["up", "degraded", "down"] },
+                    "checked_at": { "type": "string", "format": "date-time" },
+                    "components": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": { "type": "string" },
+                                "status": { "enum": ["up", "degraded", "down"] },
+                                "message": { "type": "string" },
+                                "duration_ms": { "type": "integer" }
+                            }
+                        }
+                    }
+                }
+            }
+        }),
+        json!({
+            "User": {
+                "type": "object",
+                "properties": {
+                    "id": { "type": "string" },
+                    "name": { "type": "string" },
+                    "is_admin": { "type": "boolean" },
+                    "created_at": { "type": "string", "format": "date-time" }
+                }
+            },
+            "UserList": { "type": "array", "items": schema_ref("User") },
+            "CreateUserRequest": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": { "type": "string" },
+                    "is_admin": { "type": "boolean", "default": false }
+                }
+            },
+            "CreatedUser": {
+                "type": "object",
+                "properties": {
+                    "success": { "type": "boolean" },
+                    "user": schema_ref("User"),
+                    "token": { "type": "string", "description": "Bearer token; shown only once" }
+                }
+            },
+            "ArchiveRequest": {
+                "type": "object",
+                "properties": { "older_than_days": { "type": ["integer", "null"] } }
+            }
+        }),
+    ];
+    groups.into_iter()
+        .filter_map(|group| match group {
+            Value::Object(group) => Some(group),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
+/// Swagger UI files served under `/docs`, with their content types
+const SWAGGER_UI_ASSETS: &[(&str, &str)] = &[
+    ("swagger-ui.css", "text/css; charset=utf-8"),
+    ("swagger-ui-bundle.js", "text/javascript; charset=utf-8"),
+];
+
+/// Directory of the built files in the vendored Swagger UI release
+const SWAGGER_UI_DIST: &str = "swagger-ui-5.17.14/dist";
+
+/// A Swagger UI file and its content type
+///
+/// The files come from the Swagger UI release embedded in the binary, so the
+/// page works offline and runs no script fetched from elsewhere.
+pub fn swagger_ui_asset(name: &str) -> Option<(&'static str, &'static [u8])> {
+    static ASSETS: OnceLock<Vec<(&'static str, &'static str, Vec<u8>)>> = OnceLock::new();
+    let assets = ASSETS.get_or_init(|| {
+        let release = std::io::Cursor::new(utoipa_swagger_ui_vendored::SWAGGER_UI_VENDORED);
+        let Ok(mut archive) = zip::ZipArchive::new(release) else {
+            return Vec::new();
+        };
+        SWAGGER_UI_ASSETS.iter()
+            .filter_map(|(name, content_type)| {
+                let mut file = archive.by_name(&format!("{}/{}", SWAGGER_UI_DIST, name)).ok()?;
+                let mut content = Vec::new();
+                file.read_to_end(&mut content).ok()?;
+                Some((*name, *content_type, content))
+            })
+            .collect()
+    });
+    assets.iter()
+        .find(|(asset, ..)| *asset == name)
+        .map(|(_, content_type, content)| (*content_type, content.as_slice()))
+}
+
+/// Swagger UI page for the served document
+///
+/// Its scripts and styles are served by [`swagger_ui_asset`].
+pub fn swagger_ui_page() -> String {
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>WinScrape Studio API</title>
+  <link rel="stylesheet" href="/docs/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="/docs/swagger-ui-bundle.js"></script>
+  <script>
+    window.onload = () => {{
+      window.ui = SwaggerUIBundle({{ url: "{}", dom_id: "#swagger-ui" }});
+    }};
+  </script>
+</body>
+</html>
+"##,
+        OPENAPI_PATH
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    match child {
+                        Value::String(target) if key == "$ref" => refs.push(target),
+                        _ => collect_refs(child, refs),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_every_reference_resolves() {
+        let document = openapi_document();
+        assert_eq!(document["paths"]["/api/v1/jobs/{job_id}/results"]["get"]["parameters"][0]["name"], "job_id");
+        assert!(document["paths"]["/api/v1/jobs"]["post"]["requestBody"].is_object());
+
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for target in refs {
+            let name = target.strip_prefix("#/components/schemas/").unwrap_or_else(|| panic!("unexpected reference {}", target));
+            assert!(document["components"]["schemas"].get(name).is_some(), "missing schema {}", name);
+        }
+    }
+
+    /// Method and path of each route registered in the `/api/v1` scope of `configure_routes`
+    fn registered_routes() -> Vec<(String, String)> {
+        let source = include_str!("../api.rs");
+        let start = source.find("web::scope(\"/api/v1\")").expect("API scope");
+        let scope = &source[start..start + source[start..].find(");").expect("end of API scope")];
+        scope.lines()
+            .filter_map(|line| {
+                let (path, rest) = line.trim().strip_prefix(".route(\"")?.split_once('"')?;
+                let method = rest.split_once("web::")?.1.split_once('(')?.0;
+                Some((method.to_string(), path.to_string()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let documented: Vec<(String, String)> = OPERATIONS.iter()
+            .map(|operation| (operation.method.to_string(), operation.path.to_string()))
+            .collect();
+        assert_eq!(documented, registered_routes());
+    }
+
+    #[actix_web::test]
+    async fn test_every_documented_operation_is_routed() {
+        use actix_web::http::{Method, StatusCode};
+        use actix_web::test::{call_service, init_service, TestRequest};
+
+        let app = init_service(actix_web::App::new().configure(super::super::configure_routes)).await;
+        for operation in OPERATIONS {
+            let path: Vec<&str> = operation.path.split('/')
+                .map(|segment| if segment.starts_with('{') { "x" } else { segment })
+                .collect();
+            let method = Method::from_bytes(operation.method.to_uppercase().as_bytes()).unwrap();
+            let request = TestRequest::default().method(method).uri(&format!("/api/v1{}", path.join("/"))).to_request();
+            // Without application state handlers fail, but only unrouted requests get these
+            let status = call_service(&app, request).await.status();
+            assert!(
+                status != StatusCode::NOT_FOUND && status != StatusCode::METHOD_NOT_ALLOWED,
+                "{} {} is not routed", operation.method, operation.path
+            );
+        }
+    }
+
+    #[test]
+    fn test_swagger_ui_is_embedded() {
+        let page = swagger_ui_page();
+        for (name, _) in SWAGGER_UI_ASSETS {
+            assert!(page.contains(&format!("/docs/{}", name)));
+            let (_, content) = swagger_ui_asset(name).unwrap_or_else(|| panic!("missing {}", name));
+            assert!(!content.is_empty());
+        }
+        assert!(!page.contains("https://"));
+        assert!(swagger_ui_asset("../Cargo.toml").is_none());
+    }
+}
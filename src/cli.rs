@@ -1,5 +1,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{info, error};
 
 mod core;
@@ -11,9 +13,38 @@ mod dsl;
 mod export;
 mod security;
 mod utils;
+mod error;
+mod logging;
+mod i18n;
+mod notifications;
+mod plugins;
+#[cfg(feature = "api")]
+mod api;
 
 use crate::core::WinScrapeStudio;
+use crate::core::bulk::{self, BulkAction};
+use crate::export::{ExportDestination, ExportOptions, ExportProfile};
+use crate::dsl::{LintWarning, ValidationErrors};
+use crate::security::SecurityError;
+use crate::security::output_filter::Redaction;
 use crate::config::AppConfig;
+use crate::storage::{JobFilter, JobStatus};
+use crate::utils::time_utils;
+use crate::scraper::recording::RecordingPaths;
+
+/// Print a line of text output; JSON output is printed once at the end
+macro_rules! say {
+    ($out:expr) => {
+        if $out.is_text() {
+            println!();
+        }
+    };
+    ($out:expr, $($arg:tt)*) => {
+        if $out.is_text() {
+            println!($($arg)*);
+        }
+    };
+}
 
 #[derive(Parser)]
 #[command(name = "wss-cli")]
@@ -28,6 +59,9 @@ struct Cli {
     
     #[arg(short, long, help = "Configuration file path")]
     config: Option<String>,
+    
+    #[arg(long, value_enum, default_value = "text", help = "Print results as text, or as one JSON document on stdout")]
+    output: OutputMode,
 }
 
 #[derive(Subcommand)]
@@ -45,18 +79,57 @@ enum Commands {
         
         #[arg(long, help = "Skip preview and approval (dangerous)")]
         auto_approve: bool,
+        
+        #[arg(long, conflicts_with = "auto_approve", help = "Show what the job would fetch and how long it would take, without fetching any page")]
+        simulate: bool,
     },
     
     /// List previous scraping jobs
     List {
         #[arg(short, long, help = "Number of jobs to show")]
         limit: Option<usize>,
+        
+        #[arg(long, help = "Only jobs with this status (queued, running, completed, failed, cancelled)")]
+        status: Option<JobStatus>,
+        
+        #[arg(short, long, help = "Only jobs with this tag")]
+        tag: Option<String>,
+        
+        #[arg(short, long, help = "Only jobs scraping this domain or its subdomains")]
+        domain: Option<String>,
+        
+        #[arg(long, value_parser = parse_date, help = "Only jobs created on or after this date (YYYY-MM-DD)")]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        
+        #[arg(long, value_parser = parse_date, help = "Only jobs created before this date (YYYY-MM-DD)")]
+        before: Option<chrono::DateTime<chrono::Utc>>,
+        
+        #[arg(short, long, help = "Only jobs whose title or prompt contains this text")]
+        search: Option<String>,
     },
     
+    /// Add or remove tags on a job
+    Tag {
+        #[arg(help = "Job ID")]
+        job_id: String,
+        
+        #[arg(required = true, help = "Tags")]
+        tags: Vec<String>,
+        
+        #[arg(short, long, help = "Remove the tags instead of adding them")]
+        remove: bool,
+    },
+    
+    /// List tags in use
+    Tags,
+    
     /// Show details of a specific job
     Show {
         #[arg(help = "Job ID")]
         job_id: String,
+        
+        #[arg(long, help = "List the user agent, delay and outcome of every request")]
+        requests: bool,
     },
     
     /// Re-run a previous job
@@ -68,23 +141,433 @@ enum Commands {
         output: Option<String>,
     },
     
+    /// Finish jobs paused by an interrupt or shutdown
+    Resume,
+    
+    /// List the URLs of a job that failed, grouped by reason
+    Failures {
+        #[arg(help = "Job ID")]
+        job_id: String,
+    },
+    
+    /// Fetch the failed URLs of a job again, adding the new rows to its results
+    Rescrape {
+        #[arg(help = "Job ID")]
+        job_id: String,
+    },
+    
+    /// Show which results changed between two runs
+    Diff {
+        #[arg(help = "Job ID of the earlier run")]
+        base_job_id: String,
+        
+        #[arg(help = "Job ID of the later run")]
+        job_id: String,
+        
+        #[arg(long, help = "Print the added, removed and changed rows")]
+        rows: bool,
+    },
+    
+    /// Propose item and field selectors for a page
+    Suggest {
+        #[arg(help = "URL of a sample page")]
+        url: String,
+    },
+    
+    /// Run a DSL file as a job, or keep checking it while you edit it
+    Run {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+        
+        #[arg(long = "var", help = "Variable value as NAME=VALUE (repeatable)", value_parser = parse_variable)]
+        vars: Vec<(String, String)>,
+        
+        #[arg(short, long, help = "Watch the file and act again each time it is saved")]
+        watch: bool,
+        
+        #[arg(long, value_enum, default_value = "validate", requires = "watch", help = "What to do on each change in watch mode")]
+        on_change: WatchAction,
+        
+        #[arg(long, value_name = "HAR_FILE", help = "Save every request and response of the job to a HAR file")]
+        record: Option<String>,
+        
+        #[arg(long, value_name = "HAR_FILE", help = "Answer requests from a recorded HAR file instead of the network")]
+        replay: Option<String>,
+    },
+    
+    /// Check a DSL file's selectors against sample pages without running a job
+    DryRun {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+    },
+    
+    /// Show the requests a DSL file would make and how long they would take, fetching only robots.txt
+    Simulate {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+    },
+    
+    /// Describe what a DSL file will do in plain language
+    Explain {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+        
+        #[arg(short, long, help = "Language code, e.g. en or ko")]
+        lang: Option<String>,
+    },
+    
+    /// Change a plan with a follow-up instruction, e.g. "add the rating field"
+    Refine {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+        
+        #[arg(help = "What to change")]
+        instruction: String,
+        
+        #[arg(short, long, help = "Write the refined plan here instead of printing it")]
+        output: Option<String>,
+    },
+    
+    /// Show or reset the items earlier runs of a plan saw (output.skip_seen)
+    Seen {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+        
+        #[arg(long, help = "Forget the seen items so the next run keeps every item")]
+        reset: bool,
+    },
+    
+    /// Check the configuration, data directories, database and optional components
+    Doctor {
+        #[arg(long, help = "Apply the suggested repair for each problem")]
+        repair: bool,
+    },
+    
     /// Validate a DSL file
     Validate {
-        #[arg(help = "Path to DSL file")]
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
         dsl_file: String,
     },
     
+    /// Print the JSON Schema for DSL files
+    Schema {
+        #[arg(short, long, help = "Write the schema to a file instead of stdout")]
+        output: Option<String>,
+    },
+    
+    /// Manage and run plan templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    
+    /// Manage the local plan library and .wssplan bundles
+    Plans {
+        #[command(subcommand)]
+        action: PlanAction,
+    },
+    
+    /// Manage saved export profiles that plans run after each job (output.export_profiles)
+    Profiles {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+    
+    /// Manage API users and their tokens
+    Users {
+        #[command(subcommand)]
+        action: UserAction,
+    },
+    
+    /// Check translations against English and export what is left to translate
+    Translations {
+        #[command(subcommand)]
+        action: TranslationAction,
+    },
+    
+    /// List the error codes and what fixes each
+    Errors {
+        #[arg(help = "Only this code, e.g. WSS-1003")]
+        code: Option<String>,
+    },
+    
     /// Export job results
     Export {
         #[arg(help = "Job ID")]
         job_id: String,
         
-        #[arg(short, long, help = "Output file path")]
-        output: String,
+        #[arg(short, long, help = "Output file path (default: export directory, named by the filename template)")]
+        output: Option<String>,
         
         #[arg(short, long, help = "Output format", value_enum)]
         format: OutputFormat,
+        
+        #[arg(long, value_delimiter = ',', help = "Columns to write, in order (default: all, alphabetically)")]
+        columns: Vec<String>,
+        
+        #[arg(long, help = "Column name as FIELD=NAME (repeatable)", value_parser = parse_variable)]
+        rename: Vec<(String, String)>,
+        
+        #[arg(long, help = "Leave out metadata columns such as _source_url")]
+        no_metadata: bool,
+        
+        #[arg(long, help = "strftime pattern for timestamps, e.g. \"%Y-%m-%d %H:%M\"")]
+        date_format: Option<String>,
+        
+        #[arg(long, help = "Redact a column as FIELD=hash|mask|drop|patterns (repeatable)", value_parser = parse_redaction)]
+        redact: Vec<(String, Redaction)>,
+        
+        #[arg(long, help = "Secret salt for hashed columns")]
+        redact_salt: Option<String>,
+        
+        #[arg(long, help = "Split into files of at most this many rows")]
+        max_rows_per_file: Option<usize>,
+        
+        #[arg(long, help = "Split into files of roughly at most this many megabytes")]
+        max_mb_per_file: Option<u64>,
+    },
+    
+    /// Write job results into a table of the export database (export.database_url)
+    ExportDb {
+        #[arg(help = "Job ID")]
+        job_id: String,
+        
+        #[arg(short, long, help = "Table name (default: the plan's domain)")]
+        table: Option<String>,
+        
+        #[arg(long, value_delimiter = ',', help = "Columns to write (default: all)")]
+        columns: Vec<String>,
+        
+        #[arg(long, help = "Column name as FIELD=NAME (repeatable)", value_parser = parse_variable)]
+        rename: Vec<(String, String)>,
+        
+        #[arg(long, help = "Leave out metadata columns such as _source_url")]
+        no_metadata: bool,
+    },
+    
+    /// Delete, export or re-run many jobs at once
+    Bulk {
+        #[arg(value_enum, help = "What to do with the jobs")]
+        action: BulkCommand,
+        
+        #[arg(required = true, help = "Job IDs")]
+        job_ids: Vec<String>,
+        
+        #[arg(short, long, help = "Export format", value_enum, default_value = "csv")]
+        format: OutputFormat,
     },
+    
+    /// Write a small redacted sample of a job's results for sharing
+    Sample {
+        #[arg(help = "Job ID")]
+        job_id: String,
+        
+        #[arg(short = 'n', long, default_value_t = crate::export::sample::DEFAULT_SAMPLE_ROWS, help = "Number of random rows")]
+        rows: usize,
+        
+        #[arg(long, value_delimiter = ',', help = "Columns to leave out")]
+        drop: Vec<String>,
+        
+        #[arg(long, value_delimiter = ',', help = "Columns whose values are shuffled between rows")]
+        shuffle: Vec<String>,
+        
+        #[arg(long, help = "Keep _-prefixed metadata columns such as _source_url")]
+        keep_metadata: bool,
+        
+        #[arg(long, help = "Seed for a reproducible sample")]
+        seed: Option<u64>,
+        
+        #[arg(short, long, help = "Output format", value_enum, default_value = "csv")]
+        format: OutputFormat,
+    },
+    
+    /// Copy the job database to a file, safely while jobs are running
+    Backup {
+        #[arg(help = "Backup file to write")]
+        path: String,
+    },
+    
+    /// Move results of old finished jobs from the database into Parquet files
+    Archive {
+        #[arg(long, help = "Archive jobs older than this many days (default: database.archive_after_days or 90)")]
+        older_than: Option<u32>,
+    },
+    
+    /// Replace the job database with a backup
+    Restore {
+        #[arg(help = "Backup file to restore")]
+        path: String,
+        
+        #[arg(short, long, help = "Don't ask for confirmation")]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplateAction {
+    /// List saved templates
+    List,
+    
+    /// Save a DSL file as a named template
+    Save {
+        #[arg(help = "Template name")]
+        name: String,
+        
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+        
+        #[arg(short, long, help = "Template description")]
+        description: Option<String>,
+    },
+    
+    /// Run a template with values for its variables
+    Run {
+        #[arg(help = "Template name")]
+        name: String,
+        
+        #[arg(long = "var", help = "Variable value as NAME=VALUE (repeatable)", value_parser = parse_variable)]
+        vars: Vec<(String, String)>,
+        
+        #[arg(short, long, help = "Output file path")]
+        output: Option<String>,
+        
+        #[arg(short, long, help = "Output format", value_enum)]
+        format: Option<OutputFormat>,
+    },
+    
+    /// Delete a template
+    Delete {
+        #[arg(help = "Template name")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    /// List saved export profiles
+    List,
+    
+    /// Save a profile from a YAML or JSON file, replacing one of the same name
+    Save {
+        #[arg(help = "Path to profile file")]
+        file: String,
+    },
+    
+    /// Export a job's results with a profile
+    Run {
+        #[arg(help = "Profile name")]
+        name: String,
+        
+        #[arg(help = "Job ID")]
+        job_id: String,
+    },
+    
+    /// Delete a profile
+    Delete {
+        #[arg(help = "Profile name")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum UserAction {
+    /// List API users
+    List,
+    
+    /// Add a user and print their token
+    Add {
+        #[arg(help = "User name")]
+        name: String,
+        
+        #[arg(long, help = "Let the user see every job and manage users, profiles and the database")]
+        admin: bool,
+    },
+    
+    /// Remove a user; their jobs are kept
+    Remove {
+        #[arg(help = "User ID")]
+        id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TranslationAction {
+    /// Show how complete each language is
+    Report {
+        #[arg(short, long, help = "Only this language code, e.g. ko")]
+        lang: Option<String>,
+        
+        #[arg(short, long, help = "List the missing and extra keys")]
+        verbose: bool,
+    },
+    
+    /// Write a language's untranslated keys with their English text, as a file to translate
+    Missing {
+        #[arg(help = "Language code, e.g. ko")]
+        lang: String,
+        
+        #[arg(short, long, help = "Write to a file instead of stdout")]
+        output: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlanAction {
+    /// List plans in the library
+    List {
+        #[arg(short, long, help = "Only show plans whose name contains this text")]
+        name: Option<String>,
+    },
+    
+    /// Add a DSL file to the library
+    Save {
+        #[arg(help = "Path to DSL file (YAML, JSON or TOML)")]
+        dsl_file: String,
+        
+        #[arg(short, long, help = "Plan name (defaults to the target domain)")]
+        name: Option<String>,
+        
+        #[arg(short, long, help = "Plan description")]
+        description: Option<String>,
+    },
+    
+    /// Import a .wssplan bundle into the library
+    Import {
+        #[arg(help = "Path to .wssplan bundle")]
+        bundle: String,
+    },
+    
+    /// Write a library plan or DSL file as a .wssplan bundle
+    Export {
+        #[arg(help = "Library plan ID or path to DSL file")]
+        source: String,
+        
+        #[arg(short, long, help = "Output bundle path")]
+        output: String,
+    },
+    
+    /// Remove a plan from the library
+    Delete {
+        #[arg(help = "Library plan ID")]
+        id: String,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone)]
+enum BulkCommand {
+    Delete,
+    Export,
+    Rerun,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy)]
+enum WatchAction {
+    /// Only validate and lint the plan
+    Validate,
+    /// Validate, then check the selectors against sample pages
+    Preview,
+    /// Validate, then run the plan as a job
+    Run,
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -93,20 +576,165 @@ enum OutputFormat {
     Json,
     Xlsx,
     Parquet,
+    Ndjson,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq)]
+enum OutputMode {
+    Text,
+    Json,
+}
+
+/// Exit codes scripts can rely on; clap exits with 2 on usage errors
+mod exit_code {
+    pub const SUCCESS: i32 = 0;
+    /// The command failed outright
+    pub const FAILURE: i32 = 1;
+    /// A plan has validation errors
+    pub const VALIDATION: i32 = 3;
+    /// A plan or input was refused by the security checks
+    pub const SECURITY: i32 = 4;
+    /// A scrape finished but some pages failed
+    pub const PARTIAL: i32 = 5;
+    /// Ctrl+C or SIGTERM stopped the command; running jobs were paused
+    pub const INTERRUPTED: i32 = 130;
+}
+
+/// Collects a command's result and prints it the way `--output` asks
+///
+/// In JSON mode nothing but one document goes to stdout, with logs on stderr:
+/// `{"status": "ok" | "partial" | "error", "data": ..., "warnings": [...], "error": {...}}`.
+/// `error` holds `kind` (`validation`, `security` or `failure`), `message`
+/// and, for validation errors, the `issues`.
+struct Output {
+    mode: OutputMode,
+    data: serde_json::Value,
+    warnings: Vec<String>,
+    partial: bool,
+}
+
+impl Output {
+    fn new(mode: OutputMode) -> Self {
+        Self {
+            mode,
+            data: serde_json::Value::Null,
+            warnings: Vec::new(),
+            partial: false,
+        }
+    }
+    
+    fn is_text(&self) -> bool {
+        self.mode == OutputMode::Text
+    }
+    
+    /// The command's result in JSON mode
+    fn set_data(&mut self, data: impl serde::Serialize) -> Result<()> {
+        self.data = serde_json::to_value(data)?;
+        Ok(())
+    }
+    
+    /// Note that the command only partly succeeded
+    fn partial(&mut self, warning: String) {
+        if self.is_text() {
+            eprintln!("warning: {}", warning);
+        }
+        self.warnings.push(warning);
+        self.partial = true;
+    }
+    
+    /// Print the result or error and return the exit code
+    fn finish(self, result: Result<()>) -> i32 {
+        let (code, error) = match &result {
+            Ok(()) if self.partial => (exit_code::PARTIAL, None),
+            Ok(()) => (exit_code::SUCCESS, None),
+            Err(e) => {
+                let (code, kind) = classify_error(e);
+                let error_code = crate::error::ErrorCode::of(e);
+                let mut error = serde_json::json!({
+                    "kind": kind,
+                    "message": format!("{:#}", e),
+                    "code": error_code.as_str(),
+                    "hint": error_code.hint(),
+                });
+                if let Some(errors) = e.chain().find_map(|cause| cause.downcast_ref::<ValidationErrors>()) {
+                    error["issues"] = serde_json::json!(errors.issues);
+                }
+                (code, Some(error))
+            }
+        };
+        
+        match self.mode {
+            OutputMode::Text => {
+                if let Err(e) = &result {
+                    let error_code = crate::error::ErrorCode::of(e);
+                    eprintln!("Error: {:#}", e);
+                    eprintln!("{}: {}", error_code, error_code.hint());
+                }
+            }
+            OutputMode::Json => {
+                let status = match code {
+                    exit_code::SUCCESS => "ok",
+                    exit_code::PARTIAL => "partial",
+                    _ => "error",
+                };
+                let document = serde_json::json!({
+                    "status": status,
+                    "data": self.data,
+                    "warnings": self.warnings,
+                    "error": error,
+                });
+                println!("{}", document);
+            }
+        }
+        code
+    }
+}
+
+/// Exit code and JSON `kind` of a failure
+fn classify_error(error: &anyhow::Error) -> (i32, &'static str) {
+    if error.chain().any(|cause| cause.is::<ValidationErrors>()) {
+        (exit_code::VALIDATION, "validation")
+    } else if error.chain().any(|cause| cause.is::<SecurityError>()) {
+        (exit_code::SECURITY, "security")
+    } else {
+        (exit_code::FAILURE, "failure")
+    }
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let cli = Cli::parse();
     
     // Initialize logging based on verbosity
     let log_level = if cli.verbose { "debug" } else { "info" };
     std::env::set_var("RUST_LOG", format!("winscrape_studio={}", log_level));
     
-    tracing_subscriber::fmt::init();
+    // Keep stdout for the JSON document
+    if cli.output == OutputMode::Json {
+        tracing_subscriber::fmt()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            .with_writer(std::io::stderr)
+            .init();
+    } else {
+        tracing_subscriber::fmt::init();
+    }
     
     info!("WinScrape Studio CLI v{}", env!("CARGO_PKG_VERSION"));
     
+    let mut out = Output::new(cli.output);
+    let result = run(cli, &mut out).await;
+    std::process::exit(out.finish(result));
+}
+
+async fn run(cli: Cli, out: &mut Output) -> Result<()> {
+    // The self-check has to work when the normal startup would fail
+    if let Commands::Doctor { repair } = cli.command {
+        return run_doctor(cli.config.as_deref(), repair, out).await;
+    }
+    if let Commands::Errors { code } = &cli.command {
+        return list_error_codes(code.as_deref(), out);
+    }
+    
     // Load configuration
     let config = if let Some(config_path) = cli.config {
         AppConfig::load_from_file(&config_path).await?
@@ -115,163 +743,1308 @@ async fn main() -> Result<()> {
     };
     
     // Initialize core application
-    let app = WinScrapeStudio::new(config).await?;
+    let app = Arc::new(WinScrapeStudio::new(config).await?);
     
     // Execute command
     match cli.command {
-        Commands::Scrape { description, output, format, auto_approve } => {
-            execute_scrape(&app, description, output, format, auto_approve).await?;
+        Commands::Scrape { description, simulate: true, .. } => {
+            let dsl = app.generate_dsl(&description).await?;
+            say!(out, "Generated DSL:");
+            say!(out, "{}", serde_yaml::to_string(&dsl)?);
+            simulate_plan(&app, &dsl, out).await?;
         }
-        Commands::List { limit } => {
-            list_jobs(&app, limit).await?;
+        Commands::Scrape { description, output, format, auto_approve, .. } => {
+            pause_on_interrupt(&app);
+            execute_scrape(&app, description, output, format, auto_approve, out).await?;
         }
-        Commands::Show { job_id } => {
-            show_job(&app, job_id).await?;
+        Commands::List { limit, status, tag, domain, since, before, search } => {
+            let filter = JobFilter {
+                status,
+                tag,
+                domain,
+                created_after: since,
+                created_before: before,
+                text: search,
+                owner: None,
+            };
+            list_jobs(&app, limit, &filter, out).await?;
+        }
+        Commands::Tag { job_id, tags, remove } => {
+            for tag in &tags {
+                if remove {
+                    if !app.remove_job_tag(&job_id, tag).await? {
+                        say!(out, "Job {} has no tag '{}'", job_id, tag);
+                    }
+                } else {
+                    app.add_job_tag(&job_id, tag).await?;
+                }
+            }
+            let tags = app.get_job_tags(&job_id).await?;
+            say!(out, "Tags of {}: {}", job_id, if tags.is_empty() { "(none)".to_string() } else { tags.join(", ") });
+            out.set_data(serde_json::json!({ "job_id": job_id, "tags": tags }))?;
+        }
+        Commands::Tags => {
+            let tags = app.list_tags().await?;
+            if tags.is_empty() {
+                say!(out, "No tags in use.");
+            }
+            for tag in &tags {
+                say!(out, "{:<30} {} jobs", tag.tag, tag.job_count);
+            }
+            out.set_data(tags)?;
+        }
+        Commands::Show { job_id, requests } => {
+            show_job(&app, job_id, requests, out).await?;
         }
         Commands::Rerun { job_id, output } => {
-            rerun_job(&app, job_id, output).await?;
+            pause_on_interrupt(&app);
+            rerun_job(&app, job_id, output, out).await?;
+        }
+        Commands::Resume => {
+            pause_on_interrupt(&app);
+            let resumed = app.resume_paused_jobs().await?;
+            say!(out, "Resumed {} paused jobs", resumed);
+            out.set_data(serde_json::json!({ "resumed": resumed }))?;
+        }
+        Commands::Failures { job_id } => {
+            let report = app.get_failure_report(&job_id).await?;
+            say!(out, "URLs: {} ({} succeeded, {} failed)", report.total_urls, report.succeeded, report.failed.len());
+            for reason in &report.reasons {
+                say!(out, "  {:<6} {}", reason.count, reason.reason);
+            }
+            for url in &report.failed {
+                let attempts = if url.attempts > 1 { format!(" after {} attempts", url.attempts) } else { String::new() };
+                say!(out, "{} [{}]{}", url.url, url.failure_reason(), attempts);
+            }
+            out.set_data(report)?;
+        }
+        Commands::Rescrape { job_id } => {
+            pause_on_interrupt(&app);
+            let report = app.rescrape_failed(&job_id).await?;
+            say!(out, "Fetched {} failed URLs again: {} still failing, {} rows added", report.urls, report.still_failed, report.rows_added);
+            out.set_data(report)?;
+        }
+        Commands::Diff { base_job_id, job_id, rows } => {
+            diff_jobs(&app, base_job_id, job_id, rows, out).await?;
+        }
+        Commands::Suggest { url } => {
+            suggest_selectors(&app, url, out).await?;
+        }
+        Commands::Run { dsl_file, vars, watch, on_change, record, replay } => {
+            let variables: HashMap<String, String> = vars.into_iter().collect();
+            let recording = RecordingPaths { record, replay };
+            if watch {
+                watch_plan(&app, &dsl_file, &variables, &recording, on_change, out.mode).await?;
+            } else {
+                pause_on_interrupt(&app);
+                run_plan(&app, &dsl_file, &variables, &recording, WatchAction::Run, out).await?;
+            }
+        }
+        Commands::DryRun { dsl_file } => {
+            dry_run(&app, dsl_file, out).await?;
+        }
+        Commands::Simulate { dsl_file } => {
+            let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+            simulate_plan(&app, &dsl, out).await?;
         }
+        Commands::Explain { dsl_file, lang } => {
+            explain_dsl(&app, dsl_file, lang, out).await?;
+        }
+        Commands::Refine { dsl_file, instruction, output } => {
+            refine_dsl(&app, dsl_file, instruction, output, out).await?;
+        }
+        Commands::Seen { dsl_file, reset } => {
+            let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+            if reset {
+                let removed = app.clear_seen_items(&dsl).await?;
+                say!(out, "Forgot {} seen items.", removed);
+                out.set_data(serde_json::json!({ "removed": removed }))?;
+            } else {
+                let count = app.seen_item_count(&dsl).await?;
+                say!(out, "{} items seen by earlier runs.", count);
+                if !dsl.output.skip_seen {
+                    say!(out, "The plan does not set output.skip_seen, so runs keep them anyway.");
+                }
+                out.set_data(serde_json::json!({ "seen": count, "skip_seen": dsl.output.skip_seen }))?;
+            }
+        }
+        Commands::Doctor { .. } | Commands::Errors { .. } => unreachable!("handled before startup"),
         Commands::Validate { dsl_file } => {
-            validate_dsl(&app, dsl_file).await?;
+            validate_dsl(&app, dsl_file, out).await?;
+        }
+        Commands::Schema { output } => {
+            print_schema(output, out)?;
+        }
+        Commands::Template { action } => {
+            handle_template(&app, action, out).await?;
+        }
+        Commands::Plans { action } => {
+            handle_plans(&app, action, out).await?;
+        }
+        Commands::Profiles { action } => {
+            handle_profiles(&app, action, out).await?;
+        }
+        Commands::Users { action } => {
+            handle_users(&app, action, out).await?;
+        }
+        Commands::Translations { action } => {
+            handle_translations(&app, action, out)?;
+        }
+        Commands::Export {
+            job_id, output, format, columns, rename, no_metadata, date_format,
+            redact, redact_salt, max_rows_per_file, max_mb_per_file,
+        } => {
+            let options = ExportOptions {
+                columns,
+                rename_map: rename.into_iter().collect(),
+                exclude_metadata: no_metadata,
+                date_format,
+                redact: redact.into_iter().collect(),
+                redact_salt,
+                max_rows_per_file,
+                max_mb_per_file,
+                ..Default::default()
+            };
+            export_job(&app, job_id, output, format, &options, out).await?;
+        }
+        Commands::ExportDb { job_id, table, columns, rename, no_metadata } => {
+            let options = ExportOptions {
+                columns,
+                rename_map: rename.into_iter().collect(),
+                exclude_metadata: no_metadata,
+                ..Default::default()
+            };
+            let report = app.export_job_to_database(&job_id, table.as_deref(), &options).await?;
+            if report.created {
+                say!(out, "Created table {}", report.table);
+            }
+            if !report.columns_added.is_empty() {
+                say!(out, "Added columns: {}", report.columns_added.join(", "));
+            }
+            say!(out, "Wrote {} rows of job {} to {}", report.rows_written, job_id, report.table);
+            if report.rows_skipped > 0 {
+                say!(out, "Skipped {} rows with empty key columns", report.rows_skipped);
+            }
+            out.set_data(report)?;
+        }
+        Commands::Bulk { action, job_ids, format } => {
+            let action = match action {
+                BulkCommand::Delete => BulkAction::Delete,
+                BulkCommand::Export => BulkAction::Export { format: convert_format(format) },
+                BulkCommand::Rerun => BulkAction::Rerun,
+            };
+            run_bulk(app, action, job_ids, out).await?;
+        }
+        Commands::Sample { job_id, rows, drop, shuffle, keep_metadata, seed, format } => {
+            let options = crate::export::sample::SampleOptions {
+                rows,
+                drop_columns: drop,
+                shuffle_columns: shuffle,
+                keep_metadata,
+                seed,
+            };
+            let path = app.create_shareable_sample(&job_id, &options, convert_format(format)).await?;
+            say!(out, "Shareable sample of job {} written to: {}", job_id, path.display());
+            out.set_data(serde_json::json!({ "job_id": job_id, "path": path }))?;
+        }
+        Commands::Backup { path } => {
+            let info = app.backup_database(&path).await?;
+            say!(
+                out,
+                "Backed up {} jobs and {} results to {} ({} bytes)",
+                info.job_count, info.result_count, info.path.display(), info.size_bytes
+            );
+            out.set_data(info)?;
+        }
+        Commands::Archive { older_than } => {
+            let report = app.archive_old_results(older_than).await?;
+            if report.jobs_archived == 0 {
+                say!(out, "No results to archive.");
+            } else {
+                say!(
+                    out,
+                    "Archived {} rows of {} jobs ({} bytes of Parquet)",
+                    report.rows_archived, report.jobs_archived, report.bytes_written
+                );
+            }
+            out.set_data(report)?;
         }
-        Commands::Export { job_id, output, format } => {
-            export_job(&app, job_id, output, format).await?;
+        Commands::Restore { path, yes } => {
+            if !yes && !confirm(out, &format!("Replace all jobs and results with the contents of {}?", path), "--yes")? {
+                say!(out, "Restore cancelled.");
+                return Ok(());
+            }
+            let (info, previous) = app.restore_database(&path).await?;
+            say!(out, "Restored {} jobs and {} results from {}", info.job_count, info.result_count, path);
+            say!(out, "The previous database was saved to {}", previous.display());
+            out.set_data(serde_json::json!({ "restored": info, "previous": previous }))?;
         }
     }
     
     Ok(())
 }
 
+/// Ask a yes/no question; JSON output can't ask, so the flag that skips it is required
+fn confirm(out: &Output, question: &str, skip_flag: &str) -> Result<bool> {
+    if !out.is_text() {
+        return Err(anyhow::anyhow!("Pass {} to run this with --output json", skip_flag));
+    }
+    println!("{} (y/N): ", question);
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_lowercase().starts_with('y'))
+}
+
 async fn execute_scrape(
     app: &WinScrapeStudio,
     description: String,
     output: Option<String>,
     format: Option<OutputFormat>,
     auto_approve: bool,
+    out: &mut Output,
 ) -> Result<()> {
     info!("Processing scraping request: {}", description);
     
     // Generate DSL from natural language
     let dsl = app.generate_dsl(&description).await?;
-    println!("Generated DSL:");
-    println!("{}", serde_yaml::to_string(&dsl)?);
+    say!(out, "Generated DSL:");
+    say!(out, "{}", serde_yaml::to_string(&dsl)?);
     
     // Validate and preview
     let preview = app.validate_and_preview(&dsl).await?;
-    println!("\nPreview (first 10 rows):");
+    say!(out, "\nPreview (first 10 rows):");
     for (i, row) in preview.iter().enumerate().take(10) {
-        println!("{}: {:?}", i + 1, row);
+        say!(out, "{}: {:?}", i + 1, row);
     }
     
-    if !auto_approve {
-        println!("\nProceed with full scraping? (y/N): ");
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        if !input.trim().to_lowercase().starts_with('y') {
-            println!("Scraping cancelled.");
-            return Ok(());
-        }
+    if !auto_approve && !confirm(out, "\nProceed with full scraping?", "--auto-approve")? {
+        say!(out, "Scraping cancelled.");
+        return Ok(());
     }
     
     // Execute full scraping
-    let job_id = app.execute_scraping(&dsl).await?;
-    println!("Scraping completed. Job ID: {}", job_id);
+    let job_id = app.execute_scraping(&dsl, &HashMap::new()).await?;
+    say!(out, "Scraping completed. Job ID: {}", job_id);
     
     // Export if requested
-    if let Some(output_path) = output {
-        let export_format = format.unwrap_or(OutputFormat::Csv);
-        app.export_job(&job_id, &output_path, convert_format(export_format)).await?;
-        println!("Results exported to: {}", output_path);
-    }
+    let exported = match output {
+        Some(output_path) => {
+            let export_format = format.unwrap_or(OutputFormat::Csv);
+            app.export_job(&job_id, &output_path, convert_format(export_format), &ExportOptions::default()).await?;
+            say!(out, "Results exported to: {}", output_path);
+            Some(output_path)
+        }
+        None => None,
+    };
     
+    let rows = check_scrape(app, &job_id, out).await?;
+    out.set_data(serde_json::json!({
+        "job_id": job_id,
+        "plan": dsl,
+        "preview": preview,
+        "rows": rows,
+        "exported_to": exported,
+    }))?;
     Ok(())
 }
 
-async fn list_jobs(app: &WinScrapeStudio, limit: Option<usize>) -> Result<()> {
-    let jobs = app.list_jobs(limit.unwrap_or(20)).await?;
+/// Mark a finished job whose pages partly failed; fail if every page failed
+///
+/// Returns the number of rows the job stored.
+async fn check_scrape(app: &WinScrapeStudio, job_id: &str, out: &mut Output) -> Result<usize> {
+    let job = app.get_job(job_id).await?;
+    let summary = job.summary_json.as_deref()
+        .and_then(|json| serde_json::from_str::<crate::scraper::ScrapeSummary>(json).ok())
+        .unwrap_or_default();
     
-    println!("Recent scraping jobs:");
-    println!("{:<36} {:<20} {:<15} {:<20}", "Job ID", "Title", "Status", "Created");
-    println!("{}", "-".repeat(91));
+    if summary.pages_failed > 0 {
+        if summary.pages_scraped == 0 {
+            return Err(anyhow::anyhow!("Every page of job {} failed", job_id));
+        }
+        out.partial(format!(
+            "{} of {} pages of job {} failed",
+            summary.pages_failed, summary.pages_failed + summary.pages_scraped, job_id
+        ));
+    }
+    if summary.warning_count() > 0 {
+        say!(out, "Completed with {} warnings; see `show {}` for the report", summary.warning_count(), job_id);
+    }
+    app.get_job_result_count(job_id).await
+}
+
+async fn list_jobs(app: &WinScrapeStudio, limit: Option<usize>, filter: &JobFilter, out: &mut Output) -> Result<()> {
+    let jobs = app.list_jobs_filtered(filter, limit.unwrap_or(20)).await?;
+    let ids: Vec<String> = jobs.iter().map(|job| job.id.clone()).collect();
+    let tags = app.get_tags_for_jobs(&ids).await?;
+    
+    if filter.is_empty() {
+        say!(out, "Recent scraping jobs:");
+    } else {
+        say!(out, "Matching scraping jobs:");
+    }
+    say!(out, "{:<36} {:<20} {:<15} {:<20} {}", "Job ID", "Title", "Status", "Created", "Tags");
+    say!(out, "{}", "-".repeat(100));
     
-    for job in jobs {
-        println!(
-            "{:<36} {:<20} {:<15} {:<20}",
+    for job in &jobs {
+        say!(
+            out,
+            "{:<36} {:<20} {:<15} {:<20} {}",
             job.id,
             job.title.chars().take(20).collect::<String>(),
             job.status,
-            job.created_at.format("%Y-%m-%d %H:%M:%S")
+            time_utils::format_local(&job.created_at, "%Y-%m-%d %H:%M:%S"),
+            tags.get(&job.id).map(|tags| tags.join(", ")).unwrap_or_default()
         );
     }
     
+    let jobs: Vec<serde_json::Value> = jobs.iter()
+        .map(|job| serde_json::json!({
+            "id": job.id,
+            "title": job.title,
+            "status": job.status,
+            "created_at": job.created_at,
+            "tags": tags.get(&job.id).cloned().unwrap_or_default(),
+        }))
+        .collect();
+    out.set_data(jobs)?;
     Ok(())
 }
 
-async fn show_job(app: &WinScrapeStudio, job_id: String) -> Result<()> {
+fn parse_date(text: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    time_utils::parse_local_date(text)
+        .ok_or_else(|| format!("'{}' is not a date; use YYYY-MM-DD", text))
+}
+
+async fn show_job(app: &WinScrapeStudio, job_id: String, requests: bool, out: &mut Output) -> Result<()> {
     let job = app.get_job(&job_id).await?;
+    let mut lineage = Vec::new();
+    let mut summary_data = None;
+    
+    say!(out, "Job Details:");
+    say!(out, "ID: {}", job.id);
+    say!(out, "Title: {}", job.title);
+    say!(out, "Status: {}", job.status);
+    say!(out, "Created: {}", job.created_at);
+    say!(out, "User Prompt: {}", job.user_prompt);
+    
+    if job.cloned_from.is_some() {
+        lineage = app.get_job_lineage(&job.id).await?;
+        let chain: Vec<String> = lineage.iter()
+            .map(|j| format!("{} ({})", j.id, j.title))
+            .collect();
+        say!(out, "Cloned From: {}", chain.join(" <- "));
+    }
+    
+    if let Some(summary) = job.summary_json.as_deref()
+        .and_then(|json| serde_json::from_str::<crate::scraper::ScrapeSummary>(json).ok())
+    {
+        summary_data = Some(summary.clone());
+        say!(out, "\nSummary:");
+        say!(
+            out,
+            "Pages: {} scraped, {} failed, {} skipped",
+            summary.pages_scraped, summary.pages_failed, summary.pages_skipped
+        );
+        for skipped in &summary.skipped_urls {
+            say!(out, "  skipped {}: {}", skipped.url, skipped.reason);
+        }
+        say!(out, "Items: {} matched, {} kept", summary.items_matched, summary.items_kept);
+        say!(out, "Missing Required Fields: {} items ({} dropped)", summary.items_missing_required, summary.items_dropped);
+        say!(out, "Duplicates: {}", summary.duplicates);
+        if summary.previously_seen > 0 {
+            say!(out, "Seen in earlier runs: {}", summary.previously_seen);
+        }
+        if summary.items_dropped_by_script > 0 {
+            say!(out, "Dropped by post-process script: {}", summary.items_dropped_by_script);
+        }
+        
+        if !summary.issues.is_empty() {
+            say!(out, "\nWarnings: {}", summary.warning_count());
+            for group in summary.issues.sorted() {
+                let field = group.field.as_deref().map(|field| format!(" field {}", field)).unwrap_or_default();
+                say!(out, "  {} × {} ({}{}): {}", group.count, group.kind, group.stage, field, group.message);
+                for url in &group.urls {
+                    say!(out, "      {}", url);
+                }
+            }
+        }
+        
+        let stats = summary.request_stats();
+        if !stats.is_empty() {
+            say!(out, "\nRequests by user agent / proxy:");
+            for stat in &stats {
+                say!(
+                    out,
+                    "  {} {}: {} requests, {} failed, {} blocked ({:.1}%), avg delay {:.0}ms, avg rate limit wait {:.0}ms",
+                    stat.dimension, stat.value, stat.requests, stat.failures, stat.blocked,
+                    stat.blocked_percent(), stat.avg_delay_ms, stat.avg_rate_limit_wait_ms
+                );
+            }
+        }
+        
+        if requests {
+            say!(out, "\nRequests:");
+            for trace in &summary.requests {
+                let outcome = match (trace.error.as_ref().or(trace.skipped.as_ref()), trace.status_code) {
+                    (Some(error), _) => error.clone(),
+                    (None, Some(code)) => code.to_string(),
+                    (None, None) => "-".to_string(),
+                };
+                say!(
+                    out,
+                    "  {} [{}] ua={} proxy={} delay={}ms wait={}ms",
+                    trace.url, outcome, trace.user_agent,
+                    trace.proxy.as_deref().unwrap_or("direct"), trace.delay_ms, trace.rate_limit_wait_ms
+                );
+            }
+        }
+    }
+    
+    let attempts = app.get_job_attempts(&job.id).await?;
+    if attempts.len() > 1 {
+        say!(out, "\nAttempts:");
+        for attempt in &attempts {
+            say!(
+                out,
+                "  #{} {} at {}: {} of {} URLs failed, {} rows{}",
+                attempt.attempt, attempt.outcome, time_utils::format_local(&attempt.finished_at, "%Y-%m-%d %H:%M:%S"),
+                attempt.failed_urls, attempt.urls, attempt.rows,
+                attempt.error.as_deref().map(|e| format!(" ({})", e)).unwrap_or_default()
+            );
+        }
+    }
     
-    println!("Job Details:");
-    println!("ID: {}", job.id);
-    println!("Title: {}", job.title);
-    println!("Status: {}", job.status);
-    println!("Created: {}", job.created_at);
-    println!("User Prompt: {}", job.user_prompt);
-    println!("\nDSL Plan:");
-    println!("{}", job.plan_yaml);
+    say!(out, "\nDSL Plan:");
+    say!(out, "{}", job.plan_yaml);
     
-    if let Some(settings) = job.settings_json {
-        println!("\nSettings:");
-        println!("{}", settings);
+    if let Some(settings) = &job.settings_json {
+        say!(out, "\nSettings:");
+        say!(out, "{}", settings);
     }
     
+    if let Some(summary) = summary_data.as_mut().filter(|_| !requests) {
+        summary.requests.clear();
+    }
+    let lineage: Vec<&str> = lineage.iter().map(|j| j.id.as_str()).collect();
+    out.set_data(serde_json::json!({
+        "id": job.id,
+        "title": job.title,
+        "status": job.status,
+        "created_at": job.created_at,
+        "user_prompt": job.user_prompt,
+        "cloned_from": job.cloned_from,
+        "lineage": lineage,
+        "summary": summary_data,
+        "attempts": attempts,
+        "plan_yaml": job.plan_yaml,
+    }))?;
     Ok(())
 }
 
-async fn rerun_job(app: &WinScrapeStudio, job_id: String, output: Option<String>) -> Result<()> {
+/// Make Ctrl+C or SIGTERM pause the running job, keeping its rows, then quit
+///
+/// `wss-cli resume` or the next start of the GUI or API server finishes the job.
+fn pause_on_interrupt(app: &Arc<WinScrapeStudio>) {
+    let app = app.clone();
+    tokio::spawn(async move {
+        crate::core::shutdown::termination_signal().await;
+        eprintln!("\nInterrupted; pausing the running job...");
+        app.shutdown().await;
+        std::process::exit(exit_code::INTERRUPTED);
+    });
+}
+
+async fn rerun_job(app: &WinScrapeStudio, job_id: String, output: Option<String>, out: &mut Output) -> Result<()> {
     info!("Re-running job: {}", job_id);
     
     let new_job_id = app.rerun_job(&job_id).await?;
-    println!("Job re-run completed. New Job ID: {}", new_job_id);
+    say!(out, "Job re-run completed. New Job ID: {}", new_job_id);
+    
+    if let Some(output_path) = &output {
+        app.export_job(&new_job_id, output_path, crate::export::ExportFormat::Csv, &ExportOptions::default()).await?;
+        say!(out, "Results exported to: {}", output_path);
+    }
+    
+    let rows = check_scrape(app, &new_job_id, out).await?;
+    out.set_data(serde_json::json!({ "job_id": new_job_id, "rerun_of": job_id, "rows": rows, "exported_to": output }))?;
+    Ok(())
+}
+
+async fn diff_jobs(app: &WinScrapeStudio, base_job_id: String, job_id: String, rows: bool, out: &mut Output) -> Result<()> {
+    let changes = app.compare_jobs(&base_job_id, &job_id).await?;
+    
+    match &changes.hash_fields {
+        Some(fields) => say!(out, "Compared on fields: {}", fields.join(", ")),
+        None => say!(out, "Compared on all fields"),
+    }
+    say!(out, "Added: {}", changes.added.len());
+    say!(out, "Removed: {}", changes.removed.len());
+    say!(out, "Changed: {}", changes.changed.len());
+    say!(out, "Unchanged: {}", changes.unchanged);
+    
+    if rows {
+        for row in &changes.added {
+            say!(out, "+ {}", row);
+        }
+        for row in &changes.removed {
+            say!(out, "- {}", row);
+        }
+        for (before, after) in &changes.changed {
+            say!(out, "~ {}", before);
+            say!(out, "  {}", after);
+        }
+    }
+    
+    if rows {
+        out.set_data(&changes)?;
+    } else {
+        out.set_data(serde_json::json!({
+            "base_job_id": changes.base_job_id,
+            "job_id": changes.job_id,
+            "hash_fields": changes.hash_fields,
+            "added": changes.added.len(),
+            "removed": changes.removed.len(),
+            "changed": changes.changed.len(),
+            "unchanged": changes.unchanged,
+        }))?;
+    }
+    Ok(())
+}
+
+async fn suggest_selectors(app: &WinScrapeStudio, url: String, out: &mut Output) -> Result<()> {
+    let suggestions = app.suggest_selectors(&url).await?;
+    out.set_data(&suggestions)?;
+    
+    if suggestions.items.is_empty() {
+        say!(out, "No repeated item structure found on {}", url);
+        return Ok(());
+    }
+    
+    say!(out, "Item selectors:");
+    for item in &suggestions.items {
+        say!(out, "  {:.2}  {} ({} matches)", item.confidence, item.selector, item.matches);
+    }
+    
+    say!(out, "\nFields:");
+    for field in &suggestions.fields {
+        say!(
+            out,
+            "  {:.2}  {}: {} [{:?}] e.g. {}",
+            field.confidence, field.name, field.selector, field.extraction, field.samples.join(" | ")
+        );
+    }
+    
+    Ok(())
+}
+
+async fn dry_run(app: &WinScrapeStudio, dsl_file: String, out: &mut Output) -> Result<()> {
+    let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+    let report = app.dry_run(&dsl).await?;
+    out.set_data(serde_json::json!({ "report": report, "empty_required": report.empty_required() }))?;
+    
+    for page in &report.pages {
+        say!(out, "{} ({})", page.url, page.pattern);
+        if let Some(error) = &page.error {
+            say!(out, "  error: {}", error);
+            continue;
+        }
+        say!(out, "  status {}, {} items matched", page.status_code.unwrap_or_default(), page.items_matched);
+        
+        for field in &page.fields {
+            let samples: Vec<String> = field.samples.iter().map(|v| v.to_string()).collect();
+            say!(
+                out,
+                "  {}{}: {}/{} items, {} elements  {}",
+                field.name,
+                if field.required { "*" } else { "" },
+                field.items_matched,
+                page.items_matched,
+                field.elements_matched,
+                samples.join(" | ")
+            );
+            if let Some(error) = &field.error {
+                say!(out, "    error: {}", error);
+            }
+        }
+    }
+    
+    let empty = report.empty_required();
+    if !empty.is_empty() {
+        say!(out, "\nRequired fields that would be empty: {}", empty.join(", "));
+    }
+    
+    Ok(())
+}
+
+/// Print what a run of the plan would do, failing if it could not run
+async fn simulate_plan(app: &WinScrapeStudio, dsl: &crate::dsl::ScrapePlan, out: &mut Output) -> Result<()> {
+    let simulation = app.simulate(dsl).await?;
+    out.set_data(&simulation)?;
+    
+    for issue in &simulation.issues {
+        say!(out, "{} [{}] {}", issue.path, issue.code, issue.message);
+    }
+    for warning in &simulation.lint {
+        say!(out, "warning: {} [{}] {}", warning.path, warning.rule, warning.message);
+    }
+    for error in &simulation.security.errors {
+        say!(out, "security error: {}", error);
+    }
+    for warning in &simulation.security.warnings {
+        say!(out, "security warning: {}", warning);
+    }
+    if !simulation.issues.is_empty() {
+        return Err(ValidationErrors { issues: simulation.issues }.into());
+    }
+    
+    if let Some(execution) = &simulation.execution {
+        say!(out, "\nExecution plan ({} concurrent requests, {} ms mean delay):", execution.concurrency, execution.mean_delay_ms);
+        for domain in &execution.domains {
+            let crawl_delay = domain.crawl_delay_secs
+                .map(|secs| format!(", robots.txt crawl-delay {}s", secs))
+                .unwrap_or_default();
+            say!(out, "  {:<40} {} requests, {} ms apart{}", domain.domain, domain.requests, domain.delay_ms, crawl_delay);
+        }
+        for skipped in &execution.skipped_urls {
+            say!(out, "  skip {} ({})", skipped.url, skipped.reason);
+        }
+        say!(out, "Requests: {} (up to {} with retries)", execution.requests, execution.max_requests);
+        say!(
+            out,
+            "Estimated duration: {}",
+            crate::utils::format_duration(std::time::Duration::from_secs(execution.estimated_duration_secs))
+        );
+    }
+    if simulation.security.has_errors() {
+        return Err(anyhow::anyhow!("The plan failed {} security checks", simulation.security.errors.len()));
+    }
+    if !simulation.is_runnable() {
+        return Err(anyhow::anyhow!("Robots.txt disallows every target URL"));
+    }
+    Ok(())
+}
+
+async fn explain_dsl(app: &WinScrapeStudio, dsl_file: String, lang: Option<String>, out: &mut Output) -> Result<()> {
+    let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+    let language = match lang {
+        Some(code) => Some(
+            crate::i18n::Language::from_code(&code)
+                .ok_or_else(|| anyhow::anyhow!("Unsupported language: {}", code))?
+        ),
+        None => None,
+    };
+    
+    let explanation = app.explain_plan(&dsl, language);
+    say!(out, "{}", explanation.summary);
+    for detail in &explanation.details {
+        say!(out, "  - {}", detail);
+    }
+    
+    out.set_data(explanation)?;
+    Ok(())
+}
+
+async fn refine_dsl(app: &WinScrapeStudio, dsl_file: String, instruction: String, output: Option<String>, out: &mut Output) -> Result<()> {
+    let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+    let refined = app.refine_dsl(&dsl, &instruction).await?;
+    let yaml = refined.to_yaml()?;
+    
+    match &output {
+        Some(path) => {
+            tokio::fs::write(path, yaml).await?;
+            say!(out, "Refined plan written to: {}", path);
+        }
+        None => say!(out, "{}", yaml),
+    }
+    out.set_data(serde_json::json!({ "plan": refined, "written_to": output }))?;
+    
+    Ok(())
+}
+
+async fn run_doctor(config_path: Option<&str>, repair: bool, out: &mut Output) -> Result<()> {
+    use crate::core::self_check::{self, CheckSeverity};
+    
+    let config_path = config_path.map(std::path::PathBuf::from).unwrap_or_else(AppConfig::default_path);
+    let (mut config, mut report) = self_check::startup_check(Some(&config_path)).await;
+    
+    let mut repairs = Vec::new();
+    if repair {
+        let mut applied = Vec::new();
+        for action in report.problems.iter().filter_map(|p| p.repair.as_ref()) {
+            if applied.contains(action) {
+                continue;
+            }
+            let outcome = match self_check::apply_repair(&mut config, &config_path, action).await {
+                Ok(message) => format!("Repaired: {}", message),
+                Err(e) => format!("Repair failed ({}): {}", action.label(), e),
+            };
+            say!(out, "{}", outcome);
+            repairs.push(outcome);
+            applied.push(action.clone());
+        }
+        if !applied.is_empty() {
+            report = self_check::run_self_check(&config).await;
+        }
+    }
+    
+    out.set_data(serde_json::json!({ "healthy": report.is_healthy(), "report": report, "repairs": repairs }))?;
+    if report.is_healthy() {
+        say!(out, "All checks passed.");
+        return Ok(());
+    }
+    
+    for problem in &report.problems {
+        let severity = match problem.severity {
+            CheckSeverity::Error => "error",
+            CheckSeverity::Warning => "warning",
+        };
+        say!(out, "[{}] {}: {}", severity, problem.check, problem.message);
+        if let Some(action) = &problem.repair {
+            say!(out, "    fix: {}", action.label());
+        }
+    }
+    
+    if report.needs_safe_mode() {
+        if !repair {
+            say!(out, "\nRun with --repair to apply the fixes.");
+        }
+        return Err(anyhow::anyhow!("Some checks failed"));
+    }
+    
+    Ok(())
+}
+
+async fn validate_dsl(app: &WinScrapeStudio, dsl_file: String, out: &mut Output) -> Result<()> {
+    let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+    
+    let warnings = check_plan(app, &dsl, out).await?;
+    say!(out, "DSL file is valid.");
+    out.set_data(serde_json::json!({ "valid": true, "warnings": warnings }))?;
+    
+    Ok(())
+}
+
+/// Print a plan's validation issues and lint warnings
+///
+/// Fails with `ValidationErrors` or `SecurityError` if the plan can't run,
+/// and returns the lint warnings otherwise.
+async fn check_plan(app: &WinScrapeStudio, dsl: &crate::dsl::ScrapePlan, out: &Output) -> Result<Vec<LintWarning>> {
+    let issues = app.validate_dsl_detailed(dsl);
+    if !issues.is_empty() {
+        error!("DSL validation failed with {} issue(s)", issues.len());
+        for issue in &issues {
+            say!(out, "{} [{}] {}", issue.path, issue.code, issue.message);
+            if let Some(suggestion) = &issue.suggestion {
+                say!(out, "    hint: {}", suggestion);
+            }
+        }
+        return Err(ValidationErrors { issues }.into());
+    }
+    
+    let warnings = app.lint_dsl(dsl);
+    for warning in &warnings {
+        say!(out, "warning: {} [{}] {}", warning.path, warning.rule, warning.message);
+        say!(out, "    hint: {}", warning.suggestion);
+    }
+    
+    app.validate_dsl(dsl).await?;
+    Ok(warnings)
+}
+
+/// Check a plan file, then preview or run it as `action` asks
+async fn run_plan(
+    app: &WinScrapeStudio,
+    dsl_file: &str,
+    variables: &HashMap<String, String>,
+    recording: &RecordingPaths,
+    action: WatchAction,
+    out: &mut Output,
+) -> Result<()> {
+    let dsl = crate::dsl::DSLParser::parse_file(dsl_file).await?;
+    let warnings = check_plan(app, &dsl, out).await?;
+    
+    match action {
+        WatchAction::Validate => {
+            say!(out, "DSL file is valid.");
+            out.set_data(serde_json::json!({ "valid": true, "warnings": warnings }))?;
+        }
+        WatchAction::Preview => dry_run(app, dsl_file.to_string(), out).await?,
+        WatchAction::Run => {
+            let job_id = app.execute_recorded_scraping(&dsl, variables, recording).await?;
+            let rows = check_scrape(app, &job_id, out).await?;
+            say!(out, "Scraping completed. Job ID: {} ({} rows)", job_id, rows);
+            out.set_data(serde_json::json!({ "job_id": job_id, "rows": rows }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Act on a plan file now and again each time it changes, until Ctrl+C
+///
+/// The file is polled, so editors that save by replacing it are noticed too.
+/// With JSON output every check prints its own document, one per line.
+async fn watch_plan(
+    app: &WinScrapeStudio,
+    dsl_file: &str,
+    variables: &HashMap<String, String>,
+    recording: &RecordingPaths,
+    action: WatchAction,
+    mode: OutputMode,
+) -> Result<()> {
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+    
+    async fn stamp(path: &str) -> Option<(std::time::SystemTime, u64)> {
+        let metadata = tokio::fs::metadata(path).await.ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+    
+    let mut last = stamp(dsl_file).await;
+    if last.is_none() {
+        return Err(anyhow::anyhow!("Cannot read {}", dsl_file));
+    }
+    
+    loop {
+        let mut out = Output::new(mode);
+        say!(out, "\n[{}] Checking {}", chrono::Local::now().format("%H:%M:%S"), dsl_file);
+        let result = run_plan(app, dsl_file, variables, recording, action, &mut out).await;
+        out.finish(result);
+        if mode == OutputMode::Text {
+            println!("Watching {} for changes (Ctrl+C to stop)...", dsl_file);
+        }
+        
+        loop {
+            tokio::select! {
+                // Every check has printed its own result, so there is nothing left to report
+                _ = tokio::signal::ctrl_c() => std::process::exit(exit_code::SUCCESS),
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+            }
+            // A missing file is mid-save; wait for it to come back
+            let current = stamp(dsl_file).await;
+            if current.is_some() && current != last {
+                last = current;
+                break;
+            }
+        }
+    }
+}
+
+fn print_schema(output: Option<String>, out: &mut Output) -> Result<()> {
+    let schema = crate::dsl::schema::scrape_plan_schema_json();
+    
+    match output {
+        Some(path) => {
+            std::fs::write(&path, schema)?;
+            say!(out, "Schema written to: {}", path);
+            out.set_data(serde_json::json!({ "written_to": path }))?;
+        }
+        None => {
+            say!(out, "{}", schema);
+            out.set_data(crate::dsl::schema::scrape_plan_schema())?;
+        }
+    }
     
-    if let Some(output_path) = output {
-        app.export_job(&new_job_id, &output_path, crate::export::ExportFormat::Csv).await?;
-        println!("Results exported to: {}", output_path);
+    Ok(())
+}
+
+async fn handle_template(app: &WinScrapeStudio, action: TemplateAction, out: &mut Output) -> Result<()> {
+    match action {
+        TemplateAction::List => {
+            let templates = app.list_templates().await?;
+            out.set_data(&templates)?;
+            
+            if templates.is_empty() {
+                say!(out, "No templates saved.");
+                return Ok(());
+            }
+            
+            say!(out, "{:<24} {:<30} {:<20}", "Name", "Variables", "Updated");
+            say!(out, "{}", "-".repeat(76));
+            for template in templates {
+                say!(
+                    out,
+                    "{:<24} {:<30} {:<20}",
+                    template.name,
+                    template.variables.join(", "),
+                    time_utils::format_local(&template.updated_at, "%Y-%m-%d %H:%M:%S")
+                );
+            }
+        }
+        TemplateAction::Save { name, dsl_file, description } => {
+            let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+            
+            let template = app.save_template(&name, &description.unwrap_or_default(), &dsl).await?;
+            say!(out, "Template '{}' saved.", template.name);
+            if !template.variables.is_empty() {
+                say!(out, "Variables: {}", template.variables.join(", "));
+            }
+            out.set_data(template)?;
+        }
+        TemplateAction::Run { name, vars, output, format } => {
+            let variables: HashMap<String, String> = vars.into_iter().collect();
+            let job_id = app.execute_template(&name, &variables).await?;
+            say!(out, "Scraping completed. Job ID: {}", job_id);
+            
+            if let Some(output_path) = &output {
+                let export_format = format.unwrap_or(OutputFormat::Csv);
+                app.export_job(&job_id, output_path, convert_format(export_format), &ExportOptions::default()).await?;
+                say!(out, "Results exported to: {}", output_path);
+            }
+            let rows = check_scrape(app, &job_id, out).await?;
+            out.set_data(serde_json::json!({ "job_id": job_id, "rows": rows, "exported_to": output }))?;
+        }
+        TemplateAction::Delete { name } => {
+            app.delete_template(&name).await?;
+            say!(out, "Template '{}' deleted.", name);
+            out.set_data(serde_json::json!({ "deleted": name }))?;
+        }
+    }
+    
+    Ok(())
+}
+
+async fn handle_plans(app: &WinScrapeStudio, action: PlanAction, out: &mut Output) -> Result<()> {
+    match action {
+        PlanAction::List { name } => {
+            let plans = app.list_library_plans(name.as_deref()).await?;
+            out.set_data(&plans)?;
+            
+            if plans.is_empty() {
+                say!(out, "No plans in the library.");
+                return Ok(());
+            }
+            
+            say!(out, "{:<36} {:<30} {:<20}", "ID", "Name", "Updated");
+            say!(out, "{}", "-".repeat(86));
+            for plan in plans {
+                say!(
+                    out,
+                    "{:<36} {:<30} {:<20}",
+                    plan.id,
+                    plan.name,
+                    time_utils::format_local(&plan.updated_at, "%Y-%m-%d %H:%M:%S")
+                );
+            }
+        }
+        PlanAction::Save { dsl_file, name, description } => {
+            let dsl = crate::dsl::DSLParser::parse_file(&dsl_file).await?;
+            let name = name.unwrap_or_else(|| dsl.target.domain.clone());
+            
+            let plan = app.save_to_library(&name, &description.unwrap_or_default(), &dsl).await?;
+            say!(out, "Plan '{}' saved with ID: {}", plan.name, plan.id);
+            out.set_data(plan)?;
+        }
+        PlanAction::Import { bundle } => {
+            let (plan, bundle) = app.import_plan_bundle(&bundle).await?;
+            say!(out, "Imported '{}' with ID: {}", plan.name, plan.id);
+            
+            if !bundle.credentials.is_empty() {
+                say!(out, "\nThis plan needs credentials; pass them as variables when running it:");
+                for credential in &bundle.credentials {
+                    say!(out, "  {} - {} ({})", credential.name, credential.description, credential.path);
+                }
+            }
+            out.set_data(serde_json::json!({ "plan": plan, "credentials": bundle.credentials }))?;
+        }
+        PlanAction::Export { source, output } => {
+            let dsl = if std::path::Path::new(&source).exists() {
+                crate::dsl::DSLParser::parse_file(&source).await?
+            } else {
+                crate::dsl::ScrapePlan::from_yaml(&app.get_library_plan(&source).await?.plan_yaml)?
+            };
+            
+            let bundle = app.export_plan_bundle(&dsl, &output).await?;
+            say!(out, "Plan bundle written to: {}", output);
+            for credential in &bundle.credentials {
+                say!(out, "Removed credential: {} ({})", credential.name, credential.path);
+            }
+            out.set_data(serde_json::json!({ "written_to": output, "credentials_removed": bundle.credentials }))?;
+        }
+        PlanAction::Delete { id } => {
+            app.delete_library_plan(&id).await?;
+            say!(out, "Plan {} deleted.", id);
+            out.set_data(serde_json::json!({ "deleted": id }))?;
+        }
+    }
+    
+    Ok(())
+}
+
+async fn handle_profiles(app: &WinScrapeStudio, action: ProfileAction, out: &mut Output) -> Result<()> {
+    match action {
+        ProfileAction::List => {
+            let profiles = app.list_export_profiles().await?;
+            out.set_data(&profiles)?;
+            
+            if profiles.is_empty() {
+                say!(out, "No export profiles saved.");
+                return Ok(());
+            }
+            
+            say!(out, "{:<30} {:<10} {:<40}", "Name", "Format", "Destination");
+            say!(out, "{}", "-".repeat(80));
+            for profile in profiles {
+                let destination = match &profile.destination {
+                    ExportDestination::OutputDirectory => "export directory".to_string(),
+                    ExportDestination::Directory { path } => path.display().to_string(),
+                    ExportDestination::Database { table } => format!("database table {}", table.as_deref().unwrap_or("(plan domain)")),
+                    ExportDestination::Plugin { name, .. } => format!("sink plugin {}", name),
+                };
+                say!(out, "{:<30} {:<10} {:<40}", profile.name, profile.format, destination);
+            }
+        }
+        ProfileAction::Save { file } => {
+            let content = tokio::fs::read_to_string(&file).await?;
+            // JSON is valid YAML, so one parser reads both
+            let profile: ExportProfile = serde_yaml::from_str(&content)?;
+            app.save_export_profile(&profile).await?;
+            say!(out, "Export profile '{}' saved.", profile.name);
+            out.set_data(profile)?;
+        }
+        ProfileAction::Run { name, job_id } => {
+            let target = app.export_job_with_profile(&job_id, &name).await?;
+            say!(out, "Job {} exported with profile '{}' to: {}", job_id, name, target);
+            out.set_data(serde_json::json!({ "job_id": job_id, "profile": name, "exported_to": target }))?;
+        }
+        ProfileAction::Delete { name } => {
+            let deleted = app.delete_export_profile(&name).await?;
+            if deleted {
+                say!(out, "Export profile '{}' deleted.", name);
+            } else {
+                say!(out, "No export profile named '{}'.", name);
+            }
+            out.set_data(serde_json::json!({ "name": name, "deleted": deleted }))?;
+        }
+    }
+    
+    Ok(())
+}
+
+async fn handle_users(app: &WinScrapeStudio, action: UserAction, out: &mut Output) -> Result<()> {
+    match action {
+        UserAction::List => {
+            let users = app.list_users().await?;
+            out.set_data(&users)?;
+            
+            if users.is_empty() {
+                say!(out, "No API users.");
+                return Ok(());
+            }
+            
+            say!(out, "{:<38} {:<24} {:<6} {:<20}", "ID", "Name", "Admin", "Created");
+            say!(out, "{}", "-".repeat(90));
+            for user in users {
+                say!(
+                    out,
+                    "{:<38} {:<24} {:<6} {:<20}",
+                    user.id,
+                    user.name,
+                    if user.is_admin { "yes" } else { "no" },
+                    user.created_at.format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+        }
+        UserAction::Add { name, admin } => {
+            let (user, token) = app.create_user(&name, admin).await?;
+            say!(out, "User '{}' added with ID {}.", user.name, user.id);
+            say!(out, "Token (shown only once): {}", token);
+            out.set_data(serde_json::json!({ "user": user, "token": token }))?;
+        }
+        UserAction::Remove { id } => {
+            let removed = app.delete_user(&id).await?;
+            if removed {
+                say!(out, "User {} removed.", id);
+            } else {
+                say!(out, "No user with ID {}.", id);
+            }
+            out.set_data(serde_json::json!({ "id": id, "removed": removed }))?;
+        }
     }
     
     Ok(())
 }
 
-async fn validate_dsl(app: &WinScrapeStudio, dsl_file: String) -> Result<()> {
-    let dsl_content = std::fs::read_to_string(&dsl_file)?;
-    let dsl: crate::dsl::ScrapePlan = serde_yaml::from_str(&dsl_content)?;
+fn list_error_codes(code: Option<&str>, out: &mut Output) -> Result<()> {
+    let codes: Vec<crate::error::ErrorCode> = match code {
+        Some(code) => vec![crate::error::ErrorCode::from_code(code)
+            .ok_or_else(|| anyhow::anyhow!("Unknown error code: {}", code))?],
+        None => crate::error::ErrorCode::ALL.to_vec(),
+    };
     
-    match app.validate_dsl(&dsl).await {
-        Ok(_) => println!("DSL file is valid."),
-        Err(e) => {
-            error!("DSL validation failed: {}", e);
-            std::process::exit(1);
+    for error_code in &codes {
+        say!(out, "{}  {}", error_code, error_code.hint());
+    }
+    out.set_data(codes.iter()
+        .map(|error_code| serde_json::json!({ "code": error_code.as_str(), "hint": error_code.hint() }))
+        .collect::<Vec<_>>())?;
+    Ok(())
+}
+
+fn handle_translations(app: &WinScrapeStudio, action: TranslationAction, out: &mut Output) -> Result<()> {
+    let parse_language = |code: &str| {
+        crate::i18n::Language::from_code(code).ok_or_else(|| anyhow::anyhow!("Unsupported language: {}", code))
+    };
+    
+    match action {
+        TranslationAction::Report { lang, verbose } => {
+            let language = lang.as_deref().map(parse_language).transpose()?;
+            let reports: Vec<_> = app.translation_report().into_iter()
+                .filter(|report| language.is_none_or(|language| report.language == language.code()))
+                .collect();
+            
+            say!(out, "{:<6} {:<10} {:>10} {:>8} {:>8} {:>6}", "Code", "Language", "Complete", "Done", "Missing", "Extra");
+            say!(out, "{}", "-".repeat(53));
+            for report in &reports {
+                say!(
+                    out,
+                    "{:<6} {:<10} {:>9.1}% {:>8} {:>8} {:>6}",
+                    report.language,
+                    report.name,
+                    report.percent_complete,
+                    report.translated,
+                    report.missing.len(),
+                    report.extra.len()
+                );
+            }
+            if verbose {
+                for report in &reports {
+                    if !report.missing.is_empty() {
+                        say!(out, "\nMissing in {}:", report.language);
+                        for key in &report.missing {
+                            say!(out, "  {}", key);
+                        }
+                    }
+                    if !report.extra.is_empty() {
+                        say!(out, "\nNot in English ({}):", report.language);
+                        for key in &report.extra {
+                            say!(out, "  {}", key);
+                        }
+                    }
+                }
+            }
+            out.set_data(reports)?;
+        }
+        TranslationAction::Missing { lang, output } => {
+            let language = parse_language(&lang)?;
+            let missing = app.missing_translations(language);
+            let json = serde_json::to_string_pretty(&missing)?;
+            
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json)?;
+                    say!(out, "{} untranslated keys written to: {}", missing.len(), path);
+                    say!(out, "Translate the values, then save the file as {}.json in the translations directory.", language.code());
+                    out.set_data(serde_json::json!({ "written_to": path, "missing": missing.len() }))?;
+                }
+                None => {
+                    say!(out, "{}", json);
+                    out.set_data(missing)?;
+                }
+            }
         }
     }
     
     Ok(())
 }
 
+/// Parse a `NAME=VALUE` template variable
+fn parse_variable(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+        _ => Err(format!("Expected NAME=VALUE, got '{}'", s)),
+    }
+}
+
+fn parse_redaction(s: &str) -> Result<(String, Redaction), String> {
+    let (field, action) = parse_variable(s)?;
+    action.parse().map(|redaction| (field, redaction)).map_err(|e: anyhow::Error| e.to_string())
+}
+
 async fn export_job(
     app: &WinScrapeStudio,
     job_id: String,
-    output: String,
+    output: Option<String>,
     format: OutputFormat,
+    options: &ExportOptions,
+    out: &mut Output,
 ) -> Result<()> {
-    app.export_job(&job_id, &output, convert_format(format)).await?;
-    println!("Job {} exported to: {}", job_id, output);
+    let output = match output {
+        Some(output) => app.export_job(&job_id, &output, convert_format(format), options).await?,
+        None => app.export_job_to_default_path(&job_id, convert_format(format), options).await?,
+    };
+    let output = output.display().to_string();
+    if options.is_split() {
+        say!(out, "Job {} exported in parts, listed in: {}", job_id, output);
+        out.set_data(serde_json::json!({ "job_id": job_id, "exported_to": output, "manifest": output }))?;
+    } else {
+        say!(out, "Job {} exported to: {}", job_id, output);
+        out.set_data(serde_json::json!({ "job_id": job_id, "exported_to": output }))?;
+    }
     Ok(())
 }
 
+/// Run a bulk operation, printing progress until it ends; Ctrl+C cancels it
+///
+/// Some jobs failing makes the outcome partial; all of them failing is an error.
+async fn run_bulk(app: Arc<WinScrapeStudio>, action: BulkAction, job_ids: Vec<String>, out: &mut Output) -> Result<()> {
+    let handle = bulk::start(app, action, job_ids);
+    let mut ticker = tokio::time::interval(std::time::Duration::from_millis(500));
+    
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                say!(out, "\nCancelling after the current job...");
+                handle.cancel();
+            }
+            _ = ticker.tick() => {}
+        }
+        
+        let progress = handle.progress();
+        if out.is_text() {
+            print!("\r{}: {}/{} jobs", handle.action.label(), progress.processed, progress.total);
+            std::io::Write::flush(&mut std::io::stdout())?;
+        }
+        if progress.finished {
+            say!(out);
+            for output in &progress.outputs {
+                say!(out, "  {}", output);
+            }
+            for (job_id, error) in &progress.failures {
+                say!(out, "  {} failed: {}", job_id, error);
+            }
+            if progress.cancelled {
+                say!(out, "Cancelled with {} of {} jobs done", progress.processed, progress.total);
+            }
+            
+            let failures: Vec<_> = progress.failures.iter()
+                .map(|(job_id, error)| serde_json::json!({ "job_id": job_id, "error": error }))
+                .collect();
+            out.set_data(serde_json::json!({
+                "action": handle.action.label(),
+                "total": progress.total,
+                "processed": progress.processed,
+                "succeeded": progress.succeeded,
+                "outputs": progress.outputs,
+                "failures": failures,
+                "cancelled": progress.cancelled,
+            }))?;
+            if !progress.failures.is_empty() {
+                let message = format!("{} of {} jobs failed", progress.failures.len(), progress.total);
+                if progress.succeeded.is_empty() {
+                    return Err(anyhow::anyhow!(message));
+                }
+                out.partial(message);
+            }
+            return Ok(());
+        }
+    }
+}
+
 fn convert_format(format: OutputFormat) -> crate::export::ExportFormat {
     match format {
         OutputFormat::Csv => crate::export::ExportFormat::Csv,
         OutputFormat::Json => crate::export::ExportFormat::Json,
         OutputFormat::Xlsx => crate::export::ExportFormat::Xlsx,
         OutputFormat::Parquet => crate::export::ExportFormat::Parquet,
+        OutputFormat::Ndjson => crate::export::ExportFormat::Ndjson,
     }
 }
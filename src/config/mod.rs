@@ -15,6 +15,10 @@ pub struct AppConfig {
     pub api: ApiConfig,
     pub ui: UIConfig,
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub plugins: PluginConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +27,48 @@ pub struct DatabaseConfig {
     pub max_connections: u32,
     pub enable_wal: bool,
     pub cache_size_mb: usize,
+    /// Encrypt the database with SQLCipher using a key from this source
+    ///
+    /// Requires the `sqlcipher` feature. The key itself is never stored in
+    /// the configuration file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_key: Option<EncryptionKeySource>,
+    /// Move results of finished jobs older than this many days to Parquet files
+    #[serde(default)]
+    pub archive_after_days: Option<u32>,
+    /// Where archived results go; `archive` next to the database by default
+    #[serde(default)]
+    pub archive_dir: Option<PathBuf>,
+}
+
+/// Where the database encryption key is read from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum EncryptionKeySource {
+    /// An environment variable
+    Env {
+        #[serde(default = "default_encryption_key_env")]
+        var: String,
+    },
+    /// The OS credential store: Windows Credential Manager, macOS Keychain or Secret Service
+    Keyring {
+        #[serde(default = "default_keyring_service")]
+        service: String,
+        #[serde(default = "default_keyring_account")]
+        account: String,
+    },
+}
+
+fn default_encryption_key_env() -> String {
+    "WSS_DB_KEY".to_string()
+}
+
+fn default_keyring_service() -> String {
+    "WinScrape Studio".to_string()
+}
+
+fn default_keyring_account() -> String {
+    "database".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +78,81 @@ pub struct LLMConfig {
     pub temperature: f32,
     pub max_tokens: usize,
     pub threads: usize,
+    /// Where completions come from; the GGUF model at `model_path` by default
+    #[serde(default)]
+    pub backend: LLMBackendConfig,
+    /// Times an invalid generated plan is sent back to the model with its errors
+    #[serde(default = "default_max_repair_attempts")]
+    pub max_repair_attempts: usize,
+}
+
+fn default_max_repair_attempts() -> usize {
+    2
+}
+
+/// Model server used for plan generation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LLMBackendConfig {
+    /// The GGUF model at `model_path`, run in-process with the `local-llm` feature
+    #[default]
+    Local,
+    /// Any server implementing the OpenAI chat completions API
+    #[serde(rename = "openai")]
+    OpenAI {
+        #[serde(default = "default_openai_url")]
+        base_url: String,
+        model: String,
+        /// Environment variable holding the API key; requests are unauthenticated if unset
+        #[serde(default = "default_openai_key_env")]
+        api_key_env: String,
+    },
+    Anthropic {
+        #[serde(default = "default_anthropic_url")]
+        base_url: String,
+        model: String,
+        /// Environment variable holding the API key
+        #[serde(default = "default_anthropic_key_env")]
+        api_key_env: String,
+    },
+    /// A local Ollama server
+    Ollama {
+        #[serde(default = "default_ollama_url")]
+        base_url: String,
+        model: String,
+    },
+}
+
+impl LLMBackendConfig {
+    /// Short name of the backend, e.g. `openai`
+    pub fn name(&self) -> &'static str {
+        match self {
+            LLMBackendConfig::Local => "local",
+            LLMBackendConfig::OpenAI { .. } => "openai",
+            LLMBackendConfig::Anthropic { .. } => "anthropic",
+            LLMBackendConfig::Ollama { .. } => "ollama",
+        }
+    }
+}
+
+fn default_openai_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_openai_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+fn default_anthropic_url() -> String {
+    "https://api.anthropic.com".to_string()
+}
+
+fn default_anthropic_key_env() -> String {
+    "ANTHROPIC_API_KEY".to_string()
+}
+
+fn default_ollama_url() -> String {
+    "http://localhost:11434".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,10 +162,128 @@ pub struct ScrapingConfig {
     pub max_retries: usize,
     pub retry_delay_seconds: u64,
     pub respect_robots_txt: bool,
+    /// Respect robots.txt even for plans that turn `respect_robots_txt` off;
+    /// off unless chosen in the settings, so plans decide by default
+    #[serde(default)]
+    pub always_respect_robots_txt: bool,
     pub default_delay_ms: u64,
     pub user_agents: Vec<String>,
     pub enable_browser_fallback: bool,
     pub browser_timeout_seconds: u64,
+    /// When to stop sending requests to a host that keeps failing
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// How concurrency and delays follow each host's response times and errors
+    #[serde(default)]
+    pub adaptive: AdaptiveConfig,
+    /// Which responses are read at all
+    #[serde(default)]
+    pub response_limits: ResponseLimitsConfig,
+    /// How long fetched robots.txt files are reused
+    #[serde(default)]
+    pub robots_cache: RobotsCacheConfig,
+}
+
+/// Robots.txt files are kept per host in the storage cache, so runs don't
+/// fetch them again for every URL and a host whose robots.txt can't be
+/// fetched for a moment falls back to the last copy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RobotsCacheConfig {
+    /// Hours a fetched robots.txt is used before it is fetched again
+    pub ttl_hours: u64,
+    /// Hours an outdated copy is still used while robots.txt can't be fetched
+    pub max_stale_hours: u64,
+}
+
+impl Default for RobotsCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_hours: 24,
+            max_stale_hours: 168,
+        }
+    }
+}
+
+/// Responses left unread so a plan pointed at a huge file or a binary
+/// endpoint doesn't fill memory; the URL is reported as skipped instead
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseLimitsConfig {
+    /// Largest body read, in bytes; downloads stop as soon as it is passed
+    pub max_body_bytes: u64,
+    /// Content types that are read, matched by prefix; empty reads everything
+    pub allowed_content_types: Vec<String>,
+}
+
+impl Default for ResponseLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 20 * 1024 * 1024,
+            allowed_content_types: vec![
+                "text/html".to_string(),
+                "application/xhtml+xml".to_string(),
+                "application/xml".to_string(),
+                "text/xml".to_string(),
+                "text/plain".to_string(),
+            ],
+        }
+    }
+}
+
+/// Per-host tuning that backs off when a host shows signs of throttling and
+/// speeds up again while it stays healthy; plan and politeness limits stay the ceiling
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptiveConfig {
+    pub enabled: bool,
+    /// Requests to a host between two adjustments
+    pub sample_size: usize,
+    /// Share of failed or blocked requests in a sample that counts as throttling
+    pub max_error_rate: f64,
+    /// Average response time above this multiple of the host's best counts as throttling
+    pub slowdown_factor: f64,
+    /// Most the plan's delays are stretched by
+    pub max_delay_factor: f64,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_size: 10,
+            max_error_rate: 0.2,
+            slowdown_factor: 2.0,
+            max_delay_factor: 8.0,
+        }
+    }
+}
+
+/// Per-host circuit breaker: a host that keeps failing or refusing requests is
+/// left alone for a while, then tried again with a single request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CircuitBreakerConfig {
+    pub enabled: bool,
+    /// Failed requests in a row that open the circuit
+    pub failure_threshold: u32,
+    /// 403 or 429 answers within `blocked_window_seconds` that open the circuit
+    pub blocked_threshold: usize,
+    pub blocked_window_seconds: u64,
+    /// How long the host is left alone once the circuit opens
+    pub cooldown_seconds: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 5,
+            blocked_threshold: 3,
+            blocked_window_seconds: 60,
+            cooldown_seconds: 120,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +292,58 @@ pub struct ExportConfig {
     pub max_file_size_mb: usize,
     pub compression_enabled: bool,
     pub output_directory: PathBuf,
+    /// Name of exports written to `output_directory`; plans can override it
+    #[serde(default = "default_filename_template")]
+    pub filename_template: String,
+    /// What to do when a generated export name is already taken
+    #[serde(default)]
+    pub on_collision: FilenameCollision,
+    /// Rows per XLSX sheet; longer exports continue on further sheets
+    #[serde(default = "default_xlsx_sheet_rows")]
+    pub xlsx_sheet_rows: usize,
+    /// `postgres://` or `mysql://` connection string for database exports
+    #[serde(default)]
+    pub database_url: Option<String>,
+    /// Codec used when `compression_enabled` is set
+    #[serde(default)]
+    pub compression: CompressionCodec,
+    /// Codec level; the codec's default when unset
+    #[serde(default)]
+    pub compression_level: Option<u32>,
+}
+
+fn default_filename_template() -> String {
+    crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string()
+}
+
+fn default_xlsx_sheet_rows() -> usize {
+    crate::export::xlsx_exporter::MAX_SHEET_ROWS
+}
+
+/// Compression applied to finished export files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionCodec {
+    /// `.gz`, levels 0-9
+    #[default]
+    Gzip,
+    /// `.zst`, levels 1-22
+    Zstd,
+    /// `.br`, levels 0-11
+    Brotli,
+}
+
+/// Handling of a generated export file name that already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FilenameCollision {
+    /// Append `_2`, `_3`, ... to the name until it is free
+    #[default]
+    Increment,
+    /// Replace the existing file
+    Overwrite,
+    /// Refuse to export
+    Fail,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +355,13 @@ pub struct SecurityConfig {
     pub allowed_schemes: Vec<String>,
     pub enable_rate_limiting: bool,
     pub rate_limit_requests_per_minute: usize,
+    /// Days security events are kept for the Security tab; kept for good when unset
+    #[serde(default = "default_audit_retention_days")]
+    pub audit_retention_days: Option<u32>,
+}
+
+fn default_audit_retention_days() -> Option<u32> {
+    Some(90)
 }
 
 #[cfg(feature = "api")]
@@ -75,6 +373,28 @@ pub struct ApiConfig {
     pub max_request_size_mb: usize,
     pub enable_auth: bool,
     pub auth_token: Option<String>,
+    /// Requests per second each client may make on average; unlimited when unset
+    #[serde(default)]
+    pub rate_limit_per_sec: Option<f64>,
+    /// Requests a client may make at once after being idle
+    #[serde(default = "default_rate_limit_burst")]
+    pub rate_limit_burst: u32,
+    /// Jobs each client may submit per UTC day; unlimited when unset
+    #[serde(default)]
+    pub daily_job_quota: Option<u32>,
+    /// Result rows each client's jobs may store per UTC day; unlimited when unset
+    #[serde(default)]
+    pub daily_row_quota: Option<u64>,
+    /// Port the gRPC job service listens on, on the same host; not served when unset
+    ///
+    /// Requires the `grpc` feature.
+    #[serde(default)]
+    pub grpc_port: Option<u16>,
+}
+
+#[cfg(feature = "api")]
+fn default_rate_limit_burst() -> u32 {
+    20
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +405,34 @@ pub struct UIConfig {
     pub window_height: f32,
     pub enable_dark_mode: bool,
     pub chat_history_limit: usize,
+    /// Zone for displayed and exported times: `system`, `utc` or an offset like `+02:00`
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// Rows extracted for the preview next to a plan under review
+    #[serde(default = "default_preview_rows")]
+    pub preview_rows: usize,
+    /// Closing the window hides it in the system tray while jobs keep running
+    #[serde(default)]
+    pub minimize_to_tray: bool,
+    /// Language code of the interface, like `en` or `ko`
+    #[serde(default = "default_language")]
+    pub language: String,
+    /// Directory of `<code>.json` files overriding the built-in translations;
+    /// defaults to `translations` in the data directory
+    #[serde(default)]
+    pub translations_directory: Option<PathBuf>,
+}
+
+fn default_language() -> String {
+    "en".to_string()
+}
+
+fn default_timezone() -> String {
+    "system".to_string()
+}
+
+fn default_preview_rows() -> usize {
+    10
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +444,118 @@ pub struct LoggingConfig {
     pub max_files: usize,
 }
 
+/// Notifications sent when jobs finish; plans may override them with `output.notify`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Show a desktop notification, a toast on Windows
+    #[serde(default = "default_desktop_notifications")]
+    pub desktop: bool,
+    #[serde(default = "default_true")]
+    pub on_completed: bool,
+    #[serde(default = "default_true")]
+    pub on_failed: bool,
+    /// Also send an email through this server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<SmtpConfig>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            desktop: default_desktop_notifications(),
+            on_completed: true,
+            on_failed: true,
+            email: None,
+        }
+    }
+}
+
+/// Plugins loaded at startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Where plugin libraries and `.wasm` modules are read from; `plugins`
+    /// next to the database by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+    /// Most Rhai operations or WebAssembly fuel a plan's `post_process` script may ask for
+    #[serde(default = "default_max_script_operations")]
+    pub max_script_operations: u64,
+    /// Most memory a plan's `post_process` script may ask for
+    #[serde(default = "default_max_script_memory_mb")]
+    pub max_script_memory_mb: u32,
+}
+
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: None,
+            max_script_operations: default_max_script_operations(),
+            max_script_memory_mb: default_max_script_memory_mb(),
+        }
+    }
+}
+
+fn default_max_script_operations() -> u64 {
+    100_000_000
+}
+
+fn default_max_script_memory_mb() -> u32 {
+    256
+}
+
+fn default_desktop_notifications() -> bool {
+    cfg!(windows)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// SMTP server that notification emails are sent through
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Login name; the server is used without authentication if unset
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Environment variable holding the password
+    #[serde(default = "default_smtp_password_env")]
+    pub password_env: String,
+    pub from: String,
+    pub to: Vec<String>,
+    /// Addresses besides `to` that a plan's `notify.email_to` may name
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_recipients: Vec<String>,
+}
+
+/// How the connection to the SMTP server is encrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS, usually on port 587
+    #[default]
+    StartTls,
+    /// TLS from the start, usually on port 465
+    Tls,
+    /// Unencrypted, for local relays only
+    None,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_password_env() -> String {
+    "WSS_SMTP_PASSWORD".to_string()
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         let data_dir = get_data_directory();
@@ -106,6 +566,9 @@ impl Default for AppConfig {
                 max_connections: 10,
                 enable_wal: true,
                 cache_size_mb: 64,
+                encryption_key: None,
+                archive_after_days: None,
+                archive_dir: None,
             },
             llm: LLMConfig {
                 model_path: data_dir.join("models").join("llama-2-7b-chat.q4_0.gguf"),
@@ -113,6 +576,8 @@ impl Default for AppConfig {
                 temperature: 0.1,
                 max_tokens: 512,
                 threads: 4,
+                backend: LLMBackendConfig::Local,
+                max_repair_attempts: default_max_repair_attempts(),
             },
             scraping: ScrapingConfig {
                 max_concurrent_requests: 5,
@@ -120,6 +585,7 @@ impl Default for AppConfig {
                 max_retries: 3,
                 retry_delay_seconds: 2,
                 respect_robots_txt: true,
+                always_respect_robots_txt: false,
                 default_delay_ms: 1000,
                 user_agents: vec![
                     "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
@@ -128,12 +594,22 @@ impl Default for AppConfig {
                 ],
                 enable_browser_fallback: false,
                 browser_timeout_seconds: 60,
+                circuit_breaker: CircuitBreakerConfig::default(),
+                adaptive: AdaptiveConfig::default(),
+                response_limits: ResponseLimitsConfig::default(),
+                robots_cache: RobotsCacheConfig::default(),
             },
             export: ExportConfig {
                 default_format: "csv".to_string(),
                 max_file_size_mb: 100,
                 compression_enabled: true,
                 output_directory: data_dir.join("exports"),
+                filename_template: default_filename_template(),
+                on_collision: FilenameCollision::default(),
+                xlsx_sheet_rows: default_xlsx_sheet_rows(),
+                database_url: None,
+                compression: CompressionCodec::default(),
+                compression_level: None,
             },
             security: SecurityConfig {
                 enable_input_validation: true,
@@ -150,6 +626,7 @@ impl Default for AppConfig {
                 ],
                 enable_rate_limiting: true,
                 rate_limit_requests_per_minute: 60,
+                audit_retention_days: default_audit_retention_days(),
             },
             #[cfg(feature = "api")]
             api: ApiConfig {
@@ -159,6 +636,11 @@ impl Default for AppConfig {
                 max_request_size_mb: 10,
                 enable_auth: false,
                 auth_token: None,
+                rate_limit_per_sec: None,
+                rate_limit_burst: default_rate_limit_burst(),
+                daily_job_quota: None,
+                daily_row_quota: None,
+                grpc_port: None,
             },
             ui: UIConfig {
                 theme: "dark".to_string(),
@@ -167,6 +649,11 @@ impl Default for AppConfig {
                 window_height: 800.0,
                 enable_dark_mode: true,
                 chat_history_limit: 100,
+                timezone: default_timezone(),
+                preview_rows: default_preview_rows(),
+                minimize_to_tray: false,
+                language: default_language(),
+                translations_directory: None,
             },
             logging: LoggingConfig {
                 level: "info".to_string(),
@@ -175,6 +662,8 @@ impl Default for AppConfig {
                 max_file_size_mb: 10,
                 max_files: 5,
             },
+            notifications: NotificationConfig::default(),
+            plugins: PluginConfig::default(),
         }
     }
 }
@@ -188,7 +677,9 @@ impl AppConfig {
             Self::load_from_file(&config_path).await
         } else {
             info!("No configuration file found, using defaults");
-            let config = Self::default();
+            let mut config = Self::default();
+            // Start in the user's own language rather than always in English
+            config.ui.language = crate::i18n::detect_system_language().code().to_string();
             config.save().await?;
             Ok(config)
         }
@@ -196,8 +687,7 @@ impl AppConfig {
     
     /// Load configuration from specific file
     pub async fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
-        let content = tokio::fs::read_to_string(path).await?;
-        let config: AppConfig = toml::from_str(&content)?;
+        let config = Self::parse_file(path).await?;
         
         // Validate configuration
         config.validate()?;
@@ -206,9 +696,25 @@ impl AppConfig {
         Ok(config)
     }
     
+    /// Read a configuration file without validating its values
+    pub async fn parse_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path).await?;
+        Ok(toml::from_str(&content)?)
+    }
+    
+    /// Path of the configuration file used when none is given
+    pub fn default_path() -> PathBuf {
+        get_config_path()
+    }
+    
     /// Save configuration to default location
     pub async fn save(&self) -> Result<()> {
-        let config_path = get_config_path();
+        self.save_to_file(get_config_path()).await
+    }
+    
+    /// Save configuration to a specific file
+    pub async fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        let config_path = path.as_ref();
         
         // Ensure config directory exists
         if let Some(parent) = config_path.parent() {
@@ -224,48 +730,143 @@ impl AppConfig {
     
     /// Validate configuration values
     pub fn validate(&self) -> Result<()> {
+        if let Some((_, message)) = self.validation_errors().into_iter().next() {
+            return Err(anyhow::anyhow!(message));
+        }
+        
+        info!("Configuration validation passed");
+        Ok(())
+    }
+    
+    /// All invalid values, with the section each belongs to
+    pub fn validation_errors(&self) -> Vec<(ConfigSection, String)> {
+        let mut errors = Vec::new();
+        
         // Validate database configuration
         if self.database.max_connections == 0 {
-            return Err(anyhow::anyhow!("Database max_connections must be > 0"));
+            errors.push((ConfigSection::Database, "Database max_connections must be > 0".to_string()));
+        }
+        
+        if self.database.encryption_key.is_some() && !cfg!(feature = "sqlcipher") {
+            errors.push((
+                ConfigSection::Database,
+                "Database encryption_key is set but this build has no SQLCipher support".to_string(),
+            ));
         }
         
         // Validate LLM configuration
         if self.llm.context_size == 0 {
-            return Err(anyhow::anyhow!("LLM context_size must be > 0"));
+            errors.push((ConfigSection::Llm, "LLM context_size must be > 0".to_string()));
         }
         
         if !(0.0..=2.0).contains(&self.llm.temperature) {
-            return Err(anyhow::anyhow!("LLM temperature must be between 0.0 and 2.0"));
+            errors.push((ConfigSection::Llm, "LLM temperature must be between 0.0 and 2.0".to_string()));
+        }
+        
+        match &self.llm.backend {
+            LLMBackendConfig::Local => {}
+            LLMBackendConfig::OpenAI { base_url, model, .. }
+            | LLMBackendConfig::Anthropic { base_url, model, .. }
+            | LLMBackendConfig::Ollama { base_url, model } => {
+                if model.trim().is_empty() {
+                    errors.push((ConfigSection::Llm, format!("LLM backend {} needs a model name", self.llm.backend.name())));
+                }
+                if let Err(e) = url::Url::parse(base_url) {
+                    errors.push((ConfigSection::Llm, format!("Invalid LLM backend URL {}: {}", base_url, e)));
+                }
+            }
         }
         
         // Validate scraping configuration
         if self.scraping.max_concurrent_requests == 0 {
-            return Err(anyhow::anyhow!("Scraping max_concurrent_requests must be > 0"));
+            errors.push((ConfigSection::Scraping, "Scraping max_concurrent_requests must be > 0".to_string()));
         }
         
         if self.scraping.user_agents.is_empty() {
-            return Err(anyhow::anyhow!("At least one user agent must be configured"));
+            errors.push((ConfigSection::Scraping, "At least one user agent must be configured".to_string()));
         }
         
         // Validate security configuration
         if self.security.max_input_length == 0 {
-            return Err(anyhow::anyhow!("Security max_input_length must be > 0"));
+            errors.push((ConfigSection::Security, "Security max_input_length must be > 0".to_string()));
+        }
+        
+        if self.plugins.max_script_operations == 0 || self.plugins.max_script_memory_mb == 0 {
+            errors.push((ConfigSection::Plugins, "Plugins max_script_operations and max_script_memory_mb must be > 0".to_string()));
         }
         
         // Validate export configuration
         if self.export.max_file_size_mb == 0 {
-            return Err(anyhow::anyhow!("Export max_file_size_mb must be > 0"));
+            errors.push((ConfigSection::Export, "Export max_file_size_mb must be > 0".to_string()));
+        }
+        
+        if let Err(e) = crate::export::filename::validate_template(&self.export.filename_template) {
+            errors.push((ConfigSection::Export, format!("Export filename_template is invalid: {}", e)));
+        }
+        
+        if let Some(level) = self.export.compression_level {
+            let levels = crate::export::compression::levels(self.export.compression);
+            if !levels.contains(&level) {
+                errors.push((ConfigSection::Export, format!(
+                    "Export compression_level {} is out of range {}-{} for {:?}",
+                    level, levels.start(), levels.end(), self.export.compression
+                )));
+            }
+        }
+        
+        if let Err(e) = self.ui.timezone.parse::<crate::utils::time_utils::Timezone>() {
+            errors.push((ConfigSection::Ui, e.to_string()));
+        }
+        
+        if crate::i18n::Language::from_code(&self.ui.language).is_none() {
+            errors.push((ConfigSection::Ui, format!("Unknown interface language '{}'", self.ui.language)));
         }
         
         #[cfg(feature = "api")]
         {
             if self.api.port == 0 {
-                return Err(anyhow::anyhow!("API port must be > 0"));
+                errors.push((ConfigSection::Api, "API port must be > 0".to_string()));
+            }
+            if self.api.grpc_port.is_some_and(|port| port == 0 || port == self.api.port) {
+                errors.push((ConfigSection::Api, "gRPC port must be > 0 and differ from the API port".to_string()));
             }
         }
         
-        info!("Configuration validation passed");
-        Ok(())
+        if let Some(email) = &self.notifications.email {
+            if email.host.trim().is_empty() {
+                errors.push((ConfigSection::Notifications, "Notification email host must be set".to_string()));
+            }
+            if !email.from.contains('@') || email.to.is_empty() || email.to.iter().any(|to| !to.contains('@')) {
+                errors.push((ConfigSection::Notifications, "Notification email needs a sender and at least one recipient address".to_string()));
+            }
+            let addresses = std::iter::once(&email.from).chain(&email.to).chain(&email.allowed_recipients);
+            for address in addresses {
+                if let Err(e) = crate::notifications::smtp::check_address(address) {
+                    errors.push((ConfigSection::Notifications, e.to_string()));
+                }
+            }
+        }
+        
+        errors
+    }
+    
+    /// Put one section back to its default values
+    pub fn reset_section(&mut self, section: ConfigSection) {
+        let defaults = Self::default();
+        match section {
+            ConfigSection::All => *self = defaults,
+            ConfigSection::Database => self.database = defaults.database,
+            ConfigSection::Llm => self.llm = defaults.llm,
+            ConfigSection::Scraping => self.scraping = defaults.scraping,
+            ConfigSection::Export => self.export = defaults.export,
+            ConfigSection::Security => self.security = defaults.security,
+            #[cfg(feature = "api")]
+            ConfigSection::Api => self.api = defaults.api,
+            ConfigSection::Ui => self.ui = defaults.ui,
+            ConfigSection::Logging => self.logging = defaults.logging,
+            ConfigSection::Notifications => self.notifications = defaults.notifications,
+            ConfigSection::Plugins => self.plugins = defaults.plugins,
+        }
     }
     
     /// Get data directory path
@@ -275,6 +876,16 @@ impl AppConfig {
             .to_path_buf()
     }
     
+    /// Directory plugins are loaded from
+    pub fn get_plugins_dir(&self) -> PathBuf {
+        self.plugins.directory.clone().unwrap_or_else(|| self.get_data_dir().join("plugins"))
+    }
+    
+    /// Directory translation overrides are loaded from
+    pub fn get_translations_dir(&self) -> PathBuf {
+        self.ui.translations_directory.clone().unwrap_or_else(|| self.get_data_dir().join("translations"))
+    }
+    
     /// Ensure all required directories exist
     pub async fn ensure_directories(&self) -> Result<()> {
         let dirs_to_create = vec![
@@ -294,6 +905,45 @@ impl AppConfig {
     }
 }
 
+/// Top-level section of the configuration file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSection {
+    /// The whole file
+    All,
+    Database,
+    Llm,
+    Scraping,
+    Export,
+    Security,
+    #[cfg(feature = "api")]
+    Api,
+    Ui,
+    Logging,
+    Notifications,
+    Plugins,
+}
+
+impl std::fmt::Display for ConfigSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ConfigSection::All => "all",
+            ConfigSection::Database => "database",
+            ConfigSection::Llm => "llm",
+            ConfigSection::Scraping => "scraping",
+            ConfigSection::Export => "export",
+            ConfigSection::Security => "security",
+            #[cfg(feature = "api")]
+            ConfigSection::Api => "api",
+            ConfigSection::Ui => "ui",
+            ConfigSection::Logging => "logging",
+            ConfigSection::Notifications => "notifications",
+            ConfigSection::Plugins => "plugins",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Get the default data directory
 fn get_data_directory() -> PathBuf {
     directories::ProjectDirs::from("com", "winscrape", "studio")
@@ -0,0 +1,179 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use super::WinScrapeStudio;
+use crate::export::{ExportFormat, ExportOptions};
+
+/// Jobs deleted per transaction, so the database isn't locked for long
+const DELETE_BATCH_SIZE: usize = 50;
+
+/// Operation applied to every selected job
+#[derive(Debug, Clone)]
+pub enum BulkAction {
+    /// Delete jobs with their results and logs
+    Delete,
+    /// Export each job to the export directory
+    Export { format: ExportFormat },
+    /// Run each job's plan again
+    Rerun,
+}
+
+impl BulkAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BulkAction::Delete => "delete",
+            BulkAction::Export { .. } => "export",
+            BulkAction::Rerun => "re-run",
+        }
+    }
+}
+
+/// Where a bulk operation has got to
+#[derive(Debug, Clone, Default)]
+pub struct BulkProgress {
+    pub total: usize,
+    /// Jobs handled so far, successfully or not
+    pub processed: usize,
+    /// Job IDs handled successfully
+    pub succeeded: Vec<String>,
+    /// Job IDs that failed, with the error
+    pub failures: Vec<(String, String)>,
+    /// Export paths or new job IDs, depending on the action
+    pub outputs: Vec<String>,
+    pub cancelled: bool,
+    pub finished: bool,
+}
+
+impl BulkProgress {
+    /// Share of jobs handled, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.processed as f32 / self.total as f32
+        }
+    }
+}
+
+/// Handle to a bulk operation running in the background
+#[derive(Clone)]
+pub struct BulkHandle {
+    pub action: BulkAction,
+    progress: Arc<Mutex<BulkProgress>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl BulkHandle {
+    /// Snapshot of the progress so far
+    pub fn progress(&self) -> BulkProgress {
+        self.progress.lock().map(|p| p.clone()).unwrap_or_default()
+    }
+
+    /// Stop after the job or batch in progress
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress().finished
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+
+    fn update(&self, f: impl FnOnce(&mut BulkProgress)) {
+        if let Ok(mut progress) = self.progress.lock() {
+            f(&mut progress);
+        }
+    }
+}
+
+/// Start applying an action to jobs on a background task
+pub fn start(app: Arc<WinScrapeStudio>, action: BulkAction, job_ids: Vec<String>) -> BulkHandle {
+    let handle = BulkHandle {
+        action: action.clone(),
+        progress: Arc::new(Mutex::new(BulkProgress {
+            total: job_ids.len(),
+            ..Default::default()
+        })),
+        cancel: Arc::new(AtomicBool::new(false)),
+    };
+
+    let task_handle = handle.clone();
+    tokio::spawn(async move {
+        info!("Starting bulk {} of {} jobs", action.label(), job_ids.len());
+        match &action {
+            BulkAction::Delete => delete_jobs(&app, &job_ids, &task_handle).await,
+            _ => run_each(&app, &action, &job_ids, &task_handle).await,
+        }
+
+        let cancelled = task_handle.is_cancelled();
+        task_handle.update(|p| {
+            p.cancelled = cancelled;
+            p.finished = true;
+        });
+        let progress = task_handle.progress();
+        info!(
+            "Bulk {} {}: {} succeeded, {} failed",
+            action.label(),
+            if cancelled { "cancelled" } else { "finished" },
+            progress.succeeded.len(),
+            progress.failures.len()
+        );
+    });
+
+    handle
+}
+
+async fn delete_jobs(app: &WinScrapeStudio, job_ids: &[String], handle: &BulkHandle) {
+    for batch in job_ids.chunks(DELETE_BATCH_SIZE) {
+        if handle.is_cancelled() {
+            return;
+        }
+
+        match app.storage.delete_jobs(batch).await {
+            Ok(_) => handle.update(|p| {
+                p.processed += batch.len();
+                p.succeeded.extend(batch.iter().cloned());
+            }),
+            Err(e) => {
+                warn!("Bulk delete batch failed: {}", e);
+                handle.update(|p| {
+                    p.processed += batch.len();
+                    p.failures.extend(batch.iter().map(|id| (id.clone(), e.to_string())));
+                });
+            }
+        }
+    }
+}
+
+async fn run_each(app: &WinScrapeStudio, action: &BulkAction, job_ids: &[String], handle: &BulkHandle) {
+    for job_id in job_ids {
+        if handle.is_cancelled() {
+            return;
+        }
+
+        let result = match action {
+            BulkAction::Export { format } => app.export_job_to_default_path(job_id, format.clone(), &ExportOptions::default()).await
+                .map(|path| path.display().to_string()),
+            BulkAction::Rerun => app.rerun_job(job_id).await,
+            BulkAction::Delete => unreachable!("deletes run in batches"),
+        };
+
+        handle.update(|p| {
+            p.processed += 1;
+            match result {
+                Ok(output) => {
+                    p.succeeded.push(job_id.clone());
+                    p.outputs.push(output);
+                }
+                Err(e) => {
+                    warn!("Bulk {} of job {} failed: {}", action.label(), job_id, e);
+                    p.failures.push((job_id.clone(), e.to_string()));
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use url::Url;
+
+use crate::dsl::ScrapePlan;
+use crate::scraper::robots_meta::NOFOLLOW_FIELD;
+
+/// Job plan metadata key holding the upstream job that supplied the start URLs
+pub const UPSTREAM_JOB_METADATA_KEY: &str = "upstream_job";
+
+/// Plan metadata key listing the saved plans run so far to feed a downstream plan
+pub const PLAN_CHAIN_METADATA_KEY: &str = "plan_chain";
+
+/// Saved plans already run for the plan's downstream plans, nearest first
+pub fn plan_chain(plan: &ScrapePlan) -> Vec<String> {
+    plan.get_metadata(PLAN_CHAIN_METADATA_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// HTTP(S) URLs in `field` of upstream result rows, in order and without repeats
+///
+/// Relative links are resolved against the page the row came from. Links
+/// the upstream run marked nofollow are left out, and so are links off
+/// `domain` and its subdomains, as upstream pages decide what they link to.
+pub fn upstream_urls(rows: &[serde_json::Value], field: &str, domain: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    rows.iter()
+        .filter(|row| {
+            let nofollow = row.get(NOFOLLOW_FIELD).and_then(|fields| fields.as_array());
+            !nofollow.is_some_and(|fields| fields.iter().any(|name| name.as_str() == Some(field)))
+        })
+        .filter_map(|row| {
+            let value = row.get(field)?.as_str().map(str::trim).filter(|value| !value.is_empty())?;
+            let page = row.get("_source_url").and_then(|url| url.as_str()).and_then(|url| Url::parse(url).ok());
+            let url = match page {
+                Some(page) => page.join(value).ok()?,
+                None => Url::parse(value).ok()?,
+            };
+            let host = url.host_str()?;
+            let on_domain = host == domain || host.ends_with(&format!(".{}", domain));
+            (matches!(url.scheme(), "http" | "https") && on_domain).then(|| url.to_string())
+        })
+        .filter(|url| seen.insert(url.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_upstream_urls_resolve_and_dedupe() {
+        let rows = vec![
+            json!({ "url": "/p/1", "_source_url": "https://shop.example.com/c/shoes" }),
+            json!({ "url": "https://shop.example.com/p/1" }),
+            json!({ "url": "mailto:sales@example.com" }),
+            json!({ "url": "" }),
+            json!({ "link": "https://shop.example.com/p/2" }),
+            json!({ "url": "https://shop.example.com/p/3" }),
+            json!({ "url": "https://shop.example.com/p/4", "_nofollow": ["url"] }),
+            json!({ "url": "http://169.254.169.254/latest/meta-data/" }),
+            json!({ "url": "https://evilshop.example.com/p/5" }),
+            json!({ "url": "https://img.shop.example.com/p/6" }),
+        ];
+
+        assert_eq!(
+            upstream_urls(&rows, "url", "shop.example.com"),
+            vec!["https://shop.example.com/p/1", "https://shop.example.com/p/3", "https://img.shop.example.com/p/6"]
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use super::progress::JobStage;
+use crate::storage::JobStatus;
+
+/// Events kept for slow subscribers before the oldest are dropped
+const EVENT_CAPACITY: usize = 1024;
+
+/// A step in the life of a job
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobEvent {
+    JobCreated { title: String },
+    StageChanged { stage: JobStage },
+    /// A result row was stored
+    ItemScraped { row_idx: usize, item: serde_json::Value },
+    JobCompleted {
+        /// Rows the job has stored
+        rows: usize,
+        /// Problems the run met without failing, see `ScrapeSummary::issues`
+        warnings: usize,
+    },
+    JobFailed {
+        error: String,
+        /// Troubleshooting code of the error, e.g. `WSS-1003`
+        code: String,
+    },
+    /// A run failed and the job's retry policy runs it again after a wait
+    RetryScheduled {
+        /// Number of the coming run, 2 for the first retry
+        attempt: u32,
+        delay_ms: u64,
+        reason: String,
+    },
+    /// Cancelled, or paused by a shutdown
+    JobStopped { status: JobStatus },
+}
+
+/// An event of one job
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowEvent {
+    pub job_id: String,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: JobEvent,
+}
+
+impl WorkflowEvent {
+    /// Whether the job's run has ended
+    pub fn is_final(&self) -> bool {
+        matches!(self.event, JobEvent::JobCompleted { .. } | JobEvent::JobFailed { .. } | JobEvent::JobStopped { .. })
+    }
+}
+
+/// Broadcasts job lifecycle events to the UI, API and anything else reacting to them
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<WorkflowEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(EVENT_CAPACITY);
+        Self { tx }
+    }
+
+    /// Send an event to every subscriber, if there are any
+    pub fn publish(&self, job_id: &str, event: JobEvent) {
+        let _ = self.tx.send(WorkflowEvent {
+            job_id: job_id.to_string(),
+            at: Utc::now(),
+            event,
+        });
+    }
+
+    /// Receive the events of every job from now on
+    pub fn subscribe(&self) -> EventSubscription {
+        EventSubscription { rx: self.tx.subscribe() }
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Events of every job, received in order
+pub struct EventSubscription {
+    rx: broadcast::Receiver<WorkflowEvent>,
+}
+
+impl EventSubscription {
+    /// Next event; `None` once the bus is gone
+    ///
+    /// A subscriber that falls too far behind skips the events it missed.
+    pub async fn next(&mut self) -> Option<WorkflowEvent> {
+        loop {
+            match self.rx.recv().await {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    debug!("Event subscriber missed {} events", missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Next event already sent, without waiting
+    pub fn try_next(&mut self) -> Option<WorkflowEvent> {
+        loop {
+            match self.rx.try_recv() {
+                Ok(event) => return Some(event),
+                Err(broadcast::error::TryRecvError::Lagged(missed)) => {
+                    debug!("Event subscriber missed {} events", missed);
+                }
+                Err(_) => return None,
+            }
+        }
+    }
+
+    /// Next event of one job
+    pub async fn next_of(&mut self, job_id: &str) -> Option<WorkflowEvent> {
+        loop {
+            let event = self.next().await?;
+            if event.job_id == job_id {
+                return Some(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscription_sees_every_job() {
+        let bus = EventBus::new();
+        let mut subscription = bus.subscribe();
+        bus.publish("a", JobEvent::JobCreated { title: "example.com".to_string() });
+        bus.publish("b", JobEvent::StageChanged { stage: JobStage::Fetching });
+        bus.publish("a", JobEvent::JobCompleted { rows: 3, warnings: 0 });
+
+        assert_eq!(subscription.next().await.unwrap().job_id, "a");
+        let done = subscription.next_of("a").await.unwrap();
+        assert!(done.is_final());
+
+        let json = serde_json::to_value(&done).unwrap();
+        assert_eq!((json["type"].as_str(), json["rows"].as_u64()), (Some("job_completed"), Some(3)));
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+use crate::scraper::ScrapingEngine;
+use crate::storage::StorageManager;
+use super::self_check;
+
+/// Longest a readiness probe waits for a database connection
+const DATABASE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Free space below which a directory's disk is reported as degraded
+const LOW_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Free space below which a directory's disk is reported as down
+const MIN_DISK_BYTES: u64 = 100 * 1024 * 1024;
+
+/// State of a dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    Up,
+    /// Usable, but a feature is missing or a limit is close
+    Degraded,
+    /// Jobs can't run until this is fixed
+    Down,
+}
+
+/// Outcome of one dependency check
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    /// `database`, `browser`, `llm`, `data_disk` or `export_disk`
+    pub name: String,
+    pub status: ComponentState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Whether the application can take jobs, with every dependency's state
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    /// The worst component state
+    pub status: ComponentState,
+    pub checked_at: DateTime<Utc>,
+    pub components: Vec<ComponentHealth>,
+}
+
+impl ReadinessReport {
+    /// Ready unless a component is down
+    pub fn is_ready(&self) -> bool {
+        self.status != ComponentState::Down
+    }
+}
+
+/// Check the database, browser, LLM and free disk space
+pub async fn check_readiness(config: &AppConfig, storage: &StorageManager, scraper: &ScrapingEngine) -> ReadinessReport {
+    let mut components = Vec::new();
+
+    let started = Instant::now();
+    let (status, message) = match tokio::time::timeout(DATABASE_TIMEOUT, storage.ping()).await {
+        Ok(Ok(())) => (ComponentState::Up, None),
+        Ok(Err(e)) => (ComponentState::Down, Some(format!("Query failed: {}", e))),
+        Err(_) => (ComponentState::Down, Some(format!("No connection within {}s", DATABASE_TIMEOUT.as_secs()))),
+    };
+    components.push(component("database", started, status, message));
+
+    let started = Instant::now();
+    let (status, message) = if !config.scraping.enable_browser_fallback {
+        (ComponentState::Up, Some("Browser fallback is disabled".to_string()))
+    } else if scraper.has_browser() {
+        (ComponentState::Up, None)
+    } else {
+        (ComponentState::Degraded, Some("Browser fallback is enabled but this build has no browser support".to_string()))
+    };
+    components.push(component("browser", started, status, message));
+
+    let started = Instant::now();
+    let (status, message) = match self_check::check_llm(config) {
+        Some(problem) => (ComponentState::Degraded, Some(problem.message)),
+        None => (ComponentState::Up, None),
+    };
+    components.push(component("llm", started, status, message));
+
+    for (name, dir) in [("data_disk", config.get_data_dir()), ("export_disk", config.export.output_directory.clone())] {
+        let started = Instant::now();
+        let (status, message) = disk_state(&dir);
+        components.push(component(name, started, status, message));
+    }
+
+    ReadinessReport {
+        status: components.iter().map(|c| c.status).max().unwrap_or(ComponentState::Up),
+        checked_at: Utc::now(),
+        components,
+    }
+}
+
+fn component(name: &str, started: Instant, status: ComponentState, message: Option<String>) -> ComponentHealth {
+    ComponentHealth {
+        name: name.to_string(),
+        status,
+        message,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Free space on the disk holding `dir`
+fn disk_state(dir: &Path) -> (ComponentState, Option<String>) {
+    let Some(available) = available_space(dir) else {
+        return (ComponentState::Degraded, Some(format!("Free space of {} is unknown", dir.display())));
+    };
+    let message = format!("{} MB free for {}", available / (1024 * 1024), dir.display());
+    let status = match available {
+        a if a < MIN_DISK_BYTES => ComponentState::Down,
+        a if a < LOW_DISK_BYTES => ComponentState::Degraded,
+        _ => ComponentState::Up,
+    };
+    (status, Some(message))
+}
+
+/// Available bytes on the mounted disk `dir` is on, by longest mount point
+fn available_space(dir: &Path) -> Option<u64> {
+    let dir = std::fs::canonicalize(dir).or_else(|_| std::env::current_dir()).ok()?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks.list().iter()
+        .filter(|disk| dir.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
@@ -1,19 +1,36 @@
 use anyhow::Result;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, RwLock};
 use tracing::{info, warn, error};
 use chrono::{DateTime, Utc};
 
-use crate::storage::{StorageManager, JobStatus, JobResult};
-use crate::dsl::ScrapePlan;
+use crate::storage::{content_hash, AttemptOutcome, JobAttempt, LogEntry, StorageManager, JobStatus, JobResult, FINISHED_STAGE};
+use crate::dsl::{RetryPolicy, ScrapePlan};
+use crate::error::{describe, ErrorCode};
+use crate::scraper::{ScrapeSummary, ScrapeUpdate, ScrapingEngine};
+use crate::scraper::recording::{Recorder, Recording, RECORD_METADATA_KEY, REPLAY_METADATA_KEY};
+use super::events::{EventBus, JobEvent};
+use super::progress::{JobStage, ProgressEvent, ProgressHub};
+use super::shutdown::Shutdown;
+
+/// Stage logged while a run sets up, before its first request
+const STARTING_STAGE: &str = "starting";
+
+/// How often [`JobManager::wait_until_idle`] checks for running jobs
+const IDLE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
 
 /// Manages job execution and lifecycle
 pub struct JobManager {
     storage: Arc<StorageManager>,
+    /// Configured engine every job runs on, so they share its security checks and per-host state
+    scraper: Arc<ScrapingEngine>,
     active_jobs: HashMap<String, JobHandle>,
     job_queue: Vec<QueuedJob>,
     max_concurrent_jobs: usize,
+    progress: ProgressHub,
+    events: EventBus,
+    shutdown: Shutdown,
 }
 
 /// Handle for an active job
@@ -41,26 +58,53 @@ pub enum JobPriority {
 }
 
 impl JobManager {
-    pub fn new(storage: Arc<StorageManager>) -> Self {
+    pub fn new(
+        storage: Arc<StorageManager>,
+        scraper: Arc<ScrapingEngine>,
+        progress: ProgressHub,
+        events: EventBus,
+        shutdown: Shutdown,
+    ) -> Self {
         Self {
             storage,
+            scraper,
             active_jobs: HashMap::new(),
             job_queue: Vec::new(),
             max_concurrent_jobs: 3, // Configurable limit
+            progress,
+            events,
+            shutdown,
         }
     }
     
     /// Execute a scraping job
-    pub async fn execute_job(&mut self, job_id: &str, dsl: ScrapePlan) -> Result<()> {
+    ///
+    /// The manager stays locked while an attempt runs and is released while a
+    /// failed attempt waits for its retry, so other jobs can start meanwhile.
+    /// Jobs queued behind this one run once it has ended.
+    pub async fn execute_job(manager: &RwLock<JobManager>, job_id: &str, dsl: ScrapePlan) -> Result<()> {
         info!("Executing job: {}", job_id);
         
         // Check if we can start immediately or need to queue
-        if self.active_jobs.len() >= self.max_concurrent_jobs {
-            self.queue_job(job_id, dsl, JobPriority::Normal).await?;
-            return Ok(());
+        {
+            let mut this = manager.write().await;
+            if this.active_jobs.len() >= this.max_concurrent_jobs {
+                this.queue_job(job_id, dsl, JobPriority::Normal).await?;
+                return Ok(());
+            }
         }
         
-        self.start_job(job_id, dsl).await
+        Self::start_job(manager, job_id, dsl).await?;
+        loop {
+            let next_job = {
+                let mut this = manager.write().await;
+                if this.job_queue.is_empty() {
+                    return Ok(());
+                }
+                this.job_queue.remove(0)
+            };
+            Self::start_job(manager, &next_job.job_id, next_job.dsl).await?;
+        }
     }
     
     /// Queue a job for later execution
@@ -83,16 +127,30 @@ impl JobManager {
         
         // Update job status in storage
         self.storage.update_job_status(job_id, JobStatus::Queued).await?;
+        self.progress.publish(job_id, ProgressEvent::Stage { stage: JobStage::Queued, total_urls: None });
+        self.events.publish(job_id, JobEvent::StageChanged { stage: JobStage::Queued });
+        log_job(&self.storage, job_id, JobStage::Queued.as_str(), "info", "Waiting for a free slot".to_string()).await;
         
         Ok(())
     }
     
     /// Start executing a job immediately
-    async fn start_job(&mut self, job_id: &str, dsl: ScrapePlan) -> Result<()> {
+    async fn start_job(manager: &RwLock<JobManager>, job_id: &str, dsl: ScrapePlan) -> Result<()> {
         info!("Starting job execution: {}", job_id);
+        let mut this = manager.write().await;
+        
+        // Jobs that waited for their turn while the application was closing run after the restart
+        if this.shutdown.is_triggered() {
+            info!("Job {} paused before it started", job_id);
+            this.storage.update_job_status(job_id, JobStatus::Paused).await?;
+            this.progress.publish(job_id, ProgressEvent::Finished { status: JobStatus::Paused });
+            this.events.publish(job_id, JobEvent::JobStopped { status: JobStatus::Paused });
+            log_job(&this.storage, job_id, FINISHED_STAGE, "info", "Paused before it started".to_string()).await;
+            return Ok(());
+        }
         
         // Create cancellation channel
-        let (cancel_tx, cancel_rx) = mpsc::channel(1);
+        let (cancel_tx, mut cancel_rx) = mpsc::channel(1);
         
         // Create job handle
         let handle = JobHandle {
@@ -102,43 +160,151 @@ impl JobManager {
             cancel_tx,
         };
         
-        self.active_jobs.insert(job_id.to_string(), handle);
+        this.active_jobs.insert(job_id.to_string(), handle);
         
         // Update status in storage
-        self.storage.update_job_status(job_id, JobStatus::Running).await?;
+        this.storage.update_job_status(job_id, JobStatus::Running).await?;
         
-        // Spawn job execution task
         let job_id_clone = job_id.to_string();
-        let storage_clone = self.storage.clone();
+        let storage_clone = this.storage.clone();
+        drop(this);
         
-        // Execute job in a separate task
         // Note: We need to handle the fact that scraper crate types are not Send.
         // We'll execute the job synchronously in this context instead of spawning.
-        let result = execute_scraping_job(
-            &job_id_clone,
-            dsl,
-            storage_clone.clone(),
-            cancel_rx,
-        ).await;
+        let result = Self::run_attempts(manager, job_id, dsl, &mut cancel_rx).await;
+        let mut this = manager.write().await;
+        this.active_jobs.remove(job_id);
         
-        match result {
-            Ok(_) => {
-                info!("Job {} completed successfully", job_id_clone);
-                if let Err(e) = storage_clone.update_job_status(&job_id_clone, JobStatus::Completed).await {
-                    error!("Failed to update job status: {}", e);
+        let (status, event, level, message) = match result {
+            Ok(RunOutcome::Completed { rows, summary }) => {
+                let warnings = summary.warning_count();
+                if warnings == 0 {
+                    info!("Job {} completed successfully", job_id_clone);
+                    (JobStatus::Completed, JobEvent::JobCompleted { rows, warnings }, "info", format!("Completed with {} rows", rows))
+                } else {
+                    let kinds: Vec<String> = summary.issues.counts().into_iter()
+                        .map(|(kind, count)| format!("{} {}", count, kind))
+                        .collect();
+                    info!("Job {} completed with {} warnings", job_id_clone, warnings);
+                    let message = format!("Completed with {} rows and {} warnings ({})", rows, warnings, kinds.join(", "));
+                    (JobStatus::Completed, JobEvent::JobCompleted { rows, warnings }, "warn", message)
                 }
             }
+            Ok(RunOutcome::Paused) => {
+                info!("Job {} paused for shutdown", job_id_clone);
+                let message = "Paused by a shutdown; resumes on the next start".to_string();
+                (JobStatus::Paused, JobEvent::JobStopped { status: JobStatus::Paused }, "info", message)
+            }
+            Ok(RunOutcome::Cancelled) => {
+                (JobStatus::Cancelled, JobEvent::JobStopped { status: JobStatus::Cancelled }, "warn", "Cancelled".to_string())
+            }
             Err(e) => {
-                error!("Job {} failed: {}", job_id_clone, e);
-                if let Err(e) = storage_clone.update_job_status(&job_id_clone, JobStatus::Failed).await {
-                    error!("Failed to update job status: {}", e);
-                }
+                let code = ErrorCode::of(&e);
+                error!("Job {} failed [{}]: {}", job_id_clone, code, e);
+                this.progress.publish(&job_id_clone, ProgressEvent::Error { message: e.to_string() });
+                let event = JobEvent::JobFailed { error: e.to_string(), code: code.to_string() };
+                (JobStatus::Failed, event, "error", format!("Failed: {}", describe(&e)))
             }
+        };
+        log_job(&storage_clone, &job_id_clone, FINISHED_STAGE, level, message).await;
+        if let Err(e) = storage_clone.update_job_status(&job_id_clone, status.clone()).await {
+            error!("Failed to update job status: {}", e);
         }
+        this.progress.publish(&job_id_clone, ProgressEvent::Finished { status });
+        this.events.publish(&job_id_clone, event);
         
         Ok(())
     }
     
+    /// Run a job, then run it again as long as its plan's retry policy allows
+    ///
+    /// Every run is recorded as an attempt of the job. Rows of earlier runs
+    /// stay stored, so a retry only adds the rows they were missing.
+    async fn run_attempts(manager: &RwLock<JobManager>, job_id: &str, dsl: ScrapePlan, cancel_rx: &mut mpsc::Receiver<()>) -> Result<RunOutcome> {
+        let mut this = manager.write().await;
+        let policy = dsl.retry.clone().unwrap_or(RetryPolicy { max_attempts: 1, ..Default::default() });
+        // Runs before a pause count towards the limit, but a resumed job always runs once
+        let mut attempt = this.storage.get_job_attempts(job_id).await?.len() as u32 + 1;
+        let max_attempts = policy.max_attempts.max(attempt);
+        let mut run_plan = dsl.clone();
+        // Counts of the runs so far while only failed URLs are fetched again
+        let mut earlier: Option<ScrapeSummary> = None;
+        
+        loop {
+            let started_at = Utc::now();
+            log_job(&this.storage, job_id, STARTING_STAGE, "info", format!("Attempt {} of {} started", attempt, max_attempts)).await;
+            let mut result = execute_scraping_job(
+                job_id,
+                run_plan.clone(),
+                &this.scraper,
+                this.storage.clone(),
+                cancel_rx,
+                &this.progress,
+                &this.events,
+                &this.shutdown,
+            ).await;
+            
+            if let (Ok(RunOutcome::Completed { summary, .. }), Some(earlier)) = (&mut result, earlier.take()) {
+                *summary = earlier.merge_retry(std::mem::take(summary));
+                this.storage.update_job_summary(job_id, &serde_json::to_string(summary)?).await?;
+            }
+            
+            let (outcome, urls, failed_urls, error) = match &result {
+                Ok(RunOutcome::Paused) => return result,
+                Ok(RunOutcome::Completed { summary, .. }) => {
+                    let failed_urls = summary.failed_urls();
+                    let outcome = if failed_urls.is_empty() { AttemptOutcome::Completed } else { AttemptOutcome::Partial };
+                    (outcome, summary.requests.len(), failed_urls, None)
+                }
+                Ok(RunOutcome::Cancelled) => (AttemptOutcome::Cancelled, 0, Vec::new(), None),
+                Err(e) => (AttemptOutcome::Failed, 0, Vec::new(), Some(e.to_string())),
+            };
+            let record = JobAttempt {
+                job_id: job_id.to_string(),
+                attempt,
+                started_at,
+                finished_at: Utc::now(),
+                outcome,
+                urls,
+                failed_urls: failed_urls.len(),
+                rows: this.storage.get_job_result_count(job_id).await?,
+                error: error.clone(),
+            };
+            this.storage.record_job_attempt(&record).await?;
+            
+            if !outcome.is_retryable() || attempt >= max_attempts {
+                return result;
+            }
+            
+            let delay = policy.backoff(attempt);
+            let reason = error.unwrap_or_else(|| format!("{} of {} URLs failed", failed_urls.len(), urls));
+            warn!("Attempt {} of job {} failed ({}); retrying in {}s", attempt, job_id, reason, delay.as_secs());
+            let message = format!("Attempt {} failed ({}); retrying in {}s", attempt, reason, delay.as_secs());
+            log_job(&this.storage, job_id, JobStage::Retrying.as_str(), "warn", message).await;
+            attempt += 1;
+            this.progress.publish(job_id, ProgressEvent::Stage { stage: JobStage::Retrying, total_urls: None });
+            this.events.publish(job_id, JobEvent::RetryScheduled { attempt, delay_ms: delay.as_millis() as u64, reason });
+            
+            // Other jobs can use the manager while this one waits
+            let shutdown = this.shutdown.clone();
+            drop(this);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.wait() => return Ok(RunOutcome::Paused),
+                _ = cancel_rx.recv() => return Ok(RunOutcome::Cancelled),
+            }
+            this = manager.write().await;
+            
+            run_plan = dsl.clone();
+            if let (true, Ok(RunOutcome::Completed { summary, .. })) = (policy.only_failed_urls, result) {
+                info!("Attempt {} of job {} fetches its {} failed URLs", attempt, job_id, failed_urls.len());
+                run_plan.target.start_urls = failed_urls;
+                run_plan.target.url_patterns = None;
+                earlier = Some(summary);
+            }
+        }
+    }
+    
     /// Cancel a running job
     pub async fn cancel_job(&mut self, job_id: &str) -> Result<()> {
         info!("Cancelling job: {}", job_id);
@@ -151,17 +317,19 @@ impl JobManager {
             
             // Update status
             self.storage.update_job_status(job_id, JobStatus::Cancelled).await?;
+            self.progress.publish(job_id, ProgressEvent::Finished { status: JobStatus::Cancelled });
+            self.events.publish(job_id, JobEvent::JobStopped { status: JobStatus::Cancelled });
             
-            // Remove from active jobs
+            // Remove from active jobs; the job's own run starts the next queued one
             self.active_jobs.remove(job_id);
-            
-            // Try to start next queued job
-            self.try_start_next_job().await?;
         } else {
             // Check if it's in the queue
             if let Some(pos) = self.job_queue.iter().position(|job| job.job_id == job_id) {
                 self.job_queue.remove(pos);
                 self.storage.update_job_status(job_id, JobStatus::Cancelled).await?;
+                log_job(&self.storage, job_id, FINISHED_STAGE, "warn", "Cancelled while queued".to_string()).await;
+                self.progress.publish(job_id, ProgressEvent::Finished { status: JobStatus::Cancelled });
+                self.events.publish(job_id, JobEvent::JobStopped { status: JobStatus::Cancelled });
             } else {
                 warn!("Job {} not found in active jobs or queue", job_id);
             }
@@ -170,13 +338,11 @@ impl JobManager {
         Ok(())
     }
     
-    /// Try to start the next queued job if there's capacity
-    async fn try_start_next_job(&mut self) -> Result<()> {
-        if self.active_jobs.len() < self.max_concurrent_jobs && !self.job_queue.is_empty() {
-            let next_job = self.job_queue.remove(0);
-            self.start_job(&next_job.job_id, next_job.dsl).await?;
+    /// Wait until no job is running or waiting for a retry
+    pub async fn wait_until_idle(manager: &RwLock<JobManager>) {
+        while !manager.read().await.active_jobs.is_empty() {
+            tokio::time::sleep(IDLE_POLL_INTERVAL).await;
         }
-        Ok(())
     }
     
     /// Get status of all jobs
@@ -197,6 +363,8 @@ impl JobManager {
     }
     
     /// Clean up completed jobs
+    ///
+    /// Queued jobs are started by the runs that end, not here.
     pub async fn cleanup_completed_jobs(&mut self) -> Result<()> {
         let completed_jobs: Vec<String> = self.active_jobs
             .iter()
@@ -206,75 +374,436 @@ impl JobManager {
         
         for job_id in completed_jobs {
             self.active_jobs.remove(&job_id);
-            self.try_start_next_job().await?;
         }
         
         Ok(())
     }
 }
 
+/// How a run that didn't fail ended
+enum RunOutcome {
+    Completed { rows: usize, summary: ScrapeSummary },
+    /// Stopped by a shutdown with the rows so far stored
+    Paused,
+    Cancelled,
+}
+
 /// Execute the actual scraping job
+///
+/// A job that was paused before carries on after the rows it already stored:
+/// those are left out of this run and the new ones are numbered after them.
 async fn execute_scraping_job(
     job_id: &str,
-    dsl: ScrapePlan,
+    mut dsl: ScrapePlan,
+    engine: &ScrapingEngine,
     storage: Arc<StorageManager>,
-    mut cancel_rx: mpsc::Receiver<()>,
-) -> Result<()> {
+    cancel_rx: &mut mpsc::Receiver<()>,
+    progress: &ProgressHub,
+    events: &EventBus,
+    shutdown: &Shutdown,
+) -> Result<RunOutcome> {
     info!("Executing scraping for job: {}", job_id);
     
-    // Initialize scraping engine (this would normally be passed in)
-    let scraping_config = crate::config::ScrapingConfig::default();
-    let scraper = crate::scraper::ScrapingEngine::new(&scraping_config).await?;
+    let scraper = engine.clone_for_task();
+    
+    // Answer from or save to a HAR file when the command line asked for it, see RecordingPaths
+    let scraper = match dsl.get_metadata(REPLAY_METADATA_KEY).and_then(|path| path.as_str()) {
+        Some(path) => {
+            let recording = Recording::load(std::path::Path::new(path)).await?;
+            let message = format!("Replaying {} recorded responses from {}", recording.len(), path);
+            log_job(&storage, job_id, STARTING_STAGE, "info", message).await;
+            scraper.replaying(Arc::new(recording))
+        }
+        None => scraper,
+    };
+    let recording_to = dsl.get_metadata(RECORD_METADATA_KEY)
+        .and_then(|path| path.as_str())
+        .map(|path| (std::path::PathBuf::from(path), Arc::new(Recorder::new())));
+    let scraper = match &recording_to {
+        Some((_, recorder)) => scraper.recording_to(recorder.clone()),
+        None => scraper,
+    };
+    
+    let hash_fields = dsl.output.hash_fields.as_deref();
+    storage.set_job_hash_fields(job_id, hash_fields).await?;
     
-    // Execute scraping with cancellation support
-    let scraping_future = scraper.execute_scraping(&dsl);
-    let cancellation_future = cancel_rx.recv();
+    let seen_index = dsl.output.skip_seen.then(|| dsl.seen_index_key());
+    let mut seen = match &seen_index {
+        Some(key) => storage.load_seen_items(key).await?,
+        None => Default::default(),
+    };
     
-    tokio::select! {
-        result = scraping_future => {
-            match result {
-                Ok(results) => {
-                    info!("Scraping completed for job: {}, {} results", job_id, results.len());
-                    
-                    // Store results
-                    for (idx, result) in results.into_iter().enumerate() {
-                        let job_result = JobResult {
-                            job_id: job_id.to_string(),
-                            row_idx: idx as i32,
-                            data_json: serde_json::to_string(&result)?,
-                            url: result.get("_source_url")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("unknown")
-                                .to_string(),
-                            fetched_at: Utc::now(),
-                            hash: calculate_result_hash(&result),
-                        };
-                        
-                        storage.store_job_result(&job_result).await?;
+    let resumed = storage.get_job_results(job_id).await?;
+    if !resumed.is_empty() {
+        info!("Resuming job {} after {} stored rows", job_id, resumed.len());
+        seen.extend(resumed.iter().map(|row| crate::scraper::result_buffer::seen_hash(&dsl.output, row)));
+        if let Some(limit) = dsl.output.limit.as_mut() {
+            *limit = limit.saturating_sub(resumed.len());
+        }
+    }
+    let offset = resumed.len();
+    
+    // Execute scraping with cancellation support, storing rows as they arrive
+    // so the results view can tail the job
+    let (update_tx, mut update_rx) = mpsc::unbounded_channel();
+    let scraping_future = scraper.execute_scraping_streaming(&dsl, Some(update_tx), seen);
+    tokio::pin!(scraping_future);
+    let mut streamed: Vec<serde_json::Value> = Vec::new();
+    let stopping = shutdown.wait();
+    tokio::pin!(stopping);
+    
+    let result = loop {
+        tokio::select! {
+            Some(update) = update_rx.recv() => match update {
+                ScrapeUpdate::Started { urls } => {
+                    progress.publish(job_id, ProgressEvent::Stage { stage: JobStage::Fetching, total_urls: Some(urls) });
+                    events.publish(job_id, JobEvent::StageChanged { stage: JobStage::Fetching });
+                    log_job(&storage, job_id, JobStage::Fetching.as_str(), "info", format!("Fetching {} URLs", urls)).await;
+                }
+                ScrapeUpdate::Page { trace, items } => {
+                    record_page(&storage, job_id, &trace, items).await?;
+                    progress.publish(job_id, url_processed(trace, items));
+                }
+                ScrapeUpdate::Rows(rows) => {
+                    store_streamed(&storage, events, job_id, offset, &mut streamed, rows, hash_fields).await?;
+                    progress.publish(job_id, ProgressEvent::Items { total: offset + streamed.len() });
+                }
+            },
+            result = &mut scraping_future => break result,
+            _ = &mut stopping => {
+                // Keep the rows already handed over; the rest are fetched again on resume
+                while let Ok(update) = update_rx.try_recv() {
+                    if let ScrapeUpdate::Rows(rows) = update {
+                        store_streamed(&storage, events, job_id, offset, &mut streamed, rows, hash_fields).await?;
                     }
-                    
-                    info!("Results stored for job: {}", job_id);
-                    Ok(())
                 }
-                Err(e) => {
-                    error!("Scraping failed for job {}: {}", job_id, e);
-                    Err(e)
+                progress.publish(job_id, ProgressEvent::Items { total: offset + streamed.len() });
+                warn!("Job {} paused with {} rows stored", job_id, offset + streamed.len());
+                save_recording(&storage, job_id, recording_to.as_ref()).await;
+                return Ok(RunOutcome::Paused);
+            }
+            _ = cancel_rx.recv() => {
+                warn!("Job {} was cancelled", job_id);
+                save_recording(&storage, job_id, recording_to.as_ref()).await;
+                return Ok(RunOutcome::Cancelled);
+            }
+        }
+    };
+    save_recording(&storage, job_id, recording_to.as_ref()).await;
+    
+    // Pages reported after the run ended; their rows are stored below
+    while let Ok(update) = update_rx.try_recv() {
+        if let ScrapeUpdate::Page { trace, items } = update {
+            record_page(&storage, job_id, &trace, items).await?;
+            progress.publish(job_id, url_processed(trace, items));
+        }
+    }
+    
+    match result {
+        Ok(report) => {
+            let results = report.rows;
+            info!("Scraping completed for job: {}, {} results", job_id, results.len());
+            progress.publish(job_id, ProgressEvent::Stage { stage: JobStage::Storing, total_urls: None });
+            events.publish(job_id, JobEvent::StageChanged { stage: JobStage::Storing });
+            log_job(&storage, job_id, JobStage::Storing.as_str(), "info", format!("Storing {} rows", results.len())).await;
+            
+            storage.update_job_summary(job_id, &serde_json::to_string(&report.summary)?).await?;
+            report.summary.check_missing_required(&dsl.rules.missing_required)?;
+            
+            // Remember which selectors worked for this domain
+            if let Err(e) = remember_selectors(&storage, &dsl, &results).await {
+                warn!("Failed to update selector memory for job {}: {}", job_id, e);
+            }
+            
+            // Streamed rows may since have been merged with duplicates; store the rest
+            for (idx, result) in results.iter().enumerate() {
+                let row_idx = offset + idx;
+                match streamed.get(idx) {
+                    Some(stored) if stored == result => {}
+                    Some(_) => storage.update_job_result(&to_job_result(job_id, row_idx, result, hash_fields)?).await?,
+                    None => {
+                        storage.store_job_result(&to_job_result(job_id, row_idx, result, hash_fields)?).await?;
+                        events.publish(job_id, JobEvent::ItemScraped { row_idx, item: result.clone() });
+                    }
                 }
             }
+            
+            info!("Results stored for job: {}", job_id);
+            progress.publish(job_id, ProgressEvent::Items { total: offset + results.len() });
+            
+            // Only completed runs count, so items of a failed run are fetched again
+            if let Some(key) = &seen_index {
+                let hashes: Vec<String> = resumed.iter().chain(&results)
+                    .map(|row| crate::scraper::result_buffer::seen_hash(&dsl.output, row))
+                    .collect();
+                let added = storage.mark_items_seen(key, job_id, &hashes).await?;
+                info!("Added {} items to the seen index of job {}", added, job_id);
+            }
+            Ok(RunOutcome::Completed { rows: offset + results.len(), summary: report.summary })
         }
-        _ = cancellation_future => {
-            warn!("Job {} was cancelled", job_id);
-            Err(anyhow::anyhow!("Job was cancelled"))
+        Err(e) => {
+            error!("Scraping failed for job {}: {}", job_id, e);
+            Err(e)
         }
     }
 }
 
-/// Calculate hash for deduplication
-fn calculate_result_hash(result: &serde_json::Value) -> String {
-    use sha2::{Sha256, Digest};
+/// Write the responses a run recorded so far to its HAR file
+async fn save_recording(storage: &StorageManager, job_id: &str, recording_to: Option<&(std::path::PathBuf, Arc<Recorder>)>) {
+    let Some((path, recorder)) = recording_to else {
+        return;
+    };
+    let (level, message) = match recorder.save(path).await {
+        Ok(()) => ("info", format!("Recorded {} responses to {}", recorder.len(), path.display())),
+        Err(e) => ("warn", format!("Failed to save the recording to {}: {}", path.display(), e)),
+    };
+    log_job(storage, job_id, JobStage::Storing.as_str(), level, message).await;
+}
+
+/// Store rows as they arrive, numbered after those of earlier runs
+async fn store_streamed(
+    storage: &StorageManager,
+    events: &EventBus,
+    job_id: &str,
+    offset: usize,
+    streamed: &mut Vec<serde_json::Value>,
+    rows: Vec<serde_json::Value>,
+    hash_fields: Option<&[String]>,
+) -> Result<()> {
+    for row in rows {
+        let row_idx = offset + streamed.len();
+        storage.store_job_result(&to_job_result(job_id, row_idx, &row, hash_fields)?).await?;
+        events.publish(job_id, JobEvent::ItemScraped { row_idx, item: row.clone() });
+        streamed.push(row);
+    }
+    Ok(())
+}
+
+/// Record a fetched URL, logging it when the fetch failed
+async fn record_page(storage: &StorageManager, job_id: &str, trace: &crate::scraper::RequestTrace, items: usize) -> Result<()> {
+    storage.record_job_url(job_id, trace, items).await?;
+    if trace.is_failure() {
+        let reason = match (&trace.error, trace.status_code) {
+            (Some(error), _) => error.clone(),
+            (None, Some(code)) => format!("HTTP {}", code),
+            (None, None) => "unknown error".to_string(),
+        };
+        log_job(storage, job_id, JobStage::Fetching.as_str(), "warn", format!("{} failed: {}", trace.url, reason)).await;
+    }
+    if let Some(reason) = &trace.skipped {
+        log_job(storage, job_id, JobStage::Fetching.as_str(), "warn", format!("{} skipped: {}", trace.url, reason)).await;
+    }
+    if let Some(reason) = &trace.circuit_opened {
+        log_job(storage, job_id, JobStage::Fetching.as_str(), "warn", reason.clone()).await;
+    }
+    Ok(())
+}
+
+/// Add a line to a job's stored log, shown in the job detail view
+pub(super) async fn log_job(storage: &StorageManager, job_id: &str, stage: &str, level: &str, message: String) {
+    let entry = LogEntry {
+        job_id: job_id.to_string(),
+        timestamp: Utc::now(),
+        stage: stage.to_string(),
+        level: level.to_string(),
+        message,
+    };
+    if let Err(e) = storage.store_log(&entry).await {
+        warn!("Failed to store a log entry for job {}: {}", job_id, e);
+    }
+}
+
+/// Progress event for a processed URL
+fn url_processed(trace: crate::scraper::RequestTrace, items: usize) -> ProgressEvent {
+    ProgressEvent::UrlProcessed {
+        url: trace.url,
+        status_code: trace.status_code,
+        items,
+        error: trace.error,
+    }
+}
+
+/// Build the stored form of a result row
+fn to_job_result(
+    job_id: &str,
+    row_idx: usize,
+    result: &serde_json::Value,
+    hash_fields: Option<&[String]>,
+) -> Result<JobResult> {
+    Ok(JobResult {
+        job_id: job_id.to_string(),
+        row_idx: row_idx as i32,
+        data_json: serde_json::to_string(result)?,
+        url: result.get("_source_url")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        fetched_at: Utc::now(),
+        hash: content_hash(result, hash_fields),
+    })
+}
+
+/// Fraction of items a field must be present in for its selector to count as working
+const FIELD_SUCCESS_RATIO: f64 = 0.5;
+
+/// Record selector outcomes of a finished job in the per-domain selector memory
+async fn remember_selectors(
+    storage: &StorageManager,
+    dsl: &ScrapePlan,
+    results: &[serde_json::Value],
+) -> Result<()> {
+    use crate::storage::SelectorKind;
+    
+    let domain = dsl.get_domain();
+    let found_items = !results.is_empty();
     
-    let serialized = serde_json::to_string(result).unwrap_or_default();
-    let mut hasher = Sha256::new();
-    hasher.update(serialized.as_bytes());
-    format!("{:x}", hasher.finalize())
+    storage.record_selector_outcome(domain, SelectorKind::Item, "", &dsl.rules.item_selector, found_items).await?;
+    
+    // Field selectors only say something once the item selector matched
+    if found_items {
+        for field in &dsl.rules.fields {
+            let present = results.iter()
+                .filter(|item| match item.get(&field.name) {
+                    Some(serde_json::Value::Null) | None => false,
+                    Some(serde_json::Value::String(s)) => !s.trim().is_empty(),
+                    Some(_) => true,
+                })
+                .count();
+            let success = present as f64 / results.len() as f64 >= FIELD_SUCCESS_RATIO;
+            
+            storage.record_selector_outcome(domain, SelectorKind::Field, &field.name, &field.selector, success).await?;
+        }
+    }
+    
+    // Pagination worked if items came from more than one page
+    if let Some(pagination) = &dsl.rules.pagination {
+        let selector = match &pagination.method {
+            crate::dsl::PaginationMethod::Link { next_selector } => Some(next_selector),
+            crate::dsl::PaginationMethod::Button { button_selector } => Some(button_selector),
+            _ => None,
+        };
+        
+        if let Some(selector) = selector {
+            let pages: std::collections::HashSet<&str> = results.iter()
+                .filter_map(|item| item.get("_source_url").and_then(|v| v.as_str()))
+                .collect();
+            storage.record_selector_outcome(domain, SelectorKind::Pagination, "", selector, pages.len() > 1).await?;
+        }
+    }
+    
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AppConfig, ScrapingConfig};
+    use crate::dsl::{DelayConfig, DelayDistribution};
+    use crate::scraper::testing::{Fixture, FixtureServer};
+    use crate::security::SecurityManager;
+    use crate::storage::{test_storage, Job};
+
+    #[tokio::test]
+    async fn test_job_runs_on_the_configured_engine() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(test_storage(dir.path()).await);
+
+        let server = FixtureServer::new()
+            .with_fixture("/list", Fixture::html("<article><h2>lamp</h2></article>"))
+            .with_fixture("/secret", Fixture::html("<article><h2>secret</h2></article>"))
+            .start()
+            .await
+            .unwrap();
+        // A second port, so the redirect crosses to another host the way an attacker's page would
+        let redirecting = FixtureServer::new()
+            .with_fixture("/moved", Fixture::redirect(302, &server.url("/secret")))
+            .start()
+            .await
+            .unwrap();
+
+        // The engine's security check refuses redirects to internal addresses
+        let config = ScrapingConfig { respect_robots_txt: false, max_concurrent_requests: 20, ..Default::default() };
+        let security = Arc::new(SecurityManager::new(&AppConfig::default().security).unwrap());
+        let scraper = Arc::new(ScrapingEngine::new(&config).await.unwrap().with_security(security));
+
+        let mut plan = ScrapePlan::default();
+        plan.target.domain = server.domain();
+        plan.target.start_urls = vec![server.url("/list"), redirecting.url("/moved")];
+        plan.anti_blocking.respect_robots_txt = false;
+        plan.anti_blocking.randomized_delays = DelayConfig { min_ms: 0, max_ms: 0, distribution: DelayDistribution::Uniform };
+
+        storage.create_job(&Job {
+            id: "job".to_string(),
+            title: "job".to_string(),
+            status: JobStatus::Queued,
+            created_at: Utc::now(),
+            plan_yaml: serde_yaml::to_string(&plan).unwrap(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }).await.unwrap();
+
+        let manager = RwLock::new(JobManager::new(storage.clone(), scraper, ProgressHub::new(), EventBus::new(), Shutdown::new()));
+        JobManager::execute_job(&manager, "job", plan).await.unwrap();
+
+        let job = storage.get_job("job").await.unwrap();
+        assert_eq!(job.status, JobStatus::Completed);
+        let summary: ScrapeSummary = serde_json::from_str(job.summary_json.as_deref().unwrap()).unwrap();
+        assert_eq!((summary.pages_scraped, summary.pages_skipped), (1, 1));
+        assert_eq!(storage.get_job_result_count("job").await.unwrap(), 1);
+        assert_eq!(server.request_count("/secret"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_manager_is_free_while_a_retry_waits() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(test_storage(dir.path()).await);
+
+        let server = FixtureServer::new()
+            .with_fixture("/list", Fixture::html("<article><h2>lamp</h2></article>").failing_first(1, 503))
+            .start()
+            .await
+            .unwrap();
+        let config = ScrapingConfig { respect_robots_txt: false, max_retries: 1, ..Default::default() };
+        let scraper = Arc::new(ScrapingEngine::new(&config).await.unwrap());
+
+        let mut plan = ScrapePlan::default();
+        plan.target.domain = server.domain();
+        plan.target.start_urls = vec![server.url("/list")];
+        plan.anti_blocking.respect_robots_txt = false;
+        plan.anti_blocking.randomized_delays = DelayConfig { min_ms: 0, max_ms: 0, distribution: DelayDistribution::Uniform };
+        plan.retry = Some(RetryPolicy { max_attempts: 2, backoff_ms: 200, ..Default::default() });
+
+        storage.create_job(&Job {
+            id: "job".to_string(),
+            title: "job".to_string(),
+            status: JobStatus::Queued,
+            created_at: Utc::now(),
+            plan_yaml: serde_yaml::to_string(&plan).unwrap(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }).await.unwrap();
+
+        let events = EventBus::new();
+        let mut subscription = events.subscribe();
+        let manager = RwLock::new(JobManager::new(storage.clone(), scraper, ProgressHub::new(), events, Shutdown::new()));
+        let free_during_backoff = async {
+            while let Some(event) = subscription.next().await {
+                if matches!(event.event, JobEvent::RetryScheduled { .. }) {
+                    return manager.try_write().is_ok();
+                }
+            }
+            false
+        };
+        let (result, free) = tokio::join!(JobManager::execute_job(&manager, "job", plan), free_during_backoff);
+        result.unwrap();
+
+        assert!(free);
+        assert_eq!(storage.get_job_attempts("job").await.unwrap().len(), 2);
+        assert_eq!(storage.get_job("job").await.unwrap().status, JobStatus::Completed);
+    }
 }
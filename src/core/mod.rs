@@ -1,24 +1,127 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 pub mod orchestrator;
 pub mod job_manager;
 pub mod pipeline;
+pub mod self_check;
+pub mod bulk;
+pub mod progress;
+pub mod events;
+pub mod health;
+pub mod shutdown;
+pub mod dependencies;
 
 use crate::config::AppConfig;
-use crate::storage::{StorageManager, Job, JobStatus};
-use crate::scraper::ScrapingEngine;
-use crate::llm::LLMProcessor;
-use crate::dsl::{ScrapePlan, DSLValidator};
-use crate::export::{ExportManager, ExportFormat};
-use crate::security::SecurityManager;
+use progress::{JobStage, ProgressEvent, ProgressHub, ProgressSubscription};
+use events::{EventBus, EventSubscription, JobEvent};
+use shutdown::Shutdown;
+use crate::storage::{StorageManager, ArchiveReport, AuditEventFilter, BackupInfo, FailureReport, Job, JobAttempt, JobUrl, LogEntry, StageTiming, JobFilter, JobStatus, ColumnStats, DayCount, Histogram, TagCount, LibraryPlan, PlanTemplate, ResultChanges, ResultRow, SelectorKind, SelectorMemoryEntry, User};
+use crate::scraper::{DryRunReport, ExecutionPlan, ScrapingEngine};
+use crate::scraper::post_process::ScriptSandbox;
+use crate::scraper::recording::RecordingPaths;
+use crate::llm::{processor, GenerationEvent, GenerationStream, LLMProcessor};
+use crate::llm::repair::{self, RepairAttempt, RepairOutcome, RepairTrace};
+use crate::dsl::{explain, template, DSLGenerator, InspectedPage, NotifyOptions, PageSummary, PlanBundle, PolitenessPreset, PlanExplanation, ScrapePlan, SelectorSuggestions, DSLValidator, LintWarning, ValidationIssue};
+use crate::export::{ExportDestination, ExportManager, ExportFormat, ExportOptions, ExportProfile, ExportProgress, ProgressRows, SinkReport};
+use crate::export::filename::FilenameContext;
+use crate::export::sample::{self, SampleOptions};
+use crate::error::ErrorCode;
+use crate::i18n::{self, Language};
+use crate::logging::{self, AuditEvent, AuditLogger, LogContext, LogLine};
+use crate::notifications::{Notification, Notifier};
+use crate::security::{SecurityManager, SecurityReport};
+use crate::utils::time_utils;
+
+/// Minimum aged confidence for a remembered selector to replace a generated one
+const SELECTOR_MEMORY_MIN_CONFIDENCE: f64 = 0.6;
+
+/// Job plan metadata key holding the random seed a run used
+pub const RANDOM_SEED_METADATA_KEY: &str = "random_seed";
+
+/// Most runs of one plan offered for comparison
+const PLAN_RUNS_LIMIT: usize = 100;
+
+/// Longest wait for running jobs to pause when shutting down
+const SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// A batch of result rows from a job being followed
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ResultTail {
+    pub rows: Vec<ResultRow>,
+    /// Pass as `after_row_idx` to fetch the next batch
+    pub next_after: Option<i32>,
+    pub status: JobStatus,
+    /// The job has ended and every stored row has been returned
+    pub finished: bool,
+}
+
+/// Outcome of fetching a job's failed URLs again
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RescrapeReport {
+    pub job_id: String,
+    /// Failed URLs that were fetched again
+    pub urls: usize,
+    /// URLs that failed again
+    pub still_failed: usize,
+    /// Rows added to the job's results
+    pub rows_added: usize,
+    pub status: JobStatus,
+}
+
+/// A job with what its runs recorded, for the job detail view
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct JobDetail {
+    pub job: Job,
+    pub logs: Vec<LogEntry>,
+    /// Time spent in each stage, from the log
+    pub stages: Vec<StageTiming>,
+    pub attempts: Vec<JobAttempt>,
+    pub urls: Vec<JobUrl>,
+    pub failures: FailureReport,
+    /// Problems the last run met without failing
+    pub issues: crate::scraper::IssueReport,
+}
+
+/// Column statistics and chart data for a job's results
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct JobAnalytics {
+    pub columns: Vec<ColumnStats>,
+    /// One histogram per numeric column
+    pub histograms: Vec<Histogram>,
+    pub items_per_day: Vec<DayCount>,
+}
+
+/// Bins in each numeric column's histogram
+pub const HISTOGRAM_BINS: usize = 20;
+
+/// Everything checked before a plan runs, fetching nothing but robots.txt
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Simulation {
+    pub issues: Vec<ValidationIssue>,
+    pub lint: Vec<LintWarning>,
+    pub security: SecurityReport,
+    /// Requests the run would make; `None` when the plan is invalid or unsafe
+    pub execution: Option<ExecutionPlan>,
+}
+
+impl Simulation {
+    /// Whether the plan could be approved and run as it is
+    pub fn is_runnable(&self) -> bool {
+        self.issues.is_empty()
+            && !self.security.has_errors()
+            && self.execution.as_ref().is_some_and(|execution| !execution.urls.is_empty())
+    }
+}
 
 /// Core application state and orchestrator
 pub struct WinScrapeStudio {
-    config: AppConfig,
+    /// Shared with the UI and API clones so saved settings reach all of them
+    config: Arc<std::sync::RwLock<AppConfig>>,
     storage: Arc<StorageManager>,
     scraper: Arc<ScrapingEngine>,
     llm: Arc<LLMProcessor>,
@@ -26,6 +129,10 @@ pub struct WinScrapeStudio {
     export_manager: Arc<ExportManager>,
     security_manager: Arc<SecurityManager>,
     job_manager: Arc<RwLock<job_manager::JobManager>>,
+    progress: ProgressHub,
+    events: EventBus,
+    shutdown: Shutdown,
+    notifier: Notifier,
 }
 
 impl WinScrapeStudio {
@@ -33,20 +140,65 @@ impl WinScrapeStudio {
     pub async fn new(config: AppConfig) -> Result<Self> {
         info!("Initializing WinScrape Studio core");
         
+        // Show and export times in the configured zone; storage stays in UTC
+        time_utils::set_timezone(config.ui.timezone.parse()?);
+        if let Some(language) = Language::from_code(&config.ui.language) {
+            i18n::set_language(language);
+        }
+        // Community translations can be dropped in and edited while the app runs
+        i18n::watch_translation_overrides(config.get_translations_dir());
+        
         // Initialize storage layer
         let storage = Arc::new(StorageManager::new(&config.database).await?);
         info!("Storage manager initialized");
         
+        // Move old results out of the database without delaying startup
+        if let Some(days) = config.database.archive_after_days {
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                if let Err(e) = storage.archive_results_older_than(days).await {
+                    warn!("Result archival failed: {}", e);
+                }
+            });
+        }
+        
+        // Keep security events for review in the Security tab, for as long as configured
+        if let Some(days) = config.security.audit_retention_days {
+            let storage = storage.clone();
+            tokio::spawn(async move {
+                if let Err(e) = storage.prune_audit_events(days).await {
+                    warn!("Pruning audit events failed: {}", e);
+                }
+            });
+        }
+        let (audit_tx, mut audit_rx) = tokio::sync::mpsc::unbounded_channel::<AuditEvent>();
+        AuditLogger::set_sink(audit_tx);
+        let audit_storage = storage.clone();
+        tokio::spawn(async move {
+            while let Some(event) = audit_rx.recv().await {
+                if let Err(e) = audit_storage.store_audit_event(&event).await {
+                    warn!("Failed to store audit event: {}", e);
+                }
+            }
+        });
+        
         // Initialize LLM processor
         let llm = Arc::new(LLMProcessor::new(&config.llm).await?);
         info!("LLM processor initialized");
         
-        // Initialize scraping engine
-        let scraper = Arc::new(ScrapingEngine::new(&config.scraping).await?);
-        info!("Scraping engine initialized");
+        // Plugins must be in place before plans using them are validated
+        if config.plugins.enabled {
+            let dir = config.get_plugins_dir();
+            match crate::plugins::load_directory(&dir) {
+                Ok(loaded) if !loaded.is_empty() => info!("Loaded {} plugins from {}", loaded.len(), dir.display()),
+                Ok(_) => {}
+                Err(e) => warn!("Failed to read plugins directory {}: {}", dir.display(), e),
+            }
+        }
+        
         
         // Initialize DSL validator
-        let dsl_validator = Arc::new(DSLValidator::new());
+        let dsl_validator = Arc::new(DSLValidator::new().with_scripts(ScriptSandbox::from_config(&config)));
         info!("DSL validator initialized");
         
         // Initialize export manager
@@ -57,14 +209,28 @@ impl WinScrapeStudio {
         let security_manager = Arc::new(SecurityManager::new(&config.security)?);
         info!("Security manager initialized");
         
+        // Initialize scraping engine, checking redirects against the security settings
+        let scraper = Arc::new(
+            ScrapingEngine::new(&config.scraping).await?
+                .with_security(security_manager.clone())
+                .with_scripts(ScriptSandbox::from_config(&config))
+                .with_storage(storage.clone())
+        );
+        info!("Scraping engine initialized");
+        
         // Initialize job manager
+        let progress = ProgressHub::new();
+        let events = EventBus::new();
+        let shutdown = Shutdown::new();
         let job_manager = Arc::new(RwLock::new(
-            job_manager::JobManager::new(storage.clone())
+            job_manager::JobManager::new(storage.clone(), scraper.clone(), progress.clone(), events.clone(), shutdown.clone())
         ));
         info!("Job manager initialized");
         
+        let notifier = Notifier::new(&config.notifications);
+        
         Ok(Self {
-            config,
+            config: Arc::new(std::sync::RwLock::new(config)),
             storage,
             scraper,
             llm,
@@ -72,18 +238,85 @@ impl WinScrapeStudio {
             export_manager,
             security_manager,
             job_manager,
+            progress,
+            events,
+            shutdown,
+            notifier,
         })
     }
     
+    /// The configuration in effect, including settings saved since startup
+    pub fn config(&self) -> AppConfig {
+        self.config.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    
+    /// Validate, save and apply a changed configuration
+    ///
+    /// Scraping limits, robots.txt handling, the display zone and the interface
+    /// language apply from now on; everything else takes effect on restart.
+    pub async fn update_config(&self, config: AppConfig) -> Result<()> {
+        config.validate()?;
+        let timezone = config.ui.timezone.parse()?;
+        config.save().await?;
+        
+        self.scraper.apply_config(&config.scraping);
+        time_utils::set_timezone(timezone);
+        if let Some(language) = Language::from_code(&config.ui.language) {
+            i18n::set_language(language);
+        }
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config;
+        info!("Configuration updated");
+        Ok(())
+    }
+    
     /// Generate DSL from natural language description
     pub async fn generate_dsl(&self, description: &str) -> Result<ScrapePlan> {
+        self.generate_dsl_streaming(description, None).await
+    }
+    
+    /// Generate DSL on a background task, streaming the model's output
+    ///
+    /// The stream carries tokens and partial YAML while the model writes, then
+    /// ends with the validated plan or the error.
+    pub fn generate_dsl_stream(self: Arc<Self>, description: String) -> GenerationStream {
+        let (events, stream) = GenerationStream::channel();
+        tokio::spawn(async move {
+            let result = self.generate_dsl_streaming(&description, Some(events.clone())).await;
+            let _ = events.send(match result {
+                Ok(dsl) => GenerationEvent::Done(Box::new(dsl)),
+                Err(e) => GenerationEvent::Failed(e.to_string()),
+            });
+        });
+        stream
+    }
+    
+    /// Generate DSL, sending the model's output to `events` as it is produced
+    pub async fn generate_dsl_streaming(
+        &self,
+        description: &str,
+        events: Option<tokio::sync::mpsc::UnboundedSender<GenerationEvent>>,
+    ) -> Result<ScrapePlan> {
         info!("Generating DSL from description: {}", description);
         
         // Security check on input
         self.security_manager.validate_input(description)?;
         
-        // Generate DSL using LLM
-        let dsl = self.llm.generate_dsl(description).await?;
+        // Generate DSL using LLM, grounded in the target page when it can be fetched
+        let page = self.summarize_target_page(description).await;
+        let preset = PolitenessPreset::from_description(description);
+        let mut dsl = self.llm.generate_dsl_streaming(description, preset, page.as_ref(), events).await?;
+        
+        // Prefer selectors that worked on this domain before
+        match self.apply_selector_memory(&mut dsl).await {
+            Ok(0) => {}
+            Ok(applied) => info!("Applied {} remembered selectors for {}", applied, dsl.target.domain),
+            Err(e) => warn!("Selector memory lookup failed: {}", e),
+        }
+        
+        // Send invalid model output back for repair before giving up on it
+        if dsl.get_metadata("generator").is_some() {
+            dsl = self.repair_generated_dsl(dsl, description, preset, page.as_ref()).await?;
+        }
         
         // Validate generated DSL
         self.dsl_validator.validate(&dsl)?;
@@ -92,8 +325,238 @@ impl WinScrapeStudio {
         Ok(dsl)
     }
     
+    /// Feed validation and security problems back to the model until the plan passes
+    ///
+    /// Falls back to rule-based generation once `max_repair_attempts` rounds fail.
+    /// Whenever a repair was needed the plan carries a `repair_trace` in its metadata.
+    async fn repair_generated_dsl(
+        &self,
+        mut dsl: ScrapePlan,
+        description: &str,
+        preset: PolitenessPreset,
+        page: Option<&PageSummary>,
+    ) -> Result<ScrapePlan> {
+        let max_attempts = self.llm.max_repair_attempts();
+        let mut attempts = Vec::new();
+        
+        loop {
+            let issues = self.plan_issues(&dsl);
+            if issues.is_empty() {
+                if !attempts.is_empty() {
+                    info!("Generated plan repaired after {} attempts", attempts.len());
+                    RepairTrace { attempts, outcome: RepairOutcome::Repaired, remaining_issues: Vec::new() }.record(&mut dsl);
+                }
+                return Ok(dsl);
+            }
+            
+            if attempts.len() >= max_attempts {
+                warn!("Generated plan still has {} problems, using rule-based generation", issues.len());
+                let mut fallback = self.llm.generate_fallback(description, preset, page)?;
+                if self.apply_selector_memory(&mut fallback).await.is_err() {
+                    warn!("Selector memory lookup failed for fallback plan");
+                }
+                RepairTrace { attempts, outcome: RepairOutcome::FellBack, remaining_issues: issues }.record(&mut fallback);
+                return Ok(fallback);
+            }
+            
+            let attempt = attempts.len() + 1;
+            debug!("Repair attempt {} for {} problems", attempt, issues.len());
+            let error = match self.llm.repair_dsl(&dsl, &issues).await {
+                Ok(repaired) => {
+                    dsl = repaired;
+                    None
+                }
+                Err(e) => {
+                    warn!("Repair attempt {} failed: {}", attempt, e);
+                    Some(e.to_string())
+                }
+            };
+            attempts.push(RepairAttempt { attempt, issues, error });
+        }
+    }
+    
+    /// Validation and security problems in a plan, described for the model
+    fn plan_issues(&self, dsl: &ScrapePlan) -> Vec<String> {
+        let mut issues = repair::describe_issues(&self.dsl_validator.validate_detailed(dsl));
+        if let Err(e) = self.security_manager.validate_dsl(dsl) {
+            issues.push(format!("security: {}", e));
+        }
+        issues
+    }
+    
+    /// Fetch the page a description refers to and summarize its structure
+    ///
+    /// Uses the first URL in the description, or the front page of the domain it
+    /// mentions. The URL goes through the same security checks as plans; any
+    /// failure just means generation proceeds without a page.
+    async fn summarize_target_page(&self, description: &str) -> Option<PageSummary> {
+        let target = description.split_whitespace()
+            .map(|word| word.trim_matches(|c: char| matches!(c, '"' | '\'' | '(' | ')' | '<' | '>' | ',' | '.')))
+            .find(|word| word.starts_with("http://") || word.starts_with("https://"))
+            .map(str::to_string)
+            .or_else(|| processor::LLMProcessor::extract_intent(description).domain.map(|domain| format!("https://{}", domain)))?;
+        
+        if let Err(e) = self.security_manager.validate_target_url(&target) {
+            warn!("Not fetching {} to ground plan generation: {}", target, e);
+            return None;
+        }
+        
+        let url = url::Url::parse(&target).ok()?;
+        match self.scraper.fetch_page(&url).await {
+            Ok(html) => {
+                let summary = PageSummary::from_html(&target, &html);
+                info!(
+                    "Grounding plan generation on {} ({} element kinds, {} item candidates)",
+                    target, summary.tag_classes.len(), summary.item_candidates.len()
+                );
+                Some(summary)
+            }
+            Err(e) => {
+                warn!("Could not fetch {} to ground plan generation: {}", target, e);
+                None
+            }
+        }
+    }
+    
+    /// Apply a follow-up instruction such as "add the rating field" to an existing plan
+    pub async fn refine_dsl(&self, dsl: &ScrapePlan, instruction: &str) -> Result<ScrapePlan> {
+        self.security_manager.validate_input(instruction)?;
+        
+        let refined = self.llm.refine_dsl(dsl, instruction).await?;
+        self.dsl_validator.validate(&refined)?;
+        
+        Ok(refined)
+    }
+    
+    /// Replace selectors in a plan with ones that worked on the same domain in past jobs
+    ///
+    /// Returns the number of selectors that were replaced. Used both when generating
+    /// new plans and to heal plans whose selectors stopped matching.
+    pub async fn apply_selector_memory(&self, dsl: &mut ScrapePlan) -> Result<usize> {
+        let entries = self.storage.get_domain_selectors(&dsl.target.domain).await?;
+        let mut applied = 0;
+        
+        let best = |kind: SelectorKind, field_name: &str| {
+            entries.iter().find(|e| {
+                e.kind == kind && e.field_name == field_name && e.confidence >= SELECTOR_MEMORY_MIN_CONFIDENCE
+            })
+        };
+        
+        if let Some(entry) = best(SelectorKind::Item, "") {
+            if entry.selector != dsl.rules.item_selector {
+                dsl.rules.item_selector = entry.selector.clone();
+                applied += 1;
+            }
+        }
+        
+        for field in dsl.rules.fields.iter_mut() {
+            if let Some(entry) = best(SelectorKind::Field, &field.name) {
+                if entry.selector != field.selector {
+                    field.selector = entry.selector.clone();
+                    applied += 1;
+                }
+            }
+        }
+        
+        if let Some(pagination) = dsl.rules.pagination.as_mut() {
+            if let Some(entry) = best(SelectorKind::Pagination, "") {
+                match &mut pagination.method {
+                    crate::dsl::PaginationMethod::Link { next_selector } if *next_selector != entry.selector => {
+                        *next_selector = entry.selector.clone();
+                        applied += 1;
+                    }
+                    crate::dsl::PaginationMethod::Button { button_selector } if *button_selector != entry.selector => {
+                        *button_selector = entry.selector.clone();
+                        applied += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        
+        if applied > 0 {
+            dsl.add_metadata("selector_memory_applied".to_string(), serde_json::Value::from(applied));
+        }
+        
+        Ok(applied)
+    }
+    
+    /// Fetch a page and propose item and field selectors from its repeated structure
+    pub async fn suggest_selectors(&self, url: &str) -> Result<SelectorSuggestions> {
+        self.security_manager.validate_input(url)?;
+        self.security_manager.validate_target_url(url)?;
+        let parsed = url::Url::parse(url)?;
+        
+        let html = self.scraper.fetch_page(&parsed).await?;
+        let mut suggestions = DSLGenerator::suggest_selectors(&html);
+        suggestions.url = Some(url.to_string());
+        
+        info!(
+            "Suggested {} item selectors and {} fields for {}",
+            suggestions.items.len(), suggestions.fields.len(), url
+        );
+        Ok(suggestions)
+    }
+    
+    /// Fetch a page and flatten its elements for the selector picker
+    pub async fn inspect_page(&self, url: &str) -> Result<InspectedPage> {
+        self.security_manager.validate_input(url)?;
+        self.security_manager.validate_target_url(url)?;
+        let parsed = url::Url::parse(url)?;
+        
+        let html = self.scraper.fetch_page(&parsed).await?;
+        let page = InspectedPage::from_html(url, &html);
+        info!("Inspected {} elements on {}", page.nodes.len(), url);
+        Ok(page)
+    }
+    
+    /// Get selectors remembered for a domain, best first
+    pub async fn get_domain_selectors(&self, domain: &str) -> Result<Vec<SelectorMemoryEntry>> {
+        self.storage.get_domain_selectors(domain).await
+    }
+    
+    /// Check a plan against one page per URL pattern without creating a job
+    ///
+    /// Reports per-field selector hits, sample values and required fields that would
+    /// be empty. Nothing is written to storage.
+    pub async fn dry_run(&self, dsl: &ScrapePlan) -> Result<DryRunReport> {
+        self.dsl_validator.validate(dsl)?;
+        self.security_manager.validate_dsl(dsl)?;
+        
+        let report = self.scraper.dry_run(dsl).await?;
+        info!("Dry run checked {} pages", report.pages.len());
+        Ok(report)
+    }
+    
+    /// Go through everything a run would check and work out its requests and duration
+    ///
+    /// Unlike [`Self::dry_run`] no page is fetched, only robots.txt files, so this
+    /// is safe to show before the user approves a plan.
+    pub async fn simulate(&self, dsl: &ScrapePlan) -> Result<Simulation> {
+        let issues = self.dsl_validator.validate_detailed(dsl);
+        let lint = self.lint_dsl(dsl);
+        let mut security = self.security_manager.generate_security_report(dsl);
+        if !security.has_errors() {
+            if let Err(e) = self.security_manager.validate_dsl(dsl) {
+                security.add_error(e.to_string());
+            }
+        }
+        
+        let execution = if issues.is_empty() && !security.has_errors() {
+            Some(self.scraper.simulate(dsl).await?)
+        } else {
+            None
+        };
+        Ok(Simulation { issues, lint, security, execution })
+    }
+    
     /// Validate DSL and generate preview
     pub async fn validate_and_preview(&self, dsl: &ScrapePlan) -> Result<Vec<serde_json::Value>> {
+        self.preview_plan(dsl, 10).await
+    }
+    
+    /// Validate a plan and extract up to `limit` rows from its first start URL
+    pub async fn preview_plan(&self, dsl: &ScrapePlan, limit: usize) -> Result<Vec<serde_json::Value>> {
         info!("Validating DSL and generating preview");
         
         // Validate DSL structure
@@ -102,8 +565,7 @@ impl WinScrapeStudio {
         // Security validation
         self.security_manager.validate_dsl(dsl)?;
         
-        // Generate preview (limited to 10 rows)
-        let preview = self.scraper.generate_preview(dsl, 10).await?;
+        let preview = self.scraper.generate_preview(dsl, limit).await?;
         
         info!("Preview generated with {} rows", preview.len());
         Ok(preview)
@@ -116,16 +578,84 @@ impl WinScrapeStudio {
         Ok(())
     }
     
+    /// Validate DSL structure and return every problem with its location
+    pub fn validate_dsl_detailed(&self, dsl: &ScrapePlan) -> Vec<ValidationIssue> {
+        self.dsl_validator.validate_detailed(dsl)
+    }
+    
+    /// Check a plan for likely problems that don't make it invalid
+    pub fn lint_dsl(&self, dsl: &ScrapePlan) -> Vec<LintWarning> {
+        self.dsl_validator.lint(dsl)
+    }
+    
     /// Execute full scraping job
-    pub async fn execute_scraping(&self, dsl: &ScrapePlan) -> Result<String> {
+    ///
+    /// `${name}` placeholders in the plan are resolved from `variables` first;
+    /// the job stores the resolved plan so it can be re-run as is.
+    pub async fn execute_scraping(&self, dsl: &ScrapePlan, variables: &HashMap<String, String>) -> Result<String> {
+        self.execute_recorded_scraping(dsl, variables, &RecordingPaths::default()).await
+    }
+    
+    /// Execute a scraping job that records its responses to, or replays them from, HAR files
+    pub async fn execute_recorded_scraping(
+        &self,
+        dsl: &ScrapePlan,
+        variables: &HashMap<String, String>,
+        recording: &RecordingPaths,
+    ) -> Result<String> {
+        let (job_id, run_plan) = self.create_job(dsl, variables, recording, None).await?;
+        self.run_scraping_job(&job_id, run_plan).await?;
+        Ok(job_id)
+    }
+    
+    /// Record a queued job for a plan without running it
+    ///
+    /// Returns the job ID and the plan to hand to [`run_scraping_job`](Self::run_scraping_job).
+    /// Fails without recording anything when the resolved plan is invalid or refused by
+    /// the security checks, e.g. because it targets an internal address.
+    /// Recording metadata in the plan is dropped, since only the command line may name HAR files.
+    /// The job belongs to `owner` from the start, so its events reach that user alone.
+    pub async fn create_scraping_job(
+        &self,
+        dsl: &ScrapePlan,
+        variables: &HashMap<String, String>,
+        owner: Option<&str>,
+    ) -> Result<(String, ScrapePlan)> {
+        self.create_job(dsl, variables, &RecordingPaths::default(), owner).await
+    }
+    
+    async fn create_job(
+        &self,
+        dsl: &ScrapePlan,
+        variables: &HashMap<String, String>,
+        recording: &RecordingPaths,
+        owner: Option<&str>,
+    ) -> Result<(String, ScrapePlan)> {
+        if self.shutdown.is_triggered() {
+            return Err(anyhow::anyhow!("Shutting down; no new jobs are accepted"));
+        }
+        
+        // Checked once variables are in, as they can name hosts of their own
+        let mut resolved = template::resolve_plan(dsl, variables)?;
+        self.validate_dsl(&resolved).await?;
+        recording.apply(&mut resolved);
+        if !variables.is_empty() {
+            resolved.add_metadata(template::VARIABLES_METADATA_KEY.to_string(), serde_json::to_value(variables)?);
+        }
+        
+        // Record the seed so a run can be replayed by setting anti_blocking.random_seed to it
+        let seed = resolved.anti_blocking.random_seed.unwrap_or_else(crate::scraper::new_random_seed);
+        resolved.add_metadata(RANDOM_SEED_METADATA_KEY.to_string(), serde_json::Value::from(seed));
+        let dsl = &resolved;
+        
         let job_id = Uuid::new_v4().to_string();
-        info!("Starting scraping job: {}", job_id);
+        info!("Creating scraping job: {}", job_id);
         
         // Create job record
         let job = Job {
             id: job_id.clone(),
             title: dsl.target.domain.clone(),
-            status: JobStatus::Running,
+            status: JobStatus::Queued,
             created_at: chrono::Utc::now(),
             plan_yaml: serde_yaml::to_string(dsl)?,
             user_prompt: dsl.metadata.as_ref()
@@ -133,17 +663,258 @@ impl WinScrapeStudio {
                 .and_then(|v| v.as_str())
                 .unwrap_or("Direct DSL execution")
                 .to_string(),
-            settings_json: Some(serde_json::to_string(&self.config)?),
+            settings_json: Some(serde_json::to_string(&self.config())?),
+            cloned_from: dsl.get_metadata("cloned_from")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            summary_json: None,
         };
         
         self.storage.create_job(&job).await?;
+        if let Some(owner) = owner {
+            self.storage.set_job_owner(&job_id, owner).await?;
+        }
+        self.progress.publish(&job_id, ProgressEvent::Stage { stage: JobStage::Queued, total_urls: None });
+        self.events.publish(&job_id, JobEvent::JobCreated { title: job.title.clone() });
+        
+        let mut run_plan = resolved.clone();
+        run_plan.anti_blocking.random_seed = Some(seed);
+        Ok((job_id, run_plan))
+    }
+    
+    /// Run a job recorded by [`create_scraping_job`](Self::create_scraping_job), then its plan's export profiles
+    ///
+    /// A plan that depends on another runs that one first, see [`PlanDependency`](crate::dsl::PlanDependency).
+    pub async fn run_scraping_job(&self, job_id: &str, run_plan: ScrapePlan) -> Result<()> {
+        info!("Starting scraping job: {}", job_id);
+        
+        let notify = run_plan.output.notify.clone();
+        let run_plan = match self.run_dependency(job_id, run_plan).await {
+            Ok(run_plan) => run_plan,
+            Err(e) => {
+                let message = format!("Upstream plan failed: {}", e);
+                let code = ErrorCode::of(&e);
+                self.storage.update_job_status(job_id, JobStatus::Failed).await?;
+                self.progress.publish(job_id, ProgressEvent::Error { message: message.clone() });
+                self.progress.publish(job_id, ProgressEvent::Finished { status: JobStatus::Failed });
+                self.events.publish(job_id, JobEvent::JobFailed { error: message.clone(), code: code.to_string() });
+                let logged = format!("{} ({}: {})", message, code, code.hint());
+                job_manager::log_job(&self.storage, job_id, crate::storage::FINISHED_STAGE, "error", logged).await;
+                self.notify_job_finished(job_id, notify.as_ref(), &[], Some(&message)).await;
+                return Err(e);
+            }
+        };
         
-        // Execute scraping
-        let mut job_manager = self.job_manager.write().await;
-        job_manager.execute_job(&job_id, dsl.clone()).await?;
+        // Kept to find out why the job failed, if it does
+        let mut events = self.events.subscribe();
+        job_manager::JobManager::execute_job(&self.job_manager, job_id, run_plan.clone()).await?;
+        
+        let exports = self.run_plan_export_profiles(job_id, &run_plan).await;
+        let error = std::iter::from_fn(|| events.try_next())
+            .filter(|event| event.job_id == job_id)
+            .find_map(|event| match event.event {
+                JobEvent::JobFailed { error, .. } => Some(error),
+                _ => None,
+            });
+        self.notify_job_finished(job_id, notify.as_ref(), &exports, error.as_deref()).await;
         
         info!("Scraping job {} completed", job_id);
-        Ok(job_id)
+        Ok(())
+    }
+    
+    /// Stop accepting jobs and pause the running ones, storing the rows they have so far
+    ///
+    /// Returns once nothing runs any more, or after a timeout. Paused jobs are
+    /// picked up by [`resume_paused_jobs`](Self::resume_paused_jobs) on the next start.
+    pub async fn shutdown(&self) {
+        info!("Shutting down; pausing running jobs");
+        self.shutdown.trigger();
+        
+        // Jobs waiting for a retry pause too, without holding the job manager
+        match tokio::time::timeout(SHUTDOWN_TIMEOUT, job_manager::JobManager::wait_until_idle(&self.job_manager)).await {
+            Ok(_) => info!("All jobs stopped"),
+            Err(_) => warn!("Jobs still running after {}s; they stay marked as running", SHUTDOWN_TIMEOUT.as_secs()),
+        }
+    }
+    
+    /// Pause the running and queued jobs, storing the rows they have so far
+    ///
+    /// Unlike [`shutdown`](Self::shutdown), new jobs are accepted again afterwards.
+    /// The paused jobs resume on the next start.
+    pub async fn pause_all_jobs(&self) -> Result<()> {
+        info!("Pausing all jobs");
+        self.shutdown.trigger();
+        let stopped = tokio::time::timeout(SHUTDOWN_TIMEOUT, job_manager::JobManager::wait_until_idle(&self.job_manager)).await.is_ok();
+        self.shutdown.reset();
+        
+        if !stopped {
+            return Err(anyhow::anyhow!("Jobs still running after {}s", SHUTDOWN_TIMEOUT.as_secs()));
+        }
+        Ok(())
+    }
+    
+    /// Show every finished job as a desktop notification while the window is hidden
+    pub fn set_running_in_background(&self, background: bool) {
+        self.notifier.set_background(background);
+    }
+    
+    /// The handle running jobs watch to know the application is closing
+    pub fn shutdown_handle(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+    
+    /// Run the jobs an earlier shutdown paused, oldest first, returning how many finished
+    ///
+    /// Rows they stored before pausing are kept and not fetched again.
+    pub async fn resume_paused_jobs(&self) -> Result<usize> {
+        let filter = JobFilter { status: Some(JobStatus::Paused), ..Default::default() };
+        let mut jobs = self.storage.list_jobs_filtered(&filter, i64::MAX as usize).await?;
+        jobs.reverse();
+        
+        let mut resumed = 0;
+        for job in jobs {
+            if self.shutdown.is_triggered() {
+                break;
+            }
+            info!("Resuming paused job {}", job.id);
+            let mut run_plan = match ScrapePlan::from_yaml(&job.plan_yaml) {
+                Ok(plan) => plan,
+                Err(e) => {
+                    warn!("Cannot resume job {}: {}", job.id, e);
+                    self.storage.update_job_status(&job.id, JobStatus::Failed).await?;
+                    continue;
+                }
+            };
+            if let Some(seed) = run_plan.get_metadata(RANDOM_SEED_METADATA_KEY).and_then(|v| v.as_u64()) {
+                run_plan.anti_blocking.random_seed = Some(seed);
+            }
+            match self.run_scraping_job(&job.id, run_plan).await {
+                Ok(()) => resumed += 1,
+                Err(e) => warn!("Resumed job {} failed: {}", job.id, e),
+            }
+        }
+        Ok(resumed)
+    }
+    
+    /// Run the plan a job depends on, then take the job's start URLs from its results
+    ///
+    /// The upstream plan runs as a job of its own, after its own dependencies,
+    /// and is checked like any other. Only URLs on the job's target domain are
+    /// taken, and the rewritten plan is checked again before it runs.
+    /// The URLs and upstream job are stored with the job, so resuming or
+    /// re-running it doesn't run the upstream plan again.
+    ///
+    /// Boxed, as the upstream job may have a dependency of its own.
+    fn run_dependency<'a>(&'a self, job_id: &'a str, mut run_plan: ScrapePlan) -> futures::future::BoxFuture<'a, Result<ScrapePlan>> {
+        Box::pin(async move {
+            let Some(dependency) = run_plan.target.depends_on.clone() else {
+                return Ok(run_plan);
+            };
+            if run_plan.get_metadata(dependencies::UPSTREAM_JOB_METADATA_KEY).is_some() {
+                return Ok(run_plan);
+            }
+            
+            let mut chain = dependencies::plan_chain(&run_plan);
+            if chain.iter().any(|name| name.eq_ignore_ascii_case(&dependency.plan)) {
+                return Err(anyhow::anyhow!("Plan '{}' depends on itself through {}", dependency.plan, chain.join(" <- ")));
+            }
+            chain.push(dependency.plan.clone());
+            
+            let mut upstream = self.load_saved_plan(&dependency.plan).await?;
+            upstream.add_metadata(dependencies::PLAN_CHAIN_METADATA_KEY.to_string(), serde_json::to_value(&chain)?);
+            // Variables given for the job apply along the whole chain
+            let variables: HashMap<String, String> = run_plan.get_metadata(template::VARIABLES_METADATA_KEY)
+                .and_then(|value| serde_json::from_value(value.clone()).ok())
+                .unwrap_or_default();
+            
+            info!("Job {} waits for plan '{}'", job_id, dependency.plan);
+            let upstream_job = self.execute_scraping(&upstream, &variables).await?;
+            
+            let job = self.storage.get_job(&upstream_job).await?;
+            if job.status != JobStatus::Completed {
+                return Err(anyhow::anyhow!("Job {} of plan '{}' ended as {}", upstream_job, dependency.plan, job.status));
+            }
+            let rows = self.storage.get_job_results(&upstream_job).await?;
+            let urls = dependencies::upstream_urls(&rows, &dependency.url_field, &run_plan.target.domain);
+            if urls.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Job {} of plan '{}' has no URLs on {} in field '{}'",
+                    upstream_job, dependency.plan, run_plan.target.domain, dependency.url_field
+                ));
+            }
+            info!("Job {} starts from {} URLs of job {}", job_id, urls.len(), upstream_job);
+            
+            run_plan.target.start_urls = urls;
+            self.validate_dsl(&run_plan).await?;
+            run_plan.add_metadata(dependencies::UPSTREAM_JOB_METADATA_KEY.to_string(), serde_json::Value::String(upstream_job));
+            self.storage.update_job_plan(job_id, &serde_yaml::to_string(&run_plan)?).await?;
+            Ok(run_plan)
+        })
+    }
+    
+    /// Run the export profiles a plan names once one of its jobs has completed
+    ///
+    /// A failing profile is logged and doesn't stop the others. Returns where
+    /// the successful ones exported to.
+    async fn run_plan_export_profiles(&self, job_id: &str, dsl: &ScrapePlan) -> Vec<String> {
+        let mut targets = Vec::new();
+        if dsl.output.export_profiles.is_empty() {
+            return targets;
+        }
+        match self.storage.get_job(job_id).await {
+            Ok(job) if job.status == JobStatus::Completed => {}
+            Ok(_) => return targets,
+            Err(e) => {
+                warn!("Skipping export profiles of job {}: {}", job_id, e);
+                return targets;
+            }
+        }
+        
+        for name in &dsl.output.export_profiles {
+            match self.export_job_with_profile(job_id, name).await {
+                Ok(target) => {
+                    info!("Exported job {} with profile {} to {}", job_id, name, target);
+                    targets.push(target);
+                }
+                Err(e) => warn!("Export profile {} failed for job {}: {}", name, job_id, e),
+            }
+        }
+        targets
+    }
+    
+    /// Notify about a job that completed or failed, as the configuration and its plan ask
+    async fn notify_job_finished(&self, job_id: &str, options: Option<&NotifyOptions>, exports: &[String], error: Option<&str>) {
+        let job = match self.storage.get_job(job_id).await {
+            Ok(job) => job,
+            Err(e) => {
+                warn!("Cannot notify about job {}: {}", job_id, e);
+                return;
+            }
+        };
+        let Some(channels) = self.notifier.channels(&job.status, options) else {
+            return;
+        };
+        let rows = self.storage.get_job_result_count(job_id).await.unwrap_or_default();
+        if let Some(notification) = Notification::for_job(&job, rows, exports, error) {
+            self.notifier.send(&channels, &notification).await;
+        }
+    }
+    
+    /// How complete each language's translations are, overrides included
+    pub fn translation_report(&self) -> Vec<i18n::TranslationReport> {
+        i18n::global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).completeness_reports()
+    }
+    
+    /// Untranslated keys of a language with their English text
+    pub fn missing_translations(&self, language: Language) -> std::collections::BTreeMap<String, String> {
+        i18n::global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).missing_translations(language)
+    }
+    
+    /// Describe a plan in plain language, in English unless another language is given
+    pub fn explain_plan(&self, dsl: &ScrapePlan, language: Option<Language>) -> PlanExplanation {
+        let mut manager = i18n::global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).clone();
+        manager.set_language(language.unwrap_or_default());
+        explain::explain_plan(dsl, &manager)
     }
     
     /// List recent jobs
@@ -151,6 +922,68 @@ impl WinScrapeStudio {
         self.storage.list_jobs(limit).await
     }
     
+    /// List jobs matching a filter, newest first
+    pub async fn list_jobs_filtered(&self, filter: &JobFilter, limit: usize) -> Result<Vec<Job>> {
+        self.storage.list_jobs_filtered(filter, limit).await
+    }
+    
+    /// Tag a job
+    pub async fn add_job_tag(&self, job_id: &str, tag: &str) -> Result<()> {
+        self.storage.add_job_tag(job_id, tag).await
+    }
+    
+    /// Remove a tag from a job, returning whether the job had it
+    pub async fn remove_job_tag(&self, job_id: &str, tag: &str) -> Result<bool> {
+        self.storage.remove_job_tag(job_id, tag).await
+    }
+    
+    /// Tags of a job
+    pub async fn get_job_tags(&self, job_id: &str) -> Result<Vec<String>> {
+        self.storage.get_job_tags(job_id).await
+    }
+    
+    /// Tags of several jobs, keyed by job ID
+    pub async fn get_tags_for_jobs(&self, job_ids: &[String]) -> Result<HashMap<String, Vec<String>>> {
+        self.storage.get_tags_for_jobs(job_ids).await
+    }
+    
+    /// Every tag in use with its job count
+    pub async fn list_tags(&self) -> Result<Vec<TagCount>> {
+        self.storage.list_tags().await
+    }
+    
+    /// Tags on a user's jobs with their job counts
+    pub async fn list_owner_tags(&self, owner_id: &str) -> Result<Vec<TagCount>> {
+        self.storage.list_owner_tags(owner_id).await
+    }
+    
+    /// Add an API user, returning it with its token, which is shown only this once
+    pub async fn create_user(&self, name: &str, is_admin: bool) -> Result<(User, String)> {
+        let (user, token) = self.storage.create_user(name, is_admin).await?;
+        info!("Created {} user '{}'", if user.is_admin { "admin" } else { "API" }, user.name);
+        Ok((user, token))
+    }
+    
+    /// API users, by name
+    pub async fn list_users(&self) -> Result<Vec<User>> {
+        self.storage.list_users().await
+    }
+    
+    /// Remove an API user, returning whether it existed; their jobs are kept
+    pub async fn delete_user(&self, user_id: &str) -> Result<bool> {
+        self.storage.delete_user(user_id).await
+    }
+    
+    /// User a token was issued to, if any
+    pub async fn authenticate_user(&self, token: &str) -> Result<Option<User>> {
+        self.storage.find_user_by_token(token).await
+    }
+    
+    /// User who created a job; `None` for jobs created outside the API
+    pub async fn get_job_owner(&self, job_id: &str) -> Result<Option<String>> {
+        self.storage.get_job_owner(job_id).await
+    }
+    
     /// Get job details
     pub async fn get_job(&self, job_id: &str) -> Result<Job> {
         self.storage.get_job(job_id).await
@@ -159,22 +992,646 @@ impl WinScrapeStudio {
     /// Re-run existing job
     pub async fn rerun_job(&self, job_id: &str) -> Result<String> {
         let original_job = self.storage.get_job(job_id).await?;
-        let dsl: ScrapePlan = serde_yaml::from_str(&original_job.plan_yaml)?;
+        let dsl = ScrapePlan::from_yaml(&original_job.plan_yaml)?;
+        
+        self.execute_scraping(&dsl, &HashMap::new()).await
+    }
+    
+    /// A job with its log, stage timings, attempts and fetched URLs
+    pub async fn get_job_detail(&self, job_id: &str) -> Result<JobDetail> {
+        let job = self.storage.get_job(job_id).await?;
+        let logs = self.storage.get_job_logs(job_id).await?;
+        let stages = crate::storage::stage_timings(&logs, chrono::Utc::now());
+        
+        Ok(JobDetail {
+            issues: job_issues(&job),
+            attempts: self.storage.get_job_attempts(job_id).await?,
+            urls: self.storage.get_job_urls(job_id).await?,
+            failures: self.storage.failure_report(job_id).await?,
+            job,
+            logs,
+            stages,
+        })
+    }
+    
+    /// Latest entries of the log files and of all jobs' logs, oldest first
+    pub async fn get_logs(&self, max_lines: usize) -> Result<Vec<LogLine>> {
+        let dir = logging::log_directory();
+        let mut lines = tokio::task::spawn_blocking(move || logging::tail_log_files(&dir, max_lines)).await??;
+        lines.extend(self.storage.get_recent_logs(max_lines).await?.into_iter().map(LogLine::from));
+        lines.sort_by_key(|line| line.timestamp);
         
-        self.execute_scraping(&dsl).await
+        let excess = lines.len().saturating_sub(max_lines);
+        lines.drain(..excess);
+        Ok(lines)
     }
     
-    /// Export job results
-    pub async fn export_job(&self, job_id: &str, output_path: &str, format: ExportFormat) -> Result<()> {
+    /// Latest security events matching `filter`, newest first
+    pub async fn get_audit_events(&self, filter: &AuditEventFilter, limit: usize) -> Result<Vec<AuditEvent>> {
+        self.storage.get_audit_events(filter, limit).await
+    }
+    
+    /// Write log lines to a file in the export directory
+    pub async fn export_logs(&self, lines: &[LogLine]) -> Result<std::path::PathBuf> {
+        let dir = self.config().export.output_directory;
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(format!("winscrape-logs-{}.log", chrono::Utc::now().format("%Y%m%d-%H%M%S")));
+        
+        let mut text = String::new();
+        for line in lines {
+            text.push_str(&line.to_string());
+            text.push('\n');
+        }
+        tokio::fs::write(&path, text).await?;
+        info!("Exported {} log lines to {}", lines.len(), path.display());
+        Ok(path)
+    }
+    
+    /// Problems a job's last run met without failing, grouped by kind, stage and field
+    pub async fn get_job_issues(&self, job_id: &str) -> Result<crate::scraper::IssueReport> {
+        Ok(job_issues(&self.storage.get_job(job_id).await?))
+    }
+    
+    /// Which URLs of a job failed, and why
+    pub async fn get_failure_report(&self, job_id: &str) -> Result<FailureReport> {
+        self.storage.get_job(job_id).await?;
+        self.storage.failure_report(job_id).await
+    }
+    
+    /// Fetch only the URLs that failed in a job's runs again, adding the new rows to the job
+    ///
+    /// Rows already stored stay and duplicates of them are left out, as when
+    /// resuming. The job's summary counts the earlier runs and this one.
+    pub async fn rescrape_failed(&self, job_id: &str) -> Result<RescrapeReport> {
+        let job = self.storage.get_job(job_id).await?;
+        if matches!(job.status, JobStatus::Queued | JobStatus::Running) {
+            return Err(anyhow::anyhow!("Job {} is still {}", job_id, job.status));
+        }
+        let failed = self.storage.get_failed_job_urls(job_id).await?;
+        if failed.is_empty() {
+            return Err(anyhow::anyhow!("Job {} has no failed URLs", job_id));
+        }
+        info!("Fetching {} failed URLs of job {} again", failed.len(), job_id);
+        
+        let earlier = job.summary_json.as_deref()
+            .and_then(|json| serde_json::from_str::<crate::scraper::ScrapeSummary>(json).ok());
+        let rows_before = self.storage.get_job_result_count(job_id).await?;
+        
+        let mut plan = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        if let Some(seed) = plan.get_metadata(RANDOM_SEED_METADATA_KEY).and_then(|v| v.as_u64()) {
+            plan.anti_blocking.random_seed = Some(seed);
+        }
+        let urls = failed.len();
+        plan.target.start_urls = failed;
+        plan.target.url_patterns = None;
+        plan.target.depends_on = None;
+        
+        job_manager::JobManager::execute_job(&self.job_manager, job_id, plan).await?;
+        
+        // The run stored counts of the failed URLs alone
+        let job = self.storage.get_job(job_id).await?;
+        let retry = job.summary_json.as_deref()
+            .and_then(|json| serde_json::from_str::<crate::scraper::ScrapeSummary>(json).ok());
+        if let (Some(earlier), Some(retry)) = (earlier, retry) {
+            self.storage.update_job_summary(job_id, &serde_json::to_string(&earlier.merge_retry(retry))?).await?;
+        }
+        
+        Ok(RescrapeReport {
+            job_id: job_id.to_string(),
+            urls,
+            still_failed: self.storage.get_failed_job_urls(job_id).await?.len(),
+            rows_added: self.storage.get_job_result_count(job_id).await?.saturating_sub(rows_before),
+            status: job.status,
+        })
+    }
+    
+    /// Save a plan as a named template, replacing any template with the same name
+    pub async fn save_template(&self, name: &str, description: &str, dsl: &ScrapePlan) -> Result<PlanTemplate> {
+        if name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Template name cannot be empty"));
+        }
+        
+        let now = chrono::Utc::now();
+        let created_at = match self.storage.get_plan_template(name).await {
+            Ok(existing) => existing.created_at,
+            Err(_) => now,
+        };
+        
+        let template = PlanTemplate {
+            name: name.to_string(),
+            description: description.to_string(),
+            plan_yaml: dsl.to_yaml()?,
+            variables: template::plan_variables(dsl)?,
+            created_at,
+            updated_at: now,
+        };
+        
+        self.storage.save_plan_template(&template).await?;
+        Ok(template)
+    }
+    
+    /// List saved plan templates
+    pub async fn list_templates(&self) -> Result<Vec<PlanTemplate>> {
+        self.storage.list_plan_templates().await
+    }
+    
+    /// Get a saved plan template
+    pub async fn get_template(&self, name: &str) -> Result<PlanTemplate> {
+        self.storage.get_plan_template(name).await
+    }
+    
+    /// Delete a saved plan template
+    pub async fn delete_template(&self, name: &str) -> Result<()> {
+        self.storage.delete_plan_template(name).await
+    }
+    
+    /// Run a saved template with values for its variables
+    pub async fn execute_template(&self, name: &str, variables: &HashMap<String, String>) -> Result<String> {
+        let template = self.storage.get_plan_template(name).await?;
+        let mut dsl = ScrapePlan::from_yaml(&template.plan_yaml)?;
+        dsl.add_metadata("template".to_string(), serde_json::Value::String(template.name));
+        
+        self.execute_scraping(&dsl, variables).await
+    }
+    
+    /// Delete a job and its results; refused while the job is queued or running
+    pub async fn delete_job(&self, job_id: &str) -> Result<()> {
+        let job = self.storage.get_job(job_id).await?;
+        if matches!(job.status, JobStatus::Running | JobStatus::Queued) {
+            return Err(anyhow::anyhow!("Job {} is {} and cannot be deleted", job_id, job.status));
+        }
+        self.storage.delete_job(job_id).await?;
+        info!("Deleted job {}", job_id);
+        Ok(())
+    }
+    
+    /// Check whether the database, browser, LLM and disks are fit to run jobs
+    pub async fn check_readiness(&self) -> health::ReadinessReport {
+        health::check_readiness(&self.config(), &self.storage, &self.scraper).await
+    }
+    
+    /// Follow a job's progress events from now on
+    pub fn subscribe_job_progress(&self, job_id: &str) -> ProgressSubscription {
+        self.progress.subscribe(job_id)
+    }
+    
+    /// Receive lifecycle events of every job from now on
+    pub fn subscribe_events(&self) -> EventSubscription {
+        self.events.subscribe()
+    }
+    
+    /// Number of result rows stored for a job
+    pub async fn get_job_result_count(&self, job_id: &str) -> Result<usize> {
+        self.storage.get_job_result_count(job_id).await
+    }
+    
+    /// Fetch result rows stored after `after_row_idx`, for following a running job
+    pub async fn tail_job_results(&self, job_id: &str, after_row_idx: Option<i32>, limit: usize) -> Result<ResultTail> {
+        let job = self.storage.get_job(job_id).await?;
+        let rows = self.storage.get_job_results_after(job_id, after_row_idx, limit).await?;
+        
+        // Paused jobs carry on after a restart
+        let finished = !matches!(job.status, JobStatus::Running | JobStatus::Queued | JobStatus::Paused);
+        Ok(ResultTail {
+            next_after: rows.last().map(|r| r.row_idx).or(after_row_idx),
+            // More rows may still be stored if this batch was full
+            finished: finished && rows.len() < limit,
+            status: job.status,
+            rows,
+        })
+    }
+    
+    /// Get per-column statistics of a job's results
+    ///
+    /// Computed in SQL, so callers get summaries without loading the result set.
+    pub async fn get_column_stats(&self, job_id: &str, top_n: usize) -> Result<Vec<ColumnStats>> {
+        // Fail with "Job not found" rather than returning empty stats
+        self.storage.get_job(job_id).await?;
+        self.storage.get_column_stats(job_id, top_n).await
+    }
+    
+    /// Column statistics plus histograms of numeric columns and results per day
+    pub async fn get_job_analytics(&self, job_id: &str) -> Result<JobAnalytics> {
+        let columns = self.get_column_stats(job_id, crate::storage::column_stats::DEFAULT_TOP_VALUES).await?;
+        let mut histograms = Vec::new();
+        for column in columns.iter().filter(|column| column.numeric) {
+            histograms.push(self.storage.numeric_histogram(job_id, &column.column, HISTOGRAM_BINS).await?);
+        }
+        
+        Ok(JobAnalytics {
+            items_per_day: self.storage.items_per_day(job_id).await?,
+            columns,
+            histograms,
+        })
+    }
+    
+    /// Compare a job's results with an earlier run of the same plan
+    ///
+    /// Uses the later job's `hash_fields` and dedupe keys, so volatile fields left out
+    /// of the hash don't show up as changes. Stored hashes are recomputed if needed.
+    pub async fn compare_jobs(&self, base_job_id: &str, job_id: &str) -> Result<ResultChanges> {
+        self.storage.get_job(base_job_id).await?;
+        let job = self.storage.get_job(job_id).await?;
+        let dsl = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        
+        self.storage.compare_job_results(
+            base_job_id,
+            job_id,
+            dsl.output.hash_fields.as_deref(),
+            dsl.output.dedupe_keys.as_deref(),
+        ).await
+    }
+    
+    /// Other runs of the plan a job ran, newest first
+    ///
+    /// Runs count as the same plan when they target the same pages, as for
+    /// `output.skip_seen`, so edited fields still compare with earlier runs.
+    pub async fn get_plan_runs(&self, job_id: &str) -> Result<Vec<Job>> {
+        let job = self.storage.get_job(job_id).await?;
+        let dsl = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        let key = dsl.seen_index_key();
+        
+        let filter = JobFilter { domain: Some(dsl.target.domain.clone()), ..Default::default() };
+        let runs = self.storage.list_jobs_filtered(&filter, PLAN_RUNS_LIMIT).await?;
+        Ok(runs.into_iter()
+            .filter(|run| run.id != job.id)
+            .filter(|run| ScrapePlan::from_yaml(&run.plan_yaml).is_ok_and(|plan| plan.seen_index_key() == key))
+            .collect())
+    }
+    
+    /// Number of items earlier runs of a plan recorded for `output.skip_seen`
+    pub async fn seen_item_count(&self, dsl: &ScrapePlan) -> Result<usize> {
+        self.storage.seen_item_count(&dsl.seen_index_key()).await
+    }
+    
+    /// Forget the items earlier runs of a plan saw, so the next run keeps all of them
+    pub async fn clear_seen_items(&self, dsl: &ScrapePlan) -> Result<usize> {
+        let removed = self.storage.clear_seen_items(&dsl.seen_index_key()).await?;
+        info!("Cleared {} seen items of plan for {}", removed, dsl.target.domain);
+        Ok(removed)
+    }
+    
+    /// Load a job's plan as the starting point for a variation
+    ///
+    /// The returned plan records the source job in its `cloned_from` metadata,
+    /// which becomes the new job's lineage once it is executed.
+    pub async fn clone_job(&self, job_id: &str) -> Result<ScrapePlan> {
+        let original_job = self.storage.get_job(job_id).await?;
+        let mut dsl = ScrapePlan::from_yaml(&original_job.plan_yaml)?;
+        
+        dsl.add_metadata("cloned_from".to_string(), serde_json::Value::String(job_id.to_string()));
+        if dsl.get_metadata("user_prompt").is_none() {
+            dsl.add_metadata("user_prompt".to_string(), serde_json::Value::String(original_job.user_prompt));
+        }
+        
+        info!("Cloned plan from job {}", job_id);
+        Ok(dsl)
+    }
+    
+    /// Get the jobs a job was cloned from, nearest ancestor first
+    pub async fn get_job_lineage(&self, job_id: &str) -> Result<Vec<Job>> {
+        self.storage.get_job_lineage(job_id).await
+    }
+    
+    /// Runs of a job so far with how each ended, first one first
+    pub async fn get_job_attempts(&self, job_id: &str) -> Result<Vec<JobAttempt>> {
+        self.storage.get_job_attempts(job_id).await
+    }
+    
+    /// Path of a job's log file, to attach when reporting a failed scrape
+    pub async fn get_job_log_file(&self, job_id: &str) -> Result<std::path::PathBuf> {
+        self.storage.get_job_log_file(job_id).await
+    }
+    
+    /// Content of a job's log file, rotated parts included
+    pub async fn read_job_log_file(&self, job_id: &str) -> Result<Vec<u8>> {
+        self.storage.read_job_log_file(job_id).await
+    }
+    
+    /// Save a plan to the local plan library
+    pub async fn save_to_library(&self, name: &str, description: &str, dsl: &ScrapePlan) -> Result<LibraryPlan> {
+        let now = chrono::Utc::now();
+        let plan = LibraryPlan {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            plan_yaml: dsl.to_yaml()?,
+            source: None,
+            created_at: now,
+            updated_at: now,
+        };
+        
+        self.storage.save_library_plan(&plan).await?;
+        Ok(plan)
+    }
+    
+    /// List plans in the local library, optionally filtered by name
+    pub async fn list_library_plans(&self, name_filter: Option<&str>) -> Result<Vec<LibraryPlan>> {
+        self.storage.list_library_plans(name_filter).await
+    }
+    
+    /// Get a plan from the local library
+    pub async fn get_library_plan(&self, id: &str) -> Result<LibraryPlan> {
+        self.storage.get_library_plan(id).await
+    }
+    
+    /// Find a saved plan by library ID, library name or template name
+    pub async fn load_saved_plan(&self, name: &str) -> Result<ScrapePlan> {
+        if let Ok(plan) = self.storage.get_library_plan(name).await {
+            return ScrapePlan::from_yaml(&plan.plan_yaml);
+        }
+        let library = self.storage.list_library_plans(Some(name)).await?;
+        if let Some(plan) = library.iter().find(|plan| plan.name.eq_ignore_ascii_case(name)) {
+            return ScrapePlan::from_yaml(&plan.plan_yaml);
+        }
+        
+        let template = self.storage.get_plan_template(name).await
+            .map_err(|_| anyhow::anyhow!("No saved plan or template named '{}'", name))?;
+        let mut dsl = ScrapePlan::from_yaml(&template.plan_yaml)?;
+        dsl.add_metadata("template".to_string(), serde_json::Value::String(template.name));
+        Ok(dsl)
+    }
+    
+    /// Remove a plan from the local library
+    pub async fn delete_library_plan(&self, id: &str) -> Result<()> {
+        self.storage.delete_library_plan(id).await
+    }
+    
+    /// Import a `.wssplan` bundle into the local library
+    pub async fn import_plan_bundle(&self, path: &str) -> Result<(LibraryPlan, PlanBundle)> {
+        let bytes = tokio::fs::read(path).await?;
+        let bundle = PlanBundle::from_bytes(&bytes)?;
+        let dsl = bundle.plan()?;
+        
+        // Reject bundles that would fail at run time
+        self.dsl_validator.validate(&dsl)?;
+        self.security_manager.validate_dsl(&dsl)?;
+        
+        let now = chrono::Utc::now();
+        let plan = LibraryPlan {
+            id: Uuid::new_v4().to_string(),
+            name: bundle.name.clone(),
+            description: bundle.description.clone(),
+            plan_yaml: dsl.to_yaml()?,
+            source: Some(path.to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+        
+        self.storage.save_library_plan(&plan).await?;
+        info!("Imported plan bundle {} as {}", path, plan.id);
+        Ok((plan, bundle))
+    }
+    
+    /// Read a plan file to review before running it
+    ///
+    /// Takes YAML, JSON or TOML plans and `.wssplan` bundles, and rejects plans
+    /// that would fail at run time.
+    pub async fn open_plan_file(&self, path: &str) -> Result<ScrapePlan> {
+        let is_bundle = std::path::Path::new(path).extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case(crate::dsl::BUNDLE_EXTENSION));
+        let dsl = if is_bundle {
+            PlanBundle::from_bytes(&tokio::fs::read(path).await?)?.plan()?
+        } else {
+            crate::dsl::DSLParser::parse_file(path).await?
+        };
+        
+        self.dsl_validator.validate(&dsl)?;
+        self.security_manager.validate_dsl(&dsl)?;
+        info!("Opened plan file {}", path);
+        Ok(dsl)
+    }
+    
+    /// Write a plan to a `.wssplan` bundle, with credentials replaced by placeholders
+    pub async fn export_plan_bundle(&self, dsl: &ScrapePlan, path: &str) -> Result<PlanBundle> {
+        let bundle = PlanBundle::from_plan(dsl)?;
+        tokio::fs::write(path, bundle.to_bytes()?).await?;
+        
+        info!("Exported plan bundle to {}", path);
+        Ok(bundle)
+    }
+    
+    /// Export job results, returning the file written
+    ///
+    /// Split exports write part files and a manifest named after `output_path` instead.
+    pub async fn export_job(&self, job_id: &str, output_path: &str, format: ExportFormat, options: &ExportOptions) -> Result<std::path::PathBuf> {
+        let (progress, _) = tokio::sync::watch::channel(ExportProgress::default());
+        self.export_job_with_progress(job_id, output_path, format, options, &progress).await
+    }
+    
+    /// Export job results, reporting how far the export got on `progress`
+    ///
+    /// Returns the file written: the compressed file when the options ask for
+    /// compression, or the manifest of a split export.
+    pub async fn export_job_with_progress(
+        &self,
+        job_id: &str,
+        output_path: &str,
+        format: ExportFormat,
+        options: &ExportOptions,
+        progress: &tokio::sync::watch::Sender<ExportProgress>,
+    ) -> Result<std::path::PathBuf> {
         info!("Exporting job {} to {}", job_id, output_path);
         
-        let results = self.storage.get_job_results(job_id).await?;
-        self.export_manager.export(&results, output_path, format).await?;
+        let total = self.storage.get_job_result_count(job_id).await?;
+        let mut cursor = self.storage.result_cursor(job_id, crate::export::EXPORT_PAGE_SIZE);
+        if options.is_split() {
+            let mut source = ProgressRows::new(&mut cursor, progress, total, 1);
+            let split = self.export_manager.export_split(&mut source, output_path, format, options).await?;
+            info!("Export split into {} files, listed in {}", split.manifest.files.len(), split.manifest_path.display());
+            return Ok(split.manifest_path);
+        }
+        let mut source = ProgressRows::new(&mut cursor, progress, total, format.row_passes());
+        self.export_manager.export_rows(&mut source, output_path, format, options).await?;
         
+        let path = match options.compression {
+            Some(codec) => {
+                progress.send_modify(|progress| progress.compressing = true);
+                self.export_manager.compress_file_with(output_path, codec, self.config().export.compression_level).await?
+            }
+            None => output_path.to_string(),
+        };
         info!("Export completed");
+        Ok(std::path::PathBuf::from(path))
+    }
+    
+    /// Export job results to the output directory under a templated file name
+    ///
+    /// Returns the path written, which differs from the suggested path for
+    /// compressed and split exports.
+    pub async fn export_job_to_default_path(&self, job_id: &str, format: ExportFormat, options: &ExportOptions) -> Result<std::path::PathBuf> {
+        let path = self.suggested_export_path(job_id, &format).await?;
+        self.export_job(job_id, &path.to_string_lossy(), format, options).await
+    }
+    
+    /// Path in the output directory an export of a job is written to by default
+    pub async fn suggested_export_path(&self, job_id: &str, format: &ExportFormat) -> Result<std::path::PathBuf> {
+        let job = self.storage.get_job(job_id).await?;
+        let plan = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        
+        let context = self.export_filename_context(&job, &plan, format).await?;
+        self.export_manager.generate_output_path(plan.output.filename_template.as_deref(), &context)
+    }
+    
+    /// Write job results into a table of the configured export database
+    ///
+    /// Rows are upserted on the plan's dedupe keys. The table defaults to the
+    /// plan's domain with non-alphanumeric characters replaced by `_`.
+    pub async fn export_job_to_database(&self, job_id: &str, table: Option<&str>, options: &ExportOptions) -> Result<SinkReport> {
+        let job = self.storage.get_job(job_id).await?;
+        let plan = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        let table = match table {
+            Some(table) => table.to_string(),
+            None => crate::export::database_sink::default_table_name(&plan.target.domain),
+        };
+        info!("Exporting job {} to database table {}", job_id, table);
+        
+        let mut cursor = self.storage.result_cursor(job_id, crate::export::EXPORT_PAGE_SIZE);
+        self.export_manager.export_to_database(&mut cursor, &table, plan.output.dedupe_keys.as_deref().unwrap_or_default(), options).await
+    }
+    
+    /// Save an export profile, replacing one of the same name
+    pub async fn save_export_profile(&self, profile: &ExportProfile) -> Result<()> {
+        profile.validate()?;
+        self.storage.save_export_profile(profile).await?;
+        info!("Saved export profile {}", profile.name);
         Ok(())
     }
     
+    /// Saved export profiles, by name
+    pub async fn list_export_profiles(&self) -> Result<Vec<ExportProfile>> {
+        self.storage.list_export_profiles().await
+    }
+    
+    /// Delete an export profile, returning whether it existed
+    pub async fn delete_export_profile(&self, name: &str) -> Result<bool> {
+        self.storage.delete_export_profile(name).await
+    }
+    
+    /// Export a job's results with a saved profile
+    ///
+    /// Returns where the results went: the file written, the manifest of a
+    /// split export or the database table.
+    pub async fn export_job_with_profile(&self, job_id: &str, name: &str) -> Result<String> {
+        let profile = self.storage.get_export_profile(name).await?
+            .ok_or_else(|| anyhow::anyhow!("Export profile not found: {}", name))?;
+        
+        let directory = match &profile.destination {
+            ExportDestination::Database { table } => {
+                let report = self.export_job_to_database(job_id, table.as_deref(), &profile.options).await?;
+                return Ok(format!("table {}", report.table));
+            }
+            ExportDestination::Plugin { name, args } => {
+                let mut cursor = self.storage.result_cursor(job_id, crate::export::EXPORT_PAGE_SIZE);
+                return self.export_manager.export_to_plugin(&mut cursor, name, args, &profile.options).await;
+            }
+            ExportDestination::Directory { path } => Some(path),
+            ExportDestination::OutputDirectory => None,
+        };
+        
+        let format = profile.export_format()?;
+        let job = self.storage.get_job(job_id).await?;
+        let plan = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        let context = self.export_filename_context(&job, &plan, &format).await?;
+        let template = plan.output.filename_template.as_deref();
+        let path = match directory {
+            Some(directory) => self.export_manager.generate_output_path_in(directory, template, &context)?,
+            None => self.export_manager.generate_output_path(template, &context)?,
+        };
+        
+        let path = path.to_string_lossy().into_owned();
+        self.export_job(job_id, &path, format, &profile.options).await?;
+        if profile.options.is_split() {
+            return Ok(crate::export::split::manifest_path(std::path::Path::new(&path)).display().to_string());
+        }
+        match profile.compression {
+            Some(codec) => self.export_manager.compress_file_with(&path, codec, profile.compression_level).await,
+            None => Ok(path),
+        }
+    }
+    
+    /// Export a small redacted sample of a job's results that is safe to share
+    ///
+    /// Takes random rows, drops and shuffles columns per `options` and always
+    /// applies PII redaction. The file is named like a regular export with a
+    /// `_sample` suffix on the plan name. Returns the path written.
+    pub async fn create_shareable_sample(&self, job_id: &str, options: &SampleOptions, format: ExportFormat) -> Result<std::path::PathBuf> {
+        let job = self.storage.get_job(job_id).await?;
+        let plan = ScrapePlan::from_yaml(&job.plan_yaml)?;
+        
+        let results = self.storage.get_job_results(job_id).await?;
+        if results.is_empty() {
+            return Err(anyhow::anyhow!("Job {} has no results to sample", job_id));
+        }
+        
+        let mut rows = sample::build_sample(&results, options);
+        let redacted = self.security_manager.redact_for_sharing(&mut rows)?;
+        if redacted > 0 {
+            AuditLogger::log_security_event(
+                "pii_filtered",
+                "low",
+                &format!("Redacted sensitive values in {} of {} sampled rows", redacted, rows.len()),
+                Some(LogContext::new("export", "sample").with_job_id(job_id).with_domain(&plan.target.domain)),
+            );
+        }
+        
+        let mut context = self.export_filename_context(&job, &plan, &format).await?;
+        context.plan_name.push_str("_sample");
+        let path = self.export_manager.generate_output_path(plan.output.filename_template.as_deref(), &context)?;
+        
+        self.export_manager.export(&rows, &path.to_string_lossy(), format, &ExportOptions::default()).await?;
+        info!("Wrote a {}-row shareable sample of job {} to {}", rows.len(), job_id, path.display());
+        Ok(path)
+    }
+    
+    /// Values for the export filename template of a job
+    async fn export_filename_context(&self, job: &Job, plan: &ScrapePlan, format: &ExportFormat) -> Result<FilenameContext> {
+        Ok(FilenameContext {
+            plan_name: plan.get_metadata("template")
+                .and_then(|v| v.as_str())
+                .unwrap_or(&job.title)
+                .to_string(),
+            domain: plan.target.domain.clone(),
+            job_id: job.id.clone(),
+            run_number: self.storage.get_job_run_number(job).await?,
+            date: job.created_at,
+            ext: ExportManager::get_file_extension(format).to_string(),
+        })
+    }
+    
+    /// Copy the job database to a file, e.g. to move it to another machine
+    pub async fn backup_database(&self, path: &str) -> Result<BackupInfo> {
+        self.storage.backup_to(std::path::Path::new(path)).await
+    }
+    
+    /// Move results of finished jobs older than `days` days into Parquet files
+    ///
+    /// Uses the configured age when `days` is `None`. Archived results are
+    /// still returned by every result query.
+    pub async fn archive_old_results(&self, days: Option<u32>) -> Result<ArchiveReport> {
+        let days = days
+            .or(self.config().database.archive_after_days)
+            .unwrap_or(crate::storage::archive::DEFAULT_ARCHIVE_AFTER_DAYS);
+        self.storage.archive_results_older_than(days).await
+    }
+    
+    /// Replace the job database with a backup, returning where the old one was saved
+    ///
+    /// Refused while jobs are running or queued, since their results would be lost.
+    pub async fn restore_database(&self, path: &str) -> Result<(BackupInfo, std::path::PathBuf)> {
+        let busy = self.job_manager.read().await
+            .get_job_statuses()
+            .values()
+            .filter(|status| matches!(status, JobStatus::Running | JobStatus::Queued))
+            .count();
+        if busy > 0 {
+            return Err(anyhow::anyhow!("Cannot restore while {} jobs are running or queued", busy));
+        }
+        
+        self.storage.restore_from(std::path::Path::new(path)).await
+    }
+    
     /// Run GUI interface
     #[cfg(feature = "ui")]
     pub async fn run_gui(&mut self) -> Result<()> {
@@ -202,13 +1659,11 @@ impl WinScrapeStudio {
         Ok(())
     }
     
-    /// Start optional API server
+    /// Serve the REST API until the server is stopped
     #[cfg(feature = "api")]
     async fn start_api_server(&self) -> Result<()> {
-        // API server implementation should be handled in main.rs
-        // This is just a placeholder to maintain the API
-        info!("API server requested - implementation should be in main.rs");
-        Ok(())
+        let app = self.clone_for_api().await?;
+        crate::api::serve(app, &self.config().api).await
     }
     
     /// Clone for UI usage (simplified interface)
@@ -225,6 +1680,10 @@ impl WinScrapeStudio {
             export_manager: self.export_manager.clone(),
             security_manager: self.security_manager.clone(),
             job_manager: self.job_manager.clone(),
+            progress: self.progress.clone(),
+            events: self.events.clone(),
+            shutdown: self.shutdown.clone(),
+            notifier: self.notifier.clone(),
         }))
     }
     
@@ -240,6 +1699,10 @@ impl WinScrapeStudio {
             export_manager: self.export_manager.clone(),
             security_manager: self.security_manager.clone(),
             job_manager: self.job_manager.clone(),
+            progress: self.progress.clone(),
+            events: self.events.clone(),
+            shutdown: self.shutdown.clone(),
+            notifier: self.notifier.clone(),
         }))
     }
 
@@ -253,3 +1716,94 @@ impl WinScrapeStudio {
         &self.security_manager
     }
 }
+
+/// Issues stored in a job's summary; none before its first run ends
+fn job_issues(job: &Job) -> crate::scraper::IssueReport {
+    job.summary_json.as_deref()
+        .and_then(|json| serde_json::from_str::<crate::scraper::ScrapeSummary>(json).ok())
+        .map(|summary| summary.issues)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+
+    #[tokio::test]
+    async fn test_jobs_for_internal_addresses_are_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = AppConfig::default();
+        config.database.path = dir.path().join("jobs.db");
+        let app = WinScrapeStudio::new(config).await.unwrap();
+
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.target.start_urls = vec!["http://169.254.169.254/latest/meta-data/".to_string()];
+        let error = app.create_scraping_job(&plan, &HashMap::new(), None).await.unwrap_err();
+        assert!(error.downcast_ref::<crate::security::SecurityError>().is_some());
+
+        // A variable can't smuggle one in either
+        plan.target.start_urls = vec!["http://${host}/products".to_string()];
+        let variables = HashMap::from([("host".to_string(), "127.0.0.1:8080".to_string())]);
+        assert!(app.create_scraping_job(&plan, &variables, None).await.is_err());
+
+        assert!(app.storage.list_jobs(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_owned_when_announced() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = AppConfig::default();
+        config.database.path = dir.path().join("jobs.db");
+        let app = WinScrapeStudio::new(config).await.unwrap();
+        let (alice, _) = app.storage.create_user("alice", false).await.unwrap();
+
+        let mut events = app.subscribe_events();
+        let plan = DSLExamples::ecommerce_products();
+        let (job_id, _) = app.create_scraping_job(&plan, &HashMap::new(), Some(&alice.id)).await.unwrap();
+
+        let created = events.try_next().unwrap();
+        assert_eq!(created.job_id, job_id);
+        assert!(matches!(created.event, JobEvent::JobCreated { .. }));
+        assert_eq!(app.storage.get_job_owner(&job_id).await.unwrap(), Some(alice.id));
+    }
+
+    #[tokio::test]
+    async fn test_default_path_exports_report_the_file_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = AppConfig::default();
+        config.database.path = dir.path().join("jobs.db");
+        config.export.output_directory = dir.path().join("exports");
+        let app = WinScrapeStudio::new(config).await.unwrap();
+
+        let plan = DSLExamples::ecommerce_products();
+        let job = Job {
+            id: "job-1".to_string(),
+            title: "Products".to_string(),
+            status: JobStatus::Completed,
+            created_at: chrono::Utc::now(),
+            plan_yaml: plan.to_yaml().unwrap(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        };
+        app.storage.create_job(&job).await.unwrap();
+        app.storage.store_job_result(&crate::storage::JobResult {
+            job_id: job.id.clone(),
+            row_idx: 0,
+            data_json: r#"{"title":"Lamp"}"#.to_string(),
+            url: "https://shop.example.com/lamp".to_string(),
+            fetched_at: chrono::Utc::now(),
+            hash: "lamp".to_string(),
+        }).await.unwrap();
+
+        let options = ExportOptions {
+            compression: Some(crate::config::CompressionCodec::Gzip),
+            ..ExportOptions::default()
+        };
+        let path = app.export_job_to_default_path(&job.id, ExportFormat::Csv, &options).await.unwrap();
+        assert!(path.to_string_lossy().ends_with(".gz"));
+        assert!(path.is_file());
+    }
+}
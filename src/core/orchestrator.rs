@@ -1,11 +1,17 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{info, error, debug};
 
-use crate::core::WinScrapeStudio;
+use crate::core::events::{EventSubscription, JobEvent};
+use crate::core::{Simulation, WinScrapeStudio};
 use crate::dsl::ScrapePlan;
+use crate::error::{describe, ErrorCode};
 use crate::storage::JobStatus;
 
+/// Longest wait for a job to end
+const MONITOR_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
 /// High-level orchestration logic for complex workflows
 pub struct Orchestrator {
     app: Arc<WinScrapeStudio>,
@@ -34,7 +40,7 @@ impl Orchestrator {
                 dsl
             }
             Err(e) => {
-                workflow.add_error(format!("DSL generation failed: {}", e));
+                workflow.add_error(format!("DSL generation failed: {}", describe(&e)));
                 return Ok(workflow.into_result());
             }
         };
@@ -47,7 +53,7 @@ impl Orchestrator {
                 preview
             }
             Err(e) => {
-                workflow.add_error(format!("Validation failed: {}", e));
+                workflow.add_error(format!("Validation failed: {}", describe(&e)));
                 return Ok(workflow.into_result());
             }
         };
@@ -60,22 +66,46 @@ impl Orchestrator {
             return Ok(workflow.into_result());
         }
         
+        self.execute_and_monitor(&dsl, workflow).await
+    }
+    
+    /// Run a plan the user approved and wait for its job to end
+    ///
+    /// Picks up where [`Self::execute_complete_workflow`] stopped for approval.
+    /// The plan is checked again, as it may have been edited since.
+    pub async fn run_approved_plan(&self, user_input: &str, dsl: &ScrapePlan) -> Result<WorkflowResult> {
+        info!("Running approved plan for {}", dsl.target.domain);
+        
+        let mut workflow = WorkflowExecution::new(user_input.to_string());
+        workflow.set_stage(WorkflowStage::Validation);
+        if let Err(e) = self.app.validate_dsl(dsl).await {
+            workflow.add_error(format!("Validation failed: {}", describe(&e)));
+            return Ok(workflow.into_result());
+        }
+        workflow.add_log("Plan approved".to_string());
+        self.execute_and_monitor(dsl, workflow).await
+    }
+    
+    /// Execution, monitoring and completion stages of a workflow
+    async fn execute_and_monitor(&self, dsl: &ScrapePlan, mut workflow: WorkflowExecution) -> Result<WorkflowResult> {
         // Stage 4: Execution
         workflow.set_stage(WorkflowStage::Execution);
-        let job_id = match self.app.execute_scraping(&dsl).await {
+        // Subscribe first, as the job has usually ended by the time it is monitored
+        let events = self.app.subscribe_events();
+        let job_id = match self.app.execute_scraping(dsl, &HashMap::new()).await {
             Ok(job_id) => {
                 workflow.add_log(format!("Scraping job started: {}", job_id));
                 job_id
             }
             Err(e) => {
-                workflow.add_error(format!("Execution failed: {}", e));
+                workflow.add_error(format!("Execution failed: {}", describe(&e)));
                 return Ok(workflow.into_result());
             }
         };
         
         // Stage 5: Monitoring
         workflow.set_stage(WorkflowStage::Monitoring);
-        let final_status = self.monitor_job_completion(&job_id, &mut workflow).await?;
+        let final_status = self.monitor_job_completion(&job_id, events, &mut workflow).await?;
         
         // Stage 6: Completion
         workflow.set_stage(WorkflowStage::Completed);
@@ -85,44 +115,109 @@ impl Orchestrator {
         Ok(workflow.into_result())
     }
     
-    /// Monitor job completion
+    /// Run the workflow up to approval without fetching any page
+    ///
+    /// The plan is generated, validated and simulated; the result is left
+    /// pending approval with the simulation in place of a preview.
+    pub async fn simulate_workflow(&self, user_input: &str) -> Result<WorkflowResult> {
+        info!("Simulating workflow for input: {}", user_input);
+        
+        let mut workflow = WorkflowExecution::new(user_input.to_string());
+        
+        workflow.set_stage(WorkflowStage::NLProcessing);
+        let dsl = match self.app.generate_dsl(user_input).await {
+            Ok(dsl) => {
+                workflow.add_log("DSL generated successfully".to_string());
+                dsl
+            }
+            Err(e) => {
+                workflow.add_error(format!("DSL generation failed: {}", describe(&e)));
+                return Ok(workflow.into_result());
+            }
+        };
+        
+        workflow.set_stage(WorkflowStage::Validation);
+        let simulation = match self.app.simulate(&dsl).await {
+            Ok(simulation) => simulation,
+            Err(e) => {
+                workflow.add_error(format!("Simulation failed: {}", describe(&e)));
+                return Ok(workflow.into_result());
+            }
+        };
+        for issue in &simulation.issues {
+            workflow.add_error(format!("Validation failed: {}", issue));
+        }
+        for error in &simulation.security.errors {
+            workflow.add_error(format!("Security check failed: {}", error));
+        }
+        if let Some(execution) = &simulation.execution {
+            workflow.add_log(format!(
+                "{} requests planned, {} skipped by robots.txt, about {}s",
+                execution.requests, execution.skipped_urls.len(), execution.estimated_duration_secs
+            ));
+        }
+        let runnable = simulation.is_runnable();
+        workflow.set_simulation(simulation);
+        if !runnable {
+            if workflow.errors.is_empty() {
+                workflow.add_error("Robots.txt disallows every target URL".to_string());
+            }
+            return Ok(workflow.into_result());
+        }
+        
+        workflow.set_stage(WorkflowStage::Approval);
+        workflow.add_log("Simulation ready for review".to_string());
+        workflow.set_pending_approval(dsl, Vec::new());
+        Ok(workflow.into_result())
+    }
+    
+    /// Wait for the job's final event
     async fn monitor_job_completion(
         &self,
         job_id: &str,
+        mut events: EventSubscription,
         workflow: &mut WorkflowExecution,
     ) -> Result<JobStatus> {
-        let mut attempts = 0;
-        let max_attempts = 300; // 5 minutes with 1-second intervals
-        
-        loop {
-            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            attempts += 1;
-            
-            let job = self.app.get_job(job_id).await?;
-            
-            match job.status {
-                JobStatus::Completed => {
-                    workflow.add_log("Job completed successfully".to_string());
-                    return Ok(JobStatus::Completed);
-                }
-                JobStatus::Failed => {
-                    workflow.add_error("Job failed during execution".to_string());
-                    return Ok(JobStatus::Failed);
-                }
-                JobStatus::Cancelled => {
-                    workflow.add_log("Job was cancelled".to_string());
-                    return Ok(JobStatus::Cancelled);
-                }
-                JobStatus::Running | JobStatus::Queued => {
-                    if attempts % 30 == 0 {
-                        workflow.add_log(format!("Job still running... ({}s)", attempts));
+        let watch = async {
+            while let Some(event) = events.next_of(job_id).await {
+                match event.event {
+                    JobEvent::StageChanged { stage } => workflow.add_log(format!("Job stage: {:?}", stage)),
+                    JobEvent::RetryScheduled { attempt, delay_ms, reason } => {
+                        workflow.add_log(format!("Attempt {} in {}s: {}", attempt, delay_ms / 1000, reason));
                     }
+                    JobEvent::JobCompleted { rows, warnings: 0 } => {
+                        workflow.add_log(format!("Job completed successfully with {} rows", rows));
+                        return Some(JobStatus::Completed);
+                    }
+                    JobEvent::JobCompleted { rows, warnings } => {
+                        workflow.add_log(format!("Job completed with {} rows and {} warnings", rows, warnings));
+                        return Some(JobStatus::Completed);
+                    }
+                    JobEvent::JobFailed { error, code } => {
+                        let hint = ErrorCode::from_code(&code).map(|code| code.hint()).unwrap_or_default();
+                        workflow.add_error(format!("Job failed during execution: {} ({}: {})", error, code, hint));
+                        return Some(JobStatus::Failed);
+                    }
+                    JobEvent::JobStopped { status } => {
+                        workflow.add_log(match status {
+                            JobStatus::Paused => "Job was paused by a shutdown and resumes on the next start".to_string(),
+                            _ => format!("Job was {}", status),
+                        });
+                        return Some(status);
+                    }
+                    JobEvent::JobCreated { .. } | JobEvent::ItemScraped { .. } => {}
                 }
             }
-            
-            if attempts >= max_attempts {
+            None
+        };
+        
+        match tokio::time::timeout(MONITOR_TIMEOUT, watch).await {
+            Ok(Some(status)) => Ok(status),
+            // The events are gone; storage still knows how the job ended
+            Ok(None) => Ok(self.app.get_job(job_id).await?.status),
+            Err(_) => {
                 workflow.add_error("Job monitoring timeout".to_string());
-                return Ok(JobStatus::Failed);
+                Ok(JobStatus::Failed)
             }
         }
     }
@@ -164,6 +259,8 @@ pub struct WorkflowExecution {
     pub job_id: Option<String>,
     pub final_status: Option<JobStatus>,
     pub pending_approval: Option<PendingApproval>,
+    /// Set by [`Orchestrator::simulate_workflow`]
+    pub simulation: Option<Simulation>,
     pub started_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -193,6 +290,7 @@ pub struct WorkflowResult {
     pub job_id: Option<String>,
     pub final_status: Option<JobStatus>,
     pub pending_approval: Option<PendingApproval>,
+    pub simulation: Option<Simulation>,
     pub started_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: chrono::DateTime<chrono::Utc>,
     pub success: bool,
@@ -208,6 +306,7 @@ impl WorkflowExecution {
             job_id: None,
             final_status: None,
             pending_approval: None,
+            simulation: None,
             started_at: chrono::Utc::now(),
         }
     }
@@ -239,6 +338,10 @@ impl WorkflowExecution {
         self.pending_approval = Some(PendingApproval { dsl, preview });
     }
     
+    pub fn set_simulation(&mut self, simulation: Simulation) {
+        self.simulation = Some(simulation);
+    }
+    
     pub fn into_result(self) -> WorkflowResult {
         let success = self.errors.is_empty() && 
                      matches!(self.stage, WorkflowStage::Completed | WorkflowStage::Approval);
@@ -251,6 +354,7 @@ impl WorkflowExecution {
             job_id: self.job_id,
             final_status: self.final_status,
             pending_approval: self.pending_approval,
+            simulation: self.simulation,
             started_at: self.started_at,
             completed_at: chrono::Utc::now(),
             success,
@@ -271,3 +375,28 @@ impl std::fmt::Display for WorkflowStage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::dsl::DSLExamples;
+
+    #[tokio::test]
+    async fn test_approved_plans_are_checked_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = AppConfig::default();
+        config.database.path = dir.path().join("jobs.db");
+        let app = Arc::new(WinScrapeStudio::new(config).await.unwrap());
+
+        // Edited after approval to point at an internal address
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.target.start_urls = vec!["http://169.254.169.254/latest/meta-data/".to_string()];
+        let result = Orchestrator::new(app.clone()).run_approved_plan("products", &plan).await.unwrap();
+
+        assert!(!result.success);
+        assert!(matches!(result.stage, WorkflowStage::Validation));
+        assert!(result.job_id.is_none());
+        assert!(app.list_jobs(10).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+use crate::storage::JobStatus;
+
+/// Events kept for slow subscribers before the oldest are dropped
+const PROGRESS_CAPACITY: usize = 1024;
+
+/// A step of a job's run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStage {
+    Queued,
+    Fetching,
+    Storing,
+    /// Waiting to run again after a failed run
+    Retrying,
+}
+
+impl JobStage {
+    /// Name of the stage in a job's stored log
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStage::Queued => "queued",
+            JobStage::Fetching => "fetching",
+            JobStage::Storing => "storing",
+            JobStage::Retrying => "retrying",
+        }
+    }
+}
+
+/// Something that happened while a job ran
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    Stage {
+        stage: JobStage,
+        /// URLs to fetch; set when fetching starts
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total_urls: Option<usize>,
+    },
+    UrlProcessed {
+        url: String,
+        status_code: Option<u16>,
+        /// Items extracted from the page
+        items: usize,
+        error: Option<String>,
+    },
+    /// Rows stored so far
+    Items { total: usize },
+    Error { message: String },
+    /// Always the last event of a run
+    Finished { status: JobStatus },
+}
+
+/// A progress event of one job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobProgress {
+    pub job_id: String,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: ProgressEvent,
+}
+
+impl JobProgress {
+    /// Whether no more events follow for the job
+    pub fn is_final(&self) -> bool {
+        matches!(self.event, ProgressEvent::Finished { .. })
+    }
+}
+
+/// Broadcasts progress of every job to whoever is following it
+#[derive(Clone)]
+pub struct ProgressHub {
+    tx: broadcast::Sender<JobProgress>,
+}
+
+impl ProgressHub {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(PROGRESS_CAPACITY);
+        Self { tx }
+    }
+
+    /// Send an event to the job's subscribers, if there are any
+    pub fn publish(&self, job_id: &str, event: ProgressEvent) {
+        let _ = self.tx.send(JobProgress {
+            job_id: job_id.to_string(),
+            at: Utc::now(),
+            event,
+        });
+    }
+
+    /// Follow a job's events from now on
+    pub fn subscribe(&self, job_id: &str) -> ProgressSubscription {
+        ProgressSubscription {
+            job_id: job_id.to_string(),
+            rx: self.tx.subscribe(),
+        }
+    }
+}
+
+impl Default for ProgressHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Events of one job, received in order
+pub struct ProgressSubscription {
+    job_id: String,
+    rx: broadcast::Receiver<JobProgress>,
+}
+
+impl ProgressSubscription {
+    /// Next event of the job; `None` once the hub is gone
+    ///
+    /// A subscriber that falls too far behind skips the events it missed.
+    pub async fn next(&mut self) -> Option<JobProgress> {
+        loop {
+            match self.rx.recv().await {
+                Ok(progress) if progress.job_id == self.job_id => return Some(progress),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    debug!("Progress subscriber of job {} missed {} events", self.job_id, missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscription_only_sees_its_job() {
+        let hub = ProgressHub::new();
+        let mut subscription = hub.subscribe("a");
+        hub.publish("b", ProgressEvent::Items { total: 1 });
+        hub.publish("a", ProgressEvent::Items { total: 2 });
+        hub.publish("a", ProgressEvent::Finished { status: JobStatus::Completed });
+
+        let first = subscription.next().await.unwrap();
+        assert_eq!(first.event, ProgressEvent::Items { total: 2 });
+        assert!(subscription.next().await.unwrap().is_final());
+
+        let json = serde_json::to_value(&first).unwrap();
+        assert_eq!((json["job_id"].as_str(), json["type"].as_str(), json["total"].as_u64()), (Some("a"), Some("items"), Some(2)));
+    }
+}
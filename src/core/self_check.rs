@@ -0,0 +1,349 @@
+use anyhow::Result;
+use rusqlite::OpenFlags;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::config::{AppConfig, ConfigSection, LLMBackendConfig};
+use crate::storage::encryption;
+
+/// Lines of `PRAGMA integrity_check` output kept in a problem description
+const INTEGRITY_LINES: usize = 5;
+
+/// How serious a startup problem is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckSeverity {
+    /// A feature is unavailable but the application works
+    Warning,
+    /// The application can't start normally; boot into safe mode
+    Error,
+}
+
+/// One-click fix for a startup problem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RepairAction {
+    /// Clear the cache table, rebuild indexes and compact the database
+    RebuildCache,
+    /// Put a configuration section back to its defaults
+    ResetConfigSection { section: ConfigSection },
+    /// Use a different data directory, copying the database over if it is readable
+    RelocateDataDir { to: PathBuf },
+}
+
+impl RepairAction {
+    /// Button label for the action
+    pub fn label(&self) -> String {
+        match self {
+            RepairAction::RebuildCache => "Rebuild cache".to_string(),
+            RepairAction::ResetConfigSection { section: ConfigSection::All } => "Reset configuration".to_string(),
+            RepairAction::ResetConfigSection { section } => format!("Reset [{}] settings", section),
+            RepairAction::RelocateDataDir { to } => format!("Move data to {}", to.display()),
+        }
+    }
+}
+
+/// A problem found at startup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckProblem {
+    /// Which check found it: `config`, `database`, `directories`, `browser` or `llm`
+    pub check: String,
+    pub severity: CheckSeverity,
+    pub message: String,
+    pub repair: Option<RepairAction>,
+}
+
+impl CheckProblem {
+    fn new(check: &str, severity: CheckSeverity, message: impl Into<String>, repair: Option<RepairAction>) -> Self {
+        Self {
+            check: check.to_string(),
+            severity,
+            message: message.into(),
+            repair,
+        }
+    }
+}
+
+/// Results of the startup self-check
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SelfCheckReport {
+    pub problems: Vec<CheckProblem>,
+}
+
+impl SelfCheckReport {
+    /// Whether any problem keeps the application from starting normally
+    pub fn needs_safe_mode(&self) -> bool {
+        self.problems.iter().any(|p| p.severity == CheckSeverity::Error)
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Load the configuration and check everything the application needs to start
+///
+/// Never fails: an unreadable configuration file is reported as a problem and
+/// defaults are used in its place, so safe mode can still come up.
+pub async fn startup_check(config_path: Option<&Path>) -> (AppConfig, SelfCheckReport) {
+    let path = config_path.map(Path::to_path_buf).unwrap_or_else(AppConfig::default_path);
+    let mut report = SelfCheckReport::default();
+
+    let config = if path.exists() {
+        match AppConfig::parse_file(&path).await {
+            Ok(config) => config,
+            Err(e) => {
+                report.problems.push(CheckProblem::new(
+                    "config",
+                    CheckSeverity::Error,
+                    format!("Configuration file {} can't be read: {}", path.display(), e),
+                    Some(RepairAction::ResetConfigSection { section: ConfigSection::All }),
+                ));
+                AppConfig::default()
+            }
+        }
+    } else {
+        AppConfig::default()
+    };
+
+    report.problems.extend(run_self_check(&config).await.problems);
+    (config, report)
+}
+
+/// Check configuration values, directories, the database and optional components
+pub async fn run_self_check(config: &AppConfig) -> SelfCheckReport {
+    let mut report = SelfCheckReport::default();
+
+    for (section, message) in config.validation_errors() {
+        report.problems.push(CheckProblem::new(
+            "config",
+            CheckSeverity::Error,
+            message,
+            Some(RepairAction::ResetConfigSection { section }),
+        ));
+    }
+
+    let data_dir = config.get_data_dir();
+    if let Err(e) = check_writable(&data_dir).await {
+        report.problems.push(CheckProblem::new(
+            "directories",
+            CheckSeverity::Error,
+            format!("Data directory {} is not writable: {}", data_dir.display(), e),
+            Some(RepairAction::RelocateDataDir { to: relocation_target(config) }),
+        ));
+    } else if let Some(problem) = check_database(&config.database.path, config) {
+        report.problems.push(problem);
+    }
+
+    if let Err(e) = check_writable(&config.export.output_directory).await {
+        report.problems.push(CheckProblem::new(
+            "directories",
+            CheckSeverity::Error,
+            format!("Export directory {} is not writable: {}", config.export.output_directory.display(), e),
+            Some(RepairAction::ResetConfigSection { section: ConfigSection::Export }),
+        ));
+    }
+
+    if let Some(problem) = check_browser(config).await {
+        report.problems.push(problem);
+    }
+    if let Some(problem) = check_llm(config) {
+        report.problems.push(problem);
+    }
+
+    for problem in &report.problems {
+        match problem.severity {
+            CheckSeverity::Error => warn!("Self-check error ({}): {}", problem.check, problem.message),
+            CheckSeverity::Warning => info!("Self-check warning ({}): {}", problem.check, problem.message),
+        }
+    }
+    report
+}
+
+/// Carry out a repair, saving configuration changes to `config_path`
+///
+/// Returns a description of what was done.
+pub async fn apply_repair(config: &mut AppConfig, config_path: &Path, action: &RepairAction) -> Result<String> {
+    match action {
+        RepairAction::RebuildCache => {
+            let path = config.database.path.clone();
+            let key = encryption::resolve_key(&config.database)?;
+            tokio::task::spawn_blocking(move || rebuild_cache(&path, key.as_deref())).await?
+        }
+        RepairAction::ResetConfigSection { section } => {
+            config.reset_section(*section);
+            config.save_to_file(config_path).await?;
+            Ok(format!("Reset the [{}] configuration section", section))
+        }
+        RepairAction::RelocateDataDir { to } => {
+            tokio::fs::create_dir_all(to).await?;
+            let file_name = config.database.path.file_name()
+                .map(|name| name.to_os_string())
+                .unwrap_or_else(|| "winscrape.db".into());
+            let new_path = to.join(file_name);
+
+            // Only a healthy database is worth carrying over
+            let old_path = config.database.path.clone();
+            let key = encryption::resolve_key(&config.database).ok().flatten();
+            let healthy = tokio::task::spawn_blocking(move || integrity_check(&old_path, key.as_deref()))
+                .await?
+                .is_ok_and(|lines| lines.first().map(String::as_str) == Some("ok"));
+
+            let mut message = format!("Moved the data directory to {}", to.display());
+            if healthy && !new_path.exists() {
+                match tokio::fs::copy(&config.database.path, &new_path).await {
+                    Ok(_) => message.push_str(" and copied the database"),
+                    Err(e) => {
+                        warn!("Could not copy database to {}: {}", new_path.display(), e);
+                        message.push_str(" (the old database could not be copied; a new one will be created)");
+                    }
+                }
+            } else if !healthy && config.database.path.exists() {
+                message.push_str(" (the old database is damaged and was left in place; a new one will be created)");
+            }
+
+            config.database.path = new_path;
+            config.save_to_file(config_path).await?;
+            Ok(message)
+        }
+    }
+}
+
+/// Make sure a directory exists and files can be created in it
+async fn check_writable(dir: &Path) -> Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    let probe = dir.join(".wss-write-test");
+    tokio::fs::write(&probe, b"ok").await?;
+    tokio::fs::remove_file(&probe).await?;
+    Ok(())
+}
+
+/// Run SQLite's integrity check on an existing database
+fn check_database(path: &Path, config: &AppConfig) -> Option<CheckProblem> {
+    if !path.exists() {
+        return None;
+    }
+
+    let problem = |message: String, repair: RepairAction| {
+        Some(CheckProblem::new("database", CheckSeverity::Error, message, Some(repair)))
+    };
+
+    // Moving the data elsewhere wouldn't help with a missing key
+    let key = match encryption::resolve_key(&config.database) {
+        Ok(key) => key,
+        Err(e) => return Some(CheckProblem::new("database", CheckSeverity::Error, e.to_string(), None)),
+    };
+
+    match integrity_check(path, key.as_deref()) {
+        Ok(lines) if lines.first().map(String::as_str) == Some("ok") => None,
+        Ok(lines) => problem(
+            format!("Database {} is damaged: {}", path.display(), lines.join("; ")),
+            RepairAction::RebuildCache,
+        ),
+        Err(e) => problem(
+            format!("Database {} can't be opened: {}", path.display(), e),
+            RepairAction::RelocateDataDir { to: relocation_target(config) },
+        ),
+    }
+}
+
+fn integrity_check(path: &Path, key: Option<&str>) -> Result<Vec<String>> {
+    let conn = encryption::open(path, OpenFlags::SQLITE_OPEN_READ_WRITE, key)?;
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let lines = stmt.query_map([], |row| row.get::<_, String>(0))?
+        .take(INTEGRITY_LINES)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lines)
+}
+
+fn rebuild_cache(path: &Path, key: Option<&str>) -> Result<String> {
+    let conn = encryption::open(path, OpenFlags::SQLITE_OPEN_READ_WRITE, key)?;
+    let has_cache: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'cache'",
+        [],
+        |row| row.get(0),
+    )?;
+    if has_cache {
+        conn.execute("DELETE FROM cache", [])?;
+    }
+    conn.execute_batch("REINDEX; VACUUM;")?;
+    drop(conn);
+
+    let lines = integrity_check(path, key)?;
+    if lines.first().map(String::as_str) == Some("ok") {
+        Ok("Rebuilt the cache and indexes; the database passes the integrity check".to_string())
+    } else {
+        Err(anyhow::anyhow!(
+            "The database is still damaged after rebuilding ({}); move the data directory to start with a fresh database",
+            lines.join("; ")
+        ))
+    }
+}
+
+/// Data directory to offer when the configured one is unusable
+fn relocation_target(config: &AppConfig) -> PathBuf {
+    let default_dir = AppConfig::default().get_data_dir();
+    if default_dir != config.get_data_dir() {
+        default_dir
+    } else {
+        std::env::current_dir().unwrap_or_default().join("data")
+    }
+}
+
+async fn check_browser(config: &AppConfig) -> Option<CheckProblem> {
+    if !config.scraping.enable_browser_fallback {
+        return None;
+    }
+
+    #[cfg(feature = "browser")]
+    {
+        return match crate::scraper::browser::BrowserClient::new(&config.scraping).await {
+            Ok(_) => None,
+            Err(e) => Some(CheckProblem::new(
+                "browser",
+                CheckSeverity::Warning,
+                format!("Browser fallback is enabled but the browser can't be started: {}", e),
+                Some(RepairAction::ResetConfigSection { section: ConfigSection::Scraping }),
+            )),
+        };
+    }
+
+    #[cfg(not(feature = "browser"))]
+    Some(CheckProblem::new(
+        "browser",
+        CheckSeverity::Warning,
+        "Browser fallback is enabled but this build has no browser support",
+        Some(RepairAction::ResetConfigSection { section: ConfigSection::Scraping }),
+    ))
+}
+
+pub(crate) fn check_llm(config: &AppConfig) -> Option<CheckProblem> {
+    let warning = |message: String, repair: Option<RepairAction>| {
+        Some(CheckProblem::new("llm", CheckSeverity::Warning, message, repair))
+    };
+
+    match &config.llm.backend {
+        LLMBackendConfig::Local if !cfg!(feature = "local-llm") => warning(
+            "This build can't run local models; plans use rule-based generation".to_string(),
+            None,
+        ),
+        LLMBackendConfig::Local if !config.llm.model_path.exists() => warning(
+            format!("Model file {} not found; plans use rule-based generation", config.llm.model_path.display()),
+            None,
+        ),
+        LLMBackendConfig::Local => {
+            let tokenizer = config.llm.model_path.with_file_name("tokenizer.json");
+            if tokenizer.exists() {
+                None
+            } else {
+                warning(format!("{} not found next to the model; plans use rule-based generation", tokenizer.display()), None)
+            }
+        }
+        LLMBackendConfig::Anthropic { api_key_env, .. } if std::env::var(api_key_env).is_err() => warning(
+            format!("{} is not set; plans use rule-based generation", api_key_env),
+            Some(RepairAction::ResetConfigSection { section: ConfigSection::Llm }),
+        ),
+        _ => None,
+    }
+}
@@ -0,0 +1,247 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::io::{Read, Write};
+
+use crate::dsl::{migrate, ExtractionMethod, ScrapePlan, Transform, CURRENT_DSL_VERSION};
+
+/// File extension of plan bundles
+pub const BUNDLE_EXTENSION: &str = "wssplan";
+
+/// Marker identifying a bundle document
+const BUNDLE_FORMAT: &str = "winscrape-plan-bundle";
+
+/// Bundle layout version written by this build
+const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Headers whose values are secrets and never leave the machine in a bundle
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie", "x-api-key", "x-auth-token"];
+
+/// Portable plan archive: a gzip-compressed JSON document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanBundle {
+    pub format: String,
+    pub format_version: u32,
+    /// DSL version of `plan_yaml`
+    pub dsl_version: String,
+    pub created_at: DateTime<Utc>,
+    pub name: String,
+    pub description: String,
+    /// Plan with credentials replaced by `${NAME}` placeholders
+    pub plan_yaml: String,
+    /// Plan metadata, duplicated for tools that don't parse the plan
+    pub metadata: Map<String, Value>,
+    /// JSON Schema of one output row
+    pub output_schema: Value,
+    /// Values the importer must supply before running the plan
+    pub credentials: Vec<CredentialPlaceholder>,
+}
+
+/// A secret removed from a bundled plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialPlaceholder {
+    /// Variable name used in the plan, e.g. `AUTHORIZATION`
+    pub name: String,
+    /// Location in the plan, e.g. `anti_blocking.headers.Authorization`
+    pub path: String,
+    pub description: String,
+}
+
+impl PlanBundle {
+    /// Build a bundle from a plan, stripping credentials
+    pub fn from_plan(plan: &ScrapePlan) -> Result<Self> {
+        let mut shared = plan.clone();
+        let credentials = strip_credentials(&mut shared);
+
+        let metadata: Map<String, Value> = shared.metadata.clone()
+            .map(|m| m.into_iter().collect())
+            .unwrap_or_default();
+        let name = metadata.get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&shared.target.domain)
+            .to_string();
+        let description = metadata.get("description")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        Ok(Self {
+            format: BUNDLE_FORMAT.to_string(),
+            format_version: BUNDLE_FORMAT_VERSION,
+            dsl_version: shared.version.clone(),
+            created_at: Utc::now(),
+            name,
+            description,
+            plan_yaml: shared.to_yaml()?,
+            metadata,
+            output_schema: output_schema(&shared),
+            credentials,
+        })
+    }
+
+    /// Serialize and compress the bundle
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&serde_json::to_vec_pretty(self)?)?;
+        Ok(encoder.finish()?)
+    }
+
+    /// Read a bundle written by `to_bytes`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut json = Vec::new();
+        GzDecoder::new(bytes).read_to_end(&mut json)
+            .map_err(|e| anyhow::anyhow!("Not a plan bundle: {}", e))?;
+
+        let bundle: PlanBundle = serde_json::from_slice(&json)?;
+        if bundle.format != BUNDLE_FORMAT {
+            return Err(anyhow::anyhow!("Not a plan bundle: unexpected format '{}'", bundle.format));
+        }
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(anyhow::anyhow!(
+                "Bundle format version {} is newer than the supported version {}",
+                bundle.format_version, BUNDLE_FORMAT_VERSION
+            ));
+        }
+
+        Ok(bundle)
+    }
+
+    /// Parse the bundled plan, upgrading it to the current DSL version
+    pub fn plan(&self) -> Result<ScrapePlan> {
+        migrate::from_yaml(&self.plan_yaml)
+    }
+}
+
+impl ScrapePlan {
+    /// Package the plan as a `.wssplan` bundle
+    pub fn to_bundle(&self) -> Result<Vec<u8>> {
+        PlanBundle::from_plan(self)?.to_bytes()
+    }
+
+    /// Load a plan from a `.wssplan` bundle
+    ///
+    /// Credentials are left as `${NAME}` placeholders to be supplied as run variables.
+    pub fn from_bundle(bytes: &[u8]) -> Result<Self> {
+        PlanBundle::from_bytes(bytes)?.plan()
+    }
+}
+
+/// Replace secret header values and proxy logins with placeholders
+fn strip_credentials(plan: &mut ScrapePlan) -> Vec<CredentialPlaceholder> {
+    let mut credentials = Vec::new();
+
+    if let Some(headers) = plan.anti_blocking.headers.as_mut() {
+        let mut names: Vec<String> = headers.keys().cloned().collect();
+        names.sort();
+
+        for header in names {
+            if !SENSITIVE_HEADERS.contains(&header.to_lowercase().as_str()) {
+                continue;
+            }
+            let value = headers.get_mut(&header).unwrap();
+            if value.contains("${") {
+                continue;
+            }
+
+            let name = header.to_uppercase().replace('-', "_");
+            *value = format!("${{{}}}", name);
+            credentials.push(CredentialPlaceholder {
+                name,
+                path: format!("anti_blocking.headers.{}", header),
+                description: format!("Value of the {} header", header),
+            });
+        }
+    }
+
+    if let Some(proxy) = plan.anti_blocking.proxy.as_mut() {
+        for (i, proxy_url) in proxy.proxies.iter_mut().enumerate() {
+            let (scheme, rest) = match proxy_url.split_once("://") {
+                Some(parts) => parts,
+                None => continue,
+            };
+            let authority_end = rest.find('/').unwrap_or(rest.len());
+            let host = match rest[..authority_end].rsplit_once('@') {
+                Some((userinfo, _)) if userinfo.contains("${") => continue,
+                Some((_, host)) => host,
+                None => continue,
+            };
+
+            let name = format!("PROXY_{}_LOGIN", i + 1);
+            *proxy_url = format!("{}://${{{}}}@{}{}", scheme, name, host, &rest[authority_end..]);
+            credentials.push(CredentialPlaceholder {
+                name,
+                path: format!("anti_blocking.proxy.proxies[{}]", i),
+                description: "Proxy login as user:password".to_string(),
+            });
+        }
+    }
+
+    credentials
+}
+
+/// Describe the rows a plan produces as a JSON Schema
+fn output_schema(plan: &ScrapePlan) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in &plan.rules.fields {
+        let numeric = field.transform.as_ref()
+            .map_or(false, |t| t.iter().any(|t| matches!(t, Transform::ParseNumber)));
+
+        let schema = if numeric {
+            json!({ "type": "number" })
+        } else {
+            match field.extraction {
+                ExtractionMethod::Href | ExtractionMethod::Src => json!({ "type": "string", "format": "uri" }),
+                _ => json!({ "type": "string" }),
+            }
+        };
+
+        properties.insert(field.name.clone(), schema);
+        if field.required {
+            required.push(json!(field.name));
+        }
+    }
+
+    json!({
+        "$schema": crate::dsl::schema::SCHEMA_DIALECT,
+        "title": format!("{} row", plan.target.domain),
+        "type": "object",
+        "required": required,
+        "properties": properties,
+        "x-dsl-version": CURRENT_DSL_VERSION
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_bundle_round_trip_strips_credentials() {
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.anti_blocking.headers = Some(HashMap::from([
+            ("Authorization".to_string(), "Bearer secret".to_string()),
+            ("Accept".to_string(), "text/html".to_string()),
+        ]));
+
+        let bytes = plan.to_bundle().unwrap();
+        let bundle = PlanBundle::from_bytes(&bytes).unwrap();
+
+        assert_eq!(bundle.credentials.len(), 1);
+        assert_eq!(bundle.credentials[0].name, "AUTHORIZATION");
+        assert!(!bundle.plan_yaml.contains("secret"));
+        assert!(bundle.output_schema["properties"].get("title").is_some());
+
+        let imported = ScrapePlan::from_bundle(&bytes).unwrap();
+        let headers = imported.anti_blocking.headers.unwrap();
+        assert_eq!(headers["Authorization"], "${AUTHORIZATION}");
+        assert_eq!(headers["Accept"], "text/html");
+    }
+}
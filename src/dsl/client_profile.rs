@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Named clients a plan can present itself as, bundling the user agent with
+/// the headers that browser or crawler sends alongside it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClientProfile {
+    #[serde(rename = "desktop_chrome")]
+    DesktopChrome,
+    #[serde(rename = "mobile_safari")]
+    MobileSafari,
+    #[serde(rename = "googlebot")]
+    Googlebot,
+}
+
+impl ClientProfile {
+    /// Get all available profiles
+    pub fn all() -> Vec<Self> {
+        vec![Self::DesktopChrome, Self::MobileSafari, Self::Googlebot]
+    }
+
+    /// Get the profile identifier used in plans
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DesktopChrome => "desktop_chrome",
+            Self::MobileSafari => "mobile_safari",
+            Self::Googlebot => "googlebot",
+        }
+    }
+
+    /// Get the display name of the profile
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DesktopChrome => "Desktop Chrome",
+            Self::MobileSafari => "Mobile Safari",
+            Self::Googlebot => "Googlebot",
+        }
+    }
+
+    /// Get the user agent the profile sends
+    pub fn user_agent(&self) -> &'static str {
+        match self {
+            Self::DesktopChrome => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+            Self::MobileSafari => "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1",
+            Self::Googlebot => "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        }
+    }
+
+    /// Get the headers the profile sends besides the user agent
+    ///
+    /// Only Chrome sends `sec-ch-ua` client hints; Safari and crawlers don't.
+    pub fn headers(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Self::DesktopChrome => vec![
+                ("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7"),
+                ("Accept-Language", "en-US,en;q=0.9"),
+                ("sec-ch-ua", "\"Not_A Brand\";v=\"8\", \"Chromium\";v=\"120\", \"Google Chrome\";v=\"120\""),
+                ("sec-ch-ua-mobile", "?0"),
+                ("sec-ch-ua-platform", "\"Windows\""),
+            ],
+            Self::MobileSafari => vec![
+                ("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+                ("Accept-Language", "en-US,en;q=0.9"),
+            ],
+            Self::Googlebot => vec![
+                ("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for ClientProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ClientProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .into_iter()
+            .find(|profile| profile.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| anyhow::anyhow!("Unknown client profile: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::ScrapePlan;
+
+    #[test]
+    fn test_profile_headers_give_way_to_plan_headers() {
+        let mut plan = ScrapePlan::default();
+        assert_eq!(plan.anti_blocking.fixed_user_agent(), None);
+        assert_eq!(plan.anti_blocking.request_headers(), None);
+
+        plan.anti_blocking.client_profile = Some("desktop_chrome".parse().unwrap());
+        plan.anti_blocking.headers = Some([("accept-language".to_string(), "de-DE".to_string())].into());
+        assert_eq!(plan.anti_blocking.fixed_user_agent(), Some(ClientProfile::DesktopChrome.user_agent()));
+
+        let headers = plan.anti_blocking.request_headers().unwrap();
+        assert_eq!(headers["sec-ch-ua-mobile"], "?0");
+        assert_eq!(headers["accept-language"], "de-DE");
+        assert!(!headers.contains_key("Accept-Language"));
+
+        plan.anti_blocking.user_agent = Some("ShopMonitor/1.0".to_string());
+        assert_eq!(plan.anti_blocking.fixed_user_agent(), Some("ShopMonitor/1.0"));
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dsl::{OutputFormat, ScrapePlan};
+use crate::i18n::I18nManager;
+
+/// Plain-language description of what a plan will do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanExplanation {
+    /// Language code of the text
+    pub language: String,
+    /// One-sentence overview: pages, fields, delays and export formats
+    pub summary: String,
+    /// Further notes on robots.txt, pagination, limits and duplicates
+    pub details: Vec<String>,
+}
+
+impl PlanExplanation {
+    /// Summary followed by the details as one paragraph
+    pub fn to_text(&self) -> String {
+        std::iter::once(self.summary.as_str())
+            .chain(self.details.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Describe a plan in the manager's current language
+pub fn explain_plan(plan: &ScrapePlan, i18n: &I18nManager) -> PlanExplanation {
+    let fields = if plan.rules.fields.is_empty() {
+        i18n.t("explain.no_fields")
+    } else {
+        plan.rules.fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>().join("/")
+    };
+
+    let delays = &plan.anti_blocking.randomized_delays;
+    let delay = if delays.min_ms == delays.max_ms {
+        i18n.t_args("explain.delay_fixed", &[("min", seconds(delays.min_ms))])
+    } else {
+        i18n.t_args("explain.delay_range", &[("min", seconds(delays.min_ms)), ("max", seconds(delays.max_ms))])
+    };
+
+    let formats = plan.output.format.iter()
+        .map(|format| match format {
+            OutputFormat::CSV => "CSV",
+            OutputFormat::JSON => "JSON",
+            OutputFormat::XLSX => "XLSX",
+            OutputFormat::Parquet => "Parquet",
+            OutputFormat::Ndjson => "NDJSON",
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let summary = i18n.t_args("explain.summary", &[
+        ("pages", estimated_pages(plan).to_string()),
+        ("domain", plan.target.domain.clone()),
+        ("fields", fields),
+        ("delay", delay),
+        ("formats", formats),
+    ]);
+
+    let mut details = Vec::new();
+    details.push(i18n.t_args("explain.politeness", &[("preset", plan.anti_blocking.politeness.name().to_string())]));
+    details.push(i18n.t(if plan.anti_blocking.respect_robots_txt {
+        "explain.robots_respected"
+    } else {
+        "explain.robots_ignored"
+    }));
+
+    if let Some(pagination) = &plan.rules.pagination {
+        let pages = pagination.max_pages.or(plan.target.max_pages).unwrap_or(1);
+        details.push(i18n.t_args("explain.pagination", &[("pages", pages.to_string())]));
+    }
+
+    let required: Vec<&str> = plan.rules.fields.iter()
+        .filter(|f| f.required)
+        .map(|f| f.name.as_str())
+        .collect();
+    if !required.is_empty() {
+        details.push(i18n.t_args("explain.required", &[("fields", required.join("/"))]));
+    }
+
+    if let Some(limit) = plan.output.limit {
+        details.push(i18n.t_args("explain.limit", &[("limit", limit.to_string())]));
+    }
+
+    if let Some(keys) = plan.output.dedupe_keys.as_ref().filter(|k| !k.is_empty()) {
+        details.push(i18n.t_args("explain.dedupe", &[("keys", keys.join("/"))]));
+    }
+
+    if let Some(proxy) = plan.anti_blocking.proxy.as_ref().filter(|p| p.enabled && !p.proxies.is_empty()) {
+        details.push(i18n.t_args("explain.proxy", &[("count", proxy.proxies.len().to_string())]));
+    }
+
+    PlanExplanation {
+        language: i18n.current_language().code().to_string(),
+        summary,
+        details,
+    }
+}
+
+/// Upper bound on the pages a run visits
+fn estimated_pages(plan: &ScrapePlan) -> usize {
+    let urls = plan.get_all_urls()
+        .map(|urls| urls.len())
+        .unwrap_or(plan.target.start_urls.len());
+
+    match &plan.rules.pagination {
+        Some(pagination) => urls * pagination.max_pages.or(plan.target.max_pages).unwrap_or(1).max(1),
+        None => urls,
+    }
+}
+
+/// Milliseconds as seconds without trailing zeros, e.g. `1500` -> `1.5`
+fn seconds(ms: u64) -> String {
+    let text = format!("{:.1}", ms as f64 / 1000.0);
+    text.trim_end_matches(".0").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+    use crate::i18n::Language;
+
+    #[test]
+    fn test_explain_plan() {
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.anti_blocking.randomized_delays.min_ms = 1000;
+        plan.anti_blocking.randomized_delays.max_ms = 3000;
+
+        let mut i18n = I18nManager::new();
+        let explanation = explain_plan(&plan, &i18n);
+        assert!(explanation.summary.starts_with("This will visit up to"));
+        assert!(explanation.summary.contains(&plan.target.domain));
+        assert!(explanation.summary.contains("wait 1–3 s between requests"));
+        assert!(!explanation.summary.contains('{'));
+
+        i18n.set_language(Language::Korean);
+        let korean = explain_plan(&plan, &i18n);
+        assert_eq!(korean.language, "ko");
+        assert!(korean.summary.contains("1–3초"));
+    }
+}
@@ -1,5 +1,33 @@
 use anyhow::Result;
-use crate::dsl::{ScrapePlan, Target, Field, SelectorType, ExtractionMethod};
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::dsl::{ScrapePlan, Target, Field, SelectorType, ExtractionMethod, Transform};
+
+/// Minimum number of repeats for an element group to count as a list of items
+const MIN_ITEM_REPEATS: usize = 3;
+
+/// Fraction of items a field candidate must appear in
+const MIN_FIELD_COVERAGE: f64 = 0.5;
+
+/// Item selector candidates kept in a suggestion
+const MAX_ITEM_SUGGESTIONS: usize = 5;
+
+/// Field suggestions kept for the best item selector
+const MAX_FIELD_SUGGESTIONS: usize = 8;
+
+/// Sample values kept per suggested field
+const MAX_SAMPLES: usize = 3;
+
+/// Elements that never hold scrapeable content
+const IGNORED_TAGS: &[&str] = &[
+    "html", "head", "body", "script", "style", "noscript", "template", "svg", "path",
+    "meta", "link", "br", "hr", "option", "iframe",
+];
+
+/// Class-less elements that still usually mark list items
+const LIST_TAGS: &[&str] = &["li", "tr", "article"];
 
 /// DSL generator for creating scraping plans programmatically
 pub struct DSLGenerator;
@@ -14,6 +42,7 @@ impl DSLGenerator {
             start_urls: vec![start_url.to_string()],
             url_patterns: None,
             max_pages: Some(10),
+            depends_on: None,
         };
         
         // Add basic fields
@@ -75,4 +104,504 @@ impl DSLGenerator {
         
         Ok(plan)
     }
+    
+    /// Propose an item selector and fields for a page by clustering its repeated elements
+    ///
+    /// Elements are grouped by tag and classes; groups that repeat, share a parent and
+    /// have similar, content-bearing children score highest. Fields are proposed from the
+    /// children of the best group, scored by how many items contain them.
+    pub fn suggest_selectors(html: &str) -> SelectorSuggestions {
+        let document = Html::parse_document(html);
+        
+        let mut groups: HashMap<String, Vec<ElementRef>> = HashMap::new();
+        for element in document.root_element().descendants().filter_map(ElementRef::wrap) {
+            if let Some(selector) = item_selector_for(&element) {
+                groups.entry(selector).or_default().push(element);
+            }
+        }
+        
+        let mut scored: Vec<(ItemSuggestion, Vec<ElementRef>)> = groups.into_iter()
+            .filter(|(_, elements)| elements.len() >= MIN_ITEM_REPEATS)
+            .map(|(selector, elements)| {
+                let suggestion = ItemSuggestion {
+                    selector,
+                    matches: elements.len(),
+                    confidence: item_confidence(&elements),
+                };
+                (suggestion, elements)
+            })
+            .filter(|(suggestion, _)| suggestion.confidence > 0.0)
+            .collect();
+        
+        scored.sort_by(|a, b| {
+            b.0.confidence.total_cmp(&a.0.confidence)
+                .then(b.0.matches.cmp(&a.0.matches))
+                .then(a.0.selector.cmp(&b.0.selector))
+        });
+        
+        let fields = scored.first()
+            .map(|(_, elements)| suggest_fields(elements))
+            .unwrap_or_default();
+        
+        SelectorSuggestions {
+            url: None,
+            items: scored.into_iter()
+                .take(MAX_ITEM_SUGGESTIONS)
+                .map(|(suggestion, _)| suggestion)
+                .collect(),
+            fields,
+        }
+    }
+    
+    /// Generate a plan for a page from its HTML, using suggested selectors
+    pub fn generate_from_page(domain: &str, start_url: &str, html: &str) -> Result<ScrapePlan> {
+        let mut plan = Self::generate_basic(domain, start_url)?;
+        let mut suggestions = Self::suggest_selectors(html);
+        suggestions.url = Some(start_url.to_string());
+        
+        if suggestions.best_item().is_none() {
+            return Err(anyhow::anyhow!("No repeated item structure found on {}", start_url));
+        }
+        
+        plan.rules.fields.clear();
+        suggestions.apply_to(&mut plan, 0.0);
+        plan.add_metadata("selector_suggestions".to_string(), serde_json::to_value(&suggestions)?);
+        
+        Ok(plan)
+    }
+}
+
+/// Selectors proposed for a sample page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SelectorSuggestions {
+    /// Page the suggestions were made for
+    pub url: Option<String>,
+    /// Item selector candidates, best first
+    pub items: Vec<ItemSuggestion>,
+    /// Fields found inside the best item candidate, best first
+    pub fields: Vec<FieldSuggestion>,
+}
+
+/// A candidate `item_selector`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemSuggestion {
+    pub selector: String,
+    /// Number of elements the selector matched on the page
+    pub matches: usize,
+    /// Score from 0.0 to 1.0
+    pub confidence: f64,
+}
+
+/// A candidate field, relative to the suggested item selector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSuggestion {
+    pub name: String,
+    pub selector: String,
+    pub extraction: ExtractionMethod,
+    /// Fraction of items containing the field
+    pub coverage: f64,
+    /// Score from 0.0 to 1.0
+    pub confidence: f64,
+    /// Example values from the page
+    pub samples: Vec<String>,
+}
+
+impl SelectorSuggestions {
+    /// Highest scoring item selector
+    pub fn best_item(&self) -> Option<&ItemSuggestion> {
+        self.items.first()
+    }
+    
+    /// Use suggestions scoring at least `min_confidence` in a plan
+    ///
+    /// Replaces the item selector and the selectors of fields with a matching name;
+    /// a plan without fields gets every suggested field. Returns the number of changes.
+    pub fn apply_to(&self, plan: &mut ScrapePlan, min_confidence: f64) -> usize {
+        let best = match self.best_item() {
+            Some(best) if best.confidence >= min_confidence => best,
+            _ => return 0,
+        };
+        
+        let mut applied = 0;
+        if plan.rules.item_selector != best.selector {
+            plan.rules.item_selector = best.selector.clone();
+            applied += 1;
+        }
+        
+        let suggested = self.fields.iter().filter(|f| f.confidence >= min_confidence);
+        if plan.rules.fields.is_empty() {
+            for suggestion in suggested {
+                plan.rules.fields.push(suggestion.to_field());
+                applied += 1;
+            }
+        } else {
+            for suggestion in suggested {
+                if let Some(field) = plan.rules.fields.iter_mut().find(|f| f.name == suggestion.name) {
+                    if field.selector != suggestion.selector {
+                        field.selector = suggestion.selector.clone();
+                        field.extraction = suggestion.extraction.clone();
+                        applied += 1;
+                    }
+                }
+            }
+        }
+        
+        applied
+    }
+}
+
+impl FieldSuggestion {
+    /// Plan field for this suggestion
+    pub fn to_field(&self) -> Field {
+        let transform = match (&self.extraction, self.name.as_str()) {
+            (ExtractionMethod::Text, "price") => vec![Transform::Trim, Transform::ParseNumber],
+            (ExtractionMethod::Text, _) => vec![Transform::Trim],
+            _ => Vec::new(),
+        };
+        
+        Field {
+            name: self.name.clone(),
+            selector: self.selector.clone(),
+            selector_type: SelectorType::CSS,
+            extraction: self.extraction.clone(),
+            required: self.coverage >= 0.99,
+            transform: (!transform.is_empty()).then_some(transform),
+        }
+    }
+}
+
+/// CSS-safe classes of an element, sorted
+pub(super) fn element_classes(element: &ElementRef) -> Vec<String> {
+    let mut classes: Vec<String> = element.value().classes()
+        .filter(|class| {
+            class.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '-')
+                && class.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+        .map(str::to_string)
+        .collect();
+    classes.sort();
+    classes.dedup();
+    classes
+}
+
+/// `tag.class1.class2`, or just the tag for class-less elements
+pub(super) fn element_signature(element: &ElementRef) -> String {
+    let mut signature = element.value().name().to_string();
+    for class in element_classes(element) {
+        signature.push('.');
+        signature.push_str(&class);
+    }
+    signature
+}
+
+/// Selector grouping an element with its repeats, if it can be a list item
+pub(super) fn item_selector_for(element: &ElementRef) -> Option<String> {
+    let tag = element.value().name();
+    if IGNORED_TAGS.contains(&tag) {
+        return None;
+    }
+    
+    if !element_classes(element).is_empty() {
+        return Some(element_signature(element));
+    }
+    
+    // Class-less rows and list entries are qualified by a distinctive parent
+    if LIST_TAGS.contains(&tag) {
+        let parent = element.parent().and_then(ElementRef::wrap)?;
+        let parent_selector = match parent.value().id() {
+            Some(id) if id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') => {
+                format!("{}#{}", parent.value().name(), id)
+            }
+            _ if !element_classes(&parent).is_empty() => element_signature(&parent),
+            _ => return None,
+        };
+        return Some(format!("{} > {}", parent_selector, tag));
+    }
+    
+    None
+}
+
+/// Text directly inside an element, ignoring its children
+pub(super) fn own_text(element: &ElementRef) -> String {
+    element.children()
+        .filter_map(|node| node.value().as_text())
+        .map(|text| text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// All text of an element with whitespace collapsed
+fn full_text(element: &ElementRef) -> String {
+    element.text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Score a group of similar elements as list items
+fn item_confidence(elements: &[ElementRef]) -> f64 {
+    let count = elements.len() as f64;
+    
+    // Content: text leaves, links and images inside each element
+    let mut text_score = 0.0;
+    let mut with_link = 0.0;
+    let mut with_image = 0.0;
+    let mut child_signatures: Vec<HashSet<String>> = Vec::with_capacity(elements.len());
+    
+    for element in elements {
+        let descendants: Vec<ElementRef> = element.descendants()
+            .skip(1)
+            .filter_map(ElementRef::wrap)
+            .collect();
+        
+        let text_leaves = descendants.iter()
+            .chain(std::iter::once(element))
+            .filter(|e| !own_text(e).is_empty())
+            .count();
+        text_score += (text_leaves.min(4) as f64) / 4.0;
+        
+        if descendants.iter().any(|e| e.value().name() == "a") || element.value().name() == "a" {
+            with_link += 1.0;
+        }
+        if descendants.iter().any(|e| e.value().name() == "img") {
+            with_image += 1.0;
+        }
+        
+        child_signatures.push(descendants.iter().map(element_signature).collect());
+    }
+    
+    // Items with a single leaf and no children are values, not containers
+    if child_signatures.iter().all(HashSet::is_empty) {
+        return 0.0;
+    }
+    
+    let richness = 0.5 * text_score / count + 0.25 * with_link / count + 0.25 * with_image / count;
+    
+    // Consistency: share of each element's children that most elements also have
+    let mut presence: HashMap<&str, usize> = HashMap::new();
+    for signatures in &child_signatures {
+        for signature in signatures {
+            *presence.entry(signature.as_str()).or_default() += 1;
+        }
+    }
+    let consistency = child_signatures.iter()
+        .map(|signatures| {
+            if signatures.is_empty() {
+                return 0.0;
+            }
+            let common = signatures.iter()
+                .filter(|s| presence[s.as_str()] as f64 >= count / 2.0)
+                .count();
+            common as f64 / signatures.len() as f64
+        })
+        .sum::<f64>() / count;
+    
+    // Siblings: share of elements under the most common parent
+    let mut parents: HashMap<_, usize> = HashMap::new();
+    for element in elements {
+        if let Some(parent) = element.parent() {
+            *parents.entry(parent.id()).or_default() += 1;
+        }
+    }
+    let siblings = parents.values().copied().max().unwrap_or(0) as f64 / count;
+    
+    let repetition = (count / 10.0).min(1.0);
+    
+    (0.35 * consistency + 0.3 * richness + 0.2 * siblings + 0.15 * repetition).clamp(0.0, 1.0)
+}
+
+/// Values found for one field candidate across items
+#[derive(Default)]
+struct FieldCandidate {
+    items: usize,
+    values: Vec<String>,
+}
+
+/// Propose fields from the children of a group of items
+fn suggest_fields(items: &[ElementRef]) -> Vec<FieldSuggestion> {
+    let mut candidates: HashMap<(String, &'static str), FieldCandidate> = HashMap::new();
+    
+    for item in items {
+        let mut seen: HashSet<(String, &'static str)> = HashSet::new();
+        
+        for element in item.descendants().skip(1).filter_map(ElementRef::wrap) {
+            let tag = element.value().name();
+            if IGNORED_TAGS.contains(&tag) {
+                continue;
+            }
+            let selector = element_signature(&element);
+            
+            let mut found: Vec<(&'static str, String)> = Vec::new();
+            match tag {
+                "a" => {
+                    if let Some(href) = element.value().attr("href").filter(|h| !h.is_empty() && !h.starts_with('#')) {
+                        found.push(("href", href.to_string()));
+                    }
+                }
+                "img" => {
+                    if let Some(src) = element.value().attr("src").or_else(|| element.value().attr("data-src")) {
+                        found.push(("src", src.to_string()));
+                    }
+                }
+                _ => {}
+            }
+            if !own_text(&element).is_empty() {
+                let text = full_text(&element);
+                if text.len() <= 300 {
+                    found.push(("text", text));
+                }
+            }
+            
+            // The scraper uses the first match of a selector in each item
+            for (kind, value) in found {
+                let key = (selector.clone(), kind);
+                if seen.insert(key.clone()) {
+                    let candidate = candidates.entry(key).or_default();
+                    candidate.items += 1;
+                    candidate.values.push(value);
+                }
+            }
+        }
+    }
+    
+    let total = items.len() as f64;
+    let mut suggestions: Vec<FieldSuggestion> = candidates.into_iter()
+        .filter_map(|((selector, kind), candidate)| {
+            let coverage = candidate.items as f64 / total;
+            if coverage < MIN_FIELD_COVERAGE {
+                return None;
+            }
+            
+            // Values that never change across items are labels, not data
+            let distinct = candidate.values.iter().collect::<HashSet<_>>().len() as f64;
+            let variability = distinct / candidate.values.len() as f64;
+            
+            let extraction = match kind {
+                "href" => ExtractionMethod::Href,
+                "src" => ExtractionMethod::Src,
+                _ => ExtractionMethod::Text,
+            };
+            
+            let mut samples: Vec<String> = Vec::new();
+            for value in &candidate.values {
+                if samples.len() < MAX_SAMPLES && !samples.contains(value) {
+                    samples.push(value.clone());
+                }
+            }
+            
+            Some(FieldSuggestion {
+                name: field_name(&selector, &extraction, &samples),
+                selector,
+                extraction,
+                coverage,
+                confidence: coverage * (0.4 + 0.6 * variability),
+                samples,
+            })
+        })
+        .collect();
+    
+    suggestions.sort_by(|a, b| {
+        b.confidence.total_cmp(&a.confidence).then(a.selector.cmp(&b.selector))
+    });
+    suggestions.truncate(MAX_FIELD_SUGGESTIONS);
+    
+    // Keep field names unique
+    let mut used: HashMap<String, usize> = HashMap::new();
+    for suggestion in suggestions.iter_mut() {
+        let count = used.entry(suggestion.name.clone()).or_default();
+        *count += 1;
+        if *count > 1 {
+            suggestion.name = format!("{}_{}", suggestion.name, count);
+        }
+    }
+    
+    suggestions
+}
+
+/// Guess a field name from its selector, extraction and values
+pub(super) fn field_name(selector: &str, extraction: &ExtractionMethod, samples: &[String]) -> String {
+    match extraction {
+        ExtractionMethod::Href => return "link".to_string(),
+        ExtractionMethod::Src => return "image".to_string(),
+        _ => {}
+    }
+    
+    let lower = selector.to_lowercase();
+    let tag = lower.split('.').next().unwrap_or_default();
+    let looks_like_price = !samples.is_empty() && samples.iter().all(|s| {
+        s.chars().any(|c| c.is_ascii_digit()) && s.chars().any(|c| "$€£¥₹".contains(c))
+    });
+    
+    const KEYWORDS: &[(&str, &str)] = &[
+        ("price", "price"),
+        ("cost", "price"),
+        ("title", "title"),
+        ("headline", "title"),
+        ("name", "name"),
+        ("author", "author"),
+        ("byline", "author"),
+        ("date", "date"),
+        ("time", "date"),
+        ("rating", "rating"),
+        ("desc", "description"),
+        ("summary", "description"),
+        ("excerpt", "description"),
+        ("category", "category"),
+        ("tag", "tags"),
+    ];
+    
+    if looks_like_price {
+        return "price".to_string();
+    }
+    if let Some((_, name)) = KEYWORDS.iter().find(|(keyword, _)| lower.contains(keyword)) {
+        return name.to_string();
+    }
+    if matches!(tag, "h1" | "h2" | "h3" | "h4" | "h5" | "h6") {
+        return "title".to_string();
+    }
+    if tag == "time" {
+        return "date".to_string();
+    }
+    
+    // Fall back to the last class, or the tag
+    let base = lower.rsplit('.').next().unwrap_or(tag);
+    let name: String = base.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    name.trim_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_selectors_finds_repeated_items() {
+        let mut html = String::from("<html><body><nav><a href='/'>Home</a></nav><ul class='results'>");
+        for i in 0..5 {
+            html.push_str(&format!(
+                "<li class='product-card'><h2 class='product-title'>Item {i}</h2>\
+                 <span class='price'>${i}.99</span><a href='/p/{i}'>View</a>\
+                 <img src='/img/{i}.jpg'></li>"
+            ));
+        }
+        html.push_str("</ul></body></html>");
+
+        let suggestions = DSLGenerator::suggest_selectors(&html);
+        let best = suggestions.best_item().unwrap();
+        assert_eq!(best.selector, "li.product-card");
+        assert_eq!(best.matches, 5);
+
+        let field = |name: &str| suggestions.fields.iter().find(|f| f.name == name).unwrap();
+        assert_eq!(field("title").selector, "h2.product-title");
+        assert_eq!(field("price").selector, "span.price");
+        assert_eq!(field("link").selector, "a");
+        assert!(matches!(field("image").extraction, ExtractionMethod::Src));
+
+        let mut plan = ScrapePlan::default();
+        plan.rules.fields.clear();
+        assert!(suggestions.apply_to(&mut plan, 0.5) > 0);
+        assert_eq!(plan.rules.item_selector, "li.product-card");
+    }
 }
@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::dsl::{DSLValidator, ExtractionMethod, PaginationMethod, ScrapePlan, SelectorType};
+
+/// Minimum delay below which a plan is likely to get rate limited or blocked
+const LOW_DELAY_MS: u64 = 500;
+
+/// Selectors that match far too many elements to identify an item on their own
+const GENERIC_SELECTORS: &[&str] = &["*", "div", "span", "p", "a", "li", "ul", "tr", "td", "section", "article", "body", "html"];
+
+/// Kind of likely problem reported by the linter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    /// A selector that matches almost anything on the page
+    GenericSelector,
+    /// Paginated or multi-URL plans without dedupe keys
+    MissingDedupeKeys,
+    /// Delays short enough to trigger rate limiting
+    LowDelay,
+    /// A required field relying on a single selector
+    RequiredWithoutFallback,
+    /// A URL pattern that can never produce a URL on the target domain
+    UnreachableUrlPattern,
+    /// Pagination without a page limit
+    UnboundedPagination,
+}
+
+impl LintRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintRule::GenericSelector => "generic_selector",
+            LintRule::MissingDedupeKeys => "missing_dedupe_keys",
+            LintRule::LowDelay => "low_delay",
+            LintRule::RequiredWithoutFallback => "required_without_fallback",
+            LintRule::UnreachableUrlPattern => "unreachable_url_pattern",
+            LintRule::UnboundedPagination => "unbounded_pagination",
+        }
+    }
+}
+
+impl std::fmt::Display for LintRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A likely problem in a plan that does not make it invalid
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintWarning {
+    /// Location in the plan, e.g. `rules.item_selector`
+    pub path: String,
+    pub rule: LintRule,
+    pub message: String,
+    /// What to change to address the warning
+    pub suggestion: String,
+}
+
+impl LintWarning {
+    fn new(path: impl Into<String>, rule: LintRule, message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            rule,
+            message: message.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl DSLValidator {
+    /// Flag likely problems in a plan that validation lets through
+    pub fn lint(&self, plan: &ScrapePlan) -> Vec<LintWarning> {
+        let mut warnings = Vec::new();
+
+        lint_selectors(plan, &mut warnings);
+        lint_dedupe(plan, &mut warnings);
+        lint_delays(plan, &mut warnings);
+        lint_url_patterns(plan, &mut warnings);
+        lint_pagination(plan, &mut warnings);
+
+        warnings
+    }
+}
+
+fn is_generic_selector(selector: &str) -> bool {
+    GENERIC_SELECTORS.contains(&selector.trim().to_lowercase().as_str())
+}
+
+fn lint_selectors(plan: &ScrapePlan, warnings: &mut Vec<LintWarning>) {
+    if is_generic_selector(&plan.rules.item_selector) {
+        warnings.push(LintWarning::new(
+            "rules.item_selector",
+            LintRule::GenericSelector,
+            format!("Item selector '{}' matches almost every element on the page", plan.rules.item_selector),
+            "Add a class or attribute, e.g. \"div.product\" or \"li[data-id]\"",
+        ));
+    }
+
+    for (i, field) in plan.rules.fields.iter().enumerate() {
+        let path = format!("rules.fields[{}]", i);
+
+        // Bare link/image tags are fine for href/src, but too broad for text
+        let expects_tag = matches!(
+            (&field.extraction, field.selector.trim()),
+            (ExtractionMethod::Href, "a") | (ExtractionMethod::Src, "img")
+        );
+        if !expects_tag && is_generic_selector(&field.selector) {
+            warnings.push(LintWarning::new(
+                format!("{}.selector", path),
+                LintRule::GenericSelector,
+                format!("Field '{}' uses the generic selector '{}'", field.name, field.selector),
+                "Target a specific class inside the item, e.g. \".price\"",
+            ));
+        }
+
+        // CSS selector lists ("a, b") act as fallbacks when the first one misses
+        let has_fallback = matches!(field.selector_type, SelectorType::CSS) && field.selector.contains(',');
+        if field.required && !has_fallback {
+            warnings.push(LintWarning::new(
+                format!("{}.selector", path),
+                LintRule::RequiredWithoutFallback,
+                format!("Required field '{}' has no fallback selector; items are dropped when it misses", field.name),
+                format!("Add an alternative, e.g. \"{}, [itemprop={}]\", or make the field optional", field.selector, field.name),
+            ));
+        }
+    }
+}
+
+fn lint_dedupe(plan: &ScrapePlan, warnings: &mut Vec<LintWarning>) {
+    let multi_page = plan.rules.pagination.is_some()
+        || plan.target.start_urls.len() > 1
+        || plan.target.url_patterns.as_ref().map_or(false, |p| !p.is_empty());
+
+    if multi_page && plan.output.dedupe_keys.is_none() {
+        let suggestion = match plan.rules.fields.iter().find(|f| matches!(f.extraction, ExtractionMethod::Href)) {
+            Some(field) => format!("Set output.dedupe_keys to [\"{}\"]", field.name),
+            None => "Set output.dedupe_keys to a field that identifies an item".to_string(),
+        };
+        warnings.push(LintWarning::new(
+            "output.dedupe_keys",
+            LintRule::MissingDedupeKeys,
+            "Plan visits several pages but has no dedupe keys; repeated items will be kept",
+            suggestion,
+        ));
+    }
+
+    if plan.output.skip_seen && plan.output.dedupe_keys.is_none() && plan.output.hash_fields.is_none() {
+        warnings.push(LintWarning::new(
+            "output.skip_seen",
+            LintRule::MissingDedupeKeys,
+            "Seen items are recognized by all their fields, so an item whose price or rating changes counts as new",
+            "Set output.dedupe_keys to a field that identifies an item, e.g. its URL",
+        ));
+    }
+}
+
+fn lint_delays(plan: &ScrapePlan, warnings: &mut Vec<LintWarning>) {
+    let delays = &plan.anti_blocking.randomized_delays;
+
+    if delays.min_ms < LOW_DELAY_MS {
+        warnings.push(LintWarning::new(
+            "anti_blocking.randomized_delays.min_ms",
+            LintRule::LowDelay,
+            format!("Minimum delay of {}ms is likely to trigger rate limiting", delays.min_ms),
+            format!("Use at least {}ms, or pick the Standard politeness preset", LOW_DELAY_MS),
+        ));
+    }
+}
+
+fn lint_url_patterns(plan: &ScrapePlan, warnings: &mut Vec<LintWarning>) {
+    let patterns = match &plan.target.url_patterns {
+        Some(patterns) => patterns,
+        None => return,
+    };
+
+    for (i, pattern) in patterns.iter().enumerate() {
+        let path = format!("target.url_patterns[{}]", i);
+
+        match Url::parse(&pattern.replace("{page}", "1")) {
+            Ok(url) => {
+                let host = url.host_str().unwrap_or_default();
+                let domain = &plan.target.domain;
+                // Subdomains are in, look-alikes such as `notexample.com` are not
+                if host != domain && !host.ends_with(&format!(".{}", domain)) {
+                    warnings.push(LintWarning::new(
+                        path,
+                        LintRule::UnreachableUrlPattern,
+                        format!("Pattern host '{}' is outside the target domain '{}'", host, plan.target.domain),
+                        format!("Point the pattern at {} or change target.domain", plan.target.domain),
+                    ));
+                }
+            }
+            Err(_) => {
+                warnings.push(LintWarning::new(
+                    path,
+                    LintRule::UnreachableUrlPattern,
+                    format!("Pattern '{}' does not produce a valid URL, so it is skipped", pattern),
+                    "Use an absolute URL with an optional {page} placeholder",
+                ));
+            }
+        }
+    }
+}
+
+fn lint_pagination(plan: &ScrapePlan, warnings: &mut Vec<LintWarning>) {
+    let pagination = match &plan.rules.pagination {
+        Some(pagination) => pagination,
+        None => return,
+    };
+
+    if let PaginationMethod::UrlPattern { pattern, .. } = &pagination.method {
+        if !pattern.contains("{page}") {
+            warnings.push(LintWarning::new(
+                "rules.pagination.method.pattern",
+                LintRule::UnreachableUrlPattern,
+                "Pagination pattern has no {page} placeholder, so every page is the same URL",
+                format!("Add {{page}} where the page number goes, e.g. \"{}?page={{page}}\"", pattern),
+            ));
+        }
+    }
+
+    let bounded = pagination.max_pages.is_some()
+        || plan.target.max_pages.is_some()
+        || matches!(pagination.method, PaginationMethod::UrlPattern { .. });
+    if !bounded {
+        warnings.push(LintWarning::new(
+            "rules.pagination.max_pages",
+            LintRule::UnboundedPagination,
+            "Pagination has no page limit and will follow links until they run out",
+            "Set rules.pagination.max_pages",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+
+    #[test]
+    fn test_lint_flags_generic_item_selector_and_low_delay() {
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.rules.item_selector = "div".to_string();
+        plan.anti_blocking.randomized_delays.min_ms = 100;
+
+        let warnings = DSLValidator::new().lint(&plan);
+
+        assert!(warnings.iter().any(|w| w.rule == LintRule::GenericSelector && w.path == "rules.item_selector"));
+        assert!(warnings.iter().any(|w| w.rule == LintRule::LowDelay));
+    }
+
+    #[test]
+    fn test_lint_flags_missing_dedupe_and_foreign_pattern() {
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.output.dedupe_keys = None;
+        let domain = plan.target.domain.clone();
+        plan.target.url_patterns = Some(vec![
+            "https://other.example.org/list?page={page}".to_string(),
+            format!("https://evil{}/list?page={{page}}", domain),
+            format!("https://shop.{}/list?page={{page}}", domain),
+        ]);
+
+        let warnings = DSLValidator::new().lint(&plan);
+
+        assert!(warnings.iter().any(|w| w.rule == LintRule::MissingDedupeKeys));
+        let unreachable: Vec<&str> = warnings.iter()
+            .filter(|w| w.rule == LintRule::UnreachableUrlPattern)
+            .map(|w| w.path.as_str())
+            .collect();
+        assert_eq!(unreachable, vec!["target.url_patterns[0]", "target.url_patterns[1]"]);
+    }
+}
@@ -0,0 +1,206 @@
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use tracing::info;
+
+use crate::dsl::ScrapePlan;
+
+/// DSL version written by this build
+pub const CURRENT_DSL_VERSION: &str = "1.1";
+
+/// A single upgrade step between two DSL versions
+struct Migration {
+    to: &'static str,
+    description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Upgrade steps in order; each applies to plans older than its target version
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: "1.0",
+        description: "rename legacy target/rule/field keys",
+        apply: migrate_to_1_0,
+    },
+    Migration {
+        to: "1.1",
+        description: "fill in anti_blocking/output sections and their new settings",
+        apply: migrate_to_1_1,
+    },
+];
+
+/// Parse a YAML plan of any supported version, upgrading it to the current version
+pub fn from_yaml(yaml: &str) -> Result<ScrapePlan> {
+    let value: Value = serde_yaml::from_str(yaml)?;
+    from_value(value)
+}
+
+/// Parse a JSON plan of any supported version, upgrading it to the current version
+pub fn from_json(json: &str) -> Result<ScrapePlan> {
+    let value: Value = serde_json::from_str(json)?;
+    from_value(value)
+}
+
+/// Parse a TOML plan of any supported version, upgrading it to the current version
+pub fn from_toml(toml: &str) -> Result<ScrapePlan> {
+    let value: Value = toml::from_str(toml)?;
+    from_value(value)
+}
+
+/// Upgrade an untyped plan and deserialize it
+pub fn from_value(mut value: Value) -> Result<ScrapePlan> {
+    let applied = migrate_value(&mut value)?;
+    if !applied.is_empty() {
+        info!("Migrated plan to DSL {}: {}", CURRENT_DSL_VERSION, applied.join("; "));
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Upgrade an untyped plan in place, returning the steps that were applied
+pub fn migrate_value(value: &mut Value) -> Result<Vec<String>> {
+    let plan = value.as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("Plan must be a mapping at the top level"))?;
+
+    let version_str = match plan.get("version") {
+        Some(Value::String(v)) => v.clone(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => "0.0".to_string(),
+    };
+    let version = parse_version(&version_str)
+        .ok_or_else(|| anyhow::anyhow!("Invalid plan version: {}", version_str))?;
+    let current = parse_version(CURRENT_DSL_VERSION).unwrap_or_default();
+
+    if version > current {
+        return Err(anyhow::anyhow!(
+            "Plan version {} is newer than the supported version {}",
+            version_str, CURRENT_DSL_VERSION
+        ));
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS {
+        let target = parse_version(migration.to).unwrap_or_default();
+        if version < target {
+            (migration.apply)(plan);
+            applied.push(format!("{} -> {}: {}", version_str, migration.to, migration.description));
+        }
+    }
+
+    plan.insert("version".to_string(), json!(CURRENT_DSL_VERSION));
+    Ok(applied)
+}
+
+/// Check whether a plan version is older than the current version
+pub fn needs_migration(version: &str) -> bool {
+    match (parse_version(version), parse_version(CURRENT_DSL_VERSION)) {
+        (Some(v), Some(current)) => v < current,
+        _ => false,
+    }
+}
+
+/// Parse "major.minor" (extra components and suffixes like "-beta" are ignored)
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().trim_start_matches('v').split(['.', '-']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+/// Move `from` to `to` inside an object unless `to` is already set
+fn rename_key(object: &mut Map<String, Value>, from: &str, to: &str) {
+    if !object.contains_key(to) {
+        if let Some(value) = object.remove(from) {
+            object.insert(to.to_string(), value);
+        }
+    }
+}
+
+/// Pre-1.0 drafts used singular and shorter key names
+fn migrate_to_1_0(plan: &mut Map<String, Value>) {
+    if let Some(Value::Object(target)) = plan.get_mut("target") {
+        // `start_url: "..."` became `start_urls: ["..."]`
+        if let Some(url) = target.remove("start_url") {
+            target.entry("start_urls").or_insert_with(|| json!([url]));
+        }
+        rename_key(target, "patterns", "url_patterns");
+    }
+
+    if let Some(Value::Object(rules)) = plan.get_mut("rules") {
+        rename_key(rules, "item", "item_selector");
+        rename_key(rules, "items", "item_selector");
+
+        if let Some(Value::Array(fields)) = rules.get_mut("fields") {
+            for field in fields.iter_mut().filter_map(Value::as_object_mut) {
+                rename_key(field, "type", "selector_type");
+                rename_key(field, "extract", "extraction");
+                rename_key(field, "transforms", "transform");
+                field.entry("selector_type").or_insert_with(|| json!("css"));
+                field.entry("extraction").or_insert_with(|| json!("text"));
+                field.entry("required").or_insert(json!(false));
+            }
+        }
+    }
+
+    rename_key(plan, "antiblocking", "anti_blocking");
+}
+
+/// 1.1 added politeness presets and duplicate policies, and made sections explicit
+fn migrate_to_1_1(plan: &mut Map<String, Value>) {
+    let mut defaults = serde_json::to_value(ScrapePlan::default()).unwrap_or(Value::Null);
+
+    // Dedupe keys are opt-in and the default refers to a field the plan may not have
+    if let Some(Value::Object(output)) = defaults.get_mut("output") {
+        output.insert("dedupe_keys".to_string(), Value::Null);
+    }
+
+    for section in ["anti_blocking", "output"] {
+        let default_section = defaults.get(section).cloned().unwrap_or(Value::Null);
+        match plan.get_mut(section) {
+            Some(Value::Object(existing)) => {
+                // Keep user settings, add any keys the section is missing
+                if let Value::Object(default_section) = default_section {
+                    for (key, value) in default_section {
+                        existing.entry(key).or_insert(value);
+                    }
+                }
+            }
+            _ => {
+                plan.insert(section.to_string(), default_section);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrates_legacy_plan() {
+        let yaml = r#"
+version: "0.9"
+target:
+  domain: example.com
+  start_url: https://example.com/list
+rules:
+  items: ".product"
+  fields:
+    - name: title
+      selector: ".title"
+      type: css
+"#;
+        let plan = from_yaml(yaml).unwrap();
+
+        assert_eq!(plan.version, CURRENT_DSL_VERSION);
+        assert_eq!(plan.target.start_urls, vec!["https://example.com/list"]);
+        assert_eq!(plan.rules.item_selector, ".product");
+        assert!(plan.anti_blocking.respect_robots_txt);
+        assert!(!plan.output.format.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_future_version() {
+        let mut value = serde_json::to_value(ScrapePlan::default()).unwrap();
+        value["version"] = json!("9.0");
+        assert!(migrate_value(&mut value).is_err());
+    }
+}
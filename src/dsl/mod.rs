@@ -1,14 +1,35 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use tracing::error;
 use url::Url;
 
 pub mod validator;
+pub mod lint;
+pub mod migrate;
 pub mod parser;
 pub mod generator;
+pub mod politeness;
+pub mod client_profile;
+pub mod schema;
+pub mod template;
+pub mod bundle;
+pub mod explain;
+pub mod page_summary;
+pub mod picker;
 
-pub use validator::DSLValidator;
+pub use validator::{DSLValidator, ValidationCode, ValidationErrors, ValidationIssue};
+pub use lint::{LintRule, LintWarning};
+pub use migrate::CURRENT_DSL_VERSION;
+pub use bundle::{PlanBundle, CredentialPlaceholder, BUNDLE_EXTENSION};
+pub use parser::{DSLParser, PlanFormat};
+pub use explain::PlanExplanation;
+pub use page_summary::PageSummary;
+pub use picker::{DomNode, InspectedPage, PickedSelector};
+pub use generator::{DSLGenerator, FieldSuggestion, ItemSuggestion, SelectorSuggestions};
+pub use politeness::{PolitenessPreset, PolitenessLimits};
+pub use client_profile::ClientProfile;
 
 /// Scrape-Plan DSL structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +39,9 @@ pub struct ScrapePlan {
     pub rules: Rules,
     pub anti_blocking: AntiBlocking,
     pub output: Output,
+    /// Run the job again when a run fails or some of its pages do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
@@ -25,9 +49,30 @@ pub struct ScrapePlan {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Target {
     pub domain: String,
+    /// May be empty when `depends_on` supplies the URLs
     pub start_urls: Vec<String>,
     pub url_patterns: Option<Vec<String>>,
     pub max_pages: Option<usize>,
+    /// Plan to run first, whose results supply the start URLs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<PlanDependency>,
+}
+
+/// An upstream plan feeding a plan its start URLs
+///
+/// The upstream plan runs as a job of its own first; the URLs in `url_field`
+/// of its results replace `start_urls`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanDependency {
+    /// Name of the saved plan or template to run first
+    pub plan: String,
+    /// Field of the upstream results holding the URLs
+    #[serde(default = "default_url_field")]
+    pub url_field: String,
+}
+
+fn default_url_field() -> String {
+    "url".to_string()
 }
 
 /// Scraping rules
@@ -36,7 +81,96 @@ pub struct Rules {
     pub pagination: Option<Pagination>,
     pub item_selector: String,
     pub fields: Vec<Field>,
-    pub filters: Option<Vec<Filter>>,
+    pub filters: Option<Vec<FilterExpr>>,
+    /// What to do with items that are missing a required field
+    #[serde(default)]
+    pub missing_required: MissingRequiredPolicy,
+    /// Script run on every item that passed the filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_process: Option<PostProcess>,
+}
+
+/// Handling of items where a required field could not be extracted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(tag = "type")]
+pub enum MissingRequiredPolicy {
+    /// Drop the item
+    #[default]
+    #[serde(rename = "drop")]
+    Drop,
+    /// Keep the item with the missing fields set to null
+    #[serde(rename = "keep_null")]
+    KeepNull,
+    /// Drop the item, and fail the job when more than `max_percent` of items are missing fields
+    #[serde(rename = "fail_above")]
+    FailAbove { max_percent: f64 },
+}
+
+/// Per-item script for changes the transforms can't express
+///
+/// Scripts run sandboxed, without access to files or the network, and are
+/// stopped when they go over their limits. Needs the `scripting` feature.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "language")]
+pub enum PostProcess {
+    /// Rhai script with the item in `item`
+    ///
+    /// A map the script returns replaces the item and `false` drops it; any
+    /// other value keeps `item` as the script left it.
+    #[serde(rename = "rhai")]
+    Rhai {
+        script: String,
+        #[serde(default)]
+        limits: ScriptLimits,
+    },
+    /// WebAssembly module exporting `post_process`, called like a plugin
+    /// transform (see [`crate::plugins::wasm`]) with the item as its input
+    ///
+    /// It returns the new item, or `false` to drop it.
+    #[serde(rename = "wasm")]
+    Wasm {
+        /// Module file, named relative to the plugins directory
+        module: PathBuf,
+        #[serde(default)]
+        limits: ScriptLimits,
+    },
+}
+
+impl PostProcess {
+    pub fn limits(&self) -> &ScriptLimits {
+        match self {
+            PostProcess::Rhai { limits, .. } | PostProcess::Wasm { limits, .. } => limits,
+        }
+    }
+}
+
+/// What a post-process script may use for one item
+///
+/// Capped by the `plugins` settings of the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScriptLimits {
+    /// Rhai operations or WebAssembly fuel
+    #[serde(default = "default_max_operations")]
+    pub max_operations: u64,
+    #[serde(default = "default_max_memory_mb")]
+    pub max_memory_mb: u32,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: default_max_operations(),
+            max_memory_mb: default_max_memory_mb(),
+        }
+    }
+}
+
+fn default_max_operations() -> u64 {
+    1_000_000
+}
+
+fn default_max_memory_mb() -> u32 {
+    16
 }
 
 /// Pagination configuration
@@ -80,7 +214,7 @@ pub enum SelectorType {
     XPath,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ExtractionMethod {
     #[serde(rename = "text")]
     Text,
@@ -114,6 +248,13 @@ pub enum Transform {
     RemoveHtml,
     #[serde(rename = "extract_domain")]
     ExtractDomain,
+    /// A transform registered by a plugin
+    #[serde(rename = "plugin")]
+    Plugin {
+        name: String,
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        args: serde_json::Value,
+    },
 }
 
 /// Content filters
@@ -123,6 +264,53 @@ pub struct Filter {
     pub condition: FilterCondition,
 }
 
+/// Filter expression: a single condition or a nested boolean group
+///
+/// Top-level entries in `rules.filters` are combined with AND, so plain
+/// condition lists keep their original meaning.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FilterExpr {
+    /// Passes when every nested expression passes
+    All { all: Vec<FilterExpr> },
+    /// Passes when at least one nested expression passes
+    Any { any: Vec<FilterExpr> },
+    /// Passes when the nested expression fails
+    Not { not: Box<FilterExpr> },
+    /// A single field condition
+    Condition(Filter),
+}
+
+impl FilterExpr {
+    /// Collect the names of all fields referenced by this expression
+    pub fn referenced_fields(&self) -> Vec<&str> {
+        match self {
+            FilterExpr::All { all: exprs } | FilterExpr::Any { any: exprs } => {
+                exprs.iter().flat_map(|e| e.referenced_fields()).collect()
+            }
+            FilterExpr::Not { not } => not.referenced_fields(),
+            FilterExpr::Condition(filter) => vec![filter.field.as_str()],
+        }
+    }
+    
+    /// Get the nesting depth of this expression (a single condition has depth 1)
+    pub fn depth(&self) -> usize {
+        match self {
+            FilterExpr::All { all: exprs } | FilterExpr::Any { any: exprs } => {
+                1 + exprs.iter().map(|e| e.depth()).max().unwrap_or(0)
+            }
+            FilterExpr::Not { not } => 1 + not.depth(),
+            FilterExpr::Condition(_) => 1,
+        }
+    }
+}
+
+impl From<Filter> for FilterExpr {
+    fn from(filter: Filter) -> Self {
+        FilterExpr::Condition(filter)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum FilterCondition {
@@ -140,8 +328,21 @@ pub enum FilterCondition {
     LengthMin { min: usize },
     #[serde(rename = "length_max")]
     LengthMax { max: usize },
+    /// Numeric value (or the number in a text like "$12.50") of at least `min`
+    #[serde(rename = "number_min")]
+    NumberMin { min: f64 },
+    /// Numeric value (or the number in a text) of at most `max`
+    #[serde(rename = "number_max")]
+    NumberMax { max: f64 },
     #[serde(rename = "not_empty")]
     NotEmpty,
+    /// A condition registered by a plugin
+    #[serde(rename = "plugin")]
+    Plugin {
+        name: String,
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        args: serde_json::Value,
+    },
 }
 
 /// Anti-blocking configuration
@@ -152,6 +353,102 @@ pub struct AntiBlocking {
     pub respect_robots_txt: bool,
     pub proxy: Option<ProxyConfig>,
     pub headers: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub politeness: PolitenessPreset,
+    /// Seed for delay and user agent choices; a fresh seed is drawn per run when unset
+    #[serde(default)]
+    pub random_seed: Option<u64>,
+    /// How redirects are followed
+    #[serde(default)]
+    pub redirects: RedirectPolicy,
+    /// Client to present as; its user agent replaces the rotation and its headers go under `headers`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_profile: Option<ClientProfile>,
+    /// User agent sent on every request, over rotation and the client profile's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// How robots meta tags and `X-Robots-Tag` headers are honored
+    #[serde(default)]
+    pub robots_meta: RobotsMetaPolicy,
+}
+
+impl AntiBlocking {
+    /// User agent every request uses, when the plan pins one
+    pub fn fixed_user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+            .or_else(|| self.client_profile.map(|profile| profile.user_agent()))
+    }
+    
+    /// Headers sent on every request: the client profile's, then the plan's own
+    ///
+    /// A plan header replaces the profile header of the same name, whatever its case.
+    pub fn request_headers(&self) -> Option<HashMap<String, String>> {
+        let Some(profile) = self.client_profile else {
+            return self.headers.clone();
+        };
+        let custom = self.headers.iter().flatten();
+        let mut headers: HashMap<String, String> = profile.headers().into_iter()
+            .filter(|(name, _)| !custom.clone().any(|(custom_name, _)| custom_name.eq_ignore_ascii_case(name)))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        headers.extend(custom.map(|(name, value)| (name.clone(), value.clone())));
+        Some(headers)
+    }
+}
+
+/// How far and where a plan follows redirects
+///
+/// Redirect targets are always checked against the blocked domains list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RedirectPolicy {
+    /// Redirects followed per request before it fails
+    #[serde(default = "default_max_redirects")]
+    pub max_redirects: usize,
+    /// Follow redirects to another site than the one requested
+    #[serde(default = "default_allow_cross_domain")]
+    pub allow_cross_domain: bool,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self {
+            max_redirects: default_max_redirects(),
+            allow_cross_domain: default_allow_cross_domain(),
+        }
+    }
+}
+
+fn default_max_redirects() -> usize {
+    10
+}
+
+fn default_allow_cross_domain() -> bool {
+    true
+}
+
+/// How a plan treats pages that carry robots meta directives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobotsMetaPolicy {
+    /// Keep links from `nofollow` pages and `rel="nofollow"` links out of link following,
+    /// such as a downstream plan's start URLs
+    #[serde(default = "default_respect_nofollow")]
+    pub respect_nofollow: bool,
+    /// Extract nothing from pages marked `noindex`
+    #[serde(default)]
+    pub skip_noindex: bool,
+}
+
+impl Default for RobotsMetaPolicy {
+    fn default() -> Self {
+        Self {
+            respect_nofollow: default_respect_nofollow(),
+            skip_noindex: false,
+        }
+    }
+}
+
+fn default_respect_nofollow() -> bool {
+    true
 }
 
 /// Delay configuration
@@ -188,6 +485,64 @@ pub enum ProxyRotation {
     Random,
 }
 
+/// Automatic retries of a job whose run failed or left pages unfetched
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Runs in total, the first one included
+    #[serde(default = "default_retry_attempts")]
+    pub max_attempts: u32,
+    /// Wait before the first retry
+    #[serde(default = "default_retry_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Factor the wait grows by with each further retry
+    #[serde(default = "default_retry_multiplier")]
+    pub backoff_multiplier: f64,
+    /// Longest wait between two runs
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// Fetch only the URLs that failed, keeping the rows already stored,
+    /// instead of running the whole plan again
+    #[serde(default)]
+    pub only_failed_urls: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_attempts(),
+            backoff_ms: default_retry_backoff_ms(),
+            backoff_multiplier: default_retry_multiplier(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+            only_failed_urls: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before the given retry, counting the first retry as 1
+    pub fn backoff(&self, retry: u32) -> std::time::Duration {
+        let factor = self.backoff_multiplier.max(1.0).powi(retry.saturating_sub(1) as i32);
+        let ms = (self.backoff_ms as f64 * factor).min(self.max_backoff_ms as f64);
+        std::time::Duration::from_millis(ms as u64)
+    }
+}
+
+fn default_retry_attempts() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    30_000
+}
+
+fn default_retry_multiplier() -> f64 {
+    2.0
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    600_000
+}
+
 /// Output configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Output {
@@ -196,6 +551,61 @@ pub struct Output {
     pub dedupe_keys: Option<Vec<String>>,
     pub sort_by: Option<String>,
     pub sort_order: Option<SortOrder>,
+    /// What to do with rows whose dedupe keys were already seen
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    /// Fields that make up a row's content hash; all non-metadata fields when unset
+    #[serde(default)]
+    pub hash_fields: Option<Vec<String>>,
+    /// Name for exports of this plan's results, e.g. `{plan_name}_{date}_{run_number}.{ext}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename_template: Option<String>,
+    /// Leave out items already stored by earlier runs of the same plan
+    ///
+    /// Items are recognized by their dedupe keys, or by their content hash
+    /// when the plan has none.
+    #[serde(default)]
+    pub skip_seen: bool,
+    /// Saved export profiles run after every completed job
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub export_profiles: Vec<String>,
+    /// Changes to the application's notification settings for this plan's jobs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<NotifyOptions>,
+}
+
+/// Per-plan notification settings; unset fields keep the application's
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NotifyOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_completed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub desktop: Option<bool>,
+    /// Whether to send the email configured for the application
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<bool>,
+    /// Email these addresses instead of the configured recipients
+    ///
+    /// Only addresses the configuration lists as recipients or allowed recipients are emailed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub email_to: Vec<String>,
+}
+
+/// Handling of rows that share dedupe key values with an earlier row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DuplicatePolicy {
+    /// Drop duplicates as soon as they are extracted
+    #[serde(rename = "skip")]
+    Skip,
+    /// Keep the first occurrence, filling its empty fields from later duplicates
+    #[default]
+    #[serde(rename = "keep_first")]
+    KeepFirst,
+    /// Overwrite the earlier row with the latest occurrence
+    #[serde(rename = "keep_last")]
+    KeepLast,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -208,6 +618,8 @@ pub enum OutputFormat {
     XLSX,
     #[serde(rename = "parquet")]
     Parquet,
+    #[serde(rename = "ndjson")]
+    Ndjson,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -221,12 +633,13 @@ pub enum SortOrder {
 impl Default for ScrapePlan {
     fn default() -> Self {
         Self {
-            version: "1.0".to_string(),
+            version: CURRENT_DSL_VERSION.to_string(),
             target: Target {
                 domain: "example.com".to_string(),
                 start_urls: vec!["https://example.com".to_string()],
                 url_patterns: None,
                 max_pages: Some(10),
+                depends_on: None,
             },
             rules: Rules {
                 pagination: None,
@@ -250,6 +663,8 @@ impl Default for ScrapePlan {
                     },
                 ],
                 filters: None,
+                missing_required: MissingRequiredPolicy::default(),
+                post_process: None,
             },
             anti_blocking: AntiBlocking {
                 randomized_delays: DelayConfig {
@@ -261,6 +676,12 @@ impl Default for ScrapePlan {
                 respect_robots_txt: true,
                 proxy: None,
                 headers: None,
+                politeness: PolitenessPreset::Standard,
+                random_seed: None,
+                redirects: RedirectPolicy::default(),
+                client_profile: None,
+                user_agent: None,
+                robots_meta: RobotsMetaPolicy::default(),
             },
             output: Output {
                 format: vec![OutputFormat::CSV],
@@ -268,17 +689,23 @@ impl Default for ScrapePlan {
                 dedupe_keys: Some(vec!["url".to_string()]),
                 sort_by: None,
                 sort_order: None,
+                duplicate_policy: DuplicatePolicy::default(),
+                hash_fields: None,
+                filename_template: None,
+                skip_seen: false,
+                export_profiles: Vec::new(),
+                notify: None,
             },
+            retry: None,
             metadata: None,
         }
     }
 }
 
 impl ScrapePlan {
-    /// Create a new scrape plan from YAML string
+    /// Create a new scrape plan from YAML string, upgrading older DSL versions
     pub fn from_yaml(yaml: &str) -> Result<Self> {
-        let plan: ScrapePlan = serde_yaml::from_str(yaml)?;
-        Ok(plan)
+        migrate::from_yaml(yaml)
     }
     
     /// Convert scrape plan to YAML string
@@ -286,10 +713,9 @@ impl ScrapePlan {
         Ok(serde_yaml::to_string(self)?)
     }
     
-    /// Create a new scrape plan from JSON string
+    /// Create a new scrape plan from JSON string, upgrading older DSL versions
     pub fn from_json(json: &str) -> Result<Self> {
-        let plan: ScrapePlan = serde_json::from_str(json)?;
-        Ok(plan)
+        migrate::from_json(json)
     }
     
     /// Convert scrape plan to JSON string
@@ -297,6 +723,20 @@ impl ScrapePlan {
         Ok(serde_json::to_string_pretty(self)?)
     }
     
+    /// Create a new scrape plan from TOML string, upgrading older DSL versions
+    pub fn from_toml(toml: &str) -> Result<Self> {
+        migrate::from_toml(toml)
+    }
+    
+    /// Convert scrape plan to TOML string
+    ///
+    /// TOML has no null, so unset optional values and null metadata entries are omitted.
+    pub fn to_toml(&self) -> Result<String> {
+        let mut value = serde_json::to_value(self)?;
+        strip_nulls(&mut value);
+        Ok(toml::to_string_pretty(&value)?)
+    }
+    
     /// Validate the scrape plan
     pub fn validate(&self) -> Result<()> {
         let validator = DSLValidator::new();
@@ -362,6 +802,11 @@ impl ScrapePlan {
         &self.anti_blocking.randomized_delays
     }
     
+    /// Get the politeness preset selected for this plan
+    pub fn get_politeness(&self) -> PolitenessPreset {
+        self.anti_blocking.politeness
+    }
+    
     /// Get required fields
     pub fn get_required_fields(&self) -> Vec<&Field> {
         self.rules.fields.iter().filter(|f| f.required).collect()
@@ -394,6 +839,34 @@ impl ScrapePlan {
     pub fn get_metadata(&self, key: &str) -> Option<&serde_json::Value> {
         self.metadata.as_ref()?.get(key)
     }
+    
+    /// Key of the index of items seen by runs of this plan
+    ///
+    /// Derived from the target, so edits to fields or anti-blocking settings
+    /// keep the index while pointing the plan at other pages starts a new one.
+    pub fn seen_index_key(&self) -> String {
+        use sha2::{Digest, Sha256};
+        
+        let mut hasher = Sha256::new();
+        hasher.update(self.target.domain.to_lowercase().as_bytes());
+        for url in self.target.start_urls.iter().chain(self.target.url_patterns.iter().flatten()) {
+            hasher.update([0]);
+            hasher.update(url.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Remove null object entries recursively
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
 }
 
 /// DSL parsing errors
@@ -428,12 +901,13 @@ impl DSLExamples {
     /// E-commerce product scraping example
     pub fn ecommerce_products() -> ScrapePlan {
         ScrapePlan {
-            version: "1.0".to_string(),
+            version: CURRENT_DSL_VERSION.to_string(),
             target: Target {
                 domain: "shop.example.com".to_string(),
                 start_urls: vec!["https://shop.example.com/products".to_string()],
                 url_patterns: Some(vec!["https://shop.example.com/products?page={page}".to_string()]),
                 max_pages: Some(50),
+                depends_on: None,
             },
             rules: Rules {
                 pagination: Some(Pagination {
@@ -490,12 +964,14 @@ impl DSLExamples {
                     Filter {
                         field: "price".to_string(),
                         condition: FilterCondition::NotEmpty,
-                    },
+                    }.into(),
                     Filter {
                         field: "title".to_string(),
                         condition: FilterCondition::LengthMin { min: 3 },
-                    },
+                    }.into(),
                 ]),
+                missing_required: MissingRequiredPolicy::default(),
+                post_process: None,
             },
             anti_blocking: AntiBlocking {
                 randomized_delays: DelayConfig {
@@ -511,6 +987,12 @@ impl DSLExamples {
                     headers.insert("Accept-Language".to_string(), "en-US,en;q=0.9".to_string());
                     headers
                 }),
+                politeness: PolitenessPreset::Standard,
+                random_seed: None,
+                redirects: RedirectPolicy::default(),
+                client_profile: None,
+                user_agent: None,
+                robots_meta: RobotsMetaPolicy::default(),
             },
             output: Output {
                 format: vec![OutputFormat::CSV, OutputFormat::JSON],
@@ -518,7 +1000,14 @@ impl DSLExamples {
                 dedupe_keys: Some(vec!["product_url".to_string()]),
                 sort_by: Some("price".to_string()),
                 sort_order: Some(SortOrder::Ascending),
+                duplicate_policy: DuplicatePolicy::default(),
+                hash_fields: None,
+                filename_template: None,
+                skip_seen: false,
+                export_profiles: Vec::new(),
+                notify: None,
             },
+            retry: None,
             metadata: Some({
                 let mut metadata = HashMap::new();
                 metadata.insert("description".to_string(), serde_json::Value::String("E-commerce product scraping".to_string()));
@@ -531,12 +1020,13 @@ impl DSLExamples {
     /// News article scraping example
     pub fn news_articles() -> ScrapePlan {
         ScrapePlan {
-            version: "1.0".to_string(),
+            version: CURRENT_DSL_VERSION.to_string(),
             target: Target {
                 domain: "news.example.com".to_string(),
                 start_urls: vec!["https://news.example.com/latest".to_string()],
                 url_patterns: None,
                 max_pages: Some(20),
+                depends_on: None,
             },
             rules: Rules {
                 pagination: Some(Pagination {
@@ -594,8 +1084,10 @@ impl DSLExamples {
                     Filter {
                         field: "headline".to_string(),
                         condition: FilterCondition::NotEmpty,
-                    },
+                    }.into(),
                 ]),
+                missing_required: MissingRequiredPolicy::default(),
+                post_process: None,
             },
             anti_blocking: AntiBlocking {
                 randomized_delays: DelayConfig {
@@ -607,6 +1099,12 @@ impl DSLExamples {
                 respect_robots_txt: true,
                 proxy: None,
                 headers: None,
+                politeness: PolitenessPreset::Standard,
+                random_seed: None,
+                redirects: RedirectPolicy::default(),
+                client_profile: None,
+                user_agent: None,
+                robots_meta: RobotsMetaPolicy::default(),
             },
             output: Output {
                 format: vec![OutputFormat::JSON, OutputFormat::XLSX],
@@ -614,7 +1112,14 @@ impl DSLExamples {
                 dedupe_keys: Some(vec!["article_url".to_string()]),
                 sort_by: Some("published_date".to_string()),
                 sort_order: Some(SortOrder::Descending),
+                duplicate_policy: DuplicatePolicy::default(),
+                hash_fields: None,
+                filename_template: None,
+                skip_seen: false,
+                export_profiles: Vec::new(),
+                notify: None,
             },
+            retry: None,
             metadata: Some({
                 let mut metadata = HashMap::new();
                 metadata.insert("description".to_string(), serde_json::Value::String("News article scraping".to_string()));
@@ -624,3 +1129,41 @@ impl DSLExamples {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_nested_filter_groups_parse() {
+        let yaml = r#"
+- any:
+    - field: title
+      condition: { type: contains, value: "laptop" }
+    - field: title
+      condition: { type: contains, value: "notebook" }
+- not:
+    field: price
+    condition: { type: equals, value: "0" }
+- field: price
+  condition: { type: not_empty }
+"#;
+        let filters: Vec<FilterExpr> = serde_yaml::from_str(yaml).unwrap();
+        
+        assert_eq!(filters.len(), 3);
+        assert!(matches!(filters[0], FilterExpr::Any { .. }));
+        assert!(matches!(filters[1], FilterExpr::Not { .. }));
+        assert!(matches!(filters[2], FilterExpr::Condition(_)));
+        assert_eq!(filters[0].depth(), 2);
+        assert_eq!(filters[0].referenced_fields(), vec!["title", "title"]);
+    }
+    
+    #[test]
+    fn test_retry_backoff_grows_to_limit() {
+        let policy: RetryPolicy = serde_yaml::from_str("backoff_ms: 1000\nmax_backoff_ms: 5000").unwrap();
+        
+        assert_eq!(policy.max_attempts, 3);
+        let waits: Vec<u128> = (1..=4).map(|retry| policy.backoff(retry).as_millis()).collect();
+        assert_eq!(waits, vec![1000, 2000, 4000, 5000]);
+    }
+}
@@ -0,0 +1,112 @@
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::generator::{DSLGenerator, FieldSuggestion, ItemSuggestion, SelectorSuggestions};
+
+/// `tag.class` combinations kept in a summary
+const MAX_TAG_CLASSES: usize = 25;
+
+/// Item candidates kept in a summary
+const MAX_ITEM_CANDIDATES: usize = 5;
+
+/// Elements that never hold scrapeable content
+const IGNORED_TAGS: &[&str] = &["html", "head", "title", "body", "script", "style", "noscript", "meta", "link", "svg", "path", "br"];
+
+/// Compact description of a page's DOM for grounding plan generation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PageSummary {
+    pub url: String,
+    pub title: Option<String>,
+    /// Most frequent `tag.class` combinations with their counts
+    pub tag_classes: Vec<(String, usize)>,
+    /// Repeated containers likely to hold one item each, best first
+    pub item_candidates: Vec<ItemSuggestion>,
+    /// Fields found inside the best candidate
+    pub fields: Vec<FieldSuggestion>,
+}
+
+impl PageSummary {
+    pub fn from_html(url: &str, html: &str) -> Self {
+        let document = Html::parse_document(html);
+
+        let title = Selector::parse("title").ok()
+            .and_then(|selector| document.select(&selector).next())
+            .map(|title| title.text().collect::<String>().trim().to_string())
+            .filter(|title| !title.is_empty());
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for element in document.root_element().descendants().filter_map(ElementRef::wrap) {
+            if let Some(key) = tag_class_key(&element) {
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+        let mut tag_classes: Vec<(String, usize)> = counts.into_iter().collect();
+        tag_classes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        tag_classes.truncate(MAX_TAG_CLASSES);
+
+        let suggestions = DSLGenerator::suggest_selectors(html);
+
+        Self {
+            url: url.to_string(),
+            title,
+            tag_classes,
+            item_candidates: suggestions.items.into_iter().take(MAX_ITEM_CANDIDATES).collect(),
+            fields: suggestions.fields,
+        }
+    }
+
+    /// The candidates as selector suggestions for the page
+    pub fn suggestions(&self) -> SelectorSuggestions {
+        SelectorSuggestions {
+            url: Some(self.url.clone()),
+            items: self.item_candidates.clone(),
+            fields: self.fields.clone(),
+        }
+    }
+
+    /// Summary as prompt text
+    pub fn to_prompt(&self) -> String {
+        let mut text = format!("Structure of the target page {}", self.url);
+        if let Some(title) = &self.title {
+            text.push_str(&format!(" (title: \"{}\")", title));
+        }
+        text.push_str(":\n\nMost common elements (tag.class: count):\n");
+        for (key, count) in &self.tag_classes {
+            text.push_str(&format!("- {}: {}\n", key, count));
+        }
+
+        if !self.item_candidates.is_empty() {
+            text.push_str("\nLikely item containers:\n");
+            for item in &self.item_candidates {
+                text.push_str(&format!("- {} ({} matches, confidence {:.2})\n", item.selector, item.matches, item.confidence));
+            }
+        }
+
+        if !self.fields.is_empty() {
+            text.push_str("\nFields inside the best container:\n");
+            for field in &self.fields {
+                let sample = field.samples.first().map(|s| format!(", e.g. \"{}\"", s)).unwrap_or_default();
+                text.push_str(&format!("- {}: {} ({:?}{})\n", field.name, field.selector, field.extraction, sample));
+            }
+        }
+
+        text.push_str("\nUse these real classes for item_selector and field selectors instead of generic guesses.");
+        text
+    }
+}
+
+/// `tag` or `tag.class1.class2` for an element worth counting
+fn tag_class_key(element: &ElementRef) -> Option<String> {
+    let tag = element.value().name();
+    if IGNORED_TAGS.contains(&tag) {
+        return None;
+    }
+
+    let mut classes: Vec<&str> = element.value().classes().collect();
+    if classes.is_empty() {
+        return Some(tag.to_string());
+    }
+    classes.sort_unstable();
+    Some(format!("{}.{}", tag, classes.join(".")))
+}
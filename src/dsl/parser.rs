@@ -1,18 +1,103 @@
 use anyhow::Result;
+use std::path::Path;
 use crate::dsl::ScrapePlan;
 
+/// Text formats a plan can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanFormat {
+    Yaml,
+    Json,
+    Toml,
+}
+
+impl PlanFormat {
+    /// Format implied by a file extension, if it is a known one
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "yaml" | "yml" => Some(PlanFormat::Yaml),
+            "json" => Some(PlanFormat::Json),
+            "toml" => Some(PlanFormat::Toml),
+            _ => None,
+        }
+    }
+    
+    /// Guess the format from the content; YAML is the fallback
+    pub fn detect(content: &str) -> Self {
+        let trimmed = content.trim_start();
+        if trimmed.starts_with('{') {
+            return PlanFormat::Json;
+        }
+        
+        // The first meaningful line tells TOML (`key = value`, `[table]`) from YAML (`key: value`)
+        let first_line = content.lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#') && line != &"---");
+        
+        match first_line {
+            Some(line) if line.starts_with('[') && line.ends_with(']') => PlanFormat::Toml,
+            Some(line) => {
+                let key_end = line.find(|c: char| c == '=' || c == ':').unwrap_or(line.len());
+                if line[key_end..].starts_with('=') {
+                    PlanFormat::Toml
+                } else {
+                    PlanFormat::Yaml
+                }
+            }
+            None => PlanFormat::Yaml,
+        }
+    }
+}
+
+impl std::fmt::Display for PlanFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanFormat::Yaml => write!(f, "yaml"),
+            PlanFormat::Json => write!(f, "json"),
+            PlanFormat::Toml => write!(f, "toml"),
+        }
+    }
+}
+
 /// DSL parser for converting various formats
 pub struct DSLParser;
 
 impl DSLParser {
     /// Parse DSL from YAML string
     pub fn parse_yaml(yaml: &str) -> Result<ScrapePlan> {
-        Ok(serde_yaml::from_str(yaml)?)
+        ScrapePlan::from_yaml(yaml)
     }
     
     /// Parse DSL from JSON string
     pub fn parse_json(json: &str) -> Result<ScrapePlan> {
-        Ok(serde_json::from_str(json)?)
+        ScrapePlan::from_json(json)
+    }
+    
+    /// Parse DSL from TOML string
+    pub fn parse_toml(toml: &str) -> Result<ScrapePlan> {
+        ScrapePlan::from_toml(toml)
+    }
+    
+    /// Parse DSL in the given format
+    pub fn parse_as(content: &str, format: PlanFormat) -> Result<ScrapePlan> {
+        match format {
+            PlanFormat::Yaml => Self::parse_yaml(content),
+            PlanFormat::Json => Self::parse_json(content),
+            PlanFormat::Toml => Self::parse_toml(content),
+        }
+    }
+    
+    /// Parse DSL, taking the format from the file extension or else the content
+    pub fn parse(content: &str, path: Option<&Path>) -> Result<ScrapePlan> {
+        let format = path.and_then(PlanFormat::from_path)
+            .unwrap_or_else(|| PlanFormat::detect(content));
+        Self::parse_as(content, format)
+    }
+    
+    /// Read and parse a DSL file in any supported format
+    pub async fn parse_file(path: impl AsRef<Path>) -> Result<ScrapePlan> {
+        let path = path.as_ref();
+        let content = tokio::fs::read_to_string(path).await?;
+        Self::parse(&content, Some(path))
     }
     
     /// Convert DSL to YAML
@@ -24,4 +109,43 @@ impl DSLParser {
     pub fn to_json(dsl: &ScrapePlan) -> Result<String> {
         Ok(serde_json::to_string_pretty(dsl)?)
     }
+    
+    /// Convert DSL to TOML
+    pub fn to_toml(dsl: &ScrapePlan) -> Result<String> {
+        dsl.to_toml()
+    }
+    
+    /// Convert DSL to the given format
+    pub fn to_format(dsl: &ScrapePlan, format: PlanFormat) -> Result<String> {
+        match format {
+            PlanFormat::Yaml => Self::to_yaml(dsl),
+            PlanFormat::Json => Self::to_json(dsl),
+            PlanFormat::Toml => Self::to_toml(dsl),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(PlanFormat::detect("{\"version\": \"1.1\"}"), PlanFormat::Json);
+        assert_eq!(PlanFormat::detect("# plan\nversion = \"1.1\"\n"), PlanFormat::Toml);
+        assert_eq!(PlanFormat::detect("[target]\ndomain = \"a\""), PlanFormat::Toml);
+        assert_eq!(PlanFormat::detect("---\nversion: '1.1'\n"), PlanFormat::Yaml);
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let plan = DSLExamples::ecommerce_products();
+        let toml = DSLParser::to_toml(&plan).unwrap();
+
+        let parsed = DSLParser::parse(&toml, None).unwrap();
+        assert_eq!(parsed.target.start_urls, plan.target.start_urls);
+        assert_eq!(parsed.rules.fields.len(), plan.rules.fields.len());
+        assert_eq!(parsed.output.sort_by, plan.output.sort_by);
+    }
 }
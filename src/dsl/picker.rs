@@ -0,0 +1,303 @@
+use anyhow::Result;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use super::generator::{element_classes, element_signature, field_name, item_selector_for, own_text};
+use crate::dsl::{ExtractionMethod, Field, SelectorType, Transform};
+
+/// Elements left out of the inspector, with everything inside them
+const SKIPPED_TAGS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "svg", "meta", "link", "br", "hr",
+];
+
+/// Characters of text shown next to a node
+const TEXT_PREVIEW_CHARS: usize = 60;
+
+/// Classes shown in a node label
+const LABEL_CLASSES: usize = 3;
+
+/// A fetched page, flattened for display in the selector picker
+///
+/// Nodes are the page's elements in document order, so a node's children
+/// follow it directly and end at `end`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InspectedPage {
+    pub url: String,
+    pub html: String,
+    pub nodes: Vec<DomNode>,
+}
+
+/// One element of an inspected page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DomNode {
+    pub tag: String,
+    /// `tag#id.class1.class2`
+    pub label: String,
+    /// Start of the element's own text
+    pub text: String,
+    pub depth: usize,
+    /// Index after the element's last descendant
+    pub end: usize,
+}
+
+/// Selector generated for a picked element
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PickedSelector {
+    /// Relative to `item_selector` when the element is inside an item
+    pub selector: String,
+    pub extraction: ExtractionMethod,
+    pub name: String,
+    /// Value extracted from the picked element
+    pub sample: String,
+    /// Item selector the selector is relative to
+    pub item_selector: Option<String>,
+    /// Items the selector finds something in, or elements it matches on the page
+    pub matches: usize,
+    /// Items on the page, when relative to an item selector
+    pub items: Option<usize>,
+    /// Selector grouping the element with its repeats, when it is not inside an item
+    pub suggested_item: Option<String>,
+}
+
+impl InspectedPage {
+    pub fn from_html(url: &str, html: &str) -> Self {
+        let document = Html::parse_document(html);
+        let nodes = flatten(&document).into_iter()
+            .map(|(element, depth, end)| DomNode {
+                tag: element.value().name().to_string(),
+                label: node_label(&element),
+                text: own_text(&element).chars().take(TEXT_PREVIEW_CHARS).collect(),
+                depth,
+                end,
+            })
+            .collect();
+
+        Self {
+            url: url.to_string(),
+            html: html.to_string(),
+            nodes,
+        }
+    }
+
+    /// Build a selector for node `index`, relative to the item it is in
+    pub fn pick(&self, index: usize, item_selector: Option<&str>) -> Result<PickedSelector> {
+        let document = Html::parse_document(&self.html);
+        let elements = flatten(&document);
+        let (element, _, _) = elements.get(index)
+            .ok_or_else(|| anyhow::anyhow!("Node {} is not on the inspected page", index))?;
+
+        let item = match item_selector.filter(|selector| !selector.trim().is_empty()) {
+            Some(selector) => Some((
+                selector.to_string(),
+                Selector::parse(selector).map_err(|e| anyhow::anyhow!("Invalid item selector {}: {:?}", selector, e))?,
+            )),
+            None => None,
+        };
+        let scope = item.as_ref().and_then(|(_, selector)| {
+            element.ancestors().filter_map(ElementRef::wrap).find(|ancestor| selector.matches(ancestor))
+        });
+
+        let selector = unique_selector(element, scope.as_ref(), &document);
+        let parsed = Selector::parse(&selector)
+            .map_err(|e| anyhow::anyhow!("Generated selector {} does not parse: {:?}", selector, e))?;
+
+        let extraction = match element.value().name() {
+            "a" => ExtractionMethod::Href,
+            "img" => ExtractionMethod::Src,
+            _ => ExtractionMethod::Text,
+        };
+        let sample = match extraction {
+            ExtractionMethod::Href => element.value().attr("href").unwrap_or_default().to_string(),
+            ExtractionMethod::Src => element.value().attr("src").unwrap_or_default().to_string(),
+            _ => element.text().flat_map(str::split_whitespace).collect::<Vec<_>>().join(" "),
+        };
+        let last_step = selector.rsplit(" > ").next().unwrap_or(&selector);
+        let name = field_name(last_step, &extraction, std::slice::from_ref(&sample));
+
+        let picked = match (scope, item) {
+            (Some(_), Some((item_selector, item))) => {
+                let items: Vec<ElementRef> = document.select(&item).collect();
+                PickedSelector {
+                    matches: items.iter().filter(|item| item.select(&parsed).next().is_some()).count(),
+                    items: Some(items.len()),
+                    item_selector: Some(item_selector),
+                    suggested_item: None,
+                    selector,
+                    extraction,
+                    name,
+                    sample,
+                }
+            }
+            _ => PickedSelector {
+                matches: document.select(&parsed).count(),
+                items: None,
+                item_selector: None,
+                suggested_item: suggested_item(element, &document),
+                selector,
+                extraction,
+                name,
+                sample,
+            },
+        };
+        Ok(picked)
+    }
+}
+
+impl PickedSelector {
+    /// Plan field extracting what was picked
+    pub fn to_field(&self) -> Field {
+        let transform = match self.extraction {
+            ExtractionMethod::Text => Some(vec![Transform::Trim]),
+            _ => None,
+        };
+
+        Field {
+            name: self.name.clone(),
+            selector: self.selector.clone(),
+            selector_type: SelectorType::CSS,
+            extraction: self.extraction.clone(),
+            required: false,
+            transform,
+        }
+    }
+}
+
+/// Shown elements in document order, with their depth and subtree end
+fn flatten(document: &Html) -> Vec<(ElementRef<'_>, usize, usize)> {
+    fn walk<'a>(element: ElementRef<'a>, depth: usize, out: &mut Vec<(ElementRef<'a>, usize, usize)>) {
+        let index = out.len();
+        out.push((element, depth, 0));
+        for child in element.children().filter_map(ElementRef::wrap) {
+            if !SKIPPED_TAGS.contains(&child.value().name()) {
+                walk(child, depth + 1, out);
+            }
+        }
+        out[index].2 = out.len();
+    }
+
+    let body = Selector::parse("body").ok()
+        .and_then(|body| document.select(&body).next())
+        .unwrap_or_else(|| document.root_element());
+    let mut out = Vec::new();
+    walk(body, 0, &mut out);
+    out
+}
+
+fn node_label(element: &ElementRef) -> String {
+    let mut label = element.value().name().to_string();
+    if let Some(id) = element.value().id() {
+        label.push('#');
+        label.push_str(id);
+    }
+    for class in element_classes(element).into_iter().take(LABEL_CLASSES) {
+        label.push('.');
+        label.push_str(&class);
+    }
+    label
+}
+
+/// Shortest chain of `tag.class` steps whose first match in `scope` is the element
+///
+/// Steps get `:nth-of-type` when a sibling looks the same, and an id ends the
+/// chain on page-level selectors. Inside an item ids are avoided, since they
+/// usually differ from one item to the next.
+fn unique_selector(element: &ElementRef, scope: Option<&ElementRef>, document: &Html) -> String {
+    let mut steps: Vec<String> = Vec::new();
+    let mut current = *element;
+    loop {
+        let id = current.value().id().filter(|id| {
+            scope.is_none() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                && id.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        });
+        let step = match id {
+            Some(id) => format!("{}#{}", current.value().name(), id),
+            None => element_step(&current),
+        };
+        steps.push(step);
+
+        let selector: Vec<&str> = steps.iter().rev().map(String::as_str).collect();
+        let selector = selector.join(" > ");
+        let first = Selector::parse(&selector).ok().and_then(|parsed| {
+            match scope {
+                Some(scope) => scope.select(&parsed).next(),
+                None => document.select(&parsed).next(),
+            }
+        });
+        if id.is_some() || first.is_some_and(|first| first.id() == element.id()) {
+            return selector;
+        }
+
+        match current.parent().and_then(ElementRef::wrap) {
+            Some(parent) if scope.is_none_or(|scope| parent.id() != scope.id()) => current = parent,
+            _ => return selector,
+        }
+    }
+}
+
+/// `tag.class`, with its position when a sibling has the same tag and classes
+fn element_step(element: &ElementRef) -> String {
+    let signature = element_signature(element);
+    let tag = element.value().name();
+    let siblings: Vec<ElementRef> = element.parent()
+        .map(|parent| parent.children().filter_map(ElementRef::wrap).filter(|e| e.value().name() == tag).collect())
+        .unwrap_or_default();
+
+    let lookalikes = siblings.iter().filter(|sibling| element_signature(sibling) == signature).count();
+    if lookalikes > 1 {
+        let position = siblings.iter().position(|sibling| sibling.id() == element.id()).unwrap_or(0) + 1;
+        format!("{}:nth-of-type({})", signature, position)
+    } else {
+        signature
+    }
+}
+
+/// Item selector for the closest ancestor that repeats on the page
+fn suggested_item(element: &ElementRef, document: &Html) -> Option<String> {
+    element.ancestors()
+        .filter_map(ElementRef::wrap)
+        .filter_map(|ancestor| item_selector_for(&ancestor))
+        .find(|selector| {
+            Selector::parse(selector).is_ok_and(|parsed| document.select(&parsed).nth(1).is_some())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_builds_selectors_relative_to_items() {
+        let mut html = String::from("<html><body><div id='main'><ul class='results'>");
+        for i in 0..3 {
+            html.push_str(&format!(
+                "<li class='card'><h2 class='title'>Item {i}</h2>\
+                 <span class='tag'>new</span><span class='tag'>sale {i}</span>\
+                 <a href='/p/{i}'>View</a></li>"
+            ));
+        }
+        html.push_str("</ul></div></body></html>");
+        let page = InspectedPage::from_html("https://example.com", &html);
+        let node = |label: &str, nth: usize| page.nodes.iter().enumerate()
+            .filter(|(_, node)| node.label == label)
+            .nth(nth)
+            .unwrap().0;
+
+        assert_eq!(page.nodes[0].tag, "body");
+        assert_eq!(page.nodes[0].end, page.nodes.len());
+
+        // Outside an item: the picker offers the repeating ancestor
+        let title = page.pick(node("h2.title", 1), None).unwrap();
+        assert_eq!(title.suggested_item.as_deref(), Some("li.card"));
+        assert_eq!(page.pick(node("div#main", 0), None).unwrap().selector, "div#main");
+
+        let tag = page.pick(node("span.tag", 3), Some("li.card")).unwrap();
+        assert_eq!(tag.selector, "span.tag:nth-of-type(2)");
+        assert_eq!(tag.sample, "sale 1");
+        assert_eq!((tag.matches, tag.items), (3, Some(3)));
+
+        let link = page.pick(node("a", 0), Some("li.card")).unwrap();
+        assert_eq!(link.selector, "a");
+        assert_eq!(link.extraction, ExtractionMethod::Href);
+        assert_eq!(link.to_field().name, "link");
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::dsl::{DelayConfig, DelayDistribution, ScrapePlan};
+
+/// Named politeness presets bundling delays, concurrency, retries and robots behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum PolitenessPreset {
+    #[serde(rename = "gentle")]
+    Gentle,
+    #[default]
+    #[serde(rename = "standard")]
+    Standard,
+    #[serde(rename = "fast")]
+    Fast,
+}
+
+/// Ceilings enforced by a politeness preset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolitenessLimits {
+    /// Lowest allowed minimum delay between requests
+    pub min_delay_ms: u64,
+    /// Default upper bound of the randomized delay
+    pub max_delay_ms: u64,
+    /// Maximum number of concurrent requests against the target
+    pub max_concurrency: usize,
+    /// Maximum retry attempts per request
+    pub max_retries: usize,
+    /// Whether robots.txt must be respected
+    pub respect_robots_txt: bool,
+    /// Whether a robots.txt `Crawl-delay` overrides the plan delay
+    pub honor_crawl_delay: bool,
+}
+
+impl PolitenessPreset {
+    /// Get all available presets, from most to least polite
+    pub fn all() -> Vec<Self> {
+        vec![Self::Gentle, Self::Standard, Self::Fast]
+    }
+
+    /// Get the preset identifier used in plans
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Gentle => "gentle",
+            Self::Standard => "standard",
+            Self::Fast => "fast",
+        }
+    }
+
+    /// Get the display name of the preset
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Gentle => "Gentle",
+            Self::Standard => "Standard",
+            Self::Fast => "Fast",
+        }
+    }
+
+    /// Get a short human-readable description of the preset
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Gentle => "Slow and considerate: one request at a time, long pauses, honors Crawl-delay",
+            Self::Standard => "Balanced defaults suitable for most public sites",
+            Self::Fast => "Short pauses and higher concurrency for sites you own or have permission to load",
+        }
+    }
+
+    /// Get the limits enforced by this preset
+    pub fn limits(&self) -> PolitenessLimits {
+        match self {
+            Self::Gentle => PolitenessLimits {
+                min_delay_ms: 3000,
+                max_delay_ms: 8000,
+                max_concurrency: 1,
+                max_retries: 2,
+                respect_robots_txt: true,
+                honor_crawl_delay: true,
+            },
+            Self::Standard => PolitenessLimits {
+                min_delay_ms: 1000,
+                max_delay_ms: 3000,
+                max_concurrency: 3,
+                max_retries: 3,
+                respect_robots_txt: true,
+                honor_crawl_delay: true,
+            },
+            Self::Fast => PolitenessLimits {
+                min_delay_ms: 250,
+                max_delay_ms: 1000,
+                max_concurrency: 8,
+                max_retries: 5,
+                respect_robots_txt: true,
+                honor_crawl_delay: false,
+            },
+        }
+    }
+
+    /// Get the default delay configuration for this preset
+    pub fn delay_config(&self) -> DelayConfig {
+        let limits = self.limits();
+        DelayConfig {
+            min_ms: limits.min_delay_ms,
+            max_ms: limits.max_delay_ms,
+            distribution: DelayDistribution::Uniform,
+        }
+    }
+
+    /// Pick a preset from wording in a natural language description
+    pub fn from_description(description: &str) -> Self {
+        let text = description.to_lowercase();
+
+        let gentle_keywords = ["gentle", "gently", "polite", "politely", "slowly", "carefully", "don't overload"];
+        let fast_keywords = ["fast", "quickly", "as quick as", "asap", "aggressive"];
+
+        if gentle_keywords.iter().any(|k| text.contains(k)) {
+            Self::Gentle
+        } else if fast_keywords.iter().any(|k| text.contains(k)) {
+            Self::Fast
+        } else {
+            Self::Standard
+        }
+    }
+
+    /// Apply this preset to a plan, resetting its delay and robots settings
+    pub fn apply_to(&self, plan: &mut ScrapePlan) {
+        let limits = self.limits();
+        plan.anti_blocking.politeness = *self;
+        plan.anti_blocking.randomized_delays = self.delay_config();
+        plan.anti_blocking.respect_robots_txt = limits.respect_robots_txt;
+    }
+
+    /// Check a plan against this preset and describe every exceeded ceiling
+    pub fn check_plan(&self, plan: &ScrapePlan) -> Vec<String> {
+        let limits = self.limits();
+        let delays = &plan.anti_blocking.randomized_delays;
+        let mut violations = Vec::new();
+
+        if delays.min_ms < limits.min_delay_ms {
+            violations.push(format!(
+                "Minimum delay {}ms is below the {} preset floor of {}ms",
+                delays.min_ms, self.name(), limits.min_delay_ms
+            ));
+        }
+
+        if limits.respect_robots_txt && !plan.anti_blocking.respect_robots_txt {
+            violations.push(format!(
+                "The {} preset requires robots.txt to be respected",
+                self.name()
+            ));
+        }
+
+        violations
+    }
+}
+
+impl fmt::Display for PolitenessPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for PolitenessPreset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "gentle" => Ok(Self::Gentle),
+            "standard" => Ok(Self::Standard),
+            "fast" => Ok(Self::Fast),
+            _ => Err(anyhow::anyhow!("Unknown politeness preset: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_ordered_by_politeness() {
+        let gentle = PolitenessPreset::Gentle.limits();
+        let standard = PolitenessPreset::Standard.limits();
+        let fast = PolitenessPreset::Fast.limits();
+
+        assert!(gentle.min_delay_ms > standard.min_delay_ms);
+        assert!(standard.min_delay_ms > fast.min_delay_ms);
+        assert!(gentle.max_concurrency < standard.max_concurrency);
+        assert!(standard.max_concurrency < fast.max_concurrency);
+    }
+
+    #[test]
+    fn test_apply_and_check_plan() {
+        let mut plan = ScrapePlan::default();
+        PolitenessPreset::Gentle.apply_to(&mut plan);
+
+        assert_eq!(plan.anti_blocking.politeness, PolitenessPreset::Gentle);
+        assert!(PolitenessPreset::Gentle.check_plan(&plan).is_empty());
+
+        plan.anti_blocking.randomized_delays.min_ms = 500;
+        plan.anti_blocking.respect_robots_txt = false;
+        assert_eq!(PolitenessPreset::Gentle.check_plan(&plan).len(), 2);
+    }
+
+    #[test]
+    fn test_from_description() {
+        assert_eq!(PolitenessPreset::from_description("Gently scrape example.com"), PolitenessPreset::Gentle);
+        assert_eq!(PolitenessPreset::from_description("Quickly grab prices"), PolitenessPreset::Fast);
+        assert_eq!(PolitenessPreset::from_description("Scrape news headlines"), PolitenessPreset::Standard);
+        assert_eq!("FAST".parse::<PolitenessPreset>().unwrap(), PolitenessPreset::Fast);
+    }
+}
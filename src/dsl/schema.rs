@@ -0,0 +1,447 @@
+use serde_json::{json, Value};
+
+/// JSON Schema dialect used for the generated schema
+pub const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Stable identifier of the scrape plan schema
+pub const SCHEMA_ID: &str = "https://winscrape.studio/schemas/scrape-plan.schema.json";
+
+/// Build the JSON Schema describing a `ScrapePlan`
+///
+/// The schema mirrors the serde representation of the DSL types, so any document
+/// that validates against it can be deserialized with `ScrapePlan::from_json`.
+pub fn scrape_plan_schema() -> Value {
+    json!({
+        "$schema": SCHEMA_DIALECT,
+        "$id": SCHEMA_ID,
+        "title": "ScrapePlan",
+        "description": "WinScrape Studio scrape plan",
+        "type": "object",
+        "required": ["version", "target", "rules", "anti_blocking", "output"],
+        "properties": {
+            "version": {
+                "type": "string",
+                "pattern": "^[A-Za-z0-9.-]+$",
+                "description": "DSL version of the plan"
+            },
+            "target": { "$ref": "#/$defs/Target" },
+            "rules": { "$ref": "#/$defs/Rules" },
+            "anti_blocking": { "$ref": "#/$defs/AntiBlocking" },
+            "output": { "$ref": "#/$defs/Output" },
+            "retry": nullable(retry_policy()),
+            "metadata": {
+                "type": ["object", "null"],
+                "additionalProperties": true
+            }
+        },
+        "$defs": definitions()
+    })
+}
+
+/// Render the scrape plan schema as pretty-printed JSON
+pub fn scrape_plan_schema_json() -> String {
+    serde_json::to_string_pretty(&scrape_plan_schema()).unwrap_or_default()
+}
+
+fn definitions() -> Value {
+    json!({
+        "Target": {
+            "type": "object",
+            "required": ["domain", "start_urls"],
+            "properties": {
+                "domain": { "type": "string", "minLength": 1 },
+                "start_urls": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": "^https?://" }
+                },
+                "url_patterns": nullable(json!({
+                    "type": "array",
+                    "items": { "type": "string", "minLength": 1 }
+                })),
+                "max_pages": nullable(json!({ "type": "integer", "minimum": 1 })),
+                "depends_on": {
+                    "type": "object",
+                    "description": "Saved plan run first; the URLs in url_field of its results become the start URLs",
+                    "required": ["plan"],
+                    "properties": {
+                        "plan": { "type": "string", "minLength": 1 },
+                        "url_field": { "type": "string", "minLength": 1, "default": "url" }
+                    }
+                }
+            },
+            // Start URLs may only be left out when another plan supplies them
+            "if": { "not": { "required": ["depends_on"] } },
+            "then": { "properties": { "start_urls": { "minItems": 1 } } }
+        },
+        "Rules": {
+            "type": "object",
+            "required": ["item_selector", "fields"],
+            "properties": {
+                "pagination": nullable(json!({ "$ref": "#/$defs/Pagination" })),
+                "item_selector": { "type": "string", "minLength": 1 },
+                "fields": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "$ref": "#/$defs/Field" }
+                },
+                "filters": nullable(json!({
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/FilterExpr" }
+                })),
+                "missing_required": { "$ref": "#/$defs/MissingRequiredPolicy" },
+                "post_process": nullable(json!({ "$ref": "#/$defs/PostProcess" }))
+            }
+        },
+        "PostProcess": post_process(),
+        "MissingRequiredPolicy": {
+            "oneOf": [
+                tagged("drop", json!({})),
+                tagged("keep_null", json!({})),
+                tagged("fail_above", json!({ "max_percent": { "type": "number", "minimum": 0, "maximum": 100 } }))
+            ],
+            "default": { "type": "drop" }
+        },
+        "Pagination": {
+            "type": "object",
+            "required": ["method"],
+            "properties": {
+                "method": { "$ref": "#/$defs/PaginationMethod" },
+                "selector": nullable(json!({ "type": "string" })),
+                "max_pages": nullable(json!({ "type": "integer", "minimum": 1 })),
+                "wait_time_ms": nullable(json!({ "type": "integer", "minimum": 0 }))
+            }
+        },
+        "PaginationMethod": {
+            "oneOf": [
+                tagged("link", json!({ "next_selector": { "type": "string", "minLength": 1 } })),
+                tagged("button", json!({ "button_selector": { "type": "string", "minLength": 1 } })),
+                tagged("scroll", json!({ "scroll_pause_ms": { "type": "integer", "minimum": 1 } })),
+                tagged("url_pattern", json!({
+                    "pattern": { "type": "string", "minLength": 1 },
+                    "start": { "type": "integer", "minimum": 0 },
+                    "end": { "type": "integer", "minimum": 0 }
+                }))
+            ]
+        },
+        "Field": {
+            "type": "object",
+            "required": ["name", "selector", "selector_type", "extraction", "required"],
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "selector": { "type": "string", "minLength": 1 },
+                "selector_type": { "enum": ["css", "xpath"] },
+                "extraction": { "$ref": "#/$defs/ExtractionMethod" },
+                "required": { "type": "boolean" },
+                "transform": nullable(json!({
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/Transform" }
+                }))
+            }
+        },
+        "ExtractionMethod": {
+            "oneOf": [
+                { "enum": ["text", "html", "href", "src"] },
+                {
+                    "type": "object",
+                    "required": ["attr"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "attr": {
+                            "type": "object",
+                            "required": ["name"],
+                            "properties": {
+                                "name": { "type": "string", "pattern": "^[A-Za-z0-9_-]+$" }
+                            }
+                        }
+                    }
+                }
+            ]
+        },
+        "Transform": {
+            "oneOf": [
+                tagged("trim", json!({})),
+                tagged("lowercase", json!({})),
+                tagged("uppercase", json!({})),
+                tagged("regex", json!({
+                    "pattern": { "type": "string", "minLength": 1 },
+                    "replacement": { "type": "string" }
+                })),
+                tagged("parse_number", json!({})),
+                tagged("parse_date", json!({ "format": nullable(json!({ "type": "string", "minLength": 1 })) })),
+                tagged("remove_html", json!({})),
+                tagged("extract_domain", json!({})),
+                tagged("plugin", json!({ "name": { "type": "string", "minLength": 1 }, "args": nullable(json!({})) }))
+            ]
+        },
+        "FilterExpr": {
+            "oneOf": [
+                {
+                    "type": "object",
+                    "required": ["all"],
+                    "properties": {
+                        "all": { "type": "array", "minItems": 1, "items": { "$ref": "#/$defs/FilterExpr" } }
+                    }
+                },
+                {
+                    "type": "object",
+                    "required": ["any"],
+                    "properties": {
+                        "any": { "type": "array", "minItems": 1, "items": { "$ref": "#/$defs/FilterExpr" } }
+                    }
+                },
+                {
+                    "type": "object",
+                    "required": ["not"],
+                    "properties": {
+                        "not": { "$ref": "#/$defs/FilterExpr" }
+                    }
+                },
+                { "$ref": "#/$defs/Filter" }
+            ]
+        },
+        "Filter": {
+            "type": "object",
+            "required": ["field", "condition"],
+            "properties": {
+                "field": { "type": "string", "minLength": 1 },
+                "condition": { "$ref": "#/$defs/FilterCondition" }
+            }
+        },
+        "FilterCondition": {
+            "oneOf": [
+                tagged("contains", json!({ "value": { "type": "string", "minLength": 1 } })),
+                tagged("not_contains", json!({ "value": { "type": "string", "minLength": 1 } })),
+                tagged("equals", json!({ "value": { "type": "string", "minLength": 1 } })),
+                tagged("not_equals", json!({ "value": { "type": "string", "minLength": 1 } })),
+                tagged("regex", json!({ "pattern": { "type": "string", "minLength": 1 } })),
+                tagged("length_min", json!({ "min": { "type": "integer", "minimum": 1 } })),
+                tagged("length_max", json!({ "max": { "type": "integer", "minimum": 1 } })),
+                tagged("number_min", json!({ "min": { "type": "number" } })),
+                tagged("number_max", json!({ "max": { "type": "number" } })),
+                tagged("not_empty", json!({})),
+                tagged("plugin", json!({ "name": { "type": "string", "minLength": 1 }, "args": nullable(json!({})) }))
+            ]
+        },
+        "AntiBlocking": {
+            "type": "object",
+            "required": ["randomized_delays", "user_agent_rotation", "respect_robots_txt"],
+            "properties": {
+                "randomized_delays": { "$ref": "#/$defs/DelayConfig" },
+                "user_agent_rotation": { "type": "boolean" },
+                "respect_robots_txt": { "type": "boolean" },
+                "proxy": nullable(json!({ "$ref": "#/$defs/ProxyConfig" })),
+                "headers": nullable(json!({
+                    "type": "object",
+                    "additionalProperties": { "type": "string", "minLength": 1 }
+                })),
+                "politeness": {
+                    "enum": ["gentle", "standard", "fast"],
+                    "default": "standard"
+                },
+                "random_seed": nullable(json!({ "type": "integer", "minimum": 0 })),
+                "redirects": { "$ref": "#/$defs/RedirectPolicy" },
+                "client_profile": nullable(json!({ "enum": ["desktop_chrome", "mobile_safari", "googlebot"] })),
+                "user_agent": nullable(json!({ "type": "string", "minLength": 1 })),
+                "robots_meta": { "$ref": "#/$defs/RobotsMetaPolicy" }
+            }
+        },
+        "RobotsMetaPolicy": {
+            "type": "object",
+            "properties": {
+                "respect_nofollow": { "type": "boolean", "default": true },
+                "skip_noindex": { "type": "boolean", "default": false }
+            }
+        },
+        "RedirectPolicy": {
+            "type": "object",
+            "properties": {
+                "max_redirects": { "type": "integer", "minimum": 0, "default": 10 },
+                "allow_cross_domain": { "type": "boolean", "default": true }
+            }
+        },
+        "DelayConfig": {
+            "type": "object",
+            "required": ["min_ms", "max_ms", "distribution"],
+            "properties": {
+                "min_ms": { "type": "integer", "minimum": 0 },
+                "max_ms": { "type": "integer", "minimum": 0 },
+                "distribution": { "enum": ["uniform", "exponential", "normal"] }
+            }
+        },
+        "ProxyConfig": {
+            "type": "object",
+            "required": ["enabled", "proxies", "rotation"],
+            "properties": {
+                "enabled": { "type": "boolean" },
+                "proxies": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": "^https?://" }
+                },
+                "rotation": { "enum": ["round_robin", "random"] }
+            }
+        },
+        "Output": {
+            "type": "object",
+            "required": ["format"],
+            "properties": {
+                "format": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "enum": ["csv", "json", "xlsx", "parquet", "ndjson"] }
+                },
+                "limit": nullable(json!({ "type": "integer", "minimum": 1 })),
+                "dedupe_keys": nullable(json!({
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "type": "string" }
+                })),
+                "sort_by": nullable(json!({ "type": "string", "minLength": 1 })),
+                "sort_order": nullable(json!({ "enum": ["asc", "desc"] })),
+                "duplicate_policy": {
+                    "enum": ["skip", "keep_first", "keep_last"],
+                    "default": "keep_first"
+                },
+                "hash_fields": nullable(json!({
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "type": "string" }
+                })),
+                "filename_template": nullable(json!({ "type": "string", "minLength": 1 })),
+                "skip_seen": { "type": "boolean", "default": false },
+                "export_profiles": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Saved export profiles run after every completed job"
+                },
+                "notify": nullable(notify_options())
+            }
+        }
+    })
+}
+
+/// Automatic retries of failed runs
+fn retry_policy() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "max_attempts": { "type": "integer", "minimum": 1, "maximum": 20, "default": 3 },
+            "backoff_ms": { "type": "integer", "minimum": 0, "default": 30000 },
+            "backoff_multiplier": { "type": "number", "minimum": 1, "default": 2.0 },
+            "max_backoff_ms": { "type": "integer", "minimum": 0, "default": 600000 },
+            "only_failed_urls": {
+                "type": "boolean",
+                "default": false,
+                "description": "Fetch only the URLs that failed instead of the whole plan"
+            }
+        }
+    })
+}
+
+/// Per-plan notification settings, kept apart to stay within `json!`'s recursion limit
+fn notify_options() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": false,
+        "description": "Overrides of the application's notification settings for this plan's jobs",
+        "properties": {
+            "on_completed": { "type": "boolean" },
+            "on_failed": { "type": "boolean" },
+            "desktop": { "type": "boolean" },
+            "email": { "type": "boolean" },
+            "email_to": { "type": "array", "items": { "type": "string", "minLength": 3 } }
+        }
+    })
+}
+
+fn post_process() -> Value {
+    let limits = json!({
+        "type": "object",
+        "properties": {
+            "max_operations": { "type": "integer", "minimum": 1, "default": 1_000_000 },
+            "max_memory_mb": { "type": "integer", "minimum": 1, "maximum": 1024, "default": 16 }
+        }
+    });
+    json!({
+        "description": "Script run sandboxed on every item that passed the filters",
+        "oneOf": [
+            {
+                "type": "object",
+                "required": ["language", "script"],
+                "properties": {
+                    "language": { "const": "rhai" },
+                    "script": { "type": "string", "minLength": 1 },
+                    "limits": limits
+                }
+            },
+            {
+                "type": "object",
+                "required": ["language", "module"],
+                "properties": {
+                    "language": { "const": "wasm" },
+                    "module": { "type": "string", "minLength": 1 },
+                    "limits": limits
+                }
+            }
+        ]
+    })
+}
+
+/// Object variant of an internally tagged enum (`#[serde(tag = "type")]`)
+fn tagged(tag: &str, properties: Value) -> Value {
+    let mut props = serde_json::Map::new();
+    props.insert("type".to_string(), json!({ "const": tag }));
+
+    let mut required = vec![json!("type")];
+    if let Value::Object(extra) = properties {
+        for (name, schema) in extra {
+            // Option fields may be omitted
+            let optional = schema.get("anyOf").is_some();
+            if !optional {
+                required.push(json!(name));
+            }
+            props.insert(name, schema);
+        }
+    }
+
+    json!({
+        "type": "object",
+        "required": required,
+        "properties": props
+    })
+}
+
+/// Allow `null` in addition to the given schema, matching serde's `Option<T>`
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+
+    #[test]
+    fn test_schema_covers_all_definitions() {
+        let schema = scrape_plan_schema();
+        let defs = schema["$defs"].as_object().unwrap();
+
+        for name in ["Target", "Rules", "Field", "FilterExpr", "AntiBlocking", "Output"] {
+            assert!(defs.contains_key(name), "missing definition {}", name);
+        }
+        assert_eq!(schema["$schema"], SCHEMA_DIALECT);
+    }
+
+    #[test]
+    fn test_schema_required_keys_match_serialized_plan() {
+        let schema = scrape_plan_schema();
+        let plan = serde_json::to_value(DSLExamples::ecommerce_products()).unwrap();
+
+        for key in schema["required"].as_array().unwrap() {
+            assert!(plan.get(key.as_str().unwrap()).is_some(), "plan missing {}", key);
+        }
+        for key in schema["$defs"]["Field"]["required"].as_array().unwrap() {
+            assert!(plan["rules"]["fields"][0].get(key.as_str().unwrap()).is_some());
+        }
+    }
+}
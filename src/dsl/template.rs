@@ -0,0 +1,190 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::dsl::ScrapePlan;
+
+/// Metadata key recording the variables a plan was resolved with
+pub const VARIABLES_METADATA_KEY: &str = "variables";
+
+/// Parts of a plan placeholders are resolved in, as JSON pointers: the target,
+/// the pagination URL pattern and the request headers
+///
+/// Selectors, transforms and scripts are left alone, so regex replacements
+/// like `${1}` and Rhai string interpolation keep working.
+const TEMPLATED_PARTS: &[&str] = &[
+    "/target",
+    "/rules/pagination/method/pattern",
+    "/anti_blocking/headers",
+    "/anti_blocking/user_agent",
+];
+
+/// Names of the `${name}` variables used in a plan, sorted and without duplicates
+pub fn plan_variables(plan: &ScrapePlan) -> Result<Vec<String>> {
+    let value = serde_json::to_value(plan)?;
+    let mut names = BTreeSet::new();
+    for part in TEMPLATED_PARTS.iter().filter_map(|pointer| value.pointer(pointer)) {
+        visit_strings(part, &mut |s| collect_variables(s, &mut names));
+    }
+    Ok(names.into_iter().collect())
+}
+
+/// Replace `${name}` placeholders in the target, URLs and request headers of a plan
+///
+/// Names start with a letter or `_`; write `$${name}` for a literal `${name}`.
+/// Fails listing every variable that has no value.
+pub fn resolve_plan(plan: &ScrapePlan, variables: &HashMap<String, String>) -> Result<ScrapePlan> {
+    let mut value = serde_json::to_value(plan)?;
+    let mut missing = BTreeSet::new();
+
+    for pointer in TEMPLATED_PARTS {
+        if let Some(part) = value.pointer_mut(pointer) {
+            resolve_value(part, variables, &mut missing);
+        }
+    }
+
+    if !missing.is_empty() {
+        let names: Vec<String> = missing.into_iter().collect();
+        return Err(anyhow::anyhow!("Missing values for plan variables: {}", names.join(", ")));
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Substitute variables in a single string, recording names without a value
+fn substitute(input: &str, variables: &HashMap<String, String>, missing: &mut BTreeSet<String>) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        output.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        // `$$` only escapes a placeholder; elsewhere both dollars stay
+        if let Some((_, len)) = after.strip_prefix('$').and_then(parse_placeholder) {
+            output.push_str(&after[..len + 1]);
+            rest = &after[len + 1..];
+            continue;
+        }
+
+        match parse_placeholder(after) {
+            Some((name, len)) => {
+                match variables.get(name) {
+                    Some(value) => output.push_str(value),
+                    None => {
+                        missing.insert(name.to_string());
+                        output.push_str(&rest[pos..pos + 1 + len]);
+                    }
+                }
+                rest = &after[len..];
+            }
+            None => {
+                output.push('$');
+                rest = after;
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Parse `{name}` at the start of `s`, returning the name and the consumed length
+fn parse_placeholder(s: &str) -> Option<(&str, usize)> {
+    let inner = s.strip_prefix('{')?;
+    let end = inner.find('}')?;
+    let name = &inner[..end];
+
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Some((name, end + 2))
+    } else {
+        None
+    }
+}
+
+fn collect_variables(input: &str, names: &mut BTreeSet<String>) {
+    // Resolving against no values reports every placeholder as missing
+    substitute(input, &HashMap::new(), names);
+}
+
+fn resolve_value(value: &mut Value, variables: &HashMap<String, String>, missing: &mut BTreeSet<String>) {
+    match value {
+        Value::String(s) => *s = substitute(s, variables, missing),
+        Value::Array(items) => {
+            for item in items {
+                resolve_value(item, variables, missing);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                resolve_value(item, variables, missing);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn visit_strings(value: &Value, f: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(s) => f(s),
+        Value::Array(items) => items.iter().for_each(|item| visit_strings(item, f)),
+        Value::Object(map) => map.values().for_each(|item| visit_strings(item, f)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::{DSLExamples, PostProcess, Transform};
+
+    #[test]
+    fn test_substitute_variables() {
+        let vars = HashMap::from([("category".to_string(), "books".to_string())]);
+        let mut missing = BTreeSet::new();
+
+        assert_eq!(substitute("/c/${category}?p=${page}", &vars, &mut missing), "/c/books?p=${page}");
+        assert_eq!(missing.into_iter().collect::<Vec<_>>(), vec!["page"]);
+        assert_eq!(substitute("$${category} costs $5", &vars, &mut BTreeSet::new()), "${category} costs $5");
+        assert_eq!(substitute("$$5 or $${1} or ${2}", &vars, &mut BTreeSet::new()), "$$5 or $${1} or ${2}");
+    }
+
+    #[test]
+    fn test_resolve_plan() {
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.target.start_urls = vec!["https://example.com/${category}".to_string()];
+
+        assert_eq!(plan_variables(&plan).unwrap(), vec!["category"]);
+        assert!(resolve_plan(&plan, &HashMap::new()).is_err());
+
+        let vars = HashMap::from([("category".to_string(), "shoes".to_string())]);
+        let resolved = resolve_plan(&plan, &vars).unwrap();
+        assert_eq!(resolved.target.start_urls, vec!["https://example.com/shoes"]);
+    }
+
+    #[test]
+    fn test_regex_replacements_and_scripts_are_left_alone() {
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.target.start_urls = vec!["https://example.com/${category}".to_string()];
+        plan.anti_blocking.headers = Some(HashMap::from([("Referer".to_string(), "https://example.com/${category}".to_string())]));
+        plan.rules.fields[0].transform = Some(vec![Transform::Regex {
+            pattern: r"(?P<year>\d{4})-(\d{2})".to_string(),
+            replacement: "${2}/${year}".to_string(),
+        }]);
+        let script = "let left = item.stock; item.note = `${left} left`; item";
+        plan.rules.post_process = Some(PostProcess::Rhai { script: script.to_string(), limits: Default::default() });
+
+        assert_eq!(plan_variables(&plan).unwrap(), vec!["category"]);
+        let vars = HashMap::from([("category".to_string(), "shoes".to_string())]);
+        let resolved = resolve_plan(&plan, &vars).unwrap();
+
+        assert_eq!(resolved.anti_blocking.headers.unwrap()["Referer"], "https://example.com/shoes");
+        assert!(matches!(
+            &resolved.rules.fields[0].transform.as_deref().unwrap()[0],
+            Transform::Regex { replacement, .. } if replacement == "${2}/${year}"
+        ));
+        assert!(matches!(&resolved.rules.post_process, Some(PostProcess::Rhai { script: s, .. }) if s == script));
+    }
+}
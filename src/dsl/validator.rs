@@ -1,72 +1,233 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use tracing::{warn, debug};
-use crate::dsl::{ScrapePlan, Target, Rules, Field, SelectorType, ExtractionMethod, Transform, Pagination, PaginationMethod, AntiBlocking, Output, OutputFormat, Filter, FilterCondition};
+use crate::scraper::post_process::{PostProcessor, ScriptSandbox};
+use crate::dsl::{ScrapePlan, Target, Rules, Field, SelectorType, ExtractionMethod, Transform, Pagination, PaginationMethod, AntiBlocking, Output, Filter, FilterCondition, FilterExpr, MissingRequiredPolicy, PostProcess, RetryPolicy};
 use url::Url;
 
+/// Maximum nesting depth for boolean filter groups
+const MAX_FILTER_DEPTH: usize = 8;
+
+/// Machine-readable validation error codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationCode {
+    /// A required value is empty or missing
+    Required,
+    /// A value does not have the expected format
+    InvalidFormat,
+    /// A URL cannot be parsed
+    InvalidUrl,
+    /// A URL uses a scheme other than HTTP(S)
+    UnsafeScheme,
+    /// A name is defined more than once
+    Duplicate,
+    /// A reference points to a field that is not defined
+    UnknownField,
+    /// A number is outside its allowed range
+    OutOfRange,
+    /// Filter groups are nested deeper than allowed
+    TooDeep,
+    /// A plugin transform or condition is not registered
+    UnknownPlugin,
+    /// A post-process script cannot be compiled or loaded
+    InvalidScript,
+}
+
+impl ValidationCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationCode::Required => "required",
+            ValidationCode::InvalidFormat => "invalid_format",
+            ValidationCode::InvalidUrl => "invalid_url",
+            ValidationCode::UnsafeScheme => "unsafe_scheme",
+            ValidationCode::Duplicate => "duplicate",
+            ValidationCode::UnknownField => "unknown_field",
+            ValidationCode::OutOfRange => "out_of_range",
+            ValidationCode::TooDeep => "too_deep",
+            ValidationCode::UnknownPlugin => "unknown_plugin",
+            ValidationCode::InvalidScript => "invalid_script",
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single validation problem located by its path in the plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("{path}: {message}")]
+pub struct ValidationIssue {
+    /// Location in the plan, e.g. `rules.fields[2].selector`
+    pub path: String,
+    pub code: ValidationCode,
+    pub message: String,
+    /// How to fix the problem, when there is an obvious fix
+    pub suggestion: Option<String>,
+}
+
+impl ValidationIssue {
+    pub fn new(path: impl Into<String>, code: ValidationCode, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            code,
+            message: message.into(),
+            suggestion: None,
+        }
+    }
+    
+    pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+/// All validation problems found in a plan
+///
+/// This is the error type behind `DSLValidator::validate`, so callers can
+/// `downcast_ref::<ValidationErrors>()` to get the structured issues.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{}", summarize_issues(.issues))]
+pub struct ValidationErrors {
+    pub issues: Vec<ValidationIssue>,
+}
+
+fn summarize_issues(issues: &[ValidationIssue]) -> String {
+    match issues {
+        [] => "Validation failed".to_string(),
+        [only] => only.to_string(),
+        [first, rest @ ..] => format!("{} (and {} more)", first, rest.len()),
+    }
+}
+
 /// DSL validator for comprehensive validation of scrape plans
 pub struct DSLValidator {
-    // Simple validation without complex regex patterns
+    /// Where `post_process` modules are looked up
+    scripts: ScriptSandbox,
 }
 
 impl DSLValidator {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            scripts: ScriptSandbox::default(),
+        }
+    }
+    
+    /// Look `post_process` modules up in, and cap script limits by, `sandbox`
+    pub fn with_scripts(mut self, sandbox: ScriptSandbox) -> Self {
+        self.scripts = sandbox;
+        self
     }
     
     /// Validate a complete scrape plan
+    ///
+    /// Fails with `ValidationErrors` listing every problem found.
     pub fn validate(&self, plan: &ScrapePlan) -> Result<()> {
+        let issues = self.validate_detailed(plan);
+        
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors { issues }.into())
+        }
+    }
+    
+    /// Validate a complete scrape plan and return every problem found
+    pub fn validate_detailed(&self, plan: &ScrapePlan) -> Vec<ValidationIssue> {
         debug!("Starting DSL validation for plan: {}", plan.target.domain);
+        let mut issues = Vec::new();
         
         // Validate version
-        self.validate_version(&plan.version)?;
+        self.validate_version(&plan.version, &mut issues);
         
         // Validate target
-        self.validate_target(&plan.target)?;
+        self.validate_target(&plan.target, &mut issues);
         
         // Validate rules
-        self.validate_rules(&plan.rules)?;
+        self.validate_rules(&plan.rules, &mut issues);
         
         // Validate anti-blocking settings
-        self.validate_anti_blocking(&plan.anti_blocking)?;
+        self.validate_anti_blocking(&plan.anti_blocking, &mut issues);
         
         // Validate output configuration
-        self.validate_output(&plan.output)?;
+        self.validate_output(&plan.output, &mut issues);
+        
+        if let Some(retry) = &plan.retry {
+            self.validate_retry(retry, &mut issues);
+        }
         
         // Cross-reference validation
-        self.validate_cross_references(plan)?;
+        self.validate_cross_references(plan, &mut issues);
         
-        debug!("DSL validation completed successfully");
-        Ok(())
+        if issues.is_empty() {
+            debug!("DSL validation completed successfully");
+        } else {
+            debug!("DSL validation found {} issues", issues.len());
+        }
+        issues
     }
     
-    fn validate_version(&self, version: &str) -> Result<()> {
+    fn validate_version(&self, version: &str, issues: &mut Vec<ValidationIssue>) {
         if version.is_empty() {
-            return Err(anyhow::anyhow!("Version cannot be empty"));
+            issues.push(ValidationIssue::new("version", ValidationCode::Required, "Version cannot be empty")
+                .with_suggestion("Set version to \"1.0\""));
+            return;
         }
         
         // Check if version follows semantic versioning
         if !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
-            return Err(anyhow::anyhow!("Invalid version format: {}", version));
+            issues.push(ValidationIssue::new("version", ValidationCode::InvalidFormat, format!("Invalid version format: {}", version))
+                .with_suggestion("Use a dotted version such as \"1.0\""));
         }
-        
-        Ok(())
     }
     
-    fn validate_target(&self, target: &Target) -> Result<()> {
+    fn validate_retry(&self, retry: &RetryPolicy, issues: &mut Vec<ValidationIssue>) {
+        if !(1..=20).contains(&retry.max_attempts) {
+            issues.push(ValidationIssue::new("retry.max_attempts", ValidationCode::OutOfRange, "Max attempts must be between 1 and 20")
+                .with_suggestion("3 attempts ride out most short outages"));
+        }
+        if !retry.backoff_multiplier.is_finite() || retry.backoff_multiplier < 1.0 {
+            issues.push(ValidationIssue::new("retry.backoff_multiplier", ValidationCode::OutOfRange, "Backoff multiplier must be 1 or more"));
+        }
+        if retry.max_backoff_ms < retry.backoff_ms {
+            issues.push(ValidationIssue::new("retry.max_backoff_ms", ValidationCode::OutOfRange, "Max backoff must not be shorter than the first backoff"));
+        }
+    }
+    
+    fn validate_target(&self, target: &Target, issues: &mut Vec<ValidationIssue>) {
         if target.domain.is_empty() {
-            return Err(anyhow::anyhow!("Domain cannot be empty"));
+            issues.push(ValidationIssue::new("target.domain", ValidationCode::Required, "Domain cannot be empty")
+                .with_suggestion("Set the host name of the site, e.g. \"example.com\""));
+        }
+        
+        if target.start_urls.is_empty() && target.depends_on.is_none() {
+            issues.push(ValidationIssue::new("target.start_urls", ValidationCode::Required, "At least one start URL is required")
+                .with_suggestion(format!("Add a URL such as \"https://{}/\"", target.domain)));
         }
         
-        if target.start_urls.is_empty() {
-            return Err(anyhow::anyhow!("At least one start URL is required"));
+        if let Some(dependency) = &target.depends_on {
+            if dependency.plan.trim().is_empty() {
+                issues.push(ValidationIssue::new("target.depends_on.plan", ValidationCode::Required, "Name the saved plan to run first"));
+            }
+            if dependency.url_field.trim().is_empty() {
+                issues.push(ValidationIssue::new("target.depends_on.url_field", ValidationCode::Required, "Name the field holding the upstream URLs")
+                    .with_suggestion("Use \"url\" or another field the upstream plan extracts"));
+            }
         }
         
         // Validate start URLs
-        for url_str in &target.start_urls {
+        for (i, url_str) in target.start_urls.iter().enumerate() {
+            let path = format!("target.start_urls[{}]", i);
             match Url::parse(url_str) {
                 Ok(url) => {
                     if !url.scheme().starts_with("http") {
-                        return Err(anyhow::anyhow!("URL must use HTTP or HTTPS scheme: {}", url_str));
+                        issues.push(ValidationIssue::new(path, ValidationCode::UnsafeScheme, format!("URL must use HTTP or HTTPS scheme: {}", url_str))
+                            .with_suggestion("Use an http:// or https:// URL"));
+                        continue;
                     }
                     
                     if let Some(host) = url.host_str() {
@@ -76,116 +237,157 @@ impl DSLValidator {
                     }
                 }
                 Err(e) => {
-                    return Err(anyhow::anyhow!("Invalid URL '{}': {}", url_str, e));
+                    let issue = ValidationIssue::new(path, ValidationCode::InvalidUrl, format!("Invalid URL '{}': {}", url_str, e));
+                    issues.push(if url_str.contains("://") {
+                        issue
+                    } else {
+                        issue.with_suggestion(format!("Add a scheme: https://{}", url_str))
+                    });
                 }
             }
         }
         
         // Validate URL patterns if provided
         if let Some(patterns) = &target.url_patterns {
-            for pattern in patterns {
-                self.validate_url_pattern(pattern)?;
+            for (i, pattern) in patterns.iter().enumerate() {
+                self.validate_url_pattern(pattern, &format!("target.url_patterns[{}]", i), issues);
             }
         }
         
         // Validate max_pages if provided
         if let Some(max_pages) = target.max_pages {
             if max_pages == 0 {
-                return Err(anyhow::anyhow!("Max pages must be greater than 0"));
+                issues.push(ValidationIssue::new("target.max_pages", ValidationCode::OutOfRange, "Max pages must be greater than 0")
+                    .with_suggestion("Remove max_pages or set it to 1 or more"));
             }
             if max_pages > 10000 {
                 warn!("Very high max_pages value: {}. This may cause performance issues.", max_pages);
             }
         }
-        
-        Ok(())
     }
     
-    fn validate_url_pattern(&self, pattern: &str) -> Result<()> {
+    fn validate_url_pattern(&self, pattern: &str, path: &str, issues: &mut Vec<ValidationIssue>) {
         if pattern.is_empty() {
-            return Err(anyhow::anyhow!("URL pattern cannot be empty"));
+            issues.push(ValidationIssue::new(path, ValidationCode::Required, "URL pattern cannot be empty"));
+            return;
         }
         
         // Check for basic URL pattern validity
         if !pattern.contains("http") && !pattern.contains("https") {
-            return Err(anyhow::anyhow!("URL pattern must contain http or https: {}", pattern));
+            issues.push(ValidationIssue::new(path, ValidationCode::InvalidFormat, format!("URL pattern must contain http or https: {}", pattern))
+                .with_suggestion("Use an absolute pattern like \"https://example.com/list?page={page}\""));
         }
         
         // Check for dangerous patterns
         if pattern.contains("file://") || pattern.contains("ftp://") {
-            return Err(anyhow::anyhow!("Unsafe URL scheme in pattern: {}", pattern));
+            issues.push(ValidationIssue::new(path, ValidationCode::UnsafeScheme, format!("Unsafe URL scheme in pattern: {}", pattern)));
         }
-        
-        Ok(())
     }
     
-    fn validate_rules(&self, rules: &Rules) -> Result<()> {
+    fn validate_rules(&self, rules: &Rules, issues: &mut Vec<ValidationIssue>) {
         if rules.item_selector.is_empty() {
-            return Err(anyhow::anyhow!("Item selector cannot be empty"));
+            issues.push(ValidationIssue::new("rules.item_selector", ValidationCode::Required, "Item selector cannot be empty")
+                .with_suggestion("Use a CSS selector matching one repeated item, e.g. \".product\""));
+        } else {
+            // Validate item selector
+            self.validate_selector(&rules.item_selector, &SelectorType::CSS);
         }
         
         if rules.fields.is_empty() {
-            return Err(anyhow::anyhow!("At least one field must be defined"));
+            issues.push(ValidationIssue::new("rules.fields", ValidationCode::Required, "At least one field must be defined"));
         }
         
-        // Validate item selector
-        self.validate_selector(&rules.item_selector, &SelectorType::CSS)?;
-        
         // Validate fields
-        let mut field_names = std::collections::HashSet::new();
-        for field in &rules.fields {
-            self.validate_field(field)?;
+        let mut field_names = HashSet::new();
+        for (i, field) in rules.fields.iter().enumerate() {
+            let path = format!("rules.fields[{}]", i);
+            self.validate_field(field, &path, issues);
             
-            if !field_names.insert(&field.name) {
-                return Err(anyhow::anyhow!("Duplicate field name: {}", field.name));
+            if !field.name.is_empty() && !field_names.insert(field.name.as_str()) {
+                issues.push(ValidationIssue::new(format!("{}.name", path), ValidationCode::Duplicate, format!("Duplicate field name: {}", field.name))
+                    .with_suggestion(format!("Rename it, e.g. \"{}_{}\"", field.name, i + 1)));
             }
         }
         
         // Validate pagination if provided
         if let Some(pagination) = &rules.pagination {
-            self.validate_pagination(pagination)?;
+            self.validate_pagination(pagination, issues);
         }
         
         // Validate filters if provided
         if let Some(filters) = &rules.filters {
-            for filter in filters {
-                self.validate_filter(filter, &field_names)?;
+            for (i, filter) in filters.iter().enumerate() {
+                self.validate_filter_expr(filter, &field_names, &format!("rules.filters[{}]", i), 1, issues);
             }
         }
         
-        Ok(())
+        if let MissingRequiredPolicy::FailAbove { max_percent } = rules.missing_required {
+            if !(0.0..=100.0).contains(&max_percent) {
+                issues.push(ValidationIssue::new("rules.missing_required.max_percent", ValidationCode::OutOfRange, "Max percent must be between 0 and 100"));
+            }
+        }
+        
+        if let Some(post_process) = &rules.post_process {
+            self.validate_post_process(post_process, issues);
+        }
     }
     
-    fn validate_field(&self, field: &Field) -> Result<()> {
+    fn validate_post_process(&self, post_process: &PostProcess, issues: &mut Vec<ValidationIssue>) {
+        match post_process {
+            PostProcess::Rhai { script, .. } if script.trim().is_empty() => {
+                issues.push(ValidationIssue::new("rules.post_process.script", ValidationCode::Required, "Script cannot be empty"));
+                return;
+            }
+            PostProcess::Wasm { module, .. } if module.as_os_str().is_empty() => {
+                issues.push(ValidationIssue::new("rules.post_process.module", ValidationCode::Required, "Module path cannot be empty"));
+                return;
+            }
+            _ => {}
+        }
+        
+        let limits = post_process.limits();
+        if limits.max_operations == 0 {
+            issues.push(ValidationIssue::new("rules.post_process.limits.max_operations", ValidationCode::OutOfRange, "Max operations must be greater than 0"));
+        }
+        if !(1..=1024).contains(&limits.max_memory_mb) {
+            issues.push(ValidationIssue::new("rules.post_process.limits.max_memory_mb", ValidationCode::OutOfRange, "Max memory must be between 1 and 1024 MB"));
+        }
+        
+        if let Err(e) = PostProcessor::new(post_process, &self.scripts) {
+            let path = match post_process {
+                PostProcess::Rhai { .. } => "rules.post_process.script",
+                PostProcess::Wasm { .. } => "rules.post_process.module",
+            };
+            issues.push(ValidationIssue::new(path, ValidationCode::InvalidScript, e.to_string()));
+        }
+    }
+    
+    fn validate_field(&self, field: &Field, path: &str, issues: &mut Vec<ValidationIssue>) {
         if field.name.is_empty() {
-            return Err(anyhow::anyhow!("Field name cannot be empty"));
+            issues.push(ValidationIssue::new(format!("{}.name", path), ValidationCode::Required, "Field name cannot be empty"));
         }
         
         if field.selector.is_empty() {
-            return Err(anyhow::anyhow!("Field selector cannot be empty"));
+            issues.push(ValidationIssue::new(format!("{}.selector", path), ValidationCode::Required, "Field selector cannot be empty")
+                .with_suggestion("Use a selector relative to the item, e.g. \".title\""));
+        } else {
+            // Validate selector
+            self.validate_selector(&field.selector, &field.selector_type);
         }
         
-        // Validate selector
-        self.validate_selector(&field.selector, &field.selector_type)?;
-        
         // Validate extraction method
-        self.validate_extraction_method(&field.extraction, &field.selector_type)?;
+        self.validate_extraction_method(&field.extraction, &format!("{}.extraction", path), issues);
         
         // Validate transforms if provided
         if let Some(transforms) = &field.transform {
-            for transform in transforms {
-                self.validate_transform(transform)?;
+            for (i, transform) in transforms.iter().enumerate() {
+                self.validate_transform(transform, &format!("{}.transform[{}]", path, i), issues);
             }
         }
-        
-        Ok(())
     }
     
-    fn validate_selector(&self, selector: &str, selector_type: &SelectorType) -> Result<()> {
-        if selector.is_empty() {
-            return Err(anyhow::anyhow!("Selector cannot be empty"));
-        }
-        
+    /// Selector checks only produce warnings; emptiness is checked by the caller
+    fn validate_selector(&self, selector: &str, selector_type: &SelectorType) {
         match selector_type {
             SelectorType::CSS => {
                 // Basic CSS selector validation
@@ -210,75 +412,64 @@ impl DSLValidator {
                 }
             }
         }
-        
-        Ok(())
     }
     
-    fn validate_extraction_method(
-        &self,
-        extraction: &ExtractionMethod,
-        _selector_type: &SelectorType,
-    ) -> Result<()> {
-        match extraction {
-            ExtractionMethod::Text => Ok(()),
-            ExtractionMethod::Html => Ok(()),
-            ExtractionMethod::Attribute { name } => {
-                if name.is_empty() {
-                    return Err(anyhow::anyhow!("Attribute name cannot be empty"));
-                }
-                if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-                    return Err(anyhow::anyhow!("Invalid attribute name: {}", name));
-                }
-                Ok(())
+    fn validate_extraction_method(&self, extraction: &ExtractionMethod, path: &str, issues: &mut Vec<ValidationIssue>) {
+        if let ExtractionMethod::Attribute { name } = extraction {
+            let path = format!("{}.attr.name", path);
+            if name.is_empty() {
+                issues.push(ValidationIssue::new(path, ValidationCode::Required, "Attribute name cannot be empty")
+                    .with_suggestion("Use extraction \"href\" or \"src\" for links and images"));
+            } else if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+                issues.push(ValidationIssue::new(path, ValidationCode::InvalidFormat, format!("Invalid attribute name: {}", name))
+                    .with_suggestion("Attribute names may only contain letters, digits, '-' and '_'"));
             }
-            ExtractionMethod::Href => Ok(()),
-            ExtractionMethod::Src => Ok(()),
         }
     }
     
-    fn validate_transform(&self, transform: &Transform) -> Result<()> {
+    fn validate_transform(&self, transform: &Transform, path: &str, issues: &mut Vec<ValidationIssue>) {
         match transform {
-            Transform::Trim => Ok(()),
-            Transform::Lowercase => Ok(()),
-            Transform::Uppercase => Ok(()),
             Transform::Regex { pattern, replacement: _ } => {
+                // Basic regex validation - just check it's not empty
                 if pattern.is_empty() {
-                    return Err(anyhow::anyhow!("Regex pattern cannot be empty"));
+                    issues.push(ValidationIssue::new(format!("{}.pattern", path), ValidationCode::Required, "Regex pattern cannot be empty"));
                 }
-                // Basic regex validation - just check it's not empty
-                Ok(())
             }
-            Transform::ParseNumber => Ok(()),
-            Transform::ParseDate { format } => {
-                if let Some(format) = format {
-                    if format.is_empty() {
-                        return Err(anyhow::anyhow!("Date format cannot be empty"));
-                    }
+            Transform::ParseDate { format: Some(format) } if format.is_empty() => {
+                issues.push(ValidationIssue::new(format!("{}.format", path), ValidationCode::Required, "Date format cannot be empty")
+                    .with_suggestion("Remove format to auto-detect, or use e.g. \"%Y-%m-%d\""));
+            }
+            Transform::Plugin { name, .. } => {
+                if crate::plugins::transform(name).is_none() {
+                    issues.push(unknown_plugin_issue(format!("{}.name", path), "transform", name));
                 }
-                Ok(())
             }
-            Transform::RemoveHtml => Ok(()),
-            Transform::ExtractDomain => Ok(()),
+            _ => {}
         }
     }
     
-    fn validate_pagination(&self, pagination: &Pagination) -> Result<()> {
+    fn validate_pagination(&self, pagination: &Pagination, issues: &mut Vec<ValidationIssue>) {
+        let path = "rules.pagination.method";
         match &pagination.method {
             PaginationMethod::Link { next_selector } => {
                 if next_selector.is_empty() {
-                    return Err(anyhow::anyhow!("Next selector cannot be empty for link pagination"));
+                    issues.push(ValidationIssue::new(format!("{}.next_selector", path), ValidationCode::Required, "Next selector cannot be empty for link pagination")
+                        .with_suggestion("Use a selector for the \"next page\" link, e.g. \"a[rel=next]\""));
+                } else {
+                    self.validate_selector(next_selector, &SelectorType::CSS);
                 }
-                self.validate_selector(next_selector, &SelectorType::CSS)?;
             }
             PaginationMethod::Button { button_selector } => {
                 if button_selector.is_empty() {
-                    return Err(anyhow::anyhow!("Button selector cannot be empty for button pagination"));
+                    issues.push(ValidationIssue::new(format!("{}.button_selector", path), ValidationCode::Required, "Button selector cannot be empty for button pagination"));
+                } else {
+                    self.validate_selector(button_selector, &SelectorType::CSS);
                 }
-                self.validate_selector(button_selector, &SelectorType::CSS)?;
             }
             PaginationMethod::Scroll { scroll_pause_ms } => {
                 if *scroll_pause_ms == 0 {
-                    return Err(anyhow::anyhow!("Scroll pause time must be greater than 0"));
+                    issues.push(ValidationIssue::new(format!("{}.scroll_pause_ms", path), ValidationCode::OutOfRange, "Scroll pause time must be greater than 0")
+                        .with_suggestion("A pause of 1000-2000 ms works for most pages"));
                 }
                 if *scroll_pause_ms > 10000 {
                     warn!("Very long scroll pause time: {}ms", scroll_pause_ms);
@@ -286,12 +477,12 @@ impl DSLValidator {
             }
             PaginationMethod::UrlPattern { pattern, start, end } => {
                 if pattern.is_empty() {
-                    return Err(anyhow::anyhow!("URL pattern cannot be empty"));
+                    issues.push(ValidationIssue::new(format!("{}.pattern", path), ValidationCode::Required, "URL pattern cannot be empty"));
                 }
                 if start >= end {
-                    return Err(anyhow::anyhow!("Start value must be less than end value"));
-                }
-                if end - start > 1000 {
+                    issues.push(ValidationIssue::new(format!("{}.end", path), ValidationCode::OutOfRange, "Start value must be less than end value")
+                        .with_suggestion(format!("Set end to at least {}", start + 1)));
+                } else if end - start > 1000 {
                     warn!("Very large pagination range: {} to {}", start, end);
                 }
             }
@@ -299,13 +490,13 @@ impl DSLValidator {
         
         // Validate selector if provided
         if let Some(selector) = &pagination.selector {
-            self.validate_selector(selector, &SelectorType::CSS)?;
+            self.validate_selector(selector, &SelectorType::CSS);
         }
         
         // Validate max_pages if provided
         if let Some(max_pages) = pagination.max_pages {
             if max_pages == 0 {
-                return Err(anyhow::anyhow!("Max pages must be greater than 0"));
+                issues.push(ValidationIssue::new("rules.pagination.max_pages", ValidationCode::OutOfRange, "Max pages must be greater than 0"));
             }
             if max_pages > 1000 {
                 warn!("Very high max_pages value: {}. This may cause performance issues.", max_pages);
@@ -318,97 +509,145 @@ impl DSLValidator {
                 warn!("Very long wait time: {}ms", wait_time);
             }
         }
-        
-        Ok(())
     }
     
-    fn validate_filter(
+    fn validate_filter_expr(
         &self,
-        filter: &Filter,
-        field_names: &std::collections::HashSet<&String>,
-    ) -> Result<()> {
-        if !field_names.contains(&filter.field) {
-            return Err(anyhow::anyhow!("Filter references unknown field: {}", filter.field));
-        }
-        
-        match &filter.condition {
-            FilterCondition::Contains { value } => {
-                if value.is_empty() {
-                    return Err(anyhow::anyhow!("Contains filter value cannot be empty"));
-                }
-            }
-            FilterCondition::NotContains { value } => {
-                if value.is_empty() {
-                    return Err(anyhow::anyhow!("NotContains filter value cannot be empty"));
-                }
-            }
-            FilterCondition::Equals { value } => {
-                if value.is_empty() {
-                    return Err(anyhow::anyhow!("Equals filter value cannot be empty"));
+        expr: &FilterExpr,
+        field_names: &HashSet<&str>,
+        path: &str,
+        depth: usize,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        if depth > MAX_FILTER_DEPTH {
+            issues.push(ValidationIssue::new(path, ValidationCode::TooDeep, format!("Filter groups are nested too deeply (max {} levels)", MAX_FILTER_DEPTH))
+                .with_suggestion("Flatten nested groups of the same kind"));
+            return;
+        }
+        
+        match expr {
+            FilterExpr::All { all: exprs } | FilterExpr::Any { any: exprs } => {
+                let key = if matches!(expr, FilterExpr::All { .. }) { "all" } else { "any" };
+                if exprs.is_empty() {
+                    issues.push(ValidationIssue::new(format!("{}.{}", path, key), ValidationCode::Required, "Filter group cannot be empty")
+                        .with_suggestion("Add at least one condition or remove the group"));
                 }
-            }
-            FilterCondition::NotEquals { value } => {
-                if value.is_empty() {
-                    return Err(anyhow::anyhow!("NotEquals filter value cannot be empty"));
+                for (i, nested) in exprs.iter().enumerate() {
+                    self.validate_filter_expr(nested, field_names, &format!("{}.{}[{}]", path, key, i), depth + 1, issues);
                 }
             }
-            FilterCondition::Regex { pattern } => {
-                if pattern.is_empty() {
-                    return Err(anyhow::anyhow!("Regex pattern cannot be empty"));
-                }
-                // Basic regex validation - just check it's not empty
+            FilterExpr::Not { not } => {
+                self.validate_filter_expr(not, field_names, &format!("{}.not", path), depth + 1, issues)
             }
+            FilterExpr::Condition(filter) => self.validate_filter(filter, field_names, path, issues),
+        }
+    }
+    
+    fn validate_filter(
+        &self,
+        filter: &Filter,
+        field_names: &HashSet<&str>,
+        path: &str,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        if !field_names.contains(filter.field.as_str()) {
+            issues.push(unknown_field_issue(
+                format!("{}.field", path),
+                format!("Filter references unknown field: {}", filter.field),
+                &filter.field,
+                field_names.iter().copied(),
+            ));
+        }
+        
+        let path = format!("{}.condition", path);
+        let (name, empty) = match &filter.condition {
+            FilterCondition::Contains { value } => ("Contains filter value", value.is_empty()),
+            FilterCondition::NotContains { value } => ("NotContains filter value", value.is_empty()),
+            FilterCondition::Equals { value } => ("Equals filter value", value.is_empty()),
+            FilterCondition::NotEquals { value } => ("NotEquals filter value", value.is_empty()),
+            // Basic regex validation - just check it's not empty
+            FilterCondition::Regex { pattern } => ("Regex pattern", pattern.is_empty()),
             FilterCondition::LengthMin { min } => {
                 if *min == 0 {
-                    return Err(anyhow::anyhow!("Length minimum must be greater than 0"));
+                    issues.push(ValidationIssue::new(format!("{}.min", path), ValidationCode::OutOfRange, "Length minimum must be greater than 0")
+                        .with_suggestion("Use a not_empty condition instead"));
                 }
+                return;
             }
             FilterCondition::LengthMax { max } => {
                 if *max == 0 {
-                    return Err(anyhow::anyhow!("Length maximum must be greater than 0"));
+                    issues.push(ValidationIssue::new(format!("{}.max", path), ValidationCode::OutOfRange, "Length maximum must be greater than 0"));
                 }
+                return;
             }
-            FilterCondition::NotEmpty => {}
-        }
+            FilterCondition::NumberMin { min: bound } | FilterCondition::NumberMax { max: bound } => {
+                if !bound.is_finite() {
+                    issues.push(ValidationIssue::new(path, ValidationCode::OutOfRange, "Number bound must be a finite number"));
+                }
+                return;
+            }
+            FilterCondition::NotEmpty => return,
+            FilterCondition::Plugin { name, .. } => {
+                if crate::plugins::filter(name).is_none() {
+                    issues.push(unknown_plugin_issue(format!("{}.name", path), "filter", name));
+                }
+                return;
+            }
+        };
         
-        Ok(())
+        if empty {
+            let key = if matches!(filter.condition, FilterCondition::Regex { .. }) { "pattern" } else { "value" };
+            issues.push(ValidationIssue::new(format!("{}.{}", path, key), ValidationCode::Required, format!("{} cannot be empty", name))
+                .with_suggestion("Use a not_empty condition to only require a value"));
+        }
     }
     
-    fn validate_anti_blocking(&self, anti_blocking: &AntiBlocking) -> Result<()> {
+    fn validate_anti_blocking(&self, anti_blocking: &AntiBlocking, issues: &mut Vec<ValidationIssue>) {
         // Validate delay configuration
-        if anti_blocking.randomized_delays.min_ms > anti_blocking.randomized_delays.max_ms {
-            return Err(anyhow::anyhow!("Min delay cannot be greater than max delay"));
+        let delays = &anti_blocking.randomized_delays;
+        if delays.min_ms > delays.max_ms {
+            issues.push(ValidationIssue::new("anti_blocking.randomized_delays.min_ms", ValidationCode::OutOfRange, "Min delay cannot be greater than max delay")
+                .with_suggestion(format!("Swap the values: min_ms {} and max_ms {}", delays.max_ms, delays.min_ms)));
         }
         
-        if anti_blocking.randomized_delays.min_ms == 0 && anti_blocking.randomized_delays.max_ms == 0 {
+        if delays.min_ms == 0 && delays.max_ms == 0 {
             warn!("No delays configured - this may trigger rate limiting");
         }
         
-        if anti_blocking.randomized_delays.max_ms > 30000 {
-            warn!("Very long delay configured: {}ms", anti_blocking.randomized_delays.max_ms);
+        if delays.max_ms > 30000 {
+            warn!("Very long delay configured: {}ms", delays.max_ms);
         }
         
         // Validate proxy configuration if provided
         if let Some(proxy) = &anti_blocking.proxy {
             if proxy.enabled && proxy.proxies.is_empty() {
-                return Err(anyhow::anyhow!("Proxy is enabled but no proxies are configured"));
+                issues.push(ValidationIssue::new("anti_blocking.proxy.proxies", ValidationCode::Required, "Proxy is enabled but no proxies are configured")
+                    .with_suggestion("Add a proxy URL or set proxy.enabled to false"));
             }
             
-            for proxy_url in &proxy.proxies {
+            for (i, proxy_url) in proxy.proxies.iter().enumerate() {
                 if !proxy_url.starts_with("http://") && !proxy_url.starts_with("https://") {
-                    return Err(anyhow::anyhow!("Invalid proxy URL format: {}", proxy_url));
+                    issues.push(ValidationIssue::new(format!("anti_blocking.proxy.proxies[{}]", i), ValidationCode::InvalidFormat, format!("Invalid proxy URL format: {}", proxy_url))
+                        .with_suggestion(format!("Use http://{}", proxy_url)));
                 }
             }
         }
         
+        if anti_blocking.user_agent.as_deref().is_some_and(|user_agent| user_agent.trim().is_empty()) {
+            issues.push(ValidationIssue::new("anti_blocking.user_agent", ValidationCode::Required, "User agent cannot be empty")
+                .with_suggestion("Remove user_agent to use the client profile or rotation"));
+        }
+        
         // Validate headers if provided
         if let Some(headers) = &anti_blocking.headers {
             for (name, value) in headers {
+                let path = format!("anti_blocking.headers.{}", name);
                 if name.is_empty() {
-                    return Err(anyhow::anyhow!("Header name cannot be empty"));
+                    issues.push(ValidationIssue::new(path.clone(), ValidationCode::Required, "Header name cannot be empty"));
                 }
                 if value.is_empty() {
-                    return Err(anyhow::anyhow!("Header value cannot be empty"));
+                    issues.push(ValidationIssue::new(path, ValidationCode::Required, "Header value cannot be empty")
+                        .with_suggestion("Remove the header instead of leaving it empty"));
                 }
                 
                 // Check for potentially dangerous headers
@@ -418,28 +657,19 @@ impl DSLValidator {
                 }
             }
         }
-        
-        Ok(())
     }
     
-    fn validate_output(&self, output: &Output) -> Result<()> {
+    fn validate_output(&self, output: &Output, issues: &mut Vec<ValidationIssue>) {
         if output.format.is_empty() {
-            return Err(anyhow::anyhow!("At least one output format must be specified"));
-        }
-        
-        for format in &output.format {
-            match format {
-                OutputFormat::CSV => {}
-                OutputFormat::JSON => {}
-                OutputFormat::XLSX => {}
-                OutputFormat::Parquet => {}
-            }
+            issues.push(ValidationIssue::new("output.format", ValidationCode::Required, "At least one output format must be specified")
+                .with_suggestion("Add \"csv\" or \"json\""));
         }
         
         // Validate limit if provided
         if let Some(limit) = output.limit {
             if limit == 0 {
-                return Err(anyhow::anyhow!("Output limit must be greater than 0"));
+                issues.push(ValidationIssue::new("output.limit", ValidationCode::OutOfRange, "Output limit must be greater than 0")
+                    .with_suggestion("Remove limit to keep all rows"));
             }
             if limit > 1000000 {
                 warn!("Very high output limit: {}. This may cause memory issues.", limit);
@@ -449,62 +679,79 @@ impl DSLValidator {
         // Validate dedupe keys if provided
         if let Some(dedupe_keys) = &output.dedupe_keys {
             if dedupe_keys.is_empty() {
-                return Err(anyhow::anyhow!("Dedupe keys cannot be empty"));
+                issues.push(ValidationIssue::new("output.dedupe_keys", ValidationCode::Required, "Dedupe keys cannot be empty")
+                    .with_suggestion("Remove dedupe_keys or list at least one field"));
+            }
+        }
+        
+        if let Some(hash_fields) = &output.hash_fields {
+            if hash_fields.is_empty() {
+                issues.push(ValidationIssue::new("output.hash_fields", ValidationCode::Required, "Hash fields cannot be empty")
+                    .with_suggestion("Remove hash_fields to hash every field"));
+            }
+        }
+        
+        if let Some(template) = &output.filename_template {
+            if let Err(e) = crate::export::filename::validate_template(template) {
+                issues.push(ValidationIssue::new("output.filename_template", ValidationCode::InvalidFormat, e.to_string())
+                    .with_suggestion("Use placeholders such as {plan_name}, {date}, {run_number} and {ext}"));
             }
         }
         
         // Validate sort configuration if provided
         if let Some(sort_by) = &output.sort_by {
             if sort_by.is_empty() {
-                return Err(anyhow::anyhow!("Sort field cannot be empty"));
+                issues.push(ValidationIssue::new("output.sort_by", ValidationCode::Required, "Sort field cannot be empty"));
             }
         }
-        
-        Ok(())
     }
     
-    fn validate_cross_references(&self, plan: &ScrapePlan) -> Result<()> {
-        // Check if all referenced fields in filters exist
-        if let Some(filters) = &plan.rules.filters {
-            let field_names: std::collections::HashSet<String> = plan.rules.fields
-                .iter()
-                .map(|f| f.name.clone())
-                .collect();
-            
-            for filter in filters {
-                if !field_names.contains(&filter.field) {
-                    return Err(anyhow::anyhow!("Filter references unknown field: {}", filter.field));
-                }
-            }
-        }
+    /// Check references to fields from other sections (filters are checked in `validate_rules`)
+    fn validate_cross_references(&self, plan: &ScrapePlan, issues: &mut Vec<ValidationIssue>) {
+        let field_names: HashSet<&str> = plan.rules.fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
         
         // Check if sort field exists in output configuration
         if let Some(sort_by) = &plan.output.sort_by {
-            let field_names: std::collections::HashSet<String> = plan.rules.fields
-                .iter()
-                .map(|f| f.name.clone())
-                .collect();
-            
-            if !field_names.contains(sort_by) {
-                return Err(anyhow::anyhow!("Sort field '{}' does not exist in field definitions", sort_by));
+            if !sort_by.is_empty() && !field_names.contains(sort_by.as_str()) {
+                issues.push(unknown_field_issue(
+                    "output.sort_by",
+                    format!("Sort field '{}' does not exist in field definitions", sort_by),
+                    sort_by,
+                    field_names.iter().copied(),
+                ));
             }
         }
         
         // Check if dedupe keys exist in field definitions
         if let Some(dedupe_keys) = &plan.output.dedupe_keys {
-            let field_names: std::collections::HashSet<String> = plan.rules.fields
-                .iter()
-                .map(|f| f.name.clone())
-                .collect();
-            
-            for key in dedupe_keys {
-                if !field_names.contains(key) {
-                    return Err(anyhow::anyhow!("Dedupe key '{}' does not exist in field definitions", key));
+            for (i, key) in dedupe_keys.iter().enumerate() {
+                if !field_names.contains(key.as_str()) {
+                    issues.push(unknown_field_issue(
+                        format!("output.dedupe_keys[{}]", i),
+                        format!("Dedupe key '{}' does not exist in field definitions", key),
+                        key,
+                        field_names.iter().copied(),
+                    ));
                 }
             }
         }
         
-        Ok(())
+        // Check if hash fields exist in field definitions
+        if let Some(hash_fields) = &plan.output.hash_fields {
+            for (i, name) in hash_fields.iter().enumerate() {
+                if !field_names.contains(name.as_str()) {
+                    issues.push(unknown_field_issue(
+                        format!("output.hash_fields[{}]", i),
+                        format!("Hash field '{}' does not exist in field definitions", name),
+                        name,
+                        field_names.iter().copied(),
+                    ));
+                }
+            }
+        }
     }
     
     /// Validate DSL for security concerns
@@ -539,3 +786,88 @@ impl Default for DSLValidator {
         Self::new()
     }
 }
+
+/// Build an unknown-field issue, suggesting the closest defined field name
+fn unknown_field_issue<'a>(
+    path: impl Into<String>,
+    message: String,
+    name: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> ValidationIssue {
+    let issue = ValidationIssue::new(path, ValidationCode::UnknownField, message);
+    
+    let closest = candidates
+        .map(|candidate| (edit_distance(&name.to_lowercase(), &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance);
+    
+    match closest {
+        Some((_, candidate)) => issue.with_suggestion(format!("Did you mean '{}'?", candidate)),
+        None => issue.with_suggestion("Reference one of the names defined in rules.fields"),
+    }
+}
+
+/// Issue for a plugin name no loaded plugin registered
+fn unknown_plugin_issue(path: String, kind: &str, name: &str) -> ValidationIssue {
+    let known: Vec<String> = crate::plugins::loaded().into_iter()
+        .flat_map(|plugin| if kind == "filter" { plugin.filters } else { plugin.transforms })
+        .collect();
+    let issue = ValidationIssue::new(path, ValidationCode::UnknownPlugin, format!("No {} plugin named '{}' is loaded", kind, name));
+    if known.is_empty() {
+        issue.with_suggestion("Put the plugin in the plugins directory and restart")
+    } else {
+        issue.with_suggestion(format!("Loaded {} plugins: {}", kind, known.join(", ")))
+    }
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+    
+    #[test]
+    fn test_valid_examples_have_no_issues() {
+        let validator = DSLValidator::new();
+        assert!(validator.validate_detailed(&DSLExamples::ecommerce_products()).is_empty());
+        assert!(validator.validate_detailed(&DSLExamples::news_articles()).is_empty());
+    }
+    
+    #[test]
+    fn test_issues_carry_path_code_and_suggestion() {
+        let validator = DSLValidator::new();
+        let mut plan = DSLExamples::ecommerce_products();
+        plan.rules.fields[1].selector.clear();
+        let first_field = plan.rules.fields[0].name.clone();
+        plan.output.sort_by = Some(format!("{}x", first_field));
+        
+        let issues = validator.validate_detailed(&plan);
+        
+        let selector_issue = issues.iter().find(|i| i.path == "rules.fields[1].selector").unwrap();
+        assert_eq!(selector_issue.code, ValidationCode::Required);
+        
+        let sort_issue = issues.iter().find(|i| i.path == "output.sort_by").unwrap();
+        assert_eq!(sort_issue.code, ValidationCode::UnknownField);
+        assert_eq!(sort_issue.suggestion.as_deref(), Some(format!("Did you mean '{}'?", first_field).as_str()));
+        
+        let err = validator.validate(&plan).unwrap_err();
+        let errors = err.downcast_ref::<ValidationErrors>().unwrap();
+        assert_eq!(errors.issues.len(), issues.len());
+    }
+}
@@ -175,6 +175,11 @@ impl WinScrapeError {
         Self::Job { message: message.into() }
     }
     
+    /// Create a job not found error
+    pub fn job_not_found(job_id: impl Into<String>) -> Self {
+        Self::JobNotFound { job_id: job_id.into() }
+    }
+    
     /// Create an internal error
     pub fn internal(message: impl Into<String>) -> Self {
         Self::Internal { message: message.into() }
@@ -233,6 +238,219 @@ impl WinScrapeError {
             _ => None,
         }
     }
+    
+    /// Stable code of the error, for the troubleshooting catalog
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Configuration { .. } => ErrorCode::Configuration,
+            Self::InvalidConfig { .. } => ErrorCode::InvalidConfig,
+            Self::Database { .. } => ErrorCode::Database,
+            Self::DatabaseConnection { .. } => ErrorCode::DatabaseConnection,
+            Self::Migration { .. } => ErrorCode::Migration,
+            Self::Network { .. } => ErrorCode::Network,
+            Self::HttpRequest { .. } => ErrorCode::HttpStatus,
+            Self::Timeout { .. } => ErrorCode::Timeout,
+            Self::Scraping { .. } => ErrorCode::Scraping,
+            Self::RobotsViolation { .. } => ErrorCode::RobotsDisallowed,
+            Self::RateLimit { .. } => ErrorCode::RateLimited,
+            Self::SelectorFailed { .. } => ErrorCode::SelectorFailed,
+            Self::DSLValidation { .. } | Self::InvalidDSL { .. } => ErrorCode::DslValidation,
+            Self::DSLParsing { .. } => ErrorCode::DslParsing,
+            Self::LLM { .. } | Self::TextGeneration { .. } => ErrorCode::Llm,
+            Self::ModelLoad { .. } => ErrorCode::ModelLoad,
+            Self::Security { .. } | Self::SuspiciousActivity { .. } => ErrorCode::Security,
+            Self::InputValidation { .. } => ErrorCode::InputRejected,
+            Self::DomainBlocked { .. } => ErrorCode::DomainBlocked,
+            Self::Export { .. } => ErrorCode::Export,
+            Self::FileWrite { .. } => ErrorCode::FileWrite,
+            Self::UnsupportedFormat { .. } => ErrorCode::UnsupportedFormat,
+            Self::Job { .. } => ErrorCode::Job,
+            Self::JobNotFound { .. } => ErrorCode::JobNotFound,
+            Self::JobAlreadyRunning { .. } => ErrorCode::JobAlreadyRunning,
+            Self::JobQueueFull => ErrorCode::JobQueueFull,
+            Self::System { .. } | Self::ResourceExhausted { .. } | Self::UI { .. } | Self::ThemeLoad { .. } => ErrorCode::System,
+            Self::FileSystem { .. } => ErrorCode::FileSystem,
+            Self::PermissionDenied { .. } => ErrorCode::PermissionDenied,
+            Self::Cancelled => ErrorCode::Cancelled,
+            Self::OperationTimeout => ErrorCode::Timeout,
+            Self::Internal { .. } | Self::InvalidState { .. } => ErrorCode::Internal,
+        }
+    }
+}
+
+/// Stable error codes shown to users and returned by the API
+///
+/// The thousands digit is the area: 1 network, 2 plans, 3 security, 4 scraping,
+/// 5 language model, 6 database, 7 export, 8 jobs and 9 configuration and system.
+/// Codes are never reused, so they can be searched for in issue reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    Network,
+    HttpStatus,
+    Timeout,
+    RateLimited,
+    DslValidation,
+    DslParsing,
+    Security,
+    InputRejected,
+    DomainBlocked,
+    UrlRejected,
+    Scraping,
+    RobotsDisallowed,
+    SelectorFailed,
+    NoResults,
+    Llm,
+    ModelLoad,
+    Database,
+    DatabaseConnection,
+    Migration,
+    Export,
+    FileWrite,
+    UnsupportedFormat,
+    Job,
+    JobNotFound,
+    JobAlreadyRunning,
+    JobQueueFull,
+    Configuration,
+    InvalidConfig,
+    System,
+    FileSystem,
+    PermissionDenied,
+    Cancelled,
+    Internal,
+}
+
+impl ErrorCode {
+    /// Every code, in catalog order
+    pub const ALL: [ErrorCode; 33] = [
+        Self::Network, Self::HttpStatus, Self::Timeout, Self::RateLimited,
+        Self::DslValidation, Self::DslParsing,
+        Self::Security, Self::InputRejected, Self::DomainBlocked, Self::UrlRejected,
+        Self::Scraping, Self::RobotsDisallowed, Self::SelectorFailed, Self::NoResults,
+        Self::Llm, Self::ModelLoad,
+        Self::Database, Self::DatabaseConnection, Self::Migration,
+        Self::Export, Self::FileWrite, Self::UnsupportedFormat,
+        Self::Job, Self::JobNotFound, Self::JobAlreadyRunning, Self::JobQueueFull,
+        Self::Configuration, Self::InvalidConfig, Self::System, Self::FileSystem, Self::PermissionDenied,
+        Self::Cancelled, Self::Internal,
+    ];
+    
+    /// The code as shown, e.g. `WSS-1003`
+    pub fn as_str(&self) -> &'static str {
+        self.entry().0
+    }
+    
+    /// What usually fixes the error
+    pub fn hint(&self) -> &'static str {
+        self.entry().1
+    }
+    
+    fn entry(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::Network => ("WSS-1001", "Check your internet connection and proxy settings, then try again."),
+            Self::HttpStatus => ("WSS-1002", "The site answered with an error status. Open the URL in a browser to check it is reachable, and slow the plan down if it answers 403 or 429."),
+            Self::Timeout => ("WSS-1003", "The site took too long to answer. Raise the request timeout in Settings or try again later."),
+            Self::RateLimited => ("WSS-1004", "The site is limiting requests. Pick a gentler politeness preset or raise the delays in the plan."),
+            Self::DslValidation => ("WSS-2001", "The plan has problems. Open it in the plan editor, where each issue is shown with a suggested fix."),
+            Self::DslParsing => ("WSS-2002", "The plan is not valid YAML. Check the indentation around the line mentioned, or generate the plan again."),
+            Self::Security => ("WSS-3001", "The request was refused by a security check. See the Security tab for details."),
+            Self::InputRejected => ("WSS-3002", "The text contains characters or patterns that are not accepted. Rephrase the request without code or markup."),
+            Self::DomainBlocked => ("WSS-3003", "The site is on the blocked domains list. Remove it in Settings > Security if scraping it is intended."),
+            Self::UrlRejected => ("WSS-3004", "Only public http and https addresses can be scraped. Check the start URLs of the plan."),
+            Self::Scraping => ("WSS-4001", "The scrape failed. Download the job's log file for the details and attach it when reporting the problem."),
+            Self::RobotsDisallowed => ("WSS-4002", "The site's robots.txt does not allow these pages. Choose other pages, or turn off robots.txt checks only if you have permission."),
+            Self::SelectorFailed => ("WSS-4003", "The selectors no longer match the page. Re-pick them with the selector picker or let the plan be repaired."),
+            Self::NoResults => ("WSS-4004", "No items were found. Check the item selector against the page, or whether the page needs JavaScript."),
+            Self::Llm => ("WSS-5001", "The language model could not produce an answer. Try rephrasing the request or check the model settings."),
+            Self::ModelLoad => ("WSS-5002", "The model file could not be loaded. Check the model path in Settings and that the file is complete."),
+            Self::Database => ("WSS-6001", "A database operation failed. Make sure the disk is not full, then restart the application."),
+            Self::DatabaseConnection => ("WSS-6002", "The database could not be opened. Close other copies of the application and check the database path and its encryption key."),
+            Self::Migration => ("WSS-6003", "The database could not be upgraded. Restore the latest backup or report the problem with the log files."),
+            Self::Export => ("WSS-7001", "The export failed. Check the output folder and the export settings."),
+            Self::FileWrite => ("WSS-7002", "The file could not be written. Check that the folder exists, is writable and the file is not open elsewhere."),
+            Self::UnsupportedFormat => ("WSS-7003", "This export format is not available. Pick CSV, JSON, Excel, Parquet or SQLite."),
+            Self::Job => ("WSS-8001", "The job could not be run. Download the job's log file for the details."),
+            Self::JobNotFound => ("WSS-8002", "The job does not exist. It may have been deleted; refresh the job list."),
+            Self::JobAlreadyRunning => ("WSS-8003", "The job is already running. Wait for it to finish or stop it first."),
+            Self::JobQueueFull => ("WSS-8004", "Too many jobs are queued. Wait for some to finish before starting more."),
+            Self::Configuration => ("WSS-9001", "A setting is invalid. Review Settings or reset them to the defaults."),
+            Self::InvalidConfig => ("WSS-9002", "The configuration file could not be read. Fix or delete it to start with the defaults."),
+            Self::System => ("WSS-9101", "The system refused the operation. Free up memory or disk space and try again."),
+            Self::FileSystem => ("WSS-9102", "A file could not be read or written. Check that the path exists and is accessible."),
+            Self::PermissionDenied => ("WSS-9103", "Access was denied. Run with an account that may use this file or resource."),
+            Self::Cancelled => ("WSS-9201", "The operation was cancelled. Start it again when ready."),
+            Self::Internal => ("WSS-9901", "Something unexpected went wrong. Please report it with the job's log file attached."),
+        }
+    }
+    
+    /// The code with this text, e.g. `WSS-1003`, ignoring case
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|known| known.as_str().eq_ignore_ascii_case(code.trim()))
+    }
+    
+    /// Code of an error, from the first of its causes with a known type
+    ///
+    /// Errors carrying only a message are `Internal`; return a typed error,
+    /// e.g. a [`WinScrapeError`] variant, to give one a more specific code.
+    pub fn of(error: &anyhow::Error) -> Self {
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<WinScrapeError>() {
+                return e.code();
+            }
+            if let Some(e) = cause.downcast_ref::<ContextualError>() {
+                return e.error.code();
+            }
+            if let Some(e) = cause.downcast_ref::<crate::security::SecurityError>() {
+                use crate::security::SecurityError;
+                return match e {
+                    SecurityError::InputValidation(_) | SecurityError::SelectorValidation(_) | SecurityError::HeaderValidation(_) => Self::InputRejected,
+                    SecurityError::UrlValidation(_) => Self::UrlRejected,
+                    SecurityError::DomainBlocked(_) => Self::DomainBlocked,
+                    SecurityError::OutputFiltering(_) => Self::Security,
+                };
+            }
+            if cause.is::<crate::dsl::validator::ValidationErrors>() {
+                return Self::DslValidation;
+            }
+            if cause.is::<crate::dsl::DSLError>() || cause.is::<serde_yaml::Error>() {
+                return Self::DslParsing;
+            }
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                return if e.is_timeout() {
+                    Self::Timeout
+                } else if e.status().is_some_and(|status| status.as_u16() == 429) {
+                    Self::RateLimited
+                } else if e.is_status() {
+                    Self::HttpStatus
+                } else {
+                    Self::Network
+                };
+            }
+            if cause.is::<rusqlite::Error>() {
+                return Self::Database;
+            }
+            if let Some(e) = cause.downcast_ref::<std::io::Error>() {
+                return match e.kind() {
+                    std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+                    std::io::ErrorKind::TimedOut => Self::Timeout,
+                    _ => Self::FileSystem,
+                };
+            }
+        }
+        Self::Internal
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An error as shown to users: its message, code and what to do about it
+pub fn describe(error: &anyhow::Error) -> String {
+    let code = ErrorCode::of(error);
+    format!("{} ({}: {})", error, code, code.hint())
 }
 
 /// Result type alias for WinScrape Studio
@@ -306,7 +524,7 @@ impl ContextualError {
 
 impl fmt::Display for ContextualError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} in {}::{}", self.error, self.context.component, self.context.operation)?;
+        write!(f, "[{}] {} in {}::{}", self.error.code(), self.error, self.context.component, self.context.operation)?;
         
         if !self.chain.is_empty() {
             write!(f, " (chain: {})", self.chain.join(" -> "))?;
@@ -399,5 +617,25 @@ mod tests {
         
         assert!(error_string.contains("scraper::extract_items"));
         assert!(error_string.contains("req-123"));
+        assert!(error_string.starts_with("[WSS-4001]"));
+    }
+    
+    #[test]
+    fn test_error_codes_are_unique_and_recognised() {
+        let codes: std::collections::HashSet<&str> = ErrorCode::ALL.iter().map(ErrorCode::as_str).collect();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+        assert_eq!(ErrorCode::from_code("wss-1003"), Some(ErrorCode::Timeout));
+        
+        let wrapped = anyhow::Error::new(WinScrapeError::RateLimit { domain: "example.com".to_string() })
+            .context("Scraping failed");
+        assert_eq!(ErrorCode::of(&wrapped), ErrorCode::RateLimited);
+        let disallowed = anyhow::Error::new(WinScrapeError::RobotsViolation { url: "https://example.com/".to_string() })
+            .context("Robots.txt disallows all 1 target URLs");
+        assert_eq!(ErrorCode::of(&disallowed), ErrorCode::RobotsDisallowed);
+        assert_eq!(ErrorCode::of(&WinScrapeError::job_not_found("job-1").into()), ErrorCode::JobNotFound);
+        
+        // Messages alone are not guessed at
+        assert_eq!(ErrorCode::of(&anyhow::anyhow!("export of the selector timed out")), ErrorCode::Internal);
+        assert_eq!(ErrorCode::of(&anyhow::anyhow!("something odd")), ErrorCode::Internal);
     }
 }
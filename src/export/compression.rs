@@ -0,0 +1,193 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use crate::config::CompressionCodec;
+
+/// Name of the manifest inside an export archive
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// Brotli window size, as log2 of bytes
+const BROTLI_WINDOW: u32 = 22;
+
+/// Levels a codec accepts
+pub fn levels(codec: CompressionCodec) -> RangeInclusive<u32> {
+    match codec {
+        CompressionCodec::Gzip => 0..=9,
+        CompressionCodec::Zstd => 1..=22,
+        CompressionCodec::Brotli => 0..=11,
+    }
+}
+
+/// Level used when none is configured
+fn default_level(codec: CompressionCodec) -> u32 {
+    match codec {
+        CompressionCodec::Gzip => 6,
+        CompressionCodec::Zstd => 3,
+        CompressionCodec::Brotli => 6,
+    }
+}
+
+/// File extension a codec adds, without the dot
+pub fn extension(codec: CompressionCodec) -> &'static str {
+    match codec {
+        CompressionCodec::Gzip => "gz",
+        CompressionCodec::Zstd => "zst",
+        CompressionCodec::Brotli => "br",
+    }
+}
+
+/// Compress `path` into a sibling file with the codec's extension, returning its path
+///
+/// The file is streamed, so exports of any size can be compressed. Levels
+/// outside the codec's range are clamped to it.
+pub(super) fn compress(path: &Path, codec: CompressionCodec, level: Option<u32>) -> Result<PathBuf> {
+    let range = levels(codec);
+    let level = level.unwrap_or_else(|| default_level(codec)).clamp(*range.start(), *range.end());
+    let mut target = path.as_os_str().to_owned();
+    target.push(".");
+    target.push(extension(codec));
+    let target = PathBuf::from(target);
+
+    let mut input = BufReader::new(File::open(path)?);
+    let output = BufWriter::new(File::create(&target)?);
+    let output = match codec {
+        CompressionCodec::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::new(level));
+            std::io::copy(&mut input, &mut encoder)?;
+            encoder.finish()?
+        }
+        CompressionCodec::Zstd => {
+            let mut encoder = zstd::Encoder::new(output, level as i32)?;
+            std::io::copy(&mut input, &mut encoder)?;
+            encoder.finish()?
+        }
+        CompressionCodec::Brotli => {
+            let mut encoder = brotli::CompressorWriter::new(output, 64 * 1024, level, BROTLI_WINDOW);
+            std::io::copy(&mut input, &mut encoder)?;
+            encoder.flush()?;
+            encoder.into_inner()
+        }
+    };
+    output.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    Ok(target)
+}
+
+/// Files of a multi-file export; archives store it as `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub created_at: DateTime<Utc>,
+    pub files: Vec<ArchiveEntry>,
+}
+
+/// One file of a multi-file export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub name: String,
+    pub format: String,
+    pub record_count: usize,
+    pub size_bytes: u64,
+    /// Hex SHA-256 of the file before it was archived
+    pub sha256: String,
+}
+
+impl ArchiveEntry {
+    /// Entry for a file on disk, hashing its contents
+    pub(super) fn for_file(path: &Path, format: String, record_count: usize) -> Result<Self> {
+        let mut input = BufReader::new(File::open(path)?);
+        let mut hasher = Sha256::new();
+        let size_bytes = std::io::copy(&mut input, &mut hasher)?;
+        Ok(Self {
+            name: path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            format,
+            record_count,
+            size_bytes,
+            sha256: hex::encode(hasher.finalize()),
+        })
+    }
+}
+
+/// Write `files` and their manifest into a zip archive at `archive_path`
+///
+/// Entries are named after the files; the entries given only need their
+/// format and record count filled in.
+pub(super) fn write_zip(archive_path: &Path, files: &[(PathBuf, ArchiveEntry)]) -> Result<ArchiveManifest> {
+    let mut zip = zip::ZipWriter::new(BufWriter::new(File::create(archive_path)?));
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut manifest = ArchiveManifest { created_at: Utc::now(), files: Vec::with_capacity(files.len()) };
+    for (path, entry) in files {
+        let name = path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| anyhow::anyhow!("Cannot archive {}: not a file", path.display()))?;
+        if name == MANIFEST_NAME || manifest.files.iter().any(|file| file.name == name) {
+            return Err(anyhow::anyhow!("Archive would contain two files named {}", name));
+        }
+        zip.start_file(name.as_str(), options)?;
+
+        let mut input = BufReader::new(File::open(path)?);
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0; 64 * 1024];
+        let mut size_bytes = 0;
+        loop {
+            let read = input.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            zip.write_all(&buffer[..read])?;
+            size_bytes += read as u64;
+        }
+
+        manifest.files.push(ArchiveEntry {
+            name,
+            size_bytes,
+            sha256: hex::encode(hasher.finalize()),
+            ..entry.clone()
+        });
+    }
+
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    zip.finish()?.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zstd_round_trip_and_zip_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv = dir.path().join("items.csv");
+        std::fs::write(&csv, "id,name\n1,a\n2,b\n").unwrap();
+
+        let compressed = compress(&csv, CompressionCodec::Zstd, Some(99)).unwrap();
+        assert_eq!(compressed, dir.path().join("items.csv.zst"));
+        let restored = zstd::decode_all(File::open(&compressed).unwrap()).unwrap();
+        assert_eq!(restored, std::fs::read(&csv).unwrap());
+
+        let entry = ArchiveEntry {
+            name: String::new(),
+            format: "csv".to_string(),
+            record_count: 2,
+            size_bytes: 0,
+            sha256: String::new(),
+        };
+        let archive = dir.path().join("export.zip");
+        let manifest = write_zip(&archive, &[(csv, entry)]).unwrap();
+        assert_eq!((manifest.files[0].name.as_str(), manifest.files[0].size_bytes), ("items.csv", 16));
+
+        let mut zip = zip::ZipArchive::new(File::open(&archive).unwrap()).unwrap();
+        let mut stored = String::new();
+        zip.by_name(MANIFEST_NAME).unwrap().read_to_string(&mut stored).unwrap();
+        let stored: ArchiveManifest = serde_json::from_str(&stored).unwrap();
+        assert_eq!(stored.files[0].sha256, manifest.files[0].sha256);
+    }
+}
@@ -3,49 +3,58 @@ use csv::WriterBuilder;
 use tracing::{debug, info};
 
 use crate::config::ExportConfig;
-use super::{DataTransformer, InternalExportStats};
+use super::{DataTransformer, InternalExportStats, RowSource, SliceRows};
 
 /// Export data to CSV format
 pub async fn export_csv(
     data: &[serde_json::Value],
     output_path: &str,
-    _config: &ExportConfig,
+    config: &ExportConfig,
 ) -> Result<InternalExportStats> {
-    debug!("Exporting {} records to CSV: {}", data.len(), output_path);
-    
-    if data.is_empty() {
-        return create_empty_csv(output_path).await;
-    }
-    
-    // Flatten JSON data for tabular format
-    let flattened_data = DataTransformer::flatten_json(data)?;
+    let (stats, _) = export_csv_rows(&mut SliceRows::new(data), output_path, config, &[], b',').await?;
+    Ok(stats)
+}
+
+/// Export rows to CSV format a page at a time
+///
+/// Reads the rows once to find every flattened column, then again to write
+/// them, so memory use doesn't grow with the row count. Columns named in
+/// `column_order` come first, the rest alphabetically. Returns the number
+/// of records written along with the stats.
+pub async fn export_csv_rows(
+    source: &mut dyn RowSource,
+    output_path: &str,
+    _config: &ExportConfig,
+    column_order: &[String],
+    delimiter: u8,
+) -> Result<(InternalExportStats, usize)> {
+    debug!("Exporting records to CSV: {}", output_path);
     
-    // Get all unique column names
-    let mut all_columns = std::collections::HashSet::new();
-    for row in &flattened_data {
-        for key in row.keys() {
-            all_columns.insert(key.clone());
-        }
+    let columns = flattened_columns(source, column_order).await?;
+    if columns.is_empty() {
+        return Ok((create_empty_csv(output_path).await?, 0));
     }
     
-    let mut columns: Vec<String> = all_columns.into_iter().collect();
-    columns.sort();
-    
     // Create CSV writer
     let file = std::fs::File::create(output_path)?;
     let mut writer = WriterBuilder::new()
         .has_headers(true)
+        .delimiter(delimiter)
         .from_writer(file);
     
     // Write headers
     writer.write_record(&columns)?;
     
     // Write data rows
-    for row in &flattened_data {
-        let record: Vec<String> = columns.iter()
-            .map(|col| row.get(col).cloned().unwrap_or_default())
-            .collect();
-        writer.write_record(&record)?;
+    let mut record_count = 0;
+    while let Some(page) = source.next_page().await? {
+        for row in DataTransformer::flatten_json(&page)? {
+            let record: Vec<String> = columns.iter()
+                .map(|col| row.get(col).cloned().unwrap_or_default())
+                .collect();
+            writer.write_record(&record)?;
+        }
+        record_count += page.len();
     }
     
     writer.flush()?;
@@ -54,12 +63,49 @@ pub async fn export_csv(
     // Get file stats
     let file_size = tokio::fs::metadata(output_path).await?.len();
     
-    info!("CSV export completed: {} records, {} bytes", data.len(), file_size);
+    info!("CSV export completed: {} records, {} bytes", record_count, file_size);
     
-    Ok(InternalExportStats {
-        file_size_bytes: file_size,
-        compression_ratio: None,
-    })
+    Ok((
+        InternalExportStats {
+            file_size_bytes: file_size,
+            compression_ratio: None,
+        },
+        record_count,
+    ))
+}
+
+/// Flattened column names of every row, leaving `source` rewound
+///
+/// Columns of the fields in `column_order` come first, in that order, with
+/// nested values keeping their `field_key` columns together; the rest follow
+/// alphabetically.
+pub(super) async fn flattened_columns(source: &mut dyn RowSource, column_order: &[String]) -> Result<Vec<String>> {
+    let mut remaining = std::collections::BTreeSet::new();
+    while let Some(page) = source.next_page().await? {
+        for row in DataTransformer::flatten_json(&page)? {
+            remaining.extend(row.into_keys());
+        }
+    }
+    source.rewind().await?;
+    Ok(order_columns(remaining, column_order))
+}
+
+/// Columns of the fields in `column_order` first, then the rest alphabetically
+pub(super) fn order_columns(mut remaining: std::collections::BTreeSet<String>, column_order: &[String]) -> Vec<String> {
+    let mut columns = Vec::with_capacity(remaining.len());
+    for field in column_order {
+        let prefix = format!("{}_", field);
+        let matching: Vec<String> = remaining.iter()
+            .filter(|column| *column == field || column.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for column in matching {
+            remaining.remove(&column);
+            columns.push(column);
+        }
+    }
+    columns.extend(remaining);
+    columns
 }
 
 /// Create empty CSV file with headers only
@@ -159,6 +205,12 @@ mod tests {
             max_file_size_mb: 100,
             compression_enabled: false,
             output_directory: std::path::PathBuf::from("/tmp"),
+            filename_template: crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            on_collision: crate::config::FilenameCollision::default(),
+            xlsx_sheet_rows: crate::export::xlsx_exporter::MAX_SHEET_ROWS,
+            database_url: None,
+            compression: crate::config::CompressionCodec::Gzip,
+            compression_level: None,
         };
         
         let stats = export_csv(&data, output_path, &config).await.unwrap();
@@ -185,6 +237,12 @@ mod tests {
             max_file_size_mb: 100,
             compression_enabled: false,
             output_directory: std::path::PathBuf::from("/tmp"),
+            filename_template: crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            on_collision: crate::config::FilenameCollision::default(),
+            xlsx_sheet_rows: crate::export::xlsx_exporter::MAX_SHEET_ROWS,
+            database_url: None,
+            compression: crate::config::CompressionCodec::Gzip,
+            compression_level: None,
         };
         
         let stats = export_csv(&data, output_path, &config).await.unwrap();
@@ -218,6 +276,12 @@ mod tests {
             max_file_size_mb: 100,
             compression_enabled: false,
             output_directory: std::path::PathBuf::from("/tmp"),
+            filename_template: crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            on_collision: crate::config::FilenameCollision::default(),
+            xlsx_sheet_rows: crate::export::xlsx_exporter::MAX_SHEET_ROWS,
+            database_url: None,
+            compression: crate::config::CompressionCodec::Gzip,
+            compression_level: None,
         };
         
         let stats = export_csv(&data, output_path, &config).await.unwrap();
@@ -0,0 +1,390 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::mysql::MySqlPool;
+use sqlx::postgres::PgPool;
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{debug, info};
+
+use super::parquet_exporter::{as_bool, as_float, as_int, as_text, as_timestamp};
+use super::{ColumnType, RowSource};
+
+/// Longest table name accepted; Postgres truncates identifiers past 63 bytes
+const MAX_TABLE_NAME_LEN: usize = 63;
+
+/// What a database export wrote
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SinkReport {
+    pub table: String,
+    /// Rows inserted or updated
+    pub rows_written: usize,
+    /// Rows left out because a key column was empty
+    pub rows_skipped: usize,
+    /// Columns added to an existing table
+    pub columns_added: Vec<String>,
+    /// Whether the table was created by this export
+    pub created: bool,
+}
+
+/// SQL flavour of the target database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    Postgres,
+    MySql,
+}
+
+enum SinkPool {
+    Postgres(PgPool),
+    MySql(MySqlPool),
+}
+
+/// Writes export rows into a table of an external PostgreSQL or MySQL database
+pub struct DatabaseSink {
+    pool: SinkPool,
+}
+
+/// A value converted to its column's type, ready to bind
+#[derive(Debug, Clone, PartialEq)]
+enum SqlValue {
+    Int(Option<i64>),
+    Float(Option<f64>),
+    Bool(Option<bool>),
+    Timestamp(Option<DateTime<Utc>>),
+    Text(Option<String>),
+}
+
+macro_rules! bind_values {
+    ($query:expr, $values:expr) => {{
+        let mut query = $query;
+        for value in $values {
+            query = match value {
+                SqlValue::Int(v) => query.bind(v),
+                SqlValue::Float(v) => query.bind(v),
+                SqlValue::Bool(v) => query.bind(v),
+                SqlValue::Timestamp(v) => query.bind(v),
+                SqlValue::Text(v) => query.bind(v),
+            };
+        }
+        query
+    }};
+}
+
+/// Table name for a plan's domain, e.g. `shop_example_com`
+pub fn default_table_name(domain: &str) -> String {
+    let name: String = domain.trim().to_lowercase().chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .take(MAX_TABLE_NAME_LEN)
+        .collect();
+    if name.is_empty() { "scraped_items".to_string() } else { name }
+}
+
+impl Dialect {
+    fn from_url(url: &str) -> Result<Self> {
+        let scheme = url.split("://").next().unwrap_or_default().to_lowercase();
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Ok(Dialect::Postgres),
+            "mysql" | "mariadb" => Ok(Dialect::MySql),
+            _ => Err(anyhow::anyhow!("Unsupported export database '{}': use a postgres:// or mysql:// URL", scheme)),
+        }
+    }
+
+    fn quote(self, name: &str) -> String {
+        match self {
+            Dialect::Postgres => format!("\"{}\"", name.replace('"', "\"\"")),
+            Dialect::MySql => format!("`{}`", name.replace('`', "``")),
+        }
+    }
+
+    fn placeholder(self, index: usize) -> String {
+        match self {
+            Dialect::Postgres => format!("${}", index),
+            Dialect::MySql => "?".to_string(),
+        }
+    }
+
+    fn sql_type(self, column_type: ColumnType, key: bool) -> &'static str {
+        match (self, column_type) {
+            (_, ColumnType::Int) => "BIGINT",
+            (_, ColumnType::Bool) => "BOOLEAN",
+            (Dialect::Postgres, ColumnType::Float) => "DOUBLE PRECISION",
+            (Dialect::Postgres, ColumnType::Timestamp) => "TIMESTAMPTZ",
+            (Dialect::Postgres, ColumnType::String) => "TEXT",
+            (Dialect::MySql, ColumnType::Float) => "DOUBLE",
+            (Dialect::MySql, ColumnType::Timestamp) => "DATETIME(6)",
+            // MySQL can't index TEXT without a prefix length
+            (Dialect::MySql, ColumnType::String) if key => "VARCHAR(255)",
+            (Dialect::MySql, ColumnType::String) => "TEXT",
+        }
+    }
+
+    /// Column type for a type name from `information_schema.columns`
+    fn column_type_of(self, data_type: &str) -> ColumnType {
+        match (self, data_type.to_lowercase().as_str()) {
+            (_, "bigint" | "integer" | "int" | "smallint" | "mediumint") => ColumnType::Int,
+            (_, "double precision" | "real" | "double" | "float" | "numeric" | "decimal") => ColumnType::Float,
+            (Dialect::Postgres, "boolean") | (Dialect::MySql, "tinyint") => ColumnType::Bool,
+            (Dialect::Postgres, "timestamp with time zone") | (Dialect::MySql, "datetime" | "timestamp") => ColumnType::Timestamp,
+            _ => ColumnType::String,
+        }
+    }
+
+    fn existing_columns_sql(self) -> &'static str {
+        match self {
+            Dialect::Postgres => {
+                "SELECT column_name::text, data_type::text FROM information_schema.columns
+                 WHERE table_schema = current_schema() AND table_name = $1 ORDER BY ordinal_position"
+            }
+            Dialect::MySql => {
+                "SELECT CAST(column_name AS CHAR), CAST(data_type AS CHAR) FROM information_schema.columns
+                 WHERE table_schema = DATABASE() AND table_name = ? ORDER BY ordinal_position"
+            }
+        }
+    }
+
+    fn create_table_sql(self, table: &str, columns: &[(String, ColumnType)], keys: &[String]) -> String {
+        let mut definitions: Vec<String> = columns.iter()
+            .map(|(name, column_type)| {
+                let key = keys.contains(name);
+                let not_null = if key { " NOT NULL" } else { "" };
+                format!("{} {}{}", self.quote(name), self.sql_type(*column_type, key), not_null)
+            })
+            .collect();
+        if !keys.is_empty() {
+            let key_list: Vec<String> = keys.iter().map(|key| self.quote(key)).collect();
+            definitions.push(format!("PRIMARY KEY ({})", key_list.join(", ")));
+        }
+        format!("CREATE TABLE {} ({})", self.quote(table), definitions.join(", "))
+    }
+
+    fn add_column_sql(self, table: &str, name: &str, column_type: ColumnType) -> String {
+        format!("ALTER TABLE {} ADD COLUMN {} {}", self.quote(table), self.quote(name), self.sql_type(column_type, false))
+    }
+
+    /// Single-row insert that updates the existing row when the keys match
+    fn upsert_sql(self, table: &str, columns: &[String], keys: &[String]) -> String {
+        let names: Vec<String> = columns.iter().map(|name| self.quote(name)).collect();
+        let placeholders: Vec<String> = (1..=columns.len()).map(|i| self.placeholder(i)).collect();
+        let insert = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            self.quote(table), names.join(", "), placeholders.join(", ")
+        );
+        if keys.is_empty() {
+            return insert;
+        }
+
+        let updates: Vec<&String> = columns.iter().filter(|name| !keys.contains(name)).collect();
+        match self {
+            Dialect::Postgres => {
+                let key_list: Vec<String> = keys.iter().map(|key| self.quote(key)).collect();
+                if updates.is_empty() {
+                    format!("{} ON CONFLICT ({}) DO NOTHING", insert, key_list.join(", "))
+                } else {
+                    let sets: Vec<String> = updates.iter()
+                        .map(|name| format!("{0} = EXCLUDED.{0}", self.quote(name)))
+                        .collect();
+                    format!("{} ON CONFLICT ({}) DO UPDATE SET {}", insert, key_list.join(", "), sets.join(", "))
+                }
+            }
+            Dialect::MySql => {
+                // Assigning a key to itself makes a duplicate a no-op
+                let targets = if updates.is_empty() { vec![&keys[0]] } else { updates };
+                let sets: Vec<String> = targets.iter()
+                    .map(|name| format!("{0} = VALUES({0})", self.quote(name)))
+                    .collect();
+                format!("{} ON DUPLICATE KEY UPDATE {}", insert, sets.join(", "))
+            }
+        }
+    }
+}
+
+impl SqlValue {
+    fn from_json(value: Option<&Value>, column_type: ColumnType) -> Self {
+        let value = value.filter(|v| !v.is_null());
+        match column_type {
+            ColumnType::Int => SqlValue::Int(value.and_then(as_int)),
+            ColumnType::Float => SqlValue::Float(value.and_then(as_float)),
+            ColumnType::Bool => SqlValue::Bool(value.and_then(as_bool)),
+            ColumnType::Timestamp => SqlValue::Timestamp(value.and_then(as_timestamp)),
+            ColumnType::String => SqlValue::Text(value.map(as_text)),
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            SqlValue::Int(v) => v.is_none(),
+            SqlValue::Float(v) => v.is_none(),
+            SqlValue::Bool(v) => v.is_none(),
+            SqlValue::Timestamp(v) => v.is_none(),
+            SqlValue::Text(v) => v.as_deref().unwrap_or_default().is_empty(),
+        }
+    }
+}
+
+impl DatabaseSink {
+    /// Connect to the database at `url`, a `postgres://` or `mysql://` connection string
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = match Dialect::from_url(url)? {
+            Dialect::Postgres => SinkPool::Postgres(PgPool::connect(url).await?),
+            Dialect::MySql => SinkPool::MySql(MySqlPool::connect(url).await?),
+        };
+        Ok(Self { pool })
+    }
+
+    fn dialect(&self) -> Dialect {
+        match self.pool {
+            SinkPool::Postgres(_) => Dialect::Postgres,
+            SinkPool::MySql(_) => Dialect::MySql,
+        }
+    }
+
+    /// Write every row of `source` into `table`, creating it or adding missing columns first
+    ///
+    /// A new table gets a primary key over `key_columns`, and rows whose keys
+    /// already exist are updated in place; an existing table needs a unique
+    /// index over them. Without key columns rows are only appended. Column types
+    /// are inferred as for Parquet, with `schema` taking precedence; columns the
+    /// table already has keep their type. Each page is written in one transaction.
+    pub async fn write(
+        &self,
+        source: &mut dyn RowSource,
+        table: &str,
+        key_columns: &[String],
+        schema: &BTreeMap<String, ColumnType>,
+    ) -> Result<SinkReport> {
+        if table.trim().is_empty() || table.len() > MAX_TABLE_NAME_LEN {
+            return Err(anyhow::anyhow!("Table name must be 1 to {} characters", MAX_TABLE_NAME_LEN));
+        }
+        let dialect = self.dialect();
+        let mut report = SinkReport { table: table.to_string(), ..Default::default() };
+
+        let inferred = super::options::infer_column_types(source).await?;
+        if let Some(missing) = key_columns.iter().find(|key| !inferred.contains_key(*key)) {
+            return Err(anyhow::anyhow!("Key column '{}' is not in the exported rows", missing));
+        }
+        let mut columns: Vec<(String, ColumnType)> = inferred.iter()
+            .map(|(name, column_type)| {
+                let column_type = schema.get(name).copied().or(*column_type).unwrap_or(ColumnType::String);
+                (name.clone(), column_type)
+            })
+            .collect();
+        // Keys first, so the table reads naturally
+        columns.sort_by_key(|(name, _)| !key_columns.contains(name));
+
+        let existing = self.existing_columns(table).await?;
+        if existing.is_empty() {
+            self.execute(&dialect.create_table_sql(table, &columns, key_columns)).await?;
+            report.created = true;
+            info!("Created export table {} with {} columns", table, columns.len());
+        } else {
+            for (name, column_type) in columns.iter_mut() {
+                match existing.get(name) {
+                    Some(existing_type) => *column_type = *existing_type,
+                    None => {
+                        self.execute(&dialect.add_column_sql(table, name, *column_type)).await?;
+                        report.columns_added.push(name.clone());
+                    }
+                }
+            }
+            if !report.columns_added.is_empty() {
+                info!("Added columns {} to export table {}", report.columns_added.join(", "), table);
+            }
+        }
+
+        let names: Vec<String> = columns.iter().map(|(name, _)| name.clone()).collect();
+        let sql = dialect.upsert_sql(table, &names, key_columns);
+        let key_indexes: BTreeSet<usize> = names.iter().enumerate()
+            .filter(|(_, name)| key_columns.contains(name))
+            .map(|(i, _)| i)
+            .collect();
+
+        while let Some(page) = source.next_page().await? {
+            let mut rows = Vec::with_capacity(page.len());
+            for row in &page {
+                let values: Vec<SqlValue> = columns.iter()
+                    .map(|(name, column_type)| SqlValue::from_json(row.get(name), *column_type))
+                    .collect();
+                if key_indexes.iter().any(|&i| values[i].is_null()) {
+                    report.rows_skipped += 1;
+                } else {
+                    rows.push(values);
+                }
+            }
+            report.rows_written += self.write_page(&sql, rows).await?;
+            debug!("Wrote {} rows to {}", report.rows_written, table);
+        }
+
+        info!(
+            "Database export to {} completed: {} rows written, {} skipped",
+            table, report.rows_written, report.rows_skipped
+        );
+        Ok(report)
+    }
+
+    /// Columns of `table` and their types; empty when the table doesn't exist
+    async fn existing_columns(&self, table: &str) -> Result<BTreeMap<String, ColumnType>> {
+        let dialect = self.dialect();
+        let rows: Vec<(String, String)> = match &self.pool {
+            SinkPool::Postgres(pool) => sqlx::query_as(dialect.existing_columns_sql()).bind(table).fetch_all(pool).await?,
+            SinkPool::MySql(pool) => sqlx::query_as(dialect.existing_columns_sql()).bind(table).fetch_all(pool).await?,
+        };
+        Ok(rows.into_iter()
+            .map(|(name, data_type)| (name, dialect.column_type_of(&data_type)))
+            .collect())
+    }
+
+    async fn execute(&self, sql: &str) -> Result<()> {
+        match &self.pool {
+            SinkPool::Postgres(pool) => { sqlx::query(sql).execute(pool).await?; }
+            SinkPool::MySql(pool) => { sqlx::query(sql).execute(pool).await?; }
+        }
+        Ok(())
+    }
+
+    async fn write_page(&self, sql: &str, rows: Vec<Vec<SqlValue>>) -> Result<usize> {
+        let count = rows.len();
+        match &self.pool {
+            SinkPool::Postgres(pool) => {
+                let mut tx = pool.begin().await?;
+                for values in rows {
+                    bind_values!(sqlx::query(sql), values).execute(&mut *tx).await?;
+                }
+                tx.commit().await?;
+            }
+            SinkPool::MySql(pool) => {
+                let mut tx = pool.begin().await?;
+                for values in rows {
+                    bind_values!(sqlx::query(sql), values).execute(&mut *tx).await?;
+                }
+                tx.commit().await?;
+            }
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_statements() {
+        let columns = vec!["sku".to_string(), "price".to_string()];
+        let keys = vec!["sku".to_string()];
+
+        assert_eq!(
+            Dialect::Postgres.upsert_sql("items", &columns, &keys),
+            r#"INSERT INTO "items" ("sku", "price") VALUES ($1, $2) ON CONFLICT ("sku") DO UPDATE SET "price" = EXCLUDED."price""#
+        );
+        assert_eq!(
+            Dialect::MySql.upsert_sql("items", &columns, &keys),
+            "INSERT INTO `items` (`sku`, `price`) VALUES (?, ?) ON DUPLICATE KEY UPDATE `price` = VALUES(`price`)"
+        );
+        assert_eq!(
+            Dialect::MySql.create_table_sql("items", &[("sku".to_string(), ColumnType::String), ("price".to_string(), ColumnType::Float)], &keys),
+            "CREATE TABLE `items` (`sku` VARCHAR(255) NOT NULL, `price` DOUBLE, PRIMARY KEY (`sku`))"
+        );
+        assert!(Dialect::from_url("sqlite://data.db").is_err());
+        assert_eq!(default_table_name("Shop.Example-1.com"), "shop_example_1_com");
+    }
+}
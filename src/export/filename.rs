@@ -0,0 +1,150 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+use crate::config::FilenameCollision;
+use crate::utils::{sanitize_filename, time_utils};
+
+/// Template used when neither the configuration nor the plan sets one
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{plan_name}_{date}_{run_number}.{ext}";
+
+/// Placeholders a filename template may use
+pub const PLACEHOLDERS: &[&str] = &["plan_name", "domain", "job_id", "date", "time", "run_number", "ext"];
+
+/// Highest suffix tried when incrementing a taken name
+const MAX_INCREMENT: usize = 10_000;
+
+/// Values substituted into a filename template
+#[derive(Debug, Clone)]
+pub struct FilenameContext {
+    pub plan_name: String,
+    pub domain: String,
+    pub job_id: String,
+    /// How many times the plan has run, counting this run
+    pub run_number: usize,
+    pub date: DateTime<Utc>,
+    pub ext: String,
+}
+
+impl FilenameContext {
+    fn value(&self, placeholder: &str) -> Option<String> {
+        let value = match placeholder {
+            "plan_name" => self.plan_name.clone(),
+            "domain" => self.domain.clone(),
+            "job_id" => self.job_id.clone(),
+            "date" => time_utils::format_local(&self.date, "%Y-%m-%d"),
+            "time" => time_utils::format_local(&self.date, "%H%M%S"),
+            "run_number" => self.run_number.to_string(),
+            "ext" => self.ext.clone(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Fill in a template, producing a bare file name
+///
+/// Fails on unknown placeholders and unbalanced braces. The result is passed
+/// through `sanitize_filename`, so it can never contain a path separator.
+pub fn render(template: &str, context: &FilenameContext) -> Result<String> {
+    let mut name = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(anyhow::anyhow!("Unmatched '}}' in filename template '{}'", template));
+        }
+        name.push_str(&rest[..start]);
+
+        let end = rest[start..].find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed '{{' in filename template '{}'", template))?;
+        let placeholder = &rest[start + 1..start + end];
+        let value = context.value(placeholder).ok_or_else(|| anyhow::anyhow!(
+            "Unknown placeholder {{{}}} in filename template; use one of {}",
+            placeholder,
+            PLACEHOLDERS.iter().map(|p| format!("{{{}}}", p)).collect::<Vec<_>>().join(", ")
+        ))?;
+        name.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    name.push_str(rest);
+
+    let name = sanitize_filename(name.trim());
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        return Err(anyhow::anyhow!("Filename template '{}' produces an empty file name", template));
+    }
+    Ok(name)
+}
+
+/// Check that a template renders to a usable file name
+pub fn validate_template(template: &str) -> Result<()> {
+    let sample = FilenameContext {
+        plan_name: "plan".to_string(),
+        domain: "example.com".to_string(),
+        job_id: "job".to_string(),
+        run_number: 1,
+        date: Utc::now(),
+        ext: "csv".to_string(),
+    };
+    render(template, &sample).map(|_| ())
+}
+
+/// Path in `dir` for `file_name`, applying the collision policy if it exists
+pub fn resolve_collision(dir: &Path, file_name: &str, policy: FilenameCollision) -> Result<PathBuf> {
+    let path = dir.join(file_name);
+    if !path.exists() {
+        return Ok(path);
+    }
+
+    match policy {
+        FilenameCollision::Overwrite => Ok(path),
+        FilenameCollision::Fail => Err(anyhow::anyhow!(
+            "Export file {} already exists; change the filename template or the collision policy",
+            path.display()
+        )),
+        FilenameCollision::Increment => {
+            let (stem, ext) = match file_name.rsplit_once('.') {
+                Some((stem, ext)) => (stem, format!(".{}", ext)),
+                None => (file_name, String::new()),
+            };
+            (2..=MAX_INCREMENT)
+                .map(|n| dir.join(format!("{}_{}{}", stem, n, ext)))
+                .find(|candidate| !candidate.exists())
+                .ok_or_else(|| anyhow::anyhow!("No free file name left for {}", path.display()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn context() -> FilenameContext {
+        FilenameContext {
+            plan_name: "books/fiction".to_string(),
+            domain: "books.example.com".to_string(),
+            job_id: "abc".to_string(),
+            run_number: 3,
+            date: Utc.with_ymd_and_hms(2024, 5, 17, 12, 0, 0).unwrap(),
+            ext: "csv".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_and_collisions() {
+        let name = render(DEFAULT_FILENAME_TEMPLATE, &context()).unwrap();
+        assert_eq!(name, "books_fiction_2024-05-17_3.csv");
+
+        assert!(render("{plan}.{ext}", &context()).is_err());
+        assert!(render("{plan_name.{ext}", &context()).is_err());
+        assert!(render("../{ext}", &context()).unwrap().starts_with(".._"));
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(&name), "").unwrap();
+        let next = resolve_collision(dir.path(), &name, FilenameCollision::Increment).unwrap();
+        assert_eq!(next, dir.path().join("books_fiction_2024-05-17_3_2.csv"));
+        assert!(resolve_collision(dir.path(), &name, FilenameCollision::Fail).is_err());
+        assert_eq!(resolve_collision(dir.path(), &name, FilenameCollision::Overwrite).unwrap(), dir.path().join(&name));
+    }
+}
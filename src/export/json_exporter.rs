@@ -4,6 +4,7 @@ use std::fs::File;
 use tracing::{debug, info};
 
 use crate::config::ExportConfig;
+use crate::utils::time_utils;
 use super::InternalExportStats;
 
 /// Export data to JSON format
@@ -92,7 +93,7 @@ pub async fn export_json_with_metadata(
     let export_data = serde_json::json!({
         "metadata": metadata,
         "data": data,
-        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "exported_at": time_utils::to_local_rfc3339(&chrono::Utc::now()),
         "record_count": data.len()
     });
     
@@ -170,7 +171,7 @@ pub async fn export_json_with_schema(
     let export_data = serde_json::json!({
         "schema": schema,
         "data": data,
-        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "exported_at": time_utils::to_local_rfc3339(&chrono::Utc::now()),
         "record_count": data.len(),
         "schema_validated": true
     });
@@ -257,6 +258,12 @@ mod tests {
             max_file_size_mb: 100,
             compression_enabled: false,
             output_directory: std::path::PathBuf::from("/tmp"),
+            filename_template: crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            on_collision: crate::config::FilenameCollision::default(),
+            xlsx_sheet_rows: crate::export::xlsx_exporter::MAX_SHEET_ROWS,
+            database_url: None,
+            compression: crate::config::CompressionCodec::Gzip,
+            compression_level: None,
         };
         
         let stats = export_json(&data, output_path, &config).await.unwrap();
@@ -284,6 +291,12 @@ mod tests {
             max_file_size_mb: 100,
             compression_enabled: false,
             output_directory: std::path::PathBuf::from("/tmp"),
+            filename_template: crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            on_collision: crate::config::FilenameCollision::default(),
+            xlsx_sheet_rows: crate::export::xlsx_exporter::MAX_SHEET_ROWS,
+            database_url: None,
+            compression: crate::config::CompressionCodec::Gzip,
+            compression_level: None,
         };
         
         let stats = export_jsonl(&data, output_path, &config).await.unwrap();
@@ -319,6 +332,12 @@ mod tests {
             max_file_size_mb: 100,
             compression_enabled: false,
             output_directory: std::path::PathBuf::from("/tmp"),
+            filename_template: crate::export::filename::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            on_collision: crate::config::FilenameCollision::default(),
+            xlsx_sheet_rows: crate::export::xlsx_exporter::MAX_SHEET_ROWS,
+            database_url: None,
+            compression: crate::config::CompressionCodec::Gzip,
+            compression_level: None,
         };
         
         let stats = export_json_with_metadata(&data, output_path, &metadata, &config).await.unwrap();
@@ -6,8 +6,26 @@ pub mod csv_exporter;
 pub mod json_exporter;
 pub mod xlsx_exporter;
 pub mod parquet_exporter;
+pub mod ndjson_exporter;
+pub mod database_sink;
+pub mod compression;
+pub mod split;
+pub mod profile;
+pub mod rows;
+pub mod options;
+pub mod filename;
+pub mod sample;
+pub mod progress;
 
-use crate::config::ExportConfig;
+use crate::config::{CompressionCodec, ExportConfig};
+use filename::FilenameContext;
+pub use rows::{RowSource, SliceRows, EXPORT_PAGE_SIZE};
+pub use options::{ColumnType, ExportOptions};
+pub use database_sink::{DatabaseSink, SinkReport};
+pub use compression::{ArchiveEntry, ArchiveManifest};
+pub use split::SplitExport;
+pub use progress::{ExportProgress, ProgressRows};
+pub use profile::{ExportDestination, ExportProfile};
 
 /// Export manager for handling different output formats
 pub struct ExportManager {
@@ -15,12 +33,14 @@ pub struct ExportManager {
 }
 
 /// Export format enumeration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExportFormat {
     Csv,
     Json,
     Xlsx,
     Parquet,
+    /// Newline-delimited JSON, one object per line
+    Ndjson,
 }
 
 impl std::str::FromStr for ExportFormat {
@@ -32,11 +52,22 @@ impl std::str::FromStr for ExportFormat {
             "json" => Ok(ExportFormat::Json),
             "xlsx" => Ok(ExportFormat::Xlsx),
             "parquet" => Ok(ExportFormat::Parquet),
+            "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
             _ => Err(anyhow::anyhow!("Invalid export format: {}", s)),
         }
     }
 }
 
+impl ExportFormat {
+    /// Times an export reads every row; tabular formats find their columns first
+    pub fn row_passes(&self) -> u32 {
+        match self {
+            ExportFormat::Csv | ExportFormat::Xlsx | ExportFormat::Parquet => 2,
+            ExportFormat::Json | ExportFormat::Ndjson => 1,
+        }
+    }
+}
+
 impl std::fmt::Display for ExportFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -44,6 +75,7 @@ impl std::fmt::Display for ExportFormat {
             ExportFormat::Json => write!(f, "json"),
             ExportFormat::Xlsx => write!(f, "xlsx"),
             ExportFormat::Parquet => write!(f, "parquet"),
+            ExportFormat::Ndjson => write!(f, "ndjson"),
         }
     }
 }
@@ -76,27 +108,52 @@ impl ExportManager {
         data: &[serde_json::Value],
         output_path: &str,
         format: ExportFormat,
+        options: &ExportOptions,
     ) -> Result<ExportStats> {
         info!("Exporting {} records to {} as {}", data.len(), output_path, format);
         
-        let start_time = std::time::Instant::now();
-        
         // Validate data size
         self.validate_export_size(data)?;
         
+        self.export_rows(&mut SliceRows::new(data), output_path, format, options).await
+    }
+    
+    /// Export rows read page by page from `source`
+    ///
+    /// CSV, XLSX, NDJSON and Parquet are written one page at a time, so jobs of
+    /// any size can be exported; JSON reads every row first.
+    pub async fn export_rows(
+        &self,
+        source: &mut dyn RowSource,
+        output_path: &str,
+        format: ExportFormat,
+        options: &ExportOptions,
+    ) -> Result<ExportStats> {
+        options.validate()?;
+        let start_time = std::time::Instant::now();
+        
+        // Stored times are UTC; write them in the configured zone
+        let mut localized = rows::LocalizedRows(source);
+        let mut source = options::ShapedRows { source: &mut localized, options };
+        let column_order = options.column_order();
+        
         // Perform export based on format
-        let stats = match format {
+        let (stats, record_count) = match format {
             ExportFormat::Csv => {
-                csv_exporter::export_csv(data, output_path, &self.config).await?
-            }
-            ExportFormat::Json => {
-                json_exporter::export_json(data, output_path, &self.config).await?
+                csv_exporter::export_csv_rows(&mut source, output_path, &self.config, &column_order, options.csv_delimiter()).await?
             }
             ExportFormat::Xlsx => {
-                xlsx_exporter::export_xlsx(data, output_path, &self.config).await?
+                xlsx_exporter::export_xlsx_rows(&mut source, output_path, &self.config, &column_order, options.sheet_name.as_deref()).await?
+            }
+            ExportFormat::Ndjson => {
+                ndjson_exporter::export_ndjson(&mut source, output_path, &self.config).await?
             }
             ExportFormat::Parquet => {
-                parquet_exporter::export_parquet(data, output_path, &self.config).await?
+                parquet_exporter::export_parquet_rows(&mut source, output_path, &self.config, &column_order, &options.schema).await?
+            }
+            ExportFormat::Json => {
+                let data = rows::collect_rows(&mut source).await?;
+                (json_exporter::export_json(&data, output_path, &self.config).await?, data.len())
             }
         };
         
@@ -105,7 +162,7 @@ impl ExportManager {
         let final_stats = ExportStats {
             format,
             file_path: output_path.to_string(),
-            record_count: data.len(),
+            record_count,
             file_size_bytes: stats.file_size_bytes,
             export_duration_ms: export_duration,
             compression_ratio: stats.compression_ratio,
@@ -119,12 +176,62 @@ impl ExportManager {
         Ok(final_stats)
     }
     
+    /// Write rows from `source` into a table of the configured export database
+    ///
+    /// `key_columns` are field names before renaming; rows are upserted on them.
+    pub async fn export_to_database(
+        &self,
+        source: &mut dyn RowSource,
+        table: &str,
+        key_columns: &[String],
+        options: &ExportOptions,
+    ) -> Result<SinkReport> {
+        options.validate()?;
+        let url = self.config.database_url.as_deref()
+            .ok_or_else(|| anyhow::anyhow!("No export database configured; set export.database_url"))?;
+        
+        let mut localized = rows::LocalizedRows(source);
+        let mut source = options::ShapedRows { source: &mut localized, options };
+        let keys: Vec<String> = key_columns.iter().map(|key| options.output_name(key).to_string()).collect();
+        
+        let sink = DatabaseSink::connect(url).await?;
+        sink.write(&mut source, table, &keys, &options.schema).await
+    }
+    
+    /// Hand rows to a plugin sink a page at a time, returning where they went
+    pub async fn export_to_plugin(
+        &self,
+        source: &mut dyn RowSource,
+        name: &str,
+        args: &serde_json::Value,
+        options: &ExportOptions,
+    ) -> Result<String> {
+        options.validate()?;
+        let sink = crate::plugins::sink(name)
+            .ok_or_else(|| anyhow::anyhow!("Sink plugin not loaded: {}", name))?;
+        
+        let mut localized = rows::LocalizedRows(source);
+        let mut source = options::ShapedRows { source: &mut localized, options };
+        let mut written = 0;
+        while let Some(rows) = source.next_page().await? {
+            written += rows.len();
+            let (sink, args) = (sink.clone(), args.clone());
+            tokio::task::spawn_blocking(move || sink.write(&rows, &args)).await??;
+        }
+        
+        let (sink, args) = (sink.clone(), args.clone());
+        let target = tokio::task::spawn_blocking(move || sink.finish(&args)).await??;
+        info!("Exported {} rows to sink plugin {}", written, name);
+        Ok(if target.is_empty() { format!("sink {}", name) } else { target })
+    }
+    
     /// Export to multiple formats
     pub async fn export_multiple(
         &self,
         data: &[serde_json::Value],
         base_path: &str,
         formats: &[ExportFormat],
+        options: &ExportOptions,
     ) -> Result<Vec<ExportStats>> {
         let mut all_stats = Vec::new();
         
@@ -132,7 +239,7 @@ impl ExportManager {
             let file_extension = format.to_string();
             let output_path = format!("{}.{}", base_path, file_extension);
             
-            match self.export(data, &output_path, format.clone()).await {
+            match self.export(data, &output_path, format.clone(), options).await {
                 Ok(stats) => all_stats.push(stats),
                 Err(e) => {
                     error!("Failed to export to {}: {}", format, e);
@@ -168,6 +275,7 @@ impl ExportManager {
             ExportFormat::Json,
             ExportFormat::Xlsx,
             ExportFormat::Parquet,
+            ExportFormat::Ndjson,
         ]
     }
     
@@ -178,49 +286,101 @@ impl ExportManager {
             ExportFormat::Json => "json",
             ExportFormat::Xlsx => "xlsx",
             ExportFormat::Parquet => "parquet",
+            ExportFormat::Ndjson => "ndjson",
         }
     }
     
-    /// Generate default filename
-    pub fn generate_filename(job_id: &str, format: &ExportFormat) -> String {
-        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-        let extension = Self::get_file_extension(format);
-        format!("winscrape_{}_{}.{}", job_id, timestamp, extension)
+    /// Path in the output directory for an export without an explicit destination
+    ///
+    /// Uses the plan's filename template when it has one, otherwise the configured
+    /// template, and applies the configured collision policy.
+    pub fn generate_output_path(&self, plan_template: Option<&str>, context: &FilenameContext) -> Result<std::path::PathBuf> {
+        self.generate_output_path_in(&self.config.output_directory, plan_template, context)
+    }
+    
+    /// Like [`generate_output_path`](Self::generate_output_path), in another directory
+    pub fn generate_output_path_in(
+        &self,
+        directory: &std::path::Path,
+        plan_template: Option<&str>,
+        context: &FilenameContext,
+    ) -> Result<std::path::PathBuf> {
+        let template = plan_template.unwrap_or(&self.config.filename_template);
+        let name = filename::render(template, context)?;
+        
+        std::fs::create_dir_all(directory)?;
+        filename::resolve_collision(directory, &name, self.config.on_collision)
     }
     
-    /// Compress file if enabled
+    /// Compress file if enabled, with the configured codec and level
     pub async fn compress_file(&self, file_path: &str) -> Result<String> {
         if !self.config.compression_enabled {
             return Ok(file_path.to_string());
         }
         
-        let compressed_path = format!("{}.gz", file_path);
-        
-        // Read original file
-        let data = tokio::fs::read(file_path).await?;
-        
-        // Compress data
-        let compressed_data = self.compress_data(&data)?;
-        
-        // Write compressed file
-        tokio::fs::write(&compressed_path, compressed_data).await?;
+        self.compress_file_with(file_path, self.config.compression, self.config.compression_level).await
+    }
+    
+    /// Compress a file with the given codec, replacing it; returns the compressed file's path
+    pub async fn compress_file_with(&self, file_path: &str, codec: CompressionCodec, level: Option<u32>) -> Result<String> {
+        let source = std::path::PathBuf::from(file_path);
+        let compressed_path = tokio::task::spawn_blocking(move || compression::compress(&source, codec, level)).await??;
         
         // Remove original file
         tokio::fs::remove_file(file_path).await?;
         
+        let compressed_path = compressed_path.to_string_lossy().into_owned();
         info!("File compressed: {} -> {}", file_path, compressed_path);
         Ok(compressed_path)
     }
     
-    /// Compress data using gzip
-    fn compress_data(&self, data: &[u8]) -> Result<Vec<u8>> {
-        use flate2::write::GzEncoder;
-        use flate2::Compression;
-        use std::io::Write;
+    /// Bundle finished exports into one zip archive with a `manifest.json`
+    ///
+    /// The manifest lists each file's format, record count, size and SHA-256.
+    /// The exported files are removed once the archive is written.
+    pub async fn archive_exports(&self, exports: &[ExportStats], archive_path: &str) -> Result<ArchiveManifest> {
+        let files: Vec<(std::path::PathBuf, ArchiveEntry)> = exports.iter()
+            .map(|stats| (
+                std::path::PathBuf::from(&stats.file_path),
+                ArchiveEntry {
+                    name: String::new(),
+                    format: stats.format.to_string(),
+                    record_count: stats.record_count,
+                    size_bytes: stats.file_size_bytes,
+                    sha256: String::new(),
+                },
+            ))
+            .collect();
         
-        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-        encoder.write_all(data)?;
-        Ok(encoder.finish()?)
+        let target = std::path::PathBuf::from(archive_path);
+        let archived = files.clone();
+        let manifest = tokio::task::spawn_blocking(move || compression::write_zip(&target, &archived)).await??;
+        
+        for (path, _) in &files {
+            tokio::fs::remove_file(path).await?;
+        }
+        info!("Archived {} export files into {}", files.len(), archive_path);
+        Ok(manifest)
+    }
+    
+    /// Export to several formats and bundle the files into `<base_path>.zip`
+    ///
+    /// Returns the archive path and its manifest.
+    pub async fn export_multiple_to_archive(
+        &self,
+        data: &[serde_json::Value],
+        base_path: &str,
+        formats: &[ExportFormat],
+        options: &ExportOptions,
+    ) -> Result<(String, ArchiveManifest)> {
+        let exports = self.export_multiple(data, base_path, formats, options).await?;
+        if exports.is_empty() {
+            return Err(anyhow::anyhow!("No export succeeded, nothing to archive"));
+        }
+        
+        let archive_path = format!("{}.zip", base_path);
+        let manifest = self.archive_exports(&exports, &archive_path).await?;
+        Ok((archive_path, manifest))
     }
     
     /// Get export statistics for a file
@@ -330,6 +490,22 @@ impl DataTransformer {
         sorted_columns
     }
     
+    /// Rewrite metadata timestamps such as `_scraped_at` into the configured time zone
+    pub fn localize_timestamps(data: &mut [serde_json::Value]) {
+        for item in data {
+            if let serde_json::Value::Object(obj) = item {
+                for (key, value) in obj.iter_mut() {
+                    if !(key.starts_with('_') && key.ends_with("_at")) {
+                        continue;
+                    }
+                    if let Some(localized) = value.as_str().and_then(crate::utils::time_utils::localize_rfc3339) {
+                        *value = serde_json::Value::String(localized);
+                    }
+                }
+            }
+        }
+    }
+    
     /// Normalize data types for consistent export
     pub fn normalize_data(data: &mut [serde_json::Value]) {
         for item in data {
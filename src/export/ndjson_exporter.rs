@@ -0,0 +1,90 @@
+use anyhow::Result;
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tracing::{debug, info};
+
+use crate::config::ExportConfig;
+use super::{InternalExportStats, RowSource};
+
+/// Export rows as NDJSON, one object per line
+///
+/// Only one page of rows is in memory at a time. Returns the number of
+/// records written along with the stats.
+pub async fn export_ndjson(
+    source: &mut dyn RowSource,
+    output_path: &str,
+    _config: &ExportConfig,
+) -> Result<(InternalExportStats, usize)> {
+    debug!("Streaming records to NDJSON: {}", output_path);
+    
+    let mut writer = BufWriter::new(File::create(output_path).await?);
+    let mut record_count = 0;
+    let mut line = Vec::new();
+    
+    while let Some(page) = source.next_page().await? {
+        for row in &page {
+            line.clear();
+            serde_json::to_writer(&mut line, row)?;
+            line.push(b'\n');
+            writer.write_all(&line).await?;
+        }
+        record_count += page.len();
+    }
+    writer.flush().await?;
+    
+    let file_size = tokio::fs::metadata(output_path).await?.len();
+    info!("NDJSON export completed: {} records, {} bytes", record_count, file_size);
+    
+    Ok((
+        InternalExportStats {
+            file_size_bytes: file_size,
+            compression_ratio: None,
+        },
+        record_count,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{test_storage, Job, JobResult, JobStatus};
+
+    #[tokio::test]
+    async fn test_ndjson_streams_every_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+
+        storage.create_job(&Job {
+            id: "job".to_string(),
+            title: "job".to_string(),
+            status: JobStatus::Completed,
+            created_at: chrono::Utc::now(),
+            plan_yaml: String::new(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }).await.unwrap();
+        for i in 0..5 {
+            storage.store_job_result(&JobResult {
+                job_id: "job".to_string(),
+                row_idx: i,
+                data_json: format!(r#"{{"n":{}}}"#, i),
+                url: "https://example.com".to_string(),
+                fetched_at: chrono::Utc::now(),
+                hash: i.to_string(),
+            }).await.unwrap();
+        }
+
+        let path = dir.path().join("out.ndjson");
+        let mut cursor = storage.result_cursor("job", 2);
+        let (_, count) = export_ndjson(&mut cursor, path.to_str().unwrap(), &crate::config::AppConfig::default().export).await.unwrap();
+
+        assert_eq!(count, 5);
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, (0..5).map(|n| serde_json::json!({"n": n})).collect::<Vec<_>>());
+    }
+}
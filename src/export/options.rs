@@ -0,0 +1,284 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::config::CompressionCodec;
+use crate::security::output_filter::{redact_value, Redaction};
+use crate::utils::time_utils;
+use super::RowSource;
+
+/// Which columns an export contains, in what order, under what names and how they are redacted
+///
+/// The default writes every field, with tabular formats ordering columns
+/// alphabetically.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportOptions {
+    /// Fields to write, in column order; every field when empty
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// New column names, keyed by field name
+    #[serde(default)]
+    pub rename_map: BTreeMap<String, String>,
+    /// Leave out metadata fields such as `_source_url`
+    #[serde(default)]
+    pub exclude_metadata: bool,
+    /// `strftime` pattern for timestamps, e.g. `%Y-%m-%d %H:%M`; RFC 3339 when unset
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// Parquet column types by output column name; other columns are inferred from the data
+    #[serde(default)]
+    pub schema: BTreeMap<String, ColumnType>,
+    /// Redaction of sensitive columns, keyed by field name
+    #[serde(default)]
+    pub redact: BTreeMap<String, Redaction>,
+    /// Secret mixed into hashed columns so their values can't be looked up
+    #[serde(default)]
+    pub redact_salt: Option<String>,
+    /// Split the export into files of at most this many rows
+    #[serde(default)]
+    pub max_rows_per_file: Option<usize>,
+    /// Split the export into files of roughly at most this many megabytes
+    #[serde(default)]
+    pub max_mb_per_file: Option<u64>,
+    /// CSV field separator; a comma when unset
+    #[serde(default)]
+    pub delimiter: Option<char>,
+    /// XLSX sheet name; `Sheet1`, `Sheet2`… when unset
+    #[serde(default)]
+    pub sheet_name: Option<String>,
+    /// Compress the finished file, replacing it
+    #[serde(default)]
+    pub compression: Option<CompressionCodec>,
+}
+
+/// Longest sheet name Excel accepts
+const MAX_SHEET_NAME_CHARS: usize = 31;
+
+/// Characters Excel doesn't allow in sheet names
+const INVALID_SHEET_NAME_CHARS: &[char] = &['[', ']', ':', '*', '?', '/', '\\'];
+
+/// Type of a Parquet column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnType {
+    Int,
+    Float,
+    Bool,
+    /// RFC 3339 text stored as a UTC timestamp in microseconds
+    Timestamp,
+    String,
+}
+
+impl ExportOptions {
+    /// Whether the options leave rows as they are
+    pub fn is_default(&self) -> bool {
+        self.columns.is_empty()
+            && self.rename_map.is_empty()
+            && !self.exclude_metadata
+            && self.date_format.is_none()
+            && self.redact.is_empty()
+    }
+
+    /// Whether the export is written as several part files
+    pub fn is_split(&self) -> bool {
+        self.max_rows_per_file.is_some() || self.max_mb_per_file.is_some()
+    }
+
+    /// Reject date patterns chrono can't format and renames that collide
+    pub fn validate(&self) -> Result<()> {
+        if let Some(pattern) = &self.date_format {
+            use chrono::format::{Item, StrftimeItems};
+            if StrftimeItems::new(pattern).any(|item| matches!(item, Item::Error)) {
+                return Err(anyhow::anyhow!("Invalid date format: {}", pattern));
+            }
+        }
+
+        if self.max_rows_per_file == Some(0) || self.max_mb_per_file == Some(0) {
+            return Err(anyhow::anyhow!("Split exports need a file limit above zero"));
+        }
+        if self.is_split() && self.compression.is_some() {
+            return Err(anyhow::anyhow!("Split exports can't be compressed; archive them instead"));
+        }
+        if let Some(delimiter) = self.delimiter {
+            if !delimiter.is_ascii() || delimiter == '"' || delimiter == '\n' || delimiter == '\r' {
+                return Err(anyhow::anyhow!("Invalid CSV delimiter: {:?}", delimiter));
+            }
+        }
+        if let Some(name) = &self.sheet_name {
+            if name.trim().is_empty() || name.chars().count() > MAX_SHEET_NAME_CHARS || name.contains(INVALID_SHEET_NAME_CHARS) {
+                return Err(anyhow::anyhow!(
+                    "Sheet names need 1-{} characters and none of {}",
+                    MAX_SHEET_NAME_CHARS,
+                    INVALID_SHEET_NAME_CHARS.iter().collect::<String>()
+                ));
+            }
+        }
+
+        let mut names = std::collections::HashSet::new();
+        let fields: Vec<&String> = if self.columns.is_empty() {
+            self.rename_map.keys().collect()
+        } else {
+            self.columns.iter().collect()
+        };
+        for field in fields {
+            if !names.insert(self.output_name(field)) {
+                return Err(anyhow::anyhow!("Two columns would be named '{}'", self.output_name(field)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Byte separating CSV fields
+    pub fn csv_delimiter(&self) -> u8 {
+        self.delimiter.map_or(b',', |delimiter| delimiter as u8)
+    }
+
+    /// Column names in the order given by `columns`, after renaming
+    pub fn column_order(&self) -> Vec<String> {
+        self.selected_columns().map(|field| self.output_name(field).to_string()).collect()
+    }
+
+    fn selected_columns(&self) -> impl Iterator<Item = &String> {
+        self.columns.iter().filter(|name| {
+            !(self.exclude_metadata && name.starts_with('_')) && self.redact.get(*name) != Some(&Redaction::Drop)
+        })
+    }
+
+    pub(super) fn output_name<'a>(&'a self, field: &'a str) -> &'a str {
+        self.rename_map.get(field).map(String::as_str).unwrap_or(field)
+    }
+
+    /// Apply the options to one row
+    pub fn shape_row(&self, row: serde_json::Value) -> serde_json::Value {
+        let serde_json::Value::Object(mut fields) = row else {
+            return row;
+        };
+
+        if self.exclude_metadata {
+            fields.retain(|name, _| !name.starts_with('_'));
+        }
+        let salt = self.redact_salt.as_deref().unwrap_or_default();
+        for (name, redaction) in &self.redact {
+            if let Some(value) = fields.remove(name) {
+                if let Some(redacted) = redact_value(value, *redaction, salt) {
+                    fields.insert(name.clone(), redacted);
+                }
+            }
+        }
+        if !self.columns.is_empty() {
+            // Listed fields missing from a row still get their column
+            fields = self.selected_columns()
+                .map(|name| (name.clone(), fields.remove(name).unwrap_or(serde_json::Value::Null)))
+                .collect();
+        }
+        if let Some(pattern) = &self.date_format {
+            for value in fields.values_mut() {
+                if let Some(formatted) = value.as_str().and_then(|text| format_timestamp(text, pattern)) {
+                    *value = serde_json::Value::String(formatted);
+                }
+            }
+        }
+
+        let shaped = fields.into_iter()
+            .map(|(name, value)| (self.output_name(&name).to_string(), value))
+            .collect();
+        serde_json::Value::Object(shaped)
+    }
+}
+
+fn format_timestamp(text: &str, pattern: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|time| time_utils::format_local(&time.with_timezone(&Utc), pattern))
+}
+
+impl ColumnType {
+    /// Type a single value would get on its own; `None` for null
+    pub(super) fn of_value(value: &serde_json::Value) -> Option<Self> {
+        use serde_json::Value;
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(ColumnType::Bool),
+            Value::Number(n) if n.is_i64() => Some(ColumnType::Int),
+            Value::Number(_) => Some(ColumnType::Float),
+            Value::String(s) if DateTime::parse_from_rfc3339(s).is_ok() => Some(ColumnType::Timestamp),
+            _ => Some(ColumnType::String),
+        }
+    }
+
+    /// Narrowest type holding values of both types
+    pub(super) fn merge(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        match (a, b) {
+            (None, other) | (other, None) => other,
+            (Some(a), Some(b)) if a == b => Some(a),
+            (Some(ColumnType::Int), Some(ColumnType::Float)) | (Some(ColumnType::Float), Some(ColumnType::Int)) => Some(ColumnType::Float),
+            _ => Some(ColumnType::String),
+        }
+    }
+}
+
+/// Type of every top-level field, `None` where a field is always null, leaving `source` rewound
+pub(super) async fn infer_column_types(source: &mut dyn RowSource) -> Result<BTreeMap<String, Option<ColumnType>>> {
+    let mut types: BTreeMap<String, Option<ColumnType>> = BTreeMap::new();
+    while let Some(page) = source.next_page().await? {
+        for row in &page {
+            if let serde_json::Value::Object(fields) = row {
+                for (name, value) in fields {
+                    let column = types.entry(name.clone()).or_default();
+                    *column = ColumnType::merge(*column, ColumnType::of_value(value));
+                }
+            }
+        }
+    }
+    source.rewind().await?;
+    Ok(types)
+}
+
+/// Applies export options to rows as pages are read
+pub(super) struct ShapedRows<'a> {
+    pub source: &'a mut dyn RowSource,
+    pub options: &'a ExportOptions,
+}
+
+#[async_trait::async_trait]
+impl RowSource for ShapedRows<'_> {
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>> {
+        let page = self.source.next_page().await?;
+        if self.options.is_default() {
+            return Ok(page);
+        }
+        Ok(page.map(|rows| rows.into_iter().map(|row| self.options.shape_row(row)).collect()))
+    }
+
+    async fn rewind(&mut self) -> Result<()> {
+        self.source.rewind().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_select_rename_and_drop_metadata() {
+        let options = ExportOptions {
+            columns: vec!["title".to_string(), "price".to_string(), "_source_url".to_string()],
+            rename_map: BTreeMap::from([("title".to_string(), "Name".to_string())]),
+            exclude_metadata: true,
+            date_format: Some("%Y-%m-%d".to_string()),
+            schema: BTreeMap::new(),
+            ..Default::default()
+        };
+        options.validate().unwrap();
+
+        let row = json!({"title": "A", "url": "u", "_source_url": "s", "listed": "2024-03-01T10:00:00Z"});
+        assert_eq!(options.shape_row(row), json!({"Name": "A", "price": null}));
+        assert_eq!(options.column_order(), vec!["Name", "price"]);
+
+        let dates = ExportOptions { date_format: Some("%Y".to_string()), ..Default::default() };
+        assert_eq!(dates.shape_row(json!({"listed": "2024-03-01T10:00:00Z"}))["listed"], "2024");
+        assert!(ExportOptions { date_format: Some("%Q".to_string()), ..Default::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::config::CompressionCodec;
+use super::{ExportFormat, ExportOptions};
+
+/// Longest profile name accepted, in characters
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+/// A saved export setup, run by hand or after every completed job of the plans naming it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportProfile {
+    /// Letters, digits, `-` and `_`
+    pub name: String,
+    /// File format; ignored for database destinations
+    #[serde(default = "default_format")]
+    pub format: String,
+    #[serde(default)]
+    pub destination: ExportDestination,
+    /// Column selection, names, redaction and splitting
+    #[serde(flatten)]
+    pub options: ExportOptions,
+    /// Compress the written file; not applied to split or database exports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<CompressionCodec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_level: Option<u32>,
+}
+
+/// Where a profile's exports are written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExportDestination {
+    /// The configured export directory, named by the filename template
+    #[default]
+    OutputDirectory,
+    /// Another directory, named the same way
+    Directory { path: PathBuf },
+    /// A table of the configured export database, upserted on the plan's dedupe keys
+    Database {
+        /// The plan's domain when unset
+        #[serde(default)]
+        table: Option<String>,
+    },
+    /// A sink registered by a plugin
+    Plugin {
+        name: String,
+        /// Passed to the sink as is
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        args: serde_json::Value,
+    },
+}
+
+fn default_format() -> String {
+    "csv".to_string()
+}
+
+impl ExportProfile {
+    /// Parsed file format
+    pub fn export_format(&self) -> Result<ExportFormat> {
+        self.format.parse()
+    }
+
+    /// Reject bad names, unknown formats, invalid options and out-of-range compression levels
+    pub fn validate(&self) -> Result<()> {
+        let name_ok = !self.name.is_empty()
+            && self.name.chars().count() <= MAX_PROFILE_NAME_LEN
+            && self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !name_ok {
+            return Err(anyhow::anyhow!(
+                "Profile name '{}' must be 1 to {} letters, digits, '-' or '_'",
+                self.name, MAX_PROFILE_NAME_LEN
+            ));
+        }
+        self.export_format()?;
+        self.options.validate()?;
+
+        if let (Some(codec), Some(level)) = (self.compression, self.compression_level) {
+            let levels = super::compression::levels(codec);
+            if !levels.contains(&level) {
+                return Err(anyhow::anyhow!(
+                    "Compression level {} is out of range {}-{} for {:?}",
+                    level, levels.start(), levels.end(), codec
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_from_yaml() {
+        let profile: ExportProfile = serde_yaml::from_str(
+            "name: nightly-db\n\
+             destination:\n  kind: database\n  table: products\n\
+             columns: [sku, price]\n\
+             redact:\n  email: hash\n"
+        ).unwrap();
+        profile.validate().unwrap();
+        assert_eq!(profile.format, "csv");
+        assert_eq!(profile.destination, ExportDestination::Database { table: Some("products".to_string()) });
+        assert_eq!(profile.options.columns, vec!["sku", "price"]);
+
+        let bad = ExportProfile { name: "no spaces".to_string(), ..profile };
+        assert!(bad.validate().is_err());
+    }
+}
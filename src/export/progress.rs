@@ -0,0 +1,90 @@
+use anyhow::Result;
+use serde::Serialize;
+use tokio::sync::watch;
+
+use super::RowSource;
+
+/// How far an export got, updated as its rows are read
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ExportProgress {
+    /// Rows to export
+    pub total: usize,
+    /// Times the export reads every row
+    pub passes: u32,
+    /// Current pass, from 1
+    pub pass: u32,
+    /// Rows read in the current pass
+    pub rows: usize,
+    /// The rows are written and the file is being compressed
+    pub compressing: bool,
+}
+
+impl ExportProgress {
+    /// Share of the export done, from 0 to 1
+    pub fn fraction(&self) -> f32 {
+        if self.compressing {
+            return 1.0;
+        }
+        let pass_done = if self.total == 0 { 1.0 } else { self.rows as f32 / self.total as f32 };
+        let done = self.pass.saturating_sub(1) as f32 + pass_done.min(1.0);
+        (done / self.passes.max(1) as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// Rows of another source, reporting how many were read on a watch channel
+pub struct ProgressRows<'a> {
+    source: &'a mut dyn RowSource,
+    progress: &'a watch::Sender<ExportProgress>,
+}
+
+impl<'a> ProgressRows<'a> {
+    pub fn new(source: &'a mut dyn RowSource, progress: &'a watch::Sender<ExportProgress>, total: usize, passes: u32) -> Self {
+        progress.send_replace(ExportProgress { total, passes, pass: 1, rows: 0, compressing: false });
+        Self { source, progress }
+    }
+}
+
+#[async_trait::async_trait]
+impl RowSource for ProgressRows<'_> {
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>> {
+        let page = self.source.next_page().await?;
+        if let Some(rows) = &page {
+            self.progress.send_modify(|progress| progress.rows += rows.len());
+        }
+        Ok(page)
+    }
+
+    async fn rewind(&mut self) -> Result<()> {
+        self.source.rewind().await?;
+        self.progress.send_modify(|progress| {
+            progress.pass += 1;
+            progress.rows = 0;
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::SliceRows;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_progress_counts_rows_per_pass() {
+        let rows: Vec<_> = (0..4).map(|i| json!({"n": i})).collect();
+        let (tx, rx) = watch::channel(ExportProgress::default());
+        let mut inner = SliceRows::new(&rows);
+        let mut source = ProgressRows::new(&mut inner, &tx, rows.len(), 2);
+
+        while source.next_page().await.unwrap().is_some() {}
+        assert_eq!(rx.borrow().rows, 4);
+        assert_eq!(rx.borrow().fraction(), 0.5);
+
+        source.rewind().await.unwrap();
+        source.next_page().await.unwrap();
+        let progress = rx.borrow().clone();
+        assert_eq!((progress.pass, progress.rows), (2, 4));
+        assert_eq!(progress.fraction(), 1.0);
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+
+use crate::storage::ResultCursor;
+use super::DataTransformer;
+
+/// Rows given to an exporter at a time
+pub const EXPORT_PAGE_SIZE: usize = 1000;
+
+/// Rows to export, read a page at a time
+///
+/// Tabular formats read the rows twice, once to find the columns and once to
+/// write them, so a source must be able to start over.
+#[async_trait::async_trait]
+pub trait RowSource: Send {
+    /// Next page of rows, or `None` once every row was returned
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>>;
+
+    /// Start again from the first row
+    async fn rewind(&mut self) -> Result<()>;
+}
+
+/// Rows already in memory
+pub struct SliceRows<'a> {
+    rows: &'a [serde_json::Value],
+    position: usize,
+}
+
+impl<'a> SliceRows<'a> {
+    pub fn new(rows: &'a [serde_json::Value]) -> Self {
+        Self { rows, position: 0 }
+    }
+}
+
+#[async_trait::async_trait]
+impl RowSource for SliceRows<'_> {
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>> {
+        if self.position >= self.rows.len() {
+            return Ok(None);
+        }
+        let end = (self.position + EXPORT_PAGE_SIZE).min(self.rows.len());
+        let page = self.rows[self.position..end].to_vec();
+        self.position = end;
+        Ok(Some(page))
+    }
+
+    async fn rewind(&mut self) -> Result<()> {
+        self.position = 0;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RowSource for ResultCursor<'_> {
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>> {
+        ResultCursor::next_page(self).await
+    }
+
+    async fn rewind(&mut self) -> Result<()> {
+        ResultCursor::rewind(self);
+        Ok(())
+    }
+}
+
+/// Rewrites stored UTC timestamps into the configured zone as pages are read
+pub(super) struct LocalizedRows<'a>(pub &'a mut dyn RowSource);
+
+#[async_trait::async_trait]
+impl RowSource for LocalizedRows<'_> {
+    async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>> {
+        let mut page = self.0.next_page().await?;
+        if let Some(rows) = &mut page {
+            DataTransformer::localize_timestamps(rows);
+        }
+        Ok(page)
+    }
+
+    async fn rewind(&mut self) -> Result<()> {
+        self.0.rewind().await
+    }
+}
+
+/// Read every remaining row into memory, for formats that can't be written page by page
+pub(super) async fn collect_rows(source: &mut dyn RowSource) -> Result<Vec<serde_json::Value>> {
+    let mut rows = Vec::new();
+    while let Some(page) = source.next_page().await? {
+        rows.extend(page);
+    }
+    Ok(rows)
+}
@@ -0,0 +1,80 @@
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Rows taken when no sample size is given
+pub const DEFAULT_SAMPLE_ROWS: usize = 50;
+
+/// How to cut a shareable sample out of a job's results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleOptions {
+    /// Number of rows picked at random
+    pub rows: usize,
+    /// Columns removed from every row
+    #[serde(default)]
+    pub drop_columns: Vec<String>,
+    /// Columns whose values are shuffled between the sampled rows
+    #[serde(default)]
+    pub shuffle_columns: Vec<String>,
+    /// Keep `_`-prefixed metadata such as `_source_url`
+    #[serde(default)]
+    pub keep_metadata: bool,
+    /// Seed for picking and shuffling, for a reproducible sample
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        Self {
+            rows: DEFAULT_SAMPLE_ROWS,
+            drop_columns: Vec::new(),
+            shuffle_columns: Vec::new(),
+            keep_metadata: false,
+            seed: None,
+        }
+    }
+}
+
+/// Pick random rows and drop and shuffle columns as configured
+///
+/// Rows keep their original order. Redaction of sensitive values is left to
+/// the caller.
+pub fn build_sample(data: &[Value], options: &SampleOptions) -> Vec<Value> {
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut picked = index::sample(&mut rng, data.len(), options.rows.min(data.len())).into_vec();
+    picked.sort_unstable();
+    let mut sample: Vec<Value> = picked.into_iter().map(|i| data[i].clone()).collect();
+
+    for row in sample.iter_mut() {
+        if let Value::Object(obj) = row {
+            obj.retain(|key, _| {
+                !options.drop_columns.contains(key) && (options.keep_metadata || !key.starts_with('_'))
+            });
+        }
+    }
+
+    for column in &options.shuffle_columns {
+        let mut values: Vec<Value> = sample.iter()
+            .filter_map(|row| row.get(column).cloned())
+            .collect();
+        values.shuffle(&mut rng);
+
+        let mut values = values.into_iter();
+        for row in sample.iter_mut() {
+            if let Some(slot) = row.get_mut(column) {
+                if let Some(value) = values.next() {
+                    *slot = value;
+                }
+            }
+        }
+    }
+
+    sample
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use super::{ArchiveEntry, ArchiveManifest, ExportFormat, ExportManager, ExportOptions, RowSource, SliceRows};
+
+/// Part files and manifest of a split export
+#[derive(Debug, Clone)]
+pub struct SplitExport {
+    pub manifest_path: PathBuf,
+    pub manifest: ArchiveManifest,
+}
+
+/// Path of part `number` of an export to `output_path`, e.g. `results_part_001.csv`
+pub fn part_path(output_path: &Path, number: usize) -> PathBuf {
+    let stem = output_path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match output_path.extension() {
+        Some(ext) => format!("{}_part_{:03}.{}", stem, number, ext.to_string_lossy()),
+        None => format!("{}_part_{:03}", stem, number),
+    };
+    output_path.with_file_name(name)
+}
+
+/// Path of the manifest listing the parts of an export to `output_path`
+pub fn manifest_path(output_path: &Path) -> PathBuf {
+    let stem = output_path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    output_path.with_file_name(format!("{}_manifest.json", stem))
+}
+
+impl ExportManager {
+    /// Export rows into part files of at most `max_rows_per_file` rows or `max_mb_per_file` megabytes
+    ///
+    /// Parts are named after `output_path` with a `_part_001` suffix and listed,
+    /// with their row counts and checksums, in a `_manifest.json` next to them.
+    /// Part sizes are estimated from the rows' JSON size, so the megabyte limit
+    /// is approximate. Each part is written like a standalone export, so the
+    /// size check on single exports doesn't apply.
+    pub async fn export_split(
+        &self,
+        source: &mut dyn RowSource,
+        output_path: &str,
+        format: ExportFormat,
+        options: &ExportOptions,
+    ) -> Result<SplitExport> {
+        options.validate()?;
+        let output_path = Path::new(output_path);
+        let max_rows = options.max_rows_per_file.unwrap_or(usize::MAX);
+        let max_bytes = options.max_mb_per_file.map(|mb| mb * 1024 * 1024).unwrap_or(u64::MAX);
+
+        let mut manifest = ArchiveManifest { created_at: Utc::now(), files: Vec::new() };
+        let mut pending = Vec::new();
+        let mut pending_bytes = 0;
+        while let Some(page) = source.next_page().await? {
+            for row in page {
+                let size = serde_json::to_vec(&row).map(|bytes| bytes.len() as u64 + 1).unwrap_or(0);
+                if !pending.is_empty() && (pending.len() >= max_rows || pending_bytes + size > max_bytes) {
+                    let entry = self.write_part(&pending, output_path, manifest.files.len() + 1, &format, options).await?;
+                    manifest.files.push(entry);
+                    pending.clear();
+                    pending_bytes = 0;
+                }
+                pending_bytes += size;
+                pending.push(row);
+            }
+        }
+        // An empty export still gets one (empty) part
+        if !pending.is_empty() || manifest.files.is_empty() {
+            let entry = self.write_part(&pending, output_path, manifest.files.len() + 1, &format, options).await?;
+            manifest.files.push(entry);
+        }
+
+        let manifest_path = manifest_path(output_path);
+        tokio::fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?).await?;
+        info!("Split export into {} files, listed in {}", manifest.files.len(), manifest_path.display());
+        Ok(SplitExport { manifest_path, manifest })
+    }
+
+    async fn write_part(
+        &self,
+        rows: &[serde_json::Value],
+        output_path: &Path,
+        number: usize,
+        format: &ExportFormat,
+        options: &ExportOptions,
+    ) -> Result<ArchiveEntry> {
+        let path = part_path(output_path, number);
+        let stats = self.export_rows(&mut SliceRows::new(rows), &path.to_string_lossy(), format.clone(), options).await?;
+        let format = format.to_string();
+        tokio::task::spawn_blocking(move || ArchiveEntry::for_file(&path, format, stats.record_count)).await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_export_is_split_by_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = crate::config::AppConfig::default().export;
+        config.output_directory = dir.path().to_path_buf();
+        let manager = ExportManager::new(&config).unwrap();
+
+        let rows: Vec<_> = (0..5).map(|i| json!({"n": i})).collect();
+        let options = ExportOptions { max_rows_per_file: Some(2), ..Default::default() };
+        let output = dir.path().join("results.csv");
+        let split = manager.export_split(&mut SliceRows::new(&rows), &output.to_string_lossy(), ExportFormat::Csv, &options)
+            .await
+            .unwrap();
+
+        let counts: Vec<usize> = split.manifest.files.iter().map(|file| file.record_count).collect();
+        assert_eq!(counts, vec![2, 2, 1]);
+        assert_eq!(split.manifest.files[2].name, "results_part_003.csv");
+        assert!(dir.path().join("results_part_001.csv").exists());
+        assert_eq!(split.manifest_path, dir.path().join("results_manifest.json"));
+    }
+}
@@ -1,10 +1,13 @@
 use anyhow::Result;
-use tracing::debug;
+use tracing::{debug, info};
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncWriteExt, BufWriter};
 
 use crate::config::ExportConfig;
-use super::{InternalExportStats, DataTransformer};
+use super::{InternalExportStats, DataTransformer, RowSource, SliceRows};
+
+/// Data rows Excel can show on one sheet, below the header row
+pub const MAX_SHEET_ROWS: usize = 1_048_575;
 
 /// Export data to XLSX format
 /// Note: This creates a simplified Excel XML format
@@ -12,59 +15,104 @@ use super::{InternalExportStats, DataTransformer};
 pub async fn export_xlsx(
     data: &[serde_json::Value],
     output_path: &str,
-    _config: &ExportConfig,
+    config: &ExportConfig,
 ) -> Result<InternalExportStats> {
-    debug!("Exporting {} records to XLSX: {}", data.len(), output_path);
+    let (stats, _) = export_xlsx_rows(&mut SliceRows::new(data), output_path, config, &[], None).await?;
+    Ok(stats)
+}
+
+/// Export rows to XLSX format a page at a time
+///
+/// Starts a new sheet, with its own header row, every
+/// `config.xlsx_sheet_rows` rows. Sheets are named `sheet_name`, `sheet_name (2)`…
+/// or `Sheet1`, `Sheet2`… Columns are ordered as for CSV. Returns the number
+/// of records written along with the stats.
+pub async fn export_xlsx_rows(
+    source: &mut dyn RowSource,
+    output_path: &str,
+    config: &ExportConfig,
+    column_order: &[String],
+    sheet_name: Option<&str>,
+) -> Result<(InternalExportStats, usize)> {
+    debug!("Exporting records to XLSX: {}", output_path);
     
-    if data.is_empty() {
-        return create_empty_xlsx(output_path).await;
+    let column_names = super::csv_exporter::flattened_columns(source, column_order).await?;
+    if column_names.is_empty() {
+        return Ok((create_empty_xlsx(output_path).await?, 0));
     }
+    let sheet_rows = config.xlsx_sheet_rows.clamp(1, MAX_SHEET_ROWS);
     
-    // Flatten JSON data for tabular format
-    let flattened_data = DataTransformer::flatten_json(data)?;
-    let column_names = DataTransformer::get_column_names(data);
-    
-    let mut file = File::create(output_path).await?;
+    let mut file = BufWriter::new(File::create(output_path).await?);
     
     // Write Excel XML format (simplified)
     file.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n").await?;
     file.write_all(b"<Workbook xmlns=\"urn:schemas-microsoft-com:office:spreadsheet\"\n").await?;
     file.write_all(b"          xmlns:ss=\"urn:schemas-microsoft-com:office:spreadsheet\">\n").await?;
-    file.write_all(b"  <Worksheet ss:Name=\"Sheet1\">\n").await?;
-    file.write_all(b"    <Table>\n").await?;
-    
-    // Write header row
-    file.write_all(b"      <Row>").await?;
-    for column in &column_names {
-        let escaped_column = html_escape::encode_text(column);
-        file.write_all(format!("<Cell><Data ss:Type=\"String\">{}</Data></Cell>", escaped_column).as_bytes()).await?;
-    }
-    file.write_all(b"</Row>\n").await?;
     
-    // Write data rows
-    for row in &flattened_data {
-        file.write_all(b"      <Row>").await?;
-        for column in &column_names {
-            let empty_string = String::new();
-            let value = row.get(column).unwrap_or(&empty_string);
-            let escaped_value = html_escape::encode_text(value);
-            file.write_all(format!("<Cell><Data ss:Type=\"String\">{}</Data></Cell>", escaped_value).as_bytes()).await?;
+    let mut record_count = 0;
+    let mut sheets = 0;
+    while let Some(page) = source.next_page().await? {
+        for row in DataTransformer::flatten_json(&page)? {
+            if record_count % sheet_rows == 0 {
+                if sheets > 0 {
+                    end_sheet(&mut file).await?;
+                }
+                sheets += 1;
+                let name = match sheet_name {
+                    Some(name) if sheets == 1 => name.to_string(),
+                    Some(name) => format!("{} ({})", name, sheets),
+                    None => format!("Sheet{}", sheets),
+                };
+                start_sheet(&mut file, &name, &column_names).await?;
+            }
+            
+            file.write_all(b"      <Row>").await?;
+            for column in &column_names {
+                let value = row.get(column).map(String::as_str).unwrap_or_default();
+                let escaped_value = html_escape::encode_text(value);
+                file.write_all(format!("<Cell><Data ss:Type=\"String\">{}</Data></Cell>", escaped_value).as_bytes()).await?;
+            }
+            file.write_all(b"</Row>\n").await?;
+            record_count += 1;
         }
-        file.write_all(b"</Row>\n").await?;
     }
     
-    file.write_all(b"    </Table>\n").await?;
-    file.write_all(b"  </Worksheet>\n").await?;
+    end_sheet(&mut file).await?;
     file.write_all(b"</Workbook>\n").await?;
     
     file.flush().await?;
     
     let file_size = tokio::fs::metadata(output_path).await?.len();
+    info!("XLSX export completed: {} records on {} sheets, {} bytes", record_count, sheets, file_size);
     
-    Ok(InternalExportStats {
-        file_size_bytes: file_size,
-        compression_ratio: None,
-    })
+    Ok((
+        InternalExportStats {
+            file_size_bytes: file_size,
+            compression_ratio: None,
+        },
+        record_count,
+    ))
+}
+
+/// Open a worksheet and write its header row
+async fn start_sheet(file: &mut BufWriter<File>, name: &str, column_names: &[String]) -> Result<()> {
+    let name = html_escape::encode_double_quoted_attribute(name);
+    file.write_all(format!("  <Worksheet ss:Name=\"{}\">\n", name).as_bytes()).await?;
+    file.write_all(b"    <Table>\n").await?;
+    
+    file.write_all(b"      <Row>").await?;
+    for column in column_names {
+        let escaped_column = html_escape::encode_text(column);
+        file.write_all(format!("<Cell><Data ss:Type=\"String\">{}</Data></Cell>", escaped_column).as_bytes()).await?;
+    }
+    file.write_all(b"</Row>\n").await?;
+    Ok(())
+}
+
+async fn end_sheet(file: &mut BufWriter<File>) -> Result<()> {
+    file.write_all(b"    </Table>\n").await?;
+    file.write_all(b"  </Worksheet>\n").await?;
+    Ok(())
 }
 
 async fn create_empty_xlsx(output_path: &str) -> Result<InternalExportStats> {
@@ -89,3 +137,27 @@ async fn create_empty_xlsx(output_path: &str) -> Result<InternalExportStats> {
         compression_ratio: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_rows_split_across_sheets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.xlsx");
+        let mut config = crate::config::AppConfig::default().export;
+        config.xlsx_sheet_rows = 2;
+        let data: Vec<_> = (0..5).map(|n| json!({"n": n, "name": "a&b"})).collect();
+
+        let (_, count) = export_xlsx_rows(&mut SliceRows::new(&data), path.to_str().unwrap(), &config, &[], None).await.unwrap();
+
+        assert_eq!(count, 5);
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.matches("<Worksheet").count(), 3);
+        assert_eq!(contents.matches("</Worksheet>").count(), 3);
+        assert_eq!(contents.matches(">name<").count(), 3);
+        assert!(contents.contains("a&amp;b"));
+    }
+}
@@ -1,9 +1,20 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime};
 use anyhow::Result;
 use tracing::{info, warn};
 
+/// Translations built into the binary, one JSON object of key → text per language
+const EMBEDDED_TRANSLATIONS: [(Language, &str); 2] = [
+    (Language::English, include_str!("locales/en.json")),
+    (Language::Korean, include_str!("locales/ko.json")),
+];
+
+/// How often the translation override directory is checked for changes
+const OVERRIDE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 /// Supported languages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Language {
@@ -46,6 +57,20 @@ impl Language {
         }
     }
 
+    /// Name of the language in English, as told to the language model
+    pub fn english_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Korean => "Korean",
+            Language::Japanese => "Japanese",
+            Language::Chinese => "Chinese",
+            Language::Spanish => "Spanish",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Russian => "Russian",
+        }
+    }
+
     /// Get all supported languages
     pub fn all() -> Vec<Language> {
         vec![
@@ -76,6 +101,74 @@ impl Language {
     }
 }
 
+/// CLDR plural category of a count, selecting the `<key>.<category>` translation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralCategory {
+    One,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Suffix of the translation key holding this form
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            PluralCategory::One => "one",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+impl Language {
+    /// Plural categories whole numbers fall into in this language
+    pub fn plural_categories(&self) -> Vec<PluralCategory> {
+        [PluralCategory::One, PluralCategory::Few, PluralCategory::Many, PluralCategory::Other]
+            .into_iter()
+            .filter(|category| (0..=200).any(|count| self.plural_category(count) == *category))
+            .collect()
+    }
+
+    /// CLDR plural category of a whole number in this language
+    pub fn plural_category(&self, count: u64) -> PluralCategory {
+        match self {
+            Language::Korean | Language::Japanese | Language::Chinese => PluralCategory::Other,
+            Language::French if count <= 1 => PluralCategory::One,
+            Language::Russian => match (count % 10, count % 100) {
+                (1, rem) if rem != 11 => PluralCategory::One,
+                (2..=4, rem) if !(12..=14).contains(&rem) => PluralCategory::Few,
+                _ => PluralCategory::Many,
+            },
+            _ if count == 1 => PluralCategory::One,
+            _ => PluralCategory::Other,
+        }
+    }
+}
+
+/// Key a plural form belongs to, if the key names one like `jobs.count.other`
+fn plural_base(key: &str) -> Option<&str> {
+    let (base, suffix) = key.rsplit_once('.')?;
+    matches!(suffix, "one" | "few" | "many" | "other").then_some(base)
+}
+
+/// How completely a language is translated, compared with English
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslationReport {
+    /// Language code
+    pub language: String,
+    pub name: String,
+    /// Keys the language needs: every English key, with plural keys in the language's own forms
+    pub total: usize,
+    pub translated: usize,
+    pub percent_complete: f64,
+    /// Needed keys without a translation
+    pub missing: Vec<String>,
+    /// Keys English does not have, usually renamed or mistyped
+    pub extra: Vec<String>,
+}
+
 impl Default for Language {
     fn default() -> Self {
         Language::English
@@ -174,6 +267,115 @@ impl I18nManager {
         key.to_string()
     }
 
+    /// Get translation for current language with `{name}` placeholders filled in
+    pub fn t_args(&self, key: &str, args: &[(&str, String)]) -> String {
+        let mut text = self.t(key);
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    /// Get the form of a translation matching a count, with `{count}` and other placeholders filled in
+    ///
+    /// Looks up `<key>.<category>` for the count's plural category in the current
+    /// language, then `<key>.other`, then `<key>` itself.
+    pub fn t_plural(&self, key: &str, count: u64, args: &[(&str, String)]) -> String {
+        let category = self.current_language.plural_category(count);
+        let template = [format!("{}.{}", key, category.suffix()), format!("{}.other", key)]
+            .into_iter()
+            .find_map(|form| self.lookup(&form, self.current_language))
+            .unwrap_or_else(|| self.t(key));
+
+        let mut text = template.replace("{count}", &count.to_string());
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    /// A translation in a language, or in the fallback language, without warning when missing
+    fn lookup(&self, key: &str, language: Language) -> Option<String> {
+        [language, self.fallback_language].iter()
+            .filter_map(|language| self.translations.get(language))
+            .find_map(|translations| translations.get_translation(key))
+            .cloned()
+    }
+
+    /// Keys a language needs, each with the English text to translate
+    ///
+    /// Plural keys are listed in the language's own plural forms, using the
+    /// English `other` form where English has no matching one.
+    fn required_keys(&self, language: Language) -> BTreeMap<String, String> {
+        let Some(english) = self.translations.get(&self.fallback_language) else { return BTreeMap::new() };
+
+        let mut required = BTreeMap::new();
+        for (key, text) in &english.translations {
+            match plural_base(key) {
+                Some(base) => {
+                    for category in language.plural_categories() {
+                        let form = format!("{}.{}", base, category.suffix());
+                        let text = english.get_translation(&form)
+                            .or_else(|| english.get_translation(&format!("{}.other", base)))
+                            .unwrap_or(text);
+                        required.insert(form, text.clone());
+                    }
+                }
+                None => {
+                    required.insert(key.clone(), text.clone());
+                }
+            }
+        }
+        required
+    }
+
+    /// Compare a language's translations against English
+    pub fn completeness_report(&self, language: Language) -> TranslationReport {
+        let required = self.required_keys(language);
+        let translated: BTreeSet<&String> = self.translations.get(&language)
+            .map(|translations| translations.translations.keys().collect())
+            .unwrap_or_default();
+        let english_bases: BTreeSet<&str> = required.keys().filter_map(|key| plural_base(key)).collect();
+
+        let missing: Vec<String> = required.keys().filter(|key| !translated.contains(key)).cloned().collect();
+        let extra: Vec<String> = translated.iter()
+            .filter(|key| !required.contains_key(key.as_str()))
+            .filter(|key| !plural_base(key).is_some_and(|base| english_bases.contains(base)))
+            .map(|key| key.to_string())
+            .collect();
+
+        let total = required.len();
+        let done = total - missing.len();
+        TranslationReport {
+            language: language.code().to_string(),
+            name: language.name().to_string(),
+            total,
+            translated: done,
+            percent_complete: if total > 0 { done as f64 * 100.0 / total as f64 } else { 100.0 },
+            missing,
+            extra,
+        }
+    }
+
+    /// Completeness of every supported language other than English
+    pub fn completeness_reports(&self) -> Vec<TranslationReport> {
+        Language::all().into_iter()
+            .filter(|language| *language != self.fallback_language)
+            .map(|language| self.completeness_report(language))
+            .collect()
+    }
+
+    /// Untranslated keys of a language with their English text, as a stub for translators
+    ///
+    /// The result is in the format of the override files, so a translator can fill
+    /// it in and drop it into the translations directory.
+    pub fn missing_translations(&self, language: Language) -> BTreeMap<String, String> {
+        let translations = self.translations.get(&language);
+        self.required_keys(language).into_iter()
+            .filter(|(key, _)| translations.and_then(|t| t.get_translation(key)).is_none())
+            .collect()
+    }
+
     /// Load translations from file
     pub fn load_translations_from_file(&mut self, language: Language, file_path: &str) -> Result<()> {
         let content = std::fs::read_to_string(file_path)?;
@@ -201,247 +403,62 @@ impl I18nManager {
         Ok(())
     }
 
-    /// Load default translations
+    /// Load the translations built into the binary
     fn load_default_translations(&mut self) {
-        // English translations
-        let mut english = LanguageTranslations::new(Language::English);
-        english.add_translation("app.title", "WinScrape Studio".to_string());
-        english.add_translation("app.version", "Version".to_string());
-        english.add_translation("app.description", "A natural language web scraping tool".to_string());
-        english.add_translation("app.website", "Website".to_string());
-        english.add_translation("app.support", "Support".to_string());
-        
-        // Navigation
-        english.add_translation("nav.chat", "Chat".to_string());
-        english.add_translation("nav.jobs", "Jobs".to_string());
-        english.add_translation("nav.results", "Results".to_string());
-        english.add_translation("nav.settings", "Settings".to_string());
-        english.add_translation("nav.help", "Help".to_string());
-        
-        // Chat interface
-        english.add_translation("chat.title", "Natural Language Scraping".to_string());
-        english.add_translation("chat.description", "Describe what you want to scrape in plain English. The AI will generate a scraping plan for you.".to_string());
-        english.add_translation("chat.input_placeholder", "Describe what you want to scrape...".to_string());
-        english.add_translation("chat.send", "Send".to_string());
-        english.add_translation("chat.examples", "Examples".to_string());
-        english.add_translation("chat.try_examples", "Try these examples:".to_string());
-        
-        // Jobs
-        english.add_translation("jobs.title", "Scraping Jobs".to_string());
-        english.add_translation("jobs.no_jobs", "No Jobs Yet".to_string());
-        english.add_translation("jobs.no_jobs_description", "Start by describing what you want to scrape in the Chat tab.".to_string());
-        english.add_translation("jobs.go_to_chat", "Go to Chat".to_string());
-        english.add_translation("jobs.status.running", "Running".to_string());
-        english.add_translation("jobs.status.completed", "Completed".to_string());
-        english.add_translation("jobs.status.failed", "Failed".to_string());
-        english.add_translation("jobs.status.queued", "Queued".to_string());
-        english.add_translation("jobs.status.cancelled", "Cancelled".to_string());
-        
-        // Settings
-        english.add_translation("settings.title", "Settings & Configuration".to_string());
-        english.add_translation("settings.general", "General Settings".to_string());
-        english.add_translation("settings.theme", "Theme".to_string());
-        english.add_translation("settings.theme.dark", "Dark".to_string());
-        english.add_translation("settings.theme.light", "Light".to_string());
-        english.add_translation("settings.language", "Language".to_string());
-        english.add_translation("settings.auto_save", "Auto-save settings".to_string());
-        english.add_translation("settings.notifications", "Show notifications".to_string());
-        english.add_translation("settings.minimize_to_tray", "Minimize to system tray".to_string());
-        english.add_translation("settings.icon_theme", "Icon Theme".to_string());
-        english.add_translation("settings.icon_theme.default", "Default".to_string());
-        english.add_translation("settings.icon_theme.minimal", "Minimal".to_string());
-        english.add_translation("settings.icon_theme.colorful", "Colorful".to_string());
-        english.add_translation("settings.icon_theme.monochrome", "Monochrome".to_string());
-        english.add_translation("settings.icon_theme.custom", "Custom".to_string());
-        
-        // Scraping settings
-        english.add_translation("settings.scraping", "Scraping Settings".to_string());
-        english.add_translation("settings.max_concurrent", "Max concurrent requests".to_string());
-        english.add_translation("settings.timeout", "Request timeout (seconds)".to_string());
-        english.add_translation("settings.respect_robots", "Respect robots.txt".to_string());
-        english.add_translation("settings.browser_fallback", "Enable browser fallback".to_string());
-        
-        // Export settings
-        english.add_translation("settings.export", "Export Settings".to_string());
-        
-        // Common UI elements
-        english.add_translation("button.save", "Save".to_string());
-        english.add_translation("button.cancel", "Cancel".to_string());
-        english.add_translation("button.reset", "Reset".to_string());
-        english.add_translation("button.apply", "Apply".to_string());
-        english.add_translation("button.ok", "OK".to_string());
-        english.add_translation("button.yes", "Yes".to_string());
-        english.add_translation("button.no", "No".to_string());
-        english.add_translation("status.ready", "Ready".to_string());
-        english.add_translation("status.running", "Running".to_string());
-        english.add_translation("status.completed", "Completed".to_string());
-        english.add_translation("status.error", "Error".to_string());
-        english.add_translation("status.paused", "Paused".to_string());
-        english.add_translation("settings.default_format", "Default export format".to_string());
-        english.add_translation("settings.include_metadata", "Include metadata in exports".to_string());
-        english.add_translation("settings.compress_exports", "Compress large exports".to_string());
-        
-        // Security settings
-        english.add_translation("settings.security", "Security Settings".to_string());
-        english.add_translation("settings.input_validation", "Enable input validation".to_string());
-        english.add_translation("settings.output_filtering", "Filter sensitive data from output".to_string());
-        english.add_translation("settings.blocked_domains", "Blocked domains:".to_string());
-        
-        // Help
-        english.add_translation("help.title", "Help & Documentation".to_string());
-        english.add_translation("help.getting_started", "Getting Started".to_string());
-        english.add_translation("help.step1", "1. Go to the Chat tab".to_string());
-        english.add_translation("help.step2", "2. Describe what you want to scrape in plain English".to_string());
-        english.add_translation("help.step3", "3. Review the generated scraping plan".to_string());
-        english.add_translation("help.step4", "4. Approve and run the scraping job".to_string());
-        english.add_translation("help.step5", "5. Export your results".to_string());
-        english.add_translation("help.examples", "Example Requests".to_string());
-        english.add_translation("help.features", "Features".to_string());
-        english.add_translation("help.about", "About".to_string());
-        
-        // Common actions
-        english.add_translation("action.save", "Save".to_string());
-        english.add_translation("action.cancel", "Cancel".to_string());
-        english.add_translation("action.close", "Close".to_string());
-        english.add_translation("action.ok", "OK".to_string());
-        english.add_translation("action.yes", "Yes".to_string());
-        english.add_translation("action.no", "No".to_string());
-        english.add_translation("action.export", "Export".to_string());
-        english.add_translation("action.import", "Import".to_string());
-        english.add_translation("action.refresh", "Refresh".to_string());
-        english.add_translation("action.delete", "Delete".to_string());
-        english.add_translation("action.edit", "Edit".to_string());
-        english.add_translation("action.view", "View".to_string());
-        
-        // Notifications
-        english.add_translation("notification.success", "Success".to_string());
-        english.add_translation("notification.error", "Error".to_string());
-        english.add_translation("notification.warning", "Warning".to_string());
-        english.add_translation("notification.info", "Information".to_string());
-        
-        self.translations.insert(Language::English, english);
-
-        // Korean translations
-        let mut korean = LanguageTranslations::new(Language::Korean);
-        korean.add_translation("app.title", "WinScrape Studio".to_string());
-        korean.add_translation("app.version", "버전".to_string());
-        korean.add_translation("app.description", "자연어 웹 스크래핑 도구".to_string());
-        korean.add_translation("app.website", "웹사이트".to_string());
-        korean.add_translation("app.support", "지원".to_string());
-        
-        // Navigation
-        korean.add_translation("nav.chat", "채팅".to_string());
-        korean.add_translation("nav.jobs", "작업".to_string());
-        korean.add_translation("nav.results", "결과".to_string());
-        korean.add_translation("nav.settings", "설정".to_string());
-        korean.add_translation("nav.help", "도움말".to_string());
-        
-        // Chat interface
-        korean.add_translation("chat.title", "자연어 스크래핑".to_string());
-        korean.add_translation("chat.description", "스크래핑하고 싶은 내용을 평범한 한국어로 설명하세요. AI가 스크래핑 계획을 생성해드립니다.".to_string());
-        korean.add_translation("chat.input_placeholder", "스크래핑하고 싶은 내용을 설명하세요...".to_string());
-        korean.add_translation("chat.send", "전송".to_string());
-        korean.add_translation("chat.examples", "예시".to_string());
-        korean.add_translation("chat.try_examples", "다음 예시를 시도해보세요:".to_string());
-        
-        // Jobs
-        korean.add_translation("jobs.title", "스크래핑 작업".to_string());
-        korean.add_translation("jobs.no_jobs", "작업이 없습니다".to_string());
-        korean.add_translation("jobs.no_jobs_description", "채팅 탭에서 스크래핑하고 싶은 내용을 설명하여 시작하세요.".to_string());
-        korean.add_translation("jobs.go_to_chat", "채팅으로 이동".to_string());
-        korean.add_translation("jobs.status.running", "실행 중".to_string());
-        korean.add_translation("jobs.status.completed", "완료됨".to_string());
-        korean.add_translation("jobs.status.failed", "실패함".to_string());
-        korean.add_translation("jobs.status.queued", "대기 중".to_string());
-        korean.add_translation("jobs.status.cancelled", "취소됨".to_string());
-        
-        // Settings
-        korean.add_translation("settings.title", "설정 및 구성".to_string());
-        korean.add_translation("settings.general", "일반 설정".to_string());
-        korean.add_translation("settings.theme", "테마".to_string());
-        korean.add_translation("settings.theme.dark", "다크".to_string());
-        korean.add_translation("settings.theme.light", "라이트".to_string());
-        korean.add_translation("settings.language", "언어".to_string());
-        korean.add_translation("settings.auto_save", "설정 자동 저장".to_string());
-        korean.add_translation("settings.notifications", "알림 표시".to_string());
-        korean.add_translation("settings.minimize_to_tray", "시스템 트레이로 최소화".to_string());
-        korean.add_translation("settings.icon_theme", "아이콘 테마".to_string());
-        korean.add_translation("settings.icon_theme.default", "기본값".to_string());
-        korean.add_translation("settings.icon_theme.minimal", "미니멀".to_string());
-        korean.add_translation("settings.icon_theme.colorful", "컬러풀".to_string());
-        korean.add_translation("settings.icon_theme.monochrome", "모노크롬".to_string());
-        korean.add_translation("settings.icon_theme.custom", "사용자 정의".to_string());
-        
-        // Scraping settings
-        korean.add_translation("settings.scraping", "스크래핑 설정".to_string());
-        korean.add_translation("settings.max_concurrent", "최대 동시 요청 수".to_string());
-        korean.add_translation("settings.timeout", "요청 시간 제한 (초)".to_string());
-        korean.add_translation("settings.respect_robots", "robots.txt 준수".to_string());
-        korean.add_translation("settings.browser_fallback", "브라우저 폴백 활성화".to_string());
-        
-        // Export settings
-        korean.add_translation("settings.export", "내보내기 설정".to_string());
-        korean.add_translation("settings.default_format", "기본 내보내기 형식".to_string());
-        korean.add_translation("settings.include_metadata", "내보내기에 메타데이터 포함".to_string());
-        korean.add_translation("settings.compress_exports", "대용량 내보내기 압축".to_string());
-        
-        // Common UI elements
-        korean.add_translation("button.save", "저장".to_string());
-        korean.add_translation("button.cancel", "취소".to_string());
-        korean.add_translation("button.reset", "재설정".to_string());
-        korean.add_translation("button.apply", "적용".to_string());
-        korean.add_translation("button.ok", "확인".to_string());
-        korean.add_translation("button.yes", "예".to_string());
-        korean.add_translation("button.no", "아니오".to_string());
-        korean.add_translation("status.ready", "준비됨".to_string());
-        korean.add_translation("status.running", "실행 중".to_string());
-        korean.add_translation("status.completed", "완료됨".to_string());
-        korean.add_translation("status.error", "오류".to_string());
-        korean.add_translation("status.paused", "일시정지됨".to_string());
-        
-        // Security settings
-        korean.add_translation("settings.security", "보안 설정".to_string());
-        korean.add_translation("settings.input_validation", "입력 검증 활성화".to_string());
-        korean.add_translation("settings.output_filtering", "출력에서 민감한 데이터 필터링".to_string());
-        korean.add_translation("settings.blocked_domains", "차단된 도메인:".to_string());
-        
-        // Help
-        korean.add_translation("help.title", "도움말 및 문서".to_string());
-        korean.add_translation("help.getting_started", "시작하기".to_string());
-        korean.add_translation("help.step1", "1. 채팅 탭으로 이동".to_string());
-        korean.add_translation("help.step2", "2. 스크래핑하고 싶은 내용을 평범한 한국어로 설명".to_string());
-        korean.add_translation("help.step3", "3. 생성된 스크래핑 계획 검토".to_string());
-        korean.add_translation("help.step4", "4. 스크래핑 작업 승인 및 실행".to_string());
-        korean.add_translation("help.step5", "5. 결과 내보내기".to_string());
-        korean.add_translation("help.examples", "요청 예시".to_string());
-        korean.add_translation("help.features", "기능".to_string());
-        korean.add_translation("help.about", "정보".to_string());
-        
-        // Common actions
-        korean.add_translation("action.save", "저장".to_string());
-        korean.add_translation("action.cancel", "취소".to_string());
-        korean.add_translation("action.close", "닫기".to_string());
-        korean.add_translation("action.ok", "확인".to_string());
-        korean.add_translation("action.yes", "예".to_string());
-        korean.add_translation("action.no", "아니오".to_string());
-        korean.add_translation("action.export", "내보내기".to_string());
-        korean.add_translation("action.import", "가져오기".to_string());
-        korean.add_translation("action.refresh", "새로고침".to_string());
-        korean.add_translation("action.delete", "삭제".to_string());
-        korean.add_translation("action.edit", "편집".to_string());
-        korean.add_translation("action.view", "보기".to_string());
-        
-        // Notifications
-        korean.add_translation("notification.success", "성공".to_string());
-        korean.add_translation("notification.error", "오류".to_string());
-        korean.add_translation("notification.warning", "경고".to_string());
-        korean.add_translation("notification.info", "정보".to_string());
-        
-        self.translations.insert(Language::Korean, korean);
+        for (language, json) in EMBEDDED_TRANSLATIONS {
+            match serde_json::from_str::<HashMap<String, String>>(json) {
+                Ok(translations) => self.merge_translations(language, translations),
+                Err(e) => warn!("Built-in translations for {} are invalid: {}", language.name(), e),
+            }
+        }
 
         info!("Loaded default translations for {} languages", self.translations.len());
     }
 
+    /// Load `<code>.json` files from a directory over the built-in translations
+    ///
+    /// Keys in a file replace the built-in text for that language, so a file may
+    /// correct a few strings or translate a language that has none built in.
+    /// Returns the number of files loaded; a missing directory loads none.
+    pub fn load_override_directory(&mut self, dir: &Path) -> Result<usize> {
+        if !dir.exists() {
+            return Ok(0);
+        }
+
+        let mut loaded = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(language) = path.file_stem().and_then(|stem| stem.to_str()).and_then(Language::from_code) else {
+                warn!("Ignoring translation file {}: not named after a supported language code", path.display());
+                continue;
+            };
+
+            let translations = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<HashMap<String, String>>(&content)?));
+            match translations {
+                Ok(translations) => {
+                    info!("Loaded {} translations for {} from {}", translations.len(), language.name(), path.display());
+                    self.merge_translations(language, translations);
+                    loaded += 1;
+                }
+                Err(e) => warn!("Ignoring translation file {}: {}", path.display(), e),
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Add translations for a language, replacing existing text for the same keys
+    fn merge_translations(&mut self, language: Language, translations: HashMap<String, String>) {
+        let entry = self.translations.entry(language).or_insert_with(|| LanguageTranslations::new(language));
+        for (key, value) in translations {
+            entry.add_translation(&key, value);
+        }
+    }
+
     /// Get all available languages
     pub fn available_languages(&self) -> Vec<Language> {
         self.translations.keys().cloned().collect()
@@ -467,11 +484,141 @@ pub fn create_global_i18n_manager() -> GlobalI18nManager {
     Arc::new(std::sync::RwLock::new(I18nManager::new()))
 }
 
+static GLOBAL_I18N: OnceLock<GlobalI18nManager> = OnceLock::new();
+
+/// The process-wide I18n manager behind `t()`
+pub fn global_i18n_manager() -> &'static GlobalI18nManager {
+    GLOBAL_I18N.get_or_init(create_global_i18n_manager)
+}
+
+/// Set the language `t()` translates into
+pub fn set_language(language: Language) {
+    global_i18n_manager().write().unwrap_or_else(|e| e.into_inner()).set_language(language);
+}
+
+/// The language `t()` translates into
+pub fn current_language() -> Language {
+    global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).current_language()
+}
+
+/// Rebuild the global translations from the built-in ones and a directory of overrides
+pub fn load_translation_overrides(dir: &Path) {
+    let mut manager = I18nManager::new();
+    manager.set_language(current_language());
+    match manager.load_override_directory(dir) {
+        Ok(0) => {}
+        Ok(loaded) => info!("Loaded {} translation files from {}", loaded, dir.display()),
+        Err(e) => warn!("Failed to read translations directory {}: {}", dir.display(), e),
+    }
+    *global_i18n_manager().write().unwrap_or_else(|e| e.into_inner()) = manager;
+}
+
+static WATCHED_OVERRIDE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Load translation overrides from a directory and reload them whenever its files change
+///
+/// Only the first directory is watched; later calls just load once.
+pub fn watch_translation_overrides(dir: PathBuf) {
+    load_translation_overrides(&dir);
+    if WATCHED_OVERRIDE_DIR.set(dir.clone()).is_err() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut last = directory_stamp(&dir);
+        loop {
+            tokio::time::sleep(OVERRIDE_POLL_INTERVAL).await;
+            let current = directory_stamp(&dir);
+            if current != last {
+                info!("Translation files in {} changed, reloading", dir.display());
+                load_translation_overrides(&dir);
+                last = current;
+            }
+        }
+    });
+}
+
+/// Name, modification time and size of every JSON file in a directory
+fn directory_stamp(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return Vec::new() };
+    let mut stamp: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((entry.path(), metadata.modified().ok(), metadata.len()))
+        })
+        .collect();
+    stamp.sort();
+    stamp
+}
+
+/// Environment variables naming the user's locale, most specific first
+const LOCALE_VARIABLES: [&str; 4] = ["LC_ALL", "LC_MESSAGES", "LANGUAGE", "LANG"];
+
+/// The language of the operating system's interface, if there are translations for it
+///
+/// Asks Windows for the user's UI language, and otherwise reads the usual
+/// locale variables such as `LANG=ko_KR.UTF-8`. Falls back to English.
+pub fn detect_system_language() -> Language {
+    let detected = system_ui_language().or_else(|| {
+        LOCALE_VARIABLES.iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find_map(|value| value.split(':').find_map(language_from_locale))
+    });
+
+    match detected {
+        Some(language) if global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).is_language_available(language) => {
+            info!("Detected system language: {}", language.name());
+            language
+        }
+        _ => Language::English,
+    }
+}
+
+/// Language of a locale name like `ko_KR.UTF-8`, `pt-BR` or `ru`
+pub fn language_from_locale(locale: &str) -> Option<Language> {
+    let code = locale.split(['_', '-', '.', '@']).next()?;
+    Language::from_code(code.trim())
+}
+
+#[cfg(windows)]
+fn system_ui_language() -> Option<Language> {
+    use winapi::um::winnls::GetUserDefaultUILanguage;
+
+    // The low ten bits of a LANGID are the primary language
+    let primary = unsafe { GetUserDefaultUILanguage() } & 0x3ff;
+    match primary {
+        0x09 => Some(Language::English),
+        0x12 => Some(Language::Korean),
+        0x11 => Some(Language::Japanese),
+        0x04 => Some(Language::Chinese),
+        0x0a => Some(Language::Spanish),
+        0x0c => Some(Language::French),
+        0x07 => Some(Language::German),
+        0x19 => Some(Language::Russian),
+        _ => None,
+    }
+}
+
+#[cfg(not(windows))]
+fn system_ui_language() -> Option<Language> {
+    None
+}
+
 /// Get translation helper function
-pub fn t(key: TranslationKey) -> String {
-    // This would be connected to the global I18n manager in a real implementation
-    // For now, return the key as fallback
-    key.to_string()
+pub fn t(key: &str) -> String {
+    global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).t(key)
+}
+
+/// Get translation with `{name}` placeholders filled in
+pub fn t_args(key: &str, args: &[(&str, String)]) -> String {
+    global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).t_args(key, args)
+}
+
+/// Get the plural form of a translation matching a count, with placeholders filled in
+pub fn t_plural(key: &str, count: u64, args: &[(&str, String)]) -> String {
+    global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).t_plural(key, count, args)
 }
 
 #[cfg(test)]
@@ -511,4 +658,95 @@ mod tests {
         let korean_translation = manager.t("app.title");
         assert_eq!(korean_translation, "WinScrape Studio");
     }
+
+    #[test]
+    fn test_plural_categories() {
+        assert_eq!(Language::English.plural_category(1), PluralCategory::One);
+        assert_eq!(Language::English.plural_category(0), PluralCategory::Other);
+        assert_eq!(Language::French.plural_category(0), PluralCategory::One);
+        assert_eq!(Language::Korean.plural_category(1), PluralCategory::Other);
+        assert_eq!(Language::Russian.plural_category(21), PluralCategory::One);
+        assert_eq!(Language::Russian.plural_category(11), PluralCategory::Many);
+        assert_eq!(Language::Russian.plural_category(3), PluralCategory::Few);
+        assert_eq!(Language::Russian.plural_category(13), PluralCategory::Many);
+        assert_eq!(Language::Russian.plural_category(25), PluralCategory::Many);
+    }
+
+    #[test]
+    fn test_t_plural_picks_form_and_fills_placeholders() {
+        let mut manager = I18nManager::new();
+        assert_eq!(manager.t_plural("jobs.running_count", 1, &[]), "1 job running");
+        assert_eq!(manager.t_plural("jobs.running_count", 3, &[]), "3 jobs running");
+
+        manager.set_language(Language::Korean);
+        assert_eq!(manager.t_plural("jobs.running_count", 1, &[]), "작업 1개 실행 중");
+
+        let mut russian = LanguageTranslations::new(Language::Russian);
+        russian.add_translation("rows.one", "{count} строка в {file}".to_string());
+        russian.add_translation("rows.few", "{count} строки в {file}".to_string());
+        russian.add_translation("rows.many", "{count} строк в {file}".to_string());
+        manager.translations.insert(Language::Russian, russian);
+        manager.set_language(Language::Russian);
+        let file = [("file", "out.csv".to_string())];
+        assert_eq!(manager.t_plural("rows", 22, &file), "22 строки в out.csv");
+        assert_eq!(manager.t_plural("rows", 5, &file), "5 строк в out.csv");
+    }
+
+    #[test]
+    fn test_completeness_report_follows_plural_forms() {
+        let mut manager = I18nManager::new();
+        let korean = manager.completeness_report(Language::Korean);
+        assert!(korean.missing.is_empty(), "missing: {:?}", korean.missing);
+        assert!(korean.extra.is_empty(), "extra: {:?}", korean.extra);
+        assert_eq!(korean.percent_complete, 100.0);
+
+        let mut russian = LanguageTranslations::new(Language::Russian);
+        russian.add_translation("nav.chat", "Чат".to_string());
+        russian.add_translation("jobs.running_count.one", "{count} задача выполняется".to_string());
+        russian.add_translation("nav.chats", "Чаты".to_string());
+        manager.translations.insert(Language::Russian, russian);
+
+        let report = manager.completeness_report(Language::Russian);
+        assert_eq!(report.extra, vec!["nav.chats".to_string()]);
+        assert_eq!(report.translated, 2);
+        let missing = manager.missing_translations(Language::Russian);
+        assert_eq!(missing.len(), report.missing.len());
+        assert_eq!(missing["jobs.running_count.few"], "{count} jobs running");
+        assert_eq!(missing["nav.jobs"], "Jobs");
+        assert!(!missing.contains_key("nav.chat"));
+        assert!(!missing.contains_key("jobs.running_count.other"));
+    }
+
+    #[test]
+    fn test_override_directory_replaces_and_adds_translations() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ko.json"), r#"{"nav.jobs": "작업 목록"}"#).unwrap();
+        std::fs::write(dir.path().join("ja.json"), r#"{"nav.jobs": "ジョブ"}"#).unwrap();
+        std::fs::write(dir.path().join("xx.json"), r#"{"nav.jobs": "?"}"#).unwrap();
+
+        let mut manager = I18nManager::new();
+        assert_eq!(manager.load_override_directory(dir.path()).unwrap(), 2);
+
+        assert_eq!(manager.get_translation("nav.jobs", Language::Korean), "작업 목록");
+        assert_eq!(manager.get_translation("nav.chat", Language::Korean), "채팅");
+        assert_eq!(manager.get_translation("nav.jobs", Language::Japanese), "ジョブ");
+        assert_eq!(manager.get_translation("nav.chat", Language::Japanese), "Chat");
+        assert!(manager.is_language_available(Language::Japanese));
+    }
+
+    #[test]
+    fn test_language_from_locale() {
+        assert_eq!(language_from_locale("ko_KR.UTF-8"), Some(Language::Korean));
+        assert_eq!(language_from_locale("de-AT"), Some(Language::German));
+        assert_eq!(language_from_locale("ru"), Some(Language::Russian));
+        assert_eq!(language_from_locale("C"), None);
+        assert_eq!(language_from_locale("sr@latin"), None);
+    }
+
+    #[test]
+    fn test_global_t_translates() {
+        assert_eq!(t("nav.settings"), "Settings");
+        assert_eq!(t_args("explain.limit", &[("limit", "5".to_string())]), "Output is capped at 5 rows.");
+        assert_eq!(t("no.such.key"), "no.such.key");
+    }
 }
@@ -20,6 +20,8 @@ pub mod error;
 pub mod logging;
 pub mod performance;
 pub mod i18n;
+pub mod notifications;
+pub mod plugins;
 
 #[cfg(feature = "ui")]
 pub mod ui;
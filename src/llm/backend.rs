@@ -0,0 +1,325 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info};
+
+use super::LLMError;
+use crate::config::{LLMBackendConfig, LLMConfig};
+
+/// Generation can take a while on busy or CPU-only servers
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Something that completes prompts
+#[async_trait::async_trait]
+pub trait LLMBackend: Send + Sync {
+    /// Short name recorded as the `generator` of plans it produces
+    fn name(&self) -> &str;
+
+    /// Complete a prompt, producing at most `max_tokens` tokens
+    async fn complete(&self, prompt: &str, max_tokens: usize, temperature: f32) -> Result<String>;
+
+    /// Complete a prompt, sending text to `tokens` as it is generated
+    ///
+    /// Returns the whole completion. Backends that can't stream send it as a
+    /// single chunk at the end.
+    async fn complete_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: usize,
+        temperature: f32,
+        tokens: mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let text = self.complete(prompt, max_tokens, temperature).await?;
+        let _ = tokens.send(text.clone());
+        Ok(text)
+    }
+}
+
+/// Build the remote backend selected in the configuration
+///
+/// Returns `None` for the local backend, which is loaded separately.
+pub fn create_remote_backend(config: &LLMConfig) -> Result<Option<Box<dyn LLMBackend>>> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()?;
+
+    let backend: Box<dyn LLMBackend> = match &config.backend {
+        LLMBackendConfig::Local => return Ok(None),
+        LLMBackendConfig::OpenAI { base_url, model, api_key_env } => Box::new(OpenAIBackend {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.clone(),
+            api_key: std::env::var(api_key_env).ok().filter(|key| !key.is_empty()),
+        }),
+        LLMBackendConfig::Anthropic { base_url, model, api_key_env } => {
+            let api_key = std::env::var(api_key_env)
+                .ok()
+                .filter(|key| !key.is_empty())
+                .ok_or_else(|| LLMError::ConfigError(format!("{} is not set", api_key_env)))?;
+            Box::new(AnthropicBackend {
+                client,
+                base_url: base_url.trim_end_matches('/').to_string(),
+                model: model.clone(),
+                api_key,
+            })
+        }
+        LLMBackendConfig::Ollama { base_url, model } => Box::new(OllamaBackend {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.clone(),
+        }),
+    };
+
+    info!("Using {} LLM backend", backend.name());
+    Ok(Some(backend))
+}
+
+/// OpenAI or any server exposing the same chat completions API
+pub struct OpenAIBackend {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl OpenAIBackend {
+    fn request(&self, prompt: &str, max_tokens: usize, temperature: f32, stream: bool) -> reqwest::RequestBuilder {
+        let body = json!({
+            "model": self.model,
+            "messages": [{"role": "user", "content": prompt}],
+            "max_tokens": max_tokens,
+            "temperature": temperature,
+            "stream": stream,
+        });
+
+        let request = self.client
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(&body);
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMBackend for OpenAIBackend {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    async fn complete(&self, prompt: &str, max_tokens: usize, temperature: f32) -> Result<String> {
+        let response = send(self.request(prompt, max_tokens, temperature, false)).await?;
+        text_at(&response, &["choices", "0", "message", "content"])
+    }
+
+    async fn complete_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: usize,
+        temperature: f32,
+        tokens: mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let request = self.request(prompt, max_tokens, temperature, true);
+        send_streaming(request, &tokens, |line| {
+            let event = sse_data(line)?;
+            text_at(&event, &["choices", "0", "delta", "content"]).ok()
+        }).await
+    }
+}
+
+/// Anthropic's messages API
+pub struct AnthropicBackend {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key: String,
+}
+
+impl AnthropicBackend {
+    fn request(&self, prompt: &str, max_tokens: usize, temperature: f32, stream: bool) -> reqwest::RequestBuilder {
+        let body = json!({
+            "model": self.model,
+            "messages": [{"role": "user", "content": prompt}],
+            "max_tokens": max_tokens,
+            "temperature": temperature.min(1.0),
+            "stream": stream,
+        });
+
+        self.client
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&body)
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMBackend for AnthropicBackend {
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+
+    async fn complete(&self, prompt: &str, max_tokens: usize, temperature: f32) -> Result<String> {
+        let response = send(self.request(prompt, max_tokens, temperature, false)).await?;
+        text_at(&response, &["content", "0", "text"])
+    }
+
+    async fn complete_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: usize,
+        temperature: f32,
+        tokens: mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let request = self.request(prompt, max_tokens, temperature, true);
+        send_streaming(request, &tokens, |line| {
+            let event = sse_data(line)?;
+            if event.get("type").and_then(Value::as_str) != Some("content_block_delta") {
+                return None;
+            }
+            text_at(&event, &["delta", "text"]).ok()
+        }).await
+    }
+}
+
+/// A local Ollama server
+pub struct OllamaBackend {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+}
+
+impl OllamaBackend {
+    fn request(&self, prompt: &str, max_tokens: usize, temperature: f32, stream: bool) -> reqwest::RequestBuilder {
+        let body = json!({
+            "model": self.model,
+            "prompt": prompt,
+            "stream": stream,
+            "options": {
+                "num_predict": max_tokens,
+                "temperature": temperature,
+            },
+        });
+
+        self.client
+            .post(format!("{}/api/generate", self.base_url))
+            .json(&body)
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMBackend for OllamaBackend {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    async fn complete(&self, prompt: &str, max_tokens: usize, temperature: f32) -> Result<String> {
+        let response = send(self.request(prompt, max_tokens, temperature, false)).await?;
+        text_at(&response, &["response"])
+    }
+
+    async fn complete_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: usize,
+        temperature: f32,
+        tokens: mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let request = self.request(prompt, max_tokens, temperature, true);
+        // Ollama streams one JSON object per line
+        send_streaming(request, &tokens, |line| {
+            let chunk: Value = serde_json::from_str(line).ok()?;
+            text_at(&chunk, &["response"]).ok()
+        }).await
+    }
+}
+
+/// Send a request and parse the JSON body, turning error statuses into errors
+async fn send(request: reqwest::RequestBuilder) -> Result<Value> {
+    let response = request.send().await
+        .map_err(|e| LLMError::GenerationError(e.to_string()))?;
+    let status = response.status();
+    let body = response.text().await?;
+
+    if !status.is_success() {
+        return Err(LLMError::GenerationError(format!("HTTP {}: {}", status.as_u16(), body.trim())).into());
+    }
+
+    debug!("LLM backend responded with {} bytes", body.len());
+    serde_json::from_str(&body)
+        .map_err(|e| LLMError::GenerationError(format!("Invalid response: {}", e)).into())
+}
+
+/// Send a request and read a streamed body line by line
+///
+/// `parse_line` picks the text out of a line, if it carries any. Each piece of
+/// text is sent to `tokens`; the whole completion is returned.
+async fn send_streaming(
+    request: reqwest::RequestBuilder,
+    tokens: &mpsc::UnboundedSender<String>,
+    parse_line: impl Fn(&str) -> Option<String>,
+) -> Result<String> {
+    let mut response = request.send().await
+        .map_err(|e| LLMError::GenerationError(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await?;
+        return Err(LLMError::GenerationError(format!("HTTP {}: {}", status.as_u16(), body.trim())).into());
+    }
+
+    let mut completion = String::new();
+    let mut pending: Vec<u8> = Vec::new();
+    let handle_line = |line: &[u8], completion: &mut String| {
+        let line = String::from_utf8_lossy(line);
+        if let Some(text) = parse_line(line.trim()).filter(|text| !text.is_empty()) {
+            completion.push_str(&text);
+            let _ = tokens.send(text);
+        }
+    };
+
+    while let Some(chunk) = response.chunk().await
+        .map_err(|e| LLMError::GenerationError(e.to_string()))?
+    {
+        pending.extend_from_slice(&chunk);
+        // Lines can be split across chunks, so only complete lines are parsed
+        while let Some(end) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            handle_line(&line, &mut completion);
+        }
+    }
+    if !pending.is_empty() {
+        handle_line(&pending, &mut completion);
+    }
+
+    debug!("LLM backend streamed {} bytes", completion.len());
+    if completion.is_empty() {
+        return Err(LLMError::GenerationError("Empty streamed response".to_string()).into());
+    }
+    Ok(completion)
+}
+
+/// JSON payload of a server-sent event `data:` line
+fn sse_data(line: &str) -> Option<Value> {
+    let data = line.strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return None;
+    }
+    serde_json::from_str(data).ok()
+}
+
+/// String at a path of object keys and array indices
+fn text_at(value: &Value, path: &[&str]) -> Result<String> {
+    path.iter()
+        .try_fold(value, |value, key| match key.parse::<usize>() {
+            Ok(index) => value.get(index),
+            Err(_) => value.get(key),
+        })
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| LLMError::GenerationError(format!("No completion at {} in response", path.join("."))).into())
+}
+
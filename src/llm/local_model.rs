@@ -0,0 +1,223 @@
+use anyhow::Result;
+use candle_core::quantized::gguf_file;
+use candle_core::{Device, Tensor};
+use candle_transformers::generation::{LogitsProcessor, Sampling};
+use candle_transformers::models::quantized_llama::{ModelWeights, MAX_SEQ_LEN};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokenizers::Tokenizer;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::backend::LLMBackend;
+use super::LLMError;
+use crate::config::LLMConfig;
+
+/// Tokens that end a completion in the common llama-family chat formats
+const EOS_TOKENS: &[&str] = &["</s>", "<|eot_id|>", "<|end_of_text|>", "<|im_end|>", "<|endoftext|>"];
+
+/// A quantized GGUF llama-family model running on the CPU
+pub struct LocalModel {
+    weights: ModelWeights,
+    tokenizer: Tokenizer,
+    device: Device,
+    eos_tokens: Vec<u32>,
+    context_size: usize,
+}
+
+impl LocalModel {
+    /// Load the model at `config.model_path` and the `tokenizer.json` next to it
+    ///
+    /// Blocks while the weights are read; call from a blocking task.
+    pub fn load(config: &LLMConfig) -> Result<Self> {
+        if config.context_size == 0 {
+            return Err(LLMError::ConfigError("context_size must be > 0".to_string()).into());
+        }
+
+        let path = &config.model_path;
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| LLMError::ModelLoadError(format!("{}: {}", path.display(), e)))?;
+        let content = gguf_file::Content::read(&mut file)
+            .map_err(|e| LLMError::ModelLoadError(format!("{}: {}", path.display(), e)))?;
+
+        let device = Device::Cpu;
+        let weights = ModelWeights::from_gguf(content, &mut file, &device)
+            .map_err(|e| LLMError::ModelLoadError(e.to_string()))?;
+
+        let tokenizer_path = tokenizer_path(path);
+        let tokenizer = Tokenizer::from_file(&tokenizer_path)
+            .map_err(|e| LLMError::ModelLoadError(format!("{}: {}", tokenizer_path.display(), e)))?;
+        let eos_tokens: Vec<u32> = EOS_TOKENS.iter()
+            .filter_map(|token| tokenizer.token_to_id(token))
+            .collect();
+        if eos_tokens.is_empty() {
+            warn!("No end-of-sequence token found in {}; completions run to max_tokens", tokenizer_path.display());
+        }
+
+        let context_size = config.context_size.min(MAX_SEQ_LEN);
+        if context_size < config.context_size {
+            warn!("Context size {} exceeds the model limit, using {}", config.context_size, context_size);
+        }
+
+        info!("Loaded local model {} (context {} tokens)", path.display(), context_size);
+        Ok(Self {
+            weights,
+            tokenizer,
+            device,
+            eos_tokens,
+            context_size,
+        })
+    }
+
+    /// Complete a prompt, producing at most `max_tokens` tokens
+    ///
+    /// The oldest prompt tokens are dropped if prompt and completion would not fit
+    /// into the context window.
+    pub fn generate(&mut self, prompt: &str, max_tokens: usize, temperature: f32, seed: u64) -> Result<String> {
+        self.generate_streaming(prompt, max_tokens, temperature, seed, |_| {})
+    }
+
+    /// Complete a prompt, passing each newly decoded piece of text to `on_text`
+    pub fn generate_streaming(
+        &mut self,
+        prompt: &str,
+        max_tokens: usize,
+        temperature: f32,
+        seed: u64,
+        mut on_text: impl FnMut(&str),
+    ) -> Result<String> {
+        let encoding = self.tokenizer.encode(prompt, true)
+            .map_err(|e| LLMError::GenerationError(e.to_string()))?;
+        let mut tokens = encoding.get_ids().to_vec();
+
+        let max_tokens = max_tokens.min(self.context_size / 2).max(1);
+        let prompt_budget = self.context_size.saturating_sub(max_tokens);
+        if tokens.len() > prompt_budget {
+            warn!("Prompt of {} tokens truncated to {}", tokens.len(), prompt_budget);
+            tokens.drain(..tokens.len() - prompt_budget);
+        }
+
+        let sampling = if temperature <= 0.0 {
+            Sampling::ArgMax
+        } else {
+            Sampling::All { temperature: temperature as f64 }
+        };
+        let mut logits_processor = LogitsProcessor::from_sampling(seed, sampling);
+
+        let gen_error = |e: candle_core::Error| LLMError::GenerationError(e.to_string());
+
+        // Process the whole prompt once, then feed back one token at a time
+        let input = Tensor::new(tokens.as_slice(), &self.device)
+            .and_then(|t| t.unsqueeze(0))
+            .map_err(gen_error)?;
+        let logits = self.weights.forward(&input, 0)
+            .and_then(|l| l.squeeze(0))
+            .map_err(gen_error)?;
+        let mut next = logits_processor.sample(&logits).map_err(gen_error)?;
+
+        let mut generated = Vec::with_capacity(max_tokens);
+        let mut emitted = 0;
+        let mut position = tokens.len();
+        while generated.len() < max_tokens && !self.eos_tokens.contains(&next) {
+            generated.push(next);
+
+            // Tokens can end in the middle of a character; wait for the rest
+            let text = self.tokenizer.decode(&generated, true)
+                .map_err(|e| LLMError::GenerationError(e.to_string()))?;
+            if text.len() > emitted && text.is_char_boundary(emitted) && !text.ends_with('\u{FFFD}') {
+                on_text(&text[emitted..]);
+                emitted = text.len();
+            }
+
+            let input = Tensor::new(&[next], &self.device)
+                .and_then(|t| t.unsqueeze(0))
+                .map_err(gen_error)?;
+            let logits = self.weights.forward(&input, position)
+                .and_then(|l| l.squeeze(0))
+                .map_err(gen_error)?;
+            position += 1;
+            next = logits_processor.sample(&logits).map_err(gen_error)?;
+        }
+
+        debug!("Generated {} tokens from a {} token prompt", generated.len(), tokens.len());
+        let text = self.tokenizer.decode(&generated, true)
+            .map_err(|e| LLMError::GenerationError(e.to_string()))?;
+        Ok(text)
+    }
+}
+
+/// The local model as a backend, generating on blocking threads
+pub struct LocalBackend {
+    model: Arc<Mutex<LocalModel>>,
+    /// Pool candle's CPU kernels run on while generating
+    pool: Arc<ThreadPool>,
+}
+
+impl LocalBackend {
+    /// Generate on a pool of `threads` threads; 0 uses one per CPU
+    pub fn new(model: LocalModel, threads: usize) -> Result<Self> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("local-llm-{}", i))
+            .build()
+            .map_err(|e| LLMError::ModelLoadError(format!("Failed to start {} generation threads: {}", threads, e)))?;
+        Ok(Self {
+            model: Arc::new(Mutex::new(model)),
+            pool: Arc::new(pool),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl LLMBackend for LocalBackend {
+    fn name(&self) -> &str {
+        "local_model"
+    }
+
+    async fn complete(&self, prompt: &str, max_tokens: usize, temperature: f32) -> Result<String> {
+        let model = self.model.clone();
+        let prompt = prompt.to_string();
+        let seed = rand::random::<u64>();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut guard = model.lock()
+                .map_err(|_| LLMError::GenerationError("Model lock poisoned".to_string()))?;
+            // The guard can't cross into the pool, the model it locks can
+            let model = &mut *guard;
+            pool.install(|| model.generate(&prompt, max_tokens, temperature, seed))
+        })
+        .await?
+    }
+
+    async fn complete_streaming(
+        &self,
+        prompt: &str,
+        max_tokens: usize,
+        temperature: f32,
+        tokens: mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let model = self.model.clone();
+        let prompt = prompt.to_string();
+        let seed = rand::random::<u64>();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut guard = model.lock()
+                .map_err(|_| LLMError::GenerationError("Model lock poisoned".to_string()))?;
+            let model = &mut *guard;
+            pool.install(|| {
+                model.generate_streaming(&prompt, max_tokens, temperature, seed, |text| {
+                    let _ = tokens.send(text.to_string());
+                })
+            })
+        })
+        .await?
+    }
+}
+
+/// `tokenizer.json` in the model's directory
+fn tokenizer_path(model_path: &Path) -> PathBuf {
+    model_path.with_file_name("tokenizer.json")
+}
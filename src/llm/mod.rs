@@ -1,39 +1,341 @@
 use anyhow::Result;
-use tracing::{info, warn, error};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn, error};
 
 pub mod prompts;
 pub mod processor;
+pub mod backend;
+pub mod refine;
+pub mod repair;
+pub mod stream;
+#[cfg(feature = "local-llm")]
+pub mod local_model;
 
-use crate::config::LLMConfig;
-use crate::dsl::ScrapePlan;
+pub use backend::LLMBackend;
+pub use stream::{GenerationEvent, GenerationStream};
+
+use crate::config::{LLMBackendConfig, LLMConfig};
+use crate::dsl::{PageSummary, ScrapePlan, PolitenessPreset};
+
+/// Confidence a page's selector suggestion needs to replace a generic one
+const GROUNDING_MIN_CONFIDENCE: f64 = 0.5;
 
 /// LLM processor for natural language to DSL conversion
+///
+/// Uses the backend selected in `LLMConfig.backend`: a remote OpenAI-compatible,
+/// Anthropic or Ollama server, or the GGUF model at `model_path` when built with
+/// the `local-llm` feature. Without a usable backend plans come from rule-based
+/// intent extraction.
 pub struct LLMProcessor {
     config: LLMConfig,
+    backend: Option<Box<dyn LLMBackend>>,
 }
 
 impl LLMProcessor {
     /// Create new LLM processor
     pub async fn new(config: &LLMConfig) -> Result<Self> {
-        info!("Initializing LLM processor (simplified implementation)");
+        info!("Initializing LLM processor");
         
-        // For now, we'll use a simplified implementation without actual LLM
-        // In a full implementation, you would integrate with llama.cpp or similar
+        let backend = match &config.backend {
+            LLMBackendConfig::Local => Self::load_local_backend(config).await?,
+            _ => match backend::create_remote_backend(config) {
+                Ok(backend) => backend,
+                Err(e) => {
+                    warn!("LLM backend {} unavailable, using rule-based generation: {}", config.backend.name(), e);
+                    None
+                }
+            },
+        };
         
         info!("LLM processor initialized successfully");
         Ok(Self {
             config: config.clone(),
+            backend,
         })
     }
     
+    /// Load the GGUF model at `model_path`, if present
+    #[cfg(feature = "local-llm")]
+    async fn load_local_backend(config: &LLMConfig) -> Result<Option<Box<dyn LLMBackend>>> {
+        if !config.model_path.exists() {
+            warn!("Model file {} not found, using rule-based generation", config.model_path.display());
+            return Ok(None);
+        }
+        
+        let model_config = config.clone();
+        let loaded = tokio::task::spawn_blocking(move || {
+            local_model::LocalModel::load(&model_config)
+                .and_then(|model| local_model::LocalBackend::new(model, model_config.threads))
+        }).await?;
+        match loaded {
+            Ok(backend) => Ok(Some(Box::new(backend))),
+            Err(e) => {
+                warn!("Failed to load local model, using rule-based generation: {}", e);
+                Ok(None)
+            }
+        }
+    }
+    
+    /// Without the `local-llm` feature there is no local model to load
+    #[cfg(not(feature = "local-llm"))]
+    async fn load_local_backend(_config: &LLMConfig) -> Result<Option<Box<dyn LLMBackend>>> {
+        Ok(None)
+    }
+    
+    /// Whether plans are generated by a model rather than rules
+    pub fn has_model(&self) -> bool {
+        self.backend.is_some()
+    }
+    
+    /// Run the backend on a prompt
+    async fn complete(&self, prompt: String) -> Result<String> {
+        let backend = self.backend.as_ref()
+            .ok_or_else(|| LLMError::ModelLoadError("No LLM backend available".to_string()))?;
+        backend.complete(&prompt, self.config.max_tokens, self.config.temperature).await
+    }
+    
+    /// Run the backend on a prompt, passing its output on to `events` as it arrives
+    async fn complete_streaming(&self, prompt: String, events: Option<&mpsc::UnboundedSender<GenerationEvent>>) -> Result<String> {
+        let Some(events) = events else {
+            return self.complete(prompt).await;
+        };
+        let backend = self.backend.as_ref()
+            .ok_or_else(|| LLMError::ModelLoadError("No LLM backend available".to_string()))?;
+        
+        let (tokens_tx, tokens_rx) = mpsc::unbounded_channel();
+        let forwarder = stream::TokenForwarder::new(events.clone()).run(tokens_rx);
+        let completion = backend.complete_streaming(&prompt, self.config.max_tokens, self.config.temperature, tokens_tx);
+        let (completion, ()) = tokio::join!(completion, forwarder);
+        completion
+    }
+    
     /// Generate DSL from natural language description
     pub async fn generate_dsl(&self, description: &str) -> Result<ScrapePlan> {
-        info!("Generating DSL from description: {}", description);
+        let preset = PolitenessPreset::from_description(description);
+        self.generate_dsl_with_preset(description, preset).await
+    }
+    
+    /// Generate DSL from natural language description using a politeness preset
+    pub async fn generate_dsl_with_preset(&self, description: &str, preset: PolitenessPreset) -> Result<ScrapePlan> {
+        self.generate_dsl_for_page(description, preset, None).await
+    }
+    
+    /// Generate DSL using the structure of the target page when it is known
+    ///
+    /// The model sees the page summary in its prompt; rule-based generation takes
+    /// the summary's item container and fields in place of generic selectors.
+    pub async fn generate_dsl_for_page(
+        &self,
+        description: &str,
+        preset: PolitenessPreset,
+        page: Option<&PageSummary>,
+    ) -> Result<ScrapePlan> {
+        self.generate_dsl_streaming(description, preset, page, None).await
+    }
+    
+    /// Generate DSL, sending the model's output to `events` as it is produced
+    ///
+    /// Sends `Token` and `PartialYaml` events only; the caller reports the
+    /// outcome. A rule-based plan is sent as a single `PartialYaml`.
+    pub async fn generate_dsl_streaming(
+        &self,
+        description: &str,
+        preset: PolitenessPreset,
+        page: Option<&PageSummary>,
+        events: Option<mpsc::UnboundedSender<GenerationEvent>>,
+    ) -> Result<ScrapePlan> {
+        info!("Generating DSL from description: {} (politeness: {})", description, preset);
+        
+        if self.has_model() {
+            match self.generate_with_model(description, preset, page, events.as_ref()).await {
+                Ok(plan) => return Ok(plan),
+                Err(e) => warn!("Model output was unusable, using rule-based generation: {}", e),
+            }
+        }
+        
+        let plan = self.generate_fallback(description, preset, page)?;
+        if let Some(events) = &events {
+            let _ = events.send(GenerationEvent::PartialYaml(plan.to_yaml()?));
+        }
+        Ok(plan)
+    }
+    
+    /// Generate DSL on a background task, as a stream of events
+    ///
+    /// The stream ends with `Done` or `Failed`.
+    pub fn stream_dsl(
+        self: Arc<Self>,
+        description: String,
+        preset: PolitenessPreset,
+        page: Option<PageSummary>,
+    ) -> GenerationStream {
+        let (events, stream) = GenerationStream::channel();
+        tokio::spawn(async move {
+            let result = self.generate_dsl_streaming(&description, preset, page.as_ref(), Some(events.clone())).await;
+            let _ = events.send(match result {
+                Ok(plan) => GenerationEvent::Done(Box::new(plan)),
+                Err(e) => GenerationEvent::Failed(e.to_string()),
+            });
+        });
+        stream
+    }
+    
+    /// Generate a plan without the model, using the page's selectors when known
+    pub fn generate_fallback(
+        &self,
+        description: &str,
+        preset: PolitenessPreset,
+        page: Option<&PageSummary>,
+    ) -> Result<ScrapePlan> {
+        let mut plan = self.generate_rule_based(description, preset)?;
+        if let Some(page) = page {
+            let applied = page.suggestions().apply_to(&mut plan, GROUNDING_MIN_CONFIDENCE);
+            if applied > 0 {
+                debug!("Applied {} selectors found on {}", applied, page.url);
+                ground_on(&mut plan, page);
+            }
+        }
+        Ok(plan)
+    }
+    
+    /// Times an invalid generated plan should be sent back for repair
+    pub fn max_repair_attempts(&self) -> usize {
+        if self.has_model() {
+            self.config.max_repair_attempts
+        } else {
+            0
+        }
+    }
+    
+    /// Ask the backend to fix the problems found in a plan it generated
+    ///
+    /// The repaired plan keeps the original's metadata and politeness preset.
+    pub async fn repair_dsl(&self, plan: &ScrapePlan, issues: &[String]) -> Result<ScrapePlan> {
+        let prompt = prompts::build_dsl_validation_prompt(plan, issues);
+        let response = self.complete(prompt).await?;
+        
+        let yaml = self.extract_dsl_from_response(&response)?;
+        let mut repaired = ScrapePlan::from_yaml(&yaml)
+            .map_err(|e| LLMError::DSLParsingError(e.to_string()))?;
+        if repaired.get_metadata("fallback").is_some() {
+            return Err(LLMError::DSLParsingError("no plan in model response".to_string()).into());
+        }
+        
+        let preset = plan.anti_blocking.politeness;
+        if !preset.check_plan(&repaired).is_empty() {
+            preset.apply_to(&mut repaired);
+        }
+        repaired.metadata = plan.metadata.clone();
+        
+        Ok(repaired)
+    }
+    
+    /// Generate a plan with the backend and the prompts module
+    async fn generate_with_model(
+        &self,
+        description: &str,
+        preset: PolitenessPreset,
+        page: Option<&PageSummary>,
+        events: Option<&mpsc::UnboundedSender<GenerationEvent>>,
+    ) -> Result<ScrapePlan> {
+        let prompt = match page {
+            Some(page) => prompts::build_grounded_dsl_generation_prompt(description, preset, page),
+            None => prompts::build_dsl_generation_prompt_with_preset(description, preset),
+        };
+        let response = self.complete_streaming(prompt, events).await?;
+        
+        let yaml = self.extract_dsl_from_response(&response)?;
+        let mut plan = ScrapePlan::from_yaml(&yaml)
+            .map_err(|e| LLMError::DSLParsingError(e.to_string()))?;
+        if plan.get_metadata("fallback").is_some() {
+            return Err(LLMError::DSLParsingError("no plan in model response".to_string()).into());
+        }
+        
+        // The model is asked to follow the preset; enforce it regardless
+        if !preset.check_plan(&plan).is_empty() {
+            preset.apply_to(&mut plan);
+        }
+        
+        plan.add_metadata("user_prompt".to_string(), serde_json::Value::String(description.to_string()));
+        plan.add_metadata("politeness".to_string(), serde_json::Value::String(preset.code().to_string()));
+        let generator = self.backend.as_ref().map(|b| b.name()).unwrap_or_default();
+        plan.add_metadata("generator".to_string(), serde_json::Value::String(generator.to_string()));
+        if let Some(page) = page {
+            ground_on(&mut plan, page);
+        }
         
+        info!("DSL generated by {} backend with {} fields", generator, plan.rules.fields.len());
+        Ok(plan)
+    }
+    
+    /// Change an existing plan according to a follow-up instruction
+    ///
+    /// Patches `current` rather than generating a new plan, so manual edits to
+    /// parts the instruction doesn't mention survive. The instruction is appended
+    /// to the plan's `refinements` metadata.
+    pub async fn refine_dsl(&self, current: &ScrapePlan, instruction: &str) -> Result<ScrapePlan> {
+        info!("Refining DSL: {}", instruction);
+        
+        let history = current.get_metadata(refine::REFINEMENTS_KEY).cloned();
+        
+        if self.has_model() {
+            match self.refine_with_model(current, instruction).await {
+                Ok(mut plan) => {
+                    refine::record_refinement(&mut plan, history.as_ref(), instruction);
+                    return Ok(plan);
+                }
+                Err(e) => warn!("Model refinement was unusable, using rule-based refinement: {}", e),
+            }
+        }
+        
+        let mut plan = current.clone();
+        let changes = refine::apply_instruction(&mut plan, instruction);
+        if changes.is_empty() {
+            return Err(LLMError::DSLParsingError(format!(
+                "Could not work out how to apply \"{}\" to the plan", instruction.trim()
+            )).into());
+        }
+        
+        info!("Refined DSL: {}", changes.join(", "));
+        refine::record_refinement(&mut plan, history.as_ref(), instruction);
+        Ok(plan)
+    }
+    
+    /// Ask the backend for an updated plan, keeping unmentioned fields as they were
+    async fn refine_with_model(&self, current: &ScrapePlan, instruction: &str) -> Result<ScrapePlan> {
+        let prompt = prompts::build_dsl_refinement_prompt(current, instruction);
+        let response = self.complete(prompt).await?;
+        
+        let yaml = self.extract_dsl_from_response(&response)?;
+        let mut plan = ScrapePlan::from_yaml(&yaml)
+            .map_err(|e| LLMError::DSLParsingError(e.to_string()))?;
+        if plan.get_metadata("fallback").is_some() {
+            return Err(LLMError::DSLParsingError("no plan in model response".to_string()).into());
+        }
+        
+        let restored = refine::preserve_unmentioned_fields(current, &mut plan, instruction);
+        if restored > 0 {
+            debug!("Restored {} fields the model changed without being asked", restored);
+        }
+        
+        let preset = plan.anti_blocking.politeness;
+        if !preset.check_plan(&plan).is_empty() {
+            preset.apply_to(&mut plan);
+        }
+        plan.anti_blocking.random_seed = current.anti_blocking.random_seed;
+        plan.metadata = current.metadata.clone();
+        
+        Ok(plan)
+    }
+    
+    /// Generate a plan from keyword-based intent extraction
+    fn generate_rule_based(&self, description: &str, preset: PolitenessPreset) -> Result<ScrapePlan> {
         // Enhanced rule-based approach with better pattern matching
         let analysis = processor::LLMProcessor::extract_intent(description);
         
         let mut plan = ScrapePlan::default();
+        preset.apply_to(&mut plan);
         
         // Set domain if found
         if let Some(domain) = analysis.domain {
@@ -201,6 +503,7 @@ impl LLMProcessor {
         // Add user prompt as metadata
         plan.add_metadata("user_prompt".to_string(), serde_json::Value::String(description.to_string()));
         plan.add_metadata("scraping_type".to_string(), serde_json::Value::String(format!("{:?}", analysis.scraping_type)));
+        plan.add_metadata("politeness".to_string(), serde_json::Value::String(preset.code().to_string()));
         plan.add_metadata("confidence".to_string(), serde_json::Value::Number(serde_json::Number::from_f64(analysis.confidence as f64).unwrap()));
         
         info!("DSL generated successfully with {} fields", plan.rules.fields.len());
@@ -345,6 +648,7 @@ impl LLMProcessor {
     /// Get model information
     pub fn get_model_info(&self) -> ModelInfo {
         ModelInfo {
+            backend: self.config.backend.name().to_string(),
             model_path: self.config.model_path.clone(),
             context_size: self.config.context_size,
             temperature: self.config.temperature,
@@ -354,9 +658,27 @@ impl LLMProcessor {
     }
 }
 
+/// Record the page a plan was grounded on and start from it
+///
+/// Rule-based plans start at the domain root; the fetched page is what the
+/// selectors were taken from, so it replaces that placeholder.
+fn ground_on(plan: &mut ScrapePlan, page: &PageSummary) {
+    let domain_root = format!("https://{}", plan.target.domain);
+    let placeholder = plan.target.start_urls.iter()
+        .all(|url| url.trim_end_matches('/') == domain_root);
+    if placeholder {
+        plan.target.start_urls = vec![page.url.clone()];
+        if let Some(host) = url::Url::parse(&page.url).ok().and_then(|url| url.host_str().map(str::to_string)) {
+            plan.target.domain = host;
+        }
+    }
+    plan.add_metadata("grounded_on".to_string(), serde_json::Value::String(page.url.clone()));
+}
+
 /// Model information structure
 #[derive(Debug, Clone)]
 pub struct ModelInfo {
+    pub backend: String,
     pub model_path: std::path::PathBuf,
     pub context_size: usize,
     pub temperature: f32,
@@ -26,52 +26,80 @@ impl LLMProcessor {
     }
     
     /// Detect scraping type with confidence scoring
+    ///
+    /// Each type has keywords in several languages; a description scores by the
+    /// language whose keywords it matches best, so adding languages doesn't
+    /// dilute the English score.
     fn detect_scraping_type(input: &str) -> (ScrapingType, f32) {
         let mut scores = std::collections::HashMap::new();
         
         // E-commerce indicators
-        let ecommerce_keywords = [
-            "product", "price", "cost", "buy", "purchase", "shop", "store", "cart", 
-            "checkout", "ecommerce", "e-commerce", "retail", "sale", "discount",
-            "inventory", "stock", "shipping", "delivery", "order", "payment"
+        let ecommerce_keywords: &[&[&str]] = &[
+            &[
+                "product", "price", "cost", "buy", "purchase", "shop", "store", "cart",
+                "checkout", "ecommerce", "e-commerce", "retail", "sale", "discount",
+                "inventory", "stock", "shipping", "delivery", "order", "payment"
+            ],
+            &["상품", "제품", "가격", "쇼핑", "구매", "할인", "재고", "배송"],
+            &["商品", "価格", "値段", "通販", "購入", "セール", "在庫", "送料"],
+            &["商品", "产品", "价格", "购物", "购买", "折扣", "库存", "电商"],
+            &["producto", "precio", "tienda", "comprar", "oferta", "descuento", "envío"],
+            &["produit", "prix", "boutique", "acheter", "promo", "réduction", "livraison"],
+            &["produkt", "preis", "shop", "kaufen", "angebot", "rabatt", "versand"],
+            &["товар", "цена", "магазин", "купить", "скидк", "доставк", "заказ"],
         ];
-        let ecommerce_score = ecommerce_keywords.iter()
-            .map(|&keyword| if input.contains(keyword) { 1.0 } else { 0.0 })
-            .sum::<f32>() / ecommerce_keywords.len() as f32;
-        scores.insert(ScrapingType::Ecommerce, ecommerce_score);
+        scores.insert(ScrapingType::Ecommerce, Self::keyword_score(input, ecommerce_keywords));
         
         // News indicators
-        let news_keywords = [
-            "news", "article", "story", "blog", "post", "headline", "journalism",
-            "report", "breaking", "update", "press", "media", "publication",
-            "editorial", "opinion", "analysis", "coverage"
+        let news_keywords: &[&[&str]] = &[
+            &[
+                "news", "article", "story", "blog", "post", "headline", "journalism",
+                "report", "breaking", "update", "press", "media", "publication",
+                "editorial", "opinion", "analysis", "coverage"
+            ],
+            &["뉴스", "기사", "헤드라인", "속보", "블로그", "언론", "보도"],
+            &["ニュース", "記事", "見出し", "速報", "ブログ", "報道"],
+            &["新闻", "文章", "标题", "头条", "博客", "报道", "媒体"],
+            &["noticia", "artículo", "titular", "periódico", "prensa", "blog"],
+            &["actualité", "article", "titre", "journal", "presse", "blog"],
+            &["nachricht", "artikel", "schlagzeile", "zeitung", "presse", "blog"],
+            &["новост", "стать", "заголов", "газет", "пресс", "блог"],
         ];
-        let news_score = news_keywords.iter()
-            .map(|&keyword| if input.contains(keyword) { 1.0 } else { 0.0 })
-            .sum::<f32>() / news_keywords.len() as f32;
-        scores.insert(ScrapingType::News, news_score);
+        scores.insert(ScrapingType::News, Self::keyword_score(input, news_keywords));
         
         // Directory indicators
-        let directory_keywords = [
-            "directory", "listing", "business", "contact", "address", "phone",
-            "email", "company", "organization", "yellow pages", "catalog",
-            "registry", "database", "index", "guide"
+        let directory_keywords: &[&[&str]] = &[
+            &[
+                "directory", "listing", "business", "contact", "address", "phone",
+                "email", "company", "organization", "yellow pages", "catalog",
+                "registry", "database", "index", "guide"
+            ],
+            &["업체", "주소", "전화", "연락처", "회사", "이메일", "목록"],
+            &["企業", "住所", "電話", "連絡先", "会社", "メール", "一覧"],
+            &["企业", "地址", "电话", "联系", "公司", "邮箱", "名录"],
+            &["directorio", "empresa", "dirección", "teléfono", "contacto", "correo"],
+            &["annuaire", "entreprise", "adresse", "téléphone", "contact", "courriel"],
+            &["verzeichnis", "firma", "unternehmen", "adresse", "telefon", "kontakt"],
+            &["справочник", "компани", "адрес", "телефон", "контакт", "почт"],
         ];
-        let directory_score = directory_keywords.iter()
-            .map(|&keyword| if input.contains(keyword) { 1.0 } else { 0.0 })
-            .sum::<f32>() / directory_keywords.len() as f32;
-        scores.insert(ScrapingType::Directory, directory_score);
+        scores.insert(ScrapingType::Directory, Self::keyword_score(input, directory_keywords));
         
         // Social media indicators
-        let social_keywords = [
-            "social", "post", "comment", "profile", "user", "follower", "like",
-            "share", "tweet", "facebook", "twitter", "instagram", "linkedin",
-            "community", "forum", "discussion", "chat"
+        let social_keywords: &[&[&str]] = &[
+            &[
+                "social", "post", "comment", "profile", "user", "follower", "like",
+                "share", "tweet", "facebook", "twitter", "instagram", "linkedin",
+                "community", "forum", "discussion", "chat"
+            ],
+            &["댓글", "게시글", "프로필", "팔로워", "커뮤니티", "포럼"],
+            &["コメント", "投稿", "プロフィール", "フォロワー", "コミュニティ", "掲示板"],
+            &["评论", "帖子", "个人资料", "粉丝", "社区", "论坛"],
+            &["comentario", "publicación", "perfil", "seguidores", "comunidad", "foro"],
+            &["commentaire", "publication", "profil", "abonnés", "communauté", "forum"],
+            &["kommentar", "beitrag", "profil", "follower", "community", "forum"],
+            &["комментари", "пост", "профил", "подписчик", "сообществ", "форум"],
         ];
-        let social_score = social_keywords.iter()
-            .map(|&keyword| if input.contains(keyword) { 1.0 } else { 0.0 })
-            .sum::<f32>() / social_keywords.len() as f32;
-        scores.insert(ScrapingType::Social, social_score);
+        scores.insert(ScrapingType::Social, Self::keyword_score(input, social_keywords));
         
         // Find the type with highest score
         let (best_type, best_score) = scores.iter()
@@ -86,11 +114,27 @@ impl LLMProcessor {
         }
     }
     
+    /// Share of a type's keywords found, in the language matching best
+    fn keyword_score(input: &str, keywords_by_language: &[&[&str]]) -> f32 {
+        keywords_by_language.iter()
+            .map(|keywords| {
+                let hits = keywords.iter().filter(|&&keyword| input.contains(keyword)).count();
+                hits as f32 / keywords.len() as f32
+            })
+            .fold(0.0, f32::max)
+    }
+    
     fn extract_domain_from_text(text: &str) -> Option<String> {
         // Simple domain extraction
+        let country_domains = [".kr", ".jp", ".cn", ".es", ".fr", ".de", ".ru"];
         let words: Vec<&str> = text.split_whitespace().collect();
         for word in words {
-            if word.contains('.') && (word.contains(".com") || word.contains(".org") || word.contains(".net")) {
+            // Korean and Japanese attach particles straight to the name, as in "example.co.kr에서"
+            let word = word.trim_end_matches(|c: char| !c.is_ascii());
+            let host = word.split('/').find(|part| part.contains('.')).unwrap_or(word);
+            let is_domain = word.contains(".com") || word.contains(".org") || word.contains(".net")
+                || country_domains.iter().any(|tld| host.ends_with(tld));
+            if word.contains('.') && is_domain {
                 return Some(word.to_string());
             }
         }
@@ -102,18 +146,31 @@ impl LLMProcessor {
         
         // Enhanced field extraction with more patterns
         let field_patterns: &[(&str, &[&str])] = &[
-            ("title", &["title", "name", "headline", "heading", "subject"]),
-            ("price", &["price", "cost", "amount", "fee", "rate", "charge"]),
-            ("description", &["description", "summary", "details", "content", "text", "body"]),
-            ("image", &["image", "photo", "picture", "img", "thumbnail", "screenshot"]),
-            ("url", &["url", "link", "href", "website", "page"]),
-            ("date", &["date", "time", "timestamp", "created", "published", "updated"]),
-            ("author", &["author", "writer", "creator", "byline", "publisher"]),
-            ("category", &["category", "tag", "type", "class", "genre", "section"]),
-            ("rating", &["rating", "score", "review", "stars", "grade"]),
-            ("location", &["location", "address", "place", "city", "country"]),
-            ("contact", &["contact", "phone", "email", "address", "telephone"]),
-            ("status", &["status", "state", "condition", "availability"]),
+            ("title", &["title", "name", "headline", "heading", "subject",
+                "제목", "이름", "상품명", "タイトル", "名前", "見出し", "标题", "名称",
+                "título", "nombre", "titre", "titel", "название", "заголов"]),
+            ("price", &["price", "cost", "amount", "fee", "rate", "charge",
+                "가격", "価格", "値段", "价格", "precio", "prix", "preis", "цена", "стоимость"]),
+            ("description", &["description", "summary", "details", "content", "text", "body",
+                "설명", "내용", "説明", "内容", "描述", "descripción", "beschreibung", "описание"]),
+            ("image", &["image", "photo", "picture", "img", "thumbnail", "screenshot",
+                "이미지", "사진", "画像", "写真", "图片", "imagen", "foto", "bild", "изображени", "фото"]),
+            ("url", &["url", "link", "href", "website", "page",
+                "링크", "リンク", "链接", "enlace", "ссылк"]),
+            ("date", &["date", "time", "timestamp", "created", "published", "updated",
+                "날짜", "日付", "日期", "fecha", "datum", "дата"]),
+            ("author", &["author", "writer", "creator", "byline", "publisher",
+                "작성자", "저자", "著者", "作者", "autor", "auteur", "автор"]),
+            ("category", &["category", "tag", "type", "class", "genre", "section",
+                "카테고리", "분류", "カテゴリ", "分类", "categoría", "catégorie", "kategorie", "категори"]),
+            ("rating", &["rating", "score", "review", "stars", "grade",
+                "평점", "별점", "리뷰", "評価", "レビュー", "评分", "valoración", "bewertung", "рейтинг", "отзыв"]),
+            ("location", &["location", "address", "place", "city", "country",
+                "위치", "주소", "住所", "場所", "地址", "位置", "ubicación", "dirección", "adresse", "standort", "адрес"]),
+            ("contact", &["contact", "phone", "email", "address", "telephone",
+                "연락처", "전화", "連絡先", "電話", "联系", "电话", "teléfono", "téléphone", "telefon", "телефон"]),
+            ("status", &["status", "state", "condition", "availability",
+                "상태", "재고", "状態", "在庫", "状态", "estado", "statut", "zustand", "статус", "наличи"]),
         ];
         
         for (field_name, keywords) in field_patterns.iter() {
@@ -161,3 +218,24 @@ pub enum ScrapingType {
     Directory,
     Social,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_intent_understands_other_languages() {
+        let korean = LLMProcessor::extract_intent("shop.example.co.kr에서 상품 이름과 가격을 가져와줘");
+        assert_eq!(korean.scraping_type, ScrapingType::Ecommerce);
+        assert_eq!(korean.domain.as_deref(), Some("shop.example.co.kr"));
+        assert_eq!(korean.fields, vec!["title", "price"]);
+
+        let japanese = LLMProcessor::extract_intent("news.example.jp のニュース記事の見出しと日付");
+        assert_eq!(japanese.scraping_type, ScrapingType::News);
+        assert!(japanese.fields.contains(&"date".to_string()));
+
+        let english = LLMProcessor::extract_intent("Get product prices from shop.example.com");
+        assert_eq!(english.scraping_type, ScrapingType::Ecommerce);
+        assert_eq!(english.domain.as_deref(), Some("shop.example.com"));
+    }
+}
@@ -1,20 +1,51 @@
 /// Prompt templates for LLM interactions
-use crate::dsl::{ScrapePlan, DSLExamples};
+use crate::dsl::{ScrapePlan, DSLExamples, PageSummary, PolitenessPreset};
+use crate::i18n::{self, Language};
 
 /// Build prompt for DSL generation from natural language
 pub fn build_dsl_generation_prompt(user_description: &str) -> String {
+    build_dsl_generation_prompt_with_preset(user_description, PolitenessPreset::from_description(user_description))
+}
+
+/// Build prompt for DSL generation constrained by a politeness preset
+pub fn build_dsl_generation_prompt_with_preset(user_description: &str, preset: PolitenessPreset) -> String {
     let system_prompt = get_system_prompt();
     let examples = get_dsl_examples();
+    let politeness = format_politeness_constraints(preset);
     let user_prompt = format_user_request(user_description);
     
     format!(
-        "{}\n\n{}\n\n{}\n\nPlease generate a scraping plan in YAML format:",
+        "{}\n\n{}\n\n{}\n\n{}\n\nPlease generate a scraping plan in YAML format:",
         system_prompt,
         examples,
+        politeness,
         user_prompt
     )
 }
 
+/// Build prompt for DSL generation grounded in the structure of the target page
+pub fn build_grounded_dsl_generation_prompt(user_description: &str, preset: PolitenessPreset, page: &PageSummary) -> String {
+    let base = build_dsl_generation_prompt_with_preset(user_description, preset);
+    let (instructions, request) = base.rsplit_once("\n\n").unwrap_or((&base, ""));
+    format!("{}\n\n{}\n\n{}", instructions, page.to_prompt(), request)
+}
+
+/// Describe the politeness preset the generated plan must stay within
+fn format_politeness_constraints(preset: PolitenessPreset) -> String {
+    let limits = preset.limits();
+    format!(
+        r#"Politeness preset: {} ({})
+- Set anti_blocking.politeness to "{}"
+- randomized_delays.min_ms must be at least {}
+- respect_robots_txt must be {}"#,
+        preset.name(),
+        preset.description(),
+        preset.code(),
+        limits.min_delay_ms,
+        limits.respect_robots_txt
+    )
+}
+
 /// System prompt defining the LLM's role and capabilities
 fn get_system_prompt() -> &'static str {
     r#"You are an expert web scraping assistant. Your task is to convert natural language descriptions into structured scraping plans using a specific DSL (Domain Specific Language) format.
@@ -62,11 +93,27 @@ Example 2 - News Article Scraping:
 /// Format user request with context
 fn format_user_request(description: &str) -> String {
     format!(
-        "User Request: \"{}\"\n\nBased on this description, create a scraping plan that:",
+        "{}User Request: \"{}\"\n\nBased on this description, create a scraping plan that:",
+        format_language_note(i18n::current_language()),
         description.trim()
     )
 }
 
+/// Tell the model which language the user writes in, ahead of their request
+///
+/// Empty for English. Plans stay in English either way, so field names and
+/// YAML keys match what exports and the rest of the app expect.
+fn format_language_note(language: Language) -> String {
+    if language == Language::English {
+        return String::new();
+    }
+    format!(
+        "The user writes in {0}. Understand {0} words for sites, fields and instructions, \
+but write field names in English snake_case (e.g. \"가격\" or \"価格\" becomes price) and keep YAML keys and selectors as usual.\n\n",
+        language.english_name()
+    )
+}
+
 /// Build prompt for DSL validation and improvement
 pub fn build_dsl_validation_prompt(dsl: &ScrapePlan, issues: &[String]) -> String {
     format!(
@@ -79,7 +126,8 @@ pub fn build_dsl_validation_prompt(dsl: &ScrapePlan, issues: &[String]) -> Strin
 Issues found:
 {}
 
-Please provide an improved version that addresses these issues while maintaining the same scraping goals."#,
+Please provide an improved version that addresses these issues while maintaining the same scraping goals.
+Respond with the complete plan in YAML format."#,
         dsl.to_yaml().unwrap_or_default(),
         issues.iter()
             .enumerate()
@@ -89,6 +137,27 @@ Please provide an improved version that addresses these issues while maintaining
     )
 }
 
+/// Build prompt for changing an existing plan according to a user instruction
+pub fn build_dsl_refinement_prompt(dsl: &ScrapePlan, instruction: &str) -> String {
+    format!(
+        r#"{}
+
+Here is the current scraping plan:
+
+```yaml
+{}
+```
+
+{}The user wants this change: "{}"
+
+Return the complete updated plan in YAML. Change only what the request asks for and keep every other field, selector and setting exactly as it is."#,
+        get_system_prompt(),
+        dsl.to_yaml().unwrap_or_default(),
+        format_language_note(i18n::current_language()),
+        instruction.trim()
+    )
+}
+
 /// Build prompt for selector suggestion
 pub fn build_selector_suggestion_prompt(html_snippet: &str, field_description: &str) -> String {
     format!(
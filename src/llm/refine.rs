@@ -0,0 +1,283 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::processor;
+use crate::dsl::{ExtractionMethod, Field, Filter, FilterCondition, FilterExpr, ScrapePlan, SelectorType, Transform};
+
+/// Metadata key listing the instructions a plan was refined with
+pub const REFINEMENTS_KEY: &str = "refinements";
+
+struct Patterns {
+    remove_field: Regex,
+    add_field: Regex,
+    price_bound: Regex,
+    limit: Regex,
+    pages: Regex,
+    count_noun: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        remove_field: Regex::new(
+            r"^(?:please\s+)?(?:remove|drop|delete|exclude|skip)\s+(?:the\s+)?([a-z][a-z0-9_ ]*?)(?:\s+(?:field|column)s?)?$"
+        ).unwrap(),
+        add_field: Regex::new(
+            r"^(?:please\s+)?(?:add|include|also\s+(?:get|grab|extract|scrape)|extract)\s+(?:the\s+|a\s+|an\s+)?([a-z][a-z0-9_ ]*?)(?:\s+(?:field|column)s?)?$"
+        ).unwrap(),
+        price_bound: Regex::new(
+            r"(under|below|less than|cheaper than|at most|up to|over|above|more than|at least)\s+\$?\s*(\d[\d,]*(?:\.\d+)?)"
+        ).unwrap(),
+        limit: Regex::new(
+            r"(?:limit(?:\s+it)?\s+to|only(?:\s+the)?(?:\s+first)?|first|top|at most|max(?:imum)?(?:\s+of)?)\s+(\d+)\s+(?:items?|results?|rows?|products?|entries|records?)"
+        ).unwrap(),
+        pages: Regex::new(r"(\d+)\s+pages?").unwrap(),
+        count_noun: Regex::new(r"\d+\s+(?:pages?|items?|results?|rows?|entries|records?)").unwrap(),
+    })
+}
+
+/// Patch a plan according to a plain-language instruction without a model
+///
+/// Understands adding and removing fields, price bounds, item limits and page
+/// limits. Everything the instruction doesn't mention is left untouched. Returns
+/// a description of each change made; an empty list means nothing was understood.
+pub fn apply_instruction(plan: &mut ScrapePlan, instruction: &str) -> Vec<String> {
+    let mut changes = Vec::new();
+    let text = instruction.to_lowercase();
+
+    for clause in split_clauses(&text) {
+        let patterns = patterns();
+
+        if let Some(captures) = patterns.remove_field.captures(clause) {
+            if let Some(name) = existing_field(plan, &captures[1]) {
+                remove_field(plan, &name);
+                changes.push(format!("removed field {}", name));
+                continue;
+            }
+        }
+
+        if let Some(captures) = patterns.add_field.captures(clause) {
+            let name = canonical_field_name(&captures[1]);
+            if existing_field(plan, &name).is_none() {
+                plan.rules.fields.push(new_field(&name));
+                changes.push(format!("added field {}", name));
+            }
+            continue;
+        }
+
+        if let Some(captures) = patterns.limit.captures(clause) {
+            if let Ok(limit) = captures[1].parse::<usize>() {
+                plan.output.limit = Some(limit);
+                changes.push(format!("limited output to {} items", limit));
+            }
+        }
+
+        if let Some(captures) = patterns.pages.captures(clause) {
+            if let Ok(pages) = captures[1].parse::<usize>() {
+                plan.target.max_pages = Some(pages);
+                if let Some(pagination) = plan.rules.pagination.as_mut() {
+                    pagination.max_pages = Some(pages);
+                }
+                changes.push(format!("limited crawl to {} pages", pages));
+            }
+        }
+
+        if !patterns.count_noun.is_match(clause) {
+            if let Some(captures) = patterns.price_bound.captures(clause) {
+                if let Ok(bound) = captures[2].replace(',', "").parse::<f64>() {
+                    let upper = matches!(
+                        &captures[1],
+                        "under" | "below" | "less than" | "cheaper than" | "at most" | "up to"
+                    );
+                    let field = price_field(plan, &mut changes);
+                    set_number_bound(plan, &field, bound, upper);
+                    changes.push(format!("{} must be {} {}", field, if upper { "at most" } else { "at least" }, bound));
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+/// Restore field definitions the instruction doesn't mention from the current plan
+///
+/// Models tend to "tidy up" selectors while making an unrelated change; this keeps
+/// manual edits to other fields intact.
+pub fn preserve_unmentioned_fields(current: &ScrapePlan, revised: &mut ScrapePlan, instruction: &str) -> usize {
+    let text = instruction.to_lowercase();
+    let mut restored = 0;
+
+    for field in revised.rules.fields.iter_mut() {
+        if text.contains(&field.name.to_lowercase()) {
+            continue;
+        }
+        if let Some(original) = current.rules.fields.iter().find(|f| f.name == field.name) {
+            if field.selector != original.selector
+                || field.extraction != original.extraction
+                || field.required != original.required
+            {
+                *field = original.clone();
+                restored += 1;
+            }
+        }
+    }
+
+    restored
+}
+
+/// Append an instruction to the plan's refinement history
+pub fn record_refinement(plan: &mut ScrapePlan, history: Option<&serde_json::Value>, instruction: &str) {
+    let mut refinements = history
+        .and_then(|value| value.as_array().cloned())
+        .unwrap_or_default();
+    refinements.push(serde_json::Value::String(instruction.trim().to_string()));
+    plan.add_metadata(REFINEMENTS_KEY.to_string(), serde_json::Value::Array(refinements));
+}
+
+fn split_clauses(text: &str) -> Vec<&str> {
+    text.split([',', ';', '.'])
+        .flat_map(|part| part.split(" and "))
+        .flat_map(|part| part.split(" but "))
+        .map(|clause| clause.trim().trim_start_matches("then ").trim())
+        .filter(|clause| !clause.is_empty())
+        .collect()
+}
+
+/// Name of the plan field a phrase refers to, if any
+fn existing_field(plan: &ScrapePlan, phrase: &str) -> Option<String> {
+    let snake = snake_case(phrase);
+    let canonical = canonical_field_name(phrase);
+    plan.rules.fields.iter()
+        .find(|f| f.name == snake || f.name == canonical)
+        .map(|f| f.name.clone())
+}
+
+/// Field name for a phrase, using the intent extractor's vocabulary when it matches
+fn canonical_field_name(phrase: &str) -> String {
+    let words: Vec<&str> = phrase.split_whitespace().collect();
+    if words.len() == 1 {
+        if let Some(name) = processor::LLMProcessor::extract_intent(phrase).fields.into_iter().next() {
+            return name;
+        }
+    }
+    snake_case(phrase)
+}
+
+fn snake_case(phrase: &str) -> String {
+    phrase.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn new_field(name: &str) -> Field {
+    let (selector, extraction, transform) = match name {
+        "image" => ("img".to_string(), ExtractionMethod::Src, None),
+        "url" => ("a".to_string(), ExtractionMethod::Href, None),
+        "price" | "rating" => (
+            format!(".{}, [class*='{}'], [itemprop='{}']", name, name, name),
+            ExtractionMethod::Text,
+            Some(vec![Transform::Trim]),
+        ),
+        _ => (
+            format!(".{}, [class*='{}']", name, name),
+            ExtractionMethod::Text,
+            Some(vec![Transform::Trim]),
+        ),
+    };
+
+    Field {
+        name: name.to_string(),
+        selector,
+        selector_type: SelectorType::CSS,
+        extraction,
+        required: false,
+        transform,
+    }
+}
+
+/// The field holding prices, adding one if the plan has none
+fn price_field(plan: &mut ScrapePlan, changes: &mut Vec<String>) -> String {
+    let existing = plan.rules.fields.iter()
+        .find(|f| f.name == "price")
+        .or_else(|| plan.rules.fields.iter().find(|f| f.name.contains("price") || f.name.contains("cost")));
+    if let Some(field) = existing {
+        return field.name.clone();
+    }
+
+    plan.rules.fields.push(new_field("price"));
+    changes.push("added field price".to_string());
+    "price".to_string()
+}
+
+/// Set a top-level numeric bound on a field, replacing one of the same kind
+fn set_number_bound(plan: &mut ScrapePlan, field: &str, bound: f64, upper: bool) {
+    let filters = plan.rules.filters.get_or_insert_with(Vec::new);
+    filters.retain(|expr| !matches!(
+        expr,
+        FilterExpr::Condition(Filter { field: f, condition: FilterCondition::NumberMax { .. } }) if upper && f == field
+    ) && !matches!(
+        expr,
+        FilterExpr::Condition(Filter { field: f, condition: FilterCondition::NumberMin { .. } }) if !upper && f == field
+    ));
+
+    let condition = if upper {
+        FilterCondition::NumberMax { max: bound }
+    } else {
+        FilterCondition::NumberMin { min: bound }
+    };
+    filters.push(Filter { field: field.to_string(), condition }.into());
+}
+
+/// Remove a field along with the filters, keys and sort order that refer to it
+fn remove_field(plan: &mut ScrapePlan, name: &str) {
+    plan.rules.fields.retain(|f| f.name != name);
+
+    if let Some(filters) = plan.rules.filters.as_mut() {
+        filters.retain(|expr| !expr.referenced_fields().contains(&name));
+        if filters.is_empty() {
+            plan.rules.filters = None;
+        }
+    }
+    for keys in [&mut plan.output.dedupe_keys, &mut plan.output.hash_fields] {
+        if let Some(list) = keys.as_mut() {
+            list.retain(|key| key != name);
+            if list.is_empty() {
+                *keys = None;
+            }
+        }
+    }
+    if plan.output.sort_by.as_deref() == Some(name) {
+        plan.output.sort_by = None;
+        plan.output.sort_order = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+
+    #[test]
+    fn test_apply_instruction_patches_plan() {
+        let mut plan = DSLExamples::ecommerce_products();
+        let title = plan.rules.fields.iter().find(|f| f.name == "title").cloned().unwrap();
+
+        let changes = apply_instruction(&mut plan, "Only products under $50, and add the rating field");
+        assert_eq!(changes.len(), 2, "{:?}", changes);
+
+        assert!(plan.rules.fields.iter().any(|f| f.name == "rating"));
+        assert!(plan.rules.filters.as_ref().unwrap().iter().any(|expr| matches!(
+            expr,
+            FilterExpr::Condition(Filter { field, condition: FilterCondition::NumberMax { max } })
+                if field == "price" && *max == 50.0
+        )));
+
+        // Fields the instruction didn't touch keep their selectors
+        let kept = plan.rules.fields.iter().find(|f| f.name == "title").unwrap();
+        assert_eq!(kept.selector, title.selector);
+
+        assert!(apply_instruction(&mut plan, "make it nicer").is_empty());
+    }
+}
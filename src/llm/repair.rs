@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dsl::{ScrapePlan, ValidationIssue};
+
+/// Metadata key holding the `RepairTrace` of a generated plan
+pub const REPAIR_TRACE_KEY: &str = "repair_trace";
+
+/// One round of feeding validation problems back to the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairAttempt {
+    pub attempt: usize,
+    /// Problems the model was asked to fix
+    pub issues: Vec<String>,
+    /// Why the model's answer couldn't be used, if it couldn't
+    pub error: Option<String>,
+}
+
+/// How repairing a generated plan ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairOutcome {
+    /// The model produced a plan that passes validation
+    Repaired,
+    /// Attempts ran out and the rule-based plan was used instead
+    FellBack,
+}
+
+/// Record of the repair rounds a generated plan went through
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairTrace {
+    pub attempts: Vec<RepairAttempt>,
+    pub outcome: RepairOutcome,
+    /// Problems left in the model's last plan when falling back
+    #[serde(default)]
+    pub remaining_issues: Vec<String>,
+}
+
+impl RepairTrace {
+    /// Store the trace in the plan's metadata
+    pub fn record(&self, plan: &mut ScrapePlan) {
+        if let Ok(value) = serde_json::to_value(self) {
+            plan.add_metadata(REPAIR_TRACE_KEY.to_string(), value);
+        }
+    }
+}
+
+/// Validation issues as lines for a repair prompt
+pub fn describe_issues(issues: &[ValidationIssue]) -> Vec<String> {
+    issues.iter()
+        .map(|issue| match &issue.suggestion {
+            Some(suggestion) => format!("{} (fix: {})", issue, suggestion),
+            None => issue.to_string(),
+        })
+        .collect()
+}
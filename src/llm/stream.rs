@@ -0,0 +1,129 @@
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+use crate::dsl::ScrapePlan;
+
+/// Something that happened while a plan was being generated
+#[derive(Debug, Clone)]
+pub enum GenerationEvent {
+    /// Text exactly as the model produced it
+    Token(String),
+    /// The plan's YAML as far as it has been produced
+    PartialYaml(String),
+    /// The finished, validated plan; always the last event on success
+    Done(Box<ScrapePlan>),
+    /// Generation failed; always the last event on failure
+    Failed(String),
+}
+
+/// Events of a plan generation running in the background
+pub struct GenerationStream {
+    events: mpsc::UnboundedReceiver<GenerationEvent>,
+}
+
+impl GenerationStream {
+    /// A stream and the sender its events come from
+    pub fn channel() -> (mpsc::UnboundedSender<GenerationEvent>, Self) {
+        let (tx, events) = mpsc::unbounded_channel();
+        (tx, Self { events })
+    }
+
+    /// Next event if one is ready, without waiting
+    ///
+    /// For callers like the UI that check once per frame.
+    pub fn try_next(&mut self) -> Option<GenerationEvent> {
+        self.events.try_recv().ok()
+    }
+}
+
+impl Stream for GenerationStream {
+    type Item = GenerationEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_recv(cx)
+    }
+}
+
+/// Passes model text on as `Token` and `PartialYaml` events
+pub(crate) struct TokenForwarder {
+    events: mpsc::UnboundedSender<GenerationEvent>,
+    text: String,
+    last_yaml_len: usize,
+}
+
+impl TokenForwarder {
+    pub(crate) fn new(events: mpsc::UnboundedSender<GenerationEvent>) -> Self {
+        Self {
+            events,
+            text: String::new(),
+            last_yaml_len: 0,
+        }
+    }
+
+    /// Forward text until the backend's sender is dropped
+    pub(crate) async fn run(mut self, mut tokens: mpsc::UnboundedReceiver<String>) {
+        while let Some(token) = tokens.recv().await {
+            self.push(token);
+        }
+    }
+
+    fn push(&mut self, token: String) {
+        self.text.push_str(&token);
+        let _ = self.events.send(GenerationEvent::Token(token));
+
+        if let Some(yaml) = partial_yaml(&self.text) {
+            if yaml.len() != self.last_yaml_len {
+                self.last_yaml_len = yaml.len();
+                let _ = self.events.send(GenerationEvent::PartialYaml(yaml.to_string()));
+            }
+        }
+    }
+}
+
+/// The YAML part of an unfinished model response
+///
+/// Takes the contents of the first code block, or everything from the first
+/// `version:` or `target:` line when the model doesn't use one. Returns `None`
+/// until the YAML has started.
+pub fn partial_yaml(text: &str) -> Option<&str> {
+    if let Some(fence) = text.find("```") {
+        let after_fence = &text[fence + 3..];
+        // Skip the rest of the opening fence line, such as a `yaml` tag
+        let body = &after_fence[after_fence.find('\n')? + 1..];
+        let body = match body.find("```") {
+            Some(end) => &body[..end],
+            // The closing fence may have started arriving
+            None => body.trim_end().trim_end_matches('`'),
+        };
+        return Some(body.trim_end()).filter(|yaml| !yaml.is_empty());
+    }
+
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("version:") || trimmed.starts_with("target:") {
+            return Some(text[offset..].trim_end());
+        }
+        offset += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_yaml() {
+        assert_eq!(partial_yaml("Here is the plan:"), None);
+        assert_eq!(partial_yaml("Here is the plan:\n```ya"), None);
+        assert_eq!(partial_yaml("Plan:\n```yaml\nversion: \"1.0\"\ntarg"), Some("version: \"1.0\"\ntarg"));
+        assert_eq!(
+            partial_yaml("```yaml\nversion: \"1.0\"\n```\nLet me know!"),
+            Some("version: \"1.0\"")
+        );
+        assert_eq!(partial_yaml("Sure.\nversion: \"1.0\"\nta"), Some("version: \"1.0\"\nta"));
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 use tracing::info;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::{
@@ -43,6 +45,22 @@ impl Default for LoggingConfig {
     }
 }
 
+/// Prefix of the rolling log files, followed by the date
+const LOG_FILE_PREFIX: &str = "winscrape";
+
+/// Bytes read from the end of a log file per requested line
+const TAIL_BYTES_PER_LINE: u64 = 512;
+
+/// Crate name left out of log components
+const CRATE_TARGET_PREFIX: &str = "winscrape_studio::";
+
+/// Directory the log files are written to
+pub fn log_directory() -> PathBuf {
+    directories::ProjectDirs::from("com", "winscrape", "studio")
+        .map(|dirs| dirs.data_dir().join("logs"))
+        .unwrap_or_else(|| PathBuf::from("logs"))
+}
+
 /// Initialize logging system
 pub fn init_logging(config: &LoggingConfig) -> Result<()> {
     let env_filter = EnvFilter::try_from_default_env()
@@ -73,7 +91,7 @@ pub fn init_logging(config: &LoggingConfig) -> Result<()> {
         
         let file_appender = RollingFileAppender::builder()
             .rotation(Rotation::DAILY)
-            .filename_prefix("winscrape")
+            .filename_prefix(LOG_FILE_PREFIX)
             .filename_suffix("log")
             .max_log_files(config.max_files)
             .build(&config.log_directory)?;
@@ -83,6 +101,7 @@ pub fn init_logging(config: &LoggingConfig) -> Result<()> {
                 .with_target(false)
                 .with_thread_ids(true)
                 .with_thread_names(true)
+                .with_ansi(false)
                 .with_target(config.include_targets)
                 .with_span_events(if config.include_spans {
                     FmtSpan::CLOSE
@@ -93,6 +112,7 @@ pub fn init_logging(config: &LoggingConfig) -> Result<()> {
                 .boxed()
         } else {
             fmt::layer()
+                .with_ansi(false)
                 .with_target(config.include_targets)
                 .with_span_events(if config.include_spans {
                     FmtSpan::CLOSE
@@ -123,6 +143,193 @@ pub fn init_logging(config: &LoggingConfig) -> Result<()> {
     Ok(())
 }
 
+/// One entry of the log files or of a job's log, as shown in the log viewer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogLine {
+    pub timestamp: DateTime<Utc>,
+    /// `error`, `warn`, `info`, `debug` or `trace`
+    pub level: String,
+    /// Module that logged the line, or `job` for job log entries
+    pub component: String,
+    pub job_id: Option<String>,
+    pub message: String,
+}
+
+impl LogLine {
+    /// Parse a line written by the text file layer, e.g.
+    /// `2024-05-01T10:00:00.123Z  INFO winscrape_studio::core: Job started`
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = strip_ansi(line);
+        let mut parts = line.trim_end().splitn(2, char::is_whitespace);
+        let timestamp = DateTime::parse_from_rfc3339(parts.next()?).ok()?.with_timezone(&Utc);
+        let rest = parts.next()?.trim_start();
+        let (level, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let level = level.to_ascii_lowercase();
+        level_rank(&level)?;
+
+        // Spans and the target come before the message, each ending in `: `
+        let mut component = "";
+        let mut message = rest.trim_start();
+        while let Some((prefix, remainder)) = message.split_once(": ") {
+            let is_span = prefix.ends_with('}');
+            let is_target = !prefix.contains(char::is_whitespace)
+                && (prefix.contains("::") || prefix == CRATE_TARGET_PREFIX.trim_end_matches(':'));
+            if !is_span && !is_target {
+                break;
+            }
+            if is_target {
+                component = prefix;
+            }
+            message = remainder;
+        }
+
+        Some(Self {
+            timestamp,
+            component: component.strip_prefix(CRATE_TARGET_PREFIX).unwrap_or(component).to_string(),
+            job_id: field_value(message, "job_id"),
+            level,
+            message: message.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for LogLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:>5} {}: {}", self.timestamp.to_rfc3339(), self.level.to_uppercase(), self.component, self.message)
+    }
+}
+
+/// Which log lines the log viewer shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogFilter {
+    /// Least severe level shown; all levels when `None`
+    pub level: Option<String>,
+    pub component: Option<String>,
+    pub job_id: Option<String>,
+    /// Case-insensitive text the message or component contains
+    pub search: String,
+}
+
+impl LogFilter {
+    pub fn matches(&self, line: &LogLine) -> bool {
+        if let Some(level) = &self.level {
+            if level_rank(&line.level).unwrap_or(u8::MAX) > level_rank(level).unwrap_or(u8::MAX) {
+                return false;
+            }
+        }
+        if self.component.as_ref().is_some_and(|component| *component != line.component) {
+            return false;
+        }
+        if let Some(job_id) = &self.job_id {
+            if line.job_id.as_ref() != Some(job_id) && !line.message.contains(job_id.as_str()) {
+                return false;
+            }
+        }
+        let search = self.search.trim().to_lowercase();
+        search.is_empty()
+            || line.message.to_lowercase().contains(&search)
+            || line.component.to_lowercase().contains(&search)
+    }
+}
+
+/// Severity of a level, most severe first
+pub fn level_rank(level: &str) -> Option<u8> {
+    match level {
+        "error" => Some(0),
+        "warn" => Some(1),
+        "info" => Some(2),
+        "debug" => Some(3),
+        "trace" => Some(4),
+        _ => None,
+    }
+}
+
+/// Last `max_lines` entries of the log files in `dir`, oldest first
+///
+/// Lines that do not start a new entry, like the rest of a multi-line
+/// message, are added to the entry before them.
+pub fn tail_log_files(dir: &Path, max_lines: usize) -> Result<Vec<LogLine>> {
+    let mut files: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.file_name().and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX) && name.ends_with(".log")))
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    // Rolled files are dated, so names sort oldest first
+    files.sort();
+
+    let mut lines = Vec::new();
+    for path in files.iter().rev() {
+        let mut entries: Vec<LogLine> = Vec::new();
+        for line in read_tail(path, max_lines as u64 * TAIL_BYTES_PER_LINE)?.lines() {
+            match LogLine::parse(line) {
+                Some(entry) => entries.push(entry),
+                None => {
+                    if let Some(last) = entries.last_mut().filter(|_| !line.trim().is_empty()) {
+                        last.message.push('\n');
+                        last.message.push_str(&strip_ansi(line));
+                    }
+                }
+            }
+        }
+        entries.append(&mut lines);
+        lines = entries;
+        if lines.len() >= max_lines {
+            break;
+        }
+    }
+
+    let excess = lines.len().saturating_sub(max_lines);
+    lines.drain(..excess);
+    Ok(lines)
+}
+
+/// End of a file, starting at a line boundary
+fn read_tail(path: &Path, max_bytes: u64) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    Ok(match text.split_once('\n') {
+        Some((_, rest)) if start > 0 => rest.to_string(),
+        _ => text.into_owned(),
+    })
+}
+
+/// Value of a `key=value` field of a formatted message
+fn field_value(message: &str, key: &str) -> Option<String> {
+    let start = message.find(&format!("{}=", key))? + key.len() + 1;
+    let value = message[start..].trim_start_matches('"');
+    let value: String = value.chars().take_while(|c| !c.is_whitespace() && *c != '"').collect();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Remove terminal colour codes, which older log files contain
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skip to the end of the escape sequence
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
 /// Structured logging context
 #[derive(Debug, Clone, Serialize)]
 pub struct LogContext {
@@ -401,10 +608,61 @@ impl PerformanceLogger {
     }
 }
 
+/// Severities of security events, most severe first
+pub const AUDIT_SEVERITIES: [&str; 4] = ["critical", "high", "medium", "low"];
+
+/// A security event, as stored in the `audit_events` table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp: DateTime<Utc>,
+    /// What happened, e.g. `blocked_domain` or `rejected_input`
+    pub event_type: String,
+    /// One of [`AUDIT_SEVERITIES`]
+    pub severity: String,
+    pub description: String,
+    pub job_id: Option<String>,
+    pub domain: Option<String>,
+    pub user_id: Option<String>,
+}
+
+/// Severity of a security event, most severe first; unknown ones rank last
+pub fn severity_rank(severity: &str) -> u8 {
+    AUDIT_SEVERITIES.iter()
+        .position(|known| *known == severity)
+        .unwrap_or(AUDIT_SEVERITIES.len()) as u8
+}
+
+/// Where security events go besides the log, see [`AuditLogger::set_sink`]
+static AUDIT_SINK: std::sync::Mutex<Option<tokio::sync::mpsc::UnboundedSender<AuditEvent>>> =
+    std::sync::Mutex::new(None);
+
 /// Audit logging for security events
 pub struct AuditLogger;
 
 impl AuditLogger {
+    /// Also send security events to `sink`, replacing the previous one
+    pub fn set_sink(sink: tokio::sync::mpsc::UnboundedSender<AuditEvent>) {
+        if let Ok(mut slot) = AUDIT_SINK.lock() {
+            *slot = Some(sink);
+        }
+    }
+    
+    /// Hand an event to the sink, if one is set
+    fn record(event_type: &str, severity: &str, description: &str, context: &LogContext) {
+        let Ok(slot) = AUDIT_SINK.lock() else { return };
+        if let Some(sink) = slot.as_ref() {
+            let _ = sink.send(AuditEvent {
+                timestamp: Utc::now(),
+                event_type: event_type.to_string(),
+                severity: severity.to_string(),
+                description: description.to_string(),
+                job_id: context.job_id.clone(),
+                domain: context.domain.clone(),
+                user_id: context.user_id.clone(),
+            });
+        }
+    }
+    
     pub fn log_security_event(
         event_type: &str,
         severity: &str,
@@ -417,6 +675,7 @@ impl AuditLogger {
         .with_string_field("event_type", event_type)
         .with_string_field("severity", severity)
         .with_string_field("audit", "true");
+        Self::record(event_type, severity, description, &audit_context);
         
         match severity {
             "critical" | "high" => {
@@ -493,6 +752,10 @@ impl AuditLogger {
         } else {
             "Access denied"
         };
+        if !success {
+            let description = format!("Access to {} denied{}", resource, reason.map(|r| format!(": {}", r)).unwrap_or_default());
+            Self::record("access_denied", "medium", &description, &context);
+        }
         
         if success {
             tracing::info!(
@@ -654,4 +917,27 @@ mod tests {
         
         assert_eq!(metrics.get_average_performance("scraping"), Some(150.0));
     }
+    
+    #[test]
+    fn test_log_line_parse_and_filter() {
+        let line = LogLine::parse(
+            "2024-05-01T10:00:00.123456Z \u{1b}[33m WARN\u{1b}[0m run{id=1}: winscrape_studio::core::job_manager: job_id=\"job-7\" Retrying: timed out"
+        ).unwrap();
+        assert_eq!(line.level, "warn");
+        assert_eq!(line.component, "core::job_manager");
+        assert_eq!(line.job_id.as_deref(), Some("job-7"));
+        assert_eq!(line.message, "job_id=\"job-7\" Retrying: timed out");
+        assert!(LogLine::parse("    at src/main.rs:10").is_none());
+        
+        let mut filter = LogFilter { level: Some("warn".to_string()), ..Default::default() };
+        assert!(filter.matches(&line));
+        filter.level = Some("error".to_string());
+        assert!(!filter.matches(&line));
+        filter.level = None;
+        filter.search = "TIMED".to_string();
+        filter.job_id = Some("job-7".to_string());
+        assert!(filter.matches(&line));
+        filter.component = Some("scraper".to_string());
+        assert!(!filter.matches(&line));
+    }
 }
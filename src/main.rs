@@ -18,11 +18,14 @@ mod utils;
 mod error;
 mod logging;
 mod i18n;
+mod notifications;
+mod plugins;
 #[cfg(feature = "api")]
 mod api;
 
 use crate::core::WinScrapeStudio;
 use crate::config::AppConfig;
+use crate::core::self_check::SelfCheckReport;
 use crate::logging::{LoggingConfig, LogContext, RequestIdGenerator};
 
 #[tokio::main]
@@ -32,6 +35,13 @@ async fn main() -> Result<()> {
     
     info!("Starting WinScrape Studio v{}", env!("CARGO_PKG_VERSION"));
     
+    // Check configuration, directories and the database before opening anything
+    let (checked_config, report) = crate::core::self_check::startup_check(None).await;
+    if report.needs_safe_mode() {
+        error!("Startup self-check found {} problem(s), starting in safe mode", report.problems.len());
+        return run_safe_mode(checked_config, report);
+    }
+    
     // Load configuration
     let config = AppConfig::load().await?;
     info!("Configuration loaded successfully");
@@ -50,7 +60,9 @@ async fn main() -> Result<()> {
             error!("Failed to initialize GUI: {}", e);
             return Err(anyhow::anyhow!("GUI initialization failed: {}", e));
         }
-        if let Err(e) = windows_app.run() {
+        let result = windows_app.run();
+        windows_app.shutdown().await;
+        if let Err(e) = result {
             error!("Failed to run GUI: {}", e);
             return Err(anyhow::anyhow!("GUI execution failed: {}", e));
         }
@@ -66,11 +78,22 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Show the startup problems with one-click repairs
+#[cfg(feature = "ui")]
+fn run_safe_mode(config: AppConfig, report: SelfCheckReport) -> Result<()> {
+    crate::ui::safe_mode::run(config, report)
+}
+
+/// Without a GUI the problems can only be reported
+#[cfg(not(feature = "ui"))]
+fn run_safe_mode(_config: AppConfig, report: SelfCheckReport) -> Result<()> {
+    for problem in &report.problems {
+        error!("{}: {}", problem.check, problem.message);
+    }
+    Err(anyhow::anyhow!("Startup self-check failed; run `wss-cli doctor --repair` to fix it"))
+}
+
 fn init_logging() -> Result<()> {
-    let log_dir = directories::ProjectDirs::from("com", "winscrape", "studio")
-        .map(|dirs| dirs.data_dir().join("logs"))
-        .unwrap_or_else(|| std::path::PathBuf::from("logs"));
-    
     let logging_config = LoggingConfig {
         level: std::env::var("WSS_LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
         file_enabled: true,
@@ -78,7 +101,7 @@ fn init_logging() -> Result<()> {
         json_format: false,
         max_file_size_mb: 10,
         max_files: 5,
-        log_directory: log_dir,
+        log_directory: crate::logging::log_directory(),
         include_spans: true,
         include_targets: true,
         structured_fields: true,
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::Notification;
+
+/// Longest wait for the notification helper to hand the notification over
+const SHOW_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// App ID toasts are shown under; Windows only shows toasts of registered
+/// apps, and PowerShell is registered on every install
+const TOAST_APP_ID: &str = r"{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\WindowsPowerShell\v1.0\powershell.exe";
+
+/// Show a notification on the desktop
+///
+/// A toast through PowerShell on Windows, `osascript` on macOS and
+/// `notify-send` elsewhere.
+pub async fn show(notification: &Notification) -> Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-NonInteractive", "-Command", &toast_script(notification)]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.args([
+            "-e",
+            &format!(
+                "display notification {} with title {}",
+                applescript_string(&notification.body),
+                applescript_string(&notification.title)
+            ),
+        ]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name", "WinScrape Studio", &notification.title, &notification.body]);
+        command
+    };
+    command.kill_on_drop(true);
+
+    let output = tokio::time::timeout(SHOW_TIMEOUT, command.output()).await
+        .map_err(|_| anyhow::anyhow!("Notification helper did not finish within {}s", SHOW_TIMEOUT.as_secs()))??;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Notification helper exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// PowerShell script showing the notification as a toast that opens its export when clicked
+fn toast_script(notification: &Notification) -> String {
+    let launch = notification.open.as_deref()
+        .and_then(|path| url::Url::from_file_path(path).ok())
+        .map(|url| format!(r#" activationType="protocol" launch="{}""#, xml_escape(url.as_str())))
+        .unwrap_or_default();
+    let xml = format!(
+        r#"<toast{}><visual><binding template="ToastGeneric"><text>{}</text><text>{}</text></binding></visual></toast>"#,
+        launch,
+        xml_escape(&notification.title),
+        xml_escape(&notification.body)
+    );
+
+    format!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+         [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime] | Out-Null; \
+         $xml = New-Object Windows.Data.Xml.Dom.XmlDocument; \
+         $xml.LoadXml({}); \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier({}).Show((New-Object Windows.UI.Notifications.ToastNotification $xml))",
+        powershell_string(&xml),
+        powershell_string(TOAST_APP_ID)
+    )
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Single-quoted PowerShell literal, in which only quotes are special and doubled
+fn powershell_string(text: &str) -> String {
+    let mut literal = String::from("'");
+    for c in text.chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            literal.push(c);
+        }
+        literal.push(c);
+    }
+    literal.push('\'');
+    literal
+}
+
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,232 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+pub mod desktop;
+pub mod smtp;
+
+use crate::config::NotificationConfig;
+use crate::dsl::NotifyOptions;
+use crate::scraper::ScrapeSummary;
+use crate::storage::{Job, JobStatus};
+use crate::utils::format_duration;
+
+/// What is said about a finished job
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    /// Export opened by clicking the notification
+    pub open: Option<String>,
+}
+
+impl Notification {
+    /// Describe a completed or failed job with its counts and exports; `None` for other statuses
+    pub fn for_job(job: &Job, rows: usize, exports: &[String], error: Option<&str>) -> Option<Self> {
+        let summary = job.summary_json.as_deref()
+            .and_then(|json| serde_json::from_str::<ScrapeSummary>(json).ok())
+            .unwrap_or_default();
+        let elapsed = (chrono::Utc::now() - job.created_at).to_std().unwrap_or_default();
+
+        let mut lines = Vec::new();
+        let title = match job.status {
+            JobStatus::Completed => {
+                lines.push(format!(
+                    "{} rows from {} pages in {}",
+                    rows, summary.pages_scraped, format_duration(elapsed)
+                ));
+                if summary.warning_count() > 0 {
+                    lines.push(format!("Completed with {} warnings", summary.warning_count()));
+                }
+                format!("Job completed: {}", job.title)
+            }
+            JobStatus::Failed => {
+                lines.push(format!(
+                    "Failed after {} with {} rows from {} pages",
+                    format_duration(elapsed), rows, summary.pages_scraped
+                ));
+                if let Some(error) = error {
+                    lines.push(error.to_string());
+                }
+                format!("Job failed: {}", job.title)
+            }
+            _ => return None,
+        };
+        if summary.pages_failed > 0 {
+            lines.push(format!("{} pages failed", summary.pages_failed));
+        }
+        for export in exports {
+            lines.push(format!("Exported to {}", export));
+        }
+        lines.push(format!("Job {}", job.id));
+
+        Some(Self {
+            title,
+            body: lines.join("\n"),
+            open: exports.first().cloned(),
+        })
+    }
+}
+
+/// Where the notification of one job goes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Channels {
+    pub desktop: bool,
+    /// Email recipients; none when no email is sent
+    pub email_to: Vec<String>,
+}
+
+/// Sends notifications about finished jobs through the configured channels
+#[derive(Clone)]
+pub struct Notifier {
+    config: NotificationConfig,
+    /// Set while the window is hidden; clones share it
+    background: Arc<AtomicBool>,
+}
+
+impl Notifier {
+    pub fn new(config: &NotificationConfig) -> Self {
+        Self { config: config.clone(), background: Arc::new(AtomicBool::new(false)) }
+    }
+
+    /// While running in the background every completed or failed job shows a desktop notification
+    pub fn set_background(&self, background: bool) {
+        self.background.store(background, Ordering::Relaxed);
+    }
+
+    /// Channels for a job that ended with `status`, after the plan's overrides
+    pub fn channels(&self, status: &JobStatus, options: Option<&NotifyOptions>) -> Option<Channels> {
+        let options = options.cloned().unwrap_or_default();
+        let wanted = match status {
+            JobStatus::Completed => options.on_completed.unwrap_or(self.config.on_completed),
+            JobStatus::Failed => options.on_failed.unwrap_or(self.config.on_failed),
+            _ => false,
+        };
+        // Nothing else tells about a finished job while the window is hidden
+        let background = self.background.load(Ordering::Relaxed)
+            && matches!(status, JobStatus::Completed | JobStatus::Failed);
+        if !wanted {
+            return background.then(|| Channels { desktop: true, email_to: Vec::new() });
+        }
+
+        let email_to = match &self.config.email {
+            Some(email) if options.email.unwrap_or(true) => {
+                let allowed = |to: &String| {
+                    let known = email.to.iter().chain(&email.allowed_recipients)
+                        .any(|address| address.eq_ignore_ascii_case(to));
+                    if !known {
+                        warn!("Not emailing {}, which is not among the configured recipients", to);
+                    }
+                    known
+                };
+                let email_to: Vec<String> = options.email_to.into_iter().filter(allowed).collect();
+                if email_to.is_empty() { email.to.clone() } else { email_to }
+            }
+            _ => Vec::new(),
+        };
+        let channels = Channels {
+            desktop: options.desktop.unwrap_or(self.config.desktop) || background,
+            email_to,
+        };
+        (channels.desktop || !channels.email_to.is_empty()).then_some(channels)
+    }
+
+    /// Send a notification through the given channels
+    ///
+    /// Failures are logged rather than returned, so they never fail the job.
+    pub async fn send(&self, channels: &Channels, notification: &Notification) {
+        if channels.desktop {
+            if let Err(e) = desktop::show(notification).await {
+                warn!("Desktop notification failed: {}", e);
+            }
+        }
+        if let Some(email) = self.config.email.as_ref().filter(|_| !channels.email_to.is_empty()) {
+            match smtp::send(email, &channels.email_to, &notification.title, &notification.body).await {
+                Ok(()) => info!("Notification email sent to {}", channels.email_to.join(", ")),
+                Err(e) => warn!("Notification email failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SmtpConfig, SmtpSecurity};
+
+    #[test]
+    fn test_plan_overrides_and_message() {
+        let notifier = Notifier::new(&NotificationConfig {
+            desktop: true,
+            on_completed: false,
+            on_failed: true,
+            email: Some(SmtpConfig {
+                host: "smtp.example.com".to_string(),
+                port: 587,
+                security: SmtpSecurity::StartTls,
+                username: None,
+                password_env: "WSS_SMTP_PASSWORD".to_string(),
+                from: "bot@example.com".to_string(),
+                to: vec!["team@example.com".to_string()],
+                allowed_recipients: vec!["me@example.com".to_string()],
+            }),
+        });
+        assert_eq!(notifier.channels(&JobStatus::Completed, None), None);
+        let options = NotifyOptions {
+            on_completed: Some(true),
+            desktop: Some(false),
+            email_to: vec!["me@example.com".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            notifier.channels(&JobStatus::Completed, Some(&options)),
+            Some(Channels { desktop: false, email_to: vec!["me@example.com".to_string()] })
+        );
+
+        // Plans can't make the server mail anyone else
+        let options = NotifyOptions {
+            on_completed: Some(true),
+            desktop: Some(false),
+            email_to: vec!["ME@example.com".to_string(), "someone@example.org".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(notifier.channels(&JobStatus::Completed, Some(&options)).unwrap().email_to, vec!["ME@example.com"]);
+        let options = NotifyOptions { email_to: vec!["someone@example.org".to_string()], ..options };
+        assert_eq!(notifier.channels(&JobStatus::Completed, Some(&options)).unwrap().email_to, vec!["team@example.com"]);
+
+        let job = Job {
+            id: "job-1".to_string(),
+            title: "shop.example.com".to_string(),
+            status: JobStatus::Completed,
+            created_at: chrono::Utc::now(),
+            plan_yaml: String::new(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: Some(serde_json::to_string(&ScrapeSummary { pages_scraped: 3, pages_failed: 1, ..Default::default() }).unwrap()),
+        };
+        let exports = vec!["C:\\exports\\shop.csv".to_string()];
+        let notification = Notification::for_job(&job, 42, &exports, None).unwrap();
+        assert_eq!(notification.title, "Job completed: shop.example.com");
+        assert!(notification.body.starts_with("42 rows from 3 pages in "));
+        assert!(notification.body.contains("1 pages failed\nExported to C:\\exports\\shop.csv"));
+        assert_eq!(notification.open.as_deref(), Some("C:\\exports\\shop.csv"));
+    }
+
+    #[test]
+    fn test_background_always_notifies_on_desktop() {
+        let notifier = Notifier::new(&NotificationConfig {
+            desktop: false,
+            on_completed: false,
+            on_failed: true,
+            email: None,
+        });
+        assert_eq!(notifier.channels(&JobStatus::Completed, None), None);
+
+        notifier.clone().set_background(true);
+        let desktop_only = Some(Channels { desktop: true, email_to: Vec::new() });
+        assert_eq!(notifier.channels(&JobStatus::Completed, None), desktop_only);
+        assert_eq!(notifier.channels(&JobStatus::Failed, None), desktop_only);
+        assert_eq!(notifier.channels(&JobStatus::Cancelled, None), None);
+    }
+}
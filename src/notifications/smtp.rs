@@ -0,0 +1,185 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsConnector};
+
+use crate::config::{SmtpConfig, SmtpSecurity};
+
+/// Longest time for delivering one email, from connecting to the server's final reply
+const SEND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Send a plain text email through the configured server
+pub async fn send(config: &SmtpConfig, to: &[String], subject: &str, body: &str) -> Result<()> {
+    for address in std::iter::once(&config.from).chain(to) {
+        check_address(address)?;
+    }
+    tokio::time::timeout(SEND_TIMEOUT, deliver(config, to, subject, body)).await
+        .map_err(|_| anyhow::anyhow!("SMTP server {} did not answer within {}s", config.host, SEND_TIMEOUT.as_secs()))?
+}
+
+/// Check an address before it goes into an SMTP command or header
+///
+/// Line breaks would start a command or header of their own, and angle
+/// brackets would end the address early.
+pub fn check_address(address: &str) -> Result<()> {
+    if address.contains(['\r', '\n', '<', '>']) || !address.contains('@') {
+        return Err(anyhow::anyhow!("Invalid email address {:?}", address));
+    }
+    Ok(())
+}
+
+async fn deliver(config: &SmtpConfig, to: &[String], subject: &str, body: &str) -> Result<()> {
+    let tcp = TcpStream::connect((config.host.as_str(), config.port)).await?;
+    let message = format_message(&config.from, to, subject, body);
+
+    match config.security {
+        SmtpSecurity::Tls => {
+            let mut session = Session::new(tls_connect(&config.host, tcp).await?);
+            session.expect(220).await?;
+            session.transact(config, to, &message).await
+        }
+        SmtpSecurity::StartTls => {
+            let mut session = Session::new(tcp);
+            session.expect(220).await?;
+            session.ehlo().await?;
+            session.command("STARTTLS", 220).await?;
+            let tcp = session.stream.into_inner();
+            Session::new(tls_connect(&config.host, tcp).await?).transact(config, to, &message).await
+        }
+        SmtpSecurity::None => {
+            let mut session = Session::new(tcp);
+            session.expect(220).await?;
+            session.transact(config, to, &message).await
+        }
+    }
+}
+
+async fn tls_connect(host: &str, tcp: TcpStream) -> Result<tokio_native_tls::TlsStream<TcpStream>> {
+    let connector = TlsConnector::from(native_tls::TlsConnector::new()?);
+    Ok(connector.connect(host, tcp).await?)
+}
+
+/// An SMTP conversation over one connection
+struct Session<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Session<S> {
+    fn new(stream: S) -> Self {
+        Self { stream: BufReader::new(stream) }
+    }
+
+    /// Greet the server, log in and hand over the message
+    async fn transact(&mut self, config: &SmtpConfig, to: &[String], message: &str) -> Result<()> {
+        let extensions = self.ehlo().await?;
+        if let Some(username) = &config.username {
+            let password = std::env::var(&config.password_env)
+                .map_err(|_| anyhow::anyhow!("SMTP password variable {} is not set", config.password_env))?;
+            self.login(&extensions, username, &password).await?;
+        }
+
+        self.command(&format!("MAIL FROM:<{}>", config.from), 250).await?;
+        for recipient in to {
+            self.command(&format!("RCPT TO:<{}>", recipient), 250).await?;
+        }
+        self.command("DATA", 354).await?;
+        self.command(message, 250).await?;
+        let _ = self.command("QUIT", 221).await;
+        Ok(())
+    }
+
+    /// Introduce ourselves, returning the extensions the server offers
+    async fn ehlo(&mut self) -> Result<String> {
+        self.command("EHLO winscrape-studio", 250).await
+    }
+
+    async fn login(&mut self, extensions: &str, username: &str, password: &str) -> Result<()> {
+        let auth = extensions.lines()
+            .find(|line| line.to_ascii_uppercase().starts_with("AUTH"))
+            .map(|line| line.to_ascii_uppercase())
+            .unwrap_or_default();
+        if auth.contains("LOGIN") && !auth.contains("PLAIN") {
+            self.command("AUTH LOGIN", 334).await?;
+            self.command(&STANDARD.encode(username), 334).await?;
+            self.command(&STANDARD.encode(password), 235).await?;
+        } else {
+            let credentials = STANDARD.encode(format!("\0{}\0{}", username, password));
+            self.command(&format!("AUTH PLAIN {}", credentials), 235).await?;
+        }
+        Ok(())
+    }
+
+    /// Send a line and check the reply code, returning the reply text
+    async fn command(&mut self, line: &str, code: u16) -> Result<String> {
+        self.stream.write_all(line.as_bytes()).await?;
+        self.stream.write_all(b"\r\n").await?;
+        self.stream.flush().await?;
+        self.expect(code).await
+    }
+
+    /// Read a reply, which may span several lines, and check its code
+    async fn expect(&mut self, code: u16) -> Result<String> {
+        let mut text = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                return Err(anyhow::anyhow!("SMTP server closed the connection"));
+            }
+            let line = line.trim_end();
+            let reply: u16 = line.get(..3).and_then(|reply| reply.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!("Invalid SMTP reply: {}", line))?;
+            if reply != code {
+                return Err(anyhow::anyhow!("SMTP server replied {}", line));
+            }
+            text.push_str(line.get(4..).unwrap_or_default());
+            text.push('\n');
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(text);
+            }
+        }
+    }
+}
+
+/// The message as sent after `DATA`, up to the dot that ends it
+///
+/// The body is base64 encoded, so no line needs dot-stuffing and any
+/// character set gets through.
+fn format_message(from: &str, to: &[String], subject: &str, body: &str) -> String {
+    let body = STANDARD.encode(body.replace('\n', "\r\n"));
+    let lines: Vec<&str> = body.as_bytes()
+        .chunks(76)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    let subject = if subject.is_ascii() {
+        subject.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", STANDARD.encode(subject))
+    };
+
+    format!(
+        "From: <{}>\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@winscrape-studio>\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{}\r\n.",
+        from,
+        to.iter().map(|to| format!("<{}>", to)).collect::<Vec<_>>().join(", "),
+        subject.replace(['\r', '\n'], " "),
+        chrono::Utc::now().to_rfc2822(),
+        uuid::Uuid::new_v4(),
+        lines.join("\r\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addresses_cannot_inject_commands_or_headers() {
+        assert!(check_address("team@example.com").is_ok());
+        assert!(check_address("team@example.com>\r\nRCPT TO:<victim@example.org").is_err());
+        assert!(check_address("team@example.com\nBcc: victim@example.org").is_err());
+        assert!(check_address("<team@example.com>").is_err());
+        assert!(check_address("team").is_err());
+    }
+}
@@ -0,0 +1,287 @@
+//! Extension points for custom transforms, filter conditions and export sinks
+//!
+//! Plugins are registered once at startup, either in-process with
+//! [`register`] or from a plugins directory with [`load_directory`].
+//! Plans refer to them by name: `{ type: plugin, name: slugify }` as a field
+//! transform or filter condition, and `{ kind: plugin, name: s3 }` as the
+//! destination of an export profile.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::{info, warn};
+
+#[cfg(feature = "plugins")]
+pub mod native;
+#[cfg(any(feature = "plugins", feature = "scripting"))]
+pub mod wasm;
+
+/// Version of the native plugin interface; native plugins built against another one are refused
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// Export a plugin from a `cdylib` crate so it can be loaded from the plugins directory
+///
+/// ```ignore
+/// winscrape_studio::declare_plugin!(MyPlugin::default());
+/// ```
+#[macro_export]
+macro_rules! declare_plugin {
+    ($constructor:expr) => {
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static wss_plugin_api_version: u32 = $crate::plugins::PLUGIN_API_VERSION;
+
+        #[no_mangle]
+        pub fn wss_plugin_create() -> Box<dyn $crate::plugins::Plugin> {
+            Box::new($constructor)
+        }
+    };
+}
+
+/// Changes a field value, after the built-in transforms before it
+pub trait TransformPlugin: Send + Sync {
+    /// `args` is the `args` value given in the plan, `null` when omitted
+    fn apply(&self, value: serde_json::Value, args: &serde_json::Value) -> Result<serde_json::Value>;
+}
+
+/// Decides whether an item is kept, based on one field value
+pub trait FilterPlugin: Send + Sync {
+    fn matches(&self, value: &serde_json::Value, args: &serde_json::Value) -> Result<bool>;
+}
+
+/// Receives exported rows
+///
+/// Called from a blocking thread, so implementations may do blocking I/O.
+pub trait SinkPlugin: Send + Sync {
+    /// Write one page of rows, already shaped by the export options
+    fn write(&self, rows: &[serde_json::Value], args: &serde_json::Value) -> Result<()>;
+
+    /// Called after the last page; returns where the rows went, for display
+    fn finish(&self, _args: &serde_json::Value) -> Result<String> {
+        Ok(String::new())
+    }
+}
+
+/// A set of extensions registered together
+pub trait Plugin: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Add the plugin's transforms, filters and sinks
+    fn register(&self, registry: &mut PluginRegistry);
+}
+
+/// How a plugin was loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginKind {
+    /// Registered by the embedding program
+    #[default]
+    Builtin,
+    /// A dynamic library
+    Native,
+    /// A WebAssembly module, run in a sandbox
+    Wasm,
+}
+
+/// A registered plugin and what it provides
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub kind: PluginKind,
+    pub path: Option<PathBuf>,
+    pub transforms: Vec<String>,
+    pub filters: Vec<String>,
+    pub sinks: Vec<String>,
+}
+
+/// Transforms, filters and sinks by name
+#[derive(Default)]
+pub struct PluginRegistry {
+    transforms: HashMap<String, Arc<dyn TransformPlugin>>,
+    filters: HashMap<String, Arc<dyn FilterPlugin>>,
+    sinks: HashMap<String, Arc<dyn SinkPlugin>>,
+    plugins: Vec<PluginInfo>,
+    /// Names added since the current plugin started registering
+    added: PluginInfo,
+    /// Loaded libraries, kept open for as long as their plugins may run
+    #[cfg(feature = "plugins")]
+    libraries: Vec<libloading::Library>,
+}
+
+impl PluginRegistry {
+    pub fn register_transform(&mut self, name: &str, transform: impl TransformPlugin + 'static) {
+        if self.transforms.insert(name.to_string(), Arc::new(transform)).is_some() {
+            warn!("Transform plugin {} was registered twice; the last one is used", name);
+        }
+        self.added.transforms.push(name.to_string());
+    }
+
+    pub fn register_filter(&mut self, name: &str, filter: impl FilterPlugin + 'static) {
+        if self.filters.insert(name.to_string(), Arc::new(filter)).is_some() {
+            warn!("Filter plugin {} was registered twice; the last one is used", name);
+        }
+        self.added.filters.push(name.to_string());
+    }
+
+    pub fn register_sink(&mut self, name: &str, sink: impl SinkPlugin + 'static) {
+        if self.sinks.insert(name.to_string(), Arc::new(sink)).is_some() {
+            warn!("Sink plugin {} was registered twice; the last one is used", name);
+        }
+        self.added.sinks.push(name.to_string());
+    }
+
+    /// Let a plugin register its extensions, recording what it added
+    fn add(&mut self, plugin: &dyn Plugin, kind: PluginKind, path: Option<&Path>) -> PluginInfo {
+        self.added = PluginInfo {
+            name: plugin.name().to_string(),
+            kind,
+            path: path.map(Path::to_path_buf),
+            ..Default::default()
+        };
+        plugin.register(self);
+        let info = std::mem::take(&mut self.added);
+        self.plugins.push(info.clone());
+        info
+    }
+}
+
+fn registry() -> &'static RwLock<PluginRegistry> {
+    static REGISTRY: OnceLock<RwLock<PluginRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register a plugin compiled into the program
+pub fn register(plugin: &dyn Plugin) -> PluginInfo {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    let info = registry.add(plugin, PluginKind::Builtin, None);
+    info!("Registered plugin {}", info.name);
+    info
+}
+
+/// Load every plugin in a directory: dynamic libraries and `.wasm` modules
+///
+/// Files that fail to load are logged and skipped, files loaded before are
+/// left alone. Needs the `plugins` feature; without it a non-empty directory
+/// only produces a warning.
+pub fn load_directory(dir: &Path) -> Result<Vec<PluginInfo>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let known: Vec<PathBuf> = loaded().into_iter().filter_map(|plugin| plugin.path).collect();
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| plugin_kind(path).is_some() && !known.contains(path))
+        .collect();
+    paths.sort();
+
+    #[cfg(not(feature = "plugins"))]
+    if !paths.is_empty() {
+        warn!("Ignoring {} plugins in {}: built without the plugins feature", paths.len(), dir.display());
+        return Ok(Vec::new());
+    }
+
+    let mut loaded = Vec::new();
+    for path in paths {
+        match load_file(&path) {
+            Ok(info) => {
+                info!(
+                    "Loaded plugin {} from {} ({} transforms, {} filters, {} sinks)",
+                    info.name, path.display(), info.transforms.len(), info.filters.len(), info.sinks.len()
+                );
+                loaded.push(info);
+            }
+            Err(e) => warn!("Failed to load plugin {}: {}", path.display(), e),
+        }
+    }
+    Ok(loaded)
+}
+
+#[cfg(feature = "plugins")]
+fn load_file(path: &Path) -> Result<PluginInfo> {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    match plugin_kind(path) {
+        Some(PluginKind::Wasm) => {
+            let plugin = wasm::WasmPlugin::load(path)?;
+            Ok(registry.add(&plugin, PluginKind::Wasm, Some(path)))
+        }
+        _ => {
+            // Safety: loading a library runs its initialisers; the plugins
+            // directory is trusted like the program itself
+            let (library, plugin) = unsafe { native::load(path)? };
+            let info = registry.add(plugin.as_ref(), PluginKind::Native, Some(path));
+            drop(plugin);
+            registry.libraries.push(library);
+            Ok(info)
+        }
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+fn load_file(_path: &Path) -> Result<PluginInfo> {
+    unreachable!("plugin files are skipped without the plugins feature")
+}
+
+/// Kind of plugin a file holds, judged by its extension
+fn plugin_kind(path: &Path) -> Option<PluginKind> {
+    match path.extension()?.to_str()? {
+        "wasm" => Some(PluginKind::Wasm),
+        "dll" | "so" | "dylib" => Some(PluginKind::Native),
+        _ => None,
+    }
+}
+
+/// Registered plugins, in the order they were loaded
+pub fn loaded() -> Vec<PluginInfo> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).plugins.clone()
+}
+
+pub fn transform(name: &str) -> Option<Arc<dyn TransformPlugin>> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).transforms.get(name).cloned()
+}
+
+pub fn filter(name: &str) -> Option<Arc<dyn FilterPlugin>> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).filters.get(name).cloned()
+}
+
+pub fn sink(name: &str) -> Option<Arc<dyn SinkPlugin>> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).sinks.get(name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Suffix;
+
+    impl TransformPlugin for Suffix {
+        fn apply(&self, value: serde_json::Value, args: &serde_json::Value) -> Result<serde_json::Value> {
+            Ok(format!("{}{}", value.as_str().unwrap_or_default(), args.as_str().unwrap_or_default()).into())
+        }
+    }
+
+    struct TestPlugin;
+
+    impl Plugin for TestPlugin {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn register(&self, registry: &mut PluginRegistry) {
+            registry.register_transform("test_suffix", Suffix);
+        }
+    }
+
+    #[test]
+    fn test_registered_transform_is_found() {
+        let info = register(&TestPlugin);
+        assert_eq!(info.transforms, vec!["test_suffix"]);
+        assert_eq!(info.kind, PluginKind::Builtin);
+
+        let suffix = transform("test_suffix").unwrap();
+        assert_eq!(suffix.apply("a".into(), &"-b".into()).unwrap(), "a-b");
+        assert!(filter("test_suffix").is_none());
+        assert!(loaded().iter().any(|plugin| plugin.name == "test"));
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+use libloading::{Library, Symbol};
+use std::path::Path;
+
+use super::{Plugin, PLUGIN_API_VERSION};
+
+/// Function a plugin library exports to create its plugin, see [`crate::declare_plugin`]
+pub type CreatePlugin = fn() -> Box<dyn Plugin>;
+
+/// Open a plugin library and create its plugin
+///
+/// The plugin must be dropped before the library is closed.
+///
+/// # Safety
+///
+/// Loading runs the library's initialisers, and the plugin is used through
+/// Rust trait objects, so the library must come from [`crate::declare_plugin`]
+/// built with the same compiler as this program.
+pub unsafe fn load(path: &Path) -> Result<(Library, Box<dyn Plugin>)> {
+    let library = Library::new(path)?;
+    let plugin = {
+        let version: Symbol<*const u32> = library.get(b"wss_plugin_api_version\0")
+            .map_err(|_| anyhow::anyhow!("Not a WinScrape Studio plugin: wss_plugin_api_version is missing"))?;
+        if **version != PLUGIN_API_VERSION {
+            return Err(anyhow::anyhow!(
+                "Plugin was built for interface version {}, this program has version {}",
+                **version, PLUGIN_API_VERSION
+            ));
+        }
+        let create: Symbol<CreatePlugin> = library.get(b"wss_plugin_create\0")?;
+        create()
+    };
+    Ok((library, plugin))
+}
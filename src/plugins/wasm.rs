@@ -0,0 +1,158 @@
+//! WebAssembly plugins, run without access to the host
+//!
+//! A module exports its `memory`, an `alloc(len: i32) -> i32` function and
+//! any number of:
+//!
+//! - `transform_<name>(ptr: i32, len: i32) -> i64`
+//! - `filter_<name>(ptr: i32, len: i32) -> i64`
+//!
+//! Each receives `{"value": ..., "args": ...}` as UTF-8 JSON at `ptr` and
+//! returns the JSON result, the new value or a boolean, packed as
+//! `(ptr << 32) | len`. Modules may not import anything, and every call runs
+//! in a fresh instance with limited fuel and memory.
+
+use anyhow::Result;
+use std::path::Path;
+use std::sync::Arc;
+use wasmi::core::TrapCode;
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::{FilterPlugin, Plugin, PluginRegistry, TransformPlugin};
+
+/// How much a single call may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxLimits {
+    /// Fuel for one call; roughly one unit per instruction
+    pub fuel: u64,
+    /// Largest linear memory
+    pub memory_bytes: usize,
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            fuel: 50_000_000,
+            memory_bytes: 32 * 1024 * 1024,
+        }
+    }
+}
+
+/// A compiled module whose exports take and return JSON
+pub struct WasmModule {
+    engine: Engine,
+    module: Module,
+    limits: SandboxLimits,
+}
+
+impl WasmModule {
+    pub fn new(wasm: &[u8], limits: SandboxLimits) -> Result<Self> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm)?;
+        if let Some(import) = module.imports().next() {
+            return Err(anyhow::anyhow!(
+                "WebAssembly module imports {}::{}; modules may not import anything",
+                import.module(), import.name()
+            ));
+        }
+        Ok(Self { engine, module, limits })
+    }
+
+    pub fn has_function(&self, name: &str) -> bool {
+        self.module.get_export(name).is_some_and(|export| export.func().is_some())
+    }
+
+    /// Names after `prefix` of the functions the module exports
+    pub fn exports_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.module.exports()
+            .filter(|export| export.ty().func().is_some())
+            .filter_map(|export| export.name().strip_prefix(prefix).map(str::to_string))
+            .collect()
+    }
+
+    /// Call an export with `input` and parse what it returns
+    pub fn call_json(&self, export: &str, input: &serde_json::Value) -> Result<serde_json::Value> {
+        let mut store = Store::new(&self.engine, StoreLimitsBuilder::new().memory_size(self.limits.memory_bytes).build());
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(self.limits.fuel).map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        let trapped = |e: wasmi::Error| match e.as_trap_code() {
+            Some(TrapCode::OutOfFuel) => anyhow::anyhow!("{} ran out of fuel after {} units", export, self.limits.fuel),
+            _ => anyhow::anyhow!("{} failed: {}", export, e),
+        };
+        let instance = Linker::<StoreLimits>::new(&self.engine)
+            .instantiate(&mut store, &self.module)?
+            .start(&mut store)
+            .map_err(trapped)?;
+        let memory = instance.get_memory(&store, "memory")
+            .ok_or_else(|| anyhow::anyhow!("WebAssembly module does not export its memory"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc")?;
+        let function = instance.get_typed_func::<(i32, i32), i64>(&store, export)?;
+
+        let input = serde_json::to_vec(input)?;
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len).map_err(trapped)?;
+        memory.write(&mut store, ptr as u32 as usize, &input)
+            .map_err(|e| anyhow::anyhow!("{} could not take its input: {}", export, e))?;
+
+        let packed = function.call(&mut store, (ptr, len)).map_err(trapped)? as u64;
+        let (ptr, len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        let output = memory.data(&store).get(ptr..ptr + len)
+            .ok_or_else(|| anyhow::anyhow!("{} returned a result outside its memory", export))?;
+        Ok(serde_json::from_slice(output)?)
+    }
+}
+
+/// Plugin made of the transforms and filters a module exports
+pub struct WasmPlugin {
+    name: String,
+    module: Arc<WasmModule>,
+}
+
+impl WasmPlugin {
+    /// Load a module file; the plugin is named after the file
+    pub fn load(path: &Path) -> Result<Self> {
+        let wasm = std::fs::read(path)?;
+        Ok(Self {
+            name: path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+            module: Arc::new(WasmModule::new(&wasm, SandboxLimits::default())?),
+        })
+    }
+}
+
+impl Plugin for WasmPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn register(&self, registry: &mut PluginRegistry) {
+        for name in self.module.exports_with_prefix("transform_") {
+            let export = format!("transform_{}", name);
+            registry.register_transform(&name, WasmFunction { module: self.module.clone(), export });
+        }
+        for name in self.module.exports_with_prefix("filter_") {
+            let export = format!("filter_{}", name);
+            registry.register_filter(&name, WasmFunction { module: self.module.clone(), export });
+        }
+    }
+}
+
+/// One exported function of a module
+struct WasmFunction {
+    module: Arc<WasmModule>,
+    export: String,
+}
+
+impl TransformPlugin for WasmFunction {
+    fn apply(&self, value: serde_json::Value, args: &serde_json::Value) -> Result<serde_json::Value> {
+        self.module.call_json(&self.export, &serde_json::json!({ "value": value, "args": args }))
+    }
+}
+
+impl FilterPlugin for WasmFunction {
+    fn matches(&self, value: &serde_json::Value, args: &serde_json::Value) -> Result<bool> {
+        let result = self.module.call_json(&self.export, &serde_json::json!({ "value": value, "args": args }))?;
+        result.as_bool().ok_or_else(|| anyhow::anyhow!("{} returned {} instead of true or false", self.export, result))
+    }
+}
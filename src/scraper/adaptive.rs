@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tracing::info;
+
+use crate::config::AdaptiveConfig;
+
+/// Weight of the newest sample in a host's average response time
+const RESPONSE_TIME_WEIGHT: f64 = 0.3;
+
+/// Tunes concurrency and delays per host from how the host has been answering
+///
+/// Concurrency grows by one after every healthy sample and halves after a
+/// throttled one, while delays double when throttled and shrink back towards
+/// the plan's own delays when healthy. Jobs run on copies of the
+/// application's engine, so what one job learned about a host carries over
+/// to the next, tuned by the configured `scraping.adaptive` settings.
+#[derive(Default)]
+pub struct AdaptiveController {
+    hosts: Mutex<HashMap<String, HostTuning>>,
+    released: Notify,
+}
+
+/// Current tuning of one host
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostTuning {
+    /// Requests to the host allowed at once
+    pub concurrency: usize,
+    /// Multiplier on the plan's delays
+    pub delay_factor: f64,
+    /// Smoothed response time
+    pub avg_response_ms: Option<f64>,
+    /// Best average response time seen, taken as the healthy speed
+    pub best_response_ms: Option<f64>,
+    in_flight: usize,
+    sample_requests: usize,
+    sample_errors: usize,
+}
+
+impl Default for HostTuning {
+    fn default() -> Self {
+        Self {
+            concurrency: 1,
+            delay_factor: 1.0,
+            avg_response_ms: None,
+            best_response_ms: None,
+            in_flight: 0,
+            sample_requests: 0,
+            sample_errors: 0,
+        }
+    }
+}
+
+/// A request slot for a host, given back when dropped
+pub struct HostPermit {
+    controller: Arc<AdaptiveController>,
+    host: String,
+}
+
+impl Drop for HostPermit {
+    fn drop(&mut self) {
+        let mut hosts = self.controller.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(tuning) = hosts.get_mut(&self.host) {
+            tuning.in_flight = tuning.in_flight.saturating_sub(1);
+        }
+        drop(hosts);
+        self.controller.released.notify_waiters();
+    }
+}
+
+impl AdaptiveController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait until `host` may take another request, never allowing more than `max_concurrency`
+    pub async fn acquire(self: &Arc<Self>, host: &str, max_concurrency: usize, config: &AdaptiveConfig) -> HostPermit {
+        let max_concurrency = max_concurrency.max(1);
+        loop {
+            let released = self.released.notified();
+            {
+                let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+                let tuning = hosts.entry(host.to_string()).or_insert_with(|| HostTuning {
+                    concurrency: (max_concurrency / 2).max(1),
+                    ..Default::default()
+                });
+                let limit = if config.enabled { tuning.concurrency.min(max_concurrency) } else { max_concurrency };
+                if tuning.in_flight < limit {
+                    tuning.in_flight += 1;
+                    return HostPermit { controller: self.clone(), host: host.to_string() };
+                }
+            }
+            released.await;
+        }
+    }
+
+    /// Multiplier on the plan's delays for `host`
+    pub fn delay_factor(&self, host: &str) -> f64 {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts.get(host).map_or(1.0, |tuning| tuning.delay_factor)
+    }
+
+    /// Current tuning of `host`
+    pub fn tuning(&self, host: &str) -> Option<HostTuning> {
+        let hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts.get(host).cloned()
+    }
+
+    /// Take in how a request to `host` went, adjusting the host once a sample is complete
+    ///
+    /// `failed` covers blocked answers, server errors and requests that got no answer.
+    pub fn record(&self, host: &str, response_ms: Option<u64>, failed: bool, max_concurrency: usize, config: &AdaptiveConfig) {
+        if !config.enabled {
+            return;
+        }
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let tuning = hosts.entry(host.to_string()).or_default();
+
+        if let Some(ms) = response_ms.filter(|_| !failed) {
+            let avg = tuning.avg_response_ms.map_or(ms as f64, |avg| avg + RESPONSE_TIME_WEIGHT * (ms as f64 - avg));
+            tuning.avg_response_ms = Some(avg);
+        }
+        tuning.sample_requests += 1;
+        tuning.sample_errors += failed as usize;
+        if tuning.sample_requests < config.sample_size.max(1) {
+            return;
+        }
+
+        let error_rate = tuning.sample_errors as f64 / tuning.sample_requests as f64;
+        let slow = match (tuning.avg_response_ms, tuning.best_response_ms) {
+            (Some(avg), Some(best)) => avg > best * config.slowdown_factor,
+            _ => false,
+        };
+        let before = (tuning.concurrency, tuning.delay_factor);
+        if error_rate >= config.max_error_rate || slow {
+            tuning.concurrency = (tuning.concurrency / 2).max(1);
+            tuning.delay_factor = (tuning.delay_factor * 2.0).min(config.max_delay_factor.max(1.0));
+        } else {
+            tuning.concurrency = (tuning.concurrency + 1).min(max_concurrency.max(1));
+            tuning.delay_factor = (tuning.delay_factor * 0.75).max(1.0);
+            if let Some(avg) = tuning.avg_response_ms {
+                tuning.best_response_ms = Some(tuning.best_response_ms.map_or(avg, |best| best.min(avg)));
+            }
+        }
+        tuning.sample_requests = 0;
+        tuning.sample_errors = 0;
+
+        if before != (tuning.concurrency, tuning.delay_factor) {
+            info!(
+                "Tuned {}: {} concurrent requests, delays x{:.2} ({:.0}% errors, avg {:.0}ms)",
+                host, tuning.concurrency, tuning.delay_factor, error_rate * 100.0, tuning.avg_response_ms.unwrap_or_default()
+            );
+            drop(hosts);
+            self.released.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_backs_off_when_throttled_and_speeds_up_when_healthy() {
+        let controller = Arc::new(AdaptiveController::new());
+        let config = AdaptiveConfig { sample_size: 4, ..Default::default() };
+
+        let permit = controller.acquire("example.com", 8, &config).await;
+        drop(permit);
+        assert_eq!(controller.tuning("example.com").unwrap().concurrency, 4);
+
+        for _ in 0..8 {
+            controller.record("example.com", Some(100), false, 8, &config);
+        }
+        assert_eq!(controller.tuning("example.com").unwrap().concurrency, 6);
+
+        // Slow answers count as throttling even without errors
+        for _ in 0..4 {
+            controller.record("example.com", Some(1000), false, 8, &config);
+        }
+        let tuning = controller.tuning("example.com").unwrap();
+        assert_eq!(tuning.concurrency, 3);
+        assert_eq!(tuning.delay_factor, 2.0);
+
+        for _ in 0..4 {
+            controller.record("example.com", None, true, 8, &config);
+        }
+        let tuning = controller.tuning("example.com").unwrap();
+        assert_eq!(tuning.concurrency, 1);
+        assert_eq!(controller.delay_factor("example.com"), 4.0);
+        assert_eq!(tuning.in_flight, 0);
+    }
+}
@@ -294,6 +294,9 @@ impl BrowserClient {
             Transform::ExtractDomain => {
                 "(() => { try { return new URL(value).hostname; } catch { return value; } })()".to_string()
             }
+            Transform::Plugin { name, .. } => {
+                return Err(anyhow::anyhow!("Transform plugin {} cannot run in the browser", name));
+            }
         };
         
         Ok(script)
@@ -0,0 +1,86 @@
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+
+/// Bytes of a page searched for a `<meta>` charset, as browsers do
+const META_PRESCAN_BYTES: usize = 4096;
+
+/// Decode a page body into text using the charset it declares
+///
+/// A byte order mark wins, then the `Content-Type` header, then a `<meta>`
+/// tag or XML declaration near the top of the page. Pages that declare
+/// nothing are read as UTF-8 when they are valid UTF-8 and as Windows-1252
+/// otherwise. Returns the text and the encoding used.
+pub fn decode(body: &[u8], content_type: Option<&str>) -> (String, &'static Encoding) {
+    let encoding = Encoding::for_bom(body).map(|(encoding, _)| encoding)
+        .or_else(|| content_type.and_then(header_charset))
+        .or_else(|| declared_charset(&body[..body.len().min(META_PRESCAN_BYTES)]))
+        .unwrap_or_else(|| if std::str::from_utf8(body).is_ok() { UTF_8 } else { WINDOWS_1252 });
+
+    // Strips the BOM if there is one
+    let (text, encoding, _) = encoding.decode(body);
+    (text.into_owned(), encoding)
+}
+
+/// Encoding named by the `charset` parameter of a `Content-Type` header
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';')
+        .skip(1)
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+        .and_then(|(_, value)| Encoding::for_label(value.trim().trim_matches(|c| c == '"' || c == '\'').as_bytes()))
+}
+
+/// Encoding declared by a `<meta charset>`, `<meta http-equiv>` or `<?xml encoding?>` in the page
+fn declared_charset(head: &[u8]) -> Option<&'static Encoding> {
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+
+    let mut rest = head.as_str();
+    while let Some(start) = rest.find("<meta") {
+        let tag = &rest[start..];
+        let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+        if let Some(encoding) = attribute_charset(tag, "charset=") {
+            // Pages can't really be UTF-16 when this tag could be read as ASCII
+            return Some(if encoding.output_encoding() == UTF_8 { UTF_8 } else { encoding });
+        }
+        rest = &rest[start + 5..];
+    }
+
+    head.strip_prefix("<?xml")
+        .and_then(|declaration| attribute_charset(&declaration[..declaration.find("?>")?], "encoding="))
+}
+
+/// Encoding label following `key` in a tag, with or without quotes
+fn attribute_charset(tag: &str, key: &str) -> Option<&'static Encoding> {
+    let value = &tag[tag.find(key)? + key.len()..];
+    let value = value.trim_start_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
+    let end = value.find(|c: char| c == '"' || c == '\'' || c == ';' || c == '/' || c.is_whitespace()).unwrap_or(value.len());
+    Encoding::for_label(value[..end].as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_declared_cjk_charsets() {
+        let (korean, _, _) = encoding_rs::EUC_KR.encode("<p>가격: 12,000원</p>");
+        let (text, encoding) = decode(&korean, Some("text/html; charset=EUC-KR"));
+        assert_eq!(encoding, encoding_rs::EUC_KR);
+        assert!(text.contains("가격: 12,000원"));
+
+        let page = "<html><head><meta http-equiv=\"Content-Type\" content=\"text/html; charset=Shift_JIS\"></head><body>価格</body></html>";
+        let (japanese, _, _) = encoding_rs::SHIFT_JIS.encode(page);
+        let (text, encoding) = decode(&japanese, Some("text/html"));
+        assert_eq!(encoding, encoding_rs::SHIFT_JIS);
+        assert!(text.contains("価格"));
+
+        let (chinese, _, _) = encoding_rs::GBK.encode("<meta charset='gbk'><p>价格</p>");
+        assert_eq!(decode(&chinese, None).0, "<meta charset='gbk'><p>价格</p>");
+    }
+
+    #[test]
+    fn test_undeclared_pages_fall_back_by_content() {
+        assert_eq!(decode("<p>café</p>".as_bytes(), None).1, UTF_8);
+        assert_eq!(decode(b"<p>caf\xe9</p>", None), ("<p>café</p>".to_string(), WINDOWS_1252));
+        assert_eq!(decode(b"\xef\xbb\xbf<p>ok</p>", Some("text/html; charset=euc-kr")).0, "<p>ok</p>");
+    }
+}
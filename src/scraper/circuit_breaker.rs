@@ -0,0 +1,176 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::config::CircuitBreakerConfig;
+
+/// How a request to a host went, as far as the host's health goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostOutcome {
+    Success,
+    /// The host answered 403 or 429
+    Blocked,
+    /// No answer, or a server error
+    Failure,
+}
+
+/// How often requests waiting on a host's trial request check whether it finished
+const TRIAL_POLL: Duration = Duration::from_millis(250);
+
+/// Trial requests taking longer than this no longer hold back the others, e.g. when their job was cancelled
+const TRIAL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Stops requests to hosts that keep failing
+///
+/// Jobs run on copies of the application's engine, which share one breaker,
+/// so a host one job tripped is left alone by the others too.
+#[derive(Default)]
+pub struct CircuitBreaker {
+    hosts: Mutex<HashMap<String, HostCircuit>>,
+}
+
+#[derive(Default)]
+struct HostCircuit {
+    consecutive_failures: u32,
+    /// When recent 403 and 429 answers came in
+    blocked: VecDeque<Instant>,
+    open_until: Option<Instant>,
+    /// The cooldown passed and the next request decides whether the circuit closes
+    half_open: bool,
+    /// When the trial request of a half-open circuit went out, while it is under way
+    trial_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time to wait before asking again whether a request to `host` may go out; `None` when it may now
+    ///
+    /// Once the cooldown is over exactly one caller gets through as the trial
+    /// request; the others wait until it is recorded.
+    pub fn open_for(&self, host: &str) -> Option<Duration> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        let circuit = hosts.get_mut(host)?;
+        let now = Instant::now();
+        if let Some(started) = circuit.trial_started {
+            if now.duration_since(started) < TRIAL_TIMEOUT {
+                return Some(TRIAL_POLL);
+            }
+            warn!("Trial request to {} did not finish; trying another", host);
+        } else {
+            let open_until = circuit.open_until?;
+            if now < open_until {
+                return Some(open_until - now);
+            }
+            info!("Circuit for {} is half-open; trying one request", host);
+        }
+        circuit.open_until = None;
+        circuit.half_open = true;
+        circuit.trial_started = Some(now);
+        None
+    }
+
+    /// Record how a request went; returns why the circuit opened when this request opened it
+    pub fn record(&self, host: &str, outcome: HostOutcome, config: &CircuitBreakerConfig) -> Option<String> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(circuit) = hosts.get_mut(host) {
+            // Whatever it was, the request is over, so a trial no longer holds back the others
+            circuit.trial_started = None;
+        }
+        if !config.enabled {
+            return None;
+        }
+        let circuit = hosts.entry(host.to_string()).or_default();
+        let now = Instant::now();
+
+        let reason = match outcome {
+            HostOutcome::Success => {
+                circuit.consecutive_failures = 0;
+                circuit.half_open = false;
+                return None;
+            }
+            _ if circuit.open_until.is_some() => return None,
+            _ if circuit.half_open => "the first request after the cooldown failed too".to_string(),
+            HostOutcome::Blocked => {
+                circuit.consecutive_failures += 1;
+                circuit.blocked.push_back(now);
+                let window = Duration::from_secs(config.blocked_window_seconds);
+                while circuit.blocked.front().is_some_and(|at| now.duration_since(*at) > window) {
+                    circuit.blocked.pop_front();
+                }
+                if circuit.blocked.len() >= config.blocked_threshold.max(1) {
+                    format!("{} blocked answers (403/429) within {}s", circuit.blocked.len(), config.blocked_window_seconds)
+                } else if circuit.consecutive_failures >= config.failure_threshold.max(1) {
+                    format!("{} failed requests in a row", circuit.consecutive_failures)
+                } else {
+                    return None;
+                }
+            }
+            HostOutcome::Failure => {
+                circuit.consecutive_failures += 1;
+                if circuit.consecutive_failures < config.failure_threshold.max(1) {
+                    return None;
+                }
+                format!("{} failed requests in a row", circuit.consecutive_failures)
+            }
+        };
+
+        circuit.open_until = Some(now + Duration::from_secs(config.cooldown_seconds));
+        circuit.consecutive_failures = 0;
+        circuit.blocked.clear();
+        circuit.half_open = false;
+        let reason = format!(
+            "Stopped requests to {} for {}s after {}",
+            host, config.cooldown_seconds, reason
+        );
+        warn!("{}", reason);
+        Some(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_opens_and_half_opens() {
+        let breaker = CircuitBreaker::new();
+        let config = CircuitBreakerConfig { failure_threshold: 3, blocked_threshold: 2, ..Default::default() };
+
+        assert!(breaker.record("a.example", HostOutcome::Failure, &config).is_none());
+        assert!(breaker.record("a.example", HostOutcome::Success, &config).is_none());
+        assert!(breaker.record("a.example", HostOutcome::Failure, &config).is_none());
+        assert!(breaker.record("a.example", HostOutcome::Failure, &config).is_none());
+        assert!(breaker.record("a.example", HostOutcome::Failure, &config).is_some());
+        assert!(breaker.open_for("a.example").is_some());
+        assert!(breaker.open_for("b.example").is_none());
+
+        assert!(breaker.record("b.example", HostOutcome::Blocked, &config).is_none());
+        let reason = breaker.record("b.example", HostOutcome::Blocked, &config).unwrap();
+        assert!(reason.contains("403/429"));
+
+        // Without a cooldown the next request is the trial, and its failure opens the circuit again
+        let config = CircuitBreakerConfig { failure_threshold: 1, cooldown_seconds: 0, ..Default::default() };
+        assert!(breaker.record("c.example", HostOutcome::Failure, &config).is_some());
+        assert!(breaker.open_for("c.example").is_none());
+        assert!(breaker.record("c.example", HostOutcome::Failure, &config).unwrap().contains("after the cooldown"));
+    }
+
+    #[test]
+    fn test_half_open_lets_one_trial_through() {
+        let breaker = CircuitBreaker::new();
+        let config = CircuitBreakerConfig { failure_threshold: 1, cooldown_seconds: 0, ..Default::default() };
+        assert!(breaker.record("a.example", HostOutcome::Failure, &config).is_some());
+
+        assert!(breaker.open_for("a.example").is_none());
+        assert_eq!(breaker.open_for("a.example"), Some(TRIAL_POLL));
+        assert_eq!(breaker.open_for("a.example"), Some(TRIAL_POLL));
+
+        assert!(breaker.record("a.example", HostOutcome::Success, &config).is_none());
+        assert!(breaker.open_for("a.example").is_none());
+        assert!(breaker.open_for("a.example").is_none());
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::Result;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{info, warn};
+use url::Url;
+
+use super::{http_client, url_rng, new_random_seed, ScrapingEngine};
+use crate::dsl::{ScrapePlan, SelectorType};
+use crate::error::WinScrapeError;
+
+/// Sample values kept per field in a dry run
+const DRY_RUN_SAMPLES: usize = 3;
+
+/// Selector hit statistics for a plan, gathered without storing anything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunReport {
+    pub pages: Vec<DryRunPage>,
+}
+
+/// Results of checking a plan against one page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunPage {
+    pub url: String,
+    /// Start URL shape or URL pattern this page stands for
+    pub pattern: String,
+    pub status_code: Option<u16>,
+    /// Why the page couldn't be checked
+    pub error: Option<String>,
+    /// Elements matched by the item selector
+    pub items_matched: usize,
+    pub fields: Vec<FieldHits>,
+    /// Required fields missing from at least one item
+    pub empty_required: Vec<String>,
+}
+
+/// How a field selector fared on a page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldHits {
+    pub name: String,
+    pub selector: String,
+    pub required: bool,
+    /// Items in which the selector matched at least one element
+    pub items_matched: usize,
+    /// Items in which it matched nothing
+    pub items_missing: usize,
+    /// Elements matched across all items
+    pub elements_matched: usize,
+    /// Extracted values after transforms
+    pub samples: Vec<serde_json::Value>,
+    pub error: Option<String>,
+}
+
+impl DryRunReport {
+    /// Required fields that were empty on any checked page
+    pub fn empty_required(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.pages.iter()
+            .flat_map(|page| page.empty_required.iter().map(String::as_str))
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+
+    /// Whether any page failed to load
+    pub fn has_errors(&self) -> bool {
+        self.pages.iter().any(|page| page.error.is_some())
+    }
+}
+
+impl ScrapingEngine {
+    /// Fetch one page per unique URL pattern and report what each selector matches
+    pub async fn dry_run(&self, plan: &ScrapePlan) -> Result<DryRunReport> {
+        let pages = sample_urls(plan)?;
+        info!("Dry run of {} over {} sample pages", plan.target.domain, pages.len());
+
+        let seed = plan.anti_blocking.random_seed.unwrap_or_else(new_random_seed);
+        let mut report = DryRunReport::default();
+
+        for (pattern, url) in pages {
+            let page = match self.dry_run_page(&url, plan, seed).await {
+                Ok((status_code, html)) => {
+                    let mut page = self.inspect_page(&html, plan, &url)?;
+                    page.status_code = Some(status_code);
+                    page
+                }
+                Err(e) => {
+                    warn!("Dry run could not fetch {}: {}", url, e);
+                    DryRunPage {
+                        url: url.to_string(),
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    }
+                }
+            };
+            report.pages.push(DryRunPage { pattern, ..page });
+        }
+
+        Ok(report)
+    }
+
+    /// Fetch a page the way a run would, without delays between pages
+    async fn dry_run_page(&self, url: &Url, plan: &ScrapePlan, seed: u64) -> Result<(u16, String)> {
+        let mut rng = url_rng(seed, url);
+        let user_agent = self.plan_user_agent(plan, &mut rng);
+
+        if let Some(recording) = &self.replay {
+            let exchange = recording.replay(url)?;
+            return Ok((exchange.status, exchange.body));
+        }
+
+        if plan.anti_blocking.respect_robots_txt && !self.robots_checker.is_allowed(url, &user_agent).await? {
+            return Err(WinScrapeError::RobotsViolation { url: url.to_string() }.into());
+        }
+
+        self.rate_limiter.wait_for_domain(url.host_str().unwrap_or("")).await;
+        let response = self.http_client
+            .get_with_redirects(url, &user_agent, &plan.anti_blocking.request_headers(), 1, self.redirect_rules(plan))
+            .await?
+            .response;
+        let status_code = response.status().as_u16();
+        let limits = self.config().response_limits.clone();
+
+        Ok((status_code, http_client::read_text(response, &limits).await?))
+    }
+
+    /// Count item and field selector matches in a page
+    fn inspect_page(&self, html: &str, plan: &ScrapePlan, url: &Url) -> Result<DryRunPage> {
+        let document = Html::parse_document(html);
+        let item_selector = Selector::parse(&plan.rules.item_selector)
+            .map_err(|e| anyhow::anyhow!("Invalid item selector: {}", e))?;
+        let items: Vec<_> = document.select(&item_selector).collect();
+
+        let mut page = DryRunPage {
+            url: url.to_string(),
+            items_matched: items.len(),
+            ..Default::default()
+        };
+
+        for field in &plan.rules.fields {
+            let mut hits = FieldHits {
+                name: field.name.clone(),
+                selector: field.selector.clone(),
+                required: field.required,
+                ..Default::default()
+            };
+
+            let selector = match field.selector_type {
+                SelectorType::CSS => Selector::parse(&field.selector)
+                    .map_err(|e| format!("Invalid CSS selector '{}': {}", field.selector, e)),
+                SelectorType::XPath => Err("XPath selectors not yet implemented".to_string()),
+            };
+
+            match selector {
+                Ok(selector) => {
+                    for item in &items {
+                        let mut matches = item.select(&selector);
+                        let Some(first) = matches.next() else {
+                            hits.items_missing += 1;
+                            continue;
+                        };
+                        hits.items_matched += 1;
+                        hits.elements_matched += 1 + matches.count();
+
+                        if hits.samples.len() < DRY_RUN_SAMPLES {
+                            let value = self.extract_value_by_method(&first, &field.extraction, url)
+                                .and_then(|value| match &field.transform {
+                                    Some(transforms) => self.apply_transforms(value, transforms),
+                                    None => Ok(value),
+                                });
+                            match value {
+                                Ok(value) => hits.samples.push(value),
+                                Err(e) => hits.error = Some(e.to_string()),
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    hits.items_missing = items.len();
+                    hits.error = Some(e);
+                }
+            }
+
+            if field.required && (hits.items_missing > 0 || items.is_empty()) {
+                page.empty_required.push(field.name.clone());
+            }
+            page.fields.push(hits);
+        }
+
+        Ok(page)
+    }
+}
+
+/// One URL per start URL shape and per URL pattern, labelled with what it stands for
+fn sample_urls(plan: &ScrapePlan) -> Result<Vec<(String, Url)>> {
+    let mut seen = HashSet::new();
+    let mut samples = Vec::new();
+
+    for url_str in &plan.target.start_urls {
+        let url = Url::parse(url_str)?;
+        let shape = url_shape(&url);
+        if seen.insert(shape.clone()) {
+            samples.push((shape, url));
+        }
+    }
+
+    for pattern in plan.target.url_patterns.iter().flatten() {
+        let first = pattern.replace("{page}", "1");
+        match Url::parse(&first) {
+            Ok(url) if seen.insert(pattern.clone()) => samples.push((pattern.clone(), url)),
+            Ok(_) => {}
+            Err(e) => warn!("Skipping URL pattern {} in dry run: {}", pattern, e),
+        }
+    }
+
+    Ok(samples)
+}
+
+/// Host and path with numeric segments generalized, e.g. `example.com/items/{n}`
+fn url_shape(url: &Url) -> String {
+    let path: Vec<&str> = url.path_segments()
+        .map(|segments| {
+            segments
+                .map(|s| if !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) { "{n}" } else { s })
+                .collect()
+        })
+        .unwrap_or_default();
+    format!("{}/{}", url.host_str().unwrap_or_default(), path.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_urls_one_per_pattern() {
+        let mut plan = ScrapePlan::default();
+        plan.target.start_urls = vec![
+            "https://example.com/items/1".to_string(),
+            "https://example.com/items/2".to_string(),
+            "https://example.com/about".to_string(),
+        ];
+        plan.target.url_patterns = Some(vec!["https://example.com/list?page={page}".to_string()]);
+
+        let samples = sample_urls(&plan).unwrap();
+        let urls: Vec<&str> = samples.iter().map(|(_, url)| url.as_str()).collect();
+        assert_eq!(urls, vec![
+            "https://example.com/items/1",
+            "https://example.com/about",
+            "https://example.com/list?page=1",
+        ]);
+        assert_eq!(samples[0].0, "example.com/items/{n}");
+    }
+}
@@ -1,20 +1,143 @@
 use anyhow::Result;
-use reqwest::{Client, Response, header::{HeaderMap, HeaderName, HeaderValue}};
+use reqwest::{Client, Method, Response, header::{HeaderMap, HeaderName, HeaderValue}};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use tracing::{debug, warn, info};
 use url::Url;
 use tokio::sync::RwLock;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use crate::config::ScrapingConfig;
+use crate::config::{ResponseLimitsConfig, ScrapingConfig};
+
+/// Why a response body was left unread
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ResponseRejected {
+    #[error("response is larger than the {limit} byte limit")]
+    TooLarge { limit: u64 },
+    #[error("content type '{0}' is not a page that can be scraped")]
+    ContentType(String),
+}
+
+/// Why a redirect was not followed
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RedirectRejected {
+    #[error("more than {0} redirects")]
+    TooMany(usize),
+    #[error("redirect from {from} to another site ({to}) is not allowed by the plan")]
+    CrossDomain { from: String, to: String },
+    #[error("redirect to {to} refused: {reason}")]
+    Refused { to: String, reason: String },
+}
+
+/// Check run on every redirect target before it is followed
+pub type RedirectCheck = dyn Fn(&Url) -> Result<()> + Send + Sync;
+
+/// How redirects are followed for one request
+#[derive(Clone, Copy)]
+pub struct RedirectRules<'a> {
+    pub max_redirects: usize,
+    pub allow_cross_domain: bool,
+    pub check: Option<&'a RedirectCheck>,
+}
+
+impl Default for RedirectRules<'_> {
+    fn default() -> Self {
+        Self {
+            max_redirects: 10,
+            allow_cross_domain: true,
+            check: None,
+        }
+    }
+}
+
+/// A response together with the redirects that led to it
+pub struct Fetched {
+    pub response: Response,
+    /// URLs redirected from, in order, starting with the one requested
+    pub redirects: Vec<String>,
+}
+
+/// Request headers carrying credentials, which only go to the host they were given for
+const CREDENTIAL_HEADERS: &[&str] = &["authorization", "cookie", "proxy-authorization"];
+
+/// Whether a redirect leads to another host or port, so credentials must not follow it
+fn leaves_origin(from: &Url, to: &Url) -> bool {
+    from.host_str() != to.host_str() || from.port_or_known_default() != to.port_or_known_default()
+}
+
+/// Leave out the headers that carry credentials
+fn drop_credentials(headers: &mut Option<HashMap<String, String>>) {
+    if let Some(headers) = headers {
+        headers.retain(|name, _| !CREDENTIAL_HEADERS.iter().any(|credential| name.eq_ignore_ascii_case(credential)));
+    }
+}
+
+/// Whether two URLs are on the same site, ignoring a leading `www.`
+fn same_site(a: &Url, b: &Url) -> bool {
+    let host = |url: &Url| url.host_str().map(|host| host.trim_start_matches("www.").to_ascii_lowercase());
+    host(a) == host(b)
+}
+
+/// Read a response body within `limits`, stopping the download once it grows past the size limit
+///
+/// Responses without a content type are read; an oversized body is reported
+/// before anything is downloaded when the server announces its length.
+pub async fn read_body(response: Response, limits: &ResponseLimitsConfig) -> Result<Vec<u8>> {
+    use futures::StreamExt;
+    
+    if let Some(content_type) = response.headers().get(reqwest::header::CONTENT_TYPE) {
+        let content_type = content_type.to_str().unwrap_or_default();
+        if !content_type_allowed(content_type, &limits.allowed_content_types) {
+            return Err(ResponseRejected::ContentType(content_type.to_string()).into());
+        }
+    }
+    if response.content_length().is_some_and(|length| length > limits.max_body_bytes) {
+        return Err(ResponseRejected::TooLarge { limit: limits.max_body_bytes }.into());
+    }
+    
+    let mut body = Vec::new();
+    let mut chunks = response.bytes_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        if body.len() as u64 + chunk.len() as u64 > limits.max_body_bytes {
+            return Err(ResponseRejected::TooLarge { limit: limits.max_body_bytes }.into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Read a response body within `limits` as text, decoded with the charset the page declares
+pub async fn read_text(response: Response, limits: &ResponseLimitsConfig) -> Result<String> {
+    let content_type = response.headers().get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let url = response.url().clone();
+    let body = read_body(response, limits).await?;
+    let (text, encoding) = super::charset::decode(&body, content_type.as_deref());
+    if encoding != encoding_rs::UTF_8 {
+        debug!("Decoded {} as {}", url, encoding.name());
+    }
+    Ok(text)
+}
+
+/// Whether the media type of a `Content-Type` header starts with one of `allowed`
+fn content_type_allowed(content_type: &str, allowed: &[String]) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    allowed.is_empty() || allowed.iter().any(|prefix| media_type.starts_with(&prefix.to_ascii_lowercase()))
+}
 
 /// HTTP client wrapper with retry logic, connection pooling, and performance monitoring
 pub struct HttpClient {
     client: Client,
     config: ScrapingConfig,
+    /// Per-request timeout in seconds, changeable while the client is in use
+    request_timeout_seconds: AtomicU64,
     performance_metrics: Arc<RwLock<HttpPerformanceMetrics>>,
     domain_limits: Arc<RwLock<HashMap<String, DomainLimits>>>,
+    /// Run on every redirect target, whichever method made the request
+    redirect_check: std::sync::OnceLock<Arc<RedirectCheck>>,
 }
 
 /// Performance metrics for HTTP operations
@@ -64,24 +187,44 @@ impl HttpClient {
             .connect_timeout(Duration::from_secs(10))
             .default_headers(headers)
             .cookie_store(true)
-            .redirect(reqwest::redirect::Policy::limited(10))
-            .http2_prior_knowledge() // Enable HTTP/2 for better performance
+            .redirect(reqwest::redirect::Policy::none()) // Followed by hand so each hop can be checked
             .pool_max_idle_per_host(config.max_concurrent_requests as usize)
             .pool_idle_timeout(Duration::from_secs(90))
             .tcp_keepalive(Duration::from_secs(60))
             .tcp_nodelay(true)
             .build()?;
         
-        info!("HTTP client initialized with connection pooling and HTTP/2 support");
+        info!("HTTP client initialized with connection pooling (HTTP/2 negotiated where offered)");
         
         Ok(Self {
             client,
             config: config.clone(),
+            request_timeout_seconds: AtomicU64::new(config.request_timeout_seconds),
             performance_metrics: Arc::new(RwLock::new(HttpPerformanceMetrics::default())),
             domain_limits: Arc::new(RwLock::new(HashMap::new())),
+            redirect_check: std::sync::OnceLock::new(),
         })
     }
     
+    /// Check every redirect target with `check` before following it
+    ///
+    /// Set once, before the client is shared; later calls are ignored.
+    pub fn set_redirect_check(&self, check: Arc<RedirectCheck>) {
+        if self.redirect_check.set(check).is_err() {
+            warn!("Redirect check already set; keeping the first one");
+        }
+    }
+    
+    /// The check run on every redirect target, if one is set
+    pub fn redirect_check(&self) -> Option<&RedirectCheck> {
+        self.redirect_check.get().map(|check| check.as_ref())
+    }
+    
+    /// Default redirect rules, with the client's redirect check
+    fn redirect_rules(&self) -> RedirectRules<'_> {
+        RedirectRules { check: self.redirect_check(), ..Default::default() }
+    }
+    
     /// Check and enforce rate limiting for a domain
     async fn check_rate_limit(&self, domain: &str) -> Result<()> {
         let mut limits = self.domain_limits.write().await;
@@ -170,6 +313,33 @@ impl HttpClient {
         user_agent: &str,
         custom_headers: &Option<HashMap<String, String>>,
     ) -> Result<Response> {
+        self.get_with_retries(url, user_agent, custom_headers, self.config.max_retries).await
+    }
+    
+    /// Make GET request with an explicit retry budget
+    pub async fn get_with_retries(
+        &self,
+        url: &Url,
+        user_agent: &str,
+        custom_headers: &Option<HashMap<String, String>>,
+        max_retries: usize,
+    ) -> Result<Response> {
+        let fetched = self.get_with_redirects(url, user_agent, custom_headers, max_retries, self.redirect_rules()).await?;
+        Ok(fetched.response)
+    }
+    
+    /// Make GET request with an explicit retry budget, following redirects within `rules`
+    ///
+    /// A refused redirect fails the request right away without retrying.
+    pub async fn get_with_redirects(
+        &self,
+        url: &Url,
+        user_agent: &str,
+        custom_headers: &Option<HashMap<String, String>>,
+        max_retries: usize,
+        rules: RedirectRules<'_>,
+    ) -> Result<Fetched> {
+        let max_retries = max_retries.max(1);
         let start_time = Instant::now();
         let domain = url.host_str().unwrap_or("unknown");
         
@@ -179,11 +349,11 @@ impl HttpClient {
         let mut last_error = None;
         let mut total_bytes = 0u64;
         
-        for attempt in 1..=self.config.max_retries {
+        for attempt in 1..=max_retries {
             debug!("HTTP GET attempt {} for: {}", attempt, url);
             
-            match self.make_request(url, user_agent, custom_headers).await {
-                Ok(response) => {
+            match self.follow_redirects(Method::GET, url, user_agent, custom_headers, rules).await {
+                Ok(Fetched { response, redirects }) => {
                     // Get content length for metrics
                     if let Some(content_length) = response.headers().get("content-length") {
                         if let Ok(length_str) = content_length.to_str() {
@@ -195,21 +365,26 @@ impl HttpClient {
                         debug!("HTTP GET successful for: {}", url);
                         let duration = start_time.elapsed();
                         self.record_request_metrics(duration, true, total_bytes).await;
-                        return Ok(response);
-                    } else if response.status().is_server_error() && attempt < self.config.max_retries {
+                        return Ok(Fetched { response, redirects });
+                    } else if response.status().is_server_error() && attempt < max_retries {
                         warn!("Server error {} for {}, retrying...", response.status(), url);
                         last_error = Some(anyhow::anyhow!("Server error: {}", response.status()));
                     } else {
                         let duration = start_time.elapsed();
                         self.record_request_metrics(duration, false, total_bytes).await;
-                        return Ok(response); // Return non-success responses for handling upstream
+                        return Ok(Fetched { response, redirects }); // Return non-success responses for handling upstream
                     }
                 }
+                Err(e) if e.is::<RedirectRejected>() => {
+                    warn!("Not following a redirect for {}: {}", url, e);
+                    self.record_request_metrics(start_time.elapsed(), false, total_bytes).await;
+                    return Err(e);
+                }
                 Err(e) => {
                     warn!("HTTP request failed for {} (attempt {}): {}", url, attempt, e);
                     last_error = Some(e);
                     
-                    if attempt < self.config.max_retries {
+                    if attempt < max_retries {
                         let delay = Duration::from_secs(self.config.retry_delay_seconds * attempt as u64);
                         tokio::time::sleep(delay).await;
                     }
@@ -222,14 +397,64 @@ impl HttpClient {
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retry attempts failed")))
     }
     
+    /// Use a different timeout for requests made from now on
+    pub fn set_request_timeout(&self, timeout: Duration) {
+        self.request_timeout_seconds.store(timeout.as_secs(), Ordering::Relaxed);
+    }
+    
+    fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_seconds.load(Ordering::Relaxed))
+    }
+    
+    /// Make a request and follow its redirects, checking every target before it is requested
+    ///
+    /// Credential headers of the plan are dropped once a redirect leaves the original host.
+    async fn follow_redirects(
+        &self,
+        method: Method,
+        url: &Url,
+        user_agent: &str,
+        custom_headers: &Option<HashMap<String, String>>,
+        rules: RedirectRules<'_>,
+    ) -> Result<Fetched> {
+        let mut current = url.clone();
+        let mut headers = custom_headers.clone();
+        let mut redirects = Vec::new();
+        loop {
+            let response = self.make_request(method.clone(), &current, user_agent, &headers).await?;
+            let location = response.headers().get(reqwest::header::LOCATION).and_then(|value| value.to_str().ok());
+            let next = match location {
+                Some(location) if response.status().is_redirection() => current.join(location)?,
+                _ => return Ok(Fetched { response, redirects }),
+            };
+            
+            if redirects.len() >= rules.max_redirects {
+                return Err(RedirectRejected::TooMany(rules.max_redirects).into());
+            }
+            if !rules.allow_cross_domain && !same_site(url, &next) {
+                return Err(RedirectRejected::CrossDomain { from: url.to_string(), to: next.to_string() }.into());
+            }
+            if let Some(check) = rules.check {
+                check(&next).map_err(|e| RedirectRejected::Refused { to: next.to_string(), reason: e.to_string() })?;
+            }
+            if leaves_origin(&current, &next) {
+                drop_credentials(&mut headers);
+            }
+            debug!("Following {} redirect from {} to {}", response.status(), current, next);
+            redirects.push(std::mem::replace(&mut current, next).to_string());
+        }
+    }
+    
     /// Make single HTTP request
     async fn make_request(
         &self,
+        method: Method,
         url: &Url,
         user_agent: &str,
         custom_headers: &Option<HashMap<String, String>>,
     ) -> Result<Response> {
-        let mut request = self.client.get(url.as_str())
+        let mut request = self.client.request(method, url.as_str())
+            .timeout(self.request_timeout())
             .header("User-Agent", user_agent);
         
         // Add custom headers if provided
@@ -249,45 +474,34 @@ impl HttpClient {
     }
     
     /// Get robots.txt for a domain
+    ///
+    /// A missing robots.txt reads as empty; server and network errors fail.
     pub async fn get_robots_txt(&self, domain: &str, user_agent: &str) -> Result<String> {
         let robots_url = format!("https://{}/robots.txt", domain);
         let url = Url::parse(&robots_url)?;
         
-        match self.get(&url, user_agent, &None).await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    Ok(response.text().await?)
-                } else {
-                    // If robots.txt doesn't exist, assume everything is allowed
-                    Ok(String::new())
-                }
-            }
-            Err(_) => {
-                // If we can't fetch robots.txt, assume everything is allowed
-                Ok(String::new())
-            }
+        let response = self.get(&url, user_agent, &None).await?;
+        if response.status().is_success() {
+            read_text(response, &self.config.response_limits).await
+        } else if response.status().is_server_error() {
+            Err(anyhow::anyhow!("Server error {} fetching robots.txt", response.status()))
+        } else {
+            // If robots.txt doesn't exist, assume everything is allowed
+            Ok(String::new())
         }
     }
     
     /// Check if URL is reachable
     pub async fn check_url(&self, url: &Url, user_agent: &str) -> Result<bool> {
-        match self.client.head(url.as_str())
-            .header("User-Agent", user_agent)
-            .send()
-            .await
-        {
-            Ok(response) => Ok(response.status().is_success()),
+        match self.follow_redirects(Method::HEAD, url, user_agent, &None, self.redirect_rules()).await {
+            Ok(Fetched { response, .. }) => Ok(response.status().is_success()),
             Err(_) => Ok(false),
         }
     }
     
     /// Get response headers for a URL
     pub async fn get_headers(&self, url: &Url, user_agent: &str) -> Result<HeaderMap> {
-        let response = self.client.head(url.as_str())
-            .header("User-Agent", user_agent)
-            .send()
-            .await?;
-        
+        let Fetched { response, .. } = self.follow_redirects(Method::HEAD, url, user_agent, &None, self.redirect_rules()).await?;
         Ok(response.headers().clone())
     }
 }
@@ -302,3 +516,42 @@ pub struct HttpPerformanceStats {
     pub avg_response_time: Duration,
     pub total_bytes_transferred: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_content_type_allowed_by_media_type_prefix() {
+        let allowed = ResponseLimitsConfig::default().allowed_content_types;
+        assert!(content_type_allowed("text/html; charset=EUC-KR", &allowed));
+        assert!(content_type_allowed("Application/XHTML+XML", &allowed));
+        assert!(!content_type_allowed("application/pdf", &allowed));
+        assert!(!content_type_allowed("application/octet-stream", &allowed));
+        assert!(content_type_allowed("image/png", &[]));
+    }
+    
+    #[test]
+    fn test_same_site_ignores_www_and_scheme() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert!(same_site(&url("http://example.com/a"), &url("https://www.example.com/b")));
+        assert!(!same_site(&url("https://example.com/"), &url("https://example.org/")));
+        assert!(!same_site(&url("https://example.com/"), &url("https://shop.example.com/")));
+    }
+    
+    #[test]
+    fn test_credentials_stay_with_their_host() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert!(!leaves_origin(&url("http://example.com/a"), &url("http://example.com:80/b")));
+        assert!(leaves_origin(&url("https://example.com/"), &url("https://example.com:8443/")));
+        assert!(leaves_origin(&url("https://example.com/"), &url("https://evil.example.org/")));
+        
+        let mut headers = Some(HashMap::from([
+            ("Authorization".to_string(), "Bearer secret".to_string()),
+            ("cookie".to_string(), "session=1".to_string()),
+            ("Accept-Language".to_string(), "de-DE".to_string()),
+        ]));
+        drop_credentials(&mut headers);
+        assert_eq!(headers, Some(HashMap::from([("Accept-Language".to_string(), "de-DE".to_string())])));
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::Result;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use scraper::{Html, Selector, ElementRef};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Semaphore;
+use tokio::sync::{mpsc, Semaphore};
 use tracing::{info, warn, error, debug};
 use url::Url;
 
@@ -13,20 +14,50 @@ pub mod browser;
 pub mod robots;
 pub mod rate_limiter;
 pub mod user_agent;
+pub mod result_buffer;
+pub mod dry_run;
+pub mod request_trace;
+pub mod simulation;
+pub mod post_process;
+pub mod run_issues;
+pub mod circuit_breaker;
+pub mod adaptive;
+pub mod charset;
+pub mod recording;
+pub mod robots_meta;
+pub mod testing;
+
+pub use dry_run::{DryRunPage, DryRunReport, FieldHits};
+pub use simulation::{DomainPlan, ExecutionPlan};
+pub use request_trace::{RequestStats, RequestTrace};
+pub use run_issues::{IssueGroup, IssueKind, IssueReport};
 
 use crate::config::ScrapingConfig;
-use crate::dsl::{ScrapePlan, Field, SelectorType, ExtractionMethod, Transform};
+use crate::error::WinScrapeError;
+use crate::logging::{AuditLogger, LogContext};
+use crate::dsl::{ScrapePlan, Field, SelectorType, ExtractionMethod, Transform, MissingRequiredPolicy};
 
 /// Main scraping engine
 pub struct ScrapingEngine {
-    config: ScrapingConfig,
+    /// Shared with task clones so changed settings reach running jobs
+    config: Arc<std::sync::RwLock<ScrapingConfig>>,
     http_client: Arc<http_client::HttpClient>,
     #[cfg(feature = "browser")]
     browser_client: Option<Arc<browser::BrowserClient>>,
     robots_checker: Arc<robots::RobotsChecker>,
     rate_limiter: Arc<rate_limiter::RateLimiter>,
     user_agent_rotator: Arc<user_agent::UserAgentRotator>,
-    semaphore: Arc<Semaphore>,
+    /// Engine-wide request limit, replaced when the concurrency setting changes
+    semaphore: Arc<std::sync::RwLock<Arc<Semaphore>>>,
+    circuit_breaker: Arc<circuit_breaker::CircuitBreaker>,
+    /// Per-host concurrency and delay tuning, shared with the copies jobs run on
+    adaptive: Arc<adaptive::AdaptiveController>,
+    /// Saves every page response of a run
+    recorder: Option<Arc<recording::Recorder>>,
+    /// Answers page requests from a recording instead of the network
+    replay: Option<Arc<recording::Recording>>,
+    /// Where plans' `post_process` modules come from, and how far their limits go
+    scripts: Arc<post_process::ScriptSandbox>,
 }
 
 /// Scraping result for a single item
@@ -44,6 +75,12 @@ pub struct ItemMetadata {
     pub method: ScrapingMethod,
     pub response_time_ms: u64,
     pub status_code: Option<u16>,
+    /// Where the request ended up, when it was redirected
+    #[serde(default)]
+    pub final_url: Option<String>,
+    /// URLs redirected from, starting with `source_url`
+    #[serde(default)]
+    pub redirect_chain: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +89,189 @@ pub enum ScrapingMethod {
     Browser,
 }
 
+/// Rows produced by a scraping run together with its counts
+#[derive(Debug, Clone)]
+pub struct ScrapeReport {
+    pub rows: Vec<serde_json::Value>,
+    pub summary: ScrapeSummary,
+}
+
+/// Progress of a scraping run, sent while it executes
+#[derive(Debug, Clone)]
+pub enum ScrapeUpdate {
+    /// Fetching is about to start for this many URLs
+    Started { urls: usize },
+    /// A URL was processed; `items` is the number extracted from it
+    Page { trace: RequestTrace, items: usize },
+    /// Rows newly kept, in final order
+    Rows(Vec<serde_json::Value>),
+}
+
+/// Counts collected while executing a plan, stored as the job summary
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScrapeSummary {
+    pub pages_scraped: usize,
+    pub pages_failed: usize,
+    /// Target URLs that were not fetched, e.g. because robots.txt disallows them
+    #[serde(default)]
+    pub pages_skipped: usize,
+    /// Elements matched by the item selector
+    pub items_matched: usize,
+    /// Items where at least one required field could not be extracted
+    pub items_missing_required: usize,
+    /// Items dropped because of missing required fields
+    pub items_dropped: usize,
+    /// Items the post-process script dropped or failed on
+    #[serde(default)]
+    pub items_dropped_by_script: usize,
+    pub duplicates: usize,
+    /// Items left out because an earlier run of the plan stored them
+    #[serde(default)]
+    pub previously_seen: usize,
+    /// Rows kept after filtering, deduplication and the output limit
+    pub items_kept: usize,
+    #[serde(default)]
+    pub skipped_urls: Vec<SkippedUrl>,
+    /// User agent, delays and outcome of every request, in URL order
+    #[serde(default)]
+    pub requests: Vec<RequestTrace>,
+    /// Problems met along the way, by kind, stage and field
+    #[serde(default)]
+    pub issues: IssueReport,
+}
+
+/// A target URL left out of a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedUrl {
+    pub url: String,
+    pub reason: String,
+}
+
+impl ScrapeSummary {
+    /// Share of matched items that were missing a required field, in percent
+    pub fn missing_required_percent(&self) -> f64 {
+        if self.items_matched == 0 {
+            0.0
+        } else {
+            self.items_missing_required as f64 * 100.0 / self.items_matched as f64
+        }
+    }
+
+    /// Fail when a `fail_above` policy's threshold is exceeded
+    pub fn check_missing_required(&self, policy: &MissingRequiredPolicy) -> Result<()> {
+        if let MissingRequiredPolicy::FailAbove { max_percent } = policy {
+            let percent = self.missing_required_percent();
+            if percent > *max_percent {
+                return Err(anyhow::anyhow!(
+                    "{} of {} items ({:.1}%) were missing required fields, above the {}% limit",
+                    self.items_missing_required, self.items_matched, percent, max_percent
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Request outcomes grouped by user agent and by proxy
+    pub fn request_stats(&self) -> Vec<RequestStats> {
+        request_trace::aggregate(&self.requests)
+    }
+
+    /// Problems the run met without failing, shown as "Completed with N warnings"
+    pub fn warning_count(&self) -> usize {
+        self.issues.total()
+    }
+
+    /// URLs whose request failed, in request order
+    pub fn failed_urls(&self) -> Vec<String> {
+        self.requests.iter()
+            .filter(|trace| trace.is_failure())
+            .map(|trace| trace.url.clone())
+            .collect()
+    }
+
+    /// Counts of this run followed by a run that fetched its failed URLs again
+    pub fn merge_retry(mut self, retry: ScrapeSummary) -> Self {
+        let retried: HashSet<&str> = retry.requests.iter().map(|trace| trace.url.as_str()).collect();
+        self.requests.retain(|trace| !retried.contains(trace.url.as_str()));
+        self.requests.extend(retry.requests);
+
+        self.pages_scraped += retry.pages_scraped;
+        self.pages_failed = retry.pages_failed;
+        // The retry fetched the failed URLs again, so only its fetch failures still stand
+        self.issues.groups.retain(|group| group.stage != "fetching");
+        self.issues.merge(retry.issues);
+        self.items_matched += retry.items_matched;
+        self.items_missing_required += retry.items_missing_required;
+        self.items_dropped += retry.items_dropped;
+        self.items_dropped_by_script += retry.items_dropped_by_script;
+        self.duplicates += retry.duplicates;
+        self.previously_seen += retry.previously_seen;
+        self.items_kept += retry.items_kept;
+        self
+    }
+
+    fn add_page(&mut self, page: &mut PageItems) {
+        self.pages_scraped += 1;
+        self.items_matched += page.matched;
+        self.items_missing_required += page.missing_required;
+        self.items_dropped += page.dropped;
+        self.items_dropped_by_script += page.dropped_by_script;
+        self.issues.merge(std::mem::take(&mut page.issues));
+    }
+}
+
+/// How a page was fetched, recorded in the metadata of its items
+struct PageFetch<'a> {
+    source_url: &'a Url,
+    final_url: &'a Url,
+    /// URLs redirected from, starting with `source_url`
+    redirects: &'a [String],
+    status_code: u16,
+    response_time: u64,
+    /// The page asks that its links not be followed, and the plan honors that
+    nofollow: bool,
+}
+
+/// Items extracted from a single page
+struct PageItems {
+    items: Vec<serde_json::Value>,
+    matched: usize,
+    missing_required: usize,
+    dropped: usize,
+    dropped_by_script: usize,
+    issues: IssueReport,
+}
+
+impl PageItems {
+    /// Items from a source that doesn't report per-item counts
+    fn from_items(items: Vec<serde_json::Value>) -> Self {
+        Self {
+            matched: items.len(),
+            items,
+            missing_required: 0,
+            dropped: 0,
+            dropped_by_script: 0,
+            issues: IssueReport::default(),
+        }
+    }
+    
+    /// Run a `post_process` script on every item, leaving out those it drops or fails on
+    fn post_process(&mut self, processor: &post_process::PostProcessor) {
+        for item in std::mem::take(&mut self.items) {
+            let url = item.get("_source_url").and_then(|url| url.as_str()).map(str::to_string);
+            match processor.apply(item) {
+                Ok(Some(item)) => self.items.push(item),
+                Ok(None) => self.dropped_by_script += 1,
+                Err(e) => {
+                    warn!("Leaving out an item: {}", e);
+                    self.issues.record(IssueKind::Script, "post_process", None, url.as_deref(), e.to_string());
+                    self.dropped_by_script += 1;
+                }
+            }
+        }
+    }
+}
+
 impl ScrapingEngine {
     /// Create new scraping engine
     pub async fn new(config: &ScrapingConfig) -> Result<Self> {
@@ -66,13 +286,15 @@ impl ScrapingEngine {
             None
         };
         
-        let robots_checker = Arc::new(robots::RobotsChecker::new(http_client.clone()));
+        let robots_checker = Arc::new(
+            robots::RobotsChecker::new(http_client.clone()).with_cache_config(config.robots_cache.clone())
+        );
         let rate_limiter = Arc::new(rate_limiter::RateLimiter::new());
         let user_agent_rotator = Arc::new(user_agent::UserAgentRotator::new(&config.user_agents));
-        let semaphore = Arc::new(Semaphore::new(config.max_concurrent_requests));
+        let semaphore = Arc::new(std::sync::RwLock::new(Arc::new(Semaphore::new(config.max_concurrent_requests))));
         
         Ok(Self {
-            config: config.clone(),
+            config: Arc::new(std::sync::RwLock::new(config.clone())),
             http_client,
             #[cfg(feature = "browser")]
             browser_client,
@@ -80,66 +302,284 @@ impl ScrapingEngine {
             rate_limiter,
             user_agent_rotator,
             semaphore,
+            circuit_breaker: Arc::new(circuit_breaker::CircuitBreaker::new()),
+            adaptive: Arc::new(adaptive::AdaptiveController::new()),
+            recorder: None,
+            replay: None,
+            scripts: Arc::new(post_process::ScriptSandbox::default()),
         })
     }
     
+    /// Check redirect targets against the security manager's blocked domains and internal addresses
+    ///
+    /// Applies to every request of the engine's HTTP client, robots.txt included.
+    pub fn with_security(self, security: Arc<crate::security::SecurityManager>) -> Self {
+        self.http_client.set_redirect_check(Arc::new(move |url: &Url| security.validate_redirect_target(url)));
+        self
+    }
+    
+    /// Load plans' `post_process` modules from, and cap their limits by, `sandbox`
+    pub fn with_scripts(mut self, sandbox: post_process::ScriptSandbox) -> Self {
+        self.scripts = Arc::new(sandbox);
+        self
+    }
+    
+    /// Keep fetched robots.txt files in the storage cache, for later runs and while a host's robots.txt can't be fetched
+    ///
+    /// Jobs run on copies of this engine, so they all read and fill the same cache.
+    pub fn with_storage(mut self, storage: Arc<crate::storage::StorageManager>) -> Self {
+        let config = self.config().robots_cache.clone();
+        let keep_hours = config.ttl_hours.max(config.max_stale_hours) as i64;
+        let store = Arc::new(crate::storage::cache::RobotsCache::new(storage, keep_hours));
+        self.robots_checker = Arc::new(
+            robots::RobotsChecker::new(self.http_client.clone()).with_cache_config(config).with_store(store)
+        );
+        self
+    }
+    
+    /// A copy of the engine that saves every page request and response to `recorder`
+    pub fn recording_to(&self, recorder: Arc<recording::Recorder>) -> Self {
+        Self { recorder: Some(recorder), ..self.clone_for_task() }
+    }
+    
+    /// A copy of the engine that answers page requests from `recording` without touching the network
+    ///
+    /// Delays, rate limits and robots.txt checks are left out, as nothing is fetched.
+    pub fn replaying(&self, recording: Arc<recording::Recording>) -> Self {
+        Self { replay: Some(recording), ..self.clone_for_task() }
+    }
+    
+    /// Use new settings for requests started from now on
+    ///
+    /// Requests already waiting for or holding a permit keep the old concurrency
+    /// limit. User agents and the browser itself only change on restart.
+    pub fn apply_config(&self, config: &ScrapingConfig) {
+        self.http_client.set_request_timeout(std::time::Duration::from_secs(config.request_timeout_seconds));
+        if config.max_concurrent_requests != self.config().max_concurrent_requests {
+            let mut semaphore = self.semaphore.write().unwrap_or_else(|e| e.into_inner());
+            *semaphore = Arc::new(Semaphore::new(config.max_concurrent_requests));
+        }
+        *self.config.write().unwrap_or_else(|e| e.into_inner()) = config.clone();
+        info!(
+            "Scraping settings updated: {} concurrent requests, {}s timeout, robots.txt {}",
+            config.max_concurrent_requests,
+            config.request_timeout_seconds,
+            if config.always_respect_robots_txt { "always respected" } else { "respected per plan" },
+        );
+    }
+    
+    fn config(&self) -> std::sync::RwLockReadGuard<'_, ScrapingConfig> {
+        self.config.read().unwrap_or_else(|e| e.into_inner())
+    }
+    
+    /// Whether robots.txt applies to a plan; plans decide unless the settings say always
+    ///
+    /// A replay fetches nothing, so there is no robots.txt to ask.
+    fn respects_robots(&self, plan: &ScrapePlan) -> bool {
+        self.replay.is_none() && (plan.anti_blocking.respect_robots_txt || self.config().always_respect_robots_txt)
+    }
+    
+    /// Redirects a plan's requests may follow
+    fn redirect_rules(&self, plan: &ScrapePlan) -> http_client::RedirectRules<'_> {
+        http_client::RedirectRules {
+            max_redirects: plan.anti_blocking.redirects.max_redirects,
+            allow_cross_domain: plan.anti_blocking.redirects.allow_cross_domain,
+            check: self.http_client.redirect_check(),
+        }
+    }
+    
+    /// Whether a browser is running for JavaScript-rendered pages
+    pub fn has_browser(&self) -> bool {
+        #[cfg(feature = "browser")]
+        return self.browser_client.is_some();
+        
+        #[cfg(not(feature = "browser"))]
+        false
+    }
+    
     /// Execute scraping based on DSL plan
-    pub async fn execute_scraping(&self, plan: &ScrapePlan) -> Result<Vec<serde_json::Value>> {
-        info!("Starting scraping execution for domain: {}", plan.target.domain);
+    pub async fn execute_scraping(&self, plan: &ScrapePlan) -> Result<ScrapeReport> {
+        self.execute_scraping_streaming(plan, None, HashSet::new()).await
+    }
+    
+    /// Execute scraping, sending progress and rows to `updates` as soon as they are kept
+    ///
+    /// Streamed rows are in final order but may still be updated by later
+    /// duplicates; the returned report holds their final values. Rows whose
+    /// [`result_buffer::seen_hash`] is in `seen` are left out.
+    pub async fn execute_scraping_streaming(
+        &self,
+        plan: &ScrapePlan,
+        updates: Option<mpsc::UnboundedSender<ScrapeUpdate>>,
+        seen: HashSet<String>,
+    ) -> Result<ScrapeReport> {
+        // A closed receiver only means nobody is watching
+        let send = |update: ScrapeUpdate| {
+            if let Some(tx) = &updates {
+                let _ = tx.send(update);
+            }
+        };
         
-        // Check robots.txt if required
-        if plan.anti_blocking.respect_robots_txt {
-            self.check_robots_compliance(plan).await?;
-        }
+        info!("Starting scraping execution for domain: {}", plan.target.domain);
         
         // Get all URLs to scrape
-        let urls = plan.get_all_urls()?;
+        let mut urls = plan.get_all_urls()?;
         info!("Found {} URLs to scrape", urls.len());
         
-        let mut all_results = Vec::new();
+        let mut summary = ScrapeSummary::default();
+        
+        // Leave out URLs robots.txt disallows, failing only if none remain
+        let respect_robots = self.respects_robots(plan);
+        if respect_robots {
+            let (allowed, skipped) = self.filter_robots_disallowed(urls).await?;
+            if let Some(first) = skipped.first() {
+                AuditLogger::log_security_event(
+                    "robots_disallowed",
+                    if allowed.is_empty() { "medium" } else { "low" },
+                    &format!("robots.txt disallows {} of the target URLs (first: {})", skipped.len(), first.url),
+                    Some(LogContext::new("scraper", "robots").with_domain(&plan.target.domain)),
+                );
+            }
+            if allowed.is_empty() && !skipped.is_empty() {
+                return Err(anyhow::Error::new(WinScrapeError::RobotsViolation { url: skipped[0].url.clone() })
+                    .context(format!("Robots.txt disallows all {} target URLs", skipped.len())));
+            }
+            if !skipped.is_empty() {
+                warn!("Skipping {} URLs disallowed by robots.txt", skipped.len());
+            }
+            for skipped in &skipped {
+                summary.issues.record(IssueKind::Blocked, "robots", None, Some(&skipped.url), skipped.reason.clone());
+            }
+            summary.pages_skipped = skipped.len();
+            summary.skipped_urls = skipped;
+            urls = allowed;
+        }
+        
+        // Apply the politeness preset on top of the engine configuration
+        let limits = plan.anti_blocking.politeness.limits();
+        let concurrency = self.config().max_concurrent_requests.min(limits.max_concurrency).max(1);
+        let plan_semaphore = Arc::new(Semaphore::new(concurrency));
+        info!("Using {} politeness preset ({} concurrent requests)", plan.anti_blocking.politeness, concurrency);
+        
+        if limits.honor_crawl_delay && respect_robots {
+            self.apply_crawl_delays(&urls).await;
+        }
+        
+        let seed = plan.anti_blocking.random_seed.unwrap_or_else(new_random_seed);
+        debug!("Using random seed {}", seed);
+        
+        let post_processor = plan.rules.post_process.as_ref()
+            .map(|post_process| post_process::PostProcessor::new(post_process, &self.scripts))
+            .transpose()?;
+        
+        let mut buffer = result_buffer::ResultBuffer::new(&plan.output).with_seen(seen);
+        send(ScrapeUpdate::Started { urls: urls.len() });
         
         // Process URLs with concurrency control
         let mut tasks = Vec::new();
         
         for url in urls {
-            let permit = self.semaphore.clone().acquire_owned().await?;
+            let semaphore = self.semaphore.read().unwrap_or_else(|e| e.into_inner()).clone();
+            let plan_semaphore = plan_semaphore.clone();
             let engine = self.clone_for_task();
             let plan = plan.clone();
             
             // Create a future without spawning to avoid Send trait bound issues with scraper crate
             let task = async move {
-                let _permit = permit; // Keep permit alive
-                engine.scrape_single_url(&url, &plan).await
+                let mut trace = RequestTrace::new(url.as_str());
+                let result = async {
+                    // Permits are acquired inside the future so queued URLs don't block the loop
+                    let _plan_permit = plan_semaphore.acquire_owned().await?;
+                    let _permit = semaphore.acquire_owned().await?;
+                    engine.scrape_single_url(&url, &plan, seed, &mut trace).await
+                }.await;
+                match &result {
+                    Err(e) if is_skip(e) => trace.skipped = Some(e.to_string()),
+                    Err(e) => trace.error = Some(e.to_string()),
+                    Ok(_) => {}
+                }
+                (trace, result)
             };
             
             tasks.push(task);
         }
         
-        // Execute all tasks concurrently using join_all
-        use futures::future::join_all;
-        let results = join_all(tasks).await;
+        // Execute all tasks concurrently, collecting pages in URL order as they finish
+        use futures::stream::{FuturesOrdered, StreamExt};
+        let mut results: FuturesOrdered<_> = tasks.into_iter().collect();
+        let stream_limit = plan.output.limit.unwrap_or(usize::MAX);
         
         // Collect results
-        for result in results {
+        while let Some((trace, mut result)) = results.next().await {
+            if let (Ok(page), Some(processor)) = (&mut result, &post_processor) {
+                page.post_process(processor);
+            }
+            if updates.is_some() {
+                let items = result.as_ref().map_or(0, |page| page.items.len());
+                send(ScrapeUpdate::Page { trace: trace.clone(), items });
+            }
             match result {
-                Ok(mut data) => {
-                    all_results.append(&mut data);
+                Ok(mut page) => {
+                    summary.add_page(&mut page);
+                    let before = buffer.len().min(stream_limit);
+                    buffer.extend(page.items);
+                    
+                    let after = buffer.len().min(stream_limit);
+                    if updates.is_some() && after > before {
+                        send(ScrapeUpdate::Rows(buffer.rows()[before..after].to_vec()));
+                    }
+                }
+                Err(e) if is_skip(e) => {
+                    warn!("Skipping {}: {}", trace.url, e);
+                    summary.issues.record(IssueKind::Rejected, "fetching", None, Some(&trace.url), e.to_string());
+                    summary.pages_skipped += 1;
+                    summary.skipped_urls.push(SkippedUrl { url: trace.url.clone(), reason: e.to_string() });
                 }
                 Err(e) => {
                     error!("Failed to scrape URL: {}", e);
+                    let message = e.to_string();
+                    let kind = match trace.status_code {
+                        Some(401 | 403 | 429) => IssueKind::Blocked,
+                        _ => IssueKind::classify(&message),
+                    };
+                    summary.issues.record(kind, "fetching", None, Some(&trace.url), message);
+                    summary.pages_failed += 1;
                     // Continue with other URLs
                 }
             }
+            if let Some(reason) = &trace.circuit_opened {
+                summary.issues.record(IssueKind::CircuitOpen, "fetching", None, Some(&trace.url), reason.clone());
+            }
+            summary.requests.push(trace);
         }
         
-        info!("Scraping completed. Total items: {}", all_results.len());
+        if buffer.duplicates() > 0 {
+            info!("Handled {} duplicate items with {:?} policy", buffer.duplicates(), plan.output.duplicate_policy);
+        }
+        if summary.items_dropped_by_script > 0 {
+            info!("The post-process script dropped {} items", summary.items_dropped_by_script);
+        }
+        if summary.items_missing_required > 0 {
+            info!(
+                "{} of {} items were missing required fields ({} dropped)",
+                summary.items_missing_required, summary.items_matched, summary.items_dropped
+            );
+        }
+        if buffer.previously_seen() > 0 {
+            info!("Skipped {} items seen in earlier runs", buffer.previously_seen());
+        }
+        info!("Scraping completed. Total items: {}", buffer.len());
+        summary.duplicates = buffer.duplicates();
+        summary.previously_seen = buffer.previously_seen();
+        let mut all_results = buffer.into_rows();
         
         // Apply output limits if specified
         if let Some(limit) = plan.output.limit {
             all_results.truncate(limit);
         }
+        summary.items_kept = all_results.len();
         
-        Ok(all_results)
+        Ok(ScrapeReport { rows: all_results, summary })
     }
     
     /// Generate preview with limited results
@@ -149,7 +589,13 @@ impl ScrapingEngine {
         // Use only the first start URL for preview
         if let Some(first_url) = plan.target.start_urls.first() {
             let url = Url::parse(first_url)?;
-            let mut results = self.scrape_single_url(&url, plan).await?;
+            let seed = plan.anti_blocking.random_seed.unwrap_or_else(new_random_seed);
+            let mut trace = RequestTrace::new(url.as_str());
+            let mut page = self.scrape_single_url(&url, plan, seed, &mut trace).await?;
+            if let Some(post_process) = &plan.rules.post_process {
+                page.post_process(&post_process::PostProcessor::new(post_process)?);
+            }
+            let mut results = page.items;
             results.truncate(limit);
             Ok(results)
         } else {
@@ -157,32 +603,111 @@ impl ScrapingEngine {
         }
     }
     
-    /// Scrape a single URL
-    async fn scrape_single_url(&self, url: &Url, plan: &ScrapePlan) -> Result<Vec<serde_json::Value>> {
+    /// Fetch the HTML of a page for analysis, honoring robots.txt
+    pub async fn fetch_page(&self, url: &Url) -> Result<String> {
+        let user_agent = self.user_agent_rotator.get_random_user_agent().to_string();
+        
+        if !self.robots_checker.is_allowed(url, &user_agent).await? {
+            AuditLogger::log_security_event(
+                "robots_disallowed",
+                "low",
+                &format!("robots.txt disallows fetching {}", url),
+                Some(LogContext::new("scraper", "robots").with_domain(url.host_str().unwrap_or_default())),
+            );
+            return Err(WinScrapeError::RobotsViolation { url: url.to_string() }.into());
+        }
+        
+        self.rate_limiter.wait_for_domain(url.host_str().unwrap_or("")).await;
+        let redirects = http_client::RedirectRules { check: self.http_client.redirect_check(), ..Default::default() };
+        let max_retries = self.config().max_retries;
+        let response = self.http_client
+            .get_with_redirects(url, &user_agent, &None, max_retries, redirects)
+            .await?
+            .response;
+        let limits = self.config().response_limits.clone();
+        
+        http_client::read_text(response, &limits).await
+    }
+    
+    /// Scrape a single URL, recording the anti-blocking choices made in `trace`
+    async fn scrape_single_url(&self, url: &Url, plan: &ScrapePlan, seed: u64, trace: &mut RequestTrace) -> Result<PageItems> {
         debug!("Scraping URL: {}", url);
         
+        // Draw this URL's delay and user agent up front so they only depend on the seed
+        let mut rng = url_rng(seed, url);
+        let delay = self.calculate_delay(&plan.anti_blocking.randomized_delays, &mut rng);
+        let user_agent = self.plan_user_agent(plan, &mut rng);
+        trace.user_agent = user_agent.clone();
+        
+        // A replay answers from the recording, so there is no host to be polite to
+        if self.replay.is_some() {
+            return self.fetch_and_extract(url, plan, &user_agent, 0, trace).await;
+        }
+        
+        // Leave a host that keeps failing alone until its cooldown is over
+        let host = url.host_str().unwrap_or("");
+        while let Some(wait) = self.circuit_breaker.open_for(host) {
+            debug!("Circuit for {} is open; waiting {}ms", host, wait.as_millis());
+            trace.circuit_wait_ms += wait.as_millis() as u64;
+            tokio::time::sleep(wait).await;
+        }
+        
+        // Hold one of the host's request slots, as many as its tuning allows now
+        let max_concurrency = self.config().max_concurrent_requests
+            .min(plan.anti_blocking.politeness.limits().max_concurrency)
+            .max(1);
+        let adaptive_config = self.config().adaptive.clone();
+        let _host_permit = self.adaptive.acquire(host, max_concurrency, &adaptive_config).await;
+        let delay = (delay as f64 * self.adaptive.delay_factor(host)).round() as u64;
+        trace.delay_ms = delay;
+        
         // Apply rate limiting
+        let wait_start = std::time::Instant::now();
         self.rate_limiter.wait_for_domain(url.host_str().unwrap_or("")).await;
+        trace.rate_limit_wait_ms = wait_start.elapsed().as_millis() as u64;
         
         // Apply delay
-        let delay = self.calculate_delay(&plan.anti_blocking.randomized_delays);
         tokio::time::sleep(Duration::from_millis(delay)).await;
         
+        let result = self.fetch_and_extract(url, plan, &user_agent, delay, trace).await;
+        // A response left unread or a redirect not followed says nothing about the host's health
+        let fetch_failed = result.as_ref().is_err_and(|e| !is_skip(e));
+        let outcome = if trace.is_blocked() {
+            circuit_breaker::HostOutcome::Blocked
+        } else if fetch_failed || trace.status_code.is_some_and(|code| code >= 500) {
+            circuit_breaker::HostOutcome::Failure
+        } else {
+            circuit_breaker::HostOutcome::Success
+        };
+        trace.circuit_opened = self.circuit_breaker.record(host, outcome, &self.config().circuit_breaker);
+        let failed = outcome != circuit_breaker::HostOutcome::Success;
+        self.adaptive.record(host, trace.response_time_ms, failed, max_concurrency, &adaptive_config);
+        result
+    }
+    
+    /// Fetch a page over HTTP, falling back to the browser if enabled, and extract its items
+    async fn fetch_and_extract(&self, url: &Url, plan: &ScrapePlan, user_agent: &str, delay: u64, trace: &mut RequestTrace) -> Result<PageItems> {
         // Try HTTP first
-        match self.scrape_with_http(url, plan).await {
+        trace.method = Some("http".to_string());
+        match self.scrape_with_http(url, plan, user_agent, trace).await {
             Ok(results) => {
-                debug!("HTTP scraping successful for {}", url);
+                debug!(
+                    "HTTP scraping successful for {} (user agent: {}, delay: {}ms, rate limit wait: {}ms)",
+                    url, user_agent, delay, trace.rate_limit_wait_ms
+                );
                 Ok(results)
             }
+            Err(e) if is_skip(e) => Err(e),
             Err(e) => {
                 warn!("HTTP scraping failed for {}: {}", url, e);
                 
                 // Fallback to browser if enabled
                 #[cfg(feature = "browser")]
-                if self.config.enable_browser_fallback {
+                if self.config().enable_browser_fallback && self.replay.is_none() {
                     if let Some(browser) = &self.browser_client {
                         info!("Falling back to browser for {}", url);
-                        return browser.scrape_url(url, plan).await;
+                        trace.method = Some("browser".to_string());
+                        return browser.scrape_url(url, plan).await.map(PageItems::from_items);
                     }
                 }
                 
@@ -192,64 +717,166 @@ impl ScrapingEngine {
     }
     
     /// Scrape using HTTP client
-    async fn scrape_with_http(&self, url: &Url, plan: &ScrapePlan) -> Result<Vec<serde_json::Value>> {
-        let start_time = std::time::Instant::now();
-        
-        // Get user agent
-        let user_agent = self.user_agent_rotator.get_random_user_agent();
-        
-        // Make HTTP request
-        let response = self.http_client.get(url, &user_agent, &plan.anti_blocking.headers).await?;
-        let status_code = response.status().as_u16();
-        let response_time = start_time.elapsed().as_millis() as u64;
+    async fn scrape_with_http(&self, url: &Url, plan: &ScrapePlan, user_agent: &str, trace: &mut RequestTrace) -> Result<PageItems> {
+        let exchange = match &self.replay {
+            Some(recording) => recording.replay(url)?,
+            None => self.fetch_exchange(url, plan, user_agent, trace).await?,
+        };
+        if let Some(recorder) = &self.recorder {
+            recorder.record(&exchange);
+        }
         
-        // Get response body
-        let html_content = response.text().await?;
+        let final_url = Url::parse(&exchange.final_url)?;
+        if !exchange.redirects.is_empty() {
+            trace.final_url = Some(exchange.final_url.clone());
+            trace.redirects = exchange.redirects.clone();
+        }
+        trace.status_code = Some(exchange.status);
+        trace.response_time_ms = Some(exchange.time_ms);
         
         // Parse HTML
-        let document = Html::parse_document(&html_content);
+        let document = Html::parse_document(&exchange.body);
+        
+        // Honor the page's own robots directives as the plan asks
+        let header_directives = robots_meta::RobotsDirectives::from_headers(&exchange.response_headers);
+        let directives = header_directives.merge(robots_meta::RobotsDirectives::from_html(&document));
+        trace.robots_meta = directives.describe();
+        let policy = &plan.anti_blocking.robots_meta;
+        let log_context = || LogContext::new("scraper", "robots").with_domain(url.host_str().unwrap_or_default());
+        if directives.noindex && policy.skip_noindex {
+            let source = if header_directives.noindex { "X-Robots-Tag header" } else { "robots meta tag" };
+            AuditLogger::log_security_event(
+                "robots_noindex",
+                "low",
+                &format!("Not extracting {}: marked noindex by its {}", url, source),
+                Some(log_context()),
+            );
+            return Err(robots_meta::NoIndex(source).into());
+        }
+        let nofollow = directives.nofollow && policy.respect_nofollow;
+        if nofollow {
+            AuditLogger::log_security_event(
+                "robots_nofollow",
+                "low",
+                &format!("Links on {} are marked nofollow and will not be followed", url),
+                Some(log_context()),
+            );
+        }
         
         // Extract items
-        let items = self.extract_items(&document, plan, url, status_code, response_time).await?;
+        let fetch = PageFetch {
+            source_url: url,
+            final_url: &final_url,
+            redirects: &exchange.redirects,
+            status_code: exchange.status,
+            response_time: exchange.time_ms,
+            nofollow,
+        };
+        let items = self.extract_items(&document, plan, &fetch)?;
         
         Ok(items)
     }
     
+    /// Request a page over the network and read its body
+    async fn fetch_exchange(&self, url: &Url, plan: &ScrapePlan, user_agent: &str, trace: &mut RequestTrace) -> Result<recording::Exchange> {
+        let started_at = chrono::Utc::now();
+        let start_time = std::time::Instant::now();
+        
+        // Make HTTP request, capping retries at the politeness preset
+        let max_retries = self.config().max_retries.min(plan.anti_blocking.politeness.limits().max_retries);
+        let headers = plan.anti_blocking.request_headers();
+        let http_client::Fetched { response, redirects } = self.http_client
+            .get_with_redirects(url, user_agent, &headers, max_retries, self.redirect_rules(plan))
+            .await?;
+        let final_url = response.url().to_string();
+        let status = response.status().as_u16();
+        let time_ms = start_time.elapsed().as_millis() as u64;
+        // Known before the body is read, so skipped responses still show them
+        trace.status_code = Some(status);
+        trace.response_time_ms = Some(time_ms);
+        let response_headers = response.headers().iter()
+            .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+            .collect();
+        
+        // Get response body, leaving out huge or non-page responses
+        let limits = self.config().response_limits.clone();
+        let body = http_client::read_text(response, &limits).await?;
+        
+        let mut request_headers = vec![("User-Agent".to_string(), user_agent.to_string())];
+        request_headers.extend(headers.into_iter().flatten());
+        Ok(recording::Exchange {
+            url: url.to_string(),
+            request_headers,
+            status,
+            response_headers,
+            body,
+            final_url,
+            redirects,
+            time_ms,
+            started_at,
+        })
+    }
+    
     /// Extract items from HTML document
-    async fn extract_items(
-        &self,
-        document: &Html,
-        plan: &ScrapePlan,
-        source_url: &Url,
-        status_code: u16,
-        response_time: u64,
-    ) -> Result<Vec<serde_json::Value>> {
+    fn extract_items(&self, document: &Html, plan: &ScrapePlan, fetch: &PageFetch<'_>) -> Result<PageItems> {
+        let source_url = fetch.source_url;
         let item_selector = Selector::parse(&plan.rules.item_selector)
             .map_err(|e| anyhow::anyhow!("Invalid item selector: {}", e))?;
         
-        let mut items = Vec::new();
+        let mut page = PageItems {
+            items: Vec::new(),
+            matched: 0,
+            missing_required: 0,
+            dropped: 0,
+            dropped_by_script: 0,
+            issues: IssueReport::default(),
+        };
+        let url = source_url.as_str();
         
         for element in document.select(&item_selector) {
+            page.matched += 1;
             let mut item_data = HashMap::new();
+            let mut missing_required = Vec::new();
             
             // Extract each field
             for field in &plan.rules.fields {
-                match self.extract_field_value(&element, field, source_url).await {
+                // Links are relative to where the page ended up, not to the URL requested
+                match self.extract_field_value(&element, field, fetch.final_url) {
                     Ok(Some(value)) => {
                         item_data.insert(field.name.clone(), value);
                     }
                     Ok(None) => {
                         if field.required {
-                            debug!("Required field '{}' not found, skipping item", field.name);
-                            continue;
+                            page.issues.record(
+                                IssueKind::SelectorMiss, "extracting", Some(&field.name), Some(url),
+                                format!("Required field '{}' not found with '{}'", field.name, field.selector),
+                            );
+                            missing_required.push(field.name.as_str());
                         }
                     }
                     Err(e) => {
                         warn!("Failed to extract field '{}': {}", field.name, e);
+                        page.issues.record(IssueKind::ParseError, "extracting", Some(&field.name), Some(url), e.to_string());
                         if field.required {
-                            continue;
+                            missing_required.push(field.name.as_str());
+                        }
+                    }
+                }
+            }
+            
+            if !missing_required.is_empty() {
+                page.missing_required += 1;
+                match plan.rules.missing_required {
+                    MissingRequiredPolicy::KeepNull => {
+                        for name in &missing_required {
+                            item_data.insert(name.to_string(), serde_json::Value::Null);
                         }
                     }
+                    MissingRequiredPolicy::Drop | MissingRequiredPolicy::FailAbove { .. } => {
+                        debug!("Required fields {:?} not found, dropping item", missing_required);
+                        page.dropped += 1;
+                        continue;
+                    }
                 }
             }
             
@@ -257,27 +884,50 @@ impl ScrapingEngine {
             item_data.insert("_source_url".to_string(), serde_json::Value::String(source_url.to_string()));
             item_data.insert("_scraped_at".to_string(), serde_json::Value::String(chrono::Utc::now().to_rfc3339()));
             item_data.insert("_method".to_string(), serde_json::Value::String("http".to_string()));
-            item_data.insert("_response_time_ms".to_string(), serde_json::Value::Number(response_time.into()));
-            item_data.insert("_status_code".to_string(), serde_json::Value::Number(status_code.into()));
+            item_data.insert("_response_time_ms".to_string(), serde_json::Value::Number(fetch.response_time.into()));
+            item_data.insert("_status_code".to_string(), serde_json::Value::Number(fetch.status_code.into()));
+            if !fetch.redirects.is_empty() {
+                item_data.insert("_final_url".to_string(), serde_json::Value::String(fetch.final_url.to_string()));
+                item_data.insert("_redirect_chain".to_string(), serde_json::json!(fetch.redirects));
+            }
+            if plan.anti_blocking.robots_meta.respect_nofollow {
+                let nofollow: Vec<&str> = plan.rules.fields.iter()
+                    .filter(|field| field.extraction == ExtractionMethod::Href && matches!(field.selector_type, SelectorType::CSS))
+                    .filter(|field| item_data.get(&field.name).is_some_and(|value| !value.is_null()))
+                    .filter(|field| fetch.nofollow || Selector::parse(&field.selector).ok()
+                        .and_then(|selector| element.select(&selector).next())
+                        .is_some_and(|link| robots_meta::is_nofollow_link(&link)))
+                    .map(|field| field.name.as_str())
+                    .collect();
+                if !nofollow.is_empty() {
+                    item_data.insert(robots_meta::NOFOLLOW_FIELD.to_string(), serde_json::json!(nofollow));
+                }
+            }
             
             // Apply filters
             if self.passes_filters(&item_data, &plan.rules.filters) {
-                items.push(serde_json::Value::Object(
+                page.items.push(serde_json::Value::Object(
                     item_data.into_iter().map(|(k, v)| (k, v)).collect()
                 ));
             }
         }
         
-        debug!("Extracted {} items from {}", items.len(), source_url);
-        Ok(items)
+        if page.matched == 0 {
+            page.issues.record(
+                IssueKind::SelectorMiss, "extracting", None, Some(url),
+                format!("Item selector '{}' matched nothing", plan.rules.item_selector),
+            );
+        }
+        debug!("Extracted {} items from {}", page.items.len(), source_url);
+        Ok(page)
     }
     
     /// Extract value for a single field
-    async fn extract_field_value(
+    fn extract_field_value(
         &self,
         element: &ElementRef<'_>,
         field: &Field,
-        source_url: &Url,
+        page_url: &Url,
     ) -> Result<Option<serde_json::Value>> {
         // Parse selector based on type
         let value = match field.selector_type {
@@ -286,7 +936,7 @@ impl ScrapingEngine {
                     .map_err(|e| anyhow::anyhow!("Invalid CSS selector '{}': {}", field.selector, e))?;
                 
                 if let Some(selected_element) = element.select(&selector).next() {
-                    self.extract_value_by_method(&selected_element, &field.extraction, source_url)?
+                    self.extract_value_by_method(&selected_element, &field.extraction, page_url)?
                 } else {
                     return Ok(None);
                 }
@@ -308,12 +958,12 @@ impl ScrapingEngine {
         Ok(Some(transformed_value))
     }
     
-    /// Extract value using specified method
+    /// Extract value using specified method, resolving links against `page_url`
     fn extract_value_by_method(
         &self,
         element: &ElementRef<'_>,
         method: &ExtractionMethod,
-        source_url: &Url,
+        page_url: &Url,
     ) -> Result<serde_json::Value> {
         let value = match method {
             ExtractionMethod::Text => {
@@ -328,7 +978,7 @@ impl ScrapingEngine {
             ExtractionMethod::Href => {
                 let href = element.value().attr("href").unwrap_or("");
                 // Convert relative URLs to absolute
-                if let Ok(base_url) = Url::parse(&source_url.to_string()) {
+                if let Ok(base_url) = Url::parse(&page_url.to_string()) {
                     if let Ok(absolute_url) = base_url.join(href) {
                         absolute_url.to_string()
                     } else {
@@ -341,7 +991,7 @@ impl ScrapingEngine {
             ExtractionMethod::Src => {
                 let src = element.value().attr("src").unwrap_or("");
                 // Convert relative URLs to absolute
-                if let Ok(base_url) = Url::parse(&source_url.to_string()) {
+                if let Ok(base_url) = Url::parse(&page_url.to_string()) {
                     if let Ok(absolute_url) = base_url.join(src) {
                         absolute_url.to_string()
                     } else {
@@ -366,6 +1016,13 @@ impl ScrapingEngine {
     
     /// Apply a single transformation
     fn apply_single_transform(&self, value: serde_json::Value, transform: &Transform) -> Result<serde_json::Value> {
+        // Plugins see every value, not only text
+        if let Transform::Plugin { name, args } = transform {
+            let plugin = crate::plugins::transform(name)
+                .ok_or_else(|| anyhow::anyhow!("Transform plugin not loaded: {}", name))?;
+            return plugin.apply(value, args);
+        }
+        
         if let Some(text) = value.as_str() {
             let transformed = match transform {
                 Transform::Trim => text.trim().to_string(),
@@ -400,6 +1057,7 @@ impl ScrapingEngine {
                         text.to_string()
                     }
                 }
+                Transform::Plugin { .. } => unreachable!("plugin transforms are applied above"),
             };
             
             Ok(serde_json::Value::String(transformed))
@@ -410,20 +1068,31 @@ impl ScrapingEngine {
     }
     
     /// Check if item passes all filters
-    fn passes_filters(&self, item: &HashMap<String, serde_json::Value>, filters: &Option<Vec<crate::dsl::Filter>>) -> bool {
+    fn passes_filters(&self, item: &HashMap<String, serde_json::Value>, filters: &Option<Vec<crate::dsl::FilterExpr>>) -> bool {
         if let Some(filters) = filters {
-            for filter in filters {
-                if let Some(field_value) = item.get(&filter.field) {
-                    if !self.check_filter_condition(field_value, &filter.condition) {
-                        return false;
-                    }
+            // Top-level filters are combined with AND
+            return filters.iter().all(|filter| self.evaluate_filter(item, filter));
+        }
+        true
+    }
+    
+    /// Evaluate a (possibly nested) filter expression against an item
+    fn evaluate_filter(&self, item: &HashMap<String, serde_json::Value>, filter: &crate::dsl::FilterExpr) -> bool {
+        use crate::dsl::FilterExpr;
+        
+        match filter {
+            FilterExpr::All { all } => all.iter().all(|f| self.evaluate_filter(item, f)),
+            FilterExpr::Any { any } => any.iter().any(|f| self.evaluate_filter(item, f)),
+            FilterExpr::Not { not } => !self.evaluate_filter(item, not),
+            FilterExpr::Condition(condition) => {
+                if let Some(field_value) = item.get(&condition.field) {
+                    self.check_filter_condition(field_value, &condition.condition)
                 } else {
                     // Field doesn't exist, filter fails
-                    return false;
+                    false
                 }
             }
         }
-        true
     }
     
     /// Check if a value passes a filter condition
@@ -484,6 +1153,12 @@ impl ScrapingEngine {
                     false
                 }
             }
+            FilterCondition::NumberMin { min } => {
+                numeric_value(value).is_some_and(|number| number >= *min)
+            }
+            FilterCondition::NumberMax { max } => {
+                numeric_value(value).is_some_and(|number| number <= *max)
+            }
             FilterCondition::NotEmpty => {
                 if let Some(text) = value.as_str() {
                     !text.trim().is_empty()
@@ -491,26 +1166,70 @@ impl ScrapingEngine {
                     !value.is_null()
                 }
             }
+            FilterCondition::Plugin { name, args } => {
+                let result = crate::plugins::filter(name)
+                    .ok_or_else(|| anyhow::anyhow!("Filter plugin not loaded: {}", name))
+                    .and_then(|plugin| plugin.matches(value, args));
+                result.unwrap_or_else(|e| {
+                    warn!("Filter plugin {} failed, dropping the item: {}", name, e);
+                    false
+                })
+            }
         }
     }
     
-    /// Check robots.txt compliance
-    async fn check_robots_compliance(&self, plan: &ScrapePlan) -> Result<()> {
-        for url_str in &plan.target.start_urls {
-            let url = Url::parse(url_str)?;
-            if !self.robots_checker.is_allowed(&url, "*").await? {
-                return Err(anyhow::anyhow!("Robots.txt disallows access to: {}", url));
+    /// Split target URLs into those robots.txt allows and those it disallows
+    async fn filter_robots_disallowed(&self, urls: Vec<Url>) -> Result<(Vec<Url>, Vec<SkippedUrl>)> {
+        let mut allowed = Vec::with_capacity(urls.len());
+        let mut skipped = Vec::new();
+        
+        for url in urls {
+            if self.robots_checker.is_allowed(&url, "*").await? {
+                allowed.push(url);
+            } else {
+                debug!("Robots.txt disallows access to: {}", url);
+                skipped.push(SkippedUrl {
+                    url: url.to_string(),
+                    reason: "disallowed by robots.txt".to_string(),
+                });
             }
         }
-        Ok(())
+        
+        Ok((allowed, skipped))
+    }
+    
+    /// Raise per-domain delays to any robots.txt `Crawl-delay`
+    async fn apply_crawl_delays(&self, urls: &[Url]) {
+        let mut seen = std::collections::HashSet::new();
+        
+        for url in urls {
+            let Some(domain) = url.host_str() else { continue };
+            if !seen.insert(domain.to_string()) {
+                continue;
+            }
+            
+            match self.robots_checker.get_crawl_delay(domain, "*").await {
+                Ok(Some(seconds)) => {
+                    debug!("Honoring crawl-delay of {}s for {}", seconds, domain);
+                    self.rate_limiter.set_domain_delay(domain, Duration::from_secs(seconds)).await;
+                }
+                Ok(None) => {}
+                Err(e) => debug!("Could not read crawl-delay for {}: {}", domain, e),
+            }
+        }
+    }
+    
+    /// User agent for a request: the plan's own or client profile's, else one drawn from the rotation
+    fn plan_user_agent(&self, plan: &ScrapePlan, rng: &mut impl Rng) -> String {
+        match plan.anti_blocking.fixed_user_agent() {
+            Some(user_agent) => user_agent.to_string(),
+            None => self.user_agent_rotator.choose_user_agent(rng).to_string(),
+        }
     }
     
     /// Calculate delay based on configuration
-    fn calculate_delay(&self, delay_config: &crate::dsl::DelayConfig) -> u64 {
+    fn calculate_delay(&self, delay_config: &crate::dsl::DelayConfig, rng: &mut impl Rng) -> u64 {
         use crate::dsl::DelayDistribution;
-        use rand::Rng;
-        
-        let mut rng = rand::thread_rng();
         
         match delay_config.distribution {
             DelayDistribution::Uniform => {
@@ -532,8 +1251,8 @@ impl ScrapingEngine {
         }
     }
     
-    /// Clone for async task usage
-    fn clone_for_task(&self) -> Self {
+    /// A copy for a task or job, sharing the configuration, clients and per-host state
+    pub(crate) fn clone_for_task(&self) -> Self {
         Self {
             config: self.config.clone(),
             http_client: self.http_client.clone(),
@@ -543,10 +1262,57 @@ impl ScrapingEngine {
             rate_limiter: self.rate_limiter.clone(),
             user_agent_rotator: self.user_agent_rotator.clone(),
             semaphore: self.semaphore.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
+            adaptive: self.adaptive.clone(),
+            recorder: self.recorder.clone(),
+            replay: self.replay.clone(),
+            scripts: self.scripts.clone(),
         }
     }
 }
 
+/// Whether a page was left out on purpose rather than failing
+fn is_skip(error: &anyhow::Error) -> bool {
+    error.is::<http_client::ResponseRejected>()
+        || error.is::<http_client::RedirectRejected>()
+        || error.is::<robots_meta::NoIndex>()
+}
+
+/// Draw a fresh run seed
+///
+/// Kept below 2^63 so plans that record it remain representable in TOML.
+pub fn new_random_seed() -> u64 {
+    rand::random::<u64>() >> 1
+}
+
+/// Random source for one URL of a run
+///
+/// Derived from the run seed and the URL rather than shared, so the draws don't
+/// depend on the order in which concurrent tasks run.
+fn url_rng(seed: u64, url: &Url) -> StdRng {
+    // FNV-1a, which unlike the std hasher is stable across builds
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in url.as_str().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    StdRng::seed_from_u64(seed ^ hash)
+}
+
+/// Number held by a value, reading text like "$1,299.00" as 1299
+fn numeric_value(value: &serde_json::Value) -> Option<f64> {
+    if let Some(number) = value.as_f64() {
+        return Some(number);
+    }
+    let text = value.as_str()?;
+    let start = text.find(|c: char| c.is_ascii_digit() || c == '-' || c == '.')?;
+    let number: String = text[start..].chars()
+        .take_while(|c| c.is_ascii_digit() || matches!(c, '.' | ',' | '-'))
+        .filter(|c| *c != ',')
+        .collect();
+    number.parse().ok()
+}
+
 impl Default for ScrapingConfig {
     fn default() -> Self {
         Self {
@@ -555,12 +1321,40 @@ impl Default for ScrapingConfig {
             max_retries: 3,
             retry_delay_seconds: 2,
             respect_robots_txt: true,
+            always_respect_robots_txt: false,
             default_delay_ms: 1000,
             user_agents: vec![
                 "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36".to_string(),
             ],
             enable_browser_fallback: false,
             browser_timeout_seconds: 60,
+            circuit_breaker: Default::default(),
+            adaptive: Default::default(),
+            response_limits: Default::default(),
+            robots_cache: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_apply_config_reaches_running_copies() {
+        let engine = ScrapingEngine::new(&ScrapingConfig::default()).await.unwrap();
+        let copy = engine.clone_for_task();
+        let mut plan = ScrapePlan::default();
+        plan.anti_blocking.respect_robots_txt = false;
+        assert!(!copy.respects_robots(&plan));
+        
+        engine.apply_config(&ScrapingConfig {
+            max_concurrent_requests: 3,
+            always_respect_robots_txt: true,
+            ..Default::default()
+        });
+        assert!(copy.respects_robots(&plan));
+        assert_eq!(copy.semaphore.read().unwrap().available_permits(), 3);
+        assert_eq!(copy.config().max_concurrent_requests, 3);
+    }
+}
@@ -0,0 +1,226 @@
+//! Running a plan's `post_process` script on scraped items
+
+use anyhow::Result;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::AppConfig;
+use crate::dsl::{PostProcess, ScriptLimits};
+
+#[cfg(feature = "scripting")]
+use crate::plugins::wasm::{SandboxLimits, WasmModule};
+#[cfg(feature = "scripting")]
+use tracing::debug;
+
+/// Where `post_process` modules are read from and how much a script may ask for
+///
+/// Plans come from API clients as well as the user, so they only name modules
+/// in the plugins directory and their limits are capped by the configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptSandbox {
+    pub modules_dir: PathBuf,
+    pub max_operations: u64,
+    pub max_memory_mb: u32,
+}
+
+impl ScriptSandbox {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            modules_dir: config.get_plugins_dir(),
+            max_operations: config.plugins.max_script_operations,
+            max_memory_mb: config.plugins.max_script_memory_mb,
+        }
+    }
+
+    /// Path of a module named in a plan, relative to the plugins directory
+    ///
+    /// Absolute paths and `..` are refused, as is anything but a regular file.
+    pub fn module_path(&self, module: &Path) -> Result<PathBuf> {
+        let relative = module.components().all(|part| matches!(part, Component::Normal(_) | Component::CurDir));
+        if !relative {
+            return Err(anyhow::anyhow!(
+                "post_process module {} must be named relative to the plugins directory, without ..",
+                module.display()
+            ));
+        }
+        let path = self.modules_dir.join(module);
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("No post_process module {} in {}", module.display(), self.modules_dir.display()));
+        }
+        Ok(path)
+    }
+
+    /// A plan's limits, lowered to the configured ceilings
+    pub fn limits(&self, limits: &ScriptLimits) -> ScriptLimits {
+        ScriptLimits {
+            max_operations: limits.max_operations.min(self.max_operations),
+            max_memory_mb: limits.max_memory_mb.min(self.max_memory_mb),
+        }
+    }
+}
+
+impl Default for ScriptSandbox {
+    fn default() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+}
+
+/// A compiled `post_process` script, reused for every item of a run
+#[cfg(feature = "scripting")]
+pub struct PostProcessor {
+    script: Script,
+}
+
+#[cfg(feature = "scripting")]
+enum Script {
+    Rhai { engine: Box<rhai::Engine>, ast: rhai::AST },
+    Wasm(WasmModule),
+}
+
+#[cfg(feature = "scripting")]
+impl PostProcessor {
+    /// Compile the script, or load and check the module, within `sandbox`
+    pub fn new(post_process: &PostProcess, sandbox: &ScriptSandbox) -> Result<Self> {
+        let limits = sandbox.limits(post_process.limits());
+        let memory_bytes = limits.max_memory_mb as usize * 1024 * 1024;
+        let script = match post_process {
+            PostProcess::Rhai { script, .. } => {
+                let engine = rhai_engine(limits.max_operations, memory_bytes);
+                let ast = engine.compile(script)
+                    .map_err(|e| anyhow::anyhow!("post_process script does not compile: {}", e))?;
+                Script::Rhai { engine: Box::new(engine), ast }
+            }
+            PostProcess::Wasm { module, .. } => {
+                let path = sandbox.module_path(module)?;
+                let wasm = std::fs::read(&path)
+                    .map_err(|e| anyhow::anyhow!("Cannot read post_process module {}: {}", module.display(), e))?;
+                let module = WasmModule::new(&wasm, SandboxLimits { fuel: limits.max_operations, memory_bytes })?;
+                if !module.has_function("post_process") {
+                    return Err(anyhow::anyhow!("post_process module does not export a post_process function"));
+                }
+                Script::Wasm(module)
+            }
+        };
+        Ok(Self { script })
+    }
+
+    /// Run the script on one item; `None` when the script drops it
+    pub fn apply(&self, item: serde_json::Value) -> Result<Option<serde_json::Value>> {
+        match &self.script {
+            Script::Rhai { engine, ast } => {
+                let item = rhai::serde::to_dynamic(item).map_err(|e| anyhow::anyhow!("{}", e))?;
+                let mut scope = rhai::Scope::new();
+                scope.push("item", item);
+                let result = engine.eval_ast_with_scope::<rhai::Dynamic>(&mut scope, ast)
+                    .map_err(|e| anyhow::anyhow!("post_process script failed: {}", e))?;
+
+                if result.as_bool() == Ok(false) {
+                    return Ok(None);
+                }
+                let item = if result.is_map() {
+                    result
+                } else {
+                    scope.get_value::<rhai::Dynamic>("item").unwrap_or_default()
+                };
+                let item: serde_json::Value = rhai::serde::from_dynamic(&item).map_err(|e| anyhow::anyhow!("{}", e))?;
+                if !item.is_object() {
+                    return Err(anyhow::anyhow!("post_process script left item as {} instead of a map", item));
+                }
+                Ok(Some(item))
+            }
+            Script::Wasm(module) => match module.call_json("post_process", &item)? {
+                serde_json::Value::Bool(false) => Ok(None),
+                item @ serde_json::Value::Object(_) => Ok(Some(item)),
+                other => Err(anyhow::anyhow!("post_process returned {} instead of an item or false", other)),
+            },
+        }
+    }
+}
+
+/// Rhai engine without file access, limited like a WebAssembly sandbox
+///
+/// Rhai has no memory cap as such, so strings are capped at the memory limit
+/// and arrays and maps at one element per kilobyte of it.
+#[cfg(feature = "scripting")]
+fn rhai_engine(max_operations: u64, memory_bytes: usize) -> rhai::Engine {
+    let mut engine = rhai::Engine::new();
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.set_max_modules(0);
+    engine.disable_symbol("eval");
+    engine.set_max_operations(max_operations);
+    engine.set_max_string_size(memory_bytes);
+    engine.set_max_array_size(memory_bytes / 1024);
+    engine.set_max_map_size(memory_bytes / 1024);
+    engine.set_max_call_levels(32);
+    engine.on_print(|text| debug!("post_process: {}", text));
+    engine.on_debug(|text, _, _| debug!("post_process: {}", text));
+    engine
+}
+
+// Stub implementation when scripting feature is disabled
+#[cfg(not(feature = "scripting"))]
+pub struct PostProcessor;
+
+#[cfg(not(feature = "scripting"))]
+impl PostProcessor {
+    pub fn new(_post_process: &PostProcess, _sandbox: &ScriptSandbox) -> Result<Self> {
+        Err(anyhow::anyhow!("post_process scripts need the scripting feature"))
+    }
+
+    pub fn apply(&self, item: serde_json::Value) -> Result<Option<serde_json::Value>> {
+        Ok(Some(item))
+    }
+}
+
+#[cfg(all(test, feature = "scripting"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rhai(script: &str, max_operations: u64) -> PostProcessor {
+        PostProcessor::new(&PostProcess::Rhai {
+            script: script.to_string(),
+            limits: ScriptLimits { max_operations, ..Default::default() },
+        }, &ScriptSandbox::default()).unwrap()
+    }
+
+    #[test]
+    fn test_rhai_script_changes_and_drops_items() {
+        let script = rhai(r#"
+            if item.price < 10 { return false; }
+            item.label = `${item.name} (${item.price})`;
+        "#, 10_000);
+        let kept = script.apply(json!({ "name": "Lamp", "price": 25 })).unwrap().unwrap();
+        assert_eq!(kept["label"], "Lamp (25)");
+        assert_eq!(script.apply(json!({ "name": "Pen", "price": 2 })).unwrap(), None);
+
+        let endless = rhai("loop { }", 1_000);
+        assert!(endless.apply(json!({})).is_err());
+    }
+
+    #[test]
+    fn test_modules_come_from_the_plugins_directory_only() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("outside.wasm"), b"\0asm").unwrap();
+        let sandbox = ScriptSandbox {
+            modules_dir: dir.path().join("plugins"),
+            max_operations: 5_000,
+            max_memory_mb: 8,
+        };
+        std::fs::create_dir(&sandbox.modules_dir).unwrap();
+        std::fs::write(sandbox.modules_dir.join("clean.wasm"), b"\0asm").unwrap();
+
+        assert_eq!(sandbox.module_path(Path::new("clean.wasm")).unwrap(), sandbox.modules_dir.join("clean.wasm"));
+        assert!(sandbox.module_path(Path::new("../outside.wasm")).is_err());
+        assert!(sandbox.module_path(&dir.path().join("outside.wasm")).is_err());
+        assert!(sandbox.module_path(Path::new("missing.wasm")).is_err());
+
+        let limits = sandbox.limits(&ScriptLimits { max_operations: u64::MAX, max_memory_mb: 1024 });
+        assert_eq!(limits, ScriptLimits { max_operations: 5_000, max_memory_mb: 8 });
+
+        let endless = PostProcessor::new(&PostProcess::Rhai {
+            script: "loop { }".to_string(),
+            limits: ScriptLimits { max_operations: u64::MAX, ..Default::default() },
+        }, &sandbox).unwrap();
+        assert!(endless.apply(json!({})).is_err());
+    }
+}
@@ -81,6 +81,11 @@ impl RateLimiter {
         limiter.delay = delay;
     }
     
+    /// Gap kept between requests to a domain without a delay of its own
+    pub fn default_delay(&self) -> Duration {
+        self.default_delay
+    }
+    
     /// Get current request count for domain in current window
     pub async fn get_domain_request_count(&self, domain: &str) -> usize {
         let limits = self.domain_limits.read().await;
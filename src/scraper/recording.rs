@@ -0,0 +1,435 @@
+use anyhow::Result;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+use url::Url;
+
+/// Plan metadata key naming the HAR file a job saves its requests and responses to, set by [`RecordingPaths`]
+pub const RECORD_METADATA_KEY: &str = "record_har";
+
+/// Plan metadata key naming the HAR file a job reads its responses from instead of the network, set by [`RecordingPaths`]
+pub const REPLAY_METADATA_KEY: &str = "replay_har";
+
+/// HAR files a job saves its responses to or answers them from
+///
+/// Only the command line sets these. Plans submitted any other way lose the
+/// metadata keys naming them, so API callers can't have a job read or write
+/// files of their choosing.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingPaths {
+    pub record: Option<String>,
+    pub replay: Option<String>,
+}
+
+impl RecordingPaths {
+    /// Set the plan's recording metadata to these paths, dropping whatever it named before
+    pub fn apply(&self, plan: &mut crate::dsl::ScrapePlan) {
+        if let Some(metadata) = plan.metadata.as_mut() {
+            metadata.remove(RECORD_METADATA_KEY);
+            metadata.remove(REPLAY_METADATA_KEY);
+        }
+        if let Some(path) = &self.record {
+            plan.add_metadata(RECORD_METADATA_KEY.to_string(), serde_json::Value::String(path.clone()));
+        }
+        if let Some(path) = &self.replay {
+            plan.add_metadata(REPLAY_METADATA_KEY.to_string(), serde_json::Value::String(path.clone()));
+        }
+    }
+}
+
+/// Redirects followed inside a recording before giving up
+const MAX_REPLAY_REDIRECTS: usize = 10;
+
+/// One page request and the response it got, as the engine sees it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    pub url: String,
+    pub request_headers: Vec<(String, String)>,
+    pub status: u16,
+    pub response_headers: Vec<(String, String)>,
+    /// Body decoded to text
+    pub body: String,
+    /// Where the request ended up after redirects
+    pub final_url: String,
+    /// URLs redirected from, starting with `url`
+    pub redirects: Vec<String>,
+    pub time_ms: u64,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Exchange {
+    fn content_type(&self) -> &str {
+        self.response_headers.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map_or("", |(_, value)| value.as_str())
+    }
+}
+
+/// HTTP Archive (HAR 1.2) file, the format browser developer tools save
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+impl Default for HarLog {
+    fn default() -> Self {
+        Self {
+            version: "1.2".to_string(),
+            creator: HarCreator {
+                name: "WinScrape Studio".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            entries: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    /// Milliseconds until the response was complete
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: serde_json::Value,
+    #[serde(default)]
+    pub timings: serde_json::Value,
+    /// URLs redirected from before this response, when the entry stands for the whole chain
+    #[serde(rename = "_redirects", default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub headers: Vec<HarHeader>,
+    #[serde(default)]
+    pub query_string: Vec<serde_json::Value>,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub headers: Vec<HarHeader>,
+    pub content: HarContent,
+    #[serde(default, rename = "redirectURL")]
+    pub redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// `base64` when `text` holds the raw body encoded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarHeader {
+    pub name: String,
+    pub value: String,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+fn har_headers(headers: &[(String, String)]) -> Vec<HarHeader> {
+    headers.iter()
+        .map(|(name, value)| HarHeader { name: name.clone(), value: value.clone() })
+        .collect()
+}
+
+impl From<&Exchange> for HarEntry {
+    fn from(exchange: &Exchange) -> Self {
+        let status_text = reqwest::StatusCode::from_u16(exchange.status).ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or_default();
+        Self {
+            started_date_time: exchange.started_at.to_rfc3339(),
+            time: exchange.time_ms as f64,
+            request: HarRequest {
+                method: "GET".to_string(),
+                url: exchange.url.clone(),
+                http_version: "HTTP/1.1".to_string(),
+                cookies: Vec::new(),
+                headers: har_headers(&exchange.request_headers),
+                query_string: Vec::new(),
+                headers_size: -1,
+                body_size: 0,
+            },
+            response: HarResponse {
+                status: exchange.status,
+                status_text: status_text.to_string(),
+                http_version: "HTTP/1.1".to_string(),
+                cookies: Vec::new(),
+                headers: har_headers(&exchange.response_headers),
+                content: HarContent {
+                    size: exchange.body.len() as i64,
+                    mime_type: exchange.content_type().to_string(),
+                    text: Some(exchange.body.clone()),
+                    encoding: None,
+                },
+                redirect_url: if exchange.redirects.is_empty() { String::new() } else { exchange.final_url.clone() },
+                headers_size: -1,
+                body_size: exchange.body.len() as i64,
+            },
+            cache: serde_json::json!({}),
+            timings: serde_json::json!({ "send": 0, "wait": exchange.time_ms, "receive": 0 }),
+            redirects: exchange.redirects.clone(),
+        }
+    }
+}
+
+/// Collects the exchanges of a run, to be saved as a HAR file
+#[derive(Default)]
+pub struct Recorder {
+    entries: Mutex<Vec<HarEntry>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, exchange: &Exchange) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).push(exchange.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Everything recorded so far, in the order responses came in
+    pub fn to_har(&self) -> Har {
+        let mut har = Har::default();
+        har.log.entries = self.entries.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        har
+    }
+
+    /// Write everything recorded so far to `path`
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(path, serde_json::to_vec_pretty(&self.to_har())?).await?;
+        Ok(())
+    }
+}
+
+/// Responses of a recorded run, answered in place of the network
+///
+/// Reads HAR files saved by a recording run as well as those exported by
+/// browser developer tools.
+pub struct Recording {
+    har: Har,
+}
+
+impl Recording {
+    pub fn new(har: Har) -> Self {
+        Self { har }
+    }
+
+    pub async fn load(path: &Path) -> Result<Self> {
+        let bytes = tokio::fs::read(path).await
+            .map_err(|e| anyhow::anyhow!("Cannot read recording {}: {}", path.display(), e))?;
+        let har = serde_json::from_slice(&bytes)
+            .map_err(|e| anyhow::anyhow!("{} is not a HAR file: {}", path.display(), e))?;
+        Ok(Self::new(har))
+    }
+
+    pub fn len(&self) -> usize {
+        self.har.log.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.har.log.entries.is_empty()
+    }
+
+    /// The recorded answer to a GET of `url`, following redirects recorded as separate entries
+    pub fn replay(&self, url: &Url) -> Result<Exchange> {
+        let mut current = url.to_string();
+        let mut redirects = Vec::new();
+        loop {
+            let entry = self.entry(&current)
+                .ok_or_else(|| anyhow::anyhow!("No recorded response for {}", current))?;
+            let response = &entry.response;
+            let is_redirect = (300..400).contains(&response.status) && !response.redirect_url.is_empty();
+            if is_redirect && entry.redirects.is_empty() {
+                if redirects.len() >= MAX_REPLAY_REDIRECTS {
+                    return Err(anyhow::anyhow!("more than {} redirects in the recording for {}", MAX_REPLAY_REDIRECTS, url));
+                }
+                let next = Url::parse(&current)?.join(&response.redirect_url)?.to_string();
+                redirects.push(std::mem::replace(&mut current, next));
+                continue;
+            }
+
+            if !entry.redirects.is_empty() {
+                redirects.extend(entry.redirects.iter().cloned());
+                current = response.redirect_url.clone();
+            }
+            let headers = |headers: &[HarHeader]| headers.iter().map(|h| (h.name.clone(), h.value.clone())).collect();
+            return Ok(Exchange {
+                url: url.to_string(),
+                request_headers: headers(&entry.request.headers),
+                status: response.status,
+                response_headers: headers(&response.headers),
+                body: decode_content(&response.content)?,
+                final_url: current,
+                redirects,
+                time_ms: entry.time.max(0.0) as u64,
+                started_at: chrono::DateTime::parse_from_rfc3339(&entry.started_date_time)
+                    .map_or_else(|_| chrono::Utc::now(), |at| at.with_timezone(&chrono::Utc)),
+            });
+        }
+    }
+
+    /// Last GET recorded for `url`
+    fn entry(&self, url: &str) -> Option<&HarEntry> {
+        self.har.log.entries.iter()
+            .rev()
+            .find(|entry| entry.request.method.eq_ignore_ascii_case("GET") && entry.request.url == url)
+    }
+}
+
+/// Body text of a HAR entry, decoding base64 content with its declared charset
+fn decode_content(content: &HarContent) -> Result<String> {
+    let text = content.text.as_deref().unwrap_or_default();
+    if content.encoding.as_deref() != Some("base64") {
+        return Ok(text.to_string());
+    }
+    let bytes = base64::engine::general_purpose::STANDARD.decode(text.trim())
+        .map_err(|e| anyhow::anyhow!("Recorded body is not valid base64: {}", e))?;
+    Ok(super::charset::decode(&bytes, Some(&content.mime_type)).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(url: &str, body: &str) -> Exchange {
+        Exchange {
+            url: url.to_string(),
+            request_headers: vec![("User-Agent".to_string(), "test".to_string())],
+            status: 200,
+            response_headers: vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())],
+            body: body.to_string(),
+            final_url: url.to_string(),
+            redirects: Vec::new(),
+            time_ms: 120,
+            started_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_recorded_exchanges_replay_from_har() {
+        let recorder = Recorder::new();
+        recorder.record(&exchange("https://example.com/a", "<p>A</p>"));
+        recorder.record(&Exchange {
+            final_url: "https://example.com/b2".to_string(),
+            redirects: vec!["https://example.com/b".to_string()],
+            ..exchange("https://example.com/b", "<p>B</p>")
+        });
+
+        let json = serde_json::to_string(&recorder.to_har()).unwrap();
+        assert!(json.contains("\"startedDateTime\""));
+        let recording = Recording::new(serde_json::from_str(&json).unwrap());
+
+        let a = recording.replay(&Url::parse("https://example.com/a").unwrap()).unwrap();
+        assert_eq!((a.status, a.body.as_str(), a.time_ms), (200, "<p>A</p>", 120));
+        let b = recording.replay(&Url::parse("https://example.com/b").unwrap()).unwrap();
+        assert_eq!(b.final_url, "https://example.com/b2");
+        assert_eq!(b.redirects, vec!["https://example.com/b"]);
+        assert!(recording.replay(&Url::parse("https://example.com/c").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_submitted_plans_cannot_name_recording_files() {
+        let mut plan = crate::dsl::ScrapePlan::default();
+        plan.add_metadata(RECORD_METADATA_KEY.to_string(), serde_json::json!("/etc/cron.d/job"));
+        plan.add_metadata(REPLAY_METADATA_KEY.to_string(), serde_json::json!("/etc/shadow"));
+        plan.add_metadata("category".to_string(), serde_json::json!("products"));
+
+        // What jobs created from the API, schedules and saved plans get
+        RecordingPaths::default().apply(&mut plan);
+        assert_eq!(plan.get_metadata(RECORD_METADATA_KEY), None);
+        assert_eq!(plan.get_metadata(REPLAY_METADATA_KEY), None);
+        assert_eq!(plan.get_metadata("category"), Some(&serde_json::json!("products")));
+
+        let cli = RecordingPaths { record: Some("run.har".to_string()), replay: None };
+        cli.apply(&mut plan);
+        assert_eq!(plan.get_metadata(RECORD_METADATA_KEY), Some(&serde_json::json!("run.har")));
+        assert_eq!(plan.get_metadata(REPLAY_METADATA_KEY), None);
+    }
+
+    #[test]
+    fn test_browser_har_redirect_entries_and_base64_bodies() {
+        let entry = |url: &str, status: u16, redirect: &str, content: serde_json::Value| serde_json::json!({
+            "startedDateTime": "2024-05-01T10:00:00.000Z",
+            "time": 35.5,
+            "request": { "method": "GET", "url": url, "headers": [] },
+            "response": { "status": status, "headers": [], "content": content, "redirectURL": redirect },
+        });
+        let har = serde_json::json!({ "log": { "version": "1.2", "creator": { "name": "browser", "version": "1" }, "entries": [
+            entry("http://example.com/", 301, "https://example.com/", serde_json::json!({ "size": 0, "mimeType": "" })),
+            entry("https://example.com/", 200, "", serde_json::json!({
+                "size": 9, "mimeType": "text/html", "text": "PHA+T0s8L3A+", "encoding": "base64"
+            })),
+        ] } });
+        let recording = Recording::new(serde_json::from_value(har).unwrap());
+
+        let page = recording.replay(&Url::parse("http://example.com/").unwrap()).unwrap();
+        assert_eq!(page.body, "<p>OK</p>");
+        assert_eq!(page.final_url, "https://example.com/");
+        assert_eq!(page.redirects, vec!["http://example.com/"]);
+    }
+}
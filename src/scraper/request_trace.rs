@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Status codes sites typically answer with when they block a client
+const BLOCKED_STATUS_CODES: &[u16] = &[403, 429];
+
+/// Anti-blocking choices and outcome for one target URL
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestTrace {
+    pub url: String,
+    pub user_agent: String,
+    /// Randomized delay slept before the request
+    pub delay_ms: u64,
+    /// Time spent waiting for the per-domain rate limiter
+    pub rate_limit_wait_ms: u64,
+    /// Time spent waiting for the host's circuit breaker to let requests through again
+    #[serde(default)]
+    pub circuit_wait_ms: u64,
+    /// Why this request opened the host's circuit breaker, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_opened: Option<String>,
+    /// Proxy the request went through; `None` for a direct connection
+    pub proxy: Option<String>,
+    /// `http` or `browser`, whichever produced the page
+    pub method: Option<String>,
+    pub status_code: Option<u16>,
+    pub response_time_ms: Option<u64>,
+    pub error: Option<String>,
+    /// Where the request ended up, when it was redirected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_url: Option<String>,
+    /// URLs redirected from, starting with the one requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<String>,
+    /// Why the response was left unread, e.g. too large or not a page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+    /// Robots directives the page carried, e.g. `noindex, nofollow`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub robots_meta: Option<String>,
+}
+
+impl RequestTrace {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether the site appears to have refused the request
+    pub fn is_blocked(&self) -> bool {
+        self.status_code.is_some_and(|code| BLOCKED_STATUS_CODES.contains(&code))
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some() || self.status_code.is_some_and(|code| code >= 400)
+    }
+}
+
+/// Outcomes of all requests sharing one user agent or proxy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestStats {
+    /// `user_agent` or `proxy`
+    pub dimension: String,
+    pub value: String,
+    pub requests: usize,
+    pub failures: usize,
+    pub blocked: usize,
+    pub avg_delay_ms: f64,
+    pub avg_rate_limit_wait_ms: f64,
+}
+
+impl RequestStats {
+    /// Share of requests that were blocked, in percent
+    pub fn blocked_percent(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.blocked as f64 * 100.0 / self.requests as f64
+        }
+    }
+}
+
+/// Group traces by user agent and by proxy, most used first within each dimension
+pub fn aggregate(traces: &[RequestTrace]) -> Vec<RequestStats> {
+    let mut stats = group(traces, "user_agent", |t| t.user_agent.clone());
+    stats.extend(group(traces, "proxy", |t| t.proxy.clone().unwrap_or_else(|| "direct".to_string())));
+    stats
+}
+
+fn group(
+    traces: &[RequestTrace],
+    dimension: &str,
+    key: impl Fn(&RequestTrace) -> String,
+) -> Vec<RequestStats> {
+    let mut groups: BTreeMap<String, Vec<&RequestTrace>> = BTreeMap::new();
+    for trace in traces {
+        groups.entry(key(trace)).or_default().push(trace);
+    }
+
+    let mut stats: Vec<RequestStats> = groups.into_iter()
+        .map(|(value, traces)| {
+            let count = traces.len() as f64;
+            RequestStats {
+                dimension: dimension.to_string(),
+                value,
+                requests: traces.len(),
+                failures: traces.iter().filter(|t| t.is_failure()).count(),
+                blocked: traces.iter().filter(|t| t.is_blocked()).count(),
+                avg_delay_ms: traces.iter().map(|t| t.delay_ms as f64).sum::<f64>() / count,
+                avg_rate_limit_wait_ms: traces.iter().map(|t| t.rate_limit_wait_ms as f64).sum::<f64>() / count,
+            }
+        })
+        .collect();
+
+    stats.sort_by_key(|s| std::cmp::Reverse(s.requests));
+    stats
+}
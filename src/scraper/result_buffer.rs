@@ -0,0 +1,223 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::dsl::{DuplicatePolicy, Output};
+use crate::storage::content_hash;
+
+/// Collects extracted rows and applies the plan's duplicate policy
+///
+/// Rows are identified by the values of `output.dedupe_keys`; without dedupe
+/// keys every row is kept.
+pub struct ResultBuffer {
+    dedupe_keys: Option<Vec<String>>,
+    policy: DuplicatePolicy,
+    rows: Vec<serde_json::Value>,
+    /// Dedupe key of each kept row mapped to its position in `rows`
+    index: HashMap<String, usize>,
+    duplicates: usize,
+    /// Fields identifying a row across runs; all non-metadata fields when unset
+    seen_fields: Option<Vec<String>>,
+    /// Hashes of rows stored by earlier runs, which are dropped
+    seen: HashSet<String>,
+    previously_seen: usize,
+}
+
+impl ResultBuffer {
+    pub fn new(output: &Output) -> Self {
+        Self {
+            dedupe_keys: output.dedupe_keys.clone().filter(|keys| !keys.is_empty()),
+            policy: output.duplicate_policy,
+            rows: Vec::new(),
+            index: HashMap::new(),
+            duplicates: 0,
+            seen_fields: seen_fields(output),
+            seen: HashSet::new(),
+            previously_seen: 0,
+        }
+    }
+
+    /// Drop rows whose [`seen_hash`] is in `seen`
+    pub fn with_seen(mut self, seen: HashSet<String>) -> Self {
+        self.seen = seen;
+        self
+    }
+
+    /// Add an extracted row, returning whether it was appended rather than merged
+    ///
+    /// Kept rows never move, so a row's position is stable once appended.
+    pub fn push(&mut self, row: serde_json::Value) -> bool {
+        if !self.seen.is_empty() && self.seen.contains(&content_hash(&row, self.seen_fields.as_deref())) {
+            self.previously_seen += 1;
+            return false;
+        }
+        
+        let key = match self.dedupe_key(&row) {
+            Some(key) => key,
+            None => {
+                self.rows.push(row);
+                return true;
+            }
+        };
+
+        match self.index.get(&key) {
+            None => {
+                self.index.insert(key, self.rows.len());
+                self.rows.push(row);
+                true
+            }
+            Some(&pos) => {
+                self.duplicates += 1;
+                match self.policy {
+                    DuplicatePolicy::Skip => {}
+                    DuplicatePolicy::KeepFirst => fill_missing(&mut self.rows[pos], row),
+                    DuplicatePolicy::KeepLast => self.rows[pos] = row,
+                }
+                false
+            }
+        }
+    }
+
+    /// Add several extracted rows in order
+    pub fn extend(&mut self, rows: impl IntoIterator<Item = serde_json::Value>) {
+        for row in rows {
+            self.push(row);
+        }
+    }
+
+    /// Number of rows kept so far
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Rows kept so far, in order
+    pub fn rows(&self) -> &[serde_json::Value] {
+        &self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Number of duplicate rows seen so far
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+
+    /// Number of rows dropped because an earlier run stored them
+    pub fn previously_seen(&self) -> usize {
+        self.previously_seen
+    }
+
+    pub fn into_rows(self) -> Vec<serde_json::Value> {
+        self.rows
+    }
+
+    /// Build the dedupe key of a row; rows missing every key field are never duplicates
+    fn dedupe_key(&self, row: &serde_json::Value) -> Option<String> {
+        let keys = self.dedupe_keys.as_ref()?;
+        let values: Vec<&serde_json::Value> = keys.iter()
+            .map(|k| row.get(k).unwrap_or(&serde_json::Value::Null))
+            .collect();
+
+        if values.iter().all(|v| is_empty_value(v)) {
+            return None;
+        }
+
+        serde_json::to_string(&values).ok()
+    }
+}
+
+/// Hash recognizing a row in later runs of a plan with `output`
+pub fn seen_hash(output: &Output, row: &serde_json::Value) -> String {
+    content_hash(row, seen_fields(output).as_deref())
+}
+
+fn seen_fields(output: &Output) -> Option<Vec<String>> {
+    output.dedupe_keys.clone()
+        .filter(|keys| !keys.is_empty())
+        .or_else(|| output.hash_fields.clone())
+}
+
+fn is_empty_value(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::String(s) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Fill null or empty fields of `target` with values from `source`
+fn fill_missing(target: &mut serde_json::Value, source: serde_json::Value) {
+    if let (Some(target), serde_json::Value::Object(source)) = (target.as_object_mut(), source) {
+        for (key, value) in source {
+            let missing = target.get(&key).map_or(true, is_empty_value);
+            if missing && !is_empty_value(&value) {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::DSLExamples;
+    use serde_json::json;
+
+    fn buffer(policy: DuplicatePolicy) -> ResultBuffer {
+        let mut output = DSLExamples::ecommerce_products().output;
+        output.dedupe_keys = Some(vec!["url".to_string()]);
+        output.duplicate_policy = policy;
+        ResultBuffer::new(&output)
+    }
+
+    fn rows() -> Vec<serde_json::Value> {
+        vec![
+            json!({"url": "a", "price": null, "title": "first"}),
+            json!({"url": "b", "price": 2, "title": "other"}),
+            json!({"url": "a", "price": 1, "title": "second"}),
+        ]
+    }
+
+    #[test]
+    fn test_skip_drops_duplicates() {
+        let mut buffer = buffer(DuplicatePolicy::Skip);
+        buffer.extend(rows());
+
+        assert_eq!(buffer.duplicates(), 1);
+        assert_eq!(buffer.into_rows()[0], json!({"url": "a", "price": null, "title": "first"}));
+    }
+
+    #[test]
+    fn test_keep_first_fills_missing_fields() {
+        let mut buffer = buffer(DuplicatePolicy::KeepFirst);
+        buffer.extend(rows());
+
+        let rows = buffer.into_rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], json!({"url": "a", "price": 1, "title": "first"}));
+    }
+
+    #[test]
+    fn test_keep_last_overwrites_in_place() {
+        let mut buffer = buffer(DuplicatePolicy::KeepLast);
+        buffer.extend(rows());
+
+        let rows = buffer.into_rows();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["title"], "second");
+        assert_eq!(rows[1]["url"], "b");
+    }
+
+    #[test]
+    fn test_rows_seen_by_earlier_runs_are_dropped() {
+        let mut output = DSLExamples::ecommerce_products().output;
+        output.dedupe_keys = Some(vec!["url".to_string()]);
+        let seen = HashSet::from([seen_hash(&output, &json!({"url": "a", "title": "old"}))]);
+        let mut buffer = ResultBuffer::new(&output).with_seen(seen);
+        buffer.extend(rows());
+
+        assert_eq!(buffer.previously_seen(), 2);
+        assert_eq!(buffer.duplicates(), 0);
+        assert_eq!(buffer.into_rows(), vec![json!({"url": "b", "price": 2, "title": "other"})]);
+    }
+}
@@ -1,23 +1,35 @@
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::debug;
+use tracing::{debug, warn};
 use url::Url;
 
 use super::http_client::HttpClient;
+use crate::config::RobotsCacheConfig;
+use crate::storage::cache::RobotsCache;
+
+/// Minutes before robots.txt is fetched again after a fetch failed
+const FAILED_FETCH_RETRY_MINUTES: i64 = 5;
 
 /// Robots.txt checker and parser
 pub struct RobotsChecker {
     http_client: Arc<HttpClient>,
     cache: Arc<RwLock<HashMap<String, RobotsRules>>>,
+    /// Robots.txt files kept across runs, if any
+    store: Option<Arc<RobotsCache>>,
+    cache_config: RobotsCacheConfig,
 }
 
 /// Parsed robots.txt rules for a domain
 #[derive(Debug, Clone)]
 pub struct RobotsRules {
     pub rules: Vec<RobotRule>,
-    pub cached_at: chrono::DateTime<chrono::Utc>,
+    /// When robots.txt was fetched
+    pub cached_at: DateTime<Utc>,
+    /// Used without fetching robots.txt again until then
+    pub fresh_until: DateTime<Utc>,
 }
 
 /// Individual robot rule
@@ -34,9 +46,23 @@ impl RobotsChecker {
         Self {
             http_client,
             cache: Arc::new(RwLock::new(HashMap::new())),
+            store: None,
+            cache_config: RobotsCacheConfig::default(),
         }
     }
     
+    /// Reuse fetched robots.txt files as long as `config` says
+    pub fn with_cache_config(mut self, config: RobotsCacheConfig) -> Self {
+        self.cache_config = config;
+        self
+    }
+    
+    /// Keep robots.txt files in `store`, so later runs and stale fallbacks can use them
+    pub fn with_store(mut self, store: Arc<RobotsCache>) -> Self {
+        self.store = Some(store);
+        self
+    }
+    
     /// Check if a URL is allowed for a given user agent
     pub async fn is_allowed(&self, url: &Url, user_agent: &str) -> Result<bool> {
         let domain = url.host_str().ok_or_else(|| anyhow::anyhow!("Invalid URL: no host"))?;
@@ -65,34 +91,79 @@ impl RobotsChecker {
     }
     
     /// Get robots rules for a domain (with caching)
+    ///
+    /// While robots.txt can't be fetched, a copy younger than `max_stale_hours`
+    /// is used instead; without one, everything is allowed. Either way robots.txt
+    /// is tried again a few minutes later.
     async fn get_robots_rules(&self, domain: &str) -> Result<RobotsRules> {
-        // Check cache first
-        {
-            let cache = self.cache.read().await;
-            if let Some(rules) = cache.get(domain) {
-                // Check if cache is still valid (24 hours)
-                let cache_age = chrono::Utc::now() - rules.cached_at;
-                if cache_age.num_hours() < 24 {
-                    debug!("Using cached robots.txt for domain: {}", domain);
-                    return Ok(rules.clone());
-                }
-            }
+        let now = Utc::now();
+        
+        // Check cache first, then the copy kept by earlier runs
+        let mut cached = self.cache.read().await.get(domain).cloned();
+        if cached.is_none() {
+            cached = self.load_stored(domain).await;
+        }
+        if let Some(rules) = cached.as_ref().filter(|rules| rules.fresh_until > now) {
+            debug!("Using cached robots.txt for domain: {}", domain);
+            self.cache.write().await.entry(domain.to_string()).or_insert_with(|| rules.clone());
+            return Ok(rules.clone());
         }
         
         // Fetch and parse robots.txt
         debug!("Fetching robots.txt for domain: {}", domain);
-        let robots_txt = self.http_client.get_robots_txt(domain, "WinScrape-Studio/1.0").await?;
-        let rules = self.parse_robots_txt(&robots_txt);
+        let retry_at = now + Duration::minutes(FAILED_FETCH_RETRY_MINUTES);
+        let max_stale = Duration::hours(self.cache_config.max_stale_hours as i64);
+        let rules = match self.http_client.get_robots_txt(domain, "WinScrape-Studio/1.0").await {
+            Ok(robots_txt) => {
+                self.save_stored(domain, &robots_txt).await;
+                self.parse_robots_txt(&robots_txt)
+            }
+            Err(e) => match cached.filter(|rules| now - rules.cached_at < max_stale) {
+                Some(stale) => {
+                    warn!("Could not fetch robots.txt for {} ({}); using the copy from {}", domain, e, stale.cached_at);
+                    RobotsRules { fresh_until: retry_at, ..stale }
+                }
+                None => {
+                    warn!("Could not fetch robots.txt for {} ({}); assuming everything is allowed", domain, e);
+                    RobotsRules { rules: Vec::new(), cached_at: now, fresh_until: retry_at }
+                }
+            },
+        };
         
         // Cache the rules
-        {
-            let mut cache = self.cache.write().await;
-            cache.insert(domain.to_string(), rules.clone());
-        }
-        
+        self.cache.write().await.insert(domain.to_string(), rules.clone());
         Ok(rules)
     }
     
+    /// Rules of the robots.txt an earlier run kept for a domain
+    async fn load_stored(&self, domain: &str) -> Option<RobotsRules> {
+        let store = self.store.as_ref()?;
+        match store.get_cached_robots(domain).await {
+            Ok(stored) => stored.map(|stored| RobotsRules {
+                cached_at: stored.cached_at,
+                fresh_until: stored.cached_at + self.ttl(),
+                ..self.parse_robots_txt(&stored.robots_txt)
+            }),
+            Err(e) => {
+                warn!("Failed to read cached robots.txt for {}: {}", domain, e);
+                None
+            }
+        }
+    }
+    
+    /// Keep a fetched robots.txt for later runs
+    async fn save_stored(&self, domain: &str, robots_txt: &str) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.cache_robots(domain, robots_txt.to_string()).await {
+                warn!("Failed to cache robots.txt for {}: {}", domain, e);
+            }
+        }
+    }
+    
+    fn ttl(&self) -> Duration {
+        Duration::hours(self.cache_config.ttl_hours as i64)
+    }
+    
     /// Parse robots.txt content
     fn parse_robots_txt(&self, content: &str) -> RobotsRules {
         let mut rules = Vec::new();
@@ -162,9 +233,11 @@ impl RobotsChecker {
             });
         }
         
+        let cached_at = Utc::now();
         RobotsRules {
             rules,
-            cached_at: chrono::Utc::now(),
+            cached_at,
+            fresh_until: cached_at + self.ttl(),
         }
     }
     
@@ -361,6 +434,53 @@ Crawl-delay: 2
         assert_eq!(second_rule.crawl_delay, Some(2));
     }
     
+    #[tokio::test]
+    async fn test_stale_rules_used_when_fetch_fails() {
+        use crate::config::ScrapingConfig;
+        
+        let config = ScrapingConfig { max_retries: 1, request_timeout_seconds: 2, ..Default::default() };
+        let checker = RobotsChecker::new(Arc::new(HttpClient::new(&config).await.unwrap()));
+        
+        // Nothing answers on port 443 here, so fetching robots.txt fails
+        let stale = RobotsRules {
+            cached_at: Utc::now() - Duration::hours(30),
+            fresh_until: Utc::now() - Duration::hours(6),
+            ..checker.parse_robots_txt("User-agent: *\nDisallow: /private/")
+        };
+        checker.cache.write().await.insert("127.0.0.1".to_string(), stale);
+        
+        let url = Url::parse("http://127.0.0.1/private/page").unwrap();
+        assert!(!checker.is_allowed(&url, "*").await.unwrap());
+        assert!(checker.cache.read().await["127.0.0.1"].fresh_until > Utc::now());
+        
+        // Too old to stand in for robots.txt any more
+        checker.cache.write().await.insert("127.0.0.1".to_string(), RobotsRules {
+            cached_at: Utc::now() - Duration::hours(200),
+            fresh_until: Utc::now() - Duration::hours(176),
+            ..checker.parse_robots_txt("User-agent: *\nDisallow: /private/")
+        });
+        assert!(checker.is_allowed(&url, "*").await.unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_stored_copy_outlives_the_engine() {
+        use crate::config::ScrapingConfig;
+        use crate::storage::test_storage;
+        
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(test_storage(dir.path()).await);
+        let store = Arc::new(RobotsCache::new(storage, 168));
+        store.cache_robots("127.0.0.1", "User-agent: *\nDisallow: /private/".to_string()).await.unwrap();
+        
+        // A checker of a later run finds the copy, outdated at once but still usable while the fetch fails
+        let config = ScrapingConfig { max_retries: 1, request_timeout_seconds: 2, ..Default::default() };
+        let checker = RobotsChecker::new(Arc::new(HttpClient::new(&config).await.unwrap()))
+            .with_cache_config(RobotsCacheConfig { ttl_hours: 0, max_stale_hours: 168 })
+            .with_store(store);
+        let url = Url::parse("http://127.0.0.1/private/page").unwrap();
+        assert!(!checker.is_allowed(&url, "*").await.unwrap());
+    }
+    
     #[tokio::test]
     async fn test_path_matching() {
         let http_client = create_mock_http_client().await.unwrap();
@@ -0,0 +1,108 @@
+use scraper::{ElementRef, Html, Selector};
+
+/// Item metadata field listing the link fields whose links are not to be followed
+pub const NOFOLLOW_FIELD: &str = "_nofollow";
+
+/// Page-level robots directives from `<meta name="robots">` tags and `X-Robots-Tag` headers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RobotsDirectives {
+    /// The page asks not to be indexed
+    pub noindex: bool,
+    /// The page asks that its links not be followed
+    pub nofollow: bool,
+}
+
+/// A page left unextracted because it is marked noindex
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("page is marked noindex by its {0}")]
+pub struct NoIndex(pub &'static str);
+
+impl RobotsDirectives {
+    /// Directives from the `X-Robots-Tag` headers among `headers`
+    ///
+    /// Header values addressed to a named crawler (`googlebot: noindex`) are ignored.
+    pub fn from_headers(headers: &[(String, String)]) -> Self {
+        let mut directives = Self::default();
+        for (_, value) in headers.iter().filter(|(name, _)| name.eq_ignore_ascii_case("x-robots-tag")) {
+            let addressed = value.split_once(':')
+                .is_some_and(|(agent, _)| !agent.contains(',') && !agent.trim().eq_ignore_ascii_case("unavailable_after"));
+            if !addressed {
+                directives.add(value);
+            }
+        }
+        directives
+    }
+
+    /// Directives from the page's `<meta name="robots">` tags
+    pub fn from_html(document: &Html) -> Self {
+        let mut directives = Self::default();
+        let selector = Selector::parse("meta[name][content]").expect("valid selector");
+        for meta in document.select(&selector) {
+            if meta.value().attr("name").is_some_and(|name| name.trim().eq_ignore_ascii_case("robots")) {
+                directives.add(meta.value().attr("content").unwrap_or_default());
+            }
+        }
+        directives
+    }
+
+    /// Directives of either source
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            noindex: self.noindex || other.noindex,
+            nofollow: self.nofollow || other.nofollow,
+        }
+    }
+
+    /// Add the directives in a comma-separated list
+    fn add(&mut self, list: &str) {
+        for directive in list.split(',').map(|directive| directive.trim().to_ascii_lowercase()) {
+            match directive.as_str() {
+                "noindex" => self.noindex = true,
+                "nofollow" => self.nofollow = true,
+                "none" => *self = Self { noindex: true, nofollow: true },
+                _ => {}
+            }
+        }
+    }
+
+    /// Directives as written in a tag, e.g. `noindex, nofollow`; `None` when there are none
+    pub fn describe(&self) -> Option<String> {
+        let names: Vec<&str> = [(self.noindex, "noindex"), (self.nofollow, "nofollow")]
+            .into_iter()
+            .filter_map(|(set, name)| set.then_some(name))
+            .collect();
+        (!names.is_empty()).then(|| names.join(", "))
+    }
+}
+
+/// Whether a link carries `rel="nofollow"`
+pub fn is_nofollow_link(link: &ElementRef<'_>) -> bool {
+    link.value().attr("rel")
+        .is_some_and(|rel| rel.split_whitespace().any(|value| value.eq_ignore_ascii_case("nofollow")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directives_from_meta_tags_and_headers() {
+        let page = Html::parse_document(
+            "<head><meta name=\"ROBOTS\" content=\"noindex, follow\"><meta name=\"googlebot\" content=\"nofollow\"></head>",
+        );
+        assert_eq!(RobotsDirectives::from_html(&page), RobotsDirectives { noindex: true, nofollow: false });
+
+        let headers = vec![
+            ("X-Robots-Tag".to_string(), "otherbot: noindex".to_string()),
+            ("x-robots-tag".to_string(), "nofollow, unavailable_after: 25 Jun 2030 15:00:00 PST".to_string()),
+            ("Content-Type".to_string(), "text/html".to_string()),
+        ];
+        let directives = RobotsDirectives::from_headers(&headers);
+        assert_eq!(directives, RobotsDirectives { noindex: false, nofollow: true });
+        assert_eq!(directives.merge(RobotsDirectives::from_html(&page)).describe().as_deref(), Some("noindex, nofollow"));
+
+        let none = vec![("X-Robots-Tag".to_string(), "none".to_string())];
+        assert_eq!(RobotsDirectives::from_headers(&none), RobotsDirectives { noindex: true, nofollow: true });
+        assert_eq!(RobotsDirectives::default().describe(), None);
+    }
+}
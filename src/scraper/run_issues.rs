@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Example URLs kept per issue group
+const EXAMPLE_URLS: usize = 5;
+
+/// What kind of problem a run ran into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// A selector matched nothing where something was required
+    SelectorMiss,
+    Timeout,
+    /// Refused by the site or by robots.txt
+    Blocked,
+    /// A selector, value or page could not be parsed
+    ParseError,
+    Network,
+    /// The post-process script failed on an item
+    Script,
+    /// Requests to a host were paused by its circuit breaker
+    CircuitOpen,
+    /// A response left unread for its size or content type, or a redirect the plan doesn't allow
+    Rejected,
+}
+
+impl IssueKind {
+    /// Judge the kind of a page failure from its message
+    pub fn classify(message: &str) -> Self {
+        let message = message.to_lowercase();
+        if message.contains("timed out") || message.contains("timeout") {
+            IssueKind::Timeout
+        } else if ["403", "401", "429", "forbidden", "too many requests", "captcha", "robots.txt", "blocked"]
+            .iter()
+            .any(|needle| message.contains(needle))
+        {
+            IssueKind::Blocked
+        } else if message.contains("selector") || message.contains("parse") || message.contains("invalid") {
+            IssueKind::ParseError
+        } else {
+            IssueKind::Network
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            IssueKind::SelectorMiss => "selector miss",
+            IssueKind::Timeout => "timeout",
+            IssueKind::Blocked => "blocked",
+            IssueKind::ParseError => "parse error",
+            IssueKind::Network => "network",
+            IssueKind::Script => "script",
+            IssueKind::CircuitOpen => "circuit open",
+            IssueKind::Rejected => "rejected",
+        }
+    }
+}
+
+impl std::fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Issues of one kind met at the same stage and field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueGroup {
+    pub kind: IssueKind,
+    /// Where in the run it happened, e.g. `fetching` or `extracting`
+    pub stage: String,
+    pub field: Option<String>,
+    pub count: usize,
+    /// First message seen
+    pub message: String,
+    /// A few of the URLs it happened on
+    pub urls: Vec<String>,
+}
+
+/// Problems a run met without failing, grouped so thousands of them stay readable
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueReport {
+    pub groups: Vec<IssueGroup>,
+}
+
+impl IssueReport {
+    /// Count an issue in its group
+    pub fn record(&mut self, kind: IssueKind, stage: &str, field: Option<&str>, url: Option<&str>, message: impl Into<String>) {
+        let index = self.groups.iter()
+            .position(|group| group.kind == kind && group.stage == stage && group.field.as_deref() == field)
+            .unwrap_or_else(|| {
+                self.groups.push(IssueGroup {
+                    kind,
+                    stage: stage.to_string(),
+                    field: field.map(str::to_string),
+                    count: 0,
+                    message: message.into(),
+                    urls: Vec::new(),
+                });
+                self.groups.len() - 1
+            });
+        let group = &mut self.groups[index];
+        group.count += 1;
+        if let Some(url) = url {
+            if group.urls.len() < EXAMPLE_URLS && !group.urls.iter().any(|known| known == url) {
+                group.urls.push(url.to_string());
+            }
+        }
+    }
+
+    /// Add the issues of another report, e.g. of one page
+    pub fn merge(&mut self, other: IssueReport) {
+        for theirs in other.groups {
+            match self.groups.iter_mut()
+                .find(|group| group.kind == theirs.kind && group.stage == theirs.stage && group.field == theirs.field)
+            {
+                Some(group) => {
+                    group.count += theirs.count;
+                    for url in theirs.urls {
+                        if group.urls.len() < EXAMPLE_URLS && !group.urls.contains(&url) {
+                            group.urls.push(url);
+                        }
+                    }
+                }
+                None => self.groups.push(theirs),
+            }
+        }
+    }
+
+    /// Issues of all groups together
+    pub fn total(&self) -> usize {
+        self.groups.iter().map(|group| group.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Issues per kind
+    pub fn counts(&self) -> BTreeMap<IssueKind, usize> {
+        let mut counts = BTreeMap::new();
+        for group in &self.groups {
+            *counts.entry(group.kind).or_default() += group.count;
+        }
+        counts
+    }
+
+    /// Groups with the most issues first
+    pub fn sorted(&self) -> Vec<&IssueGroup> {
+        let mut groups: Vec<&IssueGroup> = self.groups.iter().collect();
+        groups.sort_by_key(|group| std::cmp::Reverse(group.count));
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issues_are_grouped_by_kind_stage_and_field() {
+        assert_eq!(IssueKind::classify("operation timed out"), IssueKind::Timeout);
+        assert_eq!(IssueKind::classify("HTTP error: 429 Too Many Requests"), IssueKind::Blocked);
+        assert_eq!(IssueKind::classify("Invalid item selector: unexpected token"), IssueKind::ParseError);
+        assert_eq!(IssueKind::classify("connection refused"), IssueKind::Network);
+
+        let mut report = IssueReport::default();
+        for n in 0..8 {
+            let url = format!("https://example.com/{}", n);
+            report.record(IssueKind::SelectorMiss, "extracting", Some("price"), Some(&url), "Required field 'price' not found");
+        }
+        let mut page = IssueReport::default();
+        page.record(IssueKind::SelectorMiss, "extracting", Some("price"), Some("https://example.com/9"), "Required field 'price' not found");
+        page.record(IssueKind::Timeout, "fetching", None, Some("https://example.com/slow"), "timed out");
+        report.merge(page);
+
+        assert_eq!(report.total(), 10);
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.sorted()[0].count, 9);
+        assert_eq!(report.sorted()[0].urls.len(), EXAMPLE_URLS);
+        assert_eq!(report.counts()[&IssueKind::Timeout], 1);
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::info;
+
+use super::{ScrapingEngine, SkippedUrl};
+use crate::dsl::ScrapePlan;
+
+/// Assumed time to fetch and parse one page, for estimates
+const ASSUMED_FETCH_MS: u64 = 800;
+
+/// What a run of a plan would do, worked out without fetching any page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    /// URLs the run would fetch, in order
+    pub urls: Vec<String>,
+    /// URLs robots.txt would keep the run from fetching
+    pub skipped_urls: Vec<SkippedUrl>,
+    pub domains: Vec<DomainPlan>,
+    /// Concurrent requests after the politeness preset
+    pub concurrency: usize,
+    /// Mean of the plan's randomized delay
+    pub mean_delay_ms: u64,
+    /// Page requests of the first attempt, robots.txt not counted
+    pub requests: usize,
+    /// Requests if every retry the plan allows were needed
+    pub max_requests: usize,
+    /// Rough duration of the first attempt
+    pub estimated_duration_secs: u64,
+}
+
+/// Requests a run would make to one domain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DomainPlan {
+    pub domain: String,
+    pub requests: usize,
+    /// Minimum gap between requests to the domain
+    pub delay_ms: u64,
+    /// `Crawl-delay` of the domain's robots.txt, when honored
+    pub crawl_delay_secs: Option<u64>,
+}
+
+impl ScrapingEngine {
+    /// Work out the requests a run of the plan would make and how long it would take
+    ///
+    /// Only robots.txt files are fetched; every other figure comes from the
+    /// plan and the engine configuration.
+    pub async fn simulate(&self, plan: &ScrapePlan) -> Result<ExecutionPlan> {
+        let mut urls = plan.get_all_urls()?;
+        let mut skipped_urls = Vec::new();
+        let respect_robots = self.respects_robots(plan);
+        if respect_robots {
+            let (allowed, skipped) = self.filter_robots_disallowed(urls).await?;
+            urls = allowed;
+            skipped_urls = skipped;
+        }
+
+        let limits = plan.anti_blocking.politeness.limits();
+        let honor_crawl_delay = limits.honor_crawl_delay && respect_robots;
+        let mut per_domain: BTreeMap<String, usize> = BTreeMap::new();
+        for url in &urls {
+            *per_domain.entry(url.host_str().unwrap_or_default().to_string()).or_default() += 1;
+        }
+
+        let mut domains = Vec::with_capacity(per_domain.len());
+        for (domain, requests) in per_domain {
+            let crawl_delay_secs = if honor_crawl_delay {
+                self.robots_checker.get_crawl_delay(&domain, "*").await.ok().flatten()
+            } else {
+                None
+            };
+            let delay = crawl_delay_secs.map(Duration::from_secs).unwrap_or(self.rate_limiter.default_delay());
+            domains.push(DomainPlan {
+                domain,
+                requests,
+                delay_ms: delay.as_millis() as u64,
+                crawl_delay_secs,
+            });
+        }
+
+        let delays = &plan.anti_blocking.randomized_delays;
+        let mean_delay_ms = (delays.min_ms + delays.max_ms) / 2;
+        let concurrency = self.config().max_concurrent_requests.min(limits.max_concurrency).max(1);
+        let attempts = plan.retry.as_ref().map_or(1, |retry| retry.max_attempts.max(1) as usize);
+        let retries = self.config().max_retries.min(limits.max_retries);
+
+        let plan = ExecutionPlan {
+            requests: urls.len(),
+            max_requests: urls.len() * (retries + 1) * attempts,
+            estimated_duration_secs: estimate_duration(&domains, mean_delay_ms, concurrency).as_secs(),
+            urls: urls.into_iter().map(String::from).collect(),
+            skipped_urls,
+            domains,
+            concurrency,
+            mean_delay_ms,
+        };
+        info!(
+            "Simulated run: {} requests over {} domains in about {}s",
+            plan.requests, plan.domains.len(), plan.estimated_duration_secs
+        );
+        Ok(plan)
+    }
+}
+
+/// Rough time for the first attempt of a run
+///
+/// Domains are fetched side by side. Within one, requests are spaced by the
+/// domain delay, or by the plan delay and fetch time spread over the
+/// concurrent requests, whichever is slower.
+fn estimate_duration(domains: &[DomainPlan], mean_delay_ms: u64, concurrency: usize) -> Duration {
+    let per_request_ms = (mean_delay_ms + ASSUMED_FETCH_MS) / concurrency.max(1) as u64;
+    let slowest = domains.iter()
+        .map(|domain| domain.requests as u64 * domain.delay_ms.max(per_request_ms))
+        .max()
+        .unwrap_or_default();
+    Duration::from_millis(slowest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slowest_domain_sets_duration() {
+        let domain = |name: &str, requests: usize, delay_ms: u64| DomainPlan {
+            domain: name.to_string(),
+            requests,
+            delay_ms,
+            crawl_delay_secs: None,
+        };
+        let domains = vec![domain("a.example.com", 10, 1000), domain("b.example.com", 4, 10_000)];
+        assert_eq!(estimate_duration(&domains, 2000, 2), Duration::from_secs(40));
+        assert_eq!(estimate_duration(&domains[..1], 3200, 1), Duration::from_secs(40));
+        assert_eq!(estimate_duration(&[], 2000, 2), Duration::ZERO);
+    }
+}
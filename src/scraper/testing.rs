@@ -0,0 +1,373 @@
+//! Local HTTP server answering with fixed pages, for testing plans without real sites
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use winscrape_studio::scraper::testing::{Fixture, FixtureServer};
+//!
+//! let server = FixtureServer::new()
+//!     .with_fixture("/list?page=1", Fixture::html("<article><h2>Lamp</h2></article>"))
+//!     .with_fixture("/list?page=2", Fixture::html("").with_status(503))
+//!     .start()
+//!     .await?;
+//! let url = server.url("/list?page={page}");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Plans run against it need `respect_robots_txt` off, as robots.txt is
+//! fetched over HTTPS. The engine's own limits still apply: the per-domain
+//! rate limiter waits a second between requests and server errors are retried.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::debug;
+use url::Url;
+
+/// Longest request head read before the connection is dropped
+const MAX_REQUEST_HEAD: usize = 64 * 1024;
+
+/// The answer to requests for one path
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+    pub status: u16,
+    pub content_type: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Wait before answering
+    pub latency: Duration,
+    /// Requests answered with `failure_status` before the fixture itself is served
+    pub failures: usize,
+    pub failure_status: u16,
+}
+
+impl Fixture {
+    /// A 200 answer with an HTML page
+    pub fn html(body: impl Into<String>) -> Self {
+        Self::bytes("text/html; charset=utf-8", body.into().into_bytes())
+    }
+
+    /// A 200 answer with any content
+    pub fn bytes(content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: content_type.to_string(),
+            headers: Vec::new(),
+            body,
+            latency: Duration::ZERO,
+            failures: 0,
+            failure_status: 500,
+        }
+    }
+
+    /// The content of a file, its type judged from the extension
+    pub fn file(path: &Path) -> Result<Self> {
+        let body = std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read fixture {}: {}", path.display(), e))?;
+        Ok(Self::bytes(content_type_for(path), body))
+    }
+
+    /// A redirect to `location`
+    pub fn redirect(status: u16, location: &str) -> Self {
+        Self::html("").with_status(status).with_header("Location", location)
+    }
+
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Answer the first `times` requests with `status`, e.g. to test retries
+    pub fn failing_first(mut self, times: usize, status: u16) -> Self {
+        self.failures = times;
+        self.failure_status = status;
+        self
+    }
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("html" | "htm") => "text/html; charset=utf-8",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("txt") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Fixtures by path, served once [`start`](Self::start)ed
+///
+/// Paths may include a query string; a request whose path and query have no
+/// fixture falls back to the fixture for its path alone, and else gets a 404.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureServer {
+    fixtures: HashMap<String, Fixture>,
+}
+
+impl FixtureServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_fixture(mut self, path: &str, fixture: Fixture) -> Self {
+        self.fixtures.insert(path.to_string(), fixture);
+        self
+    }
+
+    /// Serve every file below `dir` at its relative path, `index.html` also at its directory
+    pub fn with_directory(mut self, dir: &Path) -> Result<Self> {
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let relative = path.strip_prefix(dir)?
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                let fixture = Fixture::file(&path)?;
+                if let Some(index_dir) = relative.strip_suffix("index.html") {
+                    self.fixtures.insert(format!("/{}", index_dir), fixture.clone());
+                }
+                self.fixtures.insert(format!("/{}", relative), fixture);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Listen on a free local port until the returned server is dropped
+    pub async fn start(self) -> Result<RunningFixtureServer> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(ServerState {
+            fixtures: self.fixtures,
+            requests: Mutex::new(Vec::new()),
+        });
+        debug!("Fixture server listening on {}", addr);
+
+        let serving = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serving.clone().answer(stream));
+            }
+        });
+        Ok(RunningFixtureServer { addr, state, task })
+    }
+}
+
+struct ServerState {
+    fixtures: HashMap<String, Fixture>,
+    /// Request targets in the order they came in
+    requests: Mutex<Vec<String>>,
+}
+
+impl ServerState {
+    async fn answer(self: Arc<Self>, mut stream: TcpStream) {
+        let Some(target) = read_request_target(&mut stream).await else {
+            return;
+        };
+        let (status, fixture) = {
+            let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+            requests.push(target.clone());
+            let path = target.split('?').next().unwrap_or_default();
+            match self.fixtures.get(&target).or_else(|| self.fixtures.get(path)) {
+                Some(fixture) => {
+                    let earlier = requests.iter().filter(|seen| **seen == target).count() - 1;
+                    let status = if earlier < fixture.failures { fixture.failure_status } else { fixture.status };
+                    (status, Some(fixture.clone()))
+                }
+                None => (404, None),
+            }
+        };
+
+        let fixture = fixture.unwrap_or_else(|| Fixture::html("<h1>Not found</h1>"));
+        tokio::time::sleep(fixture.latency).await;
+        let body: &[u8] = if status == fixture.status { &fixture.body } else { b"" };
+
+        let reason = reqwest::StatusCode::from_u16(status).ok()
+            .and_then(|code| code.canonical_reason())
+            .unwrap_or("Unknown");
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            status, reason, fixture.content_type, body.len()
+        );
+        for (name, value) in &fixture.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+
+        // The client hanging up early is its own business
+        if stream.write_all(head.as_bytes()).await.is_ok() {
+            let _ = stream.write_all(body).await;
+        }
+        let _ = stream.shutdown().await;
+    }
+}
+
+/// Target of the request line, e.g. `/list?page=1`, once the whole head has arrived
+async fn read_request_target(stream: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 4096];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 || head.len() > MAX_REQUEST_HEAD {
+            return None;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    head.lines().next()?.split_whitespace().nth(1).map(str::to_string)
+}
+
+/// A fixture server accepting requests, stopped when dropped
+pub struct RunningFixtureServer {
+    addr: SocketAddr,
+    state: Arc<ServerState>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl RunningFixtureServer {
+    /// Address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Full URL of a path on the server, kept as text so `{page}` placeholders survive
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+
+    /// Domain to put in a plan's `target.domain`
+    pub fn domain(&self) -> String {
+        self.addr.to_string()
+    }
+
+    /// Request targets received so far, in order
+    pub fn requests(&self) -> Vec<String> {
+        self.state.requests.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// How often `target` was requested
+    pub fn request_count(&self, target: &str) -> usize {
+        self.requests().iter().filter(|seen| *seen == target).count()
+    }
+
+    /// Parsed URL of a path on the server
+    pub fn parsed_url(&self, path: &str) -> Result<Url> {
+        Ok(Url::parse(&self.url(path))?)
+    }
+}
+
+impl Drop for RunningFixtureServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ScrapingConfig;
+    use crate::dsl::{DelayConfig, DelayDistribution, ScrapePlan};
+    use crate::scraper::ScrapingEngine;
+
+    fn listing(names: &[&str]) -> String {
+        let items: String = names.iter()
+            .map(|name| format!("<article><h2>{}</h2><a href=\"/item/{}\">more</a></article>", name, name))
+            .collect();
+        format!("<html><body>{}</body></html>", items)
+    }
+
+    #[tokio::test]
+    async fn test_plan_runs_against_fixtures() {
+        let server = FixtureServer::new()
+            .with_fixture("/list?page=1", Fixture::html(listing(&["lamp", "desk"])).with_latency(Duration::from_millis(50)))
+            .with_fixture("/list?page=2", Fixture::html(listing(&["chair"])).failing_first(1, 503))
+            .with_fixture("/list?page=3", Fixture::bytes("application/pdf", b"%PDF-1.4".to_vec()))
+            .start()
+            .await
+            .unwrap();
+
+        let mut plan = ScrapePlan::default();
+        plan.target.domain = server.domain();
+        plan.target.start_urls = Vec::new();
+        plan.target.url_patterns = Some(vec![server.url("/list?page={page}")]);
+        plan.target.max_pages = Some(4);
+        plan.anti_blocking.respect_robots_txt = false;
+        plan.anti_blocking.randomized_delays = DelayConfig { min_ms: 0, max_ms: 0, distribution: DelayDistribution::Uniform };
+
+        let config = ScrapingConfig { respect_robots_txt: false, max_concurrent_requests: 20, ..Default::default() };
+        let engine = ScrapingEngine::new(&config).await.unwrap();
+        let report = engine.execute_scraping(&plan).await.unwrap();
+
+        let titles: Vec<&str> = report.rows.iter().filter_map(|row| row["title"].as_str()).collect();
+        assert_eq!(titles, vec!["lamp", "desk", "chair"]);
+        assert_eq!(report.rows[0]["url"], server.url("/item/lamp"));
+        assert_eq!(server.request_count("/list?page=2"), 2);
+        // The PDF is left unread and the missing fourth page is a page without items
+        assert_eq!(report.summary.pages_skipped, 1);
+        assert_eq!(report.summary.requests[3].status_code, Some(404));
+    }
+
+    #[tokio::test]
+    async fn test_links_resolve_against_the_redirected_page() {
+        let server = FixtureServer::new()
+            .with_fixture("/old", Fixture::redirect(301, "/shop/list"))
+            .with_fixture("/shop/list", Fixture::html("<article><h2>lamp</h2><a href=\"item/lamp\">more</a></article>"))
+            .start()
+            .await
+            .unwrap();
+
+        let mut plan = ScrapePlan::default();
+        plan.target.domain = server.domain();
+        plan.target.start_urls = vec![server.url("/old")];
+        plan.anti_blocking.respect_robots_txt = false;
+        plan.anti_blocking.randomized_delays = DelayConfig { min_ms: 0, max_ms: 0, distribution: DelayDistribution::Uniform };
+
+        let config = ScrapingConfig { respect_robots_txt: false, ..Default::default() };
+        let engine = ScrapingEngine::new(&config).await.unwrap();
+        let report = engine.execute_scraping(&plan).await.unwrap();
+
+        assert_eq!(report.rows[0]["url"], server.url("/shop/item/lamp"));
+    }
+
+    #[tokio::test]
+    async fn test_directory_fixtures_and_redirects() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("shop")).unwrap();
+        std::fs::write(dir.path().join("shop").join("index.html"), "<p>shop</p>").unwrap();
+
+        let server = FixtureServer::new()
+            .with_directory(dir.path())
+            .unwrap()
+            .with_fixture("/old", Fixture::redirect(301, "/shop/"))
+            .start()
+            .await
+            .unwrap();
+
+        let response = reqwest::get(server.url("/old")).await.unwrap();
+        assert_eq!(response.url().path(), "/shop/");
+        assert_eq!(response.text().await.unwrap(), "<p>shop</p>");
+        assert_eq!(server.requests(), vec!["/old", "/shop/"]);
+        assert_eq!(reqwest::get(server.url("/missing")).await.unwrap().status(), 404);
+    }
+}
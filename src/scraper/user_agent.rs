@@ -1,4 +1,5 @@
 use rand::seq::SliceRandom;
+use rand::Rng;
 use std::sync::Arc;
 
 /// User agent rotator for avoiding detection
@@ -21,8 +22,12 @@ impl UserAgentRotator {
     
     /// Get a random user agent
     pub fn get_random_user_agent(&self) -> &str {
-        let mut rng = rand::thread_rng();
-        self.user_agents.choose(&mut rng).unwrap()
+        self.choose_user_agent(&mut rand::thread_rng())
+    }
+    
+    /// Pick a user agent with the given random source, e.g. a seeded one
+    pub fn choose_user_agent<R: Rng + ?Sized>(&self, rng: &mut R) -> &str {
+        self.user_agents.choose(rng).unwrap()
     }
     
     /// Get user agent by index (for deterministic selection)
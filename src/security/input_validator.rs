@@ -326,6 +326,7 @@ mod tests {
             allowed_schemes: vec!["http".to_string(), "https".to_string()],
             enable_rate_limiting: true,
             rate_limit_requests_per_minute: 60,
+            audit_retention_days: None,
         }
     }
     
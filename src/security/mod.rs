@@ -10,6 +10,7 @@ pub mod domain_whitelist;
 
 use crate::config::SecurityConfig;
 use crate::dsl::ScrapePlan;
+use crate::logging::{AuditLogger, LogContext};
 
 /// Security manager for validating inputs and enforcing policies
 pub struct SecurityManager {
@@ -40,7 +41,13 @@ impl SecurityManager {
     }
     
     /// Validate user input for security issues
+    ///
+    /// Fails with a `SecurityError`, so callers can tell refusals from other errors.
     pub fn validate_input(&self, input: &str) -> Result<()> {
+        self.check_input(input).map_err(|e| audited(SecurityError::InputValidation(e.to_string()), None).into())
+    }
+    
+    fn check_input(&self, input: &str) -> Result<()> {
         debug!("Validating user input for security issues");
         
         // Check input length
@@ -68,34 +75,70 @@ impl SecurityManager {
     }
     
     /// Validate DSL for security compliance
+    ///
+    /// Fails with a `SecurityError`, so callers can tell refusals from other errors.
     pub fn validate_dsl(&self, dsl: &ScrapePlan) -> Result<()> {
+        self.check_dsl(dsl).map_err(|e| audited(e, Some(&dsl.target.domain)).into())
+    }
+    
+    fn check_dsl(&self, dsl: &ScrapePlan) -> Result<(), SecurityError> {
         debug!("Validating DSL for security compliance");
         
         // Check domain whitelist
-        self.domain_whitelist.validate_domain(&dsl.target.domain)?;
+        self.domain_whitelist.validate_domain(&dsl.target.domain)
+            .map_err(|e| SecurityError::DomainBlocked(e.to_string()))?;
         
         // Validate URLs
         for url_str in &dsl.target.start_urls {
-            self.validate_url(url_str)?;
+            self.validate_url(url_str).map_err(|e| SecurityError::UrlValidation(e.to_string()))?;
         }
         
         // Validate URL patterns if present
         if let Some(patterns) = &dsl.target.url_patterns {
             for pattern in patterns {
-                self.validate_url_pattern(pattern)?;
+                self.validate_url_pattern(pattern).map_err(|e| SecurityError::UrlValidation(e.to_string()))?;
             }
         }
         
         // Check selectors for dangerous content
-        self.validate_selectors(dsl)?;
+        self.validate_selectors(dsl).map_err(|e| SecurityError::SelectorValidation(e.to_string()))?;
         
         // Validate anti-blocking settings
-        self.validate_anti_blocking_settings(dsl)?;
+        self.validate_anti_blocking_settings(dsl).map_err(|e| SecurityError::HeaderValidation(e.to_string()))?;
         
         debug!("DSL security validation passed");
         Ok(())
     }
     
+    /// Check a URL the application is about to fetch outside of a plan
+    pub fn validate_target_url(&self, url_str: &str) -> Result<()> {
+        self.validate_input(url_str)?;
+        let url = Url::parse(url_str)?;
+        let host = url.host_str();
+        
+        self.validate_url(url_str)
+            .inspect_err(|e| audit("rejected_url", "high", &e.to_string(), host))?;
+        if let Some(host) = host {
+            self.domain_whitelist.validate_domain(host)
+                .inspect_err(|e| audit("blocked_domain", "high", &e.to_string(), Some(host)))?;
+        }
+        Ok(())
+    }
+    
+    /// Check where a redirect leads before following it
+    ///
+    /// Fails with a `SecurityError` when the target is internal or its domain is blocked.
+    pub fn validate_redirect_target(&self, url: &Url) -> Result<()> {
+        let host = url.host_str();
+        self.validate_url(url.as_str())
+            .map_err(|e| audited(SecurityError::UrlValidation(format!("Redirect refused: {}", e)), host))?;
+        if let Some(host) = host {
+            self.domain_whitelist.validate_domain(host)
+                .map_err(|e| audited(SecurityError::DomainBlocked(format!("Redirect refused: {}", e)), Some(host)))?;
+        }
+        Ok(())
+    }
+    
     /// Validate URL for security
     fn validate_url(&self, url_str: &str) -> Result<()> {
         let url = Url::parse(url_str)
@@ -215,6 +258,11 @@ impl SecurityManager {
                   anti_blocking.randomized_delays.min_ms);
         }
         
+        // Check the plan against its politeness preset
+        for violation in self.check_politeness(dsl) {
+            warn!("Politeness preset exceeded: {}", violation);
+        }
+        
         // Check custom headers for dangerous content
         if let Some(headers) = &anti_blocking.headers {
             for (name, value) in headers {
@@ -290,20 +338,26 @@ impl SecurityManager {
         Ok(())
     }
     
-    /// Filter output data for sensitive information
-    pub fn filter_output(&self, data: &mut [serde_json::Value]) -> Result<()> {
-        if !self.config.enable_output_filtering {
-            return Ok(());
-        }
-        
-        debug!("Filtering output data for sensitive information");
-        
+    /// Check a plan against the ceilings of its selected politeness preset
+    pub fn check_politeness(&self, dsl: &ScrapePlan) -> Vec<String> {
+        dsl.anti_blocking.politeness.check_plan(dsl)
+    }
+    
+    /// Strip sensitive fields and values from rows that will leave the machine
+    ///
+    /// Always applies, even with output filtering turned off. Returns the
+    /// number of rows that had something removed.
+    pub fn redact_for_sharing(&self, data: &mut [serde_json::Value]) -> Result<usize> {
+        let mut redacted = 0;
         for item in data {
-            self.output_filter.filter_item(item)?;
+            let original = item.clone();
+            self.output_filter.remove_sensitive_fields(item)?;
+            self.output_filter.redact_item(item)?;
+            if *item != original {
+                redacted += 1;
+            }
         }
-        
-        debug!("Output filtering completed");
-        Ok(())
+        Ok(redacted)
     }
     
     /// Compile blocked patterns for input validation
@@ -371,12 +425,19 @@ impl SecurityManager {
             report.add_warning("Robots.txt compliance is disabled".to_string());
         }
         
+        // Check politeness preset ceilings
+        let preset = dsl.anti_blocking.politeness;
+        report.add_info(format!("Politeness preset: {} ({})", preset.name(), preset.description()));
+        for violation in self.check_politeness(dsl) {
+            report.add_warning(violation);
+        }
+        
         report
     }
 }
 
 /// Security report structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct SecurityReport {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
@@ -423,6 +484,29 @@ impl Default for SecurityReport {
     }
 }
 
+/// Record a security event in the audit log
+fn audit(event_type: &str, severity: &str, description: &str, domain: Option<&str>) {
+    let mut context = LogContext::new("security", "validation");
+    if let Some(domain) = domain {
+        context = context.with_domain(domain);
+    }
+    AuditLogger::log_security_event(event_type, severity, description, Some(context));
+}
+
+/// Record a refusal in the audit log and pass it on
+fn audited(error: SecurityError, domain: Option<&str>) -> SecurityError {
+    let (event_type, severity) = match &error {
+        SecurityError::InputValidation(_) => ("rejected_input", "medium"),
+        SecurityError::UrlValidation(_) => ("rejected_url", "high"),
+        SecurityError::DomainBlocked(_) => ("blocked_domain", "high"),
+        SecurityError::SelectorValidation(_) => ("rejected_selector", "medium"),
+        SecurityError::HeaderValidation(_) => ("rejected_header", "medium"),
+        SecurityError::OutputFiltering(_) => ("output_filtering_failed", "low"),
+    };
+    audit(event_type, severity, &error.to_string(), domain);
+    error
+}
+
 /// Security validation errors
 #[derive(Debug, thiserror::Error)]
 pub enum SecurityError {
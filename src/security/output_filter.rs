@@ -1,5 +1,8 @@
 use anyhow::Result;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
 use tracing::{debug, warn};
 
 use crate::config::SecurityConfig;
@@ -10,6 +13,34 @@ pub struct OutputFilter {
     sensitive_patterns: Vec<SensitivePattern>,
 }
 
+/// How a column is redacted when exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Redaction {
+    /// Replace with a salted SHA-256, so equal values still match
+    Hash,
+    /// Keep an email's domain or a long value's last four characters and mask the rest
+    Mask,
+    /// Leave the column out
+    Drop,
+    /// Replace emails, phone numbers and other sensitive patterns found in the text
+    Patterns,
+}
+
+impl std::str::FromStr for Redaction {
+    type Err = anyhow::Error;
+    
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "hash" => Ok(Redaction::Hash),
+            "mask" => Ok(Redaction::Mask),
+            "drop" => Ok(Redaction::Drop),
+            "patterns" => Ok(Redaction::Patterns),
+            _ => Err(anyhow::anyhow!("Unknown redaction '{}': use hash, mask, drop or patterns", s)),
+        }
+    }
+}
+
 /// Pattern for detecting sensitive information
 struct SensitivePattern {
     name: String,
@@ -34,6 +65,11 @@ impl OutputFilter {
             return Ok(());
         }
         
+        self.redact_item(item)
+    }
+    
+    /// Redact sensitive values whether or not output filtering is enabled
+    pub fn redact_item(&self, item: &mut serde_json::Value) -> Result<()> {
         match item {
             serde_json::Value::Object(obj) => {
                 for (key, value) in obj.iter_mut() {
@@ -42,7 +78,7 @@ impl OutputFilter {
             }
             serde_json::Value::Array(arr) => {
                 for value in arr.iter_mut() {
-                    self.filter_item(value)?;
+                    self.redact_item(value)?;
                 }
             }
             _ => {}
@@ -62,7 +98,7 @@ impl OutputFilter {
                 }
             }
             serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
-                self.filter_item(value)?;
+                self.redact_item(value)?;
             }
             _ => {}
         }
@@ -72,16 +108,7 @@ impl OutputFilter {
     
     /// Filter sensitive information from string
     fn filter_string(&self, input: &str) -> String {
-        let mut filtered = input.to_string();
-        
-        for pattern in &self.sensitive_patterns {
-            if pattern.regex.is_match(&filtered) {
-                warn!("Found sensitive data pattern: {}", pattern.name);
-                filtered = pattern.regex.replace_all(&filtered, &pattern.replacement).to_string();
-            }
-        }
-        
-        filtered
+        replace_patterns(&self.sensitive_patterns, input)
     }
     
     /// Create patterns for detecting sensitive information
@@ -178,6 +205,56 @@ impl OutputFilter {
     }
 }
 
+fn replace_patterns(patterns: &[SensitivePattern], input: &str) -> String {
+    let mut filtered = input.to_string();
+    
+    for pattern in patterns {
+        if pattern.regex.is_match(&filtered) {
+            warn!("Found sensitive data pattern: {}", pattern.name);
+            filtered = pattern.regex.replace_all(&filtered, &pattern.replacement).to_string();
+        }
+    }
+    
+    filtered
+}
+
+/// Redact one exported value; `None` when the column is dropped
+///
+/// Nulls stay null. Values that aren't text are redacted as their JSON text.
+pub fn redact_value(value: serde_json::Value, redaction: Redaction, salt: &str) -> Option<serde_json::Value> {
+    static PATTERNS: OnceLock<Vec<SensitivePattern>> = OnceLock::new();
+    
+    let text = match value {
+        _ if redaction == Redaction::Drop => return None,
+        serde_json::Value::Null => return Some(serde_json::Value::Null),
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    };
+    let redacted = match redaction {
+        Redaction::Hash => hex::encode(Sha256::digest(format!("{}{}", salt, text))),
+        Redaction::Mask => mask(&text),
+        Redaction::Patterns => {
+            let patterns = PATTERNS.get_or_init(|| {
+                OutputFilter::create_sensitive_patterns().expect("built-in patterns are valid")
+            });
+            replace_patterns(patterns, &text)
+        }
+        Redaction::Drop => return None,
+    };
+    Some(serde_json::Value::String(redacted))
+}
+
+fn mask(text: &str) -> String {
+    if let Some((local, domain)) = text.split_once('@').filter(|(local, _)| !local.is_empty()) {
+        let first = local.chars().next().unwrap_or('*');
+        return format!("{}{}@{}", first, "*".repeat(local.chars().count() - 1), domain);
+    }
+    let length = text.chars().count();
+    let kept = if length > 8 { 4 } else { 0 };
+    let tail: String = text.chars().skip(length - kept).collect();
+    format!("{}{}", "*".repeat(length - kept), tail)
+}
+
 /// Filtering statistics
 #[derive(Debug, Clone)]
 pub struct FilterStats {
@@ -199,6 +276,7 @@ mod tests {
             allowed_schemes: vec!["http".to_string(), "https".to_string()],
             enable_rate_limiting: true,
             rate_limit_requests_per_minute: 60,
+            audit_retention_days: None,
         }
     }
     
@@ -275,4 +353,20 @@ mod tests {
         assert!(data.get("password").is_none());
         assert!(data.get("api_key").is_none());
     }
+    
+    #[test]
+    fn test_export_redaction() {
+        assert_eq!(redact_value(json!("jane.doe@example.com"), Redaction::Mask, ""), Some(json!("j*******@example.com")));
+        assert_eq!(redact_value(json!("+1 555 123 4567"), Redaction::Mask, ""), Some(json!("***********4567")));
+        assert_eq!(redact_value(json!(null), Redaction::Hash, "s"), Some(json!(null)));
+        assert_eq!(redact_value(json!("a"), Redaction::Drop, ""), None);
+        
+        let hash = |salt| redact_value(json!("jane@example.com"), Redaction::Hash, salt);
+        assert_eq!(hash("s"), hash("s"));
+        assert_ne!(hash("s"), hash("t"));
+        assert_eq!(
+            redact_value(json!("Call 555-123-4567"), Redaction::Patterns, ""),
+            Some(json!("Call [PHONE_REDACTED]"))
+        );
+    }
 }
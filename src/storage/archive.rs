@@ -0,0 +1,372 @@
+use anyhow::Result;
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Int32Type, Int64Type};
+use arrow_array::{ArrayRef, Int32Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::StorageManager;
+
+/// Age in days after which results are archived when no age is configured
+pub const DEFAULT_ARCHIVE_AFTER_DAYS: u32 = 90;
+
+/// Rows written per Parquet row group
+const ROWS_PER_GROUP: usize = 10_000;
+
+/// What an archival pass moved out of the database
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveReport {
+    pub jobs_archived: usize,
+    pub rows_archived: usize,
+    /// Size of the Parquet files written
+    pub bytes_written: u64,
+}
+
+/// A result row as stored in an archive file
+#[derive(Debug, Clone)]
+pub(super) struct ArchivedRow {
+    pub row_idx: i32,
+    pub data_json: String,
+    pub url: String,
+    pub fetched_at: i64,
+    pub hash: String,
+}
+
+/// Where a job's results were archived to
+#[derive(Debug, Clone)]
+pub(super) struct ArchivedResults {
+    pub path: PathBuf,
+    pub row_count: usize,
+}
+
+impl StorageManager {
+    /// Directory archive files are written to
+    pub fn archive_dir(&self) -> PathBuf {
+        self.config.archive_dir.clone().unwrap_or_else(|| {
+            self.config.path.parent()
+                .map(|dir| dir.join("archive"))
+                .unwrap_or_else(|| PathBuf::from("archive"))
+        })
+    }
+
+    /// Archive the results of every finished job created more than `days` days ago
+    ///
+    /// Jobs whose archiving fails keep their results in the database and are
+    /// retried on the next pass.
+    pub async fn archive_results_older_than(&self, days: u32) -> Result<ArchiveReport> {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days as i64);
+        let job_ids: Vec<String> = {
+            let conn = self.pool.get().await;
+            let mut stmt = conn.prepare(
+                "SELECT id FROM jobs
+                 WHERE status IN ('completed', 'failed', 'cancelled')
+                   AND created_at < ?1
+                   AND id NOT IN (SELECT job_id FROM archived_results)
+                   AND EXISTS (SELECT 1 FROM results WHERE results.job_id = jobs.id)
+                 ORDER BY created_at"
+            )?;
+            let ids = stmt.query_map(params![cutoff.timestamp()], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids
+        };
+
+        let mut report = ArchiveReport::default();
+        for job_id in job_ids {
+            match self.archive_job_results(&job_id).await {
+                Ok((rows, bytes)) => {
+                    report.jobs_archived += 1;
+                    report.rows_archived += rows;
+                    report.bytes_written += bytes;
+                }
+                Err(e) => warn!("Could not archive results of job {}: {}", job_id, e),
+            }
+        }
+
+        if report.jobs_archived > 0 {
+            info!(
+                "Archived {} rows of {} jobs ({} bytes)",
+                report.rows_archived, report.jobs_archived, report.bytes_written
+            );
+        }
+        Ok(report)
+    }
+
+    /// Move one job's results into a Parquet file, returning the rows and bytes written
+    pub async fn archive_job_results(&self, job_id: &str) -> Result<(usize, u64)> {
+        if self.encryption_key.is_some() {
+            return Err(anyhow::anyhow!("Archive files are not encrypted, so archiving is disabled for encrypted databases"));
+        }
+        if self.archived_results(job_id).await?.is_some() {
+            return Err(anyhow::anyhow!("Results of job {} are already archived", job_id));
+        }
+
+        let rows = {
+            let conn = self.pool.get().await;
+            let mut stmt = conn.prepare(
+                "SELECT row_idx, data_json, url, fetched_at, hash FROM results WHERE job_id = ?1 ORDER BY row_idx"
+            )?;
+            let rows = stmt.query_map(params![job_id], |row| {
+                Ok(ArchivedRow {
+                    row_idx: row.get(0)?,
+                    data_json: row.get(1)?,
+                    url: row.get(2)?,
+                    fetched_at: row.get(3)?,
+                    hash: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        let row_count = rows.len();
+
+        let dir = self.archive_dir();
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(format!("{}.parquet", job_id));
+        let temp_path = path.with_extension("parquet.tmp");
+
+        let target = temp_path.clone();
+        tokio::task::spawn_blocking(move || write_archive(&target, &rows)).await??;
+
+        // Only drop rows from the database once the file reads back complete
+        let written = read_row_count(temp_path.clone()).await?;
+        if written != row_count {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(anyhow::anyhow!("Archive of job {} has {} rows, expected {}", job_id, written, row_count));
+        }
+        tokio::fs::rename(&temp_path, &path).await?;
+        let bytes = tokio::fs::metadata(&path).await?.len();
+
+        let mut conn = self.pool.get().await;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        tx.execute(
+            "INSERT INTO archived_results (job_id, path, row_count, archived_at) VALUES (?1, ?2, ?3, ?4)",
+            params![job_id, path.to_string_lossy(), row_count as i64, chrono::Utc::now().timestamp()],
+        )?;
+        tx.execute("DELETE FROM results WHERE job_id = ?1", params![job_id])?;
+        tx.commit()?;
+
+        info!("Archived {} result rows of job {} to {}", row_count, job_id, path.display());
+        Ok((row_count, bytes))
+    }
+
+    /// Run `query` over a job's results wherever they are, as if they were in the `results` table
+    ///
+    /// Archived results are loaded into an in-memory database first, so
+    /// queries written against the live table work on them unchanged.
+    pub(super) async fn with_results<T>(&self, job_id: &str, query: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        if let Some(archived) = self.archived_results(job_id).await? {
+            let conn = open_archive(&archived.path, job_id).await?;
+            return query(&conn);
+        }
+        let conn = self.pool.get().await;
+        query(&conn)
+    }
+
+    /// Archive file of a job, if its results were archived
+    pub(super) async fn archived_results(&self, job_id: &str) -> Result<Option<ArchivedResults>> {
+        let conn = self.pool.get().await;
+        let archived = conn.query_row(
+            "SELECT path, row_count FROM archived_results WHERE job_id = ?1",
+            params![job_id],
+            |row| Ok(ArchivedResults {
+                path: PathBuf::from(row.get::<_, String>(0)?),
+                row_count: row.get::<_, i64>(1)? as usize,
+            }),
+        ).optional()?;
+        Ok(archived)
+    }
+}
+
+/// Read every row of an archive file
+pub(super) async fn read_archive(path: &Path) -> Result<Vec<ArchivedRow>> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || read_archive_blocking(&path)).await?
+}
+
+/// An in-memory database with an archive file's rows in a `results` table shaped like the live one
+async fn open_archive(path: &Path, job_id: &str) -> Result<Connection> {
+    let rows = read_archive(path).await?;
+    let mut conn = Connection::open_in_memory()?;
+    conn.execute_batch(
+        "CREATE TABLE results (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            job_id TEXT NOT NULL,
+            row_idx INTEGER NOT NULL,
+            data_json TEXT NOT NULL,
+            url TEXT NOT NULL,
+            fetched_at INTEGER NOT NULL,
+            hash TEXT NOT NULL
+        )"
+    )?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO results (job_id, row_idx, data_json, url, fetched_at, hash) VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+        )?;
+        for row in &rows {
+            insert.execute(params![job_id, row.row_idx, row.data_json, row.url, row.fetched_at, row.hash])?;
+        }
+    }
+    tx.commit()?;
+    Ok(conn)
+}
+
+fn schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new("row_idx", DataType::Int32, false),
+        Field::new("data_json", DataType::Utf8, false),
+        Field::new("url", DataType::Utf8, false),
+        Field::new("fetched_at", DataType::Int64, false),
+        Field::new("hash", DataType::Utf8, false),
+    ]))
+}
+
+fn write_archive(path: &Path, rows: &[ArchivedRow]) -> Result<()> {
+    let schema = schema();
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_max_row_group_size(ROWS_PER_GROUP)
+        .build();
+    let file = std::fs::File::create(path)?;
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(props))?;
+
+    for chunk in rows.chunks(ROWS_PER_GROUP) {
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(chunk.iter().map(|r| r.row_idx).collect::<Int32Array>()),
+            Arc::new(chunk.iter().map(|r| Some(r.data_json.as_str())).collect::<StringArray>()),
+            Arc::new(chunk.iter().map(|r| Some(r.url.as_str())).collect::<StringArray>()),
+            Arc::new(chunk.iter().map(|r| r.fetched_at).collect::<Int64Array>()),
+            Arc::new(chunk.iter().map(|r| Some(r.hash.as_str())).collect::<StringArray>()),
+        ];
+        writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+async fn read_row_count(path: PathBuf) -> Result<usize> {
+    tokio::task::spawn_blocking(move || {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path)?)?;
+        Ok(builder.metadata().file_metadata().num_rows() as usize)
+    })
+    .await?
+}
+
+fn read_archive_blocking(path: &Path) -> Result<Vec<ArchivedRow>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| anyhow::anyhow!("Cannot open result archive {}: {}", path.display(), e))?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+
+    let mut rows = Vec::new();
+    for batch in reader {
+        let batch = batch?;
+        let row_idx = batch.column(0).as_primitive::<Int32Type>();
+        let data_json = batch.column(1).as_string::<i32>();
+        let url = batch.column(2).as_string::<i32>();
+        let fetched_at = batch.column(3).as_primitive::<Int64Type>();
+        let hash = batch.column(4).as_string::<i32>();
+
+        for i in 0..batch.num_rows() {
+            rows.push(ArchivedRow {
+                row_idx: row_idx.value(i),
+                data_json: data_json.value(i).to_string(),
+                url: url.value(i).to_string(),
+                fetched_at: fetched_at.value(i),
+                hash: hash.value(i).to_string(),
+            });
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{test_storage, Job, JobResult, JobStatus};
+
+    async fn create_job(storage: &StorageManager, id: &str, created_at: chrono::DateTime<chrono::Utc>, rows: &[serde_json::Value]) {
+        storage.create_job(&Job {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: JobStatus::Completed,
+            created_at,
+            plan_yaml: String::new(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }).await.unwrap();
+        for (i, row) in rows.iter().enumerate() {
+            storage.store_job_result(&JobResult {
+                job_id: id.to_string(),
+                row_idx: i as i32,
+                data_json: row.to_string(),
+                url: "https://example.com".to_string(),
+                fetched_at: created_at,
+                hash: i.to_string(),
+            }).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archived_results_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+
+        let created_at = chrono::Utc::now() - chrono::Duration::days(30);
+        let rows: Vec<serde_json::Value> = (0..3).map(|i| serde_json::json!({ "n": i })).collect();
+        create_job(&storage, "old", created_at, &rows).await;
+        let before = storage.get_job_results("old").await.unwrap();
+
+        assert_eq!(storage.archive_results_older_than(60).await.unwrap().jobs_archived, 0);
+        let report = storage.archive_results_older_than(7).await.unwrap();
+        assert_eq!((report.jobs_archived, report.rows_archived), (1, 3));
+        assert!(storage.archive_dir().join("old.parquet").exists());
+
+        assert_eq!(storage.get_job_results("old").await.unwrap(), before);
+        assert_eq!(storage.get_job_result_count("old").await.unwrap(), 3);
+        let tail = storage.get_job_results_after("old", Some(0), 1).await.unwrap();
+        assert_eq!(tail[0].row_idx, 1);
+
+        storage.delete_job("old").await.unwrap();
+        assert!(!storage.archive_dir().join("old.parquet").exists());
+    }
+
+    #[tokio::test]
+    async fn test_archived_results_compare_and_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+
+        let created_at = chrono::Utc::now() - chrono::Duration::days(30);
+        let base = [serde_json::json!({ "sku": "a", "price": 10 }), serde_json::json!({ "sku": "b", "price": 20 })];
+        create_job(&storage, "base", created_at, &base).await;
+        let rerun = [
+            serde_json::json!({ "sku": "a", "price": 10 }),
+            serde_json::json!({ "sku": "b", "price": 25 }),
+            serde_json::json!({ "sku": "c", "price": 5 }),
+        ];
+        create_job(&storage, "rerun", chrono::Utc::now(), &rerun).await;
+        assert_eq!(storage.archive_results_older_than(7).await.unwrap().jobs_archived, 1);
+
+        let keys = vec!["sku".to_string()];
+        let changes = storage.compare_job_results("base", "rerun", None, Some(&keys)).await.unwrap();
+        assert_eq!((changes.added.len(), changes.changed.len(), changes.removed.len(), changes.unchanged), (1, 1, 0, 1));
+        assert_eq!(changes.changed[0].0["price"], 20);
+
+        let stats = storage.get_column_stats("base", 5).await.unwrap();
+        let price = stats.iter().find(|s| s.column == "price").unwrap();
+        assert_eq!((price.count, price.max.clone()), (2, Some(serde_json::json!(20))));
+        assert_eq!(storage.numeric_histogram("base", "price", 2).await.unwrap().counts, vec![1, 1]);
+        let days = storage.items_per_day("base").await.unwrap();
+        assert_eq!(days.iter().map(|day| day.count).sum::<u64>(), 2);
+    }
+}
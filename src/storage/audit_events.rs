@@ -0,0 +1,163 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::StorageManager;
+use crate::logging::{severity_rank, AuditEvent, AUDIT_SEVERITIES};
+
+/// Which security events to list; fields left unset match every event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditEventFilter {
+    /// Least severe severity listed
+    pub min_severity: Option<String>,
+    pub event_type: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    /// Text contained in the description or domain, ignoring case
+    pub text: Option<String>,
+}
+
+impl AuditEventFilter {
+    /// SQL condition and its parameters, in placeholder order
+    fn to_sql(&self) -> (String, Vec<Value>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        if let Some(min_severity) = &self.min_severity {
+            let rank = severity_rank(min_severity) as usize;
+            let severities = &AUDIT_SEVERITIES[..=rank.min(AUDIT_SEVERITIES.len() - 1)];
+            conditions.push(format!("severity IN ({})", vec!["?"; severities.len()].join(", ")));
+            values.extend(severities.iter().map(|severity| Value::Text(severity.to_string())));
+        }
+        if let Some(event_type) = self.event_type.as_deref().filter(|t| !t.is_empty()) {
+            conditions.push("event_type = ?".to_string());
+            values.push(Value::Text(event_type.to_string()));
+        }
+        if let Some(since) = self.since {
+            conditions.push("ts >= ?".to_string());
+            values.push(Value::Integer(since.timestamp()));
+        }
+        if let Some(text) = self.text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+            let pattern = format!("%{}%", text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+            conditions.push("(description LIKE ? ESCAPE '\\' OR domain LIKE ? ESCAPE '\\')".to_string());
+            values.push(Value::Text(pattern.clone()));
+            values.push(Value::Text(pattern));
+        }
+
+        let sql = if conditions.is_empty() {
+            "1 = 1".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+        (sql, values)
+    }
+}
+
+impl StorageManager {
+    /// Record a security event
+    pub async fn store_audit_event(&self, event: &AuditEvent) -> Result<()> {
+        let conn = self.pool.get().await;
+
+        conn.execute(
+            "INSERT INTO audit_events (ts, event_type, severity, description, job_id, domain, user_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                event.timestamp.timestamp(),
+                event.event_type,
+                event.severity,
+                event.description,
+                event.job_id,
+                event.domain,
+                event.user_id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Latest security events matching `filter`, newest first
+    pub async fn get_audit_events(&self, filter: &AuditEventFilter, limit: usize) -> Result<Vec<AuditEvent>> {
+        let conn = self.pool.get().await;
+
+        let (condition, mut values) = filter.to_sql();
+        values.push(Value::Integer(limit as i64));
+        let mut stmt = conn.prepare(&format!(
+            "SELECT ts, event_type, severity, description, job_id, domain, user_id FROM audit_events
+             WHERE {} ORDER BY ts DESC, id DESC LIMIT ?",
+            condition
+        ))?;
+        let events = stmt.query_map(params_from_iter(values), |row| {
+            Ok(AuditEvent {
+                timestamp: DateTime::from_timestamp(row.get(0)?, 0).unwrap_or_else(Utc::now),
+                event_type: row.get(1)?,
+                severity: row.get(2)?,
+                description: row.get(3)?,
+                job_id: row.get(4)?,
+                domain: row.get(5)?,
+                user_id: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events)
+    }
+
+    /// Remove security events older than the given number of days
+    pub async fn prune_audit_events(&self, max_age_days: u32) -> Result<usize> {
+        let conn = self.pool.get().await;
+        let cutoff = (Utc::now() - chrono::Duration::days(max_age_days as i64)).timestamp();
+
+        let deleted = conn.execute("DELETE FROM audit_events WHERE ts < ?1", params![cutoff])?;
+        if deleted > 0 {
+            info!("Pruned {} audit events older than {} days", deleted, max_age_days);
+        }
+
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_storage;
+
+    #[tokio::test]
+    async fn test_audit_events_filter_by_severity_and_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+
+        let event = |event_type: &str, severity: &str, domain: &str| AuditEvent {
+            timestamp: Utc::now(),
+            event_type: event_type.to_string(),
+            severity: severity.to_string(),
+            description: format!("{} on {}", event_type, domain),
+            job_id: None,
+            domain: Some(domain.to_string()),
+            user_id: None,
+        };
+        storage.store_audit_event(&event("blocked_domain", "high", "localhost")).await.unwrap();
+        storage.store_audit_event(&event("rejected_input", "medium", "example.com")).await.unwrap();
+        storage.store_audit_event(&event("pii_filtered", "low", "example.com")).await.unwrap();
+
+        let all = storage.get_audit_events(&AuditEventFilter::default(), 10).await.unwrap();
+        assert_eq!(all.len(), 3);
+
+        let serious = AuditEventFilter { min_severity: Some("medium".to_string()), ..Default::default() };
+        let types: Vec<String> = storage.get_audit_events(&serious, 10).await.unwrap()
+            .into_iter().map(|e| e.event_type).collect();
+        assert_eq!(types.len(), 2);
+        assert!(!types.contains(&"pii_filtered".to_string()));
+
+        let search = AuditEventFilter {
+            event_type: Some("pii_filtered".to_string()),
+            text: Some("EXAMPLE".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(storage.get_audit_events(&search, 10).await.unwrap().len(), 1);
+
+        let old = AuditEvent { timestamp: Utc::now() - chrono::Duration::days(100), ..event("blocked_domain", "high", "old.example.com") };
+        storage.store_audit_event(&old).await.unwrap();
+        assert_eq!(storage.prune_audit_events(90).await.unwrap(), 1);
+        assert_eq!(storage.get_audit_events(&AuditEventFilter::default(), 10).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,197 @@
+use anyhow::Result;
+use rusqlite::backup::Backup;
+use rusqlite::OpenFlags;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::info;
+
+use super::{encryption, migrations, StorageManager};
+
+/// Pages copied per backup step; other connections can write between steps
+const PAGES_PER_STEP: std::os::raw::c_int = 256;
+
+/// Pause between backup steps
+const STEP_PAUSE: Duration = Duration::from_millis(10);
+
+/// Tables a database needs to be restorable
+const REQUIRED_TABLES: &[&str] = &["schema_version", "jobs", "results", "logs"];
+
+/// What a database backup contains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub schema_version: i32,
+    pub job_count: usize,
+    pub result_count: usize,
+    pub size_bytes: u64,
+}
+
+impl StorageManager {
+    /// Copy the database to `path` with SQLite's online backup API
+    ///
+    /// Jobs can keep writing while the copy runs. An existing file at `path`
+    /// is replaced. Backups of an encrypted database use the same key.
+    /// Archived results stay in their Parquet files and are not copied.
+    pub async fn backup_to(&self, path: &Path) -> Result<BackupInfo> {
+        if same_file(path, &self.config.path) {
+            return Err(anyhow::anyhow!("Cannot back up the database onto itself"));
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let source = self.config.path.clone();
+        let target = path.to_path_buf();
+        let key = self.encryption_key.clone();
+        tokio::task::spawn_blocking(move || copy_database(&source, &target, key.as_deref())).await??;
+
+        let info = inspect(path, self.encryption_key.clone()).await?;
+        info!("Backed up {} jobs to {}", info.job_count, path.display());
+        Ok(info)
+    }
+
+    /// Replace the database with the backup at `path`
+    ///
+    /// The backup must pass SQLite's integrity check and have a schema this
+    /// version understands; older schemas are migrated after restoring. The
+    /// current database is first saved next to itself, and the path of that
+    /// copy is returned along with what was restored.
+    pub async fn restore_from(&self, path: &Path) -> Result<(BackupInfo, PathBuf)> {
+        if same_file(path, &self.config.path) {
+            return Err(anyhow::anyhow!("Cannot restore the database from itself"));
+        }
+        let info = inspect(path, self.encryption_key.clone()).await?;
+
+        let safety_copy = self.config.path.with_extension(format!(
+            "before-restore-{}.db",
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        ));
+        self.backup_to(&safety_copy).await?;
+
+        let source = path.to_path_buf();
+        let target = self.config.path.clone();
+        let key = self.encryption_key.clone();
+        tokio::task::spawn_blocking(move || copy_database(&source, &target, key.as_deref())).await??;
+
+        self.run_migrations().await?;
+        info!(
+            "Restored {} jobs from {} (previous database saved to {})",
+            info.job_count, path.display(), safety_copy.display()
+        );
+        Ok((info, safety_copy))
+    }
+}
+
+/// Check that a file is an intact database from this application
+async fn inspect(path: &Path, key: Option<String>) -> Result<BackupInfo> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || inspect_blocking(&path, key.as_deref())).await?
+}
+
+fn inspect_blocking(path: &Path, key: Option<&str>) -> Result<BackupInfo> {
+    let size_bytes = std::fs::metadata(path)
+        .map_err(|e| anyhow::anyhow!("Cannot read backup {}: {}", path.display(), e))?
+        .len();
+    let conn = encryption::open(path, OpenFlags::SQLITE_OPEN_READ_ONLY, key)
+        .map_err(|e| anyhow::anyhow!("{} is not a database: {}", path.display(), e))?;
+
+    let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| anyhow::anyhow!("{} is not a database: {}", path.display(), e))?;
+    if integrity != "ok" {
+        return Err(anyhow::anyhow!("Backup {} is damaged: {}", path.display(), integrity));
+    }
+
+    for table in REQUIRED_TABLES {
+        let present: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |row| row.get(0),
+        )?;
+        if !present {
+            return Err(anyhow::anyhow!(
+                "{} is not a WinScrape Studio database (no {} table)", path.display(), table
+            ));
+        }
+    }
+
+    let schema_version = migrations::get_schema_version(&conn)?;
+    if schema_version > migrations::CURRENT_SCHEMA_VERSION {
+        return Err(anyhow::anyhow!(
+            "Backup {} has schema version {}, newer than this version supports ({}); upgrade WinScrape Studio first",
+            path.display(), schema_version, migrations::CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    let job_count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0))?;
+    let result_count: i64 = conn.query_row("SELECT COUNT(*) FROM results", [], |row| row.get(0))?;
+    Ok(BackupInfo {
+        path: path.to_path_buf(),
+        schema_version,
+        job_count: job_count as usize,
+        result_count: result_count as usize,
+        size_bytes,
+    })
+}
+
+/// Copy every page of `source` into `target` in small steps
+fn copy_database(source: &Path, target: &Path, key: Option<&str>) -> Result<()> {
+    let src = encryption::open(source, OpenFlags::SQLITE_OPEN_READ_WRITE, key)?;
+    src.busy_timeout(Duration::from_secs(10))?;
+    let mut dst = encryption::open(target, OpenFlags::default(), key)?;
+    dst.busy_timeout(Duration::from_secs(10))?;
+
+    let backup = Backup::new(&src, &mut dst)?;
+    backup.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None)?;
+    Ok(())
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{test_storage, Job, JobStatus};
+
+    fn job(id: &str) -> Job {
+        Job {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: JobStatus::Completed,
+            created_at: chrono::Utc::now(),
+            plan_yaml: String::new(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backup_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+        storage.create_job(&job("kept")).await.unwrap();
+
+        let backup_path = dir.path().join("backup.db");
+        let info = storage.backup_to(&backup_path).await.unwrap();
+        assert_eq!(info.job_count, 1);
+        assert_eq!(info.schema_version, migrations::CURRENT_SCHEMA_VERSION);
+
+        storage.create_job(&job("after_backup")).await.unwrap();
+        let (restored, safety_copy) = storage.restore_from(&backup_path).await.unwrap();
+        assert_eq!(restored.job_count, 1);
+        assert!(storage.get_job("after_backup").await.is_err());
+        assert!(storage.get_job("kept").await.is_ok());
+        assert_eq!(inspect(&safety_copy, None).await.unwrap().job_count, 2);
+
+        let not_a_database = dir.path().join("notes.txt");
+        std::fs::write(&not_a_database, "hello").unwrap();
+        assert!(storage.restore_from(&not_a_database).await.is_err());
+    }
+}
@@ -175,9 +175,10 @@ pub struct RobotRule {
 }
 
 impl RobotsCache {
-    pub fn new(storage: Arc<StorageManager>) -> Self {
+    /// Cache keeping robots.txt files for `keep_hours`, however long they count as fresh
+    pub fn new(storage: Arc<StorageManager>, keep_hours: i64) -> Self {
         Self {
-            cache: CacheManager::new(storage, 168), // 1 week default TTL
+            cache: CacheManager::new(storage, keep_hours),
         }
     }
     
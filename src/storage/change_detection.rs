@@ -0,0 +1,251 @@
+use anyhow::Result;
+use rusqlite::{params, OptionalExtension, TransactionBehavior};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tracing::info;
+
+use super::archive::read_archive;
+use super::StorageManager;
+use crate::error::WinScrapeError;
+
+/// Content hash of a result row over the given fields
+///
+/// Without a field list every field except `_`-prefixed metadata (source URL,
+/// scrape time, response time) is hashed, so reruns of unchanged pages match.
+/// Fields are hashed in name order; a missing field hashes like `null`.
+pub fn content_hash(row: &Value, hash_fields: Option<&[String]>) -> String {
+    let selected: Vec<(&str, &Value)> = match (hash_fields, row.as_object()) {
+        (Some(fields), Some(object)) => {
+            let mut names: Vec<&str> = fields.iter().map(String::as_str).collect();
+            names.sort_unstable();
+            names.dedup();
+            names.into_iter()
+                .map(|name| (name, object.get(name).unwrap_or(&Value::Null)))
+                .collect()
+        }
+        (None, Some(object)) => {
+            let mut pairs: Vec<(&str, &Value)> = object.iter()
+                .filter(|(name, _)| !name.starts_with('_'))
+                .map(|(name, value)| (name.as_str(), value))
+                .collect();
+            pairs.sort_unstable_by_key(|(name, _)| *name);
+            pairs
+        }
+        (_, None) => vec![("", row)],
+    };
+
+    let mut hasher = Sha256::new();
+    for (name, value) in selected {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(serde_json::to_string(value).unwrap_or_default().as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Differences between the results of two runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResultChanges {
+    pub base_job_id: String,
+    pub job_id: String,
+    /// Fields that took part in the comparison; `None` means all non-metadata fields
+    pub hash_fields: Option<Vec<String>>,
+    pub added: Vec<Value>,
+    pub removed: Vec<Value>,
+    /// Rows with the same key but different content, as `(before, after)`
+    pub changed: Vec<(Value, Value)>,
+    pub unchanged: usize,
+}
+
+impl ResultChanges {
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
+struct HashedRow {
+    id: i64,
+    data: Value,
+    hash: String,
+}
+
+impl StorageManager {
+    /// Record the hash field set a job's results are hashed with
+    pub async fn set_job_hash_fields(&self, job_id: &str, hash_fields: Option<&[String]>) -> Result<()> {
+        let conn = self.pool.get().await;
+
+        conn.execute(
+            "UPDATE jobs SET hash_fields_json = ?1 WHERE id = ?2",
+            params![serde_json::to_string(&hash_fields)?, job_id],
+        )?;
+
+        Ok(())
+    }
+
+    /// Make sure a job's stored hashes were computed over `hash_fields`, rehashing its rows if not
+    ///
+    /// Returns the number of rows that were rehashed.
+    pub async fn ensure_result_hashes(&self, job_id: &str, hash_fields: Option<&[String]>) -> Result<usize> {
+        // Archived results are hashed as they are read back, see `hashed_rows`
+        if self.archived_results(job_id).await?.is_some() {
+            return Ok(0);
+        }
+        let mut conn = self.pool.get().await;
+
+        let wanted = serde_json::to_string(&hash_fields)?;
+        let current: Option<String> = conn.query_row(
+            "SELECT hash_fields_json FROM jobs WHERE id = ?1",
+            params![job_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| WinScrapeError::job_not_found(job_id))?;
+
+        if current.as_deref() == Some(wanted.as_str()) {
+            return Ok(0);
+        }
+
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let rows = load_hashed_rows(&tx, job_id)?;
+        {
+            let mut update = tx.prepare("UPDATE results SET hash = ?1 WHERE id = ?2")?;
+            for row in &rows {
+                let hash = content_hash(&row.data, hash_fields);
+                if hash != row.hash {
+                    update.execute(params![hash, row.id])?;
+                }
+            }
+        }
+        tx.execute(
+            "UPDATE jobs SET hash_fields_json = ?1 WHERE id = ?2",
+            params![wanted, job_id],
+        )?;
+        tx.commit()?;
+
+        info!("Rehashed {} results of job {}", rows.len(), job_id);
+        Ok(rows.len())
+    }
+
+    /// Compare the results of `job_id` against an earlier run
+    ///
+    /// Rows are matched on `key_fields` when given (usually the plan's dedupe keys);
+    /// matched rows whose content hash differs are reported as changed. Without key
+    /// fields rows can only be added or removed. Both jobs are rehashed over
+    /// `hash_fields` first if their stored hashes used a different field set.
+    pub async fn compare_job_results(
+        &self,
+        base_job_id: &str,
+        job_id: &str,
+        hash_fields: Option<&[String]>,
+        key_fields: Option<&[String]>,
+    ) -> Result<ResultChanges> {
+        let base_rows = self.hashed_rows(base_job_id, hash_fields).await?;
+        let rows = self.hashed_rows(job_id, hash_fields).await?;
+
+        let mut changes = ResultChanges {
+            base_job_id: base_job_id.to_string(),
+            job_id: job_id.to_string(),
+            hash_fields: hash_fields.map(<[String]>::to_vec),
+            ..Default::default()
+        };
+
+        // Key each row by its identity (or its hash without key fields), keeping duplicates
+        let identity = |row: &HashedRow| match key_fields {
+            Some(keys) => key_fields_value(&row.data, keys),
+            None => row.hash.clone(),
+        };
+
+        let mut remaining: HashMap<String, Vec<HashedRow>> = HashMap::new();
+        for row in base_rows {
+            remaining.entry(identity(&row)).or_default().push(row);
+        }
+
+        for row in rows {
+            let matched = remaining.get_mut(&identity(&row)).and_then(|candidates| {
+                // Prefer an identical row when several share the key
+                let pos = candidates.iter().position(|c| c.hash == row.hash).unwrap_or(0);
+                (!candidates.is_empty()).then(|| candidates.remove(pos))
+            });
+
+            match matched {
+                Some(before) if before.hash == row.hash => changes.unchanged += 1,
+                Some(before) => changes.changed.push((before.data, row.data)),
+                None => changes.added.push(row.data),
+            }
+        }
+
+        let mut removed: Vec<HashedRow> = remaining.into_values().flatten().collect();
+        removed.sort_by_key(|row| row.id);
+        changes.removed = removed.into_iter().map(|row| row.data).collect();
+
+        Ok(changes)
+    }
+
+    /// A job's rows with their hashes over `hash_fields`, wherever the rows are stored
+    ///
+    /// Archive files can't be rewritten, so archived rows are hashed as they are read.
+    async fn hashed_rows(&self, job_id: &str, hash_fields: Option<&[String]>) -> Result<Vec<HashedRow>> {
+        if let Some(archived) = self.archived_results(job_id).await? {
+            let rows = read_archive(&archived.path).await?;
+            return Ok(rows.into_iter()
+                .map(|row| {
+                    let data = serde_json::from_str(&row.data_json).unwrap_or(Value::Null);
+                    HashedRow { id: row.row_idx.into(), hash: content_hash(&data, hash_fields), data }
+                })
+                .collect());
+        }
+
+        self.ensure_result_hashes(job_id, hash_fields).await?;
+        let conn = self.pool.get().await;
+        load_hashed_rows(&conn, job_id)
+    }
+}
+
+fn load_hashed_rows(conn: &rusqlite::Connection, job_id: &str) -> Result<Vec<HashedRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, data_json, hash FROM results WHERE job_id = ?1 ORDER BY row_idx"
+    )?;
+
+    let rows = stmt.query_map(params![job_id], |row| {
+        let json_str: String = row.get(1)?;
+        Ok(HashedRow {
+            id: row.get(0)?,
+            data: serde_json::from_str(&json_str).unwrap_or(Value::Null),
+            hash: row.get(2)?,
+        })
+    })?
+    .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows)
+}
+
+/// Identity of a row built from its key field values
+fn key_fields_value(row: &Value, keys: &[String]) -> String {
+    let values: Vec<&Value> = keys.iter()
+        .map(|key| row.get(key).unwrap_or(&Value::Null))
+        .collect();
+    serde_json::to_string(&values).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_content_hash_ignores_volatile_fields() {
+        let a = json!({"title": "Lamp", "price": 20, "views": 10, "_scraped_at": "2024-01-01"});
+        let b = json!({"price": 20, "title": "Lamp", "views": 99, "_scraped_at": "2024-02-01"});
+
+        assert_ne!(content_hash(&a, None), content_hash(&b, None));
+
+        let fields = vec!["title".to_string(), "price".to_string()];
+        assert_eq!(content_hash(&a, Some(&fields)), content_hash(&b, Some(&fields)));
+
+        let c = json!({"title": "Lamp", "price": 25, "views": 10});
+        assert_ne!(content_hash(&a, Some(&fields)), content_hash(&c, Some(&fields)));
+    }
+}
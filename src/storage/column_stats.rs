@@ -0,0 +1,286 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate};
+use rusqlite::{params, types::ValueRef, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use super::StorageManager;
+
+/// Default number of most frequent values reported per column
+pub const DEFAULT_TOP_VALUES: usize = 5;
+
+/// Summary statistics for one column of a job's results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnStats {
+    pub column: String,
+    /// Number of result rows in the job
+    pub count: u64,
+    /// Rows where the column is missing, null or an empty string
+    pub nulls: u64,
+    pub distinct: u64,
+    /// True when every non-null value is a number
+    pub numeric: bool,
+    pub min: Option<serde_json::Value>,
+    pub max: Option<serde_json::Value>,
+    /// Mean of the numeric values, if any
+    pub mean: Option<f64>,
+    pub top_values: Vec<ValueCount>,
+}
+
+/// A value and how often it occurs in a column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueCount {
+    pub value: serde_json::Value,
+    pub count: u64,
+}
+
+/// Counts of a numeric column's values in equal-width bins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub column: String,
+    /// Lower edge of the first bin
+    pub min: f64,
+    pub bin_width: f64,
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    /// Bin values between their minimum and maximum
+    ///
+    /// All values land in a single bin when they are equal.
+    pub fn from_values(column: &str, values: &[f64], bins: usize) -> Self {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if values.is_empty() || bins == 0 {
+            return Self { column: column.to_string(), min: 0.0, bin_width: 0.0, counts: Vec::new() };
+        }
+
+        let bins = if max > min { bins } else { 1 };
+        let bin_width = if max > min { (max - min) / bins as f64 } else { 1.0 };
+        let mut counts = vec![0; bins];
+        for value in values {
+            let bin = (((value - min) / bin_width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+        Self { column: column.to_string(), min, bin_width, counts }
+    }
+
+    /// Midpoint of a bin
+    pub fn bin_center(&self, bin: usize) -> f64 {
+        self.min + self.bin_width * (bin as f64 + 0.5)
+    }
+}
+
+/// Number of results fetched on one day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayCount {
+    pub day: NaiveDate,
+    pub count: u64,
+}
+
+impl StorageManager {
+    /// Compute per-column statistics over a job's results without loading them
+    ///
+    /// Archived results are loaded back from their file for the computation.
+    pub async fn get_column_stats(&self, job_id: &str, top_n: usize) -> Result<Vec<ColumnStats>> {
+        let start = Instant::now();
+        let stats = self.with_results(job_id, |conn| compute_column_stats(conn, job_id, top_n)).await?;
+        self.record_operation_metrics("get_column_stats", start.elapsed()).await;
+        Ok(stats)
+    }
+
+    /// Histogram of a column's numeric values; other values are ignored
+    pub async fn numeric_histogram(&self, job_id: &str, column: &str, bins: usize) -> Result<Histogram> {
+        let start = Instant::now();
+        let values = self.with_results(job_id, |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT j.value FROM results r, json_each(r.data_json) j
+                 WHERE r.job_id = ?1 AND j.key = ?2 AND j.type IN ('integer', 'real')"
+            )?;
+            let values = stmt.query_map(params![job_id, column], |row| row.get::<_, f64>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(values)
+        }).await?;
+        self.record_operation_metrics("numeric_histogram", start.elapsed()).await;
+        Ok(Histogram::from_values(column, &values, bins))
+    }
+
+    /// Results per day fetched, in the configured display zone
+    pub async fn items_per_day(&self, job_id: &str) -> Result<Vec<DayCount>> {
+        let start = Instant::now();
+        // Grouped by minute in SQL so any zone offset can be applied afterwards
+        let minutes = self.with_results(job_id, |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT fetched_at / 60, COUNT(*) FROM results WHERE job_id = ?1 GROUP BY fetched_at / 60"
+            )?;
+            let minutes = stmt.query_map(params![job_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(minutes)
+        }).await?;
+
+        let timezone = crate::utils::time_utils::timezone();
+        let mut days: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+        for (minute, count) in minutes {
+            if let Some(time) = DateTime::from_timestamp(minute * 60, 0) {
+                *days.entry(timezone.convert(&time).date_naive()).or_default() += count as u64;
+            }
+        }
+        self.record_operation_metrics("items_per_day", start.elapsed()).await;
+        Ok(days.into_iter().map(|(day, count)| DayCount { day, count }).collect())
+    }
+}
+
+/// Compute column statistics with SQLite's JSON functions
+///
+/// Columns starting with `_` are internal metadata (e.g. `_source_url`) and are skipped.
+pub(crate) fn compute_column_stats(conn: &Connection, job_id: &str, top_n: usize) -> Result<Vec<ColumnStats>> {
+    let total: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM results WHERE job_id = ?1",
+        params![job_id],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT j.key FROM results r, json_each(r.data_json) j
+         WHERE r.job_id = ?1 AND substr(j.key, 1, 1) != '_'
+         ORDER BY j.key"
+    )?;
+    let columns = stmt.query_map(params![job_id], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut summary_stmt = conn.prepare(
+        "SELECT
+            COUNT(*),
+            COUNT(DISTINCT j.value),
+            SUM(CASE WHEN j.type IN ('integer', 'real') THEN 1 ELSE 0 END),
+            MIN(CASE WHEN j.type IN ('integer', 'real') THEN j.value END),
+            MAX(CASE WHEN j.type IN ('integer', 'real') THEN j.value END),
+            AVG(CASE WHEN j.type IN ('integer', 'real') THEN j.value END),
+            MIN(CASE WHEN j.type = 'text' THEN j.value END),
+            MAX(CASE WHEN j.type = 'text' THEN j.value END)
+         FROM results r, json_each(r.data_json) j
+         WHERE r.job_id = ?1 AND j.key = ?2
+           AND j.type != 'null' AND NOT (j.type = 'text' AND j.value = '')"
+    )?;
+
+    let mut top_stmt = conn.prepare(
+        "SELECT j.value, COUNT(*) AS c
+         FROM results r, json_each(r.data_json) j
+         WHERE r.job_id = ?1 AND j.key = ?2
+           AND j.type NOT IN ('null', 'object', 'array') AND NOT (j.type = 'text' AND j.value = '')
+         GROUP BY j.value
+         ORDER BY c DESC, j.value
+         LIMIT ?3"
+    )?;
+
+    let mut stats = Vec::with_capacity(columns.len());
+    for column in columns {
+        let summary = summary_stmt.query_row(params![job_id, column], |row| {
+            let present: i64 = row.get(0)?;
+            let distinct: i64 = row.get(1)?;
+            let numeric_count: i64 = row.get::<_, Option<i64>>(2)?.unwrap_or(0);
+            let numeric = present > 0 && numeric_count == present;
+
+            let (min, max) = if numeric {
+                (sql_to_json(row.get_ref(3)?), sql_to_json(row.get_ref(4)?))
+            } else {
+                (sql_to_json(row.get_ref(6)?), sql_to_json(row.get_ref(7)?))
+            };
+
+            Ok((present, distinct, numeric, min, max, row.get::<_, Option<f64>>(5)?))
+        })?;
+        let (present, distinct, numeric, min, max, mean) = summary;
+
+        let top_values = top_stmt.query_map(params![job_id, column, top_n as i64], |row| {
+            Ok(ValueCount {
+                value: sql_to_json(row.get_ref(0)?).unwrap_or(serde_json::Value::Null),
+                count: row.get::<_, i64>(1)? as u64,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        stats.push(ColumnStats {
+            column,
+            count: total as u64,
+            nulls: (total - present).max(0) as u64,
+            distinct: distinct as u64,
+            numeric,
+            min,
+            max,
+            mean,
+            top_values,
+        });
+    }
+
+    Ok(stats)
+}
+
+/// Convert an SQLite value back into JSON
+fn sql_to_json(value: ValueRef<'_>) -> Option<serde_json::Value> {
+    match value {
+        ValueRef::Null => None,
+        ValueRef::Integer(i) => Some(serde_json::Value::from(i)),
+        ValueRef::Real(f) => Some(serde_json::Value::from(f)),
+        ValueRef::Text(t) => Some(serde_json::Value::String(String::from_utf8_lossy(t).into_owned())),
+        ValueRef::Blob(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::migrations;
+
+    fn insert_result(conn: &Connection, row_idx: i32, data: serde_json::Value) {
+        conn.execute(
+            "INSERT INTO results (job_id, row_idx, data_json, url, fetched_at, hash)
+             VALUES ('job', ?1, ?2, 'https://example.com', 0, ?3)",
+            params![row_idx, data.to_string(), format!("h{}", row_idx)],
+        ).unwrap();
+    }
+
+    #[test]
+    fn test_column_stats_over_results() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrations::run_migrations(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO jobs (id, title, status, created_at, plan_yaml, user_prompt) VALUES ('job', 't', 'Completed', 0, '', '')",
+            [],
+        ).unwrap();
+
+        insert_result(&conn, 0, serde_json::json!({"title": "A", "price": 10, "_source_url": "x"}));
+        insert_result(&conn, 1, serde_json::json!({"title": "B", "price": 20.5}));
+        insert_result(&conn, 2, serde_json::json!({"title": "A", "price": null}));
+        insert_result(&conn, 3, serde_json::json!({"title": ""}));
+
+        let stats = compute_column_stats(&conn, "job", 5).unwrap();
+        assert_eq!(stats.iter().map(|s| s.column.as_str()).collect::<Vec<_>>(), vec!["price", "title"]);
+
+        let price = &stats[0];
+        assert_eq!(price.count, 4);
+        assert_eq!(price.nulls, 2);
+        assert!(price.numeric);
+        assert_eq!(price.min, Some(serde_json::json!(10)));
+        assert_eq!(price.max, Some(serde_json::json!(20.5)));
+        assert!((price.mean.unwrap() - 15.25).abs() < 1e-9);
+
+        let title = &stats[1];
+        assert_eq!(title.nulls, 1);
+        assert_eq!(title.distinct, 2);
+        assert!(!title.numeric);
+        assert_eq!(title.top_values[0], ValueCount { value: serde_json::json!("A"), count: 2 });
+    }
+
+    #[test]
+    fn test_histogram_bins() {
+        let histogram = Histogram::from_values("price", &[0.0, 1.0, 2.5, 5.0, 10.0], 4);
+        assert_eq!(histogram.counts, vec![2, 1, 1, 1]);
+        assert_eq!(histogram.bin_width, 2.5);
+        assert_eq!(histogram.bin_center(0), 1.25);
+
+        let constant = Histogram::from_values("price", &[3.0, 3.0], 10);
+        assert_eq!(constant.counts, vec![2]);
+        assert!(Histogram::from_values("price", &[], 10).counts.is_empty());
+    }
+}
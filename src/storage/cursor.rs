@@ -0,0 +1,68 @@
+use anyhow::Result;
+
+use super::archive::{self, ArchivedRow};
+use super::StorageManager;
+
+/// Reads a job's results page by page, in row order
+///
+/// Only one page is held at a time for results in the database. Archived
+/// results are read from their Parquet file once, on the first page.
+pub struct ResultCursor<'a> {
+    storage: &'a StorageManager,
+    job_id: String,
+    page_size: usize,
+    last_row_idx: Option<i32>,
+    archived: Option<std::vec::IntoIter<ArchivedRow>>,
+    started: bool,
+}
+
+impl StorageManager {
+    /// Cursor over a job's results, `page_size` rows at a time
+    pub fn result_cursor(&self, job_id: &str, page_size: usize) -> ResultCursor<'_> {
+        ResultCursor {
+            storage: self,
+            job_id: job_id.to_string(),
+            page_size: page_size.max(1),
+            last_row_idx: None,
+            archived: None,
+            started: false,
+        }
+    }
+}
+
+impl ResultCursor<'_> {
+    /// Next page of result rows, or `None` once every row was returned
+    pub async fn next_page(&mut self) -> Result<Option<Vec<serde_json::Value>>> {
+        if !self.started {
+            self.started = true;
+            if let Some(archived) = self.storage.archived_results(&self.job_id).await? {
+                self.archived = Some(archive::read_archive(&archived.path).await?.into_iter());
+            }
+        }
+
+        let page: Vec<serde_json::Value> = match &mut self.archived {
+            Some(rows) => rows.by_ref()
+                .take(self.page_size)
+                .map(|row| serde_json::from_str(&row.data_json).unwrap_or(serde_json::Value::Null))
+                .collect(),
+            None => {
+                let rows = self.storage
+                    .get_job_results_after(&self.job_id, self.last_row_idx, self.page_size)
+                    .await?;
+                if let Some(last) = rows.last() {
+                    self.last_row_idx = Some(last.row_idx);
+                }
+                rows.into_iter().map(|row| row.data).collect()
+            }
+        };
+
+        Ok(if page.is_empty() { None } else { Some(page) })
+    }
+
+    /// Start again from the first row
+    pub fn rewind(&mut self) {
+        self.last_row_idx = None;
+        self.archived = None;
+        self.started = false;
+    }
+}
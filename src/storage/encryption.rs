@@ -0,0 +1,80 @@
+use anyhow::Result;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+
+use crate::config::{DatabaseConfig, EncryptionKeySource};
+
+/// Read the configured encryption key, or `None` if the database is not encrypted
+pub fn resolve_key(config: &DatabaseConfig) -> Result<Option<String>> {
+    let key = match &config.encryption_key {
+        None => return Ok(None),
+        Some(EncryptionKeySource::Env { var }) => std::env::var(var)
+            .map_err(|_| anyhow::anyhow!("Database encryption key variable {} is not set", var))?,
+        Some(EncryptionKeySource::Keyring { service, account }) => keyring_password(service, account)?,
+    };
+
+    if key.is_empty() {
+        return Err(anyhow::anyhow!("Database encryption key is empty"));
+    }
+    Ok(Some(key))
+}
+
+#[cfg(feature = "sqlcipher")]
+fn keyring_password(service: &str, account: &str) -> Result<String> {
+    keyring::Entry::new(service, account)
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| anyhow::anyhow!("Cannot read database key {}/{} from the credential store: {}", service, account, e))
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn keyring_password(_service: &str, _account: &str) -> Result<String> {
+    Err(anyhow::anyhow!("Reading the database key from the credential store requires the sqlcipher feature"))
+}
+
+/// Open a database file, unlocking it with `key` if given
+///
+/// Fails with a clear message when the key is wrong or the file isn't
+/// encrypted, instead of on the first query.
+pub fn open(path: &Path, flags: OpenFlags, key: Option<&str>) -> Result<Connection> {
+    let conn = Connection::open_with_flags(path, flags)?;
+    if let Some(key) = key {
+        apply_key(&conn, key)?;
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get::<_, i64>(0))
+            .map_err(|_| anyhow::anyhow!(
+                "Cannot unlock {}: the encryption key is wrong or the file is not encrypted",
+                path.display()
+            ))?;
+    }
+    Ok(conn)
+}
+
+#[cfg(feature = "sqlcipher")]
+fn apply_key(conn: &Connection, key: &str) -> Result<()> {
+    conn.pragma_update(None, "key", key)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn apply_key(_conn: &Connection, _key: &str) -> Result<()> {
+    // Plain SQLite ignores the key pragma and would write unencrypted data
+    Err(anyhow::anyhow!("Database encryption requires building with the sqlcipher feature"))
+}
+
+#[cfg(all(test, feature = "sqlcipher"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_database_needs_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret.db");
+        let conn = open(&path, OpenFlags::default(), Some("correct horse")).unwrap();
+        conn.execute_batch("CREATE TABLE jobs (id TEXT); INSERT INTO jobs VALUES ('a');").unwrap();
+        drop(conn);
+
+        assert!(open(&path, OpenFlags::default(), Some("wrong")).is_err());
+        let plain = Connection::open(&path).unwrap();
+        assert!(plain.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get::<_, i64>(0)).is_err());
+        assert!(open(&path, OpenFlags::default(), Some("correct horse")).is_ok());
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use rusqlite::{params, OptionalExtension};
+
+use super::StorageManager;
+use crate::export::ExportProfile;
+
+impl StorageManager {
+    /// Save an export profile, replacing any profile of the same name
+    pub async fn save_export_profile(&self, profile: &ExportProfile) -> Result<()> {
+        let conn = self.pool.get().await;
+        conn.execute(
+            "INSERT INTO export_profiles (name, profile_json, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET profile_json = excluded.profile_json, updated_at = excluded.updated_at",
+            params![profile.name, serde_json::to_string(profile)?, chrono::Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    /// Export profile by name
+    pub async fn get_export_profile(&self, name: &str) -> Result<Option<ExportProfile>> {
+        let conn = self.pool.get().await;
+        let json: Option<String> = conn.query_row(
+            "SELECT profile_json FROM export_profiles WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        ).optional()?;
+        json.map(|json| Ok(serde_json::from_str(&json)?)).transpose()
+    }
+
+    /// Every saved export profile, by name
+    pub async fn list_export_profiles(&self) -> Result<Vec<ExportProfile>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare("SELECT profile_json FROM export_profiles ORDER BY name")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.iter().map(|json| Ok(serde_json::from_str(json)?)).collect()
+    }
+
+    /// Delete an export profile, returning whether it existed
+    pub async fn delete_export_profile(&self, name: &str) -> Result<bool> {
+        let conn = self.pool.get().await;
+        let removed = conn.execute("DELETE FROM export_profiles WHERE name = ?1", params![name])?;
+        Ok(removed > 0)
+    }
+}
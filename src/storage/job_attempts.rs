@@ -0,0 +1,131 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+use super::StorageManager;
+
+/// One run of a job, recorded when it ends
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobAttempt {
+    pub job_id: String,
+    /// 1 for the first run
+    pub attempt: u32,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub outcome: AttemptOutcome,
+    /// URLs the run fetched
+    pub urls: usize,
+    pub failed_urls: usize,
+    /// Rows the job had stored when the run ended
+    pub rows: usize,
+    pub error: Option<String>,
+}
+
+/// How a run of a job ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptOutcome {
+    Completed,
+    /// Completed, but some URLs failed
+    Partial,
+    Failed,
+    Cancelled,
+}
+
+impl AttemptOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttemptOutcome::Completed => "completed",
+            AttemptOutcome::Partial => "partial",
+            AttemptOutcome::Failed => "failed",
+            AttemptOutcome::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether a retry could do better
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, AttemptOutcome::Partial | AttemptOutcome::Failed)
+    }
+}
+
+impl std::fmt::Display for AttemptOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for AttemptOutcome {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "completed" => Ok(AttemptOutcome::Completed),
+            "partial" => Ok(AttemptOutcome::Partial),
+            "failed" => Ok(AttemptOutcome::Failed),
+            "cancelled" => Ok(AttemptOutcome::Cancelled),
+            _ => Err(anyhow::anyhow!("Invalid attempt outcome: {}", s)),
+        }
+    }
+}
+
+impl StorageManager {
+    /// Record how a run of a job ended
+    pub async fn record_job_attempt(&self, attempt: &JobAttempt) -> Result<()> {
+        let conn = self.pool.get().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO job_attempts
+                (job_id, attempt, started_at, finished_at, outcome, urls, failed_urls, rows, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                attempt.job_id,
+                attempt.attempt,
+                attempt.started_at.timestamp(),
+                attempt.finished_at.timestamp(),
+                attempt.outcome.as_str(),
+                attempt.urls as i64,
+                attempt.failed_urls as i64,
+                attempt.rows as i64,
+                attempt.error,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Runs of a job so far, first one first
+    pub async fn get_job_attempts(&self, job_id: &str) -> Result<Vec<JobAttempt>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare(
+            "SELECT attempt, started_at, finished_at, outcome, urls, failed_urls, rows, error
+             FROM job_attempts WHERE job_id = ?1 ORDER BY attempt"
+        )?;
+        let rows = stmt.query_map(params![job_id], |row| {
+            Ok((
+                row.get::<_, u32>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, i64>(6)?,
+                row.get::<_, Option<String>>(7)?,
+            ))
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter()
+            .map(|(attempt, started_at, finished_at, outcome, urls, failed_urls, rows, error)| {
+                Ok(JobAttempt {
+                    job_id: job_id.to_string(),
+                    attempt,
+                    started_at: DateTime::from_timestamp(started_at, 0).unwrap_or_else(Utc::now),
+                    finished_at: DateTime::from_timestamp(finished_at, 0).unwrap_or_else(Utc::now),
+                    outcome: outcome.parse()?,
+                    urls: urls as usize,
+                    failed_urls: failed_urls as usize,
+                    rows: rows as usize,
+                    error,
+                })
+            })
+            .collect()
+    }
+}
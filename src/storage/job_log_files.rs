@@ -0,0 +1,163 @@
+use anyhow::{Context, Result};
+use chrono::Timelike;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use super::{LogEntry, StorageManager};
+use crate::logging::LogLine;
+
+/// Size past which a job's log file is rotated
+const MAX_JOB_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept per job besides the current one
+const ROTATED_JOB_LOGS: usize = 3;
+
+impl StorageManager {
+    /// Directory per-job log files are written to: `job_logs` next to the database
+    pub fn job_log_dir(&self) -> PathBuf {
+        self.config.path.parent()
+            .map(|dir| dir.join("job_logs"))
+            .unwrap_or_else(|| PathBuf::from("job_logs"))
+    }
+
+    /// Current log file of a job, whether or not it exists yet
+    fn job_log_path(&self, job_id: &str) -> PathBuf {
+        let name: String = job_id.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.job_log_dir().join(format!("{}.log", name))
+    }
+
+    /// Append an entry to its job's log file, rotating the file once it grows too big
+    pub(super) async fn append_job_log_file(&self, log: &LogEntry) -> Result<()> {
+        let path = self.job_log_path(&log.job_id);
+        if tokio::fs::metadata(&path).await.is_ok_and(|m| m.len() >= MAX_JOB_LOG_BYTES) {
+            rotate(&path).await?;
+        } else {
+            tokio::fs::create_dir_all(self.job_log_dir()).await?;
+        }
+
+        // Whole seconds, as in the `logs` table, so a file rebuilt from it reads the same
+        let mut line = LogLine::from(log.clone());
+        line.timestamp = line.timestamp.with_nanosecond(0).unwrap_or(line.timestamp);
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&path).await?;
+        file.write_all(format!("{}\n", line).as_bytes()).await?;
+        Ok(())
+    }
+
+    /// Log file of a job, written from the `logs` table if the job ran before
+    /// per-job files existed
+    pub async fn get_job_log_file(&self, job_id: &str) -> Result<PathBuf> {
+        self.get_job(job_id).await?;
+        let path = self.job_log_path(job_id);
+        if tokio::fs::try_exists(&path).await? {
+            return Ok(path);
+        }
+
+        let text: String = self.get_job_logs(job_id).await?
+            .into_iter()
+            .map(|entry| format!("{}\n", LogLine::from(entry)))
+            .collect();
+        tokio::fs::create_dir_all(self.job_log_dir()).await?;
+        tokio::fs::write(&path, text).await
+            .with_context(|| format!("Failed to write log file {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// A job's whole log as one file's content, rotated parts first
+    pub async fn read_job_log_file(&self, job_id: &str) -> Result<Vec<u8>> {
+        let path = self.get_job_log_file(job_id).await?;
+        let mut content = Vec::new();
+        for n in (1..=ROTATED_JOB_LOGS).rev() {
+            match tokio::fs::read(rotated_path(&path, n)).await {
+                Ok(part) => content.extend(part),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        content.extend(tokio::fs::read(&path).await?);
+        Ok(content)
+    }
+
+    /// Remove a job's log file and its rotated parts
+    pub(super) async fn remove_job_log_files(&self, job_id: &str) {
+        let path = self.job_log_path(job_id);
+        let rotated = (1..=ROTATED_JOB_LOGS).map(|n| rotated_path(&path, n));
+        for file in std::iter::once(path.clone()).chain(rotated) {
+            match tokio::fs::remove_file(&file).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => tracing::warn!("Could not remove job log {}: {}", file.display(), e),
+            }
+        }
+    }
+}
+
+/// `<job>.log.<n>`, the nth newest rotated part of a log file
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// Shift the rotated parts up by one, dropping the oldest, and start a new file
+async fn rotate(path: &Path) -> Result<()> {
+    for n in (1..ROTATED_JOB_LOGS).rev() {
+        match tokio::fs::rename(rotated_path(path, n), rotated_path(path, n + 1)).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    tokio::fs::rename(path, rotated_path(path, 1)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{test_storage, Job, JobStatus};
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_job_log_file_follows_the_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+        storage.create_job(&Job {
+            id: "job-1".to_string(),
+            title: "example.com".to_string(),
+            status: JobStatus::Running,
+            created_at: Utc::now(),
+            plan_yaml: String::new(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }).await.unwrap();
+
+        let entry = |message: &str| LogEntry {
+            job_id: "job-1".to_string(),
+            timestamp: Utc::now(),
+            stage: "fetching".to_string(),
+            level: "warn".to_string(),
+            message: message.to_string(),
+        };
+        storage.store_log(&entry("first page timed out")).await.unwrap();
+        storage.store_log(&entry("retrying")).await.unwrap();
+
+        let text = String::from_utf8(storage.read_job_log_file("job-1").await.unwrap()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" WARN job: fetching: first page timed out"));
+
+        // Rebuilt from the database when the file is gone
+        let path = storage.get_job_log_file("job-1").await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert_eq!(storage.read_job_log_file("job-1").await.unwrap(), text.as_bytes());
+
+        storage.delete_job("job-1").await.unwrap();
+        assert!(!path.exists());
+        assert!(storage.get_job_log_file("job-1").await.is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+
+use super::{LogEntry, StorageManager};
+use crate::logging::LogLine;
+
+/// Stage of the log entry written when a run ends
+pub const FINISHED_STAGE: &str = "finished";
+
+/// Time a job spent in one stage of a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub stage: String,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the job is still in the stage
+    pub finished_at: Option<DateTime<Utc>>,
+    pub duration_secs: i64,
+}
+
+impl StorageManager {
+    /// Latest log entries of all jobs, oldest first
+    pub async fn get_recent_logs(&self, limit: usize) -> Result<Vec<LogEntry>> {
+        let conn = self.pool.get().await;
+
+        let mut stmt = conn.prepare(
+            "SELECT job_id, ts, stage, level, message FROM logs
+             ORDER BY ts DESC, id DESC LIMIT ?1"
+        )?;
+        let mut logs = stmt.query_map(params![limit as i64], |row| {
+            Ok(LogEntry {
+                job_id: row.get(0)?,
+                timestamp: DateTime::from_timestamp(row.get(1)?, 0).unwrap_or_else(Utc::now),
+                stage: row.get(2)?,
+                level: row.get(3)?,
+                message: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        logs.reverse();
+        Ok(logs)
+    }
+}
+
+impl From<LogEntry> for LogLine {
+    fn from(entry: LogEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp,
+            level: entry.level,
+            component: "job".to_string(),
+            message: format!("{}: {}", entry.stage, entry.message),
+            job_id: Some(entry.job_id),
+        }
+    }
+}
+
+/// Stages in the order a job's log entered them, each lasting until the next
+///
+/// The last stage lasts until `now`, unless it is the one that ends a run.
+pub fn stage_timings(logs: &[LogEntry], now: DateTime<Utc>) -> Vec<StageTiming> {
+    let mut timings: Vec<StageTiming> = Vec::new();
+    for entry in logs {
+        if timings.last().is_some_and(|last| last.stage == entry.stage) {
+            continue;
+        }
+        if let Some(last) = timings.last_mut() {
+            last.finished_at = Some(entry.timestamp);
+        }
+        timings.push(StageTiming {
+            stage: entry.stage.clone(),
+            started_at: entry.timestamp,
+            finished_at: None,
+            duration_secs: 0,
+        });
+    }
+
+    if let Some(last) = timings.last_mut().filter(|last| last.stage == FINISHED_STAGE) {
+        last.finished_at = Some(last.started_at);
+    }
+    for timing in &mut timings {
+        let end = timing.finished_at.unwrap_or(now);
+        timing.duration_secs = (end - timing.started_at).num_seconds().max(0);
+    }
+    timings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_timings_run_until_the_next_stage() {
+        let start = Utc::now() - chrono::Duration::seconds(100);
+        let entry = |secs: i64, stage: &str| LogEntry {
+            job_id: "job-1".to_string(),
+            timestamp: start + chrono::Duration::seconds(secs),
+            stage: stage.to_string(),
+            level: "info".to_string(),
+            message: String::new(),
+        };
+        let logs = vec![entry(0, "starting"), entry(2, "fetching"), entry(5, "fetching"), entry(30, "storing")];
+
+        let running = stage_timings(&logs, start + chrono::Duration::seconds(40));
+        let durations: Vec<(&str, i64)> = running.iter().map(|t| (t.stage.as_str(), t.duration_secs)).collect();
+        assert_eq!(durations, vec![("starting", 2), ("fetching", 28), ("storing", 10)]);
+        assert_eq!(running[2].finished_at, None);
+
+        let mut finished = logs.clone();
+        finished.push(entry(33, FINISHED_STAGE));
+        let timings = stage_timings(&finished, Utc::now());
+        assert_eq!(timings[2].duration_secs, 3);
+        assert_eq!(timings[3].duration_secs, 0);
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::StorageManager;
+use crate::scraper::RequestTrace;
+
+/// Latest outcome of one URL of a job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobUrl {
+    pub url: String,
+    pub success: bool,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    /// Items extracted from the page
+    pub items: usize,
+    /// Times the URL was fetched for the job
+    pub attempts: u32,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl JobUrl {
+    /// Short cause of a failure, for grouping, e.g. `HTTP 503`
+    pub fn failure_reason(&self) -> String {
+        match (self.status_code, &self.error) {
+            (Some(code), _) if code >= 400 => format!("HTTP {}", code),
+            (_, Some(error)) => error.lines().next().unwrap_or_default().chars().take(120).collect(),
+            _ => "Unknown error".to_string(),
+        }
+    }
+}
+
+/// URLs of a job that failed, and why
+#[derive(Debug, Clone, Serialize)]
+pub struct FailureReport {
+    pub job_id: String,
+    /// URLs the job fetched
+    pub total_urls: usize,
+    pub succeeded: usize,
+    pub failed: Vec<JobUrl>,
+    /// Failed URLs per reason, most common first
+    pub reasons: Vec<ReasonCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReasonCount {
+    pub reason: String,
+    pub count: usize,
+}
+
+impl StorageManager {
+    /// Record how fetching a URL of a job went, replacing its earlier outcome
+    pub async fn record_job_url(&self, job_id: &str, trace: &RequestTrace, items: usize) -> Result<()> {
+        let conn = self.pool.get().await;
+        conn.execute(
+            "INSERT INTO job_urls (job_id, url, success, status_code, error, items, attempts, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7)
+             ON CONFLICT (job_id, url) DO UPDATE SET
+                success = excluded.success,
+                status_code = excluded.status_code,
+                error = excluded.error,
+                items = excluded.items,
+                attempts = attempts + 1,
+                fetched_at = excluded.fetched_at",
+            params![
+                job_id,
+                trace.url,
+                !trace.is_failure(),
+                trace.status_code,
+                trace.error.as_ref().or(trace.skipped.as_ref()),
+                items as i64,
+                Utc::now().timestamp(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every URL a job fetched with its latest outcome, in the order first fetched
+    pub async fn get_job_urls(&self, job_id: &str) -> Result<Vec<JobUrl>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare(
+            "SELECT url, success, status_code, error, items, attempts, fetched_at
+             FROM job_urls WHERE job_id = ?1 ORDER BY rowid"
+        )?;
+        let urls = stmt.query_map(params![job_id], |row| {
+            Ok(JobUrl {
+                url: row.get(0)?,
+                success: row.get(1)?,
+                status_code: row.get(2)?,
+                error: row.get(3)?,
+                items: row.get::<_, i64>(4)? as usize,
+                attempts: row.get(5)?,
+                fetched_at: DateTime::from_timestamp(row.get(6)?, 0).unwrap_or_else(Utc::now),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+        Ok(urls)
+    }
+
+    /// URLs of a job whose latest fetch failed
+    pub async fn get_failed_job_urls(&self, job_id: &str) -> Result<Vec<String>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare("SELECT url FROM job_urls WHERE job_id = ?1 AND success = 0 ORDER BY rowid")?;
+        let urls = stmt.query_map(params![job_id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(urls)
+    }
+
+    /// Which URLs of a job failed, grouped by reason
+    pub async fn failure_report(&self, job_id: &str) -> Result<FailureReport> {
+        let urls = self.get_job_urls(job_id).await?;
+        let total_urls = urls.len();
+        let failed: Vec<JobUrl> = urls.into_iter().filter(|url| !url.success).collect();
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for url in &failed {
+            *counts.entry(url.failure_reason()).or_default() += 1;
+        }
+        let mut reasons: Vec<ReasonCount> = counts.into_iter()
+            .map(|(reason, count)| ReasonCount { reason, count })
+            .collect();
+        reasons.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.reason.cmp(&b.reason)));
+
+        Ok(FailureReport {
+            job_id: job_id.to_string(),
+            total_urls,
+            succeeded: total_urls - failed.len(),
+            failed,
+            reasons,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{test_storage, Job, JobStatus};
+
+    #[tokio::test]
+    async fn test_latest_outcome_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+        storage.create_job(&Job {
+            id: "job-1".to_string(),
+            title: "example.com".to_string(),
+            status: JobStatus::Completed,
+            created_at: Utc::now(),
+            plan_yaml: String::new(),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }).await.unwrap();
+
+        let page = |url: &str, status_code: u16| RequestTrace { status_code: Some(status_code), ..RequestTrace::new(url) };
+        storage.record_job_url("job-1", &page("https://example.com/1", 200), 5).await.unwrap();
+        storage.record_job_url("job-1", &page("https://example.com/2", 503), 0).await.unwrap();
+        storage.record_job_url("job-1", &page("https://example.com/3", 503), 0).await.unwrap();
+        storage.record_job_url("job-1", &page("https://example.com/3", 200), 2).await.unwrap();
+
+        let report = storage.failure_report("job-1").await.unwrap();
+        assert_eq!((report.total_urls, report.succeeded), (3, 2));
+        assert_eq!(report.failed[0].url, "https://example.com/2");
+        assert_eq!(report.reasons[0].reason, "HTTP 503");
+        assert_eq!(storage.get_job_urls("job-1").await.unwrap()[2].attempts, 2);
+        assert_eq!(storage.get_failed_job_urls("job-1").await.unwrap(), vec!["https://example.com/2"]);
+    }
+}
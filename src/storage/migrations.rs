@@ -3,7 +3,7 @@ use rusqlite::Connection;
 use tracing::info;
 
 /// Database schema version
-const CURRENT_SCHEMA_VERSION: i32 = 1;
+pub const CURRENT_SCHEMA_VERSION: i32 = 15;
 
 /// Run all necessary database migrations
 pub fn run_migrations(conn: &Connection) -> Result<()> {
@@ -34,7 +34,7 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
 }
 
 /// Get current schema version
-fn get_schema_version(conn: &Connection) -> Result<i32> {
+pub fn get_schema_version(conn: &Connection) -> Result<i32> {
     let mut stmt = conn.prepare("SELECT COALESCE(MAX(version), 0) FROM schema_version")?;
     let version: i32 = stmt.query_row([], |row| row.get(0))?;
     Ok(version)
@@ -53,6 +53,20 @@ fn update_schema_version(conn: &Connection, version: i32) -> Result<()> {
 fn apply_migration(conn: &Connection, version: i32) -> Result<()> {
     match version {
         1 => apply_migration_v1(conn),
+        2 => apply_migration_v2(conn),
+        3 => apply_migration_v3(conn),
+        4 => apply_migration_v4(conn),
+        5 => apply_migration_v5(conn),
+        6 => apply_migration_v6(conn),
+        7 => apply_migration_v7(conn),
+        8 => apply_migration_v8(conn),
+        9 => apply_migration_v9(conn),
+        10 => apply_migration_v10(conn),
+        11 => apply_migration_v11(conn),
+        12 => apply_migration_v12(conn),
+        13 => apply_migration_v13(conn),
+        14 => apply_migration_v14(conn),
+        15 => apply_migration_v15(conn),
         _ => Err(anyhow::anyhow!("Unknown migration version: {}", version)),
     }
 }
@@ -129,6 +143,312 @@ fn apply_migration_v1(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Migration v2: Per-domain memory of selectors that worked in past jobs
+fn apply_migration_v2(conn: &Connection) -> Result<()> {
+    info!("Applying migration v2: Selector memory");
+    
+    conn.execute(
+        "CREATE TABLE selector_memory (
+            domain TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            field_name TEXT NOT NULL DEFAULT '',
+            selector TEXT NOT NULL,
+            successes INTEGER NOT NULL DEFAULT 0,
+            failures INTEGER NOT NULL DEFAULT 0,
+            last_used_at INTEGER NOT NULL,
+            PRIMARY KEY (domain, kind, field_name, selector)
+        )",
+        [],
+    )?;
+    
+    conn.execute("CREATE INDEX idx_selector_memory_last_used ON selector_memory (last_used_at)", [])?;
+    
+    info!("Migration v2 completed successfully");
+    Ok(())
+}
+
+/// Migration v3: Track which job a job was cloned from
+fn apply_migration_v3(conn: &Connection) -> Result<()> {
+    info!("Applying migration v3: Job lineage");
+    
+    conn.execute("ALTER TABLE jobs ADD COLUMN cloned_from TEXT", [])?;
+    conn.execute("CREATE INDEX idx_jobs_cloned_from ON jobs (cloned_from)", [])?;
+    
+    info!("Migration v3 completed successfully");
+    Ok(())
+}
+
+/// Migration v4: Store per-job run summaries
+fn apply_migration_v4(conn: &Connection) -> Result<()> {
+    info!("Applying migration v4: Job summaries");
+    
+    conn.execute("ALTER TABLE jobs ADD COLUMN summary_json TEXT", [])?;
+    
+    info!("Migration v4 completed successfully");
+    Ok(())
+}
+
+/// Migration v5: Named plan templates
+fn apply_migration_v5(conn: &Connection) -> Result<()> {
+    info!("Applying migration v5: Plan templates");
+    
+    conn.execute(
+        "CREATE TABLE plan_templates (
+            name TEXT PRIMARY KEY,
+            description TEXT NOT NULL,
+            plan_yaml TEXT NOT NULL,
+            variables_json TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    
+    info!("Migration v5 completed successfully");
+    Ok(())
+}
+
+/// Migration v6: Local plan library
+fn apply_migration_v6(conn: &Connection) -> Result<()> {
+    info!("Applying migration v6: Plan library");
+    
+    conn.execute(
+        "CREATE TABLE plan_library (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            plan_yaml TEXT NOT NULL,
+            source TEXT,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    
+    conn.execute("CREATE INDEX idx_plan_library_name ON plan_library (name)", [])?;
+    
+    info!("Migration v6 completed successfully");
+    Ok(())
+}
+
+/// Migration v7: Configurable result hashes
+///
+/// Hashes over a subset of fields may repeat within a job, so the results table is
+/// rebuilt without its `UNIQUE (job_id, hash)` constraint.
+fn apply_migration_v7(conn: &Connection) -> Result<()> {
+    info!("Applying migration v7: Configurable result hashes");
+    
+    conn.execute("ALTER TABLE jobs ADD COLUMN hash_fields_json TEXT", [])?;
+    
+    conn.execute(
+        "CREATE TABLE results_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            job_id TEXT NOT NULL,
+            row_idx INTEGER NOT NULL,
+            data_json TEXT NOT NULL,
+            url TEXT NOT NULL,
+            fetched_at INTEGER NOT NULL,
+            hash TEXT NOT NULL,
+            FOREIGN KEY (job_id) REFERENCES jobs (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute(
+        "INSERT INTO results_new (id, job_id, row_idx, data_json, url, fetched_at, hash)
+         SELECT id, job_id, row_idx, data_json, url, fetched_at, hash FROM results",
+        [],
+    )?;
+    conn.execute("DROP TABLE results", [])?;
+    conn.execute("ALTER TABLE results_new RENAME TO results", [])?;
+    
+    conn.execute("CREATE INDEX idx_results_job_id ON results (job_id, row_idx)", [])?;
+    conn.execute("CREATE INDEX idx_results_hash ON results (hash)", [])?;
+    
+    info!("Migration v7 completed successfully");
+    Ok(())
+}
+
+/// Migration v8: Job tags and per-job domain for filtering
+fn apply_migration_v8(conn: &Connection) -> Result<()> {
+    info!("Applying migration v8: Job tags");
+    
+    conn.execute(
+        "CREATE TABLE job_tags (
+            job_id TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            PRIMARY KEY (job_id, tag),
+            FOREIGN KEY (job_id) REFERENCES jobs (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX idx_job_tags_tag ON job_tags (tag)", [])?;
+    
+    conn.execute("ALTER TABLE jobs ADD COLUMN domain TEXT", [])?;
+    let mut stmt = conn.prepare("SELECT id, plan_yaml FROM jobs")?;
+    let plans = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (id, plan_yaml) in plans {
+        if let Some(domain) = super::plan_domain(&plan_yaml) {
+            conn.execute("UPDATE jobs SET domain = ?1 WHERE id = ?2", [domain, id])?;
+        }
+    }
+    conn.execute("CREATE INDEX idx_jobs_domain ON jobs (domain)", [])?;
+    
+    info!("Migration v8 completed successfully");
+    Ok(())
+}
+
+/// Migration v9: Results archived to Parquet files
+fn apply_migration_v9(conn: &Connection) -> Result<()> {
+    info!("Applying migration v9: Result archive");
+    
+    conn.execute(
+        "CREATE TABLE archived_results (
+            job_id TEXT PRIMARY KEY,
+            path TEXT NOT NULL,
+            row_count INTEGER NOT NULL,
+            archived_at INTEGER NOT NULL,
+            FOREIGN KEY (job_id) REFERENCES jobs (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    info!("Migration v9 completed successfully");
+    Ok(())
+}
+
+/// Migration v10: Items seen by earlier runs of a plan
+///
+/// Entries outlive the jobs that recorded them, so there is no foreign key.
+fn apply_migration_v10(conn: &Connection) -> Result<()> {
+    info!("Applying migration v10: Seen item index");
+    
+    conn.execute(
+        "CREATE TABLE seen_items (
+            plan_key TEXT NOT NULL,
+            item_hash TEXT NOT NULL,
+            first_job_id TEXT NOT NULL,
+            first_seen_at INTEGER NOT NULL,
+            PRIMARY KEY (plan_key, item_hash)
+        ) WITHOUT ROWID",
+        [],
+    )?;
+    
+    info!("Migration v10 completed successfully");
+    Ok(())
+}
+
+/// Migration v11: Saved export profiles
+fn apply_migration_v11(conn: &Connection) -> Result<()> {
+    info!("Applying migration v11: Export profiles");
+    
+    conn.execute(
+        "CREATE TABLE export_profiles (
+            name TEXT PRIMARY KEY,
+            profile_json TEXT NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    
+    info!("Migration v11 completed successfully");
+    Ok(())
+}
+
+fn apply_migration_v12(conn: &Connection) -> Result<()> {
+    info!("Applying migration v12: API users and job ownership");
+    
+    conn.execute(
+        "CREATE TABLE users (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            token_hash TEXT NOT NULL UNIQUE,
+            is_admin INTEGER NOT NULL DEFAULT 0,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    
+    // Jobs created before users existed, or outside the API, have no owner
+    conn.execute("ALTER TABLE jobs ADD COLUMN owner_id TEXT", [])?;
+    conn.execute("CREATE INDEX idx_jobs_owner ON jobs(owner_id)", [])?;
+    
+    info!("Migration v12 completed successfully");
+    Ok(())
+}
+
+/// Migration v13: Outcome of every run of a job
+fn apply_migration_v13(conn: &Connection) -> Result<()> {
+    info!("Applying migration v13: Job attempts");
+    
+    conn.execute(
+        "CREATE TABLE job_attempts (
+            job_id TEXT NOT NULL,
+            attempt INTEGER NOT NULL,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER NOT NULL,
+            outcome TEXT NOT NULL,
+            urls INTEGER NOT NULL,
+            failed_urls INTEGER NOT NULL,
+            rows INTEGER NOT NULL,
+            error TEXT,
+            PRIMARY KEY (job_id, attempt),
+            FOREIGN KEY (job_id) REFERENCES jobs (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    info!("Migration v13 completed successfully");
+    Ok(())
+}
+
+/// Migration v14: Latest outcome of every URL of a job
+fn apply_migration_v14(conn: &Connection) -> Result<()> {
+    info!("Applying migration v14: Job URLs");
+    
+    conn.execute(
+        "CREATE TABLE job_urls (
+            job_id TEXT NOT NULL,
+            url TEXT NOT NULL,
+            success INTEGER NOT NULL,
+            status_code INTEGER,
+            error TEXT,
+            items INTEGER NOT NULL,
+            attempts INTEGER NOT NULL,
+            fetched_at INTEGER NOT NULL,
+            PRIMARY KEY (job_id, url),
+            FOREIGN KEY (job_id) REFERENCES jobs (id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+    
+    info!("Migration v14 completed successfully");
+    Ok(())
+}
+
+/// Migration v15: Security events recorded by the audit logger
+fn apply_migration_v15(conn: &Connection) -> Result<()> {
+    info!("Applying migration v15: Audit events");
+    
+    conn.execute(
+        "CREATE TABLE audit_events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ts INTEGER NOT NULL,
+            event_type TEXT NOT NULL,
+            severity TEXT NOT NULL,
+            description TEXT NOT NULL,
+            job_id TEXT,
+            domain TEXT,
+            user_id TEXT
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX idx_audit_events_ts ON audit_events (ts)", [])?;
+    
+    info!("Migration v15 completed successfully");
+    Ok(())
+}
+
 // Future migrations can be added here
 // Example:
 // fn apply_migration_v2(conn: &Connection) -> Result<()> {
@@ -1,23 +1,60 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use rusqlite::{Connection, params, OptionalExtension};
+use rusqlite::{params, OptionalExtension, TransactionBehavior};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::Mutex;
 use tracing::{info, debug};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 pub mod migrations;
 pub mod cache;
+pub mod selector_memory;
+pub mod column_stats;
+pub mod templates;
+pub mod plan_library;
+pub mod change_detection;
+pub mod pool;
+pub mod backup;
+pub mod encryption;
+pub mod tags;
+pub mod archive;
+pub mod seen_items;
+pub mod cursor;
+pub mod export_profiles;
+pub mod users;
+pub mod job_attempts;
+pub mod job_urls;
+pub mod job_logs;
+pub mod job_log_files;
+pub mod audit_events;
+
+pub use selector_memory::{SelectorKind, SelectorMemoryEntry};
+pub use column_stats::{ColumnStats, DayCount, Histogram, ValueCount};
+pub use templates::PlanTemplate;
+pub use plan_library::LibraryPlan;
+pub use change_detection::{content_hash, ResultChanges};
+pub use pool::{ConnectionPool, PooledConnection};
+pub use backup::BackupInfo;
+pub use tags::{JobFilter, TagCount};
+pub use archive::ArchiveReport;
+pub use cursor::ResultCursor;
+pub use users::User;
+pub use job_attempts::{AttemptOutcome, JobAttempt};
+pub use job_urls::{FailureReport, JobUrl, ReasonCount};
+pub use job_logs::{stage_timings, StageTiming, FINISHED_STAGE};
+pub use audit_events::AuditEventFilter;
 
 use crate::config::DatabaseConfig;
+use crate::error::WinScrapeError;
 
 /// Storage manager for SQLite database operations with connection pooling
 pub struct StorageManager {
-    connection: Arc<Mutex<Connection>>,
+    pool: Arc<ConnectionPool>,
     config: DatabaseConfig,
-    prepared_statements: Arc<RwLock<HashMap<String, rusqlite::Statement<'static>>>>,
+    /// SQLCipher key, also needed to open backups
+    encryption_key: Option<String>,
     performance_metrics: Arc<Mutex<PerformanceMetrics>>,
 }
 
@@ -49,6 +86,46 @@ pub struct Job {
     pub plan_yaml: String,
     pub user_prompt: String,
     pub settings_json: Option<String>,
+    /// ID of the job this one was cloned from, if any
+    #[serde(default)]
+    pub cloned_from: Option<String>,
+    /// Counts from the last run, serialized `ScrapeSummary`
+    #[serde(default)]
+    pub summary_json: Option<String>,
+}
+
+/// Columns selected when loading a `Job`, in the order `row_to_job` expects
+const JOB_COLUMNS: &str = "id, title, status, created_at, plan_yaml, user_prompt, settings_json, cloned_from, summary_json";
+
+/// Map a row selected with `JOB_COLUMNS` to a `Job`
+fn row_to_job(row: &rusqlite::Row<'_>) -> rusqlite::Result<Job> {
+    Ok(Job {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        status: row.get::<_, String>(2)?.parse().unwrap_or(JobStatus::Failed),
+        created_at: DateTime::from_timestamp(row.get(3)?, 0).unwrap_or_else(Utc::now),
+        plan_yaml: row.get(4)?,
+        user_prompt: row.get(5)?,
+        settings_json: row.get(6)?,
+        cloned_from: row.get(7)?,
+        summary_json: row.get(8)?,
+    })
+}
+
+/// Delete the archive file of a deleted job; a leftover file is only logged
+async fn remove_archive_file(path: &std::path::Path) {
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        tracing::warn!("Could not remove result archive {}: {}", path.display(), e);
+    }
+}
+
+/// Target domain of a plan, kept in its own column so jobs can be filtered by it
+///
+/// Reads the YAML loosely so jobs with plans that no longer validate still get one.
+fn plan_domain(plan_yaml: &str) -> Option<String> {
+    let plan: serde_yaml::Value = serde_yaml::from_str(plan_yaml).ok()?;
+    let domain = plan.get("target")?.get("domain")?.as_str()?.trim().to_lowercase();
+    Some(domain).filter(|d| !d.is_empty())
 }
 
 /// Job status enumeration
@@ -59,6 +136,8 @@ pub enum JobStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Stopped by a shutdown; resumed on the next start
+    Paused,
 }
 
 impl std::fmt::Display for JobStatus {
@@ -69,6 +148,7 @@ impl std::fmt::Display for JobStatus {
             JobStatus::Completed => write!(f, "completed"),
             JobStatus::Failed => write!(f, "failed"),
             JobStatus::Cancelled => write!(f, "cancelled"),
+            JobStatus::Paused => write!(f, "paused"),
         }
     }
 }
@@ -83,6 +163,7 @@ impl std::str::FromStr for JobStatus {
             "completed" => Ok(JobStatus::Completed),
             "failed" => Ok(JobStatus::Failed),
             "cancelled" => Ok(JobStatus::Cancelled),
+            "paused" => Ok(JobStatus::Paused),
             _ => Err(anyhow::anyhow!("Invalid job status: {}", s)),
         }
     }
@@ -99,6 +180,13 @@ pub struct JobResult {
     pub hash: String,
 }
 
+/// A stored result row with its position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultRow {
+    pub row_idx: i32,
+    pub data: serde_json::Value,
+}
+
 /// Log entry structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
@@ -128,22 +216,18 @@ impl StorageManager {
             tokio::fs::create_dir_all(parent).await?;
         }
         
-        // Open database connection with optimized settings
-        let connection = Connection::open(&config.path)?;
-        
-        // Configure SQLite for optimal performance
-        connection.execute("PRAGMA foreign_keys = ON", [])?;
-        // connection.execute("PRAGMA journal_mode = WAL", [])?;
-        // connection.execute(&format!("PRAGMA cache_size = -{}", config.cache_size_mb * 1024), [])?;
-        // connection.execute("PRAGMA synchronous = NORMAL", [])?;
-        // connection.execute("PRAGMA temp_store = MEMORY", [])?;
-        // connection.execute("PRAGMA mmap_size = 268435456", [])?; // 256MB memory mapping
-        // connection.execute("PRAGMA optimize", [])?; // This can cause issues in some SQLite versions
+        // Open pooled connections with WAL and cache settings from the config
+        let encryption_key = encryption::resolve_key(config)?;
+        let pool = ConnectionPool::open(config, encryption_key.as_deref())?;
+        info!(
+            "Opened {} database connections (WAL: {}, encrypted: {})",
+            pool.size(), config.enable_wal, encryption_key.is_some()
+        );
         
         let storage = Self {
-            connection: Arc::new(Mutex::new(connection)),
+            pool: Arc::new(pool),
             config: config.clone(),
-            prepared_statements: Arc::new(RwLock::new(HashMap::new())),
+            encryption_key,
             performance_metrics: Arc::new(Mutex::new(PerformanceMetrics::default())),
         };
         
@@ -159,11 +243,10 @@ impl StorageManager {
     
     /// Prepare common SQL statements for better performance
     async fn prepare_common_statements(&self) -> Result<()> {
-        let _conn = self.connection.lock().await;
-        let _statements = self.prepared_statements.write().await;
+        let _conn = self.pool.get().await;
         
-        // Note: In a real implementation, you'd need to handle the lifetime issues
-        // with prepared statements. For now, we'll use dynamic queries.
+        // Statements are cached per connection by `prepare_cached`; a map of
+        // `Statement<'static>` here would make the manager `!Send`.
         info!("Prepared common SQL statements for performance optimization");
         Ok(())
     }
@@ -219,20 +302,27 @@ impl StorageManager {
         Ok(stats)
     }
     
+    /// Check that a connection can be had and answers a query
+    pub async fn ping(&self) -> Result<()> {
+        let conn = self.pool.get().await;
+        conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))?;
+        Ok(())
+    }
+    
     /// Run database migrations
     async fn run_migrations(&self) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         migrations::run_migrations(&*conn)?;
         Ok(())
     }
     
     /// Create a new job
     pub async fn create_job(&self, job: &Job) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         conn.execute(
-            "INSERT INTO jobs (id, title, status, created_at, plan_yaml, user_prompt, settings_json)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO jobs (id, title, status, created_at, plan_yaml, user_prompt, settings_json, cloned_from, summary_json, domain)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
             params![
                 job.id,
                 job.title,
@@ -240,7 +330,10 @@ impl StorageManager {
                 job.created_at.timestamp(),
                 job.plan_yaml,
                 job.user_prompt,
-                job.settings_json
+                job.settings_json,
+                job.cloned_from,
+                job.summary_json,
+                plan_domain(&job.plan_yaml)
             ],
         )?;
         
@@ -250,31 +343,20 @@ impl StorageManager {
     
     /// Get job by ID
     pub async fn get_job(&self, job_id: &str) -> Result<Job> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let job = conn.query_row(
-            "SELECT id, title, status, created_at, plan_yaml, user_prompt, settings_json
-             FROM jobs WHERE id = ?1",
+            &format!("SELECT {} FROM jobs WHERE id = ?1", JOB_COLUMNS),
             params![job_id],
-            |row| {
-                Ok(Job {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    status: row.get::<_, String>(2)?.parse().unwrap_or(JobStatus::Failed),
-                    created_at: DateTime::from_timestamp(row.get(3)?, 0).unwrap_or_else(Utc::now),
-                    plan_yaml: row.get(4)?,
-                    user_prompt: row.get(5)?,
-                    settings_json: row.get(6)?,
-                })
-            },
+            row_to_job,
         ).optional()?;
         
-        job.ok_or_else(|| anyhow::anyhow!("Job not found: {}", job_id))
+        job.ok_or_else(|| WinScrapeError::job_not_found(job_id).into())
     }
     
     /// Update job status
     pub async fn update_job_status(&self, job_id: &str, status: JobStatus) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let updated = conn.execute(
             "UPDATE jobs SET status = ?1 WHERE id = ?2",
@@ -282,41 +364,115 @@ impl StorageManager {
         )?;
         
         if updated == 0 {
-            return Err(anyhow::anyhow!("Job not found: {}", job_id));
+            return Err(WinScrapeError::job_not_found(job_id).into());
         }
         
         debug!("Updated job {} status to {}", job_id, status);
         Ok(())
     }
     
+    /// Store the run summary of a job
+    pub async fn update_job_summary(&self, job_id: &str, summary_json: &str) -> Result<()> {
+        let conn = self.pool.get().await;
+        
+        let updated = conn.execute(
+            "UPDATE jobs SET summary_json = ?1 WHERE id = ?2",
+            params![summary_json, job_id],
+        )?;
+        
+        if updated == 0 {
+            return Err(WinScrapeError::job_not_found(job_id).into());
+        }
+        
+        debug!("Updated job {} summary", job_id);
+        Ok(())
+    }
+    
+    /// Replace the plan stored with a job, e.g. once its start URLs are known
+    pub async fn update_job_plan(&self, job_id: &str, plan_yaml: &str) -> Result<()> {
+        let conn = self.pool.get().await;
+        
+        let updated = conn.execute(
+            "UPDATE jobs SET plan_yaml = ?1 WHERE id = ?2",
+            params![plan_yaml, job_id],
+        )?;
+        
+        if updated == 0 {
+            return Err(WinScrapeError::job_not_found(job_id).into());
+        }
+        
+        debug!("Updated job {} plan", job_id);
+        Ok(())
+    }
+    
     /// List recent jobs
     pub async fn list_jobs(&self, limit: usize) -> Result<Vec<Job>> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let mut stmt = conn.prepare(
-            "SELECT id, title, status, created_at, plan_yaml, user_prompt, settings_json
-             FROM jobs ORDER BY created_at DESC LIMIT ?1"
+            &format!("SELECT {} FROM jobs ORDER BY created_at DESC LIMIT ?1", JOB_COLUMNS)
         )?;
         
-        let jobs = stmt.query_map(params![limit], |row| {
-            Ok(Job {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                status: row.get::<_, String>(2)?.parse().unwrap_or(JobStatus::Failed),
-                created_at: DateTime::from_timestamp(row.get(3)?, 0).unwrap_or_else(Utc::now),
-                plan_yaml: row.get(4)?,
-                user_prompt: row.get(5)?,
-                settings_json: row.get(6)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+        let jobs = stmt.query_map(params![limit], row_to_job)?
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(jobs)
+    }
+    
+    /// How many jobs with the same title were created up to and including this one
+    pub async fn get_job_run_number(&self, job: &Job) -> Result<usize> {
+        let conn = self.pool.get().await;
+        
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM jobs WHERE title = ?1 AND created_at <= ?2",
+            params![job.title, job.created_at.timestamp()],
+            |row| row.get(0),
+        )?;
+        
+        Ok((count as usize).max(1))
+    }
+    
+    /// List jobs cloned directly from the given job, newest first
+    pub async fn list_job_clones(&self, job_id: &str) -> Result<Vec<Job>> {
+        let conn = self.pool.get().await;
+        
+        let mut stmt = conn.prepare(
+            &format!("SELECT {} FROM jobs WHERE cloned_from = ?1 ORDER BY created_at DESC", JOB_COLUMNS)
+        )?;
+        
+        let jobs = stmt.query_map(params![job_id], row_to_job)?
+            .collect::<Result<Vec<_>, _>>()?;
         
         Ok(jobs)
     }
     
+    /// Get the chain of jobs a job was cloned from, nearest ancestor first
+    pub async fn get_job_lineage(&self, job_id: &str) -> Result<Vec<Job>> {
+        let mut lineage: Vec<Job> = Vec::new();
+        let mut next = self.get_job(job_id).await?.cloned_from;
+        
+        while let Some(parent_id) = next {
+            // Guard against cycles from hand-edited databases
+            if parent_id == job_id || lineage.iter().any(|j| j.id == parent_id) {
+                break;
+            }
+            
+            match self.get_job(&parent_id).await {
+                Ok(parent) => {
+                    next = parent.cloned_from.clone();
+                    lineage.push(parent);
+                }
+                // The original job may have been deleted
+                Err(_) => break,
+            }
+        }
+        
+        Ok(lineage)
+    }
+    
     /// Store job result
     pub async fn store_job_result(&self, result: &JobResult) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         conn.execute(
             "INSERT INTO results (job_id, row_idx, data_json, url, fetched_at, hash)
@@ -335,8 +491,17 @@ impl StorageManager {
     }
     
     /// Get job results
+    ///
+    /// Results moved to the archive are read back from their Parquet file.
     pub async fn get_job_results(&self, job_id: &str) -> Result<Vec<serde_json::Value>> {
-        let conn = self.connection.lock().await;
+        if let Some(archived) = self.archived_results(job_id).await? {
+            let rows = archive::read_archive(&archived.path).await?;
+            return Ok(rows.iter()
+                .map(|row| serde_json::from_str(&row.data_json).unwrap_or(serde_json::Value::Null))
+                .collect());
+        }
+        
+        let conn = self.pool.get().await;
         
         let mut stmt = conn.prepare(
             "SELECT data_json FROM results WHERE job_id = ?1 ORDER BY row_idx"
@@ -351,9 +516,65 @@ impl StorageManager {
         Ok(results)
     }
     
+    /// Get up to `limit` result rows stored after `after_row_idx`, for tailing a running job
+    pub async fn get_job_results_after(&self, job_id: &str, after_row_idx: Option<i32>, limit: usize) -> Result<Vec<ResultRow>> {
+        if let Some(archived) = self.archived_results(job_id).await? {
+            let after = after_row_idx.unwrap_or(-1);
+            let rows = archive::read_archive(&archived.path).await?;
+            return Ok(rows.into_iter()
+                .filter(|row| row.row_idx > after)
+                .take(limit)
+                .map(|row| ResultRow {
+                    row_idx: row.row_idx,
+                    data: serde_json::from_str(&row.data_json).unwrap_or(serde_json::Value::Null),
+                })
+                .collect());
+        }
+        
+        let conn = self.pool.get().await;
+        
+        let mut stmt = conn.prepare(
+            "SELECT row_idx, data_json FROM results WHERE job_id = ?1 AND row_idx > ?2 ORDER BY row_idx LIMIT ?3"
+        )?;
+        
+        let rows = stmt.query_map(params![job_id, after_row_idx.unwrap_or(-1), limit as i64], |row| {
+            let json_str: String = row.get(1)?;
+            Ok(ResultRow {
+                row_idx: row.get(0)?,
+                data: serde_json::from_str(&json_str).unwrap_or(serde_json::Value::Null),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        
+        Ok(rows)
+    }
+    
+    /// Replace the data of an already stored result row
+    pub async fn update_job_result(&self, result: &JobResult) -> Result<()> {
+        let conn = self.pool.get().await;
+        
+        conn.execute(
+            "UPDATE results SET data_json = ?1, url = ?2, fetched_at = ?3, hash = ?4 WHERE job_id = ?5 AND row_idx = ?6",
+            params![
+                result.data_json,
+                result.url,
+                result.fetched_at.timestamp(),
+                result.hash,
+                result.job_id,
+                result.row_idx
+            ],
+        )?;
+        
+        Ok(())
+    }
+    
     /// Get job result count
     pub async fn get_job_result_count(&self, job_id: &str) -> Result<usize> {
-        let conn = self.connection.lock().await;
+        if let Some(archived) = self.archived_results(job_id).await? {
+            return Ok(archived.row_count);
+        }
+        
+        let conn = self.pool.get().await;
         
         let count: i64 = conn.query_row(
             "SELECT COUNT(*) FROM results WHERE job_id = ?1",
@@ -366,7 +587,7 @@ impl StorageManager {
     
     /// Store log entry
     pub async fn store_log(&self, log: &LogEntry) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         conn.execute(
             "INSERT INTO logs (job_id, ts, stage, level, message)
@@ -379,17 +600,21 @@ impl StorageManager {
                 log.message
             ],
         )?;
+        drop(conn);
         
+        if let Err(e) = self.append_job_log_file(log).await {
+            tracing::warn!("Could not write log file of job {}: {}", log.job_id, e);
+        }
         Ok(())
     }
     
     /// Get job logs
     pub async fn get_job_logs(&self, job_id: &str) -> Result<Vec<LogEntry>> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let mut stmt = conn.prepare(
             "SELECT job_id, ts, stage, level, message FROM logs 
-             WHERE job_id = ?1 ORDER BY ts"
+             WHERE job_id = ?1 ORDER BY ts, id"
         )?;
         
         let logs = stmt.query_map(params![job_id], |row| {
@@ -408,7 +633,7 @@ impl StorageManager {
     
     /// Store cache entry
     pub async fn store_cache(&self, entry: &CacheEntry) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         conn.execute(
             "INSERT OR REPLACE INTO cache (key, value_blob, ttl, created_at)
@@ -426,7 +651,7 @@ impl StorageManager {
     
     /// Get cache entry
     pub async fn get_cache(&self, key: &str) -> Result<Option<CacheEntry>> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let entry = conn.query_row(
             "SELECT key, value_blob, ttl, created_at FROM cache WHERE key = ?1",
@@ -458,7 +683,7 @@ impl StorageManager {
     
     /// Clean expired cache entries
     pub async fn clean_expired_cache(&self) -> Result<usize> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let deleted = conn.execute(
             "DELETE FROM cache WHERE ttl IS NOT NULL AND ttl < ?1",
@@ -474,24 +699,65 @@ impl StorageManager {
     
     /// Delete job and all related data
     pub async fn delete_job(&self, job_id: &str) -> Result<()> {
-        let conn = self.connection.lock().await;
+        let archived = self.archived_results(job_id).await?;
+        let conn = self.pool.get().await;
         
         // Delete in order due to foreign key constraints
         conn.execute("DELETE FROM logs WHERE job_id = ?1", params![job_id])?;
         conn.execute("DELETE FROM results WHERE job_id = ?1", params![job_id])?;
+        conn.execute("DELETE FROM archived_results WHERE job_id = ?1", params![job_id])?;
         let deleted = conn.execute("DELETE FROM jobs WHERE id = ?1", params![job_id])?;
         
         if deleted == 0 {
-            return Err(anyhow::anyhow!("Job not found: {}", job_id));
+            return Err(WinScrapeError::job_not_found(job_id).into());
         }
+        if let Some(archived) = archived {
+            remove_archive_file(&archived.path).await;
+        }
+        self.remove_job_log_files(job_id).await;
         
         info!("Deleted job and all related data: {}", job_id);
         Ok(())
     }
     
+    /// Delete several jobs and their results and logs in one transaction
+    ///
+    /// Returns the number of jobs deleted; IDs that don't exist are skipped.
+    pub async fn delete_jobs(&self, job_ids: &[String]) -> Result<usize> {
+        let mut archives = Vec::new();
+        for job_id in job_ids {
+            archives.extend(self.archived_results(job_id).await?);
+        }
+        
+        let deleted = {
+            let mut conn = self.pool.get().await;
+            let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+            
+            let mut deleted = 0;
+            for job_id in job_ids {
+                tx.execute("DELETE FROM logs WHERE job_id = ?1", params![job_id])?;
+                tx.execute("DELETE FROM results WHERE job_id = ?1", params![job_id])?;
+                tx.execute("DELETE FROM archived_results WHERE job_id = ?1", params![job_id])?;
+                deleted += tx.execute("DELETE FROM jobs WHERE id = ?1", params![job_id])?;
+            }
+            tx.commit()?;
+            deleted
+        };
+        
+        for archived in archives {
+            remove_archive_file(&archived.path).await;
+        }
+        for job_id in job_ids {
+            self.remove_job_log_files(job_id).await;
+        }
+        
+        info!("Deleted {} of {} jobs with their related data", deleted, job_ids.len());
+        Ok(deleted)
+    }
+    
     /// Get database statistics
     pub async fn get_statistics(&self) -> Result<DatabaseStatistics> {
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         
         let job_count: i64 = conn.query_row("SELECT COUNT(*) FROM jobs", [], |row| row.get(0))?;
         let result_count: i64 = conn.query_row("SELECT COUNT(*) FROM results", [], |row| row.get(0))?;
@@ -515,7 +781,7 @@ impl StorageManager {
     /// Vacuum database to reclaim space
     pub async fn vacuum(&self) -> Result<()> {
         info!("Starting database vacuum operation");
-        let conn = self.connection.lock().await;
+        let conn = self.pool.get().await;
         conn.execute("VACUUM", [])?;
         info!("Database vacuum completed");
         Ok(())
@@ -553,3 +819,23 @@ pub struct OperationStats {
     pub p95_duration: Duration,
     pub p99_duration: Duration,
 }
+
+/// Settings of a small database in `dir`, for tests
+#[cfg(test)]
+pub(crate) fn test_database_config(dir: &std::path::Path) -> DatabaseConfig {
+    DatabaseConfig {
+        path: dir.join("data.db"),
+        max_connections: 2,
+        enable_wal: true,
+        cache_size_mb: 1,
+        encryption_key: None,
+        archive_after_days: None,
+        archive_dir: None,
+    }
+}
+
+/// Empty store in `dir`, for tests
+#[cfg(test)]
+pub(crate) async fn test_storage(dir: &std::path::Path) -> StorageManager {
+    StorageManager::new(&test_database_config(dir)).await.unwrap()
+}
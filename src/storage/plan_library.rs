@@ -0,0 +1,112 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::StorageManager;
+
+/// A plan kept in the local library, independent of any job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryPlan {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub plan_yaml: String,
+    /// File the plan was imported from, if any
+    pub source: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+const LIBRARY_COLUMNS: &str = "id, name, description, plan_yaml, source, created_at, updated_at";
+
+fn row_to_library_plan(row: &rusqlite::Row<'_>) -> rusqlite::Result<LibraryPlan> {
+    Ok(LibraryPlan {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        plan_yaml: row.get(3)?,
+        source: row.get(4)?,
+        created_at: DateTime::from_timestamp(row.get(5)?, 0).unwrap_or_else(Utc::now),
+        updated_at: DateTime::from_timestamp(row.get(6)?, 0).unwrap_or_else(Utc::now),
+    })
+}
+
+impl StorageManager {
+    /// Add a plan to the library, or update it if the id exists
+    pub async fn save_library_plan(&self, plan: &LibraryPlan) -> Result<()> {
+        let conn = self.pool.get().await;
+
+        conn.execute(
+            "INSERT INTO plan_library (id, name, description, plan_yaml, source, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT (id) DO UPDATE SET
+                name = excluded.name,
+                description = excluded.description,
+                plan_yaml = excluded.plan_yaml,
+                source = excluded.source,
+                updated_at = excluded.updated_at",
+            params![
+                plan.id,
+                plan.name,
+                plan.description,
+                plan.plan_yaml,
+                plan.source,
+                plan.created_at.timestamp(),
+                plan.updated_at.timestamp()
+            ],
+        )?;
+
+        info!("Saved library plan: {} ({})", plan.name, plan.id);
+        Ok(())
+    }
+
+    /// Get a library plan by id
+    pub async fn get_library_plan(&self, id: &str) -> Result<LibraryPlan> {
+        let conn = self.pool.get().await;
+
+        conn.query_row(
+            &format!("SELECT {} FROM plan_library WHERE id = ?1", LIBRARY_COLUMNS),
+            params![id],
+            row_to_library_plan,
+        )
+        .optional()?
+        .ok_or_else(|| anyhow::anyhow!("Library plan not found: {}", id))
+    }
+
+    /// List library plans, most recently updated first, optionally filtered by name
+    pub async fn list_library_plans(&self, name_filter: Option<&str>) -> Result<Vec<LibraryPlan>> {
+        let conn = self.pool.get().await;
+
+        let mut stmt = conn.prepare(
+            &format!(
+                "SELECT {} FROM plan_library WHERE name LIKE ?1 ESCAPE '\\' ORDER BY updated_at DESC",
+                LIBRARY_COLUMNS
+            )
+        )?;
+
+        let pattern = match name_filter {
+            Some(filter) => format!("%{}%", filter.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")),
+            None => "%".to_string(),
+        };
+
+        let plans = stmt.query_map(params![pattern], row_to_library_plan)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(plans)
+    }
+
+    /// Remove a plan from the library
+    pub async fn delete_library_plan(&self, id: &str) -> Result<()> {
+        let conn = self.pool.get().await;
+
+        let deleted = conn.execute("DELETE FROM plan_library WHERE id = ?1", params![id])?;
+        if deleted == 0 {
+            return Err(anyhow::anyhow!("Library plan not found: {}", id));
+        }
+
+        info!("Deleted library plan: {}", id);
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use rusqlite::{Connection, OpenFlags};
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::{debug, warn};
+
+use super::encryption;
+use crate::config::DatabaseConfig;
+
+/// How long a connection waits for another one's write lock before failing
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Bytes of the database file SQLite may memory-map per connection
+const MMAP_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Fixed set of SQLite connections to one database file
+///
+/// With WAL enabled readers don't block each other or the writer, so jobs
+/// storing results and the UI listing jobs can run at the same time. Writers
+/// still take turns; a writer waits up to `BUSY_TIMEOUT` for the lock.
+pub struct ConnectionPool {
+    idle: Mutex<Vec<Connection>>,
+    available: Semaphore,
+    size: usize,
+}
+
+impl ConnectionPool {
+    /// Open `max_connections` connections with the configured pragmas
+    ///
+    /// `key` unlocks an encrypted database; see `encryption::resolve_key`.
+    pub fn open(config: &DatabaseConfig, key: Option<&str>) -> Result<Self> {
+        let size = (config.max_connections as usize).max(1);
+        let connections = (0..size)
+            .map(|_| open_connection(config, key))
+            .collect::<Result<Vec<_>>>()?;
+
+        debug!("Opened {} database connections to {}", size, config.path.display());
+        Ok(Self {
+            idle: Mutex::new(connections),
+            available: Semaphore::new(size),
+            size,
+        })
+    }
+
+    /// Wait for a free connection
+    ///
+    /// The connection goes back to the pool when the returned guard is dropped.
+    pub async fn get(&self) -> PooledConnection<'_> {
+        let permit = self.available.acquire().await
+            .expect("pool semaphore is never closed");
+        let conn = self.idle.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .pop()
+            .expect("a permit guarantees an idle connection");
+
+        PooledConnection {
+            conn: Some(conn),
+            pool: self,
+            _permit: permit,
+        }
+    }
+
+    /// Number of connections in the pool
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Connections not currently in use
+    pub fn idle_count(&self) -> usize {
+        self.available.available_permits()
+    }
+}
+
+/// A connection borrowed from a `ConnectionPool`
+pub struct PooledConnection<'a> {
+    conn: Option<Connection>,
+    pool: &'a ConnectionPool,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection is present until drop")
+    }
+}
+
+impl DerefMut for PooledConnection<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection is present until drop")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        // Runs before the permit is released, so the next waiter finds it idle
+        if let Some(conn) = self.conn.take() {
+            self.pool.idle.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(conn);
+        }
+    }
+}
+
+fn open_connection(config: &DatabaseConfig, key: Option<&str>) -> Result<Connection> {
+    // The key has to be applied before anything else touches the file
+    let conn = encryption::open(&config.path, OpenFlags::default(), key)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+
+    if config.enable_wal {
+        let mode: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+        if mode.eq_ignore_ascii_case("wal") {
+            // Safe against corruption in WAL mode; only the last commits can be lost on power failure
+            conn.pragma_update(None, "synchronous", "NORMAL")?;
+        } else {
+            warn!("Database {} does not support WAL, using journal mode {}", config.path.display(), mode);
+        }
+    }
+
+    // Negative sizes are in KiB
+    conn.pragma_update(None, "cache_size", -((config.cache_size_mb * 1024) as i64))?;
+    conn.pragma_update(None, "temp_store", "MEMORY")?;
+    conn.pragma_update(None, "mmap_size", MMAP_SIZE)?;
+    Ok(conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_database_config;
+
+    #[tokio::test]
+    async fn test_reads_proceed_during_write_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = ConnectionPool::open(&test_database_config(dir.path()), None).unwrap();
+        pool.get().await.execute("CREATE TABLE items (id INTEGER)", []).unwrap();
+
+        let mut writer = pool.get().await;
+        let tx = writer.transaction().unwrap();
+        tx.execute("INSERT INTO items VALUES (1)", []).unwrap();
+        assert_eq!(pool.idle_count(), 1);
+
+        // The uncommitted row is invisible, but reading doesn't wait for the writer
+        let reader = pool.get().await;
+        let count: i64 = reader.query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 0);
+        drop(reader);
+
+        tx.commit().unwrap();
+        drop(writer);
+        assert_eq!(pool.idle_count(), 2);
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use rusqlite::{params, TransactionBehavior};
+use std::collections::HashSet;
+
+use super::StorageManager;
+
+impl StorageManager {
+    /// Item hashes recorded for a plan's seen index
+    pub async fn load_seen_items(&self, plan_key: &str) -> Result<HashSet<String>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare("SELECT item_hash FROM seen_items WHERE plan_key = ?1")?;
+        let hashes = stmt.query_map(params![plan_key], |row| row.get(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(hashes)
+    }
+
+    /// Add item hashes to a plan's seen index, returning how many were new
+    pub async fn mark_items_seen(&self, plan_key: &str, job_id: &str, hashes: &[String]) -> Result<usize> {
+        let now = chrono::Utc::now().timestamp();
+        let mut conn = self.pool.get().await;
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let mut added = 0;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO seen_items (plan_key, item_hash, first_job_id, first_seen_at)
+                 VALUES (?1, ?2, ?3, ?4)"
+            )?;
+            for hash in hashes {
+                added += stmt.execute(params![plan_key, hash, job_id, now])?;
+            }
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    /// Number of items in a plan's seen index
+    pub async fn seen_item_count(&self, plan_key: &str) -> Result<usize> {
+        let conn = self.pool.get().await;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM seen_items WHERE plan_key = ?1",
+            params![plan_key],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Empty a plan's seen index so its next run keeps every item, returning the items removed
+    pub async fn clear_seen_items(&self, plan_key: &str) -> Result<usize> {
+        let conn = self.pool.get().await;
+        let removed = conn.execute("DELETE FROM seen_items WHERE plan_key = ?1", params![plan_key])?;
+        Ok(removed)
+    }
+}
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use super::StorageManager;
+
+/// Days after which a remembered selector's confidence is halved
+const CONFIDENCE_HALF_LIFE_DAYS: f64 = 30.0;
+
+/// Kind of selector remembered for a domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelectorKind {
+    Item,
+    Field,
+    Pagination,
+}
+
+impl std::fmt::Display for SelectorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorKind::Item => write!(f, "item"),
+            SelectorKind::Field => write!(f, "field"),
+            SelectorKind::Pagination => write!(f, "pagination"),
+        }
+    }
+}
+
+impl std::str::FromStr for SelectorKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "item" => Ok(SelectorKind::Item),
+            "field" => Ok(SelectorKind::Field),
+            "pagination" => Ok(SelectorKind::Pagination),
+            _ => Err(anyhow::anyhow!("Invalid selector kind: {}", s)),
+        }
+    }
+}
+
+/// A selector remembered for a domain with its track record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectorMemoryEntry {
+    pub domain: String,
+    pub kind: SelectorKind,
+    /// Field name for field selectors, empty for item/pagination selectors
+    pub field_name: String,
+    pub selector: String,
+    pub successes: u32,
+    pub failures: u32,
+    /// Confidence after aging, in the range 0.0..=1.0
+    pub confidence: f64,
+    pub last_used_at: DateTime<Utc>,
+}
+
+impl SelectorMemoryEntry {
+    /// Confidence from the success ratio, with a prior so single observations stay modest
+    pub fn raw_confidence(successes: u32, failures: u32) -> f64 {
+        (successes as f64 + 1.0) / (successes as f64 + failures as f64 + 2.0)
+    }
+
+    /// Apply exponential aging to a confidence score based on when it was last used
+    pub fn aged_confidence(confidence: f64, last_used_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let age_days = (now - last_used_at).num_seconds().max(0) as f64 / 86_400.0;
+        confidence * 0.5f64.powf(age_days / CONFIDENCE_HALF_LIFE_DAYS)
+    }
+}
+
+impl StorageManager {
+    /// Record whether a selector worked for a domain in a finished job
+    pub async fn record_selector_outcome(
+        &self,
+        domain: &str,
+        kind: SelectorKind,
+        field_name: &str,
+        selector: &str,
+        success: bool,
+    ) -> Result<()> {
+        let conn = self.pool.get().await;
+        let now = Utc::now().timestamp();
+        let (success_inc, failure_inc) = if success { (1, 0) } else { (0, 1) };
+
+        conn.execute(
+            "INSERT INTO selector_memory (domain, kind, field_name, selector, successes, failures, last_used_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT (domain, kind, field_name, selector) DO UPDATE SET
+                successes = successes + excluded.successes,
+                failures = failures + excluded.failures,
+                last_used_at = excluded.last_used_at",
+            params![
+                domain.to_lowercase(),
+                kind.to_string(),
+                field_name,
+                selector,
+                success_inc,
+                failure_inc,
+                now
+            ],
+        )?;
+
+        debug!("Recorded {} {} selector '{}' for {}: {}", kind, field_name, selector, domain, success);
+        Ok(())
+    }
+
+    /// Get remembered selectors for a domain, best first
+    pub async fn get_domain_selectors(&self, domain: &str) -> Result<Vec<SelectorMemoryEntry>> {
+        let conn = self.pool.get().await;
+        let now = Utc::now();
+
+        let mut stmt = conn.prepare(
+            "SELECT domain, kind, field_name, selector, successes, failures, last_used_at
+             FROM selector_memory WHERE domain = ?1"
+        )?;
+
+        let mut entries = stmt.query_map(params![domain.to_lowercase()], |row| {
+            let successes: u32 = row.get(4)?;
+            let failures: u32 = row.get(5)?;
+            let last_used_at = DateTime::from_timestamp(row.get(6)?, 0).unwrap_or_else(Utc::now);
+
+            Ok(SelectorMemoryEntry {
+                domain: row.get(0)?,
+                kind: row.get::<_, String>(1)?.parse().unwrap_or(SelectorKind::Field),
+                field_name: row.get(2)?,
+                selector: row.get(3)?,
+                successes,
+                failures,
+                confidence: SelectorMemoryEntry::aged_confidence(
+                    SelectorMemoryEntry::raw_confidence(successes, failures),
+                    last_used_at,
+                    now,
+                ),
+                last_used_at,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        entries.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(entries)
+    }
+
+    /// Get the best remembered selector of a kind (and field) above a confidence threshold
+    pub async fn get_best_selector(
+        &self,
+        domain: &str,
+        kind: SelectorKind,
+        field_name: &str,
+        min_confidence: f64,
+    ) -> Result<Option<SelectorMemoryEntry>> {
+        let entries = self.get_domain_selectors(domain).await?;
+
+        Ok(entries.into_iter().find(|e| {
+            e.kind == kind && e.field_name == field_name && e.confidence >= min_confidence
+        }))
+    }
+
+    /// Remove selectors that haven't been used within the given number of days
+    pub async fn prune_selector_memory(&self, max_age_days: i64) -> Result<usize> {
+        let conn = self.pool.get().await;
+        let cutoff = (Utc::now() - chrono::Duration::days(max_age_days)).timestamp();
+
+        let deleted = conn.execute(
+            "DELETE FROM selector_memory WHERE last_used_at < ?1",
+            params![cutoff],
+        )?;
+
+        if deleted > 0 {
+            info!("Pruned {} stale selector memory entries", deleted);
+        }
+
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_confidence() {
+        assert_eq!(SelectorMemoryEntry::raw_confidence(0, 0), 0.5);
+        assert!(SelectorMemoryEntry::raw_confidence(9, 1) > 0.8);
+        assert!(SelectorMemoryEntry::raw_confidence(1, 9) < 0.2);
+    }
+
+    #[test]
+    fn test_aged_confidence_halves_after_half_life() {
+        let now = Utc::now();
+        let fresh = SelectorMemoryEntry::aged_confidence(0.8, now, now);
+        let old = SelectorMemoryEntry::aged_confidence(0.8, now - chrono::Duration::days(30), now);
+
+        assert!((fresh - 0.8).abs() < 1e-9);
+        assert!((old - 0.4).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,254 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+
+use super::{row_to_job, Job, JobStatus, StorageManager, JOB_COLUMNS};
+use crate::error::WinScrapeError;
+
+/// Longest tag accepted, in characters
+const MAX_TAG_LEN: usize = 50;
+
+/// Criteria for listing jobs; fields left unset match every job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobFilter {
+    pub status: Option<JobStatus>,
+    pub tag: Option<String>,
+    /// Target domain of the job's plan; subdomains match too
+    pub domain: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// Text contained in the title or prompt, ignoring case
+    pub text: Option<String>,
+    /// ID of the API user who created the job
+    pub owner: Option<String>,
+}
+
+impl JobFilter {
+    /// Whether the filter matches every job
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.tag.is_none()
+            && self.domain.is_none()
+            && self.created_after.is_none()
+            && self.created_before.is_none()
+            && self.text.is_none()
+            && self.owner.is_none()
+    }
+
+    /// SQL condition and its parameters, in placeholder order
+    fn to_sql(&self) -> (String, Vec<Value>) {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        if let Some(status) = &self.status {
+            conditions.push("status = ?");
+            values.push(Value::Text(status.to_string()));
+        }
+        if let Some(tag) = self.tag.as_deref().map(normalize_tag).filter(|t| !t.is_empty()) {
+            conditions.push("id IN (SELECT job_id FROM job_tags WHERE tag = ?)");
+            values.push(Value::Text(tag));
+        }
+        if let Some(domain) = self.domain.as_deref().map(|d| d.trim().to_lowercase()).filter(|d| !d.is_empty()) {
+            conditions.push("(domain = ? OR domain LIKE ? ESCAPE '\\')");
+            values.push(Value::Text(domain.clone()));
+            values.push(Value::Text(format!("%.{}", escape_like(&domain))));
+        }
+        if let Some(after) = self.created_after {
+            conditions.push("created_at >= ?");
+            values.push(Value::Integer(after.timestamp()));
+        }
+        if let Some(before) = self.created_before {
+            conditions.push("created_at < ?");
+            values.push(Value::Integer(before.timestamp()));
+        }
+        if let Some(text) = self.text.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+            let pattern = format!("%{}%", escape_like(text));
+            conditions.push("(title LIKE ? ESCAPE '\\' OR user_prompt LIKE ? ESCAPE '\\')");
+            values.push(Value::Text(pattern.clone()));
+            values.push(Value::Text(pattern));
+        }
+
+        if let Some(owner) = &self.owner {
+            conditions.push("owner_id = ?");
+            values.push(Value::Text(owner.clone()));
+        }
+
+        let sql = if conditions.is_empty() {
+            "1 = 1".to_string()
+        } else {
+            conditions.join(" AND ")
+        };
+        (sql, values)
+    }
+}
+
+/// A tag and the number of jobs carrying it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagCount {
+    pub tag: String,
+    pub job_count: usize,
+}
+
+/// Tags are compared without case and surrounding whitespace
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+impl StorageManager {
+    /// Tag a job; tagging it twice with the same tag has no effect
+    pub async fn add_job_tag(&self, job_id: &str, tag: &str) -> Result<()> {
+        let tag = normalize_tag(tag);
+        if tag.is_empty() {
+            return Err(anyhow::anyhow!("Tag cannot be empty"));
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(anyhow::anyhow!("Tag is longer than {} characters", MAX_TAG_LEN));
+        }
+
+        let conn = self.pool.get().await;
+        let exists: bool = conn.query_row(
+            "SELECT COUNT(*) > 0 FROM jobs WHERE id = ?1",
+            params![job_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(WinScrapeError::job_not_found(job_id).into());
+        }
+
+        conn.execute(
+            "INSERT OR IGNORE INTO job_tags (job_id, tag) VALUES (?1, ?2)",
+            params![job_id, tag],
+        )?;
+        info!("Tagged job {} with '{}'", job_id, tag);
+        Ok(())
+    }
+
+    /// Remove a tag from a job, returning whether the job had it
+    pub async fn remove_job_tag(&self, job_id: &str, tag: &str) -> Result<bool> {
+        let conn = self.pool.get().await;
+        let removed = conn.execute(
+            "DELETE FROM job_tags WHERE job_id = ?1 AND tag = ?2",
+            params![job_id, normalize_tag(tag)],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Tags of one job, alphabetically
+    pub async fn get_job_tags(&self, job_id: &str) -> Result<Vec<String>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare("SELECT tag FROM job_tags WHERE job_id = ?1 ORDER BY tag")?;
+        let tags = stmt.query_map(params![job_id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// Tags of several jobs at once, keyed by job ID; untagged jobs are left out
+    pub async fn get_tags_for_jobs(&self, job_ids: &[String]) -> Result<HashMap<String, Vec<String>>> {
+        if job_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.pool.get().await;
+        let placeholders = vec!["?"; job_ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT job_id, tag FROM job_tags WHERE job_id IN ({}) ORDER BY tag",
+            placeholders
+        ))?;
+        let rows = stmt.query_map(params_from_iter(job_ids), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            let (job_id, tag) = row?;
+            tags.entry(job_id).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Every tag in use, most used first
+    pub async fn list_tags(&self) -> Result<Vec<TagCount>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare(
+            "SELECT tag, COUNT(*) FROM job_tags GROUP BY tag ORDER BY COUNT(*) DESC, tag"
+        )?;
+        let tags = stmt.query_map([], |row| {
+            Ok(TagCount {
+                tag: row.get(0)?,
+                job_count: row.get::<_, i64>(1)? as usize,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// Jobs matching `filter`, newest first
+    pub async fn list_jobs_filtered(&self, filter: &JobFilter, limit: usize) -> Result<Vec<Job>> {
+        let (condition, mut values) = filter.to_sql();
+        values.push(Value::Integer(limit as i64));
+
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM jobs WHERE {} ORDER BY created_at DESC LIMIT ?",
+            JOB_COLUMNS, condition
+        ))?;
+        let jobs = stmt.query_map(params_from_iter(values), row_to_job)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_storage;
+
+    fn job(id: &str, domain: &str, status: JobStatus) -> Job {
+        Job {
+            id: id.to_string(),
+            title: format!("Products from {}", domain),
+            status,
+            created_at: Utc::now(),
+            plan_yaml: format!("version: \"1.0\"\ntarget:\n  domain: {}\n  start_urls: []\n", domain),
+            user_prompt: String::new(),
+            settings_json: None,
+            cloned_from: None,
+            summary_json: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_tag_domain_and_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+
+        storage.create_job(&job("a", "shop.example.com", JobStatus::Completed)).await.unwrap();
+        storage.create_job(&job("b", "example.org", JobStatus::Failed)).await.unwrap();
+        storage.add_job_tag("a", " Prices ").await.unwrap();
+        storage.add_job_tag("a", "prices").await.unwrap();
+        assert!(storage.add_job_tag("missing", "prices").await.is_err());
+        assert_eq!(storage.get_job_tags("a").await.unwrap(), vec!["prices"]);
+
+        let ids = |jobs: Vec<Job>| jobs.into_iter().map(|j| j.id).collect::<Vec<_>>();
+        let by_tag = JobFilter { tag: Some("PRICES".to_string()), ..Default::default() };
+        assert_eq!(ids(storage.list_jobs_filtered(&by_tag, 10).await.unwrap()), vec!["a"]);
+
+        let by_domain = JobFilter { domain: Some("example.com".to_string()), ..Default::default() };
+        assert_eq!(ids(storage.list_jobs_filtered(&by_domain, 10).await.unwrap()), vec!["a"]);
+
+        let by_status = JobFilter {
+            status: Some(JobStatus::Failed),
+            text: Some("org".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(storage.list_jobs_filtered(&by_status, 10).await.unwrap()), vec!["b"]);
+        assert_eq!(storage.list_jobs_filtered(&JobFilter::default(), 10).await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::StorageManager;
+
+/// A named, reusable plan with `${name}` variables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanTemplate {
+    pub name: String,
+    pub description: String,
+    pub plan_yaml: String,
+    /// Variables the plan expects, sorted
+    pub variables: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+const TEMPLATE_COLUMNS: &str = "name, description, plan_yaml, variables_json, created_at, updated_at";
+
+fn row_to_template(row: &rusqlite::Row<'_>) -> rusqlite::Result<PlanTemplate> {
+    let variables_json: String = row.get(3)?;
+
+    Ok(PlanTemplate {
+        name: row.get(0)?,
+        description: row.get(1)?,
+        plan_yaml: row.get(2)?,
+        variables: serde_json::from_str(&variables_json).unwrap_or_default(),
+        created_at: DateTime::from_timestamp(row.get(4)?, 0).unwrap_or_else(Utc::now),
+        updated_at: DateTime::from_timestamp(row.get(5)?, 0).unwrap_or_else(Utc::now),
+    })
+}
+
+impl StorageManager {
+    /// Create or replace a named plan template
+    pub async fn save_plan_template(&self, template: &PlanTemplate) -> Result<()> {
+        let conn = self.pool.get().await;
+
+        conn.execute(
+            "INSERT INTO plan_templates (name, description, plan_yaml, variables_json, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT (name) DO UPDATE SET
+                description = excluded.description,
+                plan_yaml = excluded.plan_yaml,
+                variables_json = excluded.variables_json,
+                updated_at = excluded.updated_at",
+            params![
+                template.name,
+                template.description,
+                template.plan_yaml,
+                serde_json::to_string(&template.variables)?,
+                template.created_at.timestamp(),
+                template.updated_at.timestamp()
+            ],
+        )?;
+
+        info!("Saved plan template: {}", template.name);
+        Ok(())
+    }
+
+    /// Get a plan template by name
+    pub async fn get_plan_template(&self, name: &str) -> Result<PlanTemplate> {
+        let conn = self.pool.get().await;
+
+        conn.query_row(
+            &format!("SELECT {} FROM plan_templates WHERE name = ?1", TEMPLATE_COLUMNS),
+            params![name],
+            row_to_template,
+        )
+        .optional()?
+        .ok_or_else(|| anyhow::anyhow!("Template not found: {}", name))
+    }
+
+    /// List plan templates by name
+    pub async fn list_plan_templates(&self) -> Result<Vec<PlanTemplate>> {
+        let conn = self.pool.get().await;
+
+        let mut stmt = conn.prepare(
+            &format!("SELECT {} FROM plan_templates ORDER BY name", TEMPLATE_COLUMNS)
+        )?;
+
+        let templates = stmt.query_map([], row_to_template)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(templates)
+    }
+
+    /// Delete a plan template
+    pub async fn delete_plan_template(&self, name: &str) -> Result<()> {
+        let conn = self.pool.get().await;
+
+        let deleted = conn.execute("DELETE FROM plan_templates WHERE name = ?1", params![name])?;
+        if deleted == 0 {
+            return Err(anyhow::anyhow!("Template not found: {}", name));
+        }
+
+        info!("Deleted plan template: {}", name);
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use rand::RngCore;
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{StorageManager, TagCount};
+use crate::error::WinScrapeError;
+
+/// Prefix of issued API tokens, so leaked ones are easy to spot
+const TOKEN_PREFIX: &str = "wss_";
+
+/// Someone allowed to use the API with their own token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+    /// Admins see every job and manage users, profiles and the database
+    pub is_admin: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+fn row_to_user(row: &Row) -> rusqlite::Result<User> {
+    Ok(User {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        is_admin: row.get(2)?,
+        created_at: Utc.timestamp_opt(row.get(3)?, 0).single().unwrap_or_default(),
+    })
+}
+
+/// Only the hash of a token is stored
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+impl StorageManager {
+    /// Add a user, returning it with its token; the token can't be read back later
+    pub async fn create_user(&self, name: &str, is_admin: bool) -> Result<(User, String)> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("User name must not be empty"));
+        }
+
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let token = format!("{}{}", TOKEN_PREFIX, hex::encode(secret));
+        let user = User {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            is_admin,
+            // Stored as whole seconds
+            created_at: Utc.timestamp_opt(Utc::now().timestamp(), 0).single().unwrap_or_default(),
+        };
+
+        let conn = self.pool.get().await;
+        let taken: bool = conn.query_row("SELECT COUNT(*) > 0 FROM users WHERE name = ?1", params![user.name], |row| row.get(0))?;
+        if taken {
+            return Err(anyhow::anyhow!("A user named '{}' already exists", user.name));
+        }
+        conn.execute(
+            "INSERT INTO users (id, name, token_hash, is_admin, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![user.id, user.name, hash_token(&token), user.is_admin, user.created_at.timestamp()],
+        )?;
+        Ok((user, token))
+    }
+
+    /// User a token was issued to
+    pub async fn find_user_by_token(&self, token: &str) -> Result<Option<User>> {
+        let conn = self.pool.get().await;
+        let user = conn.query_row(
+            "SELECT id, name, is_admin, created_at FROM users WHERE token_hash = ?1",
+            params![hash_token(token)],
+            row_to_user,
+        ).optional()?;
+        Ok(user)
+    }
+
+    /// Every user, by name
+    pub async fn list_users(&self) -> Result<Vec<User>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare("SELECT id, name, is_admin, created_at FROM users ORDER BY name")?;
+        let users = stmt.query_map([], row_to_user)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(users)
+    }
+
+    /// Remove a user, returning whether it existed; their jobs are kept
+    pub async fn delete_user(&self, user_id: &str) -> Result<bool> {
+        let conn = self.pool.get().await;
+        let removed = conn.execute("DELETE FROM users WHERE id = ?1", params![user_id])?;
+        Ok(removed > 0)
+    }
+
+    /// Record which user created a job
+    pub async fn set_job_owner(&self, job_id: &str, owner_id: &str) -> Result<()> {
+        let conn = self.pool.get().await;
+        let updated = conn.execute("UPDATE jobs SET owner_id = ?1 WHERE id = ?2", params![owner_id, job_id])?;
+        if updated == 0 {
+            return Err(WinScrapeError::job_not_found(job_id).into());
+        }
+        Ok(())
+    }
+
+    /// User who created a job; `None` for jobs created outside the API
+    pub async fn get_job_owner(&self, job_id: &str) -> Result<Option<String>> {
+        let conn = self.pool.get().await;
+        let owner: Option<Option<String>> = conn.query_row(
+            "SELECT owner_id FROM jobs WHERE id = ?1",
+            params![job_id],
+            |row| row.get(0),
+        ).optional()?;
+        owner.ok_or_else(|| WinScrapeError::job_not_found(job_id).into())
+    }
+
+    /// Tags on a user's jobs with their job counts, most used first
+    pub async fn list_owner_tags(&self, owner_id: &str) -> Result<Vec<TagCount>> {
+        let conn = self.pool.get().await;
+        let mut stmt = conn.prepare(
+            "SELECT tag, COUNT(*) FROM job_tags
+             WHERE job_id IN (SELECT id FROM jobs WHERE owner_id = ?1)
+             GROUP BY tag ORDER BY COUNT(*) DESC, tag"
+        )?;
+        let tags = stmt.query_map(params![owner_id], |row| {
+            Ok(TagCount {
+                tag: row.get(0)?,
+                job_count: row.get::<_, i64>(1)? as usize,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{test_storage, Job, JobFilter, JobStatus};
+
+    #[tokio::test]
+    async fn test_tokens_and_job_ownership() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = test_storage(dir.path()).await;
+
+        let (alice, token) = storage.create_user("alice", false).await.unwrap();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert!(storage.create_user("alice", true).await.is_err());
+        assert_eq!(storage.find_user_by_token(&token).await.unwrap(), Some(alice.clone()));
+        assert_eq!(storage.find_user_by_token("wss_guess").await.unwrap(), None);
+
+        for id in ["mine", "theirs"] {
+            storage.create_job(&Job {
+                id: id.to_string(),
+                title: id.to_string(),
+                status: JobStatus::Completed,
+                created_at: Utc::now(),
+                plan_yaml: String::new(),
+                user_prompt: String::new(),
+                settings_json: None,
+                cloned_from: None,
+                summary_json: None,
+            }).await.unwrap();
+            storage.add_job_tag(id, id).await.unwrap();
+        }
+        storage.set_job_owner("mine", &alice.id).await.unwrap();
+        assert_eq!(storage.get_job_owner("mine").await.unwrap(), Some(alice.id.clone()));
+        assert_eq!(storage.get_job_owner("theirs").await.unwrap(), None);
+
+        let filter = JobFilter { owner: Some(alice.id.clone()), ..Default::default() };
+        let jobs = storage.list_jobs_filtered(&filter, 10).await.unwrap();
+        assert_eq!(jobs.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(), vec!["mine"]);
+        let tags = storage.list_owner_tags(&alice.id).await.unwrap();
+        assert_eq!(tags.iter().map(|t| t.tag.as_str()).collect::<Vec<_>>(), vec!["mine"]);
+
+        assert!(storage.delete_user(&alice.id).await.unwrap());
+        assert_eq!(storage.find_user_by_token(&token).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,181 @@
+use eframe::egui;
+use egui_plot::{Bar, BarChart, Plot};
+
+use crate::core::JobAnalytics;
+use super::windows_theme::WindowsTheme;
+
+/// Analytics of a job's results, or why they could not be computed
+pub type AnalyticsResult = Result<JobAnalytics, String>;
+
+/// Columns with more missing values than this are highlighted
+const HIGH_NULL_RATE: f64 = 0.5;
+
+/// Window with per-column statistics and charts of a job's results
+pub struct AnalyticsView {
+    pub job_id: String,
+    pub analytics: Option<JobAnalytics>,
+    pub error: Option<String>,
+    pub loading: bool,
+    /// Column whose histogram is shown
+    histogram_column: Option<String>,
+}
+
+/// What the analytics window asked for
+pub enum AnalyticsAction {
+    None,
+    Close,
+    Refresh,
+}
+
+impl AnalyticsView {
+    pub fn new(job_id: String) -> Self {
+        Self {
+            job_id,
+            analytics: None,
+            error: None,
+            loading: true,
+            histogram_column: None,
+        }
+    }
+
+    pub fn set_analytics(&mut self, result: AnalyticsResult) {
+        self.loading = false;
+        match result {
+            Ok(analytics) => {
+                // Prefer a price-like column, since that's what users usually check first
+                let keep = self.histogram_column.as_ref()
+                    .is_some_and(|column| analytics.histograms.iter().any(|h| &h.column == column));
+                if !keep {
+                    self.histogram_column = analytics.histograms.iter()
+                        .find(|h| h.column.to_lowercase().contains("price"))
+                        .or_else(|| analytics.histograms.first())
+                        .map(|h| h.column.clone());
+                }
+                self.analytics = Some(analytics);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, theme: &WindowsTheme) -> AnalyticsAction {
+        let mut action = AnalyticsAction::None;
+        let mut open = true;
+        egui::Window::new(format!("📈 Analytics — {}", self.job_id))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([640.0, 520.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.loading, egui::Button::new("🔄 Refresh")).clicked() {
+                        action = AnalyticsAction::Refresh;
+                    }
+                    if self.loading {
+                        ui.spinner();
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(theme.get_status_color("error"), format!("❌ {}", error));
+                }
+                ui.separator();
+
+                let Some(analytics) = &self.analytics else { return };
+                if analytics.columns.is_empty() {
+                    ui.label("This job has no results yet.");
+                    return;
+                }
+
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    ui.heading("Columns");
+                    render_column_grid(ui, analytics, theme);
+                    ui.add_space(12.0);
+
+                    if !analytics.histograms.is_empty() {
+                        ui.horizontal(|ui| {
+                            ui.heading("Distribution");
+                            egui::ComboBox::from_id_source("analytics_histogram_column")
+                                .selected_text(self.histogram_column.as_deref().unwrap_or_default())
+                                .show_ui(ui, |ui| {
+                                    for histogram in &analytics.histograms {
+                                        ui.selectable_value(&mut self.histogram_column, Some(histogram.column.clone()), &histogram.column);
+                                    }
+                                });
+                        });
+                        let histogram = analytics.histograms.iter()
+                            .find(|h| Some(&h.column) == self.histogram_column.as_ref());
+                        if let Some(histogram) = histogram {
+                            let bars = histogram.counts.iter().enumerate()
+                                .map(|(bin, count)| {
+                                    let low = histogram.min + histogram.bin_width * bin as f64;
+                                    Bar::new(histogram.bin_center(bin), *count as f64)
+                                        .width(histogram.bin_width * 0.9)
+                                        .name(format!("{:.2} – {:.2}", low, low + histogram.bin_width))
+                                })
+                                .collect();
+                            Plot::new("analytics_histogram")
+                                .height(180.0)
+                                .allow_drag(false)
+                                .allow_zoom(false)
+                                .show(ui, |plot_ui| {
+                                    plot_ui.bar_chart(BarChart::new(bars).color(theme.get_status_color("info")).name(&histogram.column));
+                                });
+                        }
+                        ui.add_space(12.0);
+                    }
+
+                    ui.heading("Items per day");
+                    let bars = analytics.items_per_day.iter().enumerate()
+                        .map(|(index, day)| Bar::new(index as f64, day.count as f64).width(0.8).name(day.day.format("%Y-%m-%d")))
+                        .collect();
+                    Plot::new("analytics_items_per_day")
+                        .height(160.0)
+                        .allow_drag(false)
+                        .allow_zoom(false)
+                        .show(ui, |plot_ui| {
+                            plot_ui.bar_chart(BarChart::new(bars).color(theme.get_status_color("success")).name("Items"));
+                        });
+                });
+            });
+
+        if !open {
+            action = AnalyticsAction::Close;
+        }
+        action
+    }
+}
+
+/// Null rate, distinct count and range of every column
+fn render_column_grid(ui: &mut egui::Ui, analytics: &JobAnalytics, theme: &WindowsTheme) {
+    egui::Grid::new("analytics_columns").striped(true).num_columns(7).show(ui, |ui| {
+        for header in ["Column", "Type", "Null rate", "Distinct", "Min", "Max", "Mean"] {
+            ui.strong(header);
+        }
+        ui.end_row();
+
+        for column in &analytics.columns {
+            ui.label(&column.column);
+            ui.label(if column.numeric { "number" } else { "text" });
+            let null_rate = if column.count > 0 { column.nulls as f64 / column.count as f64 } else { 0.0 };
+            let null_label = format!("{:.1}%", null_rate * 100.0);
+            if null_rate > HIGH_NULL_RATE {
+                ui.colored_label(theme.get_status_color("warning"), null_label);
+            } else {
+                ui.label(null_label);
+            }
+            ui.label(column.distinct.to_string());
+            ui.label(column.min.as_ref().map(format_value).unwrap_or_default());
+            ui.label(column.max.as_ref().map(format_value).unwrap_or_default());
+            ui.label(column.mean.map(|mean| format!("{:.2}", mean)).unwrap_or_default());
+            ui.end_row();
+        }
+    });
+}
+
+/// A value without the quotes JSON puts around strings
+fn format_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
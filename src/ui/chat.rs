@@ -2,6 +2,10 @@
 use eframe::egui;
 #[cfg(feature = "ui")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "ui")]
+use std::path::PathBuf;
+#[cfg(feature = "ui")]
+use crate::export::ExportFormat;
 
 /// Chat interface for natural language interaction
 #[cfg(feature = "ui")]
@@ -11,6 +15,12 @@ pub struct ChatInterface {
     pub input_text: String,
     #[serde(skip)]
     pub pending_input: Option<String>,
+    /// Plan YAML the model is still writing
+    #[serde(skip)]
+    pub draft: Option<String>,
+    /// Item limit set with `/limit`, applied to plans started from the chat
+    #[serde(skip)]
+    pub item_limit: Option<usize>,
 }
 
 #[cfg(feature = "ui")]
@@ -20,6 +30,8 @@ impl ChatInterface {
             messages: Vec::new(),
             input_text: String::new(),
             pending_input: None,
+            draft: None,
+            item_limit: None,
         };
         
         // Add welcome message
@@ -38,6 +50,9 @@ impl ChatInterface {
                 for message in &self.messages {
                     self.render_message(ui, message);
                 }
+                if let Some(draft) = &self.draft {
+                    self.render_draft(ui, draft);
+                }
             });
         
         ui.separator();
@@ -47,7 +62,7 @@ impl ChatInterface {
             let input_response = ui.add_sized(
                 [ui.available_width() - 60.0, 25.0],
                 egui::TextEdit::singleline(&mut self.input_text)
-                    .hint_text("Describe what you want to scrape...")
+                    .hint_text("Describe what you want to scrape, or type /help")
             );
             
             let send_button = ui.button("Send");
@@ -90,7 +105,7 @@ impl ChatInterface {
                 ui.vertical(|ui| {
                     ui.label(&message.content);
                     ui.label(
-                        egui::RichText::new(message.timestamp.format("%H:%M:%S").to_string())
+                        egui::RichText::new(crate::utils::time_utils::format_local(&message.timestamp, "%H:%M:%S"))
                             .size(10.0)
                             .color(egui::Color32::GRAY)
                     );
@@ -99,6 +114,29 @@ impl ChatInterface {
         });
     }
     
+    /// Render the plan being generated, with a cursor where text is arriving
+    fn render_draft(&self, ui: &mut egui::Ui, draft: &str) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("🕷️");
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new("Writing plan...").italics().color(egui::Color32::GRAY));
+                    ui.label(egui::RichText::new(format!("{}▌", draft)).monospace());
+                });
+            });
+        });
+    }
+    
+    /// Show the partial plan YAML received so far
+    pub fn set_draft(&mut self, yaml: String) {
+        self.draft = Some(yaml);
+    }
+    
+    /// Remove the partial plan once generation has finished
+    pub fn clear_draft(&mut self) {
+        self.draft = None;
+    }
+    
     pub fn add_user_message(&mut self, content: String) {
         self.messages.push(ChatMessage {
             sender: MessageSender::User,
@@ -144,6 +182,73 @@ pub enum MessageSender {
     Assistant,
 }
 
+/// Help shown for `/help` and unknown commands
+#[cfg(feature = "ui")]
+pub const COMMAND_HELP: &str = "Commands:
+/plan <yaml path> - open a plan file for review
+/run <saved plan> - run a library plan or template by name or ID
+/jobs - list recent jobs
+/export <job id|last> [csv|json|xlsx|parquet] - export a job's results
+/limit <n|off> - cap the items of plans started from the chat
+/help - show this list";
+
+/// Shortcut typed into the chat instead of a description
+#[cfg(feature = "ui")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatCommand {
+    /// Open a plan file for review
+    Plan(PathBuf),
+    /// Run a saved plan by name or ID
+    Run(String),
+    /// List recent jobs
+    Jobs,
+    /// Export a job's results; `job` may be `last`
+    Export { job: String, format: ExportFormat },
+    /// Set or clear the item limit
+    Limit(Option<usize>),
+    Help,
+}
+
+#[cfg(feature = "ui")]
+impl ChatCommand {
+    /// Parse chat input starting with `/`
+    ///
+    /// Returns `None` for ordinary messages, and an error to show in the chat
+    /// for unknown or malformed commands.
+    pub fn parse(input: &str) -> Option<Result<Self, String>> {
+        let input = input.trim().strip_prefix('/')?;
+        let (name, args) = match input.split_once(char::is_whitespace) {
+            Some((name, args)) => (name, args.trim()),
+            None => (input, ""),
+        };
+        
+        let command = match (name.to_lowercase().as_str(), args) {
+            ("plan", "") => Err("Usage: /plan <yaml path>".to_string()),
+            ("plan", path) => Ok(ChatCommand::Plan(PathBuf::from(path.trim_matches('"')))),
+            ("run", "") => Err("Usage: /run <saved plan>".to_string()),
+            ("run", name) => Ok(ChatCommand::Run(name.to_string())),
+            ("jobs", _) => Ok(ChatCommand::Jobs),
+            ("export", args) => {
+                let mut parts = args.split_whitespace();
+                let job = parts.next().unwrap_or("last").to_string();
+                parts.next()
+                    .map_or(Ok(ExportFormat::Csv), str::parse)
+                    .map(|format| ChatCommand::Export { job, format })
+                    .map_err(|e| e.to_string())
+            }
+            ("limit", "off") | ("limit", "none") => Ok(ChatCommand::Limit(None)),
+            ("limit", n) => n.parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(|n| ChatCommand::Limit(Some(n)))
+                .ok_or_else(|| "Usage: /limit <n|off>".to_string()),
+            ("help", _) | ("?", _) => Ok(ChatCommand::Help),
+            _ => Err(format!("Unknown command /{}\n{}", name, COMMAND_HELP)),
+        };
+        Some(command)
+    }
+}
+
 // Stub implementation when UI feature is disabled
 #[cfg(not(feature = "ui"))]
 pub struct ChatInterface;
@@ -154,3 +259,36 @@ impl ChatInterface {
         Self
     }
 }
+
+#[cfg(all(test, feature = "ui"))]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(ChatCommand::parse("collect prices from shop.example.com"), None);
+        assert_eq!(ChatCommand::parse(" /plan \"C:\\plans\\shop.yaml\" "), Some(Ok(ChatCommand::Plan(PathBuf::from("C:\\plans\\shop.yaml")))));
+        assert_eq!(ChatCommand::parse("/RUN Daily prices"), Some(Ok(ChatCommand::Run("Daily prices".to_string()))));
+        assert_eq!(ChatCommand::parse("/jobs"), Some(Ok(ChatCommand::Jobs)));
+        assert_eq!(
+            ChatCommand::parse("/export"),
+            Some(Ok(ChatCommand::Export { job: "last".to_string(), format: ExportFormat::Csv }))
+        );
+        assert_eq!(
+            ChatCommand::parse("/export 1234 json"),
+            Some(Ok(ChatCommand::Export { job: "1234".to_string(), format: ExportFormat::Json }))
+        );
+        assert_eq!(ChatCommand::parse("/limit 25"), Some(Ok(ChatCommand::Limit(Some(25)))));
+        assert_eq!(ChatCommand::parse("/limit off"), Some(Ok(ChatCommand::Limit(None))));
+        assert_eq!(ChatCommand::parse("/?"), Some(Ok(ChatCommand::Help)));
+    }
+    
+    #[test]
+    fn test_parse_rejects_malformed_commands() {
+        assert!(matches!(ChatCommand::parse("/run"), Some(Err(usage)) if usage.starts_with("Usage: /run")));
+        assert!(matches!(ChatCommand::parse("/plan   "), Some(Err(usage)) if usage.starts_with("Usage: /plan")));
+        assert!(matches!(ChatCommand::parse("/limit 0"), Some(Err(_))));
+        assert!(matches!(ChatCommand::parse("/export last pdf"), Some(Err(_))));
+        assert!(matches!(ChatCommand::parse("/frobnicate"), Some(Err(message)) if message.contains(COMMAND_HELP)));
+    }
+}
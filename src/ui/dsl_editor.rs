@@ -0,0 +1,388 @@
+use eframe::egui;
+use egui::text::{LayoutJob, TextFormat};
+use egui::{Color32, FontId};
+
+use crate::dsl::{DSLValidator, ScrapePlan};
+
+/// Rows of a preview, or why it failed
+pub type PreviewResult = Result<Vec<serde_json::Value>, String>;
+
+/// YAML editor for a plan awaiting approval, validated as it is typed
+pub struct DslEditor {
+    pub text: String,
+    /// The text parsed as a plan, when it did
+    plan: Option<ScrapePlan>,
+    issues: Vec<EditorIssue>,
+    /// Rows of the last preview run on the edited plan, or why it failed
+    pub preview: Option<PreviewResult>,
+    pub preview_running: bool,
+}
+
+/// A parse or validation problem, placed on the line it refers to
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditorIssue {
+    /// Zero-based line, when the problem could be placed
+    pub line: Option<usize>,
+    pub path: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+/// What the buttons under the editor asked for
+pub enum EditorAction {
+    None,
+    /// Run a preview of the edited plan
+    Preview(ScrapePlan),
+    /// Replace the plan under review with the edited one
+    Apply(ScrapePlan),
+    Discard,
+}
+
+impl DslEditor {
+    pub fn new(plan: &ScrapePlan) -> Self {
+        let mut editor = Self {
+            text: serde_yaml::to_string(plan).unwrap_or_default(),
+            plan: None,
+            issues: Vec::new(),
+            preview: None,
+            preview_running: false,
+        };
+        editor.check();
+        editor
+    }
+
+    /// The edited plan, when it parses and validates
+    pub fn valid_plan(&self) -> Option<&ScrapePlan> {
+        self.plan.as_ref().filter(|_| self.issues.is_empty())
+    }
+
+    /// Parse and validate the text again
+    pub fn check(&mut self) {
+        self.issues.clear();
+        self.plan = match serde_yaml::from_str::<ScrapePlan>(&self.text) {
+            Ok(plan) => Some(plan),
+            Err(e) => {
+                self.issues.push(EditorIssue {
+                    line: e.location().map(|location| location.line().saturating_sub(1)),
+                    path: String::new(),
+                    message: e.to_string(),
+                    suggestion: None,
+                });
+                return;
+            }
+        };
+
+        if let Some(plan) = &self.plan {
+            self.issues = DSLValidator::new().validate_detailed(plan).into_iter()
+                .map(|issue| EditorIssue {
+                    line: line_for_path(&self.text, &issue.path),
+                    path: issue.path,
+                    message: issue.message,
+                    suggestion: issue.suggestion,
+                })
+                .collect();
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui) -> EditorAction {
+        let error_lines: Vec<usize> = self.issues.iter().filter_map(|issue| issue.line).collect();
+        let dark = ui.visuals().dark_mode;
+        let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+            let mut job = highlight_yaml(text, &error_lines, dark);
+            job.wrap.max_width = wrap_width;
+            ui.fonts(|fonts| fonts.layout_job(job))
+        };
+
+        let response = egui::ScrollArea::vertical()
+            .id_source("dsl_editor_text")
+            .max_height(320.0)
+            .show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(&mut self.text)
+                    .code_editor()
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(15)
+                    .layouter(&mut layouter))
+            })
+            .inner;
+        if response.changed() {
+            self.check();
+        }
+
+        ui.add_space(4.0);
+        if self.issues.is_empty() {
+            ui.colored_label(Color32::from_rgb(80, 170, 90), "✔ Plan is valid");
+        } else {
+            egui::ScrollArea::vertical()
+                .id_source("dsl_editor_issues")
+                .max_height(100.0)
+                .show(ui, |ui| {
+                    for issue in &self.issues {
+                        let location = match issue.line {
+                            Some(line) => format!("Line {}", line + 1),
+                            None => "Plan".to_string(),
+                        };
+                        let text = if issue.path.is_empty() {
+                            format!("❌ {}: {}", location, issue.message)
+                        } else {
+                            format!("❌ {} ({}): {}", location, issue.path, issue.message)
+                        };
+                        let label = ui.colored_label(Color32::from_rgb(220, 80, 80), text);
+                        if let Some(suggestion) = &issue.suggestion {
+                            label.on_hover_text(suggestion);
+                        }
+                    }
+                });
+        }
+
+        self.render_preview(ui);
+
+        let mut action = EditorAction::None;
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            let valid = self.valid_plan();
+            let preview_enabled = valid.is_some() && !self.preview_running;
+            if ui.add_enabled(preview_enabled, egui::Button::new("🔄 Re-preview")).clicked() {
+                action = valid.cloned().map_or(EditorAction::None, EditorAction::Preview);
+            }
+            if ui.add_enabled(valid.is_some(), egui::Button::new("💾 Apply changes")).clicked() {
+                action = valid.cloned().map_or(EditorAction::None, EditorAction::Apply);
+            }
+            if ui.button("↩ Discard").clicked() {
+                action = EditorAction::Discard;
+            }
+            if self.preview_running {
+                ui.spinner();
+            }
+        });
+        action
+    }
+
+    fn render_preview(&self, ui: &mut egui::Ui) {
+        match &self.preview {
+            None => {}
+            Some(Err(e)) => {
+                ui.colored_label(Color32::from_rgb(220, 80, 80), format!("Preview failed: {}", e));
+            }
+            Some(Ok(rows)) => {
+                egui::CollapsingHeader::new(format!("Preview ({} rows)", rows.len()))
+                    .id_source("dsl_editor_preview")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .id_source("dsl_editor_preview_rows")
+                            .max_height(150.0)
+                            .show(ui, |ui| {
+                                for row in rows {
+                                    ui.label(egui::RichText::new(row.to_string()).monospace());
+                                }
+                            });
+                    });
+            }
+        }
+    }
+}
+
+/// Zero-based line a validation path such as `rules.fields[2].selector` points at
+///
+/// Follows keys and list items down the indentation of the YAML text; when a
+/// step can't be found, the line of the deepest step that could is returned.
+pub fn line_for_path(yaml: &str, path: &str) -> Option<usize> {
+    let lines: Vec<&str> = yaml.lines().collect();
+    let (mut start, mut end) = (0, lines.len());
+    let mut found = None;
+
+    for segment in path_segments(path) {
+        let line = match segment {
+            PathSegment::Key(key) => {
+                let Some(column) = (start..end).filter_map(|i| key_at(lines[i]).map(|(column, _)| column)).min() else { break };
+                let Some(line) = (start..end).find(|&i| key_at(lines[i]) == Some((column, key))) else { break };
+                // A key's value may be a list at the key's own indentation
+                start = line + 1;
+                end = block_end(&lines, start, |indent, item| indent < column || (indent == column && !item));
+                line
+            }
+            PathSegment::Index(index) => {
+                let Some(column) = (start..end).filter_map(|i| dash_at(lines[i])).min() else { break };
+                let Some(line) = (start..end).filter(|&i| dash_at(lines[i]) == Some(column)).nth(index) else { break };
+                // The item's first key shares its line
+                start = line;
+                end = block_end(&lines, line + 1, |indent, _| indent <= column);
+                line
+            }
+        };
+        found = Some(line);
+    }
+    found
+}
+
+enum PathSegment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+fn path_segments(path: &str) -> Vec<PathSegment<'_>> {
+    let mut segments = Vec::new();
+    for part in path.split('.').filter(|part| !part.is_empty()) {
+        let mut pieces = part.split('[');
+        if let Some(key) = pieces.next().filter(|key| !key.is_empty()) {
+            segments.push(PathSegment::Key(key));
+        }
+        for index in pieces {
+            if let Ok(index) = index.trim_end_matches(']').parse() {
+                segments.push(PathSegment::Index(index));
+            }
+        }
+    }
+    segments
+}
+
+/// Indentation of a line, `None` for blank lines and comments
+fn indent_of(line: &str) -> Option<usize> {
+    let content = line.trim_start();
+    (!content.is_empty() && !content.starts_with('#')).then(|| line.len() - content.len())
+}
+
+/// Column of the dash when the line starts a list item
+fn dash_at(line: &str) -> Option<usize> {
+    let indent = indent_of(line)?;
+    let rest = &line[indent..];
+    (rest == "-" || rest.starts_with("- ")).then_some(indent)
+}
+
+/// Column and name of the mapping key a line starts with, after any list dash
+fn key_at(line: &str) -> Option<(usize, &str)> {
+    let mut column = indent_of(line)?;
+    if dash_at(line).is_some() {
+        let after_dash = &line[column + 1..];
+        column += 1 + after_dash.len() - after_dash.trim_start().len();
+    }
+    let content = &line[column..];
+    let colon = content.find(':')?;
+    let key = &content[..colon];
+    let after = &content[colon + 1..];
+    let plain = !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    (plain && (after.is_empty() || after.starts_with(' '))).then_some((column, key))
+}
+
+/// First line from `from` that `ends(indent, is_list_item)` says is past the block
+fn block_end(lines: &[&str], from: usize, ends: impl Fn(usize, bool) -> bool) -> usize {
+    (from..lines.len())
+        .find(|&i| indent_of(lines[i]).is_some_and(|indent| ends(indent, dash_at(lines[i]).is_some())))
+        .unwrap_or(lines.len())
+}
+
+/// Colors keys, strings, numbers and comments, and marks lines with problems
+fn highlight_yaml(text: &str, error_lines: &[usize], dark: bool) -> LayoutJob {
+    let (plain, key, string, number, comment, punct) = if dark {
+        (
+            Color32::from_rgb(212, 212, 212),
+            Color32::from_rgb(86, 156, 214),
+            Color32::from_rgb(206, 145, 120),
+            Color32::from_rgb(181, 206, 168),
+            Color32::from_rgb(106, 153, 85),
+            Color32::from_rgb(150, 150, 150),
+        )
+    } else {
+        (
+            Color32::from_rgb(30, 30, 30),
+            Color32::from_rgb(0, 80, 160),
+            Color32::from_rgb(163, 21, 21),
+            Color32::from_rgb(9, 134, 88),
+            Color32::from_rgb(0, 128, 0),
+            Color32::from_rgb(110, 110, 110),
+        )
+    };
+    let error_background = Color32::from_rgba_unmultiplied(220, 60, 60, 50);
+
+    let mut job = LayoutJob::default();
+    for (number_of_line, line) in text.split_inclusive('\n').enumerate() {
+        let background = if error_lines.contains(&number_of_line) { error_background } else { Color32::TRANSPARENT };
+        let mut add = |piece: &str, color: Color32| {
+            if !piece.is_empty() {
+                job.append(piece, 0.0, TextFormat {
+                    font_id: FontId::monospace(13.0),
+                    color,
+                    background,
+                    ..Default::default()
+                });
+            }
+        };
+
+        let (body, newline) = line.strip_suffix('\n').map_or((line, ""), |body| (body, "\n"));
+        let (code, trailing_comment) = split_comment(body);
+
+        let mut rest = code;
+        let indent = rest.len() - rest.trim_start().len();
+        add(&rest[..indent], plain);
+        rest = &rest[indent..];
+        while let Some(after_dash) = rest.strip_prefix("- ").or_else(|| (rest == "-").then_some("")) {
+            let item = after_dash.trim_start();
+            add(&rest[..rest.len() - item.len()], punct);
+            rest = item;
+        }
+
+        if let Some((_, name)) = key_at(rest) {
+            add(name, key);
+            add(":", punct);
+            rest = &rest[name.len() + 1..];
+        }
+
+        let value = rest.trim();
+        let color = if value.starts_with('"') || value.starts_with('\'') {
+            string
+        } else if value.parse::<f64>().is_ok() || matches!(value, "true" | "false" | "null" | "~") {
+            number
+        } else {
+            plain
+        };
+        add(rest, color);
+        add(trailing_comment, comment);
+        add(newline, plain);
+    }
+    job
+}
+
+/// Split a line before a `#` comment that is not inside quotes
+fn split_comment(line: &str) -> (&str, &str) {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('#', None) if i == 0 || line[..i].ends_with(' ') => return line.split_at(i),
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_paths_map_to_lines() {
+        let yaml = "\
+version: '1.0'
+rules:
+  item_selector: .item
+  fields:
+  - name: title
+    selector: h2
+  - name: price
+    selector: ''
+    transform:
+    - type: trim
+  filters:
+  - all:
+    - field: price
+";
+        assert_eq!(line_for_path(yaml, "rules.item_selector"), Some(2));
+        assert_eq!(line_for_path(yaml, "rules.fields[1].selector"), Some(7));
+        assert_eq!(line_for_path(yaml, "rules.fields[1].transform[0]"), Some(9));
+        assert_eq!(line_for_path(yaml, "rules.filters[0].all[0].field"), Some(12));
+        // Missing steps fall back to the deepest one found
+        assert_eq!(line_for_path(yaml, "rules.fields[0].required"), Some(4));
+        assert_eq!(line_for_path(yaml, "output.format"), None);
+    }
+}
@@ -0,0 +1,418 @@
+use eframe::egui;
+use std::path::{Path, PathBuf};
+use tokio::sync::watch;
+
+use crate::config::CompressionCodec;
+use crate::export::{ExportFormat, ExportManager, ExportOptions, ExportProgress};
+use crate::storage::ColumnStats;
+use super::windows_components::WindowsComponents;
+use super::windows_theme::WindowsTheme;
+
+/// Columns of the job and where its export goes by default
+pub struct ExportSetup {
+    pub columns: Vec<ColumnStats>,
+    pub path: PathBuf,
+}
+
+/// Export setup, or why it could not be loaded
+pub type ExportSetupResult = Result<ExportSetup, String>;
+
+/// File a finished export wrote, or why it failed
+pub type ExportOutcome = Result<PathBuf, String>;
+
+const FORMATS: [(ExportFormat, &str); 5] = [
+    (ExportFormat::Csv, "CSV"),
+    (ExportFormat::Xlsx, "Excel (XLSX)"),
+    (ExportFormat::Json, "JSON"),
+    (ExportFormat::Ndjson, "NDJSON"),
+    (ExportFormat::Parquet, "Parquet"),
+];
+
+const DELIMITERS: [(char, &str); 4] = [(',', "Comma"), (';', "Semicolon"), ('\t', "Tab"), ('|', "Pipe")];
+
+const CODECS: [(CompressionCodec, &str); 3] = [
+    (CompressionCodec::Gzip, "gzip (.gz)"),
+    (CompressionCodec::Zstd, "Zstandard (.zst)"),
+    (CompressionCodec::Brotli, "Brotli (.br)"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Destination,
+    Options,
+    Columns,
+    Export,
+}
+
+impl Step {
+    const ALL: [Step; 4] = [Step::Destination, Step::Options, Step::Columns, Step::Export];
+
+    fn label(self) -> &'static str {
+        match self {
+            Step::Destination => "Destination",
+            Step::Options => "Options",
+            Step::Columns => "Columns",
+            Step::Export => "Export",
+        }
+    }
+}
+
+/// Dialog exporting one job's results step by step
+pub struct ExportWizard {
+    pub job_id: String,
+    step: Step,
+    format: ExportFormat,
+    path: String,
+    delimiter: char,
+    sheet_name: String,
+    compression: Option<CompressionCodec>,
+    exclude_metadata: bool,
+    /// The job's columns and whether each is exported; `None` until loaded
+    columns: Option<Vec<(ColumnStats, bool)>>,
+    error: Option<String>,
+    progress: Option<watch::Receiver<ExportProgress>>,
+    outcome: Option<ExportOutcome>,
+}
+
+/// What the wizard asked for
+pub enum ExportWizardAction {
+    None,
+    Close,
+    Start {
+        path: String,
+        format: ExportFormat,
+        options: ExportOptions,
+    },
+    OpenFolder(PathBuf),
+}
+
+impl ExportWizard {
+    pub fn new(job_id: String) -> Self {
+        Self {
+            job_id,
+            step: Step::Destination,
+            format: ExportFormat::Csv,
+            path: String::new(),
+            delimiter: ',',
+            sheet_name: String::new(),
+            compression: None,
+            exclude_metadata: false,
+            columns: None,
+            error: None,
+            progress: None,
+            outcome: None,
+        }
+    }
+
+    pub fn set_setup(&mut self, result: ExportSetupResult) {
+        match result {
+            Ok(setup) => {
+                if self.path.is_empty() {
+                    self.path = setup.path.display().to_string();
+                    self.set_format(self.format.clone());
+                }
+                self.columns = Some(setup.columns.into_iter().map(|column| (column, true)).collect());
+            }
+            Err(e) => {
+                self.columns = Some(Vec::new());
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// Follow an export that was started
+    pub fn started(&mut self, progress: watch::Receiver<ExportProgress>) {
+        self.progress = Some(progress);
+        self.outcome = None;
+        self.error = None;
+    }
+
+    pub fn finished(&mut self, outcome: ExportOutcome) {
+        self.outcome = Some(outcome);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.progress.is_some() && self.outcome.is_none()
+    }
+
+    /// Switch formats, giving the file the format's extension
+    fn set_format(&mut self, format: ExportFormat) {
+        if !self.path.is_empty() {
+            let path = Path::new(&self.path).with_extension(ExportManager::get_file_extension(&format));
+            self.path = path.display().to_string();
+        }
+        self.format = format;
+    }
+
+    fn options(&self) -> ExportOptions {
+        let columns = self.columns.as_deref().unwrap_or_default();
+        let all_selected = columns.iter().all(|(_, included)| *included);
+        ExportOptions {
+            columns: if all_selected {
+                Vec::new()
+            } else {
+                columns.iter().filter(|(_, included)| *included).map(|(column, _)| column.column.clone()).collect()
+            },
+            exclude_metadata: self.exclude_metadata,
+            delimiter: (self.format == ExportFormat::Csv && self.delimiter != ',').then_some(self.delimiter),
+            sheet_name: Some(self.sheet_name.trim().to_string())
+                .filter(|name| self.format == ExportFormat::Xlsx && !name.is_empty()),
+            compression: self.compression,
+            ..Default::default()
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, theme: &WindowsTheme) -> ExportWizardAction {
+        let mut action = ExportWizardAction::None;
+        let mut open = true;
+        egui::Window::new("📤 Export Results")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([480.0, 360.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    for (index, step) in Step::ALL.into_iter().enumerate() {
+                        if index > 0 {
+                            ui.label("›");
+                        }
+                        let label = egui::RichText::new(format!("{}. {}", index + 1, step.label()));
+                        ui.label(if step == self.step { label.strong() } else { label.weak() });
+                    }
+                });
+                ui.separator();
+
+                match self.step {
+                    Step::Destination => self.render_destination(ui),
+                    Step::Options => self.render_options(ui),
+                    Step::Columns => self.render_columns(ui),
+                    Step::Export => {
+                        if let Some(requested) = self.render_export(ui, theme) {
+                            action = requested;
+                        }
+                    }
+                }
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(theme.get_status_color("error"), format!("❌ {}", error));
+                }
+                ui.separator();
+                if let Some(requested) = self.render_buttons(ui) {
+                    action = requested;
+                }
+            });
+
+        if !open && !self.is_running() {
+            action = ExportWizardAction::Close;
+        }
+        action
+    }
+
+    fn render_destination(&mut self, ui: &mut egui::Ui) {
+        ui.label("Format:");
+        ui.horizontal_wrapped(|ui| {
+            for (format, label) in FORMATS {
+                if ui.radio(self.format == format, label).clicked() {
+                    self.set_format(format);
+                }
+            }
+        });
+        ui.add_space(8.0);
+
+        ui.label("Save to:");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.path).desired_width(300.0).hint_text("Export file"));
+            #[cfg(feature = "rfd")]
+            self.render_pickers(ui);
+        });
+    }
+
+    /// Native dialogs choosing the export file or the folder it goes in
+    #[cfg(feature = "rfd")]
+    fn render_pickers(&mut self, ui: &mut egui::Ui) {
+        let current = PathBuf::from(&self.path);
+        let folder = current.parent().filter(|parent| parent.is_dir());
+
+        if ui.button("📁 Browse…").clicked() {
+            let extension = ExportManager::get_file_extension(&self.format);
+            let mut dialog = rfd::FileDialog::new().add_filter(extension.to_uppercase(), &[extension]);
+            if let Some(folder) = folder {
+                dialog = dialog.set_directory(folder);
+            }
+            if let Some(name) = current.file_name() {
+                dialog = dialog.set_file_name(name.to_string_lossy());
+            }
+            if let Some(path) = dialog.save_file() {
+                self.path = path.display().to_string();
+            }
+        }
+        if ui.button("📂 Folder…").on_hover_text("Keep the file name, change the folder").clicked() {
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(folder) = folder {
+                dialog = dialog.set_directory(folder);
+            }
+            if let Some(folder) = dialog.pick_folder() {
+                let name = current.file_name().map(PathBuf::from).unwrap_or_else(|| {
+                    PathBuf::from(format!("{}.{}", self.job_id, ExportManager::get_file_extension(&self.format)))
+                });
+                self.path = folder.join(name).display().to_string();
+            }
+        }
+    }
+
+    fn render_options(&mut self, ui: &mut egui::Ui) {
+        match self.format {
+            ExportFormat::Csv => {
+                ui.horizontal(|ui| {
+                    ui.label("Delimiter:");
+                    for (delimiter, label) in DELIMITERS {
+                        ui.radio_value(&mut self.delimiter, delimiter, label);
+                    }
+                });
+            }
+            ExportFormat::Xlsx => {
+                ui.horizontal(|ui| {
+                    ui.label("Sheet name:");
+                    ui.add(egui::TextEdit::singleline(&mut self.sheet_name).hint_text("Sheet1").desired_width(180.0));
+                });
+            }
+            _ => {
+                ui.label(egui::RichText::new("This format has no options of its own.").weak());
+            }
+        }
+        ui.add_space(8.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Compression:");
+            let selected = CODECS.iter()
+                .find(|(codec, _)| Some(*codec) == self.compression)
+                .map_or("None", |(_, label)| label);
+            egui::ComboBox::from_id_source("export_wizard_compression")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.compression, None, "None");
+                    for (codec, label) in CODECS {
+                        ui.selectable_value(&mut self.compression, Some(codec), label);
+                    }
+                });
+        });
+    }
+
+    fn render_columns(&mut self, ui: &mut egui::Ui) {
+        let Some(columns) = self.columns.as_mut() else {
+            WindowsComponents::loading_spinner(ui, "Loading columns...");
+            return;
+        };
+        if columns.is_empty() {
+            ui.label("The job has no results yet; every column will be exported.");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.small_button("Select all").clicked() {
+                columns.iter_mut().for_each(|(_, included)| *included = true);
+            }
+            if ui.small_button("Select none").clicked() {
+                columns.iter_mut().for_each(|(_, included)| *included = false);
+            }
+            ui.checkbox(&mut self.exclude_metadata, "Leave out metadata (_source_url, …)");
+        });
+        egui::ScrollArea::vertical()
+            .id_source("export_wizard_columns")
+            .max_height(220.0)
+            .show(ui, |ui| {
+                for (column, included) in columns.iter_mut() {
+                    let metadata = column.column.starts_with('_');
+                    ui.add_enabled_ui(!(metadata && self.exclude_metadata), |ui| {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(included, &column.column);
+                            let filled = column.count.saturating_sub(column.nulls);
+                            ui.label(egui::RichText::new(format!("{} of {} rows filled", filled, column.count)).weak());
+                        });
+                    });
+                }
+            });
+    }
+
+    fn render_export(&mut self, ui: &mut egui::Ui, theme: &WindowsTheme) -> Option<ExportWizardAction> {
+        match &self.outcome {
+            Some(Ok(path)) => {
+                ui.colored_label(theme.get_status_color("success"), "✅ Export finished");
+                ui.monospace(path.display().to_string());
+                if ui.button("📂 Open containing folder").clicked() {
+                    return Some(ExportWizardAction::OpenFolder(path.clone()));
+                }
+            }
+            Some(Err(e)) => {
+                ui.colored_label(theme.get_status_color("error"), format!("❌ Export failed: {}", e));
+            }
+            None => {
+                let Some(progress) = &self.progress else { return None };
+                let progress = progress.borrow().clone();
+                let text = if progress.compressing {
+                    "Compressing…".to_string()
+                } else if progress.pass < progress.passes {
+                    format!("Finding columns: {} of {} rows", progress.rows, progress.total)
+                } else {
+                    format!("Writing {} of {} rows", progress.rows, progress.total)
+                };
+                WindowsComponents::progress_indicator(ui, progress.fraction(), &text);
+            }
+        }
+        None
+    }
+
+    fn render_buttons(&mut self, ui: &mut egui::Ui) -> Option<ExportWizardAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            let running = self.is_running();
+            let back_to = match self.step {
+                Step::Destination => None,
+                Step::Options => Some(Step::Destination),
+                Step::Columns => Some(Step::Options),
+                Step::Export => Some(Step::Columns).filter(|_| self.outcome.as_ref().is_some_and(|outcome| outcome.is_err())),
+            };
+            if let Some(step) = back_to {
+                if ui.button("← Back").clicked() {
+                    self.step = step;
+                    self.error = None;
+                }
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.step == Step::Export {
+                    if ui.add_enabled(!running, egui::Button::new("Close")).clicked() {
+                        action = Some(ExportWizardAction::Close);
+                    }
+                    return;
+                }
+                if ui.button("Cancel").clicked() {
+                    action = Some(ExportWizardAction::Close);
+                }
+                if self.step == Step::Columns {
+                    if ui.button("📤 Export").clicked() {
+                        let options = self.options();
+                        match options.validate() {
+                            Ok(()) => {
+                                self.error = None;
+                                self.progress = None;
+                                self.outcome = None;
+                                self.step = Step::Export;
+                                action = Some(ExportWizardAction::Start {
+                                    path: self.path.trim().to_string(),
+                                    format: self.format.clone(),
+                                    options,
+                                });
+                            }
+                            Err(e) => self.error = Some(e.to_string()),
+                        }
+                    }
+                } else if ui.add_enabled(!self.path.trim().is_empty(), egui::Button::new("Next →")).clicked() {
+                    self.error = None;
+                    self.step = if self.step == Step::Destination { Step::Options } else { Step::Columns };
+                }
+            });
+        });
+        action
+    }
+}
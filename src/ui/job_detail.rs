@@ -0,0 +1,307 @@
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+use crate::core::JobDetail;
+use crate::storage::JobStatus;
+use crate::utils::format_duration;
+use crate::utils::time_utils::format_local;
+use super::windows_theme::WindowsTheme;
+
+/// How often the detail of a running job is reloaded
+pub const LIVE_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Detail of a job, or why it could not be loaded
+pub type JobDetailResult = Result<JobDetail, String>;
+
+/// Screen showing one job's log, stage timings, URLs and errors
+pub struct JobDetailView {
+    pub job_id: String,
+    pub detail: Option<JobDetail>,
+    pub error: Option<String>,
+    pub loading: bool,
+    loaded_at: Option<Instant>,
+    log_level: LogLevel,
+    failed_urls_only: bool,
+}
+
+/// Least severe log entries shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogLevel {
+    All,
+    Warnings,
+    Errors,
+}
+
+impl LogLevel {
+    fn label(self) -> &'static str {
+        match self {
+            LogLevel::All => "All",
+            LogLevel::Warnings => "Warnings and errors",
+            LogLevel::Errors => "Errors",
+        }
+    }
+
+    fn shows(self, level: &str) -> bool {
+        match self {
+            LogLevel::All => true,
+            LogLevel::Warnings => matches!(level, "warn" | "error"),
+            LogLevel::Errors => level == "error",
+        }
+    }
+}
+
+/// What the buttons of the detail screen asked for
+pub enum JobDetailAction {
+    None,
+    Back,
+    Refresh,
+    ViewResults,
+    Cancel,
+}
+
+impl JobDetailView {
+    pub fn new(job_id: String) -> Self {
+        Self {
+            job_id,
+            detail: None,
+            error: None,
+            loading: false,
+            loaded_at: None,
+            log_level: LogLevel::All,
+            failed_urls_only: false,
+        }
+    }
+
+    pub fn set_detail(&mut self, result: JobDetailResult) {
+        self.loading = false;
+        self.loaded_at = Some(Instant::now());
+        match result {
+            Ok(detail) => {
+                self.detail = Some(detail);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Whether the job may still change, so the view follows it
+    pub fn is_live(&self) -> bool {
+        self.detail.as_ref()
+            .is_none_or(|detail| matches!(detail.job.status, JobStatus::Queued | JobStatus::Running))
+    }
+
+    /// Whether it is time to load the detail again
+    pub fn needs_refresh(&self) -> bool {
+        !self.loading && match self.loaded_at {
+            None => true,
+            Some(loaded_at) => self.is_live() && loaded_at.elapsed() >= LIVE_REFRESH_INTERVAL,
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, theme: &WindowsTheme) -> JobDetailAction {
+        let mut action = JobDetailAction::None;
+        ui.horizontal(|ui| {
+            if ui.button("← Jobs").clicked() {
+                action = JobDetailAction::Back;
+            }
+            if let Some(detail) = &self.detail {
+                ui.heading(&detail.job.title);
+                ui.colored_label(theme.get_status_color(&detail.job.status.to_string()), detail.job.status.to_string());
+            }
+            if self.is_live() {
+                ui.colored_label(theme.get_status_color("running"), "● Live");
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("🔄 Refresh").clicked() {
+                    action = JobDetailAction::Refresh;
+                }
+                if ui.button("📊 View Results").clicked() {
+                    action = JobDetailAction::ViewResults;
+                }
+                if self.detail.as_ref().is_some_and(|d| d.job.status == JobStatus::Running)
+                    && ui.button("⏹️ Cancel").clicked()
+                {
+                    action = JobDetailAction::Cancel;
+                }
+            });
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(theme.get_status_color("error"), format!("❌ {}", error));
+        }
+        let Some(detail) = &self.detail else {
+            ui.spinner();
+            return action;
+        };
+
+        ui.label(format!(
+            "Created {} · {} attempt(s) · {} of {} URLs fetched successfully",
+            format_local(&detail.job.created_at, "%Y-%m-%d %H:%M:%S"),
+            detail.attempts.len(),
+            detail.failures.succeeded,
+            detail.failures.total_urls,
+        ));
+        if !detail.job.user_prompt.is_empty() {
+            ui.label(egui::RichText::new(&detail.job.user_prompt).weak());
+        }
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .id_source("job_detail_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                render_stages(ui, detail, theme);
+                render_errors(ui, detail, theme);
+                render_issues(ui, detail, theme);
+                render_urls(ui, detail, theme, &mut self.failed_urls_only);
+                render_log(ui, detail, theme, &mut self.log_level);
+            });
+        action
+    }
+}
+
+fn render_stages(ui: &mut egui::Ui, detail: &JobDetail, theme: &WindowsTheme) {
+    egui::CollapsingHeader::new("⏱ Stages")
+        .default_open(true)
+        .show(ui, |ui| {
+            if detail.stages.is_empty() {
+                ui.label("No stages recorded yet.");
+                return;
+            }
+            let total = detail.stages.iter().map(|stage| stage.duration_secs).sum::<i64>().max(1) as f32;
+            egui::Grid::new("job_detail_stages").striped(true).show(ui, |ui| {
+                for stage in &detail.stages {
+                    ui.label(&stage.stage);
+                    ui.label(format_local(&stage.started_at, "%H:%M:%S"));
+                    let mut duration = format_duration(Duration::from_secs(stage.duration_secs as u64));
+                    if stage.finished_at.is_none() {
+                        duration.push('…');
+                    }
+                    ui.label(duration);
+                    ui.add(egui::ProgressBar::new(stage.duration_secs as f32 / total)
+                        .desired_width(160.0)
+                        .fill(theme.get_status_color("running")));
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+fn render_errors(ui: &mut egui::Ui, detail: &JobDetail, theme: &WindowsTheme) {
+    let failed_attempts: Vec<_> = detail.attempts.iter().filter(|attempt| attempt.error.is_some()).collect();
+    if detail.failures.reasons.is_empty() && failed_attempts.is_empty() {
+        return;
+    }
+
+    egui::CollapsingHeader::new(format!("❗ Errors ({} URLs failed)", detail.failures.failed.len()))
+        .default_open(true)
+        .show(ui, |ui| {
+            egui::Grid::new("job_detail_errors").striped(true).show(ui, |ui| {
+                for reason in &detail.failures.reasons {
+                    ui.colored_label(theme.get_status_color("error"), &reason.reason);
+                    ui.label(format!("{} URL(s)", reason.count));
+                    ui.end_row();
+                }
+                for attempt in failed_attempts {
+                    ui.colored_label(theme.get_status_color("error"), attempt.error.as_deref().unwrap_or_default());
+                    ui.label(format!("attempt {}", attempt.attempt));
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+fn render_issues(ui: &mut egui::Ui, detail: &JobDetail, theme: &WindowsTheme) {
+    if detail.issues.is_empty() {
+        return;
+    }
+
+    egui::CollapsingHeader::new(format!("⚠️ Warnings ({})", detail.issues.total()))
+        .default_open(true)
+        .show(ui, |ui| {
+            egui::Grid::new("job_detail_issues").striped(true).show(ui, |ui| {
+                for group in detail.issues.sorted() {
+                    ui.colored_label(theme.get_status_color("warning"), group.kind.label());
+                    ui.label(format!("{}×", group.count));
+                    ui.label(match &group.field {
+                        Some(field) => format!("{} · {}", group.stage, field),
+                        None => group.stage.clone(),
+                    });
+                    let message = ui.label(&group.message);
+                    if !group.urls.is_empty() {
+                        message.on_hover_text(group.urls.join("\n"));
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+}
+
+fn render_urls(ui: &mut egui::Ui, detail: &JobDetail, theme: &WindowsTheme, failed_only: &mut bool) {
+    egui::CollapsingHeader::new(format!("🌐 URLs ({})", detail.urls.len()))
+        .default_open(detail.urls.len() <= 50)
+        .show(ui, |ui| {
+            ui.checkbox(failed_only, "Failed only");
+            let urls: Vec<_> = detail.urls.iter().filter(|url| !*failed_only || !url.success).collect();
+            let row_height = ui.spacing().interact_size.y;
+            egui::ScrollArea::vertical()
+                .id_source("job_detail_urls")
+                .max_height(240.0)
+                .show_rows(ui, row_height, urls.len(), |ui, rows| {
+                    for url in &urls[rows] {
+                        ui.horizontal(|ui| {
+                            let (color, icon) = if url.success {
+                                (theme.get_status_color("success"), "✅")
+                            } else {
+                                (theme.get_status_color("error"), "❌")
+                            };
+                            ui.colored_label(color, icon);
+                            ui.monospace(url.status_code.map_or("---".to_string(), |code| code.to_string()));
+                            ui.label(format!("{} items", url.items));
+                            if url.attempts > 1 {
+                                ui.label(format!("×{}", url.attempts));
+                            }
+                            let link = ui.hyperlink_to(&url.url, &url.url);
+                            if !url.success {
+                                link.on_hover_text(url.failure_reason());
+                            }
+                        });
+                    }
+                });
+        });
+}
+
+fn render_log(ui: &mut egui::Ui, detail: &JobDetail, theme: &WindowsTheme, level: &mut LogLevel) {
+    egui::CollapsingHeader::new(format!("📜 Log ({})", detail.logs.len()))
+        .default_open(true)
+        .show(ui, |ui| {
+            egui::ComboBox::from_id_source("job_detail_log_level")
+                .selected_text(level.label())
+                .show_ui(ui, |ui| {
+                    for option in [LogLevel::All, LogLevel::Warnings, LogLevel::Errors] {
+                        ui.selectable_value(level, option, option.label());
+                    }
+                });
+
+            let entries: Vec<_> = detail.logs.iter().filter(|entry| level.shows(&entry.level)).collect();
+            let row_height = ui.spacing().interact_size.y;
+            egui::ScrollArea::vertical()
+                .id_source("job_detail_log")
+                .max_height(260.0)
+                .stick_to_bottom(true)
+                .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                    for entry in &entries[rows] {
+                        ui.horizontal(|ui| {
+                            ui.monospace(format_local(&entry.timestamp, "%H:%M:%S"));
+                            ui.label(egui::RichText::new(&entry.stage).weak());
+                            let color = match entry.level.as_str() {
+                                "error" => theme.get_status_color("error"),
+                                "warn" => theme.get_status_color("warning"),
+                                _ => theme.get_status_color("info"),
+                            };
+                            ui.colored_label(color, &entry.message);
+                        });
+                    }
+                });
+        });
+}
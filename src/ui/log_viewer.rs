@@ -0,0 +1,154 @@
+use eframe::egui;
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use crate::logging::{LogFilter, LogLine};
+use crate::utils::time_utils::format_local;
+use super::windows_theme::WindowsTheme;
+
+/// How often the logs are reloaded while following
+pub const LOG_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Entries loaded from the log files and from the jobs' logs
+pub const LOG_TAIL_LINES: usize = 2000;
+
+/// Loaded log lines, or why they could not be loaded
+pub type LogsResult = Result<Vec<LogLine>, String>;
+
+/// Levels offered in the level filter, most severe first
+const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Logs tab: the tail of the log files and the jobs' logs, filtered and searchable
+pub struct LogViewer {
+    pub lines: Vec<LogLine>,
+    pub error: Option<String>,
+    pub loading: bool,
+    /// Keep reloading and scrolled to the newest line
+    pub follow: bool,
+    loaded_at: Option<Instant>,
+    filter: LogFilter,
+    job_id: String,
+}
+
+/// What the log viewer asked for
+pub enum LogViewerAction {
+    None,
+    Refresh,
+    /// Write these lines to a file
+    Export(Vec<LogLine>),
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        Self {
+            lines: Vec::new(),
+            error: None,
+            loading: false,
+            follow: true,
+            loaded_at: None,
+            filter: LogFilter::default(),
+            job_id: String::new(),
+        }
+    }
+}
+
+impl LogViewer {
+    pub fn set_lines(&mut self, result: LogsResult) {
+        self.loading = false;
+        self.loaded_at = Some(Instant::now());
+        match result {
+            Ok(lines) => {
+                self.lines = lines;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Whether it is time to load the logs again
+    pub fn needs_refresh(&self) -> bool {
+        !self.loading && match self.loaded_at {
+            None => true,
+            Some(loaded_at) => self.follow && loaded_at.elapsed() >= LOG_REFRESH_INTERVAL,
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, theme: &WindowsTheme) -> LogViewerAction {
+        let mut action = LogViewerAction::None;
+        let components: BTreeSet<&str> = self.lines.iter()
+            .map(|line| line.component.as_str())
+            .filter(|component| !component.is_empty())
+            .collect();
+
+        ui.horizontal_wrapped(|ui| {
+            egui::ComboBox::from_id_source("log_viewer_level")
+                .selected_text(self.filter.level.as_deref().map_or("All levels".to_string(), |level| format!("{} and above", level)))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.level, None, "All levels");
+                    for level in LEVELS {
+                        ui.selectable_value(&mut self.filter.level, Some(level.to_string()), level);
+                    }
+                });
+            egui::ComboBox::from_id_source("log_viewer_component")
+                .selected_text(self.filter.component.as_deref().unwrap_or("All components"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.component, None, "All components");
+                    for component in &components {
+                        ui.selectable_value(&mut self.filter.component, Some(component.to_string()), *component);
+                    }
+                });
+            ui.label("Job:");
+            ui.add(egui::TextEdit::singleline(&mut self.job_id).hint_text("job id").desired_width(120.0));
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.filter.search).hint_text("Search messages").desired_width(180.0));
+            ui.checkbox(&mut self.follow, "Follow");
+            if ui.add_enabled(!self.loading, egui::Button::new("🔄 Refresh")).clicked() {
+                action = LogViewerAction::Refresh;
+            }
+            if self.loading {
+                ui.spinner();
+            }
+        });
+        let job_id = self.job_id.trim();
+        self.filter.job_id = (!job_id.is_empty()).then(|| job_id.to_string());
+
+        if let Some(error) = &self.error {
+            ui.colored_label(theme.get_status_color("error"), format!("❌ {}", error));
+        }
+
+        let shown: Vec<&LogLine> = self.lines.iter().filter(|line| self.filter.matches(line)).collect();
+        ui.horizontal(|ui| {
+            ui.label(format!("{} of {} lines", shown.len(), self.lines.len()));
+            if ui.add_enabled(!shown.is_empty(), egui::Button::new("💾 Export to file")).clicked() {
+                action = LogViewerAction::Export(shown.iter().map(|line| (*line).clone()).collect());
+            }
+        });
+        ui.separator();
+
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical()
+            .id_source("log_viewer_lines")
+            .auto_shrink([false, false])
+            .stick_to_bottom(self.follow)
+            .show_rows(ui, row_height, shown.len(), |ui, rows| {
+                for line in &shown[rows] {
+                    ui.horizontal(|ui| {
+                        ui.monospace(format_local(&line.timestamp, "%m-%d %H:%M:%S"));
+                        let color = match line.level.as_str() {
+                            "error" => theme.get_status_color("error"),
+                            "warn" => theme.get_status_color("warning"),
+                            _ => theme.get_status_color("info"),
+                        };
+                        ui.colored_label(color, egui::RichText::new(line.level.to_uppercase()).monospace());
+                        ui.label(egui::RichText::new(&line.component).weak());
+                        let first_line = line.message.lines().next().unwrap_or_default();
+                        let message = ui.label(first_line);
+                        if first_line.len() < line.message.len() {
+                            message.on_hover_text(&line.message);
+                        }
+                    });
+                }
+            });
+        action
+    }
+}
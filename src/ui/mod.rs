@@ -5,6 +5,8 @@ use std::sync::Arc;
 #[cfg(feature = "ui")]
 use tracing::{info, error, debug};
 
+#[cfg(feature = "ui")]
+pub mod analytics;
 #[cfg(feature = "ui")]
 pub mod chat;
 #[cfg(feature = "ui")]
@@ -20,6 +22,22 @@ pub mod windows_components;
 #[cfg(feature = "ui")]
 pub mod results_viewer;
 #[cfg(feature = "ui")]
+pub mod dsl_editor;
+#[cfg(feature = "ui")]
+pub mod export_wizard;
+#[cfg(feature = "ui")]
+pub mod job_detail;
+#[cfg(feature = "ui")]
+pub mod log_viewer;
+#[cfg(feature = "ui")]
+pub mod security_view;
+#[cfg(feature = "ui")]
+pub mod run_comparison;
+#[cfg(feature = "ui")]
+pub mod selector_picker;
+#[cfg(feature = "ui")]
+pub mod tray;
+#[cfg(feature = "ui")]
 pub mod windows_ui;
 #[cfg(feature = "ui")]
 pub mod windows_launcher;
@@ -27,11 +45,21 @@ pub mod windows_launcher;
 pub mod windows_app;
 #[cfg(feature = "ui")]
 pub mod icon_manager;
+#[cfg(feature = "ui")]
+pub mod safe_mode;
 
 #[cfg(feature = "ui")]
 use crate::core::WinScrapeStudio;
 #[cfg(feature = "ui")]
-use crate::core::orchestrator::{WorkflowResult, WorkflowStage};
+use crate::core::orchestrator::{Orchestrator, PendingApproval, WorkflowResult, WorkflowStage};
+
+/// Jobs shown in the jobs view
+#[cfg(feature = "ui")]
+const JOB_LIST_LIMIT: usize = 100;
+
+/// Jobs loaded in the background, or why loading failed
+#[cfg(feature = "ui")]
+type LoadedJobs = Result<Vec<state::JobInfo>, String>;
 
 /// Main UI application
 #[cfg(feature = "ui")]
@@ -40,21 +68,31 @@ pub struct WinScrapeUI {
     state: state::UIState,
     chat: chat::ChatInterface,
     theme: theme::Theme,
+    /// Outcomes of workflows run in the background
+    workflow_tx: state::WorkflowSender,
+    workflow_rx: tokio::sync::mpsc::UnboundedReceiver<state::WorkflowState>,
+    /// Jobs loaded from storage, picked up on the next frame
+    loaded_jobs: Arc<std::sync::Mutex<Option<LoadedJobs>>>,
 }
 
 #[cfg(feature = "ui")]
 impl WinScrapeUI {
     /// Create new UI application
     pub fn new(app: Arc<WinScrapeStudio>) -> Self {
-        let state = state::UIState::new();
+        let mut state = state::UIState::new();
+        state.settings = state::UISettings::from_config(&app.config());
         let chat = chat::ChatInterface::new();
         let theme = theme::Theme::dark();
+        let (workflow_tx, workflow_rx) = tokio::sync::mpsc::unbounded_channel();
         
         Self {
             app,
             state,
             chat,
             theme,
+            workflow_tx,
+            workflow_rx,
+            loaded_jobs: Arc::new(std::sync::Mutex::new(None)),
         }
     }
     
@@ -113,6 +151,7 @@ impl WinScrapeUI {
                     state::View::Chat => self.render_chat_view(ui, ctx),
                     state::View::Jobs => self.render_jobs_view(ui, ctx),
                     state::View::Settings => self.render_settings_view(ui, ctx),
+                    state::View::Logs | state::View::Security => self.render_logs_view(ui),
                     state::View::Help => self.render_help_view(ui, ctx),
                 }
             });
@@ -146,6 +185,13 @@ impl WinScrapeUI {
                     self.state.current_view = state::View::Help;
                 }
                 
+                if ui.selectable_label(
+                    matches!(self.state.current_view, state::View::Logs),
+                    "📜 Logs"
+                ).clicked() {
+                    self.state.current_view = state::View::Logs;
+                }
+                
                 if ui.selectable_label(
                     matches!(self.state.current_view, state::View::Settings),
                     "⚙️ Settings"
@@ -182,6 +228,75 @@ impl WinScrapeUI {
         if let Some(user_input) = self.chat.get_pending_input() {
             self.handle_chat_input(user_input, ctx);
         }
+        
+        if let Some(approval) = self.state.pending_approval.clone() {
+            self.render_approval_dialog(ctx, &approval);
+        }
+    }
+    
+    /// Show the plan awaiting approval with its preview rows
+    fn render_approval_dialog(&mut self, ctx: &egui::Context, approval: &PendingApproval) {
+        egui::Window::new("Review Scraping Plan")
+            .collapsible(false)
+            .resizable(true)
+            .default_size([600.0, 420.0])
+            .show(ctx, |ui| {
+                ui.label("Please review the generated scraping plan:");
+                if let Ok(yaml) = serde_yaml::to_string(&approval.dsl) {
+                    egui::ScrollArea::vertical()
+                        .id_source("approval_yaml")
+                        .max_height(220.0)
+                        .show(ui, |ui| {
+                            ui.add(egui::TextEdit::multiline(&mut yaml.as_str())
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(f32::INFINITY));
+                        });
+                }
+                
+                ui.add_space(8.0);
+                ui.label(format!("Preview ({} rows):", approval.preview.len()));
+                egui::ScrollArea::vertical()
+                    .id_source("approval_preview")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        for row in &approval.preview {
+                            ui.monospace(row.to_string());
+                        }
+                    });
+                
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("✅ Approve & Run").clicked() {
+                        self.approve_plan();
+                    }
+                    if ui.button("❌ Reject").clicked() {
+                        self.state.pending_approval = None;
+                        self.chat.add_system_message("Scraping plan rejected.".to_string());
+                    }
+                });
+            });
+    }
+    
+    /// Run the plan under review and report back once its job has ended
+    fn approve_plan(&mut self) {
+        let Some(approval) = self.state.pending_approval.take() else { return };
+        self.chat.add_system_message("Plan approved, starting the scraping job...".to_string());
+        self.state.current_workflow = Some(state::WorkflowState::Processing);
+        self.state.status_message = Some("Running scraping job...".to_string());
+        
+        let orchestrator = Orchestrator::new(self.app.clone());
+        let updates = self.workflow_tx.clone();
+        tokio::spawn(async move {
+            let user_input = approval.dsl.get_metadata("user_prompt")
+                .and_then(|prompt| prompt.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let outcome = match orchestrator.run_approved_plan(&user_input, &approval.dsl).await {
+                Ok(result) => state::WorkflowState::Completed(result),
+                Err(e) => state::WorkflowState::Failed(e.to_string()),
+            };
+            let _ = updates.send(outcome);
+        });
     }
     
     /// Render jobs view
@@ -221,7 +336,7 @@ impl WinScrapeUI {
                 ui.add(egui::Slider::new(&mut self.state.settings.request_timeout, 5..=120));
             });
             
-            ui.checkbox(&mut self.state.settings.respect_robots_txt, "Respect robots.txt");
+            ui.checkbox(&mut self.state.settings.always_respect_robots_txt, "Respect robots.txt for every plan");
             ui.checkbox(&mut self.state.settings.enable_browser_fallback, "Enable browser fallback");
         });
         
@@ -250,6 +365,13 @@ impl WinScrapeUI {
         }
     }
     
+    /// Render logs view
+    fn render_logs_view(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Application Logs");
+        ui.label("Log files are written to:");
+        ui.monospace(crate::logging::log_directory().display().to_string());
+    }
+    
     /// Render help view
     fn render_help_view(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context) {
         ui.heading("Help & Documentation");
@@ -294,7 +416,7 @@ impl WinScrapeUI {
             ui.horizontal(|ui| {
                 ui.label(&job.title);
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.label(job.created_at.format("%Y-%m-%d %H:%M").to_string());
+                    ui.label(crate::utils::time_utils::format_local(&job.created_at, "%Y-%m-%d %H:%M"));
                     
                     // Status indicator
                     let (color, text) = match job.status {
@@ -303,6 +425,7 @@ impl WinScrapeUI {
                         state::JobStatus::Failed => (egui::Color32::RED, "❌ Failed"),
                         state::JobStatus::Queued => (egui::Color32::BLUE, "⏳ Queued"),
                         state::JobStatus::Cancelled => (egui::Color32::GRAY, "🚫 Cancelled"),
+                        state::JobStatus::Paused => (egui::Color32::LIGHT_BLUE, "⏸️ Paused"),
                     };
                     
                     ui.colored_label(color, text);
@@ -354,20 +477,25 @@ impl WinScrapeUI {
         self.state.current_workflow = Some(state::WorkflowState::Processing);
         self.state.status_message = Some("Processing your request...".to_string());
         
-        // Spawn async task to handle the request
-        let _app = self.app.clone();
-        let _ctx = ctx.clone();
-        
+        // Generate, validate and preview the plan, stopping for approval
+        let orchestrator = Orchestrator::new(self.app.clone());
+        let updates = self.workflow_tx.clone();
+        let ctx = ctx.clone();
         tokio::spawn(async move {
-            // This would need to be handled differently in a real implementation
-            // For now, we'll simulate the workflow
             info!("Starting workflow for input: {}", input);
+            let outcome = match orchestrator.execute_complete_workflow(&input, false).await {
+                Ok(result) => state::WorkflowState::Completed(result),
+                Err(e) => state::WorkflowState::Failed(e.to_string()),
+            };
+            let _ = updates.send(outcome);
+            ctx.request_repaint();
         });
     }
     
     /// Handle background tasks
     fn handle_background_tasks(&mut self, ctx: &egui::Context) {
         // Check for completed workflows
+        self.state.poll_workflow(&mut self.workflow_rx);
         if let Some(workflow_state) = &self.state.current_workflow {
             match workflow_state {
                 state::WorkflowState::Processing => {
@@ -385,6 +513,13 @@ impl WinScrapeUI {
             }
         }
         
+        let loaded = self.loaded_jobs.lock().ok().and_then(|mut slot| slot.take());
+        match loaded {
+            Some(Ok(jobs)) => self.state.jobs = jobs,
+            Some(Err(e)) => error!("Failed to load jobs: {}", e),
+            None => {}
+        }
+        
         // Refresh jobs periodically
         if self.state.last_job_refresh.elapsed() > std::time::Duration::from_secs(30) {
             self.refresh_jobs();
@@ -404,14 +539,20 @@ impl WinScrapeUI {
                 }
             }
             WorkflowStage::Completed => {
-                self.chat.add_system_message("Scraping completed successfully!".to_string());
+                if result.success {
+                    self.chat.add_system_message("Scraping completed successfully!".to_string());
+                } else {
+                    self.chat.add_system_message(format!("Scraping failed: {}", result.errors.join("; ")));
+                }
                 if let Some(job_id) = &result.job_id {
                     self.refresh_job_details(job_id);
                 }
+                self.refresh_jobs();
             }
             WorkflowStage::Failed => {
                 let error_msg = result.errors.join("; ");
                 self.chat.add_system_message(format!("Scraping failed: {}", error_msg));
+                self.refresh_jobs();
             }
             _ => {
                 self.chat.add_system_message(format!("Workflow stage: {}", result.stage));
@@ -430,9 +571,19 @@ impl WinScrapeUI {
     
     /// Refresh jobs list
     fn refresh_jobs(&mut self) {
-        // This would fetch jobs from the app
         self.state.last_job_refresh = std::time::Instant::now();
         debug!("Refreshing jobs list");
+        
+        let app = self.app.clone();
+        let slot = self.loaded_jobs.clone();
+        tokio::spawn(async move {
+            let jobs = app.list_jobs(JOB_LIST_LIMIT).await
+                .map(|jobs| jobs.into_iter().map(state::JobInfo::from).collect())
+                .map_err(|e| e.to_string());
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(jobs);
+            }
+        });
     }
     
     /// Refresh specific job details
@@ -458,10 +609,18 @@ impl WinScrapeUI {
         // This would cancel the job
     }
     
-    /// Save settings
+    /// Save the settings to the configuration file and apply them to the running engine
     fn save_settings(&mut self) {
         info!("Saving settings");
-        // This would save settings to config
+        let mut config = self.app.config();
+        self.state.settings.apply_to(&mut config);
+        
+        let app = self.app.clone();
+        tokio::spawn(async move {
+            if let Err(e) = app.update_config(config).await {
+                error!("Failed to save settings: {}", e);
+            }
+        });
     }
 }
 
@@ -3,7 +3,13 @@ use eframe::egui;
 #[cfg(feature = "ui")]
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ui")]
-use std::collections::HashMap;
+use std::cmp::Ordering;
+#[cfg(feature = "ui")]
+use std::collections::{HashMap, HashSet};
+
+/// Width of a table column
+#[cfg(feature = "ui")]
+const COLUMN_WIDTH: f32 = 160.0;
 
 /// Results viewer for displaying scraped data
 #[cfg(feature = "ui")]
@@ -16,9 +22,26 @@ pub struct ResultsViewer {
     pub sort_column: Option<String>,
     pub sort_ascending: bool,
     pub filter_text: String,
-    pub selected_rows: std::collections::HashSet<usize>,
+    /// Substring each column must contain, by column
+    pub column_filters: HashMap<String, String>,
+    pub hidden_columns: HashSet<String>,
+    /// Checked rows, as indices into `data`
+    pub selected_rows: HashSet<usize>,
+    /// Row shown in the detail pane, as an index into `data`
+    pub detail_row: Option<usize>,
     pub view_mode: ViewMode,
     pub export_format: ExportFormat,
+    /// Keep fetching rows while the job runs
+    pub following: bool,
+    /// Last `row_idx` received, rows after it are fetched next
+    pub last_row_idx: Option<i32>,
+    /// Every column seen so far, in display order
+    #[serde(skip)]
+    columns: Vec<String>,
+    /// Indices into `data` of the rows passing the filters, in display order;
+    /// `None` until worked out again after a change
+    #[serde(skip)]
+    view: Option<Vec<usize>>,
 }
 
 #[cfg(feature = "ui")]
@@ -39,10 +62,20 @@ pub enum ExportFormat {
     Parquet,
 }
 
+/// Something clicked in the table, applied once drawing is done
+#[cfg(feature = "ui")]
+enum TableAction {
+    Sort(String),
+    Hide(String),
+    Check(usize, bool),
+    Detail(usize),
+    Copy(String),
+}
+
 #[cfg(feature = "ui")]
 impl ResultsViewer {
     pub fn new(job_id: String, data: Vec<HashMap<String, serde_json::Value>>) -> Self {
-        Self {
+        let mut viewer = Self {
             job_id,
             data,
             current_page: 0,
@@ -50,18 +83,64 @@ impl ResultsViewer {
             sort_column: None,
             sort_ascending: true,
             filter_text: String::new(),
-            selected_rows: std::collections::HashSet::new(),
+            column_filters: HashMap::new(),
+            hidden_columns: HashSet::new(),
+            selected_rows: HashSet::new(),
+            detail_row: None,
             view_mode: ViewMode::Table,
             export_format: ExportFormat::CSV,
+            following: false,
+            last_row_idx: None,
+            columns: Vec::new(),
+            view: None,
+        };
+        viewer.add_columns(0);
+        viewer
+    }
+    
+    /// Open an empty viewer that tails a job's results as they are stored
+    pub fn follow(job_id: String) -> Self {
+        let mut viewer = Self::new(job_id, Vec::new());
+        viewer.following = true;
+        viewer
+    }
+    
+    /// Append rows fetched from the job
+    pub fn append_rows(&mut self, rows: Vec<crate::storage::ResultRow>) {
+        let first_new = self.data.len();
+        for row in rows {
+            self.last_row_idx = Some(row.row_idx);
+            if let serde_json::Value::Object(map) = row.data {
+                self.data.push(map.into_iter().collect());
+            }
+        }
+        self.add_columns(first_new);
+        
+        // Unsorted views only grow at the end, so the new rows can be matched on their own
+        if self.sort_column.is_some() {
+            self.view = None;
+        } else if let Some(view) = &mut self.view {
+            let filter = RowFilter::new(&self.filter_text, &self.column_filters);
+            view.extend((first_new..self.data.len()).filter(|&idx| filter.matches(&self.data[idx])));
         }
     }
     
     pub fn render(&mut self, ui: &mut egui::Ui) {
+        if self.columns.is_empty() && !self.data.is_empty() {
+            self.add_columns(0);
+        }
+        self.refresh_view();
+        
         // Header with controls
         self.render_header(ui);
         
         ui.separator();
         
+        // The detail pane has to be added before the table it sits beside
+        if self.view_mode == ViewMode::Table && self.detail_row.is_some() {
+            self.render_detail_pane(ui);
+        }
+        
         // Main content area
         match self.view_mode {
             ViewMode::Table => self.render_table_view(ui),
@@ -70,7 +149,7 @@ impl ResultsViewer {
             ViewMode::Statistics => self.render_statistics_view(ui),
         }
         
-        // Footer with pagination
+        // Footer with row counts or pagination
         self.render_footer(ui);
     }
     
@@ -90,19 +169,50 @@ impl ResultsViewer {
             
             // Search/filter
             ui.label("🔍");
-            ui.add(egui::TextEdit::singleline(&mut self.filter_text).hint_text("Filter data..."));
+            if ui.add(egui::TextEdit::singleline(&mut self.filter_text).hint_text("Filter data...")).changed() {
+                self.invalidate_view();
+            }
             
             ui.separator();
             
-            // Page size selector
-            ui.label("Page size:");
-            egui::ComboBox::from_id_source("page_size")
-                .selected_text(self.page_size.to_string())
-                .show_ui(ui, |ui| {
-                    for size in [25, 50, 100, 200] {
-                        ui.selectable_value(&mut self.page_size, size, size.to_string());
+            // Column visibility
+            ui.menu_button("Columns", |ui| {
+                for column in &self.columns {
+                    let mut shown = !self.hidden_columns.contains(column);
+                    if ui.checkbox(&mut shown, column).changed() {
+                        if shown {
+                            self.hidden_columns.remove(column);
+                        } else {
+                            self.hidden_columns.insert(column.clone());
+                        }
                     }
-                });
+                }
+                ui.separator();
+                if ui.button("Show all").clicked() {
+                    self.hidden_columns.clear();
+                    ui.close_menu();
+                }
+            });
+            
+            if self.has_filters() && ui.button("Clear filters").clicked() {
+                self.filter_text.clear();
+                self.column_filters.clear();
+                self.invalidate_view();
+            }
+            
+            if self.view_mode != ViewMode::Table {
+                ui.separator();
+                
+                // Page size selector
+                ui.label("Page size:");
+                egui::ComboBox::from_id_source("page_size")
+                    .selected_text(self.page_size.to_string())
+                    .show_ui(ui, |ui| {
+                        for size in [25, 50, 100, 200] {
+                            ui.selectable_value(&mut self.page_size, size, size.to_string());
+                        }
+                    });
+            }
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 // Export button
@@ -125,100 +235,221 @@ impl ResultsViewer {
         });
     }
     
+    /// Table of every row passing the filters; only the rows in sight are laid out
     fn render_table_view(&mut self, ui: &mut egui::Ui) {
-        let filtered_data = self.get_filtered_data();
-        let sorted_data = self.get_sorted_data(&filtered_data);
-        let paginated_data = self.get_paginated_data(&sorted_data);
-        
-        if paginated_data.is_empty() {
+        if self.data.is_empty() {
             ui.centered_and_justified(|ui| {
                 ui.label("No data to display");
             });
             return;
         }
         
-        // Get column headers
-        let headers = self.get_column_headers(&paginated_data);
+        let columns: Vec<String> = self.visible_columns();
+        let row_height = ui.spacing().interact_size.y;
+        let mut actions = Vec::new();
+        let mut filters_changed = false;
         
-        egui::ScrollArea::both()
+        egui::ScrollArea::horizontal()
+            .id_source("results_table_columns")
             .auto_shrink([false, false])
             .show(ui, |ui| {
-                egui::Grid::new("results_table")
-                    .num_columns(headers.len() + 1) // +1 for selection checkbox
-                    .spacing([4.0, 2.0])
-                    .show(ui, |ui| {
-                        // Header row
-                        ui.checkbox(&mut false, ""); // Select all checkbox
-                        for header in &headers {
-                            let header_text = if self.sort_column.as_ref() == Some(header) {
-                                format!("{} {}", header, if self.sort_ascending { "↑" } else { "↓" })
-                            } else {
-                                header.clone()
+                ui.vertical(|ui| {
+                    // Header row: click to sort, right-click to hide
+                    ui.horizontal(|ui| {
+                        ui.add_space(ui.spacing().interact_size.x);
+                        for column in &columns {
+                            let arrow = match &self.sort_column {
+                                Some(sorted) if sorted == column => if self.sort_ascending { " ↑" } else { " ↓" },
+                                _ => "",
                             };
-                            
-                            if ui.button(header_text).clicked() {
-                                self.sort_by_column(header);
+                            let response = ui.add_sized(
+                                [COLUMN_WIDTH, row_height],
+                                egui::Button::new(format!("{}{}", column, arrow)),
+                            );
+                            if response.clicked() {
+                                actions.push(TableAction::Sort(column.clone()));
                             }
+                            response.context_menu(|ui| {
+                                if ui.button("Hide column").clicked() {
+                                    actions.push(TableAction::Hide(column.clone()));
+                                    ui.close_menu();
+                                }
+                            });
                         }
-                        ui.end_row();
-                        
-                        // Data rows
-                        for (row_idx, row) in paginated_data.iter().enumerate() {
-                            let global_idx = self.current_page * self.page_size + row_idx;
-                            let mut is_selected = self.selected_rows.contains(&global_idx);
-                            
-                            ui.checkbox(&mut is_selected, "");
-                            if is_selected {
-                                self.selected_rows.insert(global_idx);
-                            } else {
-                                self.selected_rows.remove(&global_idx);
-                            }
-                            
-                            for header in &headers {
-                                let value = row.get(header)
-                                    .map(|v| format_value(v))
-                                    .unwrap_or_else(|| "".to_string());
-                                ui.label(value);
+                    });
+                    
+                    // Per-column filters
+                    ui.horizontal(|ui| {
+                        ui.add_space(ui.spacing().interact_size.x);
+                        for column in &columns {
+                            let filter = self.column_filters.entry(column.clone()).or_default();
+                            let edit = egui::TextEdit::singleline(filter)
+                                .hint_text("Filter")
+                                .desired_width(COLUMN_WIDTH - ui.spacing().item_spacing.x);
+                            filters_changed |= ui.add(edit).changed();
+                        }
+                    });
+                    
+                    ui.separator();
+                    
+                    let view = self.view.as_deref().unwrap_or_default();
+                    if view.is_empty() {
+                        ui.label("No rows match the filters");
+                        return;
+                    }
+                    
+                    egui::ScrollArea::vertical()
+                        .id_source("results_table_rows")
+                        .auto_shrink([false, false])
+                        .show_rows(ui, row_height, view.len(), |ui, range| {
+                            for &idx in &view[range] {
+                                let row = &self.data[idx];
+                                let in_detail = self.detail_row == Some(idx);
+                                ui.horizontal(|ui| {
+                                    let mut checked = self.selected_rows.contains(&idx);
+                                    if ui.checkbox(&mut checked, "").changed() {
+                                        actions.push(TableAction::Check(idx, checked));
+                                    }
+                                    
+                                    for column in &columns {
+                                        let value = row.get(column);
+                                        let mut text = egui::RichText::new(value.map(format_value).unwrap_or_default());
+                                        if in_detail {
+                                            text = text.background_color(ui.visuals().selection.bg_fill);
+                                        }
+                                        let response = ui.add_sized(
+                                            [COLUMN_WIDTH, row_height],
+                                            egui::Label::new(text).truncate(true).sense(egui::Sense::click()),
+                                        );
+                                        if response.clicked() {
+                                            actions.push(TableAction::Detail(idx));
+                                        }
+                                        response.context_menu(|ui| {
+                                            if ui.button("Copy value").clicked() {
+                                                actions.push(TableAction::Copy(value.map(copy_text).unwrap_or_default()));
+                                                ui.close_menu();
+                                            }
+                                            if ui.button("Copy row as JSON").clicked() {
+                                                actions.push(TableAction::Copy(row_json(row)));
+                                                ui.close_menu();
+                                            }
+                                            if ui.button("Show details").clicked() {
+                                                actions.push(TableAction::Detail(idx));
+                                                ui.close_menu();
+                                            }
+                                        });
+                                    }
+                                });
                             }
-                            ui.end_row();
+                        });
+                });
+            });
+        
+        if filters_changed {
+            self.invalidate_view();
+        }
+        for action in actions {
+            match action {
+                TableAction::Sort(column) => self.sort_by_column(&column),
+                TableAction::Hide(column) => {
+                    self.hidden_columns.insert(column);
+                }
+                TableAction::Check(idx, true) => {
+                    self.selected_rows.insert(idx);
+                }
+                TableAction::Check(idx, false) => {
+                    self.selected_rows.remove(&idx);
+                }
+                TableAction::Detail(idx) => self.detail_row = Some(idx),
+                TableAction::Copy(text) => ui.ctx().output_mut(|output| output.copied_text = text),
+            }
+        }
+    }
+    
+    /// Source URL and raw JSON of the row picked in the table
+    fn render_detail_pane(&mut self, ui: &mut egui::Ui) {
+        let Some(row) = self.detail_row.and_then(|idx| self.data.get(idx)) else {
+            self.detail_row = None;
+            return;
+        };
+        let json = row_json(row);
+        let mut close = false;
+        
+        egui::SidePanel::right("results_detail")
+            .resizable(true)
+            .default_width(320.0)
+            .show_inside(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Row details");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        close = ui.button("✖").clicked();
+                        if ui.button("📋 Copy JSON").clicked() {
+                            ui.ctx().output_mut(|output| output.copied_text = json.clone());
                         }
                     });
+                });
+                
+                ui.separator();
+                
+                match row.get("_source_url").and_then(|url| url.as_str()) {
+                    Some(url) => {
+                        ui.label("Source:");
+                        ui.hyperlink(url);
+                    }
+                    None => {
+                        ui.label("Source: unknown");
+                    }
+                }
+                
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical()
+                    .id_source("results_detail_json")
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        ui.add(egui::TextEdit::multiline(&mut json.as_str())
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY));
+                    });
             });
+        
+        if close {
+            self.detail_row = None;
+        }
     }
     
     fn render_cards_view(&mut self, ui: &mut egui::Ui) {
-        let filtered_data = self.get_filtered_data();
-        let sorted_data = self.get_sorted_data(&filtered_data);
-        let paginated_data = self.get_paginated_data(&sorted_data);
+        let page = self.page_indices();
         
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
-                for (row_idx, row) in paginated_data.iter().enumerate() {
-                    let global_idx = self.current_page * self.page_size + row_idx;
-                    let is_selected = self.selected_rows.contains(&global_idx);
+                for idx in page {
+                    let is_selected = self.selected_rows.contains(&idx);
                     
                     egui::Frame::group(&egui::Style::default())
-                        .fill(if is_selected { 
-                            ui.style().visuals.selection.bg_fill 
-                        } else { 
-                            ui.style().visuals.panel_fill 
+                        .fill(if is_selected {
+                            ui.style().visuals.selection.bg_fill
+                        } else {
+                            ui.style().visuals.panel_fill
                         })
                         .stroke(egui::Stroke::new(1.0, ui.style().visuals.window_stroke.color))
                         .rounding(egui::Rounding::same(8.0))
                         .inner_margin(egui::Margin::same(12.0))
                         .show(ui, |ui| {
                             ui.horizontal(|ui| {
-                                let mut is_selected_copy = self.selected_rows.contains(&global_idx);
+                                let mut is_selected_copy = is_selected;
                                 ui.checkbox(&mut is_selected_copy, "");
                                 if is_selected_copy {
-                                    self.selected_rows.insert(global_idx);
+                                    self.selected_rows.insert(idx);
                                 } else {
-                                    self.selected_rows.remove(&global_idx);
+                                    self.selected_rows.remove(&idx);
                                 }
                                 
                                 ui.vertical(|ui| {
-                                    for (key, value) in row {
+                                    for (key, value) in &self.data[idx] {
+                                        if self.hidden_columns.contains(key) {
+                                            continue;
+                                        }
                                         ui.horizontal(|ui| {
                                             ui.strong(format!("{}: ", key));
                                             ui.label(format_value(value));
@@ -234,13 +465,15 @@ impl ResultsViewer {
     }
     
     fn render_json_view(&mut self, ui: &mut egui::Ui) {
-        let filtered_data = self.get_filtered_data();
+        let page: Vec<serde_json::Value> = self.page_indices().into_iter()
+            .map(|idx| serde_json::Value::Object(self.data[idx].clone().into_iter().collect()))
+            .collect();
         
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
-                if let Ok(json_str) = serde_json::to_string_pretty(&filtered_data) {
-                    ui.add(egui::TextEdit::multiline(&mut json_str.clone())
+                if let Ok(json_str) = serde_json::to_string_pretty(&page) {
+                    ui.add(egui::TextEdit::multiline(&mut json_str.as_str())
                         .font(egui::TextStyle::Monospace)
                         .desired_width(f32::INFINITY)
                         .desired_rows(20));
@@ -251,76 +484,87 @@ impl ResultsViewer {
     }
     
     fn render_statistics_view(&mut self, ui: &mut egui::Ui) {
-        let filtered_data = self.get_filtered_data();
+        let view = self.view.as_deref().unwrap_or_default();
         
         ui.vertical(|ui| {
             ui.heading("Data Statistics");
             
             // Basic stats
             ui.group(|ui| {
-                ui.label(format!("Total records: {}", filtered_data.len()));
+                ui.label(format!("Total records: {}", view.len()));
                 ui.label(format!("Selected records: {}", self.selected_rows.len()));
-                ui.label(format!("Current page: {}", self.current_page + 1));
-                ui.label(format!("Total pages: {}", (filtered_data.len() + self.page_size - 1) / self.page_size));
+                ui.label(format!("Hidden columns: {}", self.hidden_columns.len()));
             });
             
             // Column statistics
-            if !filtered_data.is_empty() {
+            if !view.is_empty() {
                 ui.add_space(16.0);
                 ui.heading("Column Statistics");
                 
-                let headers = self.get_column_headers(&filtered_data);
-                
-                for header in headers {
-                    ui.group(|ui| {
-                        ui.strong(&header);
-                        
-                        let values: Vec<&serde_json::Value> = filtered_data.iter()
-                            .filter_map(|row| row.get(&header))
-                            .collect();
-                        
-                        if !values.is_empty() {
-                            ui.label(format!("Count: {}", values.len()));
-                            
-                            // Type analysis
-                            let types: std::collections::HashMap<String, usize> = values.iter()
-                                .map(|v| match v {
-                                    serde_json::Value::String(_) => "String",
-                                    serde_json::Value::Number(_) => "Number",
-                                    serde_json::Value::Bool(_) => "Boolean",
-                                    serde_json::Value::Null => "Null",
-                                    _ => "Other",
-                                })
-                                .fold(std::collections::HashMap::new(), |mut acc, t| {
-                                    *acc.entry(t.to_string()).or_insert(0) += 1;
-                                    acc
-                                });
-                            
-                            for (type_name, count) in types {
-                                ui.label(format!("  {}: {}", type_name, count));
-                            }
-                            
-                            // Sample values
-                            ui.label("Sample values:");
-                            for (i, value) in values.iter().take(3).enumerate() {
-                                ui.label(format!("  {}: {}", i + 1, format_value(value)));
-                            }
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        for header in self.visible_columns() {
+                            ui.group(|ui| {
+                                ui.strong(&header);
+                                
+                                let values: Vec<&serde_json::Value> = view.iter()
+                                    .filter_map(|&idx| self.data[idx].get(&header))
+                                    .collect();
+                                
+                                if !values.is_empty() {
+                                    ui.label(format!("Count: {}", values.len()));
+                                    
+                                    // Type analysis
+                                    let mut types: Vec<(&str, usize)> = values.iter()
+                                        .map(|v| match v {
+                                            serde_json::Value::String(_) => "String",
+                                            serde_json::Value::Number(_) => "Number",
+                                            serde_json::Value::Bool(_) => "Boolean",
+                                            serde_json::Value::Null => "Null",
+                                            _ => "Other",
+                                        })
+                                        .fold(HashMap::new(), |mut acc, t| {
+                                            *acc.entry(t).or_insert(0) += 1;
+                                            acc
+                                        })
+                                        .into_iter()
+                                        .collect();
+                                    types.sort();
+                                    
+                                    for (type_name, count) in types {
+                                        ui.label(format!("  {}: {}", type_name, count));
+                                    }
+                                    
+                                    // Sample values
+                                    ui.label("Sample values:");
+                                    for (i, value) in values.iter().take(3).enumerate() {
+                                        ui.label(format!("  {}: {}", i + 1, format_value(value)));
+                                    }
+                                }
+                            });
                         }
                     });
-                }
             }
         });
     }
     
     fn render_footer(&mut self, ui: &mut egui::Ui) {
+        let shown = self.view.as_ref().map_or(0, Vec::len);
+        
         ui.separator();
         ui.horizontal(|ui| {
-            let total_pages = (self.get_filtered_data().len() + self.page_size - 1) / self.page_size;
+            if self.view_mode == ViewMode::Table || self.view_mode == ViewMode::Statistics {
+                ui.label(format!("{} of {} rows", shown, self.data.len()));
+                return;
+            }
             
+            let total_pages = shown.div_ceil(self.page_size).max(1);
+            self.current_page = self.current_page.min(total_pages - 1);
             ui.label(format!("Page {} of {}", self.current_page + 1, total_pages));
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui.button("Next").clicked() && self.current_page < total_pages - 1 {
+                if ui.button("Next").clicked() && self.current_page + 1 < total_pages {
                     self.current_page += 1;
                 }
                 
@@ -331,73 +575,79 @@ impl ResultsViewer {
         });
     }
     
-    fn get_filtered_data(&self) -> Vec<HashMap<String, serde_json::Value>> {
-        if self.filter_text.is_empty() {
-            return self.data.clone();
-        }
-        
-        self.data.iter()
-            .filter(|row| {
-                row.values().any(|value| {
-                    format_value(value).to_lowercase().contains(&self.filter_text.to_lowercase())
-                })
-            })
+    /// Columns of the table, leaving out hidden ones
+    fn visible_columns(&self) -> Vec<String> {
+        self.columns.iter()
+            .filter(|column| !self.hidden_columns.contains(*column))
             .cloned()
             .collect()
     }
     
-    fn get_sorted_data(&self, data: &[HashMap<String, serde_json::Value>]) -> Vec<HashMap<String, serde_json::Value>> {
-        if let Some(ref column) = self.sort_column {
-            let mut sorted = data.to_vec();
-            sorted.sort_by(|a, b| {
-                let a_val = a.get(column).map(format_value).unwrap_or_default();
-                let b_val = b.get(column).map(format_value).unwrap_or_default();
-                
-                if self.sort_ascending {
-                    a_val.cmp(&b_val)
-                } else {
-                    b_val.cmp(&a_val)
-                }
-            });
-            sorted
-        } else {
-            data.to_vec()
+    /// Add the columns of rows from `first` on, scraped fields before `_` metadata
+    fn add_columns(&mut self, first: usize) {
+        let known: HashSet<&String> = self.columns.iter().collect();
+        let mut new: Vec<String> = self.data[first..].iter()
+            .flat_map(|row| row.keys())
+            .filter(|key| !known.contains(key))
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if new.is_empty() {
+            return;
         }
+        new.append(&mut self.columns);
+        new.sort_by(|a, b| (a.starts_with('_'), a).cmp(&(b.starts_with('_'), b)));
+        self.columns = new;
     }
     
-    fn get_paginated_data(&self, data: &[HashMap<String, serde_json::Value>]) -> Vec<HashMap<String, serde_json::Value>> {
-        let start = self.current_page * self.page_size;
-        let end = (start + self.page_size).min(data.len());
-        
-        if start >= data.len() {
-            Vec::new()
-        } else {
-            data[start..end].to_vec()
-        }
+    fn has_filters(&self) -> bool {
+        !self.filter_text.is_empty() || self.column_filters.values().any(|filter| !filter.is_empty())
     }
     
-    fn get_column_headers(&self, data: &[HashMap<String, serde_json::Value>]) -> Vec<String> {
-        if data.is_empty() {
-            return Vec::new();
+    fn invalidate_view(&mut self) {
+        self.view = None;
+        self.current_page = 0;
+    }
+    
+    /// Work out which rows pass the filters, and their order, after a change
+    fn refresh_view(&mut self) {
+        if self.view.is_some() {
+            return;
         }
         
-        let mut headers: std::collections::HashSet<String> = std::collections::HashSet::new();
-        for row in data {
-            headers.extend(row.keys().cloned());
-        }
+        let filter = RowFilter::new(&self.filter_text, &self.column_filters);
+        let mut view: Vec<usize> = (0..self.data.len())
+            .filter(|&idx| filter.matches(&self.data[idx]))
+            .collect();
         
-        let mut headers: Vec<String> = headers.into_iter().collect();
-        headers.sort();
-        headers
+        if let Some(column) = &self.sort_column {
+            view.sort_by(|&a, &b| {
+                let ordering = compare_values(self.data[a].get(column), self.data[b].get(column));
+                if self.sort_ascending { ordering } else { ordering.reverse() }
+            });
+        }
+        self.view = Some(view);
+    }
+    
+    /// Rows of the current page, for the paged views
+    fn page_indices(&self) -> Vec<usize> {
+        let view = self.view.as_deref().unwrap_or_default();
+        view.iter()
+            .skip(self.current_page * self.page_size)
+            .take(self.page_size)
+            .copied()
+            .collect()
     }
     
     fn sort_by_column(&mut self, column: &str) {
-        if self.sort_column.as_ref() == Some(&column.to_string()) {
+        if self.sort_column.as_deref() == Some(column) {
             self.sort_ascending = !self.sort_ascending;
         } else {
             self.sort_column = Some(column.to_string());
             self.sort_ascending = true;
         }
+        self.invalidate_view();
     }
     
     fn export_data(&self) {
@@ -406,6 +656,56 @@ impl ResultsViewer {
     }
 }
 
+/// The global filter and per-column filters, lowercased once per refresh
+#[cfg(feature = "ui")]
+struct RowFilter {
+    any: String,
+    columns: Vec<(String, String)>,
+}
+
+#[cfg(feature = "ui")]
+impl RowFilter {
+    fn new(any: &str, columns: &HashMap<String, String>) -> Self {
+        Self {
+            any: any.to_lowercase(),
+            columns: columns.iter()
+                .filter(|(_, filter)| !filter.is_empty())
+                .map(|(column, filter)| (column.clone(), filter.to_lowercase()))
+                .collect(),
+        }
+    }
+    
+    fn matches(&self, row: &HashMap<String, serde_json::Value>) -> bool {
+        let contains = |value: &serde_json::Value, needle: &str| format_value(value).to_lowercase().contains(needle);
+        (self.any.is_empty() || row.values().any(|value| contains(value, &self.any)))
+            && self.columns.iter().all(|(column, needle)| row.get(column).is_some_and(|value| contains(value, needle)))
+    }
+}
+
+/// Order of two cells: numbers by value, anything else as text, missing cells first
+#[cfg(feature = "ui")]
+fn compare_values(a: Option<&serde_json::Value>, b: Option<&serde_json::Value>) -> Ordering {
+    match (a.and_then(serde_json::Value::as_f64), b.and_then(serde_json::Value::as_f64)) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        _ => a.map(format_value).cmp(&b.map(format_value)),
+    }
+}
+
+/// Text put on the clipboard for a cell, the full JSON for arrays and objects
+#[cfg(feature = "ui")]
+fn copy_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(feature = "ui")]
+fn row_json(row: &HashMap<String, serde_json::Value>) -> String {
+    let object: serde_json::Map<String, serde_json::Value> = row.clone().into_iter().collect();
+    serde_json::to_string_pretty(&object).unwrap_or_default()
+}
+
 fn format_value(value: &serde_json::Value) -> String {
     match value {
         serde_json::Value::String(s) => s.clone(),
@@ -444,3 +744,32 @@ pub enum ExportFormat {
     XLSX,
     Parquet,
 }
+
+#[cfg(all(test, feature = "ui"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    fn row(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        serde_json::from_value(value).unwrap()
+    }
+    
+    #[test]
+    fn test_view_filters_and_sorts_numbers_by_value() {
+        let mut viewer = ResultsViewer::new("job".to_string(), vec![
+            row(json!({ "name": "Lamp", "price": 25, "_source_url": "https://example.com/1" })),
+            row(json!({ "name": "Desk", "price": 120 })),
+            row(json!({ "name": "Pen", "price": 3 })),
+        ]);
+        assert_eq!(viewer.columns, vec!["name", "price", "_source_url"]);
+        
+        viewer.sort_by_column("price");
+        viewer.refresh_view();
+        assert_eq!(viewer.view, Some(vec![2, 0, 1]));
+        
+        viewer.column_filters.insert("name".to_string(), "e".to_string());
+        viewer.invalidate_view();
+        viewer.refresh_view();
+        assert_eq!(viewer.view, Some(vec![2, 1]));
+    }
+}
@@ -0,0 +1,329 @@
+use chrono::{DateTime, Utc};
+use eframe::egui;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::storage::{Job, ResultChanges};
+use crate::utils::time_utils::format_local;
+use super::windows_theme::WindowsTheme;
+
+/// Other runs of a job's plan, or why they could not be listed
+pub type PlanRunsResult = Result<Vec<Job>, String>;
+
+/// Differences between two runs, or why they could not be compared
+pub type ComparisonResult = Result<ResultChanges, String>;
+
+/// Most rows listed per kind of change
+const MAX_ROWS_SHOWN: usize = 500;
+
+/// Kind of change shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Changed,
+    Added,
+    Removed,
+}
+
+/// Window comparing a job's results with another run of the same plan
+pub struct RunComparison {
+    /// The later run
+    pub job_id: String,
+    created_at: DateTime<Utc>,
+    /// Other runs of the plan, newest first
+    runs: Option<Vec<Job>>,
+    /// The run compared against
+    pub base_job_id: Option<String>,
+    changes: Option<ResultChanges>,
+    pub error: Option<String>,
+    pub loading: bool,
+    tab: Tab,
+}
+
+/// What the comparison window asked for
+pub enum RunComparisonAction {
+    None,
+    Close,
+    /// Compare the job with this run
+    Compare(String),
+}
+
+/// A field whose value differs between two versions of a row
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl RunComparison {
+    pub fn new(job_id: String, created_at: DateTime<Utc>) -> Self {
+        Self {
+            job_id,
+            created_at,
+            runs: None,
+            base_job_id: None,
+            changes: None,
+            error: None,
+            loading: true,
+            tab: Tab::Changed,
+        }
+    }
+
+    /// Take the list of runs, comparing with the newest earlier one if there is one
+    ///
+    /// Returns the run to compare with.
+    pub fn set_runs(&mut self, result: PlanRunsResult) -> Option<String> {
+        self.loading = false;
+        match result {
+            Ok(runs) => {
+                let base = runs.iter()
+                    .find(|run| run.created_at < self.created_at)
+                    .or_else(|| runs.first())
+                    .map(|run| run.id.clone());
+                self.runs = Some(runs);
+                self.error = None;
+                if base.is_some() {
+                    self.base_job_id = base.clone();
+                    self.loading = true;
+                }
+                base
+            }
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    pub fn set_changes(&mut self, result: ComparisonResult) {
+        self.loading = false;
+        match result {
+            Ok(changes) => {
+                self.changes = Some(changes);
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, theme: &WindowsTheme) -> RunComparisonAction {
+        let mut action = RunComparisonAction::None;
+        let mut open = true;
+        egui::Window::new(format!("🔀 Compare Runs — {}", self.job_id))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([720.0, 520.0])
+            .show(ctx, |ui| {
+                if let Some(requested) = self.render_run_picker(ui) {
+                    action = requested;
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(theme.get_status_color("error"), format!("❌ {}", error));
+                }
+                ui.separator();
+
+                let Some(changes) = &self.changes else { return };
+                ui.horizontal(|ui| {
+                    ui.colored_label(theme.get_status_color("warning"), format!("~ {} changed", changes.changed.len()));
+                    ui.colored_label(theme.get_status_color("success"), format!("+ {} added", changes.added.len()));
+                    ui.colored_label(theme.get_status_color("error"), format!("− {} removed", changes.removed.len()));
+                    ui.label(format!("{} unchanged", changes.unchanged));
+                });
+                match &changes.hash_fields {
+                    Some(fields) => ui.weak(format!("Compared on: {}", fields.join(", "))),
+                    None => ui.weak("Compared on all fields"),
+                };
+                ui.add_space(4.0);
+
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.tab, Tab::Changed, "Changed");
+                    ui.selectable_value(&mut self.tab, Tab::Added, "Added");
+                    ui.selectable_value(&mut self.tab, Tab::Removed, "Removed");
+                });
+                ui.separator();
+
+                egui::ScrollArea::both().auto_shrink([false, false]).show(ui, |ui| {
+                    match self.tab {
+                        Tab::Changed => render_changed_rows(ui, &changes.changed, theme),
+                        Tab::Added => render_rows(ui, "comparison_added", &changes.added),
+                        Tab::Removed => render_rows(ui, "comparison_removed", &changes.removed),
+                    }
+                });
+            });
+
+        if !open {
+            action = RunComparisonAction::Close;
+        }
+        action
+    }
+
+    /// Choice of the run to compare with
+    fn render_run_picker(&mut self, ui: &mut egui::Ui) -> Option<RunComparisonAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.label("Compare with:");
+            match &self.runs {
+                None => {}
+                Some(runs) if runs.is_empty() => {
+                    ui.label("No other runs of this plan yet.");
+                }
+                Some(runs) => {
+                    let selected = runs.iter()
+                        .find(|run| Some(&run.id) == self.base_job_id.as_ref())
+                        .map(run_label)
+                        .unwrap_or_default();
+                    egui::ComboBox::from_id_source("comparison_base_run")
+                        .selected_text(selected)
+                        .width(320.0)
+                        .show_ui(ui, |ui| {
+                            for run in runs {
+                                let is_selected = Some(&run.id) == self.base_job_id.as_ref();
+                                if ui.selectable_label(is_selected, run_label(run)).clicked() && !is_selected {
+                                    action = Some(RunComparisonAction::Compare(run.id.clone()));
+                                }
+                            }
+                        });
+                }
+            }
+            if self.loading {
+                ui.spinner();
+            }
+        });
+
+        if let Some(RunComparisonAction::Compare(base_job_id)) = &action {
+            self.base_job_id = Some(base_job_id.clone());
+            self.loading = true;
+        }
+        action
+    }
+}
+
+/// A run as listed in the picker
+fn run_label(run: &Job) -> String {
+    format!("{} · {} · {}", format_local(&run.created_at, "%Y-%m-%d %H:%M"), run.status, short_id(&run.id))
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Rows with the same key and different content, as the fields that changed
+fn render_changed_rows(ui: &mut egui::Ui, changed: &[(Value, Value)], theme: &WindowsTheme) {
+    if changed.is_empty() {
+        ui.label("No rows changed.");
+        return;
+    }
+    for (index, (before, after)) in changed.iter().take(MAX_ROWS_SHOWN).enumerate() {
+        let changes = field_changes(before, after);
+        let title = changes.iter().map(|change| change.field.as_str()).collect::<Vec<_>>().join(", ");
+        egui::CollapsingHeader::new(format!("{}  ({})", row_label(after), title))
+            .id_source(("comparison_changed", index))
+            .default_open(index < 20)
+            .show(ui, |ui| {
+                egui::Grid::new(("comparison_changed_fields", index)).striped(true).show(ui, |ui| {
+                    for change in &changes {
+                        ui.strong(&change.field);
+                        ui.colored_label(theme.get_status_color("error"), format_field(change.before.as_ref()));
+                        ui.label("→");
+                        ui.colored_label(theme.get_status_color("success"), format_field(change.after.as_ref()));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+    if changed.len() > MAX_ROWS_SHOWN {
+        ui.weak(format!("… and {} more", changed.len() - MAX_ROWS_SHOWN));
+    }
+}
+
+/// Added or removed rows as a table over all their fields
+fn render_rows(ui: &mut egui::Ui, id: &str, rows: &[Value]) {
+    if rows.is_empty() {
+        ui.label("No rows.");
+        return;
+    }
+    let columns: BTreeSet<&str> = rows.iter()
+        .filter_map(Value::as_object)
+        .flat_map(|object| object.keys())
+        .map(String::as_str)
+        .filter(|name| !name.starts_with('_'))
+        .collect();
+
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        for column in &columns {
+            ui.strong(*column);
+        }
+        ui.end_row();
+        for row in rows.iter().take(MAX_ROWS_SHOWN) {
+            for column in &columns {
+                ui.label(format_field(row.get(*column)));
+            }
+            ui.end_row();
+        }
+    });
+    if rows.len() > MAX_ROWS_SHOWN {
+        ui.weak(format!("… and {} more", rows.len() - MAX_ROWS_SHOWN));
+    }
+}
+
+/// Fields that differ between two versions of a row, metadata left out, in name order
+pub fn field_changes(before: &Value, after: &Value) -> Vec<FieldChange> {
+    let fields: BTreeSet<&String> = before.as_object().into_iter()
+        .chain(after.as_object())
+        .flat_map(|object| object.keys())
+        .filter(|name| !name.starts_with('_'))
+        .collect();
+
+    fields.into_iter()
+        .filter_map(|field| {
+            let (old, new) = (before.get(field), after.get(field));
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Something to recognise a row by: its first text field
+fn row_label(row: &Value) -> String {
+    let text = row.as_object()
+        .and_then(|object| object.iter()
+            .filter(|(name, _)| !name.starts_with('_'))
+            .find_map(|(_, value)| value.as_str()))
+        .unwrap_or("Row");
+    if text.chars().count() > 50 {
+        format!("{}…", text.chars().take(50).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+fn format_field(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "—".to_string(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_changes_skip_metadata_and_equal_fields() {
+        let before = json!({"title": "Lamp", "price": 20, "stock": 3, "_scraped_at": "a"});
+        let after = json!({"title": "Lamp", "price": 18, "color": "red", "_scraped_at": "b"});
+
+        let changes = field_changes(&before, &after);
+        assert_eq!(changes, vec![
+            FieldChange { field: "color".to_string(), before: None, after: Some(json!("red")) },
+            FieldChange { field: "price".to_string(), before: Some(json!(20)), after: Some(json!(18)) },
+            FieldChange { field: "stock".to_string(), before: Some(json!(3)), after: None },
+        ]);
+    }
+}
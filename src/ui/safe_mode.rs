@@ -0,0 +1,147 @@
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use tracing::{error, info};
+
+use crate::config::AppConfig;
+use crate::core::self_check::{self, CheckSeverity, RepairAction, SelfCheckReport};
+
+/// Outcome of a repair together with the configuration and checks after it
+type RepairOutcome = (AppConfig, SelfCheckReport, Result<String, String>);
+
+/// Window listing startup problems with a repair button for each
+pub struct SafeModeUI {
+    config: AppConfig,
+    config_path: PathBuf,
+    report: SelfCheckReport,
+    messages: Vec<String>,
+    pending: Option<mpsc::Receiver<RepairOutcome>>,
+}
+
+impl SafeModeUI {
+    pub fn new(config: AppConfig, report: SelfCheckReport) -> Self {
+        Self {
+            config,
+            config_path: AppConfig::default_path(),
+            report,
+            messages: Vec::new(),
+            pending: None,
+        }
+    }
+
+    /// Run a repair in the background, re-checking once it is done
+    fn start_repair(&mut self, action: RepairAction, ctx: &egui::Context) {
+        let (tx, rx) = mpsc::channel();
+        let mut config = self.config.clone();
+        let config_path = self.config_path.clone();
+        let ctx = ctx.clone();
+
+        tokio::spawn(async move {
+            let result = self_check::apply_repair(&mut config, &config_path, &action).await
+                .map_err(|e| format!("{}: {}", action.label(), e));
+            let report = self_check::run_self_check(&config).await;
+            let _ = tx.send((config, report, result));
+            ctx.request_repaint();
+        });
+
+        self.pending = Some(rx);
+    }
+
+    fn poll_repair(&mut self) {
+        let Some(rx) = &self.pending else { return };
+        if let Ok((config, report, result)) = rx.try_recv() {
+            match result {
+                Ok(message) => {
+                    info!("Safe mode repair: {}", message);
+                    self.messages.push(message);
+                }
+                Err(e) => {
+                    error!("Safe mode repair failed: {}", e);
+                    self.messages.push(format!("Repair failed: {}", e));
+                }
+            }
+            self.config = config;
+            self.report = report;
+            self.pending = None;
+        }
+    }
+}
+
+impl eframe::App for SafeModeUI {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_repair();
+        let busy = self.pending.is_some();
+        let mut repair = None;
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("WinScrape Studio - Safe Mode");
+            ui.label("Some problems keep WinScrape Studio from starting normally.");
+            ui.add_space(12.0);
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for problem in &self.report.problems {
+                    ui.group(|ui| {
+                        let (label, color) = match problem.severity {
+                            CheckSeverity::Error => ("Error", egui::Color32::from_rgb(220, 80, 80)),
+                            CheckSeverity::Warning => ("Warning", egui::Color32::from_rgb(220, 170, 60)),
+                        };
+                        ui.horizontal(|ui| {
+                            ui.colored_label(color, label);
+                            ui.strong(&problem.check);
+                        });
+                        ui.label(&problem.message);
+                        if let Some(action) = &problem.repair {
+                            if ui.add_enabled(!busy, egui::Button::new(action.label())).clicked() {
+                                repair = Some(action.clone());
+                            }
+                        }
+                    });
+                    ui.add_space(6.0);
+                }
+            });
+
+            if !self.messages.is_empty() {
+                ui.separator();
+                for message in &self.messages {
+                    ui.label(message);
+                }
+            }
+
+            ui.separator();
+            if busy {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Repairing...");
+                });
+            } else if self.report.needs_safe_mode() {
+                ui.label("Apply the repairs above, then restart WinScrape Studio.");
+            } else {
+                ui.label("All blocking problems are fixed. Restart WinScrape Studio to continue.");
+            }
+            if ui.button("Close").clicked() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        });
+
+        if let Some(action) = repair {
+            self.start_repair(action, ctx);
+        }
+    }
+}
+
+/// Show the safe mode window until the user closes it
+pub fn run(config: AppConfig, report: SelfCheckReport) -> anyhow::Result<()> {
+    let native_options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default()
+            .with_inner_size([720.0, 520.0])
+            .with_min_inner_size([480.0, 360.0]),
+        ..Default::default()
+    };
+
+    let ui = SafeModeUI::new(config, report);
+    eframe::run_native(
+        "WinScrape Studio - Safe Mode",
+        native_options,
+        Box::new(|_cc| Box::new(ui)),
+    ).map_err(|e| anyhow::anyhow!("Failed to start safe mode UI: {}", e))
+}
@@ -0,0 +1,160 @@
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+use crate::logging::{AuditEvent, AUDIT_SEVERITIES};
+use crate::storage::AuditEventFilter;
+use crate::utils::time_utils::format_local;
+use super::windows_theme::WindowsTheme;
+
+/// How often the events are reloaded while the tab is open
+pub const AUDIT_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Most events loaded at once
+pub const AUDIT_EVENT_LIMIT: usize = 1000;
+
+/// Loaded security events, or why they could not be loaded
+pub type AuditEventsResult = Result<Vec<AuditEvent>, String>;
+
+/// Event types offered in the type filter
+const EVENT_TYPES: [&str; 8] = [
+    "blocked_domain",
+    "rejected_url",
+    "rejected_input",
+    "rejected_selector",
+    "rejected_header",
+    "robots_disallowed",
+    "pii_filtered",
+    "access_denied",
+];
+
+/// Security tab: recorded security events, newest first
+#[derive(Default)]
+pub struct SecurityView {
+    pub events: Vec<AuditEvent>,
+    pub error: Option<String>,
+    pub loading: bool,
+    loaded_at: Option<Instant>,
+    filter: AuditEventFilter,
+    search: String,
+}
+
+/// What the security view asked for
+pub enum SecurityViewAction {
+    None,
+    /// Load the events matching this filter
+    Refresh(AuditEventFilter),
+}
+
+impl SecurityView {
+    pub fn set_events(&mut self, result: AuditEventsResult) {
+        self.loading = false;
+        self.loaded_at = Some(Instant::now());
+        match result {
+            Ok(events) => {
+                self.events = events;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    /// Whether it is time to load the events again
+    pub fn needs_refresh(&self) -> bool {
+        !self.loading && self.loaded_at.is_none_or(|loaded_at| loaded_at.elapsed() >= AUDIT_REFRESH_INTERVAL)
+    }
+
+    /// The filter currently set
+    pub fn filter(&self) -> AuditEventFilter {
+        self.filter.clone()
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, theme: &WindowsTheme) -> SecurityViewAction {
+        let before = (self.filter.min_severity.clone(), self.filter.event_type.clone());
+        let mut refresh = false;
+
+        ui.horizontal_wrapped(|ui| {
+            egui::ComboBox::from_id_source("security_view_severity")
+                .selected_text(self.filter.min_severity.as_deref().map_or("All severities".to_string(), |severity| format!("{} and above", severity)))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.min_severity, None, "All severities");
+                    for severity in AUDIT_SEVERITIES {
+                        ui.selectable_value(&mut self.filter.min_severity, Some(severity.to_string()), severity);
+                    }
+                });
+            egui::ComboBox::from_id_source("security_view_type")
+                .selected_text(self.filter.event_type.as_deref().unwrap_or("All events"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.filter.event_type, None, "All events");
+                    for event_type in EVENT_TYPES {
+                        ui.selectable_value(&mut self.filter.event_type, Some(event_type.to_string()), event_type);
+                    }
+                });
+            ui.label("🔍");
+            let search = ui.add(egui::TextEdit::singleline(&mut self.search).hint_text("Description or domain").desired_width(180.0));
+            if search.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                refresh = true;
+            }
+            if ui.add_enabled(!self.loading, egui::Button::new("🔄 Refresh")).clicked() {
+                refresh = true;
+            }
+            if self.loading {
+                ui.spinner();
+            }
+        });
+        let search = self.search.trim();
+        self.filter.text = (!search.is_empty()).then(|| search.to_string());
+
+        if let Some(error) = &self.error {
+            ui.colored_label(theme.get_status_color("error"), format!("❌ {}", error));
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{} events", self.events.len()));
+            for severity in AUDIT_SEVERITIES {
+                let count = self.events.iter().filter(|event| event.severity == severity).count();
+                if count > 0 {
+                    ui.colored_label(severity_color(severity, theme), format!("{} {}", count, severity));
+                }
+            }
+        });
+        ui.separator();
+
+        if self.events.is_empty() && !self.loading {
+            ui.label("No security events recorded.");
+        }
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical()
+            .id_source("security_view_events")
+            .auto_shrink([false, false])
+            .show_rows(ui, row_height, self.events.len(), |ui, rows| {
+                for event in &self.events[rows] {
+                    ui.horizontal(|ui| {
+                        ui.monospace(format_local(&event.timestamp, "%m-%d %H:%M:%S"));
+                        ui.colored_label(severity_color(&event.severity, theme), egui::RichText::new(event.severity.to_uppercase()).monospace());
+                        ui.label(egui::RichText::new(&event.event_type).weak());
+                        if let Some(domain) = &event.domain {
+                            ui.monospace(domain);
+                        }
+                        let description = ui.label(&event.description);
+                        if let Some(job_id) = &event.job_id {
+                            description.on_hover_text(format!("Job {}", job_id));
+                        }
+                    });
+                }
+            });
+
+        if refresh || before != (self.filter.min_severity.clone(), self.filter.event_type.clone()) {
+            SecurityViewAction::Refresh(self.filter.clone())
+        } else {
+            SecurityViewAction::None
+        }
+    }
+}
+
+fn severity_color(severity: &str, theme: &WindowsTheme) -> egui::Color32 {
+    match severity {
+        "critical" | "high" => theme.get_status_color("error"),
+        "medium" => theme.get_status_color("warning"),
+        _ => theme.get_status_color("info"),
+    }
+}
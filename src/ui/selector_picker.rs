@@ -0,0 +1,237 @@
+use eframe::egui;
+use std::collections::HashSet;
+
+use crate::dsl::{ExtractionMethod, Field, InspectedPage, PickedSelector};
+
+/// A fetched page, or why it could not be fetched
+pub type InspectResult = Result<InspectedPage, String>;
+
+/// Nodes at this depth and deeper start collapsed
+const COLLAPSED_DEPTH: usize = 3;
+
+/// Inspector panel where clicking an element of a page turns it into a plan field
+pub struct SelectorPicker {
+    pub url: String,
+    pub page: Option<InspectedPage>,
+    pub error: Option<String>,
+    pub loading: bool,
+    collapsed: HashSet<usize>,
+    selected: Option<usize>,
+    picked: Option<Result<PickedSelector, String>>,
+    /// Item selector `picked` was built against
+    picked_for: String,
+}
+
+/// What the picker asked for
+pub enum PickerAction {
+    None,
+    /// Fetch and inspect this page
+    Load(String),
+    AddField(Field),
+    UseItemSelector(String),
+    Close,
+}
+
+impl SelectorPicker {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            page: None,
+            error: None,
+            loading: false,
+            collapsed: HashSet::new(),
+            selected: None,
+            picked: None,
+            picked_for: String::new(),
+        }
+    }
+
+    /// Show a fetched page, with deep nodes collapsed
+    pub fn set_page(&mut self, result: InspectResult) {
+        self.loading = false;
+        self.selected = None;
+        self.picked = None;
+        match result {
+            Ok(page) => {
+                self.collapsed = page.nodes.iter().enumerate()
+                    .filter(|(index, node)| node.depth >= COLLAPSED_DEPTH && node.end > index + 1)
+                    .map(|(index, _)| index)
+                    .collect();
+                self.error = None;
+                self.page = Some(page);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn pick(&mut self, index: usize, item_selector: &str) {
+        let Some(page) = &self.page else { return };
+        self.selected = Some(index);
+        self.picked = Some(page.pick(index, Some(item_selector)).map_err(|e| e.to_string()));
+        self.picked_for = item_selector.to_string();
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, item_selector: &str) -> PickerAction {
+        // Selectors are relative to the item selector, so follow its changes
+        if let Some(index) = self.selected.filter(|_| self.picked_for != item_selector) {
+            self.pick(index, item_selector);
+        }
+
+        let mut action = PickerAction::None;
+        let mut open = true;
+        egui::Window::new("🎯 Pick Selectors")
+            .open(&mut open)
+            .resizable(true)
+            .default_size([560.0, 520.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Page:");
+                    let response = ui.add(egui::TextEdit::singleline(&mut self.url).desired_width(360.0));
+                    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.add_enabled(!self.loading, egui::Button::new("Load")).clicked() || submitted {
+                        self.loading = true;
+                        action = PickerAction::Load(self.url.trim().to_string());
+                    }
+                    if self.loading {
+                        ui.spinner();
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::from_rgb(220, 80, 80), format!("❌ {}", error));
+                }
+
+                if self.page.is_some() {
+                    ui.label(egui::RichText::new("Click an element to build a selector for it").weak());
+                    self.render_tree(ui, item_selector);
+                    ui.separator();
+                    if let Some(picked) = self.render_picked(ui) {
+                        action = picked;
+                    }
+                }
+            });
+
+        if !open {
+            action = PickerAction::Close;
+        }
+        action
+    }
+
+    /// Elements of the page, one row each, skipping collapsed subtrees
+    fn render_tree(&mut self, ui: &mut egui::Ui, item_selector: &str) {
+        let Some(page) = &self.page else { return };
+        let mut visible = Vec::with_capacity(page.nodes.len());
+        let mut index = 0;
+        while index < page.nodes.len() {
+            visible.push(index);
+            index = if self.collapsed.contains(&index) { page.nodes[index].end } else { index + 1 };
+        }
+
+        let row_height = ui.spacing().interact_size.y;
+        let mut toggled = None;
+        let mut clicked = None;
+        egui::ScrollArea::vertical()
+            .id_source("selector_picker_tree")
+            .max_height(280.0)
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, visible.len(), |ui, rows| {
+                for &index in &visible[rows] {
+                    let node = &page.nodes[index];
+                    ui.horizontal(|ui| {
+                        ui.add_space(node.depth as f32 * 14.0);
+                        if node.end > index + 1 {
+                            let arrow = if self.collapsed.contains(&index) { "▸" } else { "▾" };
+                            if ui.small_button(arrow).clicked() {
+                                toggled = Some(index);
+                            }
+                        } else {
+                            ui.add_space(ui.spacing().interact_size.y);
+                        }
+                        let label = egui::RichText::new(&node.label).monospace();
+                        if ui.selectable_label(self.selected == Some(index), label).clicked() {
+                            clicked = Some(index);
+                        }
+                        if !node.text.is_empty() {
+                            ui.label(egui::RichText::new(&node.text).weak());
+                        }
+                    });
+                }
+            });
+
+        if let Some(index) = toggled {
+            if !self.collapsed.remove(&index) {
+                self.collapsed.insert(index);
+            }
+        }
+        if let Some(index) = clicked {
+            self.pick(index, item_selector);
+        }
+    }
+
+    fn render_picked(&mut self, ui: &mut egui::Ui) -> Option<PickerAction> {
+        let picked = match self.picked.as_mut()? {
+            Ok(picked) => picked,
+            Err(e) => {
+                ui.colored_label(egui::Color32::from_rgb(220, 80, 80), format!("❌ {}", e));
+                return None;
+            }
+        };
+
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.label("Selector:");
+            ui.add(egui::TextEdit::singleline(&mut picked.selector)
+                .font(egui::TextStyle::Monospace)
+                .desired_width(f32::INFINITY));
+        });
+        match (&picked.item_selector, picked.items) {
+            (Some(item), Some(items)) => {
+                ui.label(format!("Found in {} of {} items matching {}", picked.matches, items, item));
+            }
+            _ => {
+                ui.label(format!("Matches {} element(s) on the page, outside any item", picked.matches));
+            }
+        }
+        if !picked.sample.is_empty() {
+            ui.label(format!("Value: {}", picked.sample));
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Field name:");
+            ui.add(egui::TextEdit::singleline(&mut picked.name).desired_width(140.0));
+            egui::ComboBox::from_id_source("selector_picker_extraction")
+                .selected_text(extraction_label(&picked.extraction))
+                .show_ui(ui, |ui| {
+                    for extraction in [ExtractionMethod::Text, ExtractionMethod::Href, ExtractionMethod::Src, ExtractionMethod::Html] {
+                        let label = extraction_label(&extraction);
+                        ui.selectable_value(&mut picked.extraction, extraction, label);
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            let named = !picked.name.trim().is_empty() && !picked.selector.trim().is_empty();
+            if ui.add_enabled(named, egui::Button::new("➕ Add as field")).clicked() {
+                action = Some(PickerAction::AddField(picked.to_field()));
+            }
+            if let Some(item) = &picked.suggested_item {
+                if ui.button(format!("📦 Use {} as item selector", item))
+                    .on_hover_text("Fields are picked relative to each item")
+                    .clicked()
+                {
+                    action = Some(PickerAction::UseItemSelector(item.clone()));
+                }
+            }
+        });
+        action
+    }
+}
+
+fn extraction_label(extraction: &ExtractionMethod) -> &'static str {
+    match extraction {
+        ExtractionMethod::Text => "Text",
+        ExtractionMethod::Html => "HTML",
+        ExtractionMethod::Attribute { .. } => "Attribute",
+        ExtractionMethod::Href => "Link (href)",
+        ExtractionMethod::Src => "Image (src)",
+    }
+}
@@ -43,6 +43,8 @@ pub enum View {
     Chat,
     Jobs,
     Settings,
+    Logs,
+    Security,
     Help,
 }
 
@@ -57,6 +59,14 @@ pub struct JobInfo {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub result_count: Option<usize>,
+    /// Job this one was cloned from
+    #[serde(default)]
+    pub cloned_from: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Problems the last run met without failing
+    #[serde(default)]
+    pub warnings: usize,
 }
 
 /// Job status for UI
@@ -68,6 +78,7 @@ pub enum JobStatus {
     Completed,
     Failed,
     Cancelled,
+    Paused,
 }
 
 /// UI settings
@@ -76,11 +87,17 @@ pub enum JobStatus {
 pub struct UISettings {
     pub max_concurrent_requests: usize,
     pub request_timeout: u64,
-    pub respect_robots_txt: bool,
+    /// Respect robots.txt even for plans that turn it off
+    pub always_respect_robots_txt: bool,
     pub enable_browser_fallback: bool,
     pub default_export_format: String,
+    /// Rows extracted for the preview next to a plan under review
+    pub preview_rows: usize,
     pub enable_input_validation: bool,
     pub enable_output_filtering: bool,
+    /// Hide the window in the system tray instead of closing it
+    pub minimize_to_tray: bool,
+    pub language: crate::i18n::Language,
 }
 
 #[cfg(feature = "ui")]
@@ -89,11 +106,74 @@ impl Default for UISettings {
         Self {
             max_concurrent_requests: 5,
             request_timeout: 30,
-            respect_robots_txt: true,
+            always_respect_robots_txt: false,
             enable_browser_fallback: false,
             default_export_format: "csv".to_string(),
+            preview_rows: 10,
             enable_input_validation: true,
             enable_output_filtering: true,
+            minimize_to_tray: false,
+            language: crate::i18n::Language::English,
+        }
+    }
+}
+
+#[cfg(feature = "ui")]
+impl UISettings {
+    /// Settings as stored in the configuration
+    pub fn from_config(config: &crate::config::AppConfig) -> Self {
+        Self {
+            max_concurrent_requests: config.scraping.max_concurrent_requests,
+            request_timeout: config.scraping.request_timeout_seconds,
+            always_respect_robots_txt: config.scraping.always_respect_robots_txt,
+            enable_browser_fallback: config.scraping.enable_browser_fallback,
+            default_export_format: config.export.default_format.clone(),
+            preview_rows: config.ui.preview_rows,
+            enable_input_validation: config.security.enable_input_validation,
+            enable_output_filtering: config.security.enable_output_filtering,
+            minimize_to_tray: config.ui.minimize_to_tray,
+            language: crate::i18n::Language::from_code(&config.ui.language).unwrap_or_default(),
+        }
+    }
+
+    /// Copy the settings into a configuration, leaving everything else as it is
+    pub fn apply_to(&self, config: &mut crate::config::AppConfig) {
+        config.scraping.max_concurrent_requests = self.max_concurrent_requests;
+        config.scraping.request_timeout_seconds = self.request_timeout;
+        config.scraping.always_respect_robots_txt = self.always_respect_robots_txt;
+        config.scraping.enable_browser_fallback = self.enable_browser_fallback;
+        config.export.default_format = self.default_export_format.clone();
+        config.ui.preview_rows = self.preview_rows;
+        config.security.enable_input_validation = self.enable_input_validation;
+        config.security.enable_output_filtering = self.enable_output_filtering;
+        config.ui.minimize_to_tray = self.minimize_to_tray;
+        config.ui.language = self.language.code().to_string();
+    }
+}
+
+#[cfg(feature = "ui")]
+impl From<crate::storage::Job> for JobInfo {
+    fn from(job: crate::storage::Job) -> Self {
+        Self {
+            status: match job.status {
+                crate::storage::JobStatus::Queued => JobStatus::Queued,
+                crate::storage::JobStatus::Running => JobStatus::Running,
+                crate::storage::JobStatus::Completed => JobStatus::Completed,
+                crate::storage::JobStatus::Failed => JobStatus::Failed,
+                crate::storage::JobStatus::Cancelled => JobStatus::Cancelled,
+                crate::storage::JobStatus::Paused => JobStatus::Paused,
+            },
+            description: job.user_prompt,
+            created_at: job.created_at,
+            completed_at: None,
+            result_count: None,
+            cloned_from: job.cloned_from,
+            tags: Vec::new(),
+            warnings: job.summary_json.as_deref()
+                .and_then(|json| serde_json::from_str::<crate::scraper::ScrapeSummary>(json).ok())
+                .map_or(0, |summary| summary.warning_count()),
+            title: job.title,
+            id: job.id,
         }
     }
 }
@@ -107,6 +187,23 @@ pub enum WorkflowState {
     Failed(String),
 }
 
+/// Sends the outcome of a workflow run in the background back to the UI
+#[cfg(feature = "ui")]
+pub type WorkflowSender = tokio::sync::mpsc::UnboundedSender<WorkflowState>;
+
+#[cfg(feature = "ui")]
+impl UIState {
+    /// Take the next workflow outcome once the previous one has been handled
+    pub fn poll_workflow(&mut self, updates: &mut tokio::sync::mpsc::UnboundedReceiver<WorkflowState>) {
+        if matches!(self.current_workflow, Some(WorkflowState::Completed(_) | WorkflowState::Failed(_))) {
+            return;
+        }
+        if let Ok(update) = updates.try_recv() {
+            self.current_workflow = Some(update);
+        }
+    }
+}
+
 // Stub implementations when UI feature is disabled
 #[cfg(not(feature = "ui"))]
 pub struct UIState;
@@ -117,3 +214,38 @@ impl UIState {
         Self
     }
 }
+
+#[cfg(all(test, feature = "ui"))]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use crate::i18n::Language;
+
+    #[test]
+    fn test_settings_round_trip_through_the_config() {
+        let mut config = AppConfig::default();
+        config.scraping.max_concurrent_requests = 7;
+        config.ui.language = "ko".to_string();
+
+        let mut settings = UISettings::from_config(&config);
+        assert_eq!(settings.max_concurrent_requests, 7);
+        assert_eq!(settings.language, Language::Korean);
+        // Plans decide about robots.txt unless the user says otherwise
+        assert!(!settings.always_respect_robots_txt);
+
+        settings.request_timeout = 45;
+        settings.always_respect_robots_txt = true;
+        settings.minimize_to_tray = true;
+        let before = config.clone();
+        settings.apply_to(&mut config);
+        assert_eq!(config.scraping.request_timeout_seconds, 45);
+        assert!(config.scraping.always_respect_robots_txt);
+        assert!(config.ui.minimize_to_tray);
+        assert_eq!(config.ui.language, "ko");
+
+        // Settings the window doesn't show are left alone
+        assert_eq!(config.scraping.user_agents, before.scraping.user_agents);
+        assert_eq!(config.scraping.respect_robots_txt, before.scraping.respect_robots_txt);
+        assert_eq!(config.database.path, before.database.path);
+    }
+}
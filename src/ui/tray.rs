@@ -0,0 +1,121 @@
+use anyhow::Result;
+use eframe::egui;
+use tray_icon::menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem};
+use tray_icon::{Icon, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::i18n;
+use super::icon_manager::IconManager;
+
+/// Size of the plain icon used when no application icon is available
+const ICON_SIZE: u32 = 32;
+
+/// Colour of the dot drawn on the icon while jobs run
+const BADGE_COLOR: [u8; 4] = [0xe8, 0x11, 0x23, 0xff];
+
+/// What was picked in the tray menu, or a click on the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    /// Show the window again
+    Open,
+    PauseAll,
+    Quit,
+}
+
+/// Icon in the system tray with a badge while jobs run and a menu to reopen the window
+pub struct Tray {
+    icon: TrayIcon,
+    menu_actions: Vec<(MenuId, TrayAction)>,
+    base_icon: egui::IconData,
+    running_jobs: Option<usize>,
+}
+
+impl Tray {
+    /// Add the icon to the system tray; it is removed when dropped
+    pub fn new() -> Result<Self> {
+        let base_icon = IconManager::new().get_current_icon().unwrap_or_else(|| egui::IconData {
+            rgba: [0x00, 0x78, 0xd4, 0xff].repeat((ICON_SIZE * ICON_SIZE) as usize),
+            width: ICON_SIZE,
+            height: ICON_SIZE,
+        });
+
+        let open = MenuItem::new("Open WinScrape Studio", true, None);
+        let pause = MenuItem::new("Pause all jobs", true, None);
+        let quit = MenuItem::new("Quit", true, None);
+        let menu = Menu::new();
+        menu.append_items(&[&open, &PredefinedMenuItem::separator(), &pause, &quit])?;
+
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip("WinScrape Studio")
+            .with_icon(Icon::from_rgba(base_icon.rgba.clone(), base_icon.width, base_icon.height)?)
+            .build()?;
+
+        Ok(Self {
+            icon,
+            menu_actions: vec![
+                (open.id().clone(), TrayAction::Open),
+                (pause.id().clone(), TrayAction::PauseAll),
+                (quit.id().clone(), TrayAction::Quit),
+            ],
+            base_icon,
+            running_jobs: None,
+        })
+    }
+
+    /// Show the number of running jobs in the tooltip, and a badge on the icon while any run
+    pub fn set_running_jobs(&mut self, running: usize) -> Result<()> {
+        let previous = self.running_jobs.replace(running);
+        if previous == Some(running) {
+            return Ok(());
+        }
+
+        let tooltip = match running {
+            0 => "WinScrape Studio".to_string(),
+            n => format!("WinScrape Studio — {}", i18n::t_plural("jobs.running_count", n as u64, &[])),
+        };
+        self.icon.set_tooltip(Some(tooltip))?;
+
+        if previous.is_none_or(|previous| (previous > 0) != (running > 0)) {
+            let rgba = if running > 0 { with_badge(&self.base_icon) } else { self.base_icon.rgba.clone() };
+            self.icon.set_icon(Some(Icon::from_rgba(rgba, self.base_icon.width, self.base_icon.height)?))?;
+        }
+        Ok(())
+    }
+
+    /// The next menu pick or left click on the icon since the last call
+    pub fn next_action(&self) -> Option<TrayAction> {
+        while let Ok(event) = MenuEvent::receiver().try_recv() {
+            if let Some((_, action)) = self.menu_actions.iter().find(|(id, _)| *id == event.id) {
+                return Some(*action);
+            }
+        }
+        while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+            match event {
+                TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. }
+                | TrayIconEvent::DoubleClick { .. } => return Some(TrayAction::Open),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// The icon's pixels with a dot in the bottom right corner
+fn with_badge(icon: &egui::IconData) -> Vec<u8> {
+    let mut rgba = icon.rgba.clone();
+    let (width, height) = (icon.width as usize, icon.height as usize);
+    let radius = width.min(height) as f64 / 4.0;
+    let (cx, cy) = (width as f64 - radius, height as f64 - radius);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f64 + 0.5 - cx, y as f64 + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let offset = (y * width + x) * 4;
+                if let Some(pixel) = rgba.get_mut(offset..offset + 4) {
+                    pixel.copy_from_slice(&BADGE_COLOR);
+                }
+            }
+        }
+    }
+    rgba
+}
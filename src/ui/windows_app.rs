@@ -13,15 +13,15 @@ use super::{
     windows_launcher::WindowsLauncher,
     icon_manager::IconManager,
 };
-use crate::i18n::{I18nManager, Language};
+use crate::i18n::{self, Language};
 
 /// Windows application wrapper with proper initialization and error handling
 #[cfg(feature = "ui")]
 pub struct WindowsApp {
     ui: Option<WindowsUI>,
+    core: Option<Arc<WinScrapeStudio>>,
     launcher: WindowsLauncher,
     icon_manager: IconManager,
-    i18n_manager: I18nManager,
     initialized: bool,
     error_message: Option<String>,
 }
@@ -32,9 +32,9 @@ impl WindowsApp {
     pub fn new() -> Self {
         Self {
             ui: None,
+            core: None,
             launcher: WindowsLauncher,
             icon_manager: IconManager::new(),
-            i18n_manager: I18nManager::new(),
             initialized: false,
             error_message: None,
         }
@@ -60,7 +60,18 @@ impl WindowsApp {
         
         // Create UI
         let app_arc = Arc::new(app);
-        self.ui = Some(WindowsUI::new(app_arc));
+        self.ui = Some(WindowsUI::new(app_arc.clone()));
+        
+        // Finish jobs paused when the application last closed
+        let resumer = app_arc.clone();
+        tokio::spawn(async move {
+            match resumer.resume_paused_jobs().await {
+                Ok(0) => {}
+                Ok(count) => info!("Resumed {} paused jobs", count),
+                Err(e) => warn!("Could not resume paused jobs: {}", e),
+            }
+        });
+        self.core = Some(app_arc);
         
         self.initialized = true;
         info!("Windows application initialized successfully");
@@ -105,6 +116,13 @@ impl WindowsApp {
         Ok(())
     }
     
+    /// Pause jobs still running once the window has closed; they resume on the next start
+    pub async fn shutdown(&self) {
+        if let Some(core) = &self.core {
+            core.shutdown().await;
+        }
+    }
+    
     /// Load application icon
     fn load_icon(&self) -> Option<egui::IconData> {
         // Try to load icon from file
@@ -242,18 +260,18 @@ impl WindowsApp {
     
     /// Set application language
     pub fn set_language(&mut self, language: Language) {
-        self.i18n_manager.set_language(language);
+        i18n::set_language(language);
         info!("Language changed to: {}", language.name());
     }
     
     /// Get current language
     pub fn current_language(&self) -> Language {
-        self.i18n_manager.current_language()
+        i18n::current_language()
     }
     
     /// Get available languages
     pub fn available_languages(&self) -> Vec<Language> {
-        self.i18n_manager.available_languages()
+        i18n::global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()).available_languages()
     }
     
     /// Set icon theme
@@ -286,7 +304,7 @@ impl WindowsApp {
     
     /// Get translation
     pub fn t(&self, key: &str) -> String {
-        self.i18n_manager.t(key)
+        i18n::t(key)
     }
 }
 
@@ -314,6 +332,7 @@ impl WindowsApp {
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         Err("GUI feature not enabled".into())
     }
+    pub async fn shutdown(&self) {}
     pub fn show_about(&self) {}
     pub async fn check_for_updates(&self) {}
     pub fn create_shortcuts(&self) -> Result<(), Box<dyn std::error::Error>> { Ok(()) }
@@ -60,7 +60,7 @@ impl WindowsComponents {
             }
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.label(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string());
+                ui.label(crate::utils::time_utils::format_local(&chrono::Utc::now(), "%Y-%m-%d %H:%M:%S"));
             });
         });
     }
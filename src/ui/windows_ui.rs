@@ -5,22 +5,45 @@ use std::sync::Arc;
 #[cfg(feature = "ui")]
 use tracing::{info, error, debug, warn};
 #[cfg(feature = "ui")]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[cfg(feature = "ui")]
-use crate::core::WinScrapeStudio;
+use crate::core::{ResultTail, WinScrapeStudio};
 #[cfg(feature = "ui")]
-use crate::core::orchestrator::{WorkflowResult, WorkflowStage};
+use crate::core::bulk::{self, BulkAction, BulkHandle};
+#[cfg(feature = "ui")]
+use crate::core::orchestrator::{Orchestrator, WorkflowResult, WorkflowStage};
+#[cfg(feature = "ui")]
+use crate::core::events::JobEvent;
+use crate::core::progress::JobStage;
+#[cfg(feature = "ui")]
+use crate::dsl::{PolitenessPreset, ScrapePlan};
+#[cfg(feature = "ui")]
+use crate::llm::{GenerationEvent, GenerationStream};
+#[cfg(feature = "ui")]
+use crate::storage::{self, JobFilter};
 #[cfg(feature = "ui")]
 use super::{
-    chat::ChatInterface,
-    state::{UIState, View, JobInfo, JobStatus, UISettings, WorkflowState},
+    chat::{ChatCommand, ChatInterface, COMMAND_HELP},
+    state::{UIState, View, JobInfo, JobStatus, UISettings, WorkflowSender, WorkflowState},
     windows_theme::WindowsTheme,
     windows_components::{WindowsComponents, NotificationLevel},
     results_viewer::ResultsViewer,
+    analytics::{AnalyticsAction, AnalyticsResult, AnalyticsView},
+    run_comparison::{ComparisonResult, PlanRunsResult, RunComparison, RunComparisonAction},
+    tray::{Tray, TrayAction},
+    job_detail::{JobDetailAction, JobDetailResult, JobDetailView, LIVE_REFRESH_INTERVAL},
+    log_viewer::{LogViewer, LogViewerAction, LogsResult, LOG_REFRESH_INTERVAL, LOG_TAIL_LINES},
+    security_view::{AuditEventsResult, SecurityView, SecurityViewAction, AUDIT_EVENT_LIMIT, AUDIT_REFRESH_INTERVAL},
+    export_wizard::{ExportOutcome, ExportSetup, ExportSetupResult, ExportWizard, ExportWizardAction},
+    dsl_editor::{DslEditor, EditorAction, PreviewResult},
+    selector_picker::{InspectResult, PickerAction, SelectorPicker},
     icon_manager::IconManager,
 };
-use crate::i18n::{I18nManager, Language};
+use crate::i18n::{self, Language};
+use crate::error::describe;
+use crate::logging::LogLine;
+use crate::storage::AuditEventFilter;
 
 /// Main Windows-native UI application
 #[cfg(feature = "ui")]
@@ -30,15 +53,171 @@ pub struct WindowsUI {
     chat: ChatInterface,
     theme: WindowsTheme,
     icon_manager: IconManager,
-    i18n_manager: I18nManager,
     results_viewer: Option<ResultsViewer>,
+    /// Job shown in place of the job list
+    job_detail: Option<JobDetailView>,
+    /// Detail of a job loaded in the background, picked up on the next frame
+    loaded_job_detail: Arc<std::sync::Mutex<Option<(String, JobDetailResult)>>>,
+    /// Logs tab
+    log_viewer: LogViewer,
+    /// Log lines loaded in the background, picked up on the next frame
+    loaded_logs: Arc<std::sync::Mutex<Option<LogsResult>>>,
+    /// Path of the exported log file, or why the export failed
+    exported_logs: Arc<std::sync::Mutex<Option<Result<String, String>>>>,
+    /// Security tab
+    security_view: SecurityView,
+    /// Security events loaded in the background, picked up on the next frame
+    loaded_audit_events: Arc<std::sync::Mutex<Option<AuditEventsResult>>>,
+    /// Editor open on the plan under review
+    dsl_editor: Option<DslEditor>,
+    /// Preview of the edited plan, picked up on the next frame
+    editor_preview: Arc<std::sync::Mutex<Option<PreviewResult>>>,
+    /// Selector picker open on the plan under review
+    selector_picker: Option<SelectorPicker>,
+    /// Page fetched for the selector picker, picked up on the next frame
+    inspected_page: Arc<std::sync::Mutex<Option<InspectResult>>>,
+    approval_preview: ApprovalPreview,
+    /// Number of the last preview run, so results of older runs are dropped
+    approval_preview_run: u64,
+    /// Rows of a preview run on the plan under review, picked up on the next frame
+    approval_preview_rows: Arc<std::sync::Mutex<Option<(u64, PreviewResult)>>>,
     notifications: Vec<Notification>,
     show_about: bool,
-    show_export_dialog: bool,
     show_language_dialog: bool,
     show_icon_dialog: bool,
-    export_path: String,
+    /// Export of one job's results, step by step
+    export_wizard: Option<ExportWizard>,
+    /// Columns and default path of the job being exported, picked up on the next frame
+    export_setup: Arc<std::sync::Mutex<Option<(String, ExportSetupResult)>>>,
+    /// Outcome of the export the wizard started, picked up on the next frame
+    export_outcome: Arc<std::sync::Mutex<Option<(String, ExportOutcome)>>>,
+    /// Outcome of saving the settings, picked up on the next frame
+    saved_settings: Arc<std::sync::Mutex<Option<Result<(), String>>>>,
+    /// Statistics and charts of one job's results
+    analytics: Option<AnalyticsView>,
+    /// Analytics computed in the background, picked up on the next frame
+    loaded_analytics: Arc<std::sync::Mutex<Option<(String, AnalyticsResult)>>>,
+    /// Comparison of a job's results with another run of its plan
+    comparison: Option<RunComparison>,
+    /// Runs offered for comparison, picked up on the next frame
+    comparison_runs: Arc<std::sync::Mutex<Option<(String, PlanRunsResult)>>>,
+    /// Compared runs and their differences, picked up on the next frame
+    comparison_changes: Arc<std::sync::Mutex<Option<((String, String), ComparisonResult)>>>,
     window_title: String,
+    /// Plan loaded by "Clone and edit", picked up on the next frame
+    cloned_plan: Arc<std::sync::Mutex<Option<Result<ScrapePlan, String>>>>,
+    /// Path of the last shareable sample written, picked up on the next frame
+    shared_sample: Arc<std::sync::Mutex<Option<Result<String, String>>>>,
+    /// Latest batch fetched for the results viewer
+    result_tail: Arc<std::sync::Mutex<Option<Result<ResultTail, String>>>>,
+    tail_in_flight: bool,
+    last_tail_poll: std::time::Instant,
+    /// Jobs ticked in the jobs view
+    selected_jobs: HashSet<String>,
+    /// Bulk operation running in the background, if any
+    bulk_operation: Option<BulkHandle>,
+    /// Plan being generated from the last chat message
+    generation: Option<GenerationStream>,
+    /// Results of chat commands, picked up on the next frame
+    command_replies: Arc<std::sync::Mutex<Vec<CommandReply>>>,
+    /// Backup file entered in the database settings
+    database_file: String,
+    /// Restore was clicked once and waits for confirmation
+    confirm_restore: bool,
+    /// Age in days entered for archiving results
+    archive_days: u32,
+    /// Outcome of the last backup, restore or archival, picked up on the next frame
+    database_result: Arc<std::sync::Mutex<Option<Result<String, String>>>>,
+    /// Filter controls above the job list
+    job_filter: JobFilterInputs,
+    /// Jobs loaded from storage, picked up on the next frame
+    loaded_jobs: Arc<std::sync::Mutex<Option<Result<Vec<JobInfo>, String>>>>,
+    /// Outcomes of approved plans run in the background
+    workflow_tx: WorkflowSender,
+    workflow_rx: tokio::sync::mpsc::UnboundedReceiver<WorkflowState>,
+    /// Job whose tag field is open, and the text typed into it
+    tag_input: Option<(String, String)>,
+    /// Set when a job was created or ended, so the list is reloaded on the next frame
+    jobs_changed: Arc<std::sync::atomic::AtomicBool>,
+    /// Jobs fetching or storing right now, counted from job events
+    running_jobs: Arc<std::sync::atomic::AtomicUsize>,
+    /// Present while "Minimize to system tray" is on
+    tray: Option<Tray>,
+    /// Quit was picked in the tray, so closing the window really closes it
+    quitting: bool,
+    /// Outcome of pausing all jobs, picked up on the next frame
+    paused_jobs: Arc<std::sync::Mutex<Option<Result<(), String>>>>,
+    /// Plan read from a dropped or picked file, with its path, picked up on the next frame
+    opened_plan: Arc<std::sync::Mutex<Option<(String, Result<ScrapePlan, String>)>>>,
+}
+
+/// Filter controls above the job list, as typed
+#[cfg(feature = "ui")]
+#[derive(Default)]
+struct JobFilterInputs {
+    status: Option<storage::JobStatus>,
+    tag: String,
+    domain: String,
+    text: String,
+    /// Only jobs from the last this many days
+    days: Option<i64>,
+}
+
+#[cfg(feature = "ui")]
+impl JobFilterInputs {
+    fn to_filter(&self) -> JobFilter {
+        let text = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        JobFilter {
+            status: self.status.clone(),
+            tag: text(&self.tag),
+            domain: text(&self.domain),
+            created_after: self.days.map(|days| chrono::Utc::now() - chrono::Duration::days(days)),
+            created_before: None,
+            text: text(&self.text),
+            owner: None,
+        }
+    }
+}
+
+/// Jobs loaded into the job list at most
+#[cfg(feature = "ui")]
+const JOB_LIST_LIMIT: usize = 200;
+
+/// Outcome of a chat command run in the background
+#[cfg(feature = "ui")]
+enum CommandReply {
+    /// Text to show in the chat
+    Message(String),
+    /// A plan to open in the review dialog
+    Review(ScrapePlan),
+    /// A job was started or removed, so the job list is stale
+    JobsChanged(String),
+}
+
+/// Rows fetched per results tail request
+#[cfg(feature = "ui")]
+const RESULT_TAIL_BATCH: usize = 500;
+
+/// Delay between results tail requests while a job runs
+#[cfg(feature = "ui")]
+const RESULT_TAIL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Extensions of plan text files that can be opened, besides `.wssplan` bundles
+#[cfg(feature = "ui")]
+const PLAN_FILE_EXTENSIONS: &[&str] = &["yaml", "yml", "json", "toml"];
+
+/// Preview shown next to the plan under review
+#[cfg(feature = "ui")]
+#[derive(Debug, Clone, PartialEq)]
+enum ApprovalPreview {
+    /// Not run yet for this plan
+    NotRun,
+    Running,
+    /// `pending_approval.preview` holds rows of the current plan
+    Ready,
+    /// The plan was changed after its rows were extracted
+    Stale,
+    Failed(String),
 }
 
 #[cfg(feature = "ui")]
@@ -56,28 +235,101 @@ struct Notification {
 impl WindowsUI {
     /// Create new Windows UI application
     pub fn new(app: Arc<WinScrapeStudio>) -> Self {
-        let state = UIState::new();
+        // Start from the settings saved in the configuration
+        let config = app.config();
+        let mut state = UIState::new();
+        state.settings = UISettings::from_config(&config);
         let chat = ChatInterface::new();
-        let theme = WindowsTheme::windows11_dark();
+        let theme = if config.ui.enable_dark_mode {
+            WindowsTheme::windows11_dark()
+        } else {
+            WindowsTheme::windows11_light()
+        };
         let icon_manager = IconManager::new();
-        let i18n_manager = I18nManager::new();
+        let (workflow_tx, workflow_rx) = tokio::sync::mpsc::unbounded_channel();
         
-        Self {
+        let mut ui = Self {
             app,
             state,
             chat,
             theme,
             icon_manager,
-            i18n_manager,
             results_viewer: None,
+            job_detail: None,
+            loaded_job_detail: Arc::new(std::sync::Mutex::new(None)),
+            log_viewer: LogViewer::default(),
+            loaded_logs: Arc::new(std::sync::Mutex::new(None)),
+            exported_logs: Arc::new(std::sync::Mutex::new(None)),
+            security_view: SecurityView::default(),
+            loaded_audit_events: Arc::new(std::sync::Mutex::new(None)),
+            dsl_editor: None,
+            editor_preview: Arc::new(std::sync::Mutex::new(None)),
+            selector_picker: None,
+            inspected_page: Arc::new(std::sync::Mutex::new(None)),
+            approval_preview: ApprovalPreview::NotRun,
+            approval_preview_run: 0,
+            approval_preview_rows: Arc::new(std::sync::Mutex::new(None)),
             notifications: Vec::new(),
             show_about: false,
-            show_export_dialog: false,
             show_language_dialog: false,
             show_icon_dialog: false,
-            export_path: String::new(),
+            export_wizard: None,
+            export_setup: Arc::new(std::sync::Mutex::new(None)),
+            export_outcome: Arc::new(std::sync::Mutex::new(None)),
+            saved_settings: Arc::new(std::sync::Mutex::new(None)),
+            analytics: None,
+            loaded_analytics: Arc::new(std::sync::Mutex::new(None)),
+            comparison: None,
+            comparison_runs: Arc::new(std::sync::Mutex::new(None)),
+            comparison_changes: Arc::new(std::sync::Mutex::new(None)),
             window_title: format!("WinScrape Studio v{}", env!("CARGO_PKG_VERSION")),
-        }
+            cloned_plan: Arc::new(std::sync::Mutex::new(None)),
+            shared_sample: Arc::new(std::sync::Mutex::new(None)),
+            result_tail: Arc::new(std::sync::Mutex::new(None)),
+            tail_in_flight: false,
+            last_tail_poll: std::time::Instant::now(),
+            selected_jobs: HashSet::new(),
+            bulk_operation: None,
+            generation: None,
+            command_replies: Arc::new(std::sync::Mutex::new(Vec::new())),
+            database_file: String::new(),
+            confirm_restore: false,
+            archive_days: crate::storage::archive::DEFAULT_ARCHIVE_AFTER_DAYS,
+            database_result: Arc::new(std::sync::Mutex::new(None)),
+            job_filter: JobFilterInputs::default(),
+            loaded_jobs: Arc::new(std::sync::Mutex::new(None)),
+            workflow_tx,
+            workflow_rx,
+            tag_input: None,
+            jobs_changed: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            running_jobs: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            tray: None,
+            quitting: false,
+            paused_jobs: Arc::new(std::sync::Mutex::new(None)),
+            opened_plan: Arc::new(std::sync::Mutex::new(None)),
+        };
+        ui.refresh_jobs();
+        
+        // Follow job events rather than polling storage for changes
+        let mut events = ui.app.subscribe_events();
+        let changed = ui.jobs_changed.clone();
+        let running_jobs = ui.running_jobs.clone();
+        tokio::spawn(async move {
+            let mut running = HashSet::new();
+            while let Some(event) = events.next().await {
+                if event.is_final() || matches!(event.event, JobEvent::JobCreated { .. }) {
+                    changed.store(true, std::sync::atomic::Ordering::Relaxed);
+                }
+                match &event.event {
+                    JobEvent::StageChanged { stage: JobStage::Queued } => { running.remove(&event.job_id); }
+                    JobEvent::StageChanged { .. } => { running.insert(event.job_id.clone()); }
+                    _ if event.is_final() => { running.remove(&event.job_id); }
+                    _ => {}
+                }
+                running_jobs.store(running.len(), std::sync::atomic::Ordering::Relaxed);
+            }
+        });
+        ui
     }
     
     /// Set theme
@@ -109,18 +361,20 @@ impl WindowsUI {
     
     /// Set language
     pub fn set_language(&mut self, language: Language) {
-        self.i18n_manager.set_language(language);
+        i18n::set_language(language);
+        self.state.settings.language = language;
         info!("Language changed to: {}", language.name());
     }
     
     /// Get current language
     pub fn current_language(&self) -> Language {
-        self.i18n_manager.current_language()
+        i18n::current_language()
     }
     
-    /// Get available languages
+    /// Get available languages, in the order they are listed
     pub fn available_languages(&self) -> Vec<Language> {
-        self.i18n_manager.available_languages()
+        let manager = i18n::global_i18n_manager().read().unwrap_or_else(|e| e.into_inner());
+        Language::all().into_iter().filter(|language| manager.is_language_available(*language)).collect()
     }
     
     /// Set icon theme
@@ -141,7 +395,7 @@ impl WindowsUI {
     
     /// Get translation
     pub fn t(&self, key: &str) -> String {
-        self.i18n_manager.t(key)
+        i18n::t(key)
     }
 }
 
@@ -168,6 +422,8 @@ impl eframe::App for WindowsUI {
         
         // Handle background tasks
         self.handle_background_tasks(ctx);
+        self.handle_tray(ctx);
+        self.handle_dropped_files(ctx);
         
         // Request repaint for animations
         ctx.request_repaint();
@@ -230,6 +486,8 @@ impl WindowsUI {
         let jobs_label = self.t("nav.jobs");
         let results_label = self.t("nav.results");
         let settings_label = self.t("nav.settings");
+        let logs_label = self.t("nav.logs");
+        let security_label = self.t("nav.security");
         let help_label = self.t("nav.help");
         
         let views = [
@@ -237,6 +495,8 @@ impl WindowsUI {
             ("jobs", jobs_label.as_str(), "📋"),
             ("results", results_label.as_str(), "📊"),
             ("settings", settings_label.as_str(), "⚙️"),
+            ("logs", logs_label.as_str(), "📜"),
+            ("security", security_label.as_str(), "🛡"),
             ("help", help_label.as_str(), "❓"),
         ];
         
@@ -244,6 +504,8 @@ impl WindowsUI {
             View::Chat => "chat",
             View::Jobs => "jobs",
             View::Settings => "settings",
+            View::Logs => "logs",
+            View::Security => "security",
             View::Help => "help",
         };
         
@@ -252,6 +514,8 @@ impl WindowsUI {
                 "chat" => View::Chat,
                 "jobs" => View::Jobs,
                 "settings" => View::Settings,
+                "logs" => View::Logs,
+                "security" => View::Security,
                 "help" => View::Help,
                 _ => View::Chat,
             };
@@ -265,6 +529,8 @@ impl WindowsUI {
                 View::Chat => self.t("chat.title"),
                 View::Jobs => self.t("jobs.title"),
                 View::Settings => self.t("settings.title"),
+                View::Logs => self.t("logs.title"),
+                View::Security => self.t("security.title"),
                 View::Help => self.t("help.title"),
             };
             ui.heading(&title);
@@ -291,6 +557,8 @@ impl WindowsUI {
             View::Chat => self.render_chat_sidebar(ui),
             View::Jobs => self.render_jobs_sidebar(ui),
             View::Settings => self.render_settings_sidebar(ui),
+            View::Logs => self.render_logs_sidebar(ui),
+            View::Security => self.render_security_sidebar(ui),
             View::Help => self.render_help_sidebar(ui),
         }
     }
@@ -306,6 +574,17 @@ impl WindowsUI {
                 self.state.current_view = View::Jobs;
             }
             
+            #[cfg(feature = "rfd")]
+            if ui.button("📂 Open Plan…").on_hover_text("Or drop a plan file onto the window").clicked() {
+                let extensions = [PLAN_FILE_EXTENSIONS, &[crate::dsl::BUNDLE_EXTENSION]].concat();
+                let picked = rfd::FileDialog::new()
+                    .add_filter("Plans", extensions.as_slice())
+                    .pick_file();
+                if let Some(path) = picked {
+                    self.open_plan_file(path.display().to_string());
+                }
+            }
+            
             if ui.button("⚙️ Settings").clicked() {
                 self.state.current_view = View::Settings;
             }
@@ -406,6 +685,42 @@ impl WindowsUI {
         });
     }
     
+    /// Render logs sidebar
+    fn render_logs_sidebar(&mut self, ui: &mut egui::Ui) {
+        WindowsComponents::card_with_header(ui, "Log Files", |ui| {
+            ui.label("Application logs are written to:");
+            ui.monospace(crate::logging::log_directory().display().to_string());
+            ui.add_space(8.0);
+            ui.label("Job log entries are shown with the component \"job\".");
+        });
+    }
+    
+    /// Render logs view
+    fn render_logs_view(&mut self, ui: &mut egui::Ui) {
+        match self.log_viewer.render(ui, &self.theme) {
+            LogViewerAction::None => {}
+            LogViewerAction::Refresh => self.load_logs(),
+            LogViewerAction::Export(lines) => self.export_logs(lines),
+        }
+    }
+    
+    /// Render security sidebar
+    fn render_security_sidebar(&mut self, ui: &mut egui::Ui) {
+        WindowsComponents::card_with_header(ui, "Security Events", |ui| {
+            ui.label("Blocked domains, rejected input, robots.txt refusals and redacted personal data are recorded here.");
+            ui.add_space(8.0);
+            ui.label("Blocked domains and input checks are set on the Settings tab.");
+        });
+    }
+    
+    /// Render security view
+    fn render_security_view(&mut self, ui: &mut egui::Ui) {
+        match self.security_view.render(ui, &self.theme) {
+            SecurityViewAction::None => {}
+            SecurityViewAction::Refresh(filter) => self.load_audit_events(filter),
+        }
+    }
+    
     /// Render help sidebar
     fn render_help_sidebar(&mut self, ui: &mut egui::Ui) {
         WindowsComponents::card_with_header(ui, "Quick Help", |ui| {
@@ -440,8 +755,12 @@ impl WindowsUI {
             View::Chat => self.render_chat_view(ui, ctx),
             View::Jobs => self.render_jobs_view(ui, ctx),
             View::Settings => self.render_settings_view(ui, ctx),
+            View::Logs => self.render_logs_view(ui),
+            View::Security => self.render_security_view(ui),
             View::Help => self.render_help_view(ui, ctx),
         }
+        
+        self.render_results_window(ctx);
     }
     
     /// Render chat view
@@ -465,9 +784,61 @@ impl WindowsUI {
         }
     }
     
+    /// Render the results viewer window for the job being viewed
+    fn render_results_window(&mut self, ctx: &egui::Context) {
+        let running = match &self.results_viewer {
+            Some(viewer) => self.state.jobs.iter()
+                .any(|j| j.id == viewer.job_id && matches!(j.status, JobStatus::Running)),
+            None => return,
+        };
+        
+        let mut open = true;
+        let mut cancel_job = None;
+        
+        if let Some(viewer) = self.results_viewer.as_mut() {
+            egui::Window::new(format!("Results – {}", viewer.job_id.chars().take(8).collect::<String>()))
+                .open(&mut open)
+                .default_size([900.0, 600.0])
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        if viewer.following {
+                            ui.colored_label(self.theme.get_status_color("running"), "● Live");
+                        }
+                        ui.label(format!("{} rows", viewer.data.len()));
+                        ui.checkbox(&mut viewer.following, "Follow new rows");
+                        
+                        if running && ui.button("⏹️ Cancel job").clicked() {
+                            cancel_job = Some(viewer.job_id.clone());
+                        }
+                    });
+                    ui.separator();
+                    viewer.render(ui);
+                });
+        }
+        
+        if let Some(job_id) = cancel_job {
+            self.cancel_job(&job_id);
+        }
+        if !open {
+            self.results_viewer = None;
+        }
+    }
+    
     /// Render jobs view
     fn render_jobs_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        if self.state.jobs.is_empty() {
+        if let Some(view) = self.job_detail.as_mut() {
+            let job_id = view.job_id.clone();
+            match view.render(ui, &self.theme) {
+                JobDetailAction::None => {}
+                JobDetailAction::Back => self.job_detail = None,
+                JobDetailAction::Refresh => self.load_job_detail(),
+                JobDetailAction::ViewResults => self.view_job_results(&job_id),
+                JobDetailAction::Cancel => self.cancel_job(&job_id),
+            }
+            return;
+        }
+        
+        if self.state.jobs.is_empty() && self.job_filter.to_filter().is_empty() {
             ui.centered_and_justified(|ui| {
                 ui.vertical_centered(|ui| {
                     ui.heading("No Jobs Yet");
@@ -480,6 +851,16 @@ impl WindowsUI {
                 });
             });
         } else {
+            self.render_job_filter_bar(ui);
+            ui.add_space(8.0);
+            self.render_bulk_bar(ui, ctx);
+            ui.add_space(8.0);
+            
+            if self.state.jobs.is_empty() {
+                ui.label("No jobs match the filter.");
+                return;
+            }
+            
             egui::ScrollArea::vertical()
                 .auto_shrink([false, false])
                 .show(ui, |ui| {
@@ -492,6 +873,115 @@ impl WindowsUI {
         }
     }
     
+    /// Render status, date, tag, domain and text filters for the job list
+    fn render_job_filter_bar(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        
+        ui.horizontal_wrapped(|ui| {
+            ui.label("🔍");
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.job_filter.text)
+                .hint_text("Search titles and prompts")
+                .desired_width(180.0))
+                .lost_focus();
+            
+            let status_label = self.job_filter.status.as_ref()
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| "Any status".to_string());
+            egui::ComboBox::from_id_source("job_status_filter")
+                .selected_text(status_label)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.job_filter.status, None, "Any status").changed();
+                    for status in [
+                        storage::JobStatus::Queued,
+                        storage::JobStatus::Running,
+                        storage::JobStatus::Completed,
+                        storage::JobStatus::Failed,
+                        storage::JobStatus::Cancelled,
+                        storage::JobStatus::Paused,
+                    ] {
+                        let label = status.to_string();
+                        changed |= ui.selectable_value(&mut self.job_filter.status, Some(status), label).changed();
+                    }
+                });
+            
+            let days_label = match self.job_filter.days {
+                None => "Any time".to_string(),
+                Some(1) => "Last 24 hours".to_string(),
+                Some(days) => format!("Last {} days", days),
+            };
+            egui::ComboBox::from_id_source("job_date_filter")
+                .selected_text(days_label)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut self.job_filter.days, None, "Any time").changed();
+                    changed |= ui.selectable_value(&mut self.job_filter.days, Some(1), "Last 24 hours").changed();
+                    changed |= ui.selectable_value(&mut self.job_filter.days, Some(7), "Last 7 days").changed();
+                    changed |= ui.selectable_value(&mut self.job_filter.days, Some(30), "Last 30 days").changed();
+                });
+            
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.job_filter.tag)
+                .hint_text("Tag")
+                .desired_width(100.0))
+                .lost_focus();
+            changed |= ui.add(egui::TextEdit::singleline(&mut self.job_filter.domain)
+                .hint_text("Domain")
+                .desired_width(140.0))
+                .lost_focus();
+            
+            if !self.job_filter.to_filter().is_empty() && ui.button("✖ Clear").clicked() {
+                self.job_filter = JobFilterInputs::default();
+                changed = true;
+            }
+        });
+        
+        if changed {
+            self.refresh_jobs();
+        }
+    }
+    
+    /// Render selection controls, bulk actions and bulk progress
+    fn render_bulk_bar(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        if let Some(handle) = self.bulk_operation.clone() {
+            let progress = handle.progress();
+            ui.horizontal(|ui| {
+                ui.label(format!("Bulk {}: {}/{}", handle.action.label(), progress.processed, progress.total));
+                ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage());
+                if ui.button("⏹️ Cancel").clicked() {
+                    handle.cancel();
+                }
+            });
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+            return;
+        }
+        
+        ui.horizontal(|ui| {
+            let mut select_all = self.selected_jobs.len() == self.state.jobs.len();
+            if ui.checkbox(&mut select_all, "Select all").changed() {
+                if select_all {
+                    self.selected_jobs = self.state.jobs.iter().map(|job| job.id.clone()).collect();
+                } else {
+                    self.selected_jobs.clear();
+                }
+            }
+            
+            if self.selected_jobs.is_empty() {
+                return;
+            }
+            
+            ui.separator();
+            ui.label(format!("{} selected", self.selected_jobs.len()));
+            
+            if ui.button("🗑️ Delete").clicked() {
+                self.start_bulk_operation(BulkAction::Delete);
+            }
+            if ui.button("📥 Export").clicked() {
+                self.start_bulk_operation(BulkAction::Export { format: crate::export::ExportFormat::Csv });
+            }
+            if ui.button("🔄 Rerun").clicked() {
+                self.start_bulk_operation(BulkAction::Rerun);
+            }
+        });
+    }
+    
     /// Render settings view
     fn render_settings_view(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         egui::ScrollArea::vertical()
@@ -514,15 +1004,19 @@ impl WindowsUI {
                     // Language selection
                     ui.horizontal(|ui| {
                         ui.label(&self.t("settings.language"));
+                        let mut current_language = self.current_language();
                         egui::ComboBox::from_id_source("language_combo")
-                            .selected_text(self.current_language().name())
+                            .selected_text(current_language.name())
                             .show_ui(ui, |ui| {
                                 for language in self.available_languages() {
-                                    ui.selectable_value(
-                                        &mut self.current_language(),
+                                    if ui.selectable_value(
+                                        &mut current_language,
                                         language,
                                         language.name()
-                                    );
+                                    ).clicked() {
+                                        self.set_language(current_language);
+                                        self.save_settings();
+                                    }
                                 }
                             });
                     });
@@ -552,50 +1046,100 @@ impl WindowsUI {
                     
                     WindowsComponents::checkbox(ui, &self.t("settings.auto_save"), &mut true);
                     WindowsComponents::checkbox(ui, &self.t("settings.notifications"), &mut true);
-                    WindowsComponents::checkbox(ui, &self.t("settings.minimize_to_tray"), &mut false);
+                    let minimize_to_tray = self.t("settings.minimize_to_tray");
+                    WindowsComponents::checkbox(ui, &minimize_to_tray, &mut self.state.settings.minimize_to_tray);
                 });
                 
                 ui.add_space(16.0);
                 
                 // Scraping Settings
-                WindowsComponents::card_with_header(ui, "Scraping Settings", |ui| {
+                WindowsComponents::card_with_header(ui, &self.t("settings.scraping"), |ui| {
                     let mut max_requests = self.state.settings.max_concurrent_requests as f32;
                     let mut timeout = self.state.settings.request_timeout as f32;
-                    WindowsComponents::slider(ui, "Max concurrent requests", &mut max_requests, 1.0, 20.0);
-                    WindowsComponents::slider(ui, "Request timeout (seconds)", &mut timeout, 5.0, 120.0);
+                    WindowsComponents::slider(ui, &self.t("settings.max_concurrent"), &mut max_requests, 1.0, 20.0);
+                    WindowsComponents::slider(ui, &self.t("settings.timeout"), &mut timeout, 5.0, 120.0);
                     self.state.settings.max_concurrent_requests = max_requests as usize;
                     self.state.settings.request_timeout = timeout as u64;
                     
+                    let mut preview_rows = self.state.settings.preview_rows as f32;
+                    WindowsComponents::slider(ui, &self.t("settings.preview_rows"), &mut preview_rows, 1.0, 50.0);
+                    self.state.settings.preview_rows = preview_rows as usize;
+                    
                     ui.add_space(8.0);
                     
-                    WindowsComponents::checkbox(ui, "Respect robots.txt", &mut self.state.settings.respect_robots_txt);
-                    WindowsComponents::checkbox(ui, "Enable browser fallback", &mut self.state.settings.enable_browser_fallback);
+                    WindowsComponents::checkbox(ui, &self.t("settings.respect_robots"), &mut self.state.settings.always_respect_robots_txt);
+                    WindowsComponents::checkbox(ui, &self.t("settings.browser_fallback"), &mut self.state.settings.enable_browser_fallback);
                 });
                 
                 ui.add_space(16.0);
                 
                 // Export Settings
-                WindowsComponents::card_with_header(ui, "Export Settings", |ui| {
-                    let formats = ["csv", "json", "xlsx", "parquet"];
-                    WindowsComponents::dropdown(ui, "Default export format", &mut self.state.settings.default_export_format, &formats.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+                WindowsComponents::card_with_header(ui, &self.t("settings.export"), |ui| {
+                    let formats = ["csv", "json", "xlsx", "parquet", "ndjson"];
+                    WindowsComponents::dropdown(ui, &self.t("settings.default_format"), &mut self.state.settings.default_export_format, &formats.iter().map(|s| s.to_string()).collect::<Vec<_>>());
                     
                     ui.add_space(8.0);
                     
-                    WindowsComponents::checkbox(ui, "Include metadata in exports", &mut true);
-                    WindowsComponents::checkbox(ui, "Compress large exports", &mut true);
+                    WindowsComponents::checkbox(ui, &self.t("settings.include_metadata"), &mut true);
+                    WindowsComponents::checkbox(ui, &self.t("settings.compress_exports"), &mut true);
                 });
                 
                 ui.add_space(16.0);
                 
                 // Security Settings
-                WindowsComponents::card_with_header(ui, "Security Settings", |ui| {
-                    WindowsComponents::checkbox(ui, "Enable input validation", &mut self.state.settings.enable_input_validation);
-                    WindowsComponents::checkbox(ui, "Filter sensitive data from output", &mut self.state.settings.enable_output_filtering);
+                WindowsComponents::card_with_header(ui, &self.t("settings.security"), |ui| {
+                    WindowsComponents::checkbox(ui, &self.t("settings.input_validation"), &mut self.state.settings.enable_input_validation);
+                    WindowsComponents::checkbox(ui, &self.t("settings.output_filtering"), &mut self.state.settings.enable_output_filtering);
+                    
+                    ui.add_space(8.0);
+                    
+                    ui.label(self.t("settings.blocked_domains"));
+                    ui.add(egui::TextEdit::multiline(&mut String::new()).hint_text(self.t("settings.blocked_domains_hint")));
+                });
+                
+                ui.add_space(16.0);
+                
+                // Database backup and restore
+                WindowsComponents::card_with_header(ui, &self.t("settings.database"), |ui| {
+                    ui.label(self.t("settings.database_description"));
+                    ui.add_space(8.0);
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(self.t("settings.backup_file"));
+                        ui.add(egui::TextEdit::singleline(&mut self.database_file).hint_text("C:\\Backups\\winscrape.db"));
+                    });
                     
                     ui.add_space(8.0);
                     
-                    ui.label("Blocked domains:");
-                    ui.add(egui::TextEdit::multiline(&mut String::new()).hint_text("Enter domains to block, one per line"));
+                    let has_file = !self.database_file.trim().is_empty();
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(has_file, egui::Button::new(format!("💾 {}", self.t("settings.back_up")))).clicked() {
+                            self.backup_database();
+                        }
+                        
+                        if self.confirm_restore {
+                            ui.colored_label(self.theme.get_status_color("warning"), self.t("settings.restore_warning"));
+                            if ui.button(format!("♻️ {}", self.t("settings.confirm_restore"))).clicked() {
+                                self.confirm_restore = false;
+                                self.restore_database();
+                            }
+                            if ui.button(self.t("action.cancel")).clicked() {
+                                self.confirm_restore = false;
+                            }
+                        } else if ui.add_enabled(has_file, egui::Button::new(format!("♻️ {}", self.t("settings.restore")))).clicked() {
+                            self.confirm_restore = true;
+                        }
+                    });
+                    
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        ui.label(self.t("settings.archive_before"));
+                        ui.add(egui::DragValue::new(&mut self.archive_days).clamp_range(1..=3650));
+                        ui.label(self.t("settings.archive_after"));
+                        if ui.button(format!("📦 {}", self.t("settings.archive"))).clicked() {
+                            self.archive_old_results();
+                        }
+                    });
                 });
             });
     }
@@ -605,17 +1149,15 @@ impl WindowsUI {
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
-                WindowsComponents::card_with_header(ui, "Getting Started", |ui| {
-                    ui.label("1. Go to the Chat tab");
-                    ui.label("2. Describe what you want to scrape in plain English");
-                    ui.label("3. Review the generated scraping plan");
-                    ui.label("4. Approve and run the scraping job");
-                    ui.label("5. Export your results");
+                WindowsComponents::card_with_header(ui, &self.t("help.getting_started"), |ui| {
+                    for step in ["help.step1", "help.step2", "help.step3", "help.step4", "help.step5"] {
+                        ui.label(self.t(step));
+                    }
                 });
                 
                 ui.add_space(16.0);
                 
-                WindowsComponents::card_with_header(ui, "Example Requests", |ui| {
+                WindowsComponents::card_with_header(ui, &self.t("help.examples"), |ui| {
                     let examples = [
                         "Scrape product prices from shop.example.com",
                         "Get news headlines from news.example.com",
@@ -631,31 +1173,31 @@ impl WindowsUI {
                 
                 ui.add_space(16.0);
                 
-                WindowsComponents::card_with_header(ui, "Features", |ui| {
+                WindowsComponents::card_with_header(ui, &self.t("help.features"), |ui| {
                     let features = [
-                        "✅ Natural language to scraping plan conversion",
-                        "✅ HTTP-first with browser fallback",
-                        "✅ Robots.txt compliance",
-                        "✅ Rate limiting and anti-blocking",
-                        "✅ Multiple export formats (CSV, JSON, XLSX, Parquet)",
-                        "✅ Data validation and filtering",
-                        "✅ Real-time job monitoring",
-                        "✅ Windows-native interface",
+                        "help.feature.plans",
+                        "help.feature.browser_fallback",
+                        "help.feature.robots",
+                        "help.feature.rate_limiting",
+                        "help.feature.formats",
+                        "help.feature.validation",
+                        "help.feature.monitoring",
+                        "help.feature.native",
                     ];
                     
                     for feature in features {
-                        ui.label(feature);
+                        ui.label(format!("✅ {}", self.t(feature)));
                     }
                 });
                 
                 ui.add_space(16.0);
                 
-                WindowsComponents::card_with_header(ui, "About", |ui| {
+                WindowsComponents::card_with_header(ui, &self.t("help.about"), |ui| {
                     ui.label(format!("WinScrape Studio v{}", env!("CARGO_PKG_VERSION")));
-                    ui.label("A natural language web scraping tool");
-                    ui.label("Built with Rust and egui");
-                    ui.hyperlink_to("Documentation", "https://github.com/winscrape-studio/docs");
-                    ui.hyperlink_to("GitHub Repository", "https://github.com/winscrape-studio");
+                    ui.label(self.t("app.description"));
+                    ui.label(self.t("help.built_with"));
+                    ui.hyperlink_to(self.t("help.documentation"), "https://github.com/winscrape-studio/docs");
+                    ui.hyperlink_to(self.t("help.repository"), "https://github.com/winscrape-studio");
                 });
             });
     }
@@ -664,74 +1206,245 @@ impl WindowsUI {
     fn render_job_card(&mut self, ui: &mut egui::Ui, job: &JobInfo) {
         WindowsComponents::card_with_header(ui, &job.title, |ui| {
             ui.horizontal(|ui| {
+                let mut selected = self.selected_jobs.contains(&job.id);
+                if ui.checkbox(&mut selected, "").changed() {
+                    if selected {
+                        self.selected_jobs.insert(job.id.clone());
+                    } else {
+                        self.selected_jobs.remove(&job.id);
+                    }
+                }
+                
                 ui.vertical(|ui| {
                     ui.label(&job.description);
-                    ui.label(format!("Created: {}", job.created_at.format("%Y-%m-%d %H:%M")));
+                    ui.label(format!("Created: {}", crate::utils::time_utils::format_local(&job.created_at, "%Y-%m-%d %H:%M")));
                     if let Some(completed_at) = job.completed_at {
-                        ui.label(format!("Completed: {}", completed_at.format("%Y-%m-%d %H:%M")));
+                        ui.label(format!("Completed: {}", crate::utils::time_utils::format_local(&completed_at, "%Y-%m-%d %H:%M")));
                     }
+                    if let Some(parent_id) = &job.cloned_from {
+                        let parent_title = self.state.jobs.iter()
+                            .find(|j| &j.id == parent_id)
+                            .map(|j| j.title.clone())
+                            .unwrap_or_else(|| parent_id.chars().take(8).collect());
+                        ui.label(format!("Cloned from: {}", parent_title));
+                    }
+                    self.render_job_tags(ui, job);
                 });
                 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     // Status indicator
-                    let (color, text) = match job.status {
-                        JobStatus::Running => (self.theme.get_status_color("running"), "🔄 Running"),
-                        JobStatus::Completed => (self.theme.get_status_color("completed"), "✅ Completed"),
-                        JobStatus::Failed => (self.theme.get_status_color("error"), "❌ Failed"),
-                        JobStatus::Queued => (self.theme.get_status_color("info"), "⏳ Queued"),
-                        JobStatus::Cancelled => (self.theme.get_status_color("warning"), "🚫 Cancelled"),
+                    let (color, icon, key) = match job.status {
+                        JobStatus::Running => (self.theme.get_status_color("running"), "🔄", "jobs.status.running"),
+                        JobStatus::Completed => (self.theme.get_status_color("completed"), "✅", "jobs.status.completed"),
+                        JobStatus::Failed => (self.theme.get_status_color("error"), "❌", "jobs.status.failed"),
+                        JobStatus::Queued => (self.theme.get_status_color("info"), "⏳", "jobs.status.queued"),
+                        JobStatus::Cancelled => (self.theme.get_status_color("warning"), "🚫", "jobs.status.cancelled"),
+                        JobStatus::Paused => (self.theme.get_status_color("info"), "⏸️", "jobs.status.paused"),
                     };
                     
-                    ui.colored_label(color, text);
+                    if job.status == JobStatus::Completed && job.warnings > 0 {
+                        let label = i18n::t_plural("jobs.status.completed_warnings", job.warnings as u64, &[]);
+                        ui.colored_label(self.theme.get_status_color("warning"), format!("⚠️ {}", label));
+                    } else {
+                        ui.colored_label(color, format!("{} {}", icon, self.t(key)));
+                    }
                 });
             });
             
             ui.add_space(8.0);
             
             ui.horizontal(|ui| {
-                if ui.button("📊 View Results").clicked() {
+                if ui.button(format!("🔍 {}", self.t("jobs.action.details"))).clicked() {
+                    self.job_detail = Some(JobDetailView::new(job.id.clone()));
+                }
+                
+                if ui.button(format!("📊 {}", self.t("jobs.action.view_results"))).clicked() {
                     self.view_job_results(&job.id);
                 }
                 
-                if ui.button("📥 Export").clicked() {
+                if ui.button(format!("📈 {}", self.t("jobs.action.analytics"))).clicked() {
+                    self.analytics = Some(AnalyticsView::new(job.id.clone()));
+                    self.load_analytics();
+                }
+                
+                if ui.button(format!("🔀 {}", self.t("jobs.action.compare"))).on_hover_text(self.t("jobs.action.compare_hint")).clicked() {
+                    self.comparison = Some(RunComparison::new(job.id.clone(), job.created_at));
+                    self.load_comparison_runs();
+                }
+                
+                if ui.button(format!("📥 {}", self.t("action.export"))).clicked() {
                     self.export_job_results(&job.id);
                 }
                 
                 if matches!(job.status, JobStatus::Running) {
-                    if ui.button("⏹️ Cancel").clicked() {
+                    if ui.button(format!("⏹️ {}", self.t("action.cancel"))).clicked() {
                         self.cancel_job(&job.id);
                     }
                 }
                 
-                if ui.button("🔄 Rerun").clicked() {
+                if ui.button(format!("🔄 {}", self.t("jobs.action.rerun"))).clicked() {
                     self.rerun_job(&job.id);
                 }
+                
+                if ui.button(format!("📋 {}", self.t("jobs.action.clone_edit"))).clicked() {
+                    self.clone_and_edit_job(&job.id);
+                }
+                
+                if ui.button(format!("🔗 {}", self.t("jobs.action.share_sample"))).clicked() {
+                    self.create_shareable_sample(&job.id);
+                }
             });
         });
     }
     
+    /// Render a job's tags; clicking one filters by it, ✖ removes it
+    fn render_job_tags(&mut self, ui: &mut egui::Ui, job: &JobInfo) {
+        ui.horizontal_wrapped(|ui| {
+            for tag in &job.tags {
+                if ui.small_button(format!("🏷️ {}", tag)).on_hover_text("Show jobs with this tag").clicked() {
+                    self.job_filter.tag = tag.clone();
+                    self.refresh_jobs();
+                }
+                if ui.small_button("✖").on_hover_text("Remove tag").clicked() {
+                    self.update_job_tag(&job.id, tag, false);
+                }
+            }
+            
+            let editing = matches!(&self.tag_input, Some((id, _)) if id == &job.id);
+            if editing {
+                let mut submitted = None;
+                if let Some((_, text)) = &mut self.tag_input {
+                    let response = ui.add(egui::TextEdit::singleline(text).hint_text("New tag").desired_width(100.0));
+                    if response.lost_focus() {
+                        if ui.input(|i| i.key_pressed(egui::Key::Enter)) && !text.trim().is_empty() {
+                            submitted = Some(text.trim().to_string());
+                        }
+                        self.tag_input = None;
+                    } else {
+                        response.request_focus();
+                    }
+                }
+                if let Some(tag) = submitted {
+                    self.update_job_tag(&job.id, &tag, true);
+                }
+            } else if ui.small_button("+ Tag").clicked() {
+                self.tag_input = Some((job.id.clone(), String::new()));
+            }
+        });
+    }
+    
+    /// Add or remove a tag in the background, then reload the job list
+    fn update_job_tag(&mut self, job_id: &str, tag: &str, add: bool) {
+        let app = self.app.clone();
+        let slot = self.loaded_jobs.clone();
+        let filter = self.job_filter.to_filter();
+        let (job_id, tag) = (job_id.to_string(), tag.to_string());
+        
+        tokio::spawn(async move {
+            let updated = if add {
+                app.add_job_tag(&job_id, &tag).await
+            } else {
+                app.remove_job_tag(&job_id, &tag).await.map(|_| ())
+            };
+            let jobs = match updated {
+                Ok(()) => load_jobs(&app, &filter).await.map_err(|e| describe(&e)),
+                Err(e) => Err(format!("Could not update tag '{}': {}", tag, e)),
+            };
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(jobs);
+            }
+        });
+    }
+    
     /// Render approval dialog
     fn render_approval_dialog(&mut self, ui: &mut egui::Ui, approval: &crate::core::orchestrator::PendingApproval) {
+        let current_preset = approval.dsl.anti_blocking.politeness;
+        let mut selected_preset = current_preset;
+        let mut editor_action = EditorAction::None;
+        let mut refresh_preview = self.approval_preview == ApprovalPreview::NotRun;
+        
         egui::Window::new("Review Scraping Plan")
             .collapsible(false)
             .resizable(true)
-            .default_size([600.0, 400.0])
+            .default_size([900.0, 480.0])
             .show(ui.ctx(), |ui| {
                 ui.label("Please review the generated scraping plan:");
                 ui.add_space(8.0);
                 
-                // Show DSL preview
-                if let Ok(dsl_yaml) = serde_yaml::to_string(&approval.dsl) {
-                    ui.add(egui::TextEdit::multiline(&mut dsl_yaml.clone())
-                        .font(egui::TextStyle::Monospace)
-                        .desired_width(f32::INFINITY)
-                        .desired_rows(15));
+                if let Some(parent_id) = approval.dsl.get_metadata("cloned_from").and_then(|v| v.as_str()) {
+                    ui.label(format!("📋 Cloned from job {}", parent_id));
+                }
+                
+                // Plain-language summary of the plan
+                let explanation = crate::dsl::explain::explain_plan(&approval.dsl, &i18n::global_i18n_manager().read().unwrap_or_else(|e| e.into_inner()));
+                ui.label(egui::RichText::new(&explanation.summary).strong());
+                for detail in &explanation.details {
+                    ui.label(format!("• {}", detail));
+                }
+                ui.add_space(8.0);
+                
+                // Politeness preset selection
+                ui.horizontal(|ui| {
+                    ui.label("Politeness:");
+                    egui::ComboBox::from_id_source("politeness_combo")
+                        .selected_text(current_preset.name())
+                        .show_ui(ui, |ui| {
+                            for preset in PolitenessPreset::all() {
+                                ui.selectable_value(&mut selected_preset, preset, preset.name());
+                            }
+                        });
+                    ui.label(current_preset.description());
+                });
+                
+                // Flag settings that exceed the preset's ceilings
+                let warning_color = self.theme.get_status_color("warning");
+                for violation in self.app.get_security_manager().check_politeness(&approval.dsl) {
+                    ui.colored_label(warning_color, format!("⚠️ {}", violation));
                 }
                 
+                // Plan linter findings
+                let lint_warnings = self.app.lint_dsl(&approval.dsl);
+                if !lint_warnings.is_empty() {
+                    ui.add_space(4.0);
+                    egui::CollapsingHeader::new(format!("💡 {} suggestion(s)", lint_warnings.len()))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for warning in &lint_warnings {
+                                ui.colored_label(warning_color, format!("⚠️ {}", warning.message))
+                                    .on_hover_text(&warning.path);
+                                ui.label(format!("    → {}", warning.suggestion));
+                            }
+                        });
+                }
+                
+                ui.add_space(8.0);
+                
+                // Show the plan, or edit it, next to rows extracted with it
+                ui.columns(2, |columns| {
+                    if let Some(editor) = self.dsl_editor.as_mut() {
+                        editor_action = editor.render(&mut columns[0]);
+                    } else if let Ok(dsl_yaml) = serde_yaml::to_string(&approval.dsl) {
+                        egui::ScrollArea::vertical()
+                            .id_source("approval_plan_yaml")
+                            .max_height(320.0)
+                            .show(&mut columns[0], |ui| {
+                                ui.add(egui::TextEdit::multiline(&mut dsl_yaml.clone())
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(15));
+                            });
+                    }
+                    refresh_preview |= self.render_approval_preview(&mut columns[1], &approval.preview);
+                });
+                
                 ui.add_space(8.0);
                 
                 ui.horizontal(|ui| {
-                    if ui.button("✅ Approve & Run").clicked() {
+                    let editing = self.dsl_editor.is_some();
+                    if ui.add_enabled(!editing, egui::Button::new("✅ Approve & Run"))
+                        .on_disabled_hover_text("Apply or discard your changes first")
+                        .clicked()
+                    {
                         self.approve_scraping_plan();
                     }
                     
@@ -739,69 +1452,357 @@ impl WindowsUI {
                         self.reject_scraping_plan();
                     }
                     
-                    if ui.button("✏️ Edit").clicked() {
+                    if !editing && ui.button("✏️ Edit").clicked() {
                         self.edit_scraping_plan();
                     }
+                    
+                    if !editing && self.selector_picker.is_none() && ui.button("🎯 Pick selectors").clicked() {
+                        let url = approval.dsl.target.start_urls.first().cloned().unwrap_or_default();
+                        self.selector_picker = Some(SelectorPicker::new(&url));
+                    }
                 });
             });
-    }
-    
-    /// Render status bar
-    fn render_status_bar(&mut self, ui: &mut egui::Ui) {
-        let job_count = format!("{}", self.state.jobs.len());
-        let mut status_items = vec![
-            ("Jobs", self.theme.get_status_color("info")),
-            (job_count.as_str(), self.theme.get_status_color("info")),
-        ];
         
-        if let Some(status) = &self.state.status_message {
-            status_items.push((status.as_str(), self.theme.get_status_color("info")));
+        if refresh_preview {
+            self.run_approval_preview(approval.dsl.clone());
         }
         
-        WindowsComponents::status_bar(ui, &status_items);
-    }
-    
-    /// Render notifications
-    fn render_notifications(&mut self, ctx: &egui::Context) {
-        let mut to_remove = Vec::new();
+        match editor_action {
+            EditorAction::None => {}
+            EditorAction::Preview(dsl) => self.preview_edited_plan(dsl),
+            EditorAction::Apply(dsl) => self.apply_plan_edits(dsl),
+            EditorAction::Discard => self.dsl_editor = None,
+        }
         
-        for (i, notification) in self.notifications.iter().enumerate() {
-            let mut open = true;
-            
-            egui::Window::new(&notification.title)
-                .id(egui::Id::new(&notification.id))
-                .anchor(egui::Align2::RIGHT_TOP, [-16.0, 16.0 + (i as f32 * 100.0)])
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    WindowsComponents::notification(ui, notification.level, &notification.title, &notification.message);
-                    
-                    ui.horizontal(|ui| {
-                        if ui.button("Close").clicked() {
-                            open = false;
-                        }
-                    });
-                });
-            
-            if !open {
-                to_remove.push(notification.id.clone());
-            }
+        if selected_preset != current_preset {
+            self.apply_politeness_preset(selected_preset);
         }
         
-        for id in to_remove {
-            self.remove_notification(&id);
+        let picker_action = match self.selector_picker.as_mut() {
+            Some(picker) => picker.render(ui.ctx(), &approval.dsl.rules.item_selector),
+            None => PickerAction::None,
+        };
+        match picker_action {
+            PickerAction::None => {}
+            PickerAction::Load(url) => self.inspect_page(url),
+            PickerAction::AddField(field) => self.add_picked_field(field),
+            PickerAction::UseItemSelector(selector) => {
+                if let Some(approval) = self.state.pending_approval.as_mut() {
+                    approval.dsl.rules.item_selector = selector;
+                    self.approval_preview = ApprovalPreview::Stale;
+                }
+            }
+            PickerAction::Close => self.selector_picker = None,
         }
     }
     
-    /// Render dialogs
-    fn render_dialogs(&mut self, ctx: &egui::Context) {
-        if self.show_about {
-            self.render_about_dialog(ctx);
+    /// Fetch a page for the selector picker in the background
+    fn inspect_page(&mut self, url: String) {
+        let app = self.app.clone();
+        let slot = self.inspected_page.clone();
+        tokio::spawn(async move {
+            let result = app.inspect_page(&url).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Add a field built with the selector picker to the plan under review
+    fn add_picked_field(&mut self, mut field: crate::dsl::Field) {
+        let Some(approval) = self.state.pending_approval.as_mut() else { return };
+        let fields = &mut approval.dsl.rules.fields;
+        let base = field.name.clone();
+        let mut suffix = 2;
+        while fields.iter().any(|existing| existing.name == field.name) {
+            field.name = format!("{}_{}", base, suffix);
+            suffix += 1;
         }
         
-        if self.show_export_dialog {
-            self.render_export_dialog(ctx);
-        }
+        info!("Added field {} ({}) to pending plan", field.name, field.selector);
+        let name = field.name.clone();
+        fields.push(field);
+        self.approval_preview = ApprovalPreview::Stale;
+        self.add_notification(
+            NotificationLevel::Success,
+            "Field Added".to_string(),
+            format!("Added field {} to the plan.", name),
+        );
+    }
+    
+    /// Rows extracted with the plan under review; returns whether a refresh was asked for
+    fn render_approval_preview(&self, ui: &mut egui::Ui, rows: &[serde_json::Value]) -> bool {
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            ui.strong("Preview");
+            let running = self.approval_preview == ApprovalPreview::Running;
+            if ui.add_enabled(!running, egui::Button::new("🔄 Refresh")).clicked() {
+                refresh = true;
+            }
+            if running {
+                ui.spinner();
+                ui.label("Extracting rows…");
+            }
+        });
+        
+        let warning_color = self.theme.get_status_color("warning");
+        match &self.approval_preview {
+            ApprovalPreview::Failed(e) => {
+                ui.colored_label(self.theme.get_status_color("error"), format!("❌ {}", e));
+                return refresh;
+            }
+            ApprovalPreview::Stale => {
+                ui.colored_label(warning_color, "⚠️ The plan changed since these rows were extracted; refresh to update them");
+            }
+            ApprovalPreview::Ready if rows.is_empty() => {
+                ui.colored_label(warning_color, "⚠️ No rows extracted; check the item selector");
+                return refresh;
+            }
+            _ => {}
+        }
+        if rows.is_empty() {
+            return refresh;
+        }
+        
+        // Columns in the order they first appear, without internal fields
+        let mut columns: Vec<&str> = Vec::new();
+        for row in rows {
+            if let Some(object) = row.as_object() {
+                for key in object.keys() {
+                    if !key.starts_with('_') && !columns.contains(&key.as_str()) {
+                        columns.push(key);
+                    }
+                }
+            }
+        }
+        
+        ui.label(egui::RichText::new(format!("First {} row(s) from the first start URL", rows.len())).weak());
+        egui::ScrollArea::both()
+            .id_source("approval_preview_rows")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("approval_preview_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for column in &columns {
+                            let filled = rows.iter()
+                                .filter(|row| row.get(column).is_some_and(|value| !value.is_null() && value != ""))
+                                .count();
+                            ui.strong(*column)
+                                .on_hover_text(format!("Filled in {} of {} rows", filled, rows.len()));
+                        }
+                        ui.end_row();
+                        
+                        for row in rows {
+                            for column in &columns {
+                                let text = match row.get(column) {
+                                    Some(serde_json::Value::String(text)) => text.clone(),
+                                    Some(serde_json::Value::Null) | None => String::new(),
+                                    Some(value) => value.to_string(),
+                                };
+                                if text.is_empty() {
+                                    ui.colored_label(warning_color, "—");
+                                } else if text.chars().count() > 60 {
+                                    let short: String = text.chars().take(60).collect();
+                                    ui.label(format!("{}…", short)).on_hover_text(text);
+                                } else {
+                                    ui.label(text);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+        refresh
+    }
+    
+    /// Open plan files dropped onto the window, highlighting the window while files hover over it
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let (hovering, dropped) = ctx.input(|i| (!i.raw.hovered_files.is_empty(), i.raw.dropped_files.clone()));
+        if hovering {
+            let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("plan_drop_overlay")));
+            let screen = ctx.screen_rect();
+            painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                screen.center(),
+                egui::Align2::CENTER_CENTER,
+                "📂 Drop a plan file to review it",
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE,
+            );
+        }
+        
+        // Only the first plan is opened; the review dialog holds one at a time
+        let Some(path) = dropped.iter().find_map(|file| file.path.clone()) else { return };
+        if is_plan_file(&path) {
+            self.open_plan_file(path.display().to_string());
+        } else {
+            self.add_notification(
+                NotificationLevel::Warning,
+                "Not a Plan File".to_string(),
+                format!(
+                    "{} is not a plan. Drop a .yaml, .json, .toml or .{} file.",
+                    path.display(), crate::dsl::BUNDLE_EXTENSION
+                ),
+            );
+        }
+    }
+    
+    /// Read and validate a plan file in the background, then open it for review
+    fn open_plan_file(&mut self, path: String) {
+        info!("Opening plan file {}", path);
+        let app = self.app.clone();
+        let slot = self.opened_plan.clone();
+        tokio::spawn(async move {
+            let plan = app.open_plan_file(&path).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((path, plan));
+            }
+        });
+    }
+    
+    /// Open a plan in the review dialog; its preview starts on the next frame
+    fn review_plan(&mut self, approval: crate::core::orchestrator::PendingApproval) {
+        self.approval_preview = if approval.preview.is_empty() {
+            ApprovalPreview::NotRun
+        } else {
+            ApprovalPreview::Ready
+        };
+        self.state.pending_approval = Some(approval);
+    }
+    
+    /// Extract preview rows for the plan under review in the background
+    fn run_approval_preview(&mut self, dsl: ScrapePlan) {
+        self.approval_preview = ApprovalPreview::Running;
+        self.approval_preview_run += 1;
+        
+        let run = self.approval_preview_run;
+        let limit = self.state.settings.preview_rows.max(1);
+        let app = self.app.clone();
+        let slot = self.approval_preview_rows.clone();
+        tokio::spawn(async move {
+            let result = app.preview_plan(&dsl, limit).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((run, result));
+            }
+        });
+    }
+    
+    /// Run a preview of the edited plan in the background
+    fn preview_edited_plan(&mut self, dsl: ScrapePlan) {
+        if let Some(editor) = self.dsl_editor.as_mut() {
+            editor.preview_running = true;
+        }
+        
+        let app = self.app.clone();
+        let slot = self.editor_preview.clone();
+        tokio::spawn(async move {
+            let result = app.validate_and_preview(&dsl).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Replace the plan under review with the edited one
+    fn apply_plan_edits(&mut self, dsl: ScrapePlan) {
+        self.dsl_editor = None;
+        if let Some(approval) = self.state.pending_approval.as_mut() {
+            approval.dsl = dsl;
+            self.approval_preview = ApprovalPreview::Stale;
+            info!("Applied edits to pending plan");
+        }
+    }
+    
+    /// Apply a politeness preset to the plan awaiting approval
+    fn apply_politeness_preset(&mut self, preset: PolitenessPreset) {
+        if let Some(approval) = self.state.pending_approval.as_mut() {
+            preset.apply_to(&mut approval.dsl);
+            info!("Applied {} politeness preset to pending plan", preset);
+        }
+    }
+    
+    /// Render status bar
+    fn render_status_bar(&mut self, ui: &mut egui::Ui) {
+        let job_count = format!("{}", self.state.jobs.len());
+        let mut status_items = vec![
+            ("Jobs", self.theme.get_status_color("info")),
+            (job_count.as_str(), self.theme.get_status_color("info")),
+        ];
+        
+        if let Some(status) = &self.state.status_message {
+            status_items.push((status.as_str(), self.theme.get_status_color("info")));
+        }
+        
+        WindowsComponents::status_bar(ui, &status_items);
+    }
+    
+    /// Render notifications
+    fn render_notifications(&mut self, ctx: &egui::Context) {
+        let mut to_remove = Vec::new();
+        
+        for (i, notification) in self.notifications.iter().enumerate() {
+            let mut open = true;
+            
+            egui::Window::new(&notification.title)
+                .id(egui::Id::new(&notification.id))
+                .anchor(egui::Align2::RIGHT_TOP, [-16.0, 16.0 + (i as f32 * 100.0)])
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    WindowsComponents::notification(ui, notification.level, &notification.title, &notification.message);
+                    
+                    ui.horizontal(|ui| {
+                        if ui.button("Close").clicked() {
+                            open = false;
+                        }
+                    });
+                });
+            
+            if !open {
+                to_remove.push(notification.id.clone());
+            }
+        }
+        
+        for id in to_remove {
+            self.remove_notification(&id);
+        }
+    }
+    
+    /// Render dialogs
+    fn render_dialogs(&mut self, ctx: &egui::Context) {
+        if self.show_about {
+            self.render_about_dialog(ctx);
+        }
+        
+        if let Some(wizard) = self.export_wizard.as_mut() {
+            match wizard.render(ctx, &self.theme) {
+                ExportWizardAction::None => {}
+                ExportWizardAction::Close => self.export_wizard = None,
+                ExportWizardAction::Start { path, format, options } => self.start_export(path, format, options),
+                ExportWizardAction::OpenFolder(path) => {
+                    if let Err(e) = crate::utils::file_utils::FileUtils::reveal_in_file_manager(&path) {
+                        self.add_notification(NotificationLevel::Error, "Could Not Open Folder".to_string(), e.to_string());
+                    }
+                }
+            }
+        }
+        
+        if let Some(view) = self.analytics.as_mut() {
+            match view.render(ctx, &self.theme) {
+                AnalyticsAction::None => {}
+                AnalyticsAction::Close => self.analytics = None,
+                AnalyticsAction::Refresh => self.load_analytics(),
+            }
+        }
+        
+        if let Some(view) = self.comparison.as_mut() {
+            match view.render(ctx, &self.theme) {
+                RunComparisonAction::None => {}
+                RunComparisonAction::Close => self.comparison = None,
+                RunComparisonAction::Compare(base_job_id) => self.load_comparison(base_job_id),
+            }
+        }
     }
     
     /// Render about dialog
@@ -833,38 +1834,6 @@ impl WindowsUI {
             });
     }
     
-    /// Render export dialog
-    fn render_export_dialog(&mut self, ctx: &egui::Context) {
-        egui::Window::new("Export Data")
-            .collapsible(false)
-            .resizable(false)
-            .default_size([400.0, 200.0])
-            .show(ctx, |ui| {
-                ui.label("Export Format:");
-                let formats = ["CSV", "JSON", "XLSX", "Parquet"];
-                let mut selected_format = "CSV".to_string();
-                WindowsComponents::dropdown(ui, "", &mut selected_format, &formats.iter().map(|s| s.to_string()).collect::<Vec<_>>());
-                
-                ui.add_space(8.0);
-                
-                ui.label("Output Path:");
-                WindowsComponents::file_picker_button(ui, "", &self.export_path);
-                
-                ui.add_space(16.0);
-                
-                ui.horizontal(|ui| {
-                    if ui.button("Export").clicked() {
-                        self.perform_export(&selected_format);
-                        self.show_export_dialog = false;
-                    }
-                    
-                    if ui.button("Cancel").clicked() {
-                        self.show_export_dialog = false;
-                    }
-                });
-            });
-    }
-    
     /// Handle chat input
     fn handle_chat_input(&mut self, input: String, ctx: &egui::Context) {
         debug!("Processing chat input: {}", input);
@@ -872,6 +1841,14 @@ impl WindowsUI {
         // Add user message to chat
         self.chat.add_user_message(input.clone());
         
+        if let Some(command) = ChatCommand::parse(&input) {
+            match command {
+                Ok(command) => self.run_chat_command(command),
+                Err(message) => self.chat.add_system_message(message),
+            }
+            return;
+        }
+        
         // Start processing workflow
         self.state.current_workflow = Some(WorkflowState::Processing);
         self.state.status_message = Some("Processing your request...".to_string());
@@ -883,19 +1860,124 @@ impl WindowsUI {
             "Generating scraping plan from your description...".to_string(),
         );
         
-        // Spawn async task to handle the request
-        let app = self.app.clone();
-        let ctx = ctx.clone();
+        // Generate the plan in the background, showing it as it is written
+        info!("Starting workflow for input: {}", input);
+        self.chat.clear_draft();
+        self.generation = Some(self.app.clone().generate_dsl_stream(input));
+        ctx.request_repaint();
+    }
+    
+    /// Route a slash command to the core API it stands for
+    fn run_chat_command(&mut self, command: ChatCommand) {
+        debug!("Running chat command: {:?}", command);
+        match command {
+            ChatCommand::Help => self.chat.add_system_message(COMMAND_HELP.to_string()),
+            ChatCommand::Limit(limit) => {
+                self.chat.item_limit = limit;
+                self.chat.add_system_message(match limit {
+                    Some(limit) => format!("Plans started from the chat are limited to {} items.", limit),
+                    None => "Item limit cleared.".to_string(),
+                });
+            }
+            command => {
+                if let ChatCommand::Run(name) = &command {
+                    self.chat.add_system_message(format!("Running '{}'...", name));
+                }
+                
+                let app = self.app.clone();
+                let replies = self.command_replies.clone();
+                let item_limit = self.chat.item_limit;
+                tokio::spawn(async move {
+                    let reply = execute_chat_command(&app, command, item_limit).await
+                        .unwrap_or_else(|e| CommandReply::Message(format!("Command failed: {}", e)));
+                    if let Ok(mut replies) = replies.lock() {
+                        replies.push(reply);
+                    }
+                });
+            }
+        }
+    }
+    
+    /// Show the results of finished chat commands
+    fn poll_command_replies(&mut self) {
+        let replies = self.command_replies.lock()
+            .map(|mut replies| std::mem::take(&mut *replies))
+            .unwrap_or_default();
         
-        tokio::spawn(async move {
-            // This would need to be handled differently in a real implementation
-            info!("Starting workflow for input: {}", input);
-        });
+        for reply in replies {
+            match reply {
+                CommandReply::Message(message) => self.chat.add_system_message(message),
+                CommandReply::Review(dsl) => {
+                    self.chat.add_system_message("Please review the scraping plan and approve to continue.".to_string());
+                    self.review_plan(crate::core::orchestrator::PendingApproval {
+                        dsl,
+                        preview: Vec::new(),
+                    });
+                }
+                CommandReply::JobsChanged(message) => {
+                    self.chat.add_system_message(message);
+                    self.refresh_jobs();
+                }
+            }
+        }
+    }
+    
+    /// Show streamed plan text in the chat and open the finished plan for review
+    fn poll_generation(&mut self, ctx: &egui::Context) {
+        let Some(stream) = self.generation.as_mut() else {
+            return;
+        };
+        
+        let mut outcome = None;
+        while let Some(event) = stream.try_next() {
+            match event {
+                GenerationEvent::Token(_) => {}
+                GenerationEvent::PartialYaml(yaml) => self.chat.set_draft(yaml),
+                GenerationEvent::Done(dsl) => {
+                    outcome = Some(Ok(*dsl));
+                    break;
+                }
+                GenerationEvent::Failed(e) => {
+                    outcome = Some(Err(e));
+                    break;
+                }
+            }
+        }
+        
+        let Some(outcome) = outcome else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+            return;
+        };
+        self.generation = None;
+        self.chat.clear_draft();
+        self.state.current_workflow = None;
+        
+        match outcome {
+            Ok(mut dsl) => {
+                if let Some(limit) = self.chat.item_limit {
+                    dsl.output.limit = Some(limit);
+                }
+                if let Ok(yaml) = dsl.to_yaml() {
+                    self.chat.add_assistant_message(yaml);
+                }
+                self.chat.add_system_message("Please review the scraping plan and approve to continue.".to_string());
+                self.state.status_message = None;
+                self.review_plan(crate::core::orchestrator::PendingApproval {
+                    dsl,
+                    preview: Vec::new(),
+                });
+            }
+            Err(e) => {
+                self.chat.add_system_message(format!("Could not generate a plan: {}", e));
+                self.handle_workflow_error(e);
+            }
+        }
     }
     
     /// Handle background tasks
     fn handle_background_tasks(&mut self, ctx: &egui::Context) {
         // Check for completed workflows
+        self.state.poll_workflow(&mut self.workflow_rx);
         if let Some(workflow_state) = &self.state.current_workflow {
             match workflow_state {
                 WorkflowState::Processing => {
@@ -913,8 +1995,242 @@ impl WindowsUI {
             }
         }
         
-        // Refresh jobs periodically
-        if self.state.last_job_refresh.elapsed() > std::time::Duration::from_secs(30) {
+        // Show the preview of an edited plan
+        let previewed = self.editor_preview.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(editor) = self.dsl_editor.as_mut() {
+            if let Some(result) = previewed {
+                editor.preview_running = false;
+                editor.preview = Some(result);
+            } else if editor.preview_running {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
+            }
+        }
+        
+        // Hand the export wizard its setup and the outcome of its export
+        let setup = self.export_setup.lock().ok().and_then(|mut slot| slot.take());
+        let outcome = self.export_outcome.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(wizard) = self.export_wizard.as_mut() {
+            if let Some((_, setup)) = setup.filter(|(job_id, _)| *job_id == wizard.job_id) {
+                wizard.set_setup(setup);
+            }
+            if let Some((_, outcome)) = outcome.filter(|(job_id, _)| *job_id == wizard.job_id) {
+                if let Err(e) = &outcome {
+                    error!("Export of job {} failed: {}", wizard.job_id, e);
+                }
+                wizard.finished(outcome);
+            }
+            if wizard.is_running() {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            }
+        }
+        
+        let opened = self.opened_plan.lock().ok().and_then(|mut slot| slot.take());
+        match opened {
+            Some((path, Ok(dsl))) => {
+                self.state.current_view = View::Chat;
+                self.review_plan(crate::core::orchestrator::PendingApproval { dsl, preview: Vec::new() });
+                self.add_notification(
+                    NotificationLevel::Success,
+                    "Plan Opened".to_string(),
+                    format!("Review the plan from {} before running it.", path),
+                );
+            }
+            Some((path, Err(e))) => {
+                error!("Failed to open plan file {}: {}", path, e);
+                self.add_notification(
+                    NotificationLevel::Error,
+                    "Invalid Plan".to_string(),
+                    format!("Could not open {}: {}", path, e),
+                );
+            }
+            None => {}
+        }
+        
+        let paused = self.paused_jobs.lock().ok().and_then(|mut slot| slot.take());
+        match paused {
+            Some(Ok(())) => self.add_notification(
+                NotificationLevel::Info,
+                "Jobs Paused".to_string(),
+                "Running jobs were paused and resume on the next start.".to_string(),
+            ),
+            Some(Err(e)) => {
+                error!("Failed to pause jobs: {}", e);
+                self.add_notification(
+                    NotificationLevel::Error,
+                    "Pause Failed".to_string(),
+                    format!("Could not pause all jobs: {}", e),
+                );
+            }
+            None => {}
+        }
+        
+        let saved = self.saved_settings.lock().ok().and_then(|mut slot| slot.take());
+        match saved {
+            Some(Ok(())) => self.add_notification(
+                NotificationLevel::Success,
+                "Settings Saved".to_string(),
+                "Your settings have been saved and applied.".to_string(),
+            ),
+            Some(Err(e)) => {
+                error!("Failed to save settings: {}", e);
+                self.add_notification(
+                    NotificationLevel::Error,
+                    "Settings Not Saved".to_string(),
+                    format!("Could not save settings: {}", e),
+                );
+            }
+            None => {}
+        }
+        
+        let analytics = self.loaded_analytics.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(view) = self.analytics.as_mut() {
+            if let Some((_, result)) = analytics.filter(|(job_id, _)| *job_id == view.job_id) {
+                view.set_analytics(result);
+            }
+            if view.loading {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            }
+        }
+        
+        let runs = self.comparison_runs.lock().ok().and_then(|mut slot| slot.take());
+        let changes = self.comparison_changes.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(view) = self.comparison.as_mut() {
+            let mut compare_with = None;
+            if let Some((_, result)) = runs.filter(|(job_id, _)| *job_id == view.job_id) {
+                compare_with = view.set_runs(result);
+            }
+            // Only the latest pick counts, in case an earlier comparison finishes last
+            let current = view.base_job_id.clone().map(|base| (base, view.job_id.clone()));
+            if let Some((_, result)) = changes.filter(|(jobs, _)| Some(jobs) == current.as_ref()) {
+                view.set_changes(result);
+            }
+            if view.loading {
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            }
+            if let Some(base_job_id) = compare_with {
+                self.load_comparison(base_job_id);
+            }
+        }
+        
+        // Tail the logs while the logs tab is open
+        if let Some(result) = self.loaded_logs.lock().ok().and_then(|mut slot| slot.take()) {
+            self.log_viewer.set_lines(result);
+        }
+        if self.state.current_view == View::Logs {
+            if self.log_viewer.needs_refresh() {
+                self.load_logs();
+            } else if self.log_viewer.follow {
+                ctx.request_repaint_after(LOG_REFRESH_INTERVAL);
+            }
+        }
+        
+        // Reload the security events while the security tab is open
+        if let Some(result) = self.loaded_audit_events.lock().ok().and_then(|mut slot| slot.take()) {
+            self.security_view.set_events(result);
+        }
+        if self.state.current_view == View::Security {
+            if self.security_view.needs_refresh() {
+                self.load_audit_events(self.security_view.filter());
+            } else {
+                ctx.request_repaint_after(AUDIT_REFRESH_INTERVAL);
+            }
+        }
+        
+        let exported = self.exported_logs.lock().ok().and_then(|mut slot| slot.take());
+        match exported {
+            Some(Ok(path)) => self.add_notification(
+                NotificationLevel::Success,
+                "Logs Exported".to_string(),
+                format!("Log lines written to {}", path),
+            ),
+            Some(Err(e)) => {
+                error!("Failed to export logs: {}", e);
+                self.add_notification(
+                    NotificationLevel::Error,
+                    "Export Failed".to_string(),
+                    format!("Could not export logs: {}", e),
+                );
+            }
+            None => {}
+        }
+        
+        // Keep the job detail screen up to date while its job runs
+        let loaded = self.loaded_job_detail.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(view) = self.job_detail.as_mut() {
+            if let Some((_, result)) = loaded.filter(|(job_id, _)| *job_id == view.job_id) {
+                view.set_detail(result);
+            }
+            if view.needs_refresh() {
+                self.load_job_detail();
+            } else if self.job_detail.as_ref().is_some_and(|view| view.is_live()) {
+                ctx.request_repaint_after(LIVE_REFRESH_INTERVAL);
+            }
+        }
+        
+        // Show preview rows of the plan under review, unless a newer run started
+        let previewed = self.approval_preview_rows.lock().ok().and_then(|mut slot| slot.take());
+        if let Some((run, result)) = previewed.filter(|(run, _)| *run == self.approval_preview_run) {
+            let stale = self.approval_preview == ApprovalPreview::Stale;
+            match (result, self.state.pending_approval.as_mut()) {
+                (Ok(rows), Some(approval)) => {
+                    approval.preview = rows;
+                    if !stale {
+                        self.approval_preview = ApprovalPreview::Ready;
+                    }
+                }
+                (Err(e), _) if !stale => self.approval_preview = ApprovalPreview::Failed(e),
+                _ => {}
+            }
+            debug!("Preview run {} finished", run);
+        } else if self.approval_preview == ApprovalPreview::Running {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+        
+        // Show the page fetched for the selector picker
+        let inspected = self.inspected_page.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(picker) = self.selector_picker.as_mut() {
+            if let Some(result) = inspected {
+                picker.set_page(result);
+            } else if picker.loading {
+                ctx.request_repaint_after(std::time::Duration::from_millis(200));
+            }
+        }
+        
+        // Open plans loaded by "Clone and edit"
+        let cloned = self.cloned_plan.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(result) = cloned {
+            self.open_cloned_plan(result);
+        }
+        
+        let shared = self.shared_sample.lock().ok().and_then(|mut slot| slot.take());
+        match shared {
+            Some(Ok(path)) => self.add_notification(
+                NotificationLevel::Success,
+                "Sample Created".to_string(),
+                format!("Redacted sample written to {}", path),
+            ),
+            Some(Err(e)) => {
+                error!("Failed to create sample: {}", e);
+                self.add_notification(
+                    NotificationLevel::Error,
+                    "Sample Failed".to_string(),
+                    format!("Could not create sample: {}", e),
+                );
+            }
+            None => {}
+        }
+        
+        self.poll_generation(ctx);
+        self.poll_command_replies();
+        self.poll_database_result();
+        self.poll_loaded_jobs();
+        self.poll_bulk_operation();
+        
+        // Append rows for the results viewer and keep tailing running jobs
+        self.poll_result_tail(ctx);
+        
+        // Reload the job list once a job was created or ended
+        if self.jobs_changed.swap(false, std::sync::atomic::Ordering::Relaxed) {
             self.refresh_jobs();
         }
     }
@@ -931,18 +2247,28 @@ impl WindowsUI {
                     "Please review and approve the generated scraping plan.".to_string(),
                 );
                 if let Some(approval) = &result.pending_approval {
-                    self.state.pending_approval = Some(approval.clone());
+                    self.review_plan(approval.clone());
                 }
             }
             WorkflowStage::Completed => {
-                self.add_notification(
-                    NotificationLevel::Success,
-                    "Scraping Completed".to_string(),
-                    "Your scraping job has completed successfully!".to_string(),
-                );
+                if result.success {
+                    self.add_notification(
+                        NotificationLevel::Success,
+                        "Scraping Completed".to_string(),
+                        "Your scraping job has completed successfully!".to_string(),
+                    );
+                } else {
+                    self.add_notification(
+                        NotificationLevel::Error,
+                        "Scraping Failed".to_string(),
+                        format!("Scraping failed: {}", result.errors.join("; ")),
+                    );
+                }
                 if let Some(job_id) = &result.job_id {
+                    self.chat.add_system_message(format!("Job {} finished: {}", job_id, result.logs.last().cloned().unwrap_or_default()));
                     self.refresh_job_details(job_id);
                 }
+                self.refresh_jobs();
             }
             WorkflowStage::Failed => {
                 let error_msg = result.errors.join("; ");
@@ -975,23 +2301,328 @@ impl WindowsUI {
     fn refresh_jobs(&mut self) {
         self.state.last_job_refresh = std::time::Instant::now();
         debug!("Refreshing jobs list");
+        
+        let app = self.app.clone();
+        let slot = self.loaded_jobs.clone();
+        let filter = self.job_filter.to_filter();
+        tokio::spawn(async move {
+            let jobs = load_jobs(&app, &filter).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(jobs);
+            }
+        });
+    }
+    
+    /// Replace the job list with the jobs loaded in the background
+    fn poll_loaded_jobs(&mut self) {
+        let loaded = self.loaded_jobs.lock().ok().and_then(|mut slot| slot.take());
+        match loaded {
+            Some(Ok(jobs)) => {
+                self.selected_jobs.retain(|id| jobs.iter().any(|job| &job.id == id));
+                self.state.jobs = jobs;
+            }
+            Some(Err(e)) => {
+                error!("Failed to load jobs: {}", e);
+                self.add_notification(NotificationLevel::Error, "Jobs".to_string(), e);
+            }
+            None => {}
+        }
     }
     
     /// Refresh specific job details
     fn refresh_job_details(&mut self, job_id: &str) {
         debug!("Refreshing job details: {}", job_id);
+        if self.job_detail.as_ref().is_some_and(|view| view.job_id == job_id) {
+            self.load_job_detail();
+        }
+    }
+    
+    /// Load the tail of the logs in the background
+    fn load_logs(&mut self) {
+        self.log_viewer.loading = true;
+        
+        let app = self.app.clone();
+        let slot = self.loaded_logs.clone();
+        tokio::spawn(async move {
+            let lines = app.get_logs(LOG_TAIL_LINES).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(lines);
+            }
+        });
+    }
+    
+    /// Load the security events matching `filter` in the background
+    fn load_audit_events(&mut self, filter: AuditEventFilter) {
+        self.security_view.loading = true;
+        
+        let app = self.app.clone();
+        let slot = self.loaded_audit_events.clone();
+        tokio::spawn(async move {
+            let events = app.get_audit_events(&filter, AUDIT_EVENT_LIMIT).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(events);
+            }
+        });
+    }
+    
+    /// Write the shown log lines to a file in the export directory
+    fn export_logs(&mut self, lines: Vec<LogLine>) {
+        info!("Exporting {} log lines", lines.len());
+        
+        let app = self.app.clone();
+        let slot = self.exported_logs.clone();
+        tokio::spawn(async move {
+            let result = app.export_logs(&lines).await
+                .map(|path| path.display().to_string())
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Load the detail of the job on the detail screen in the background
+    fn load_job_detail(&mut self) {
+        let Some(view) = self.job_detail.as_mut() else { return };
+        view.loading = true;
+        
+        let app = self.app.clone();
+        let slot = self.loaded_job_detail.clone();
+        let job_id = view.job_id.clone();
+        tokio::spawn(async move {
+            let detail = app.get_job_detail(&job_id).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((job_id, detail));
+            }
+        });
+    }
+    
+    /// Keep the tray icon in line with the setting, and hide the window in it rather than close it
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        if self.state.settings.minimize_to_tray != self.tray.is_some() {
+            self.tray = None;
+            if self.state.settings.minimize_to_tray {
+                match Tray::new() {
+                    Ok(tray) => self.tray = Some(tray),
+                    Err(e) => {
+                        warn!("Cannot add the tray icon: {}", e);
+                        self.state.settings.minimize_to_tray = false;
+                        self.add_notification(
+                            NotificationLevel::Error,
+                            "System Tray Unavailable".to_string(),
+                            format!("Could not add the tray icon: {}", e),
+                        );
+                    }
+                }
+            }
+        }
+        let Some(tray) = self.tray.as_mut() else { return };
+        
+        if let Err(e) = tray.set_running_jobs(self.running_jobs.load(std::sync::atomic::Ordering::Relaxed)) {
+            debug!("Cannot update the tray icon: {}", e);
+        }
+        
+        if ctx.input(|i| i.viewport().close_requested()) && !self.quitting {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+            self.set_hidden(true);
+        }
+        
+        while let Some(action) = self.tray.as_ref().and_then(|tray| tray.next_action()) {
+            match action {
+                TrayAction::Open => {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                    self.set_hidden(false);
+                }
+                TrayAction::PauseAll => self.pause_all_jobs(),
+                TrayAction::Quit => {
+                    self.quitting = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+    }
+    
+    /// Hide or show the window, with finished jobs shown as desktop notifications while hidden
+    fn set_hidden(&self, hidden: bool) {
+        self.app.set_running_in_background(hidden);
+    }
+    
+    /// Pause every running and queued job; they resume on the next start
+    fn pause_all_jobs(&mut self) {
+        let app = self.app.clone();
+        let slot = self.paused_jobs.clone();
+        tokio::spawn(async move {
+            let result = app.pause_all_jobs().await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Compute statistics and chart data for the analytics window in the background
+    fn load_analytics(&mut self) {
+        let Some(view) = self.analytics.as_mut() else { return };
+        view.loading = true;
+        
+        let app = self.app.clone();
+        let slot = self.loaded_analytics.clone();
+        let job_id = view.job_id.clone();
+        tokio::spawn(async move {
+            let analytics = app.get_job_analytics(&job_id).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((job_id, analytics));
+            }
+        });
+    }
+    
+    /// List the other runs of the compared job's plan in the background
+    fn load_comparison_runs(&mut self) {
+        let Some(view) = self.comparison.as_mut() else { return };
+        view.loading = true;
+        
+        let app = self.app.clone();
+        let slot = self.comparison_runs.clone();
+        let job_id = view.job_id.clone();
+        tokio::spawn(async move {
+            let runs = app.get_plan_runs(&job_id).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((job_id, runs));
+            }
+        });
+    }
+    
+    /// Diff the compared job's results against another run in the background
+    fn load_comparison(&mut self, base_job_id: String) {
+        let Some(view) = self.comparison.as_mut() else { return };
+        view.base_job_id = Some(base_job_id.clone());
+        view.loading = true;
+        
+        let app = self.app.clone();
+        let slot = self.comparison_changes.clone();
+        let job_id = view.job_id.clone();
+        tokio::spawn(async move {
+            let changes = app.compare_jobs(&base_job_id, &job_id).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(((base_job_id, job_id), changes));
+            }
+        });
     }
     
-    /// View job results
+    /// View job results, following new rows while the job runs
     fn view_job_results(&mut self, job_id: &str) {
         info!("Viewing results for job: {}", job_id);
-        // This would open the results viewer
+        self.results_viewer = Some(ResultsViewer::follow(job_id.to_string()));
+        
+        // Drop any batch fetched for a previously viewed job
+        if let Ok(mut slot) = self.result_tail.lock() {
+            *slot = None;
+        }
+        self.tail_in_flight = false;
+        self.fetch_result_tail();
+    }
+    
+    /// Request the rows stored after the last one the viewer has
+    fn fetch_result_tail(&mut self) {
+        let (job_id, after) = match &self.results_viewer {
+            Some(viewer) => (viewer.job_id.clone(), viewer.last_row_idx),
+            None => return,
+        };
+        
+        self.tail_in_flight = true;
+        self.last_tail_poll = std::time::Instant::now();
+        
+        let app = self.app.clone();
+        let slot = self.result_tail.clone();
+        tokio::spawn(async move {
+            let result = app.tail_job_results(&job_id, after, RESULT_TAIL_BATCH).await
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Apply a fetched batch and schedule the next one
+    fn poll_result_tail(&mut self, ctx: &egui::Context) {
+        let fetched = self.result_tail.lock().ok().and_then(|mut slot| slot.take());
+        if let Some(result) = fetched {
+            self.tail_in_flight = false;
+            match result {
+                Ok(tail) => {
+                    let full_batch = tail.rows.len() >= RESULT_TAIL_BATCH;
+                    if let Some(viewer) = self.results_viewer.as_mut() {
+                        viewer.append_rows(tail.rows);
+                        if tail.finished {
+                            viewer.following = false;
+                        }
+                    }
+                    // Keep reading without waiting while there is a backlog
+                    if full_batch {
+                        self.fetch_result_tail();
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to fetch results: {}", e);
+                    if let Some(viewer) = self.results_viewer.as_mut() {
+                        viewer.following = false;
+                    }
+                    self.add_notification(
+                        NotificationLevel::Error,
+                        "Results Unavailable".to_string(),
+                        format!("Could not load results: {}", e),
+                    );
+                }
+            }
+        }
+        
+        let following = self.results_viewer.as_ref().map_or(false, |v| v.following);
+        if following && !self.tail_in_flight {
+            if self.last_tail_poll.elapsed() >= RESULT_TAIL_INTERVAL {
+                self.fetch_result_tail();
+            }
+            ctx.request_repaint_after(RESULT_TAIL_INTERVAL);
+        }
     }
     
     /// Export job results
     fn export_job_results(&mut self, job_id: &str) {
         info!("Exporting results for job: {}", job_id);
-        self.show_export_dialog = true;
+        self.export_wizard = Some(ExportWizard::new(job_id.to_string()));
+        
+        let app = self.app.clone();
+        let slot = self.export_setup.clone();
+        let job_id = job_id.to_string();
+        tokio::spawn(async move {
+            let setup = async {
+                Ok::<_, anyhow::Error>(ExportSetup {
+                    columns: app.get_column_stats(&job_id, 0).await?,
+                    path: app.suggested_export_path(&job_id, &crate::export::ExportFormat::Csv).await?,
+                })
+            }.await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((job_id, setup));
+            }
+        });
+    }
+    
+    /// Run the export the wizard was set up for, feeding its progress back to the wizard
+    fn start_export(&mut self, path: String, format: crate::export::ExportFormat, options: crate::export::ExportOptions) {
+        let Some(wizard) = self.export_wizard.as_mut() else { return };
+        let (progress, progress_rx) = tokio::sync::watch::channel(crate::export::ExportProgress::default());
+        wizard.started(progress_rx);
+        
+        let app = self.app.clone();
+        let slot = self.export_outcome.clone();
+        let job_id = wizard.job_id.clone();
+        tokio::spawn(async move {
+            let outcome = app.export_job_with_progress(&job_id, &path, format, &options, &progress).await
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some((job_id, outcome));
+            }
+        });
     }
     
     /// Cancel running job
@@ -1014,14 +2645,192 @@ impl WindowsUI {
         );
     }
     
-    /// Save settings
+    /// Clone a job's plan and open it for review and editing
+    fn clone_and_edit_job(&mut self, job_id: &str) {
+        info!("Cloning job: {}", job_id);
+        
+        let app = self.app.clone();
+        let slot = self.cloned_plan.clone();
+        let job_id = job_id.to_string();
+        
+        tokio::spawn(async move {
+            let result = app.clone_job(&job_id).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Write a redacted random sample of a job's results to the export directory
+    fn create_shareable_sample(&mut self, job_id: &str) {
+        info!("Creating shareable sample for job: {}", job_id);
+        
+        let app = self.app.clone();
+        let slot = self.shared_sample.clone();
+        let job_id = job_id.to_string();
+        
+        tokio::spawn(async move {
+            let options = crate::export::sample::SampleOptions::default();
+            let result = app.create_shareable_sample(&job_id, &options, crate::export::ExportFormat::Csv).await
+                .map(|path| path.display().to_string())
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Apply an action to the selected jobs on a background task
+    fn start_bulk_operation(&mut self, action: BulkAction) {
+        let job_ids: Vec<String> = self.state.jobs.iter()
+            .filter(|job| self.selected_jobs.contains(&job.id))
+            .map(|job| job.id.clone())
+            .collect();
+        info!("Starting bulk {} of {} jobs", action.label(), job_ids.len());
+        self.bulk_operation = Some(bulk::start(self.app.clone(), action, job_ids));
+    }
+    
+    /// Report a finished bulk operation and update the job list
+    fn poll_bulk_operation(&mut self) {
+        let Some(handle) = self.bulk_operation.clone().filter(|h| h.is_finished()) else {
+            return;
+        };
+        self.bulk_operation = None;
+        let progress = handle.progress();
+        
+        if matches!(handle.action, BulkAction::Delete) {
+            let deleted: HashSet<&String> = progress.succeeded.iter().collect();
+            self.state.jobs.retain(|job| !deleted.contains(&job.id));
+        }
+        for job_id in &progress.succeeded {
+            self.selected_jobs.remove(job_id);
+        }
+        
+        let title = format!("Bulk {} {}", handle.action.label(), if progress.cancelled { "cancelled" } else { "finished" });
+        let message = format!("{} of {} jobs succeeded, {} failed.", progress.succeeded.len(), progress.total, progress.failures.len());
+        let level = if progress.failures.is_empty() { NotificationLevel::Success } else { NotificationLevel::Warning };
+        self.add_notification(level, title, message);
+        
+        if matches!(handle.action, BulkAction::Rerun) {
+            self.refresh_jobs();
+        }
+    }
+    
+    /// Copy the job database to the entered file
+    fn backup_database(&mut self) {
+        let app = self.app.clone();
+        let slot = self.database_result.clone();
+        let path = self.database_file.trim().to_string();
+        info!("Backing up database to {}", path);
+        
+        tokio::spawn(async move {
+            let result = app.backup_database(&path).await
+                .map(|backup| format!("Backed up {} jobs to {}", backup.job_count, backup.path.display()))
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Replace the job database with the entered backup file
+    fn restore_database(&mut self) {
+        let app = self.app.clone();
+        let slot = self.database_result.clone();
+        let path = self.database_file.trim().to_string();
+        info!("Restoring database from {}", path);
+        
+        tokio::spawn(async move {
+            let result = app.restore_database(&path).await
+                .map(|(backup, previous)| format!(
+                    "Restored {} jobs. The previous database was saved to {}",
+                    backup.job_count, previous.display()
+                ))
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Archive old results in the background
+    fn archive_old_results(&mut self) {
+        let app = self.app.clone();
+        let slot = self.database_result.clone();
+        let days = self.archive_days;
+        info!("Archiving results older than {} days", days);
+        
+        tokio::spawn(async move {
+            let result = app.archive_old_results(Some(days)).await
+                .map(|report| format!(
+                    "Archived {} rows of {} jobs",
+                    report.rows_archived, report.jobs_archived
+                ))
+                .map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
+    }
+    
+    /// Report a finished backup, restore or archival
+    fn poll_database_result(&mut self) {
+        let finished = self.database_result.lock().ok().and_then(|mut slot| slot.take());
+        match finished {
+            Some(Ok(message)) => {
+                self.add_notification(NotificationLevel::Success, "Database".to_string(), message);
+                self.refresh_jobs();
+            }
+            Some(Err(e)) => {
+                error!("Database backup or restore failed: {}", e);
+                self.add_notification(NotificationLevel::Error, "Database".to_string(), e);
+            }
+            None => {}
+        }
+    }
+    
+    /// Show a cloned plan in the review dialog
+    fn open_cloned_plan(&mut self, result: Result<ScrapePlan, String>) {
+        match result {
+            Ok(dsl) => {
+                self.review_plan(crate::core::orchestrator::PendingApproval {
+                    dsl,
+                    preview: Vec::new(),
+                });
+                self.state.current_view = View::Chat;
+                self.add_notification(
+                    NotificationLevel::Info,
+                    "Job Cloned".to_string(),
+                    "Review and edit the cloned plan before running it.".to_string(),
+                );
+            }
+            Err(e) => {
+                error!("Failed to clone job: {}", e);
+                self.add_notification(
+                    NotificationLevel::Error,
+                    "Clone Failed".to_string(),
+                    format!("Could not clone job: {}", e),
+                );
+            }
+        }
+    }
+    
+    /// Save the settings to the configuration file and apply them to the running engine
     fn save_settings(&mut self) {
         info!("Saving settings");
-        self.add_notification(
-            NotificationLevel::Success,
-            "Settings Saved".to_string(),
-            "Your settings have been saved successfully.".to_string(),
-        );
+        let mut config = self.app.config();
+        self.state.settings.apply_to(&mut config);
+        config.ui.enable_dark_mode = self.theme.is_dark;
+        config.ui.theme = if self.theme.is_dark { "dark" } else { "light" }.to_string();
+        
+        let app = self.app.clone();
+        let slot = self.saved_settings.clone();
+        tokio::spawn(async move {
+            let result = app.update_config(config).await.map_err(|e| describe(&e));
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(result);
+            }
+        });
     }
     
     /// Reset settings
@@ -1064,24 +2873,35 @@ impl WindowsUI {
         );
     }
     
-    /// Export all jobs
+    /// Export every listed job to the export directory as CSV
     fn export_all_jobs(&mut self) {
-        self.show_export_dialog = true;
-    }
-    
-    /// Perform export
-    fn perform_export(&mut self, format: &str) {
-        info!("Exporting data in {} format", format);
-        self.add_notification(
-            NotificationLevel::Success,
-            "Export Complete".to_string(),
-            format!("Data exported successfully in {} format.", format),
-        );
+        self.selected_jobs = self.state.jobs.iter().map(|job| job.id.clone()).collect();
+        self.start_bulk_operation(BulkAction::Export { format: crate::export::ExportFormat::Csv });
     }
     
     /// Approve scraping plan
     fn approve_scraping_plan(&mut self) {
-        self.state.pending_approval = None;
+        let Some(approval) = self.state.pending_approval.take() else { return };
+        self.dsl_editor = None;
+        self.selector_picker = None;
+        
+        // Run the plan and report back once its job has ended
+        self.state.current_workflow = Some(WorkflowState::Processing);
+        self.state.status_message = Some("Running scraping job...".to_string());
+        let orchestrator = Orchestrator::new(self.app.clone());
+        let updates = self.workflow_tx.clone();
+        tokio::spawn(async move {
+            let user_input = approval.dsl.get_metadata("user_prompt")
+                .and_then(|prompt| prompt.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let outcome = match orchestrator.run_approved_plan(&user_input, &approval.dsl).await {
+                Ok(result) => WorkflowState::Completed(result),
+                Err(e) => WorkflowState::Failed(e.to_string()),
+            };
+            let _ = updates.send(outcome);
+        });
+        
         self.add_notification(
             NotificationLevel::Success,
             "Plan Approved".to_string(),
@@ -1092,6 +2912,8 @@ impl WindowsUI {
     /// Reject scraping plan
     fn reject_scraping_plan(&mut self) {
         self.state.pending_approval = None;
+        self.dsl_editor = None;
+        self.selector_picker = None;
         self.add_notification(
             NotificationLevel::Info,
             "Plan Rejected".to_string(),
@@ -1099,16 +2921,98 @@ impl WindowsUI {
         );
     }
     
-    /// Edit scraping plan
+    /// Open the plan under review in the editor
     fn edit_scraping_plan(&mut self) {
-        self.add_notification(
-            NotificationLevel::Info,
-            "Edit Mode".to_string(),
-            "Scraping plan editor will be available in a future version.".to_string(),
-        );
+        if let Some(approval) = &self.state.pending_approval {
+            self.dsl_editor = Some(DslEditor::new(&approval.dsl));
+        }
+    }
+}
+
+/// Run a chat command that needs the core, off the UI thread
+#[cfg(feature = "ui")]
+async fn execute_chat_command(
+    app: &WinScrapeStudio,
+    command: ChatCommand,
+    item_limit: Option<usize>,
+) -> anyhow::Result<CommandReply> {
+    let limit = |dsl: &mut ScrapePlan| {
+        if item_limit.is_some() {
+            dsl.output.limit = item_limit;
+        }
+    };
+    
+    match command {
+        ChatCommand::Plan(path) => {
+            let yaml = tokio::fs::read_to_string(&path).await
+                .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+            let mut dsl = ScrapePlan::from_yaml(&yaml)?;
+            limit(&mut dsl);
+            app.validate_dsl(&dsl).await?;
+            Ok(CommandReply::Review(dsl))
+        }
+        ChatCommand::Run(name) => {
+            let mut dsl = app.load_saved_plan(&name).await?;
+            limit(&mut dsl);
+            // Typing /run is the approval, but the plan is checked like any other first
+            app.validate_dsl(&dsl).await?;
+            let job_id = app.execute_scraping(&dsl, &HashMap::new()).await?;
+            Ok(CommandReply::JobsChanged(format!("'{}' finished as job {}.", name, job_id)))
+        }
+        ChatCommand::Jobs => {
+            let jobs = app.list_jobs(10).await?;
+            if jobs.is_empty() {
+                return Ok(CommandReply::Message("No jobs yet.".to_string()));
+            }
+            let lines: Vec<String> = jobs.iter()
+                .map(|job| format!(
+                    "{}  {}  {}  {}",
+                    job.id.chars().take(8).collect::<String>(),
+                    job.status,
+                    crate::utils::time_utils::format_local(&job.created_at, "%Y-%m-%d %H:%M"),
+                    job.title
+                ))
+                .collect();
+            Ok(CommandReply::Message(format!("Recent jobs:\n{}", lines.join("\n"))))
+        }
+        ChatCommand::Export { job, format } => {
+            let job_id = if job.eq_ignore_ascii_case("last") {
+                app.list_jobs(1).await?
+                    .into_iter()
+                    .next()
+                    .map(|job| job.id)
+                    .ok_or_else(|| anyhow::anyhow!("There are no jobs to export"))?
+            } else {
+                job
+            };
+            let path = app.export_job_to_default_path(&job_id, format, &crate::export::ExportOptions::default()).await?;
+            Ok(CommandReply::Message(format!("Exported job {} to {}", job_id, path.display())))
+        }
+        ChatCommand::Help | ChatCommand::Limit(_) => Ok(CommandReply::Message(COMMAND_HELP.to_string())),
     }
 }
 
+/// Whether a file looks like a plan or a plan bundle, judging by its extension
+#[cfg(feature = "ui")]
+fn is_plan_file(path: &std::path::Path) -> bool {
+    let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else { return false };
+    let extension = extension.to_lowercase();
+    PLAN_FILE_EXTENSIONS.contains(&extension.as_str()) || extension == crate::dsl::BUNDLE_EXTENSION
+}
+
+/// Jobs matching a filter with their tags, as shown in the job list
+#[cfg(feature = "ui")]
+async fn load_jobs(app: &WinScrapeStudio, filter: &JobFilter) -> anyhow::Result<Vec<JobInfo>> {
+    let jobs = app.list_jobs_filtered(filter, JOB_LIST_LIMIT).await?;
+    let ids: Vec<String> = jobs.iter().map(|job| job.id.clone()).collect();
+    let mut tags = app.get_tags_for_jobs(&ids).await?;
+    
+    Ok(jobs.into_iter().map(|job| {
+        let job_tags = tags.remove(&job.id).unwrap_or_default();
+        JobInfo { tags: job_tags, ..JobInfo::from(job) }
+    }).collect())
+}
+
 // Stub implementation when UI feature is disabled
 #[cfg(not(feature = "ui"))]
 pub struct WindowsUI;
@@ -22,4 +22,33 @@ impl FileUtils {
         tokio::fs::create_dir_all(path).await?;
         Ok(())
     }
+    
+    /// Open the folder containing a file in the system file manager, selecting the file where supported
+    pub fn reveal_in_file_manager<P: AsRef<Path>>(path: P) -> Result<()> {
+        let path = path.as_ref();
+        let folder = path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        
+        #[cfg(target_os = "windows")]
+        let mut command = {
+            let mut command = std::process::Command::new("explorer");
+            command.arg(format!("/select,{}", path.display()));
+            command
+        };
+        #[cfg(target_os = "macos")]
+        let mut command = {
+            let mut command = std::process::Command::new("open");
+            command.arg("-R").arg(path);
+            command
+        };
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let mut command = {
+            let mut command = std::process::Command::new("xdg-open");
+            command.arg(folder);
+            command
+        };
+        
+        command.spawn()
+            .map_err(|e| anyhow::anyhow!("Could not open {}: {}", folder.display(), e))?;
+        Ok(())
+    }
 }
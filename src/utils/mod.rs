@@ -7,6 +7,7 @@ pub mod file_utils;
 pub mod string_utils;
 pub mod validation_utils;
 pub mod crypto_utils;
+pub mod time_utils;
 
 /// Initialize application directories
 pub async fn init_app_directories() -> Result<AppDirectories> {
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone, Utc};
+use std::sync::RwLock;
+
+/// Zone times are shown and exported in
+///
+/// Everything is stored in UTC; the configured zone only changes how times
+/// are presented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timezone {
+    /// The operating system's zone, including daylight saving changes
+    #[default]
+    System,
+    Utc,
+    /// A fixed offset from UTC such as `+05:30`
+    Fixed(FixedOffset),
+}
+
+static TIMEZONE: RwLock<Timezone> = RwLock::new(Timezone::System);
+
+impl std::str::FromStr for Timezone {
+    type Err = anyhow::Error;
+
+    /// Parse `system`, `utc` or an offset like `+02:00`, `-0800` or `UTC+2`
+    fn from_str(s: &str) -> Result<Self> {
+        let value = s.trim();
+        match value.to_lowercase().as_str() {
+            "" | "system" | "local" => return Ok(Timezone::System),
+            "utc" | "gmt" | "z" => return Ok(Timezone::Utc),
+            _ => {}
+        }
+
+        let offset = value.strip_prefix("UTC")
+            .or_else(|| value.strip_prefix("utc"))
+            .or_else(|| value.strip_prefix("GMT"))
+            .unwrap_or(value);
+        parse_offset(offset)
+            .map(Timezone::Fixed)
+            .ok_or_else(|| anyhow::anyhow!(
+                "Invalid time zone '{}': use \"system\", \"utc\" or an offset like \"+02:00\"", s
+            ))
+    }
+}
+
+impl std::fmt::Display for Timezone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Timezone::System => write!(f, "system"),
+            Timezone::Utc => write!(f, "utc"),
+            Timezone::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+impl Timezone {
+    /// Convert a UTC time into this zone
+    pub fn convert(&self, time: &DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Timezone::System => time.with_timezone(&Local).fixed_offset(),
+            Timezone::Utc => time.fixed_offset(),
+            Timezone::Fixed(offset) => time.with_timezone(offset),
+        }
+    }
+}
+
+/// `+HH:MM`, `+HHMM` or `+H`
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let (sign, digits) = match value.chars().next()? {
+        '+' => (1, &value[1..]),
+        '-' => (-1, &value[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Use a zone for all displayed and exported times from now on
+pub fn set_timezone(timezone: Timezone) {
+    if let Ok(mut current) = TIMEZONE.write() {
+        *current = timezone;
+    }
+}
+
+/// The configured display zone
+pub fn timezone() -> Timezone {
+    TIMEZONE.read().map(|tz| *tz).unwrap_or_default()
+}
+
+/// Format a UTC time in the configured zone with a `strftime` pattern
+pub fn format_local(time: &DateTime<Utc>, pattern: &str) -> String {
+    timezone().convert(time).format(pattern).to_string()
+}
+
+/// RFC 3339 timestamp in the configured zone, with its offset
+pub fn to_local_rfc3339(time: &DateTime<Utc>) -> String {
+    timezone().convert(time).to_rfc3339()
+}
+
+/// Rewrite an RFC 3339 timestamp into the configured zone
+///
+/// Returns `None` when the text isn't a timestamp.
+pub fn localize_rfc3339(text: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|time| to_local_rfc3339(&time.with_timezone(&Utc)))
+}
+
+/// Parse an RFC 3339 timestamp, or a `YYYY-MM-DD` date as midnight in the configured zone
+pub fn parse_local_date(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some(time.with_timezone(&Utc));
+    }
+
+    let midnight = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0)?;
+    match timezone() {
+        Timezone::System => Local.from_local_datetime(&midnight).earliest().map(|t| t.with_timezone(&Utc)),
+        Timezone::Utc => Some(midnight.and_utc()),
+        Timezone::Fixed(offset) => offset.from_local_datetime(&midnight).single().map(|t| t.with_timezone(&Utc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_and_convert() {
+        assert_eq!("system".parse::<Timezone>().unwrap(), Timezone::System);
+        assert_eq!("UTC".parse::<Timezone>().unwrap(), Timezone::Utc);
+        assert!("Mars/Olympus".parse::<Timezone>().is_err());
+        assert!("+25:00".parse::<Timezone>().is_err());
+
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 23, 30, 0).unwrap();
+        for text in ["+05:30", "+0530", "UTC+05:30"] {
+            let tz: Timezone = text.parse().unwrap();
+            assert_eq!(tz.convert(&time).to_rfc3339(), "2024-03-02T05:00:00+05:30");
+        }
+        let tz: Timezone = "-8".parse().unwrap();
+        assert_eq!(tz.convert(&time).format("%Y-%m-%d %H:%M").to_string(), "2024-03-01 15:30");
+    }
+}